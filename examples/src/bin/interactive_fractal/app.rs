@@ -70,6 +70,7 @@ impl FractalApp {
         ));
         let descriptor_set_allocator = Arc::new(StandardDescriptorSetAllocator::new(
             gfx_queue.device().clone(),
+            Default::default(),
         ));
 
         FractalApp {
@@ -118,7 +119,7 @@ Usage:
     }
 
     /// Runs our compute pipeline and return a future of when the compute is finished.
-    pub fn compute(&self, image_target: Arc<ImageView>) -> Box<dyn GpuFuture> {
+    pub fn compute(&mut self, image_target: Arc<ImageView>) -> Box<dyn GpuFuture> {
         self.fractal_pipeline.compute(
             image_target,
             self.c,
@@ -134,6 +135,14 @@ Usage:
         !self.input_state.should_quit
     }
 
+    /// Returns the GPU time in milliseconds spent computing the fractal in the last finished frame.
+    pub fn gpu_compute_ms(&self) -> Option<f64> {
+        self.fractal_pipeline
+            .gpu_timings()
+            .first()
+            .map(|&(_, ms)| ms)
+    }
+
     /// Returns the average FPS.
     pub fn avg_fps(&self) -> f32 {
         self.avg_fps
@@ -29,6 +29,7 @@ use vulkano::{
     },
     sync::GpuFuture,
 };
+use vulkano_util::{gpu_scope, profiler::GpuProfiler};
 
 pub struct FractalComputePipeline {
     queue: Arc<Queue>,
@@ -39,6 +40,7 @@ pub struct FractalComputePipeline {
     palette: Subbuffer<[[f32; 4]]>,
     palette_size: i32,
     end_color: [f32; 4],
+    profiler: GpuProfiler,
 }
 
 impl FractalComputePipeline {
@@ -96,6 +98,9 @@ impl FractalComputePipeline {
             .unwrap()
         };
 
+        // Measures the GPU time of the fractal dispatch.
+        let profiler = GpuProfiler::new(queue.device().clone(), queue.queue_family_index(), 1);
+
         FractalComputePipeline {
             queue,
             pipeline,
@@ -105,9 +110,15 @@ impl FractalComputePipeline {
             palette,
             palette_size,
             end_color,
+            profiler,
         }
     }
 
+    /// Returns the GPU time in milliseconds of each profiled scope of the last finished frame.
+    pub fn gpu_timings(&self) -> &[(String, f64)] {
+        self.profiler.timings()
+    }
+
     /// Randomizes our color palette.
     pub fn randomize_palette(&mut self) {
         let mut colors = vec![];
@@ -135,7 +146,7 @@ impl FractalComputePipeline {
     }
 
     pub fn compute(
-        &self,
+        &mut self,
         image_view: Arc<ImageView>,
         c: Vector2<f32>,
         scale: Vector2<f32>,
@@ -173,15 +184,20 @@ impl FractalComputePipeline {
             max_iters: max_iters as i32,
             is_julia: is_julia as u32,
         };
-        builder
-            .bind_pipeline_compute(self.pipeline.clone())
-            .unwrap()
-            .bind_descriptor_sets(PipelineBindPoint::Compute, pipeline_layout.clone(), 0, set)
-            .unwrap()
-            .push_constants(pipeline_layout.clone(), 0, push_constants)
-            .unwrap()
-            .dispatch([image_extent[0] / 8, image_extent[1] / 8, 1])
-            .unwrap();
+        self.profiler.begin_frame(&mut builder).unwrap();
+        let mut profiled_builder = self.profiler.record(&mut builder);
+        gpu_scope!(profiled_builder, "fractal", {
+            profiled_builder
+                .bind_pipeline_compute(self.pipeline.clone())
+                .unwrap()
+                .bind_descriptor_sets(PipelineBindPoint::Compute, pipeline_layout.clone(), 0, set)
+                .unwrap()
+                .push_constants(pipeline_layout.clone(), 0, push_constants)
+                .unwrap()
+                .dispatch([image_extent[0] / 8, image_extent[1] / 8, 1])
+                .unwrap();
+        })
+        .unwrap();
         let command_buffer = builder.build().unwrap();
         let finished = command_buffer.execute(self.queue.clone()).unwrap();
         finished.then_signal_fence_and_flush().unwrap().boxed()
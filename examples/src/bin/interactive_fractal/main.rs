@@ -57,11 +57,13 @@ fn main() {
     let primary_window_renderer = windows.get_primary_renderer_mut().unwrap();
 
     // Make sure the image usage is correct (based on your pipeline).
-    primary_window_renderer.add_additional_image_view(
-        render_target_id,
-        DEFAULT_IMAGE_FORMAT,
-        ImageUsage::SAMPLED | ImageUsage::STORAGE | ImageUsage::TRANSFER_DST,
-    );
+    primary_window_renderer
+        .add_additional_image_view(
+            render_target_id,
+            DEFAULT_IMAGE_FORMAT,
+            ImageUsage::SAMPLED | ImageUsage::STORAGE | ImageUsage::TRANSFER_DST,
+        )
+        .unwrap();
 
     // Create app to hold the logic of our fractal explorer.
     let gfx_queue = context.graphics_queue();
@@ -125,10 +127,11 @@ pub fn process_event(
             app.reset_input_state();
             app.update_time();
             renderer.window().set_title(&format!(
-                "{} fps: {:.2} dt: {:.2}, Max Iterations: {}",
+                "{} fps: {:.2} dt: {:.2}, GPU compute: {:.3} ms, Max Iterations: {}",
                 if app.is_julia { "Julia" } else { "Mandelbrot" },
                 app.avg_fps(),
                 app.dt(),
+                app.gpu_compute_ms().unwrap_or(0.0),
                 app.max_iters
             ));
         }
@@ -165,5 +168,7 @@ fn compute_then_render(
 
     // Finish the frame (which presents the view), inputting the last future. Wait for the future
     // so resources are not in use when we render.
-    renderer.present(after_renderpass_future, true);
+    if let Err(e) = renderer.present(after_renderpass_future, true) {
+        println!("{e}");
+    }
 }
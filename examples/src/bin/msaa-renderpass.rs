@@ -88,7 +88,7 @@ use vulkano::{
             GraphicsPipelineCreateInfo,
         },
         layout::PipelineDescriptorSetLayoutCreateInfo,
-        GraphicsPipeline, PipelineLayout, PipelineShaderStageCreateInfo,
+        GraphicsPipeline, PipelineLayout, PipelineShaderStageCreateInfo, StateMode,
     },
     render_pass::{Framebuffer, FramebufferCreateInfo, Subpass},
     sync::GpuFuture,
@@ -348,7 +348,7 @@ fn main() {
                 viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
                 rasterization_state: Some(RasterizationState::default()),
                 multisample_state: Some(MultisampleState {
-                    rasterization_samples: subpass.num_samples().unwrap(),
+                    rasterization_samples: StateMode::Fixed(subpass.num_samples().unwrap()),
                     ..Default::default()
                 }),
                 color_blend_state: Some(ColorBlendState::new(subpass.num_color_attachments())),
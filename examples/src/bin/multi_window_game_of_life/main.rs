@@ -219,5 +219,7 @@ fn compute_then_render(
         .render(after_compute, color_image, target_image);
 
     // Finish the frame. Wait for the future so resources are not in use when we render.
-    window_renderer.present(after_render, true);
+    if let Err(e) = window_renderer.present(after_render, true) {
+        println!("{e}");
+    }
 }
@@ -22,7 +22,6 @@ use vulkano::{
         physical::PhysicalDeviceType, Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo,
         QueueFlags,
     },
-    format::Format,
     image::{view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage},
     instance::{Instance, InstanceCreateFlags, InstanceCreateInfo},
     memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
@@ -287,7 +286,7 @@ fn main() {
                 store_op: Store,
             },
             depth_stencil: {
-                format: Format::D16_UNORM,
+                format: device.physical_device().best_depth_stencil_format(false).unwrap(),
                 samples: 1,
                 load_op: Clear,
                 store_op: DontCare,
@@ -575,7 +574,7 @@ fn window_size_dependent_setup(
             memory_allocator,
             ImageCreateInfo {
                 image_type: ImageType::Dim2d,
-                format: Format::D16_UNORM,
+                format: render_pass.attachments()[1].format,
                 extent: images[0].extent(),
                 usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT | ImageUsage::TRANSIENT_ATTACHMENT,
                 ..Default::default()
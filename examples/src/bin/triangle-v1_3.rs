@@ -470,11 +470,7 @@ fn main() {
 
     // Dynamic viewports allow us to recreate just the viewport when the window is resized.
     // Otherwise we would have to recreate the whole pipeline.
-    let mut viewport = Viewport {
-        offset: [0.0, 0.0],
-        extent: [0.0, 0.0],
-        depth_range: 0.0..=1.0,
-    };
+    let mut viewport = Viewport::for_extent([0, 0]);
 
     // When creating the swapchain, we only created plain images. To use them as an attachment for
     // rendering, we must wrap then in an image view.
@@ -694,7 +690,7 @@ fn window_size_dependent_setup(
     viewport: &mut Viewport,
 ) -> Vec<Arc<ImageView>> {
     let extent = images[0].extent();
-    viewport.extent = [extent[0] as f32, extent[1] as f32];
+    *viewport = Viewport::for_extent([extent[0], extent[1]]);
 
     images
         .iter()
@@ -465,11 +465,7 @@ fn main() {
 
     // Dynamic viewports allow us to recreate just the viewport when the window is resized.
     // Otherwise we would have to recreate the whole pipeline.
-    let mut viewport = Viewport {
-        offset: [0.0, 0.0],
-        extent: [0.0, 0.0],
-        depth_range: 0.0..=1.0,
-    };
+    let mut viewport = Viewport::for_extent([0, 0]);
 
     // The render pass we created above only describes the layout of our framebuffers. Before we
     // can draw we also need to create the actual framebuffers.
@@ -692,7 +688,7 @@ fn window_size_dependent_setup(
     viewport: &mut Viewport,
 ) -> Vec<Arc<Framebuffer>> {
     let extent = images[0].extent();
-    viewport.extent = [extent[0] as f32, extent[1] as f32];
+    *viewport = Viewport::for_extent([extent[0], extent[1]]);
 
     images
         .iter()
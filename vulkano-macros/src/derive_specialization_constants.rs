@@ -0,0 +1,80 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::bail;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::Parse, Data, DataStruct, Fields, LitInt, Result};
+
+pub fn derive_specialization_constants(ast: syn::DeriveInput) -> Result<TokenStream> {
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = ast.generics.split_for_impl();
+
+    let fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => bail!("expected a struct with named fields"),
+    };
+
+    let crate_ident = crate::crate_ident();
+    let mut constant_ids = Vec::with_capacity(fields.len());
+    let mut values = Vec::with_capacity(fields.len());
+
+    for field in fields.iter() {
+        let field_name = field.ident.as_ref().unwrap();
+        let mut constant_id = None;
+
+        for attr in &field.attrs {
+            if attr.path.is_ident("constant_id") {
+                if constant_id.is_some() {
+                    bail!(attr, "duplicate `#[constant_id(...)]`-attribute");
+                }
+
+                let lit = attr.parse_args_with(LitInt::parse)?;
+                constant_id = Some((lit.base10_parse::<u32>()?, lit));
+            }
+        }
+
+        let (constant_id, lit) = match constant_id {
+            Some(x) => x,
+            None => bail!(
+                field_name,
+                "expected `#[constant_id(...)]`-attribute with the `constant_id` of the \
+                specialization constant",
+            ),
+        };
+
+        if constant_ids.contains(&constant_id) {
+            bail!(lit, "this `constant_id` is already used by another field");
+        }
+
+        constant_ids.push(constant_id);
+        values.push(quote! {
+            (
+                #constant_id,
+                ::#crate_ident::shader::SpecializationConstant::from(self.#field_name),
+            )
+        });
+    }
+
+    Ok(quote! {
+        impl #impl_generics ::#crate_ident::shader::SpecializationConstants
+            for #struct_name #type_generics #where_clause
+        {
+            #[inline]
+            fn specialization_constants(
+                &self,
+            ) -> ::std::vec::Vec<(u32, ::#crate_ident::shader::SpecializationConstant)> {
+                ::std::vec![#(#values),*]
+            }
+        }
+    })
+}
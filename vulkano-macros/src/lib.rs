@@ -16,6 +16,7 @@ use proc_macro_crate::{crate_name, FoundCrate};
 use syn::{parse_macro_input, DeriveInput, Error};
 
 mod derive_buffer_contents;
+mod derive_specialization_constants;
 mod derive_vertex;
 
 /// Derives the [`Vertex`] trait.
@@ -42,6 +43,21 @@ pub fn derive_buffer_contents(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Derives the [`SpecializationConstants`] trait.
+///
+/// Each field must have a `#[constant_id(...)]` attribute with the `constant_id` of the
+/// specialization constant that it provides the value for.
+///
+/// [`SpecializationConstants`]: https://docs.rs/vulkano/latest/vulkano/shader/trait.SpecializationConstants.html
+#[proc_macro_derive(SpecializationConstants, attributes(constant_id))]
+pub fn derive_specialization_constants(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    derive_specialization_constants::derive_specialization_constants(ast)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
 fn crate_ident() -> syn::Ident {
     let found_crate = crate_name("vulkano").unwrap();
     let name = match &found_crate {
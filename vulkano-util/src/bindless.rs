@@ -0,0 +1,388 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+use vulkano::{
+    descriptor_set::{
+        allocator::StandardDescriptorSetAllocator,
+        layout::{
+            DescriptorBindingFlags, DescriptorSetLayout, DescriptorSetLayoutBinding,
+            DescriptorSetLayoutCreateFlags, DescriptorSetLayoutCreateInfo, DescriptorType,
+        },
+        PersistentDescriptorSet, WriteDescriptorSet,
+    },
+    device::DeviceOwned,
+    image::{sampler::Sampler, view::ImageView},
+    shader::ShaderStages,
+    Validated, ValidationError, VulkanError,
+};
+
+/// A bindless texture array.
+///
+/// The manager owns a single descriptor set, with one binding (binding 0) that is a large
+/// variable-count array of combined image samplers. Textures are registered with
+/// [`BindlessTextures::register`], which writes them into a free slot of the array and returns a
+/// [`BindlessTexture`] handle holding the slot's index. Shaders then select a texture by indexing
+/// into the array, for example with an index passed in a push constant or a buffer, so the
+/// descriptor set only needs to be bound once per frame.
+///
+/// The binding uses the `UPDATE_AFTER_BIND`, `UPDATE_UNUSED_WHILE_PENDING`, `PARTIALLY_BOUND` and
+/// `VARIABLE_DESCRIPTOR_COUNT` binding flags, so textures can be registered while the descriptor
+/// set is bound or in use by the GPU, and unused slots may be left empty. The following device
+/// features must be enabled:
+///
+/// - [`descriptor_binding_sampled_image_update_after_bind`]
+/// - [`descriptor_binding_update_unused_while_pending`]
+/// - [`descriptor_binding_partially_bound`]
+/// - [`descriptor_binding_variable_descriptor_count`]
+/// - [`runtime_descriptor_array`], to declare the array without a size in the shader.
+///
+/// The layout reflected from a shader does not have these flags, so pipelines that use the
+/// texture array must be created with [`BindlessTextures::layout`] in place of the reflected
+/// layout of the set.
+///
+/// [`BindlessTextures::register`] is unsafe because command buffers only see the descriptor set,
+/// not the textures written into it. Since the set may be updated after it is bound, vulkano can't
+/// check which textures a command buffer uses, so it can't transition their layouts, synchronize
+/// access to them, or keep a slot from being reused while the GPU may still read it. The caller
+/// is responsible for all three.
+///
+/// [`descriptor_binding_sampled_image_update_after_bind`]: vulkano::device::Features::descriptor_binding_sampled_image_update_after_bind
+/// [`descriptor_binding_update_unused_while_pending`]: vulkano::device::Features::descriptor_binding_update_unused_while_pending
+/// [`descriptor_binding_partially_bound`]: vulkano::device::Features::descriptor_binding_partially_bound
+/// [`descriptor_binding_variable_descriptor_count`]: vulkano::device::Features::descriptor_binding_variable_descriptor_count
+/// [`runtime_descriptor_array`]: vulkano::device::Features::runtime_descriptor_array
+pub struct BindlessTextures {
+    layout: Arc<DescriptorSetLayout>,
+    descriptor_set: Arc<PersistentDescriptorSet>,
+    sampler: Arc<Sampler>,
+    // The image views written to each slot, kept alive until the slot is overwritten.
+    views: Vec<Arc<ImageView>>,
+    free_indices: Arc<Mutex<Vec<u32>>>,
+    capacity: u32,
+}
+
+impl BindlessTextures {
+    /// Creates a new `BindlessTextures` with room for `capacity` textures, which are all sampled
+    /// with `sampler`.
+    ///
+    /// The texture array is accessible from all graphics shader stages and the compute stage.
+    pub fn new(
+        descriptor_set_allocator: &StandardDescriptorSetAllocator,
+        sampler: Arc<Sampler>,
+        capacity: u32,
+    ) -> Result<Self, Validated<VulkanError>> {
+        let layout = DescriptorSetLayout::new(
+            sampler.device().clone(),
+            DescriptorSetLayoutCreateInfo {
+                flags: DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL,
+                bindings: BTreeMap::from([(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        binding_flags: DescriptorBindingFlags::UPDATE_AFTER_BIND
+                            | DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING
+                            | DescriptorBindingFlags::PARTIALLY_BOUND
+                            | DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT,
+                        descriptor_count: capacity,
+                        stages: ShaderStages::all_graphics() | ShaderStages::COMPUTE,
+                        ..DescriptorSetLayoutBinding::descriptor_type(
+                            DescriptorType::CombinedImageSampler,
+                        )
+                    },
+                )]),
+                ..Default::default()
+            },
+        )?;
+
+        let descriptor_set = PersistentDescriptorSet::new_variable(
+            descriptor_set_allocator,
+            layout.clone(),
+            capacity,
+            [],
+            [],
+        )?;
+
+        Ok(BindlessTextures {
+            layout,
+            descriptor_set,
+            sampler,
+            views: Vec::new(),
+            free_indices: Arc::new(Mutex::new(Vec::new())),
+            capacity,
+        })
+    }
+
+    /// Returns the layout of the descriptor set, to use when creating pipeline layouts.
+    #[inline]
+    pub fn layout(&self) -> &Arc<DescriptorSetLayout> {
+        &self.layout
+    }
+
+    /// Returns the descriptor set to bind in command buffers.
+    #[inline]
+    pub fn descriptor_set(&self) -> &Arc<PersistentDescriptorSet> {
+        &self.descriptor_set
+    }
+
+    /// Returns the maximum number of textures that can be registered at the same time.
+    #[inline]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Writes `image_view` into a free slot of the texture array, and returns a handle holding
+    /// the index of the slot. The slot is freed when the handle is dropped.
+    ///
+    /// Returns an error if all slots are in use.
+    ///
+    /// # Safety
+    ///
+    /// - The image must be in the [`ShaderReadOnlyOptimal`] layout, or the `General` layout if
+    ///   its usage requires it, whenever a shader accesses it. Command buffers don't know about
+    ///   the textures in the array, so they don't transition or synchronize them.
+    /// - The handle must not be dropped while a command buffer that is pending execution may
+    ///   still access its index, since the slot may then be given to another texture.
+    ///
+    /// [`ShaderReadOnlyOptimal`]: vulkano::image::ImageLayout::ShaderReadOnlyOptimal
+    pub unsafe fn register(
+        &mut self,
+        image_view: Arc<ImageView>,
+    ) -> Result<BindlessTexture, Box<ValidationError>> {
+        let free_index = self.free_indices.lock().unwrap().pop();
+        let index = match free_index {
+            Some(index) => index,
+            None if (self.views.len() as u32) < self.capacity => self.views.len() as u32,
+            None => {
+                return Err(Box::new(ValidationError {
+                    problem: format!("all {} texture slots are in use", self.capacity).into(),
+                    ..Default::default()
+                }));
+            }
+        };
+
+        let result =
+            self.descriptor_set
+                .update_after_bind([WriteDescriptorSet::image_view_sampler_array(
+                    0,
+                    index,
+                    [(image_view.clone(), self.sampler.clone())],
+                )]);
+
+        if let Err(err) = result {
+            if free_index.is_some() {
+                self.free_indices.lock().unwrap().push(index);
+            }
+
+            return Err(err);
+        }
+
+        if let Some(view) = self.views.get_mut(index as usize) {
+            *view = image_view;
+        } else {
+            self.views.push(image_view);
+        }
+
+        Ok(BindlessTexture {
+            index,
+            free_indices: self.free_indices.clone(),
+        })
+    }
+}
+
+/// A texture registered in a [`BindlessTextures`] array.
+///
+/// Dropping the handle frees the texture's slot for reuse.
+#[derive(Debug)]
+pub struct BindlessTexture {
+    index: u32,
+    free_indices: Arc<Mutex<Vec<u32>>>,
+}
+
+impl BindlessTexture {
+    /// Returns the index of the texture in the texture array.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl Drop for BindlessTexture {
+    fn drop(&mut self) {
+        if let Ok(mut free_indices) = self.free_indices.lock() {
+            free_indices.push(self.index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BindlessTextures;
+    use std::sync::Arc;
+    use vulkano::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+        },
+        descriptor_set::allocator::StandardDescriptorSetAllocator,
+        device::Device,
+        format::Format,
+        image::{
+            sampler::{Sampler, SamplerCreateInfo},
+            view::ImageView,
+            Image, ImageCreateInfo, ImageType, ImageUsage,
+        },
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+        pipeline::{
+            compute::ComputePipelineCreateInfo, layout::PipelineLayoutCreateInfo, ComputePipeline,
+            Pipeline, PipelineBindPoint, PipelineLayout, PipelineShaderStageCreateInfo,
+        },
+        shader::{ShaderModule, ShaderModuleCreateInfo},
+    };
+
+    fn textures(device: &Arc<Device>, capacity: u32) -> BindlessTextures {
+        let descriptor_set_allocator =
+            StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+        let sampler = Sampler::new(device.clone(), SamplerCreateInfo::default()).unwrap();
+
+        BindlessTextures::new(&descriptor_set_allocator, sampler, capacity).unwrap()
+    }
+
+    fn view(memory_allocator: &Arc<StandardMemoryAllocator>) -> Arc<ImageView> {
+        let image = Image::new(
+            memory_allocator.clone(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [4, 4, 1],
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        ImageView::new_default(image).unwrap()
+    }
+
+    #[test]
+    fn index_reuse() {
+        let (device, _) = gfx_dev_and_queue!(
+            descriptor_binding_sampled_image_update_after_bind,
+            descriptor_binding_update_unused_while_pending,
+            descriptor_binding_partially_bound,
+            descriptor_binding_variable_descriptor_count,
+            runtime_descriptor_array
+        );
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let mut textures = textures(&device, 2);
+
+        unsafe {
+            let texture0 = textures.register(view(&memory_allocator)).unwrap();
+            let texture1 = textures.register(view(&memory_allocator)).unwrap();
+            assert_eq!(texture0.index(), 0);
+            assert_eq!(texture1.index(), 1);
+
+            // The slot of a dropped texture is given to the next texture.
+            drop(texture0);
+            let texture2 = textures.register(view(&memory_allocator)).unwrap();
+            assert_eq!(texture2.index(), 0);
+        }
+    }
+
+    #[test]
+    fn capacity_exhausted() {
+        let (device, _) = gfx_dev_and_queue!(
+            descriptor_binding_sampled_image_update_after_bind,
+            descriptor_binding_update_unused_while_pending,
+            descriptor_binding_partially_bound,
+            descriptor_binding_variable_descriptor_count,
+            runtime_descriptor_array
+        );
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let mut textures = textures(&device, 1);
+
+        unsafe {
+            let texture = textures.register(view(&memory_allocator)).unwrap();
+            assert!(textures.register(view(&memory_allocator)).is_err());
+
+            drop(texture);
+            textures.register(view(&memory_allocator)).unwrap();
+        }
+    }
+
+    #[test]
+    fn partially_written_dispatch() {
+        let (device, queue) = gfx_dev_and_queue!(
+            descriptor_binding_sampled_image_update_after_bind,
+            descriptor_binding_update_unused_while_pending,
+            descriptor_binding_partially_bound,
+            descriptor_binding_variable_descriptor_count,
+            runtime_descriptor_array
+        );
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let mut textures = textures(&device, 4);
+
+        let cs = unsafe {
+            // A compute shader that loads the first element of a runtime-sized array of combined
+            // image samplers at set 0, binding 0, using the `RuntimeDescriptorArray` capability.
+            const MODULE: [u32; 100] = [
+                119734787, 65536, 0, 16, 0, 131089, 1, 131089, 5302, 524298, 1599492179,
+                1599363141, 1668506980, 1953524082, 1767862895, 2019910766, 6778473, 196622, 0, 1,
+                327695, 5, 1, 1852399981, 0, 393232, 1, 17, 1, 1, 1, 262215, 9, 34, 0, 262215, 9,
+                33, 0, 131091, 2, 196641, 3, 2, 196630, 4, 32, 589849, 5, 4, 1, 0, 0, 0, 1, 0,
+                196635, 6, 5, 196637, 7, 6, 262176, 8, 0, 7, 262176, 10, 0, 6, 262165, 11, 32, 1,
+                262187, 11, 12, 0, 262203, 8, 9, 0, 327734, 2, 1, 0, 3, 131320, 13, 327745, 10, 14,
+                9, 12, 262205, 6, 15, 14, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineLayoutCreateInfo {
+                set_layouts: vec![textures.layout().clone()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let pipeline = ComputePipeline::new(
+            device.clone(),
+            None,
+            ComputePipelineCreateInfo::stage_layout(PipelineShaderStageCreateInfo::new(cs), layout),
+        )
+        .unwrap();
+
+        // Only the first of the four slots is written. The shader's array is runtime-sized, so
+        // without `PARTIALLY_BOUND` the other slots would be reported as unwritten.
+        let _texture = unsafe { textures.register(view(&memory_allocator)).unwrap() };
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder
+            .bind_pipeline_compute(pipeline.clone())
+            .unwrap()
+            .bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                pipeline.layout().clone(),
+                0,
+                textures.descriptor_set().clone(),
+            )
+            .unwrap()
+            .dispatch([1, 1, 1])
+            .unwrap();
+    }
+}
@@ -7,22 +7,39 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
-use std::sync::Arc;
+use std::{
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
 #[cfg(target_os = "macos")]
 use vulkano::instance::InstanceCreateFlags;
 use vulkano::{
+    buffer::{Buffer, BufferAllocateError, BufferCreateInfo, BufferUsage, Subbuffer},
+    command_buffer::{
+        allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+        CommandBufferExecError, CommandBufferUsage, CopyBufferInfo, CopyImageToBufferInfo,
+        PrimaryAutoCommandBuffer,
+    },
     device::{
         physical::{PhysicalDevice, PhysicalDeviceType},
         Device, DeviceCreateInfo, DeviceExtensions, Features, Queue, QueueCreateInfo, QueueFlags,
     },
+    image::Image,
     instance::{
         debug::{DebugUtilsMessenger, DebugUtilsMessengerCreateInfo},
         Instance, InstanceCreateInfo, InstanceExtensions,
     },
-    memory::allocator::StandardMemoryAllocator,
-    Version, VulkanLibrary,
+    memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+    sync::{self, GpuFuture, HostAccessError},
+    DeviceSize, Validated, ValidationError, Version, VulkanError, VulkanLibrary,
 };
 
+/// A function that scores a physical device for selection, or rejects it by returning `None`.
+pub type DeviceScoreFn = Arc<dyn Fn(&PhysicalDevice) -> Option<u32>>;
+
 /// A configuration struct to pass various creation options to create [`VulkanoContext`].
 ///
 /// Instance extensions that are required for surface creation will be appended to the config when
@@ -40,6 +57,24 @@ pub struct VulkanoConfig {
     /// Pass priority order function for your physical device selection. See default for example.
     pub device_priority_fn: Arc<dyn Fn(&PhysicalDevice) -> u32>,
 
+    /// Pass scoring function for your physical device selection. Out of the devices that pass
+    /// `device_filter_fn`, the one with the highest score is chosen, and devices for which the
+    /// function returns `None` are rejected. See [`select_physical_device`].
+    ///
+    /// If this is `Some`, it is used instead of `device_priority_fn`.
+    ///
+    /// The default value is `None`.
+    pub device_score_fn: Option<DeviceScoreFn>,
+
+    /// Selects a specific physical device, for example to force the discrete GPU on a system
+    /// with several GPUs.
+    ///
+    /// If this is `Some`, the identified device is used instead of scoring the devices with
+    /// `device_score_fn` or `device_priority_fn`. The device must still pass `device_filter_fn`.
+    ///
+    /// The default value is `None`.
+    pub physical_device_id: Option<PhysicalDeviceId>,
+
     pub device_extensions: DeviceExtensions,
 
     pub device_features: Features,
@@ -80,6 +115,8 @@ impl Default for VulkanoConfig {
                 PhysicalDeviceType::Other => 5,
                 _ => 6,
             }),
+            device_score_fn: None,
+            physical_device_id: None,
             print_device_name: false,
             device_extensions,
             device_features: Features::empty(),
@@ -87,6 +124,183 @@ impl Default for VulkanoConfig {
     }
 }
 
+/// Enumerates the physical devices of `instance`, and returns the one for which `score_fn`
+/// returns the highest score.
+///
+/// Devices for which `score_fn` returns `None` are never chosen. If several devices have the
+/// same score, the one that was enumerated first is chosen.
+/// [`default_physical_device_score`] can be used as a starting point, or as a fallback.
+///
+/// ## Examples
+///
+/// Forcing the selection of a specific GPU:
+///
+/// ```no_run
+/// use std::sync::Arc;
+/// use vulkano::instance::Instance;
+/// use vulkano_util::context::select_physical_device;
+///
+/// # let instance: Arc<Instance> = return;
+/// let uuid: [u8; 16] = [0; 16];
+/// let physical_device = select_physical_device(&instance, |p| {
+///     (p.properties().device_uuid == Some(uuid)).then_some(1)
+/// })
+/// .unwrap();
+/// ```
+pub fn select_physical_device(
+    instance: &Arc<Instance>,
+    score_fn: impl Fn(&PhysicalDevice) -> Option<u32>,
+) -> Result<Arc<PhysicalDevice>, PhysicalDeviceSelectionError> {
+    let mut selected: Option<(u32, Arc<PhysicalDevice>)> = None;
+
+    for physical_device in instance.enumerate_physical_devices()? {
+        let score = match score_fn(&physical_device) {
+            Some(score) => score,
+            None => continue,
+        };
+
+        match &selected {
+            Some((selected_score, _)) if *selected_score >= score => (),
+            _ => selected = Some((score, physical_device)),
+        }
+    }
+
+    selected
+        .map(|(_, physical_device)| physical_device)
+        .ok_or(PhysicalDeviceSelectionError::NoSuitableDevice)
+}
+
+/// The default scoring function for [`select_physical_device`].
+///
+/// Discrete GPUs are preferred, followed by integrated GPUs, virtual GPUs, CPUs and then all other
+/// device types. No device is rejected.
+pub fn default_physical_device_score(physical_device: &PhysicalDevice) -> Option<u32> {
+    Some(match physical_device.properties().device_type {
+        PhysicalDeviceType::DiscreteGpu => 5,
+        PhysicalDeviceType::IntegratedGpu => 4,
+        PhysicalDeviceType::VirtualGpu => 3,
+        PhysicalDeviceType::Cpu => 2,
+        PhysicalDeviceType::Other => 1,
+        _ => 0,
+    })
+}
+
+/// Identifies a specific physical device.
+///
+/// ## Examples
+///
+/// Selecting the first physical device that the instance enumerates:
+///
+/// ```no_run
+/// use vulkano_util::context::{PhysicalDeviceId, VulkanoConfig, VulkanoContext};
+///
+/// let context = VulkanoContext::new(VulkanoConfig {
+///     physical_device_id: Some(PhysicalDeviceId::Index(0)),
+///     ..Default::default()
+/// });
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PhysicalDeviceId {
+    /// The device at this index, in the order that the instance enumerates the physical devices.
+    Index(usize),
+
+    /// The first device whose name contains this string.
+    Name(String),
+
+    /// The device with this [`device_uuid`].
+    ///
+    /// [`device_uuid`]: vulkano::device::Properties::device_uuid
+    Uuid([u8; 16]),
+
+    /// The device at this PCI bus address.
+    ///
+    /// This only matches devices that report their PCI bus address, which requires the
+    /// [`ext_pci_bus_info`] extension to be supported.
+    ///
+    /// [`ext_pci_bus_info`]: vulkano::device::DeviceExtensions::ext_pci_bus_info
+    Pci {
+        domain: u32,
+        bus: u32,
+        device: u32,
+        function: u32,
+    },
+}
+
+impl PhysicalDeviceId {
+    /// Enumerates the physical devices of `instance`, and returns the one identified by `self`.
+    pub fn find(
+        &self,
+        instance: &Arc<Instance>,
+    ) -> Result<Arc<PhysicalDevice>, PhysicalDeviceSelectionError> {
+        let mut physical_devices = instance.enumerate_physical_devices()?;
+
+        match self {
+            &Self::Index(index) => physical_devices.nth(index),
+            _ => physical_devices.find(|p| self.matches(p)),
+        }
+        .ok_or(PhysicalDeviceSelectionError::NoSuitableDevice)
+    }
+
+    /// Returns whether `self` identifies `physical_device`.
+    ///
+    /// This always returns `false` for [`PhysicalDeviceId::Index`], as the index is not a
+    /// property of the device.
+    pub fn matches(&self, physical_device: &PhysicalDevice) -> bool {
+        let properties = physical_device.properties();
+
+        match self {
+            Self::Index(_) => false,
+            Self::Name(name) => properties.device_name.contains(name.as_str()),
+            Self::Uuid(uuid) => properties.device_uuid == Some(*uuid),
+            &Self::Pci {
+                domain,
+                bus,
+                device,
+                function,
+            } => {
+                properties.pci_domain == Some(domain)
+                    && properties.pci_bus == Some(bus)
+                    && properties.pci_device == Some(device)
+                    && properties.pci_function == Some(function)
+            }
+        }
+    }
+}
+
+/// Error that can happen when selecting a physical device.
+#[derive(Clone, Debug)]
+pub enum PhysicalDeviceSelectionError {
+    /// Enumerating the physical devices failed.
+    Vulkan(VulkanError),
+
+    /// The scoring function rejected every physical device.
+    NoSuitableDevice,
+}
+
+impl Error for PhysicalDeviceSelectionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Vulkan(err) => Some(err),
+            Self::NoSuitableDevice => None,
+        }
+    }
+}
+
+impl Display for PhysicalDeviceSelectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::Vulkan(_) => write!(f, "enumerating the physical devices failed"),
+            Self::NoSuitableDevice => write!(f, "no suitable physical device was found"),
+        }
+    }
+}
+
+impl From<VulkanError> for PhysicalDeviceSelectionError {
+    fn from(err: VulkanError) -> Self {
+        Self::Vulkan(err)
+    }
+}
+
 /// A utility struct to create, access and hold alive Vulkano device, instance and queues.
 ///
 /// Vulkano context is used in the creation of your graphics or compute pipelines, images and
@@ -110,6 +324,7 @@ pub struct VulkanoContext {
     graphics_queue: Arc<Queue>,
     compute_queue: Arc<Queue>,
     memory_allocator: Arc<StandardMemoryAllocator>,
+    command_buffer_allocator: StandardCommandBufferAllocator,
 }
 
 impl Default for VulkanoContext {
@@ -152,13 +367,31 @@ impl VulkanoContext {
                 .expect("failed to create debug callback")
         });
 
-        // Get prioritized device
-        let physical_device = instance
-            .enumerate_physical_devices()
-            .expect("failed to enumerate physical devices")
-            .filter(|p| (config.device_filter_fn)(p))
-            .min_by_key(|p| (config.device_priority_fn)(p))
-            .expect("failed to create physical device");
+        // Get the requested device, or the prioritized device
+        let physical_device = match &config.physical_device_id {
+            Some(physical_device_id) => {
+                let physical_device = physical_device_id
+                    .find(&instance)
+                    .unwrap_or_else(|err| panic!("failed to find {physical_device_id:?}: {err}"));
+                assert!(
+                    (config.device_filter_fn)(&physical_device),
+                    "the physical device {physical_device_id:?} was rejected by `device_filter_fn`",
+                );
+
+                physical_device
+            }
+            None => select_physical_device(&instance, |p| {
+                if !(config.device_filter_fn)(p) {
+                    return None;
+                }
+
+                match &config.device_score_fn {
+                    Some(device_score_fn) => device_score_fn(p),
+                    None => Some(u32::MAX - (config.device_priority_fn)(p)),
+                }
+            })
+            .expect("failed to select a physical device"),
+        };
         // Print used device
         if config.print_device_name {
             println!(
@@ -176,6 +409,8 @@ impl VulkanoContext {
         );
 
         let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
 
         Self {
             instance,
@@ -184,6 +419,7 @@ impl VulkanoContext {
             graphics_queue,
             compute_queue,
             memory_allocator,
+            command_buffer_allocator,
         }
     }
 
@@ -305,4 +541,449 @@ impl VulkanoContext {
     pub fn memory_allocator(&self) -> &Arc<StandardMemoryAllocator> {
         &self.memory_allocator
     }
+
+    /// Copies the contents of `buffer` to the host, and returns them as bytes.
+    ///
+    /// The copy is submitted to the compute queue, and this function blocks until it has
+    /// completed. This is mainly meant for debugging and tests, for example to inspect the output
+    /// of a compute shader; it is too slow to be used every frame.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// # use vulkano::buffer::Subbuffer;
+    /// # use vulkano_util::context::VulkanoContext;
+    /// # fn test(context: &VulkanoContext, buffer: Subbuffer<[u32]>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let bytes = context.download_buffer(buffer)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn download_buffer<T: ?Sized>(
+        &self,
+        buffer: Subbuffer<T>,
+    ) -> Result<Vec<u8>, DownloadError> {
+        self.download_buffer_with_timeout(buffer, None)
+    }
+
+    /// Same as [`download_buffer`](Self::download_buffer), but gives up waiting after `timeout`
+    /// if it is `Some`, and returns [`DownloadError::Timeout`].
+    pub fn download_buffer_with_timeout<T: ?Sized>(
+        &self,
+        buffer: Subbuffer<T>,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<u8>, DownloadError> {
+        let buffer = buffer.into_bytes();
+        let size = buffer.size();
+
+        self.download(size, timeout, |builder, dst_buffer| {
+            builder.copy_buffer(CopyBufferInfo::buffers(buffer, dst_buffer))?;
+
+            Ok(())
+        })
+    }
+
+    /// Copies the first mip level of `image` to the host, and returns it as bytes.
+    ///
+    /// The texels are returned in the format of the image, tightly packed: rows follow each other
+    /// without padding, regardless of the row pitch that the image has in device memory. For
+    /// block-compressed formats, each element is a whole block. Array layers and depth slices
+    /// follow each other in order.
+    ///
+    /// `image` must have been created with [`ImageUsage::TRANSFER_SRC`], and its format must have
+    /// a single aspect; combined depth/stencil and multi-planar formats are not supported.
+    ///
+    /// The copy is submitted to the compute queue, and this function blocks until it has
+    /// completed. This is mainly meant for debugging and tests, for example to inspect the output
+    /// of a compute shader; it is too slow to be used every frame.
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// # use std::sync::Arc;
+    /// # use vulkano::image::Image;
+    /// # use vulkano_util::context::VulkanoContext;
+    /// # fn test(context: &VulkanoContext, image: Arc<Image>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let pixels = context.download_image(image)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`ImageUsage::TRANSFER_SRC`]: vulkano::image::ImageUsage::TRANSFER_SRC
+    pub fn download_image(&self, image: Arc<Image>) -> Result<Vec<u8>, DownloadError> {
+        self.download_image_with_timeout(image, None)
+    }
+
+    /// Same as [`download_image`](Self::download_image), but gives up waiting after `timeout` if
+    /// it is `Some`, and returns [`DownloadError::Timeout`].
+    pub fn download_image_with_timeout(
+        &self,
+        image: Arc<Image>,
+        timeout: Option<Duration>,
+    ) -> Result<Vec<u8>, DownloadError> {
+        let format = image.format();
+
+        if format.aspects().count() != 1 {
+            return Err(DownloadError::Vulkan(
+                Box::new(ValidationError {
+                    context: "image.format()".into(),
+                    problem: "does not have exactly one aspect".into(),
+                    ..Default::default()
+                })
+                .into(),
+            ));
+        }
+
+        let extent = image.extent();
+        let block_extent = format.block_extent();
+        let block_count = (0..3)
+            .map(|i| extent[i].div_ceil(block_extent[i]) as DeviceSize)
+            .product::<DeviceSize>()
+            * image.array_layers() as DeviceSize;
+        let size = block_count * format.block_size();
+
+        self.download(size, timeout, |builder, dst_buffer| {
+            builder.copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(image, dst_buffer))?;
+
+            Ok(())
+        })
+    }
+
+    fn download(
+        &self,
+        size: DeviceSize,
+        timeout: Option<Duration>,
+        record: impl FnOnce(
+            &mut AutoCommandBufferBuilder<PrimaryAutoCommandBuffer>,
+            Subbuffer<[u8]>,
+        ) -> Result<(), Box<ValidationError>>,
+    ) -> Result<Vec<u8>, DownloadError> {
+        let dst_buffer = Buffer::new_slice::<u8>(
+            self.memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            size,
+        )
+        .map_err(DownloadError::AllocateBuffer)?;
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &self.command_buffer_allocator,
+            self.compute_queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )?;
+        record(&mut builder, dst_buffer.clone())?;
+        let command_buffer = builder.build()?;
+
+        let future = sync::now(self.device.clone())
+            .then_execute(self.compute_queue.clone(), command_buffer)
+            .map_err(DownloadError::Execute)?
+            .then_signal_fence_and_flush()?;
+
+        match future.wait(timeout) {
+            Ok(()) => (),
+            Err(Validated::Error(VulkanError::Timeout)) => {
+                // Dropping the future blocks until the copy has completed, so let another thread
+                // do it.
+                thread::spawn(move || drop(future));
+
+                return Err(DownloadError::Timeout);
+            }
+            Err(err) => return Err(err.into()),
+        }
+
+        let bytes = dst_buffer
+            .read()
+            .map_err(DownloadError::HostAccess)?
+            .to_vec();
+
+        Ok(bytes)
+    }
+}
+
+/// Error that can happen when downloading data with [`VulkanoContext`].
+#[derive(Clone, Debug)]
+pub enum DownloadError {
+    /// Allocating the host-visible buffer to copy the data into failed.
+    AllocateBuffer(Validated<BufferAllocateError>),
+
+    /// Submitting the copy failed.
+    Execute(CommandBufferExecError),
+
+    /// The copy didn't complete before the timeout. It is left to complete in the background.
+    Timeout,
+
+    /// Reading the copied data on the host failed.
+    HostAccess(HostAccessError),
+
+    /// Another error happened.
+    Vulkan(Validated<VulkanError>),
+}
+
+impl Error for DownloadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::AllocateBuffer(err) => Some(err),
+            Self::Execute(err) => Some(err),
+            Self::Timeout => None,
+            Self::HostAccess(err) => Some(err),
+            Self::Vulkan(err) => Some(err),
+        }
+    }
+}
+
+impl Display for DownloadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::AllocateBuffer(_) => write!(f, "allocating the download buffer failed"),
+            Self::Execute(_) => write!(f, "submitting the copy failed"),
+            Self::Timeout => write!(f, "the copy did not complete before the timeout"),
+            Self::HostAccess(_) => write!(f, "reading the downloaded data failed"),
+            Self::Vulkan(_) => write!(f, "a Vulkan error occurred"),
+        }
+    }
+}
+
+impl From<Validated<VulkanError>> for DownloadError {
+    fn from(err: Validated<VulkanError>) -> Self {
+        Self::Vulkan(err)
+    }
+}
+
+impl From<Box<ValidationError>> for DownloadError {
+    fn from(err: Box<ValidationError>) -> Self {
+        Self::Vulkan(err.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        default_physical_device_score, select_physical_device, DownloadError, PhysicalDeviceId,
+        PhysicalDeviceSelectionError,
+    };
+    use vulkano::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        command_buffer::{
+            AutoCommandBufferBuilder, ClearColorImageInfo, CommandBufferUsage,
+            PrimaryCommandBufferAbstract,
+        },
+        format::{Format, FormatFeatures},
+        image::{Image, ImageCreateInfo, ImageType, ImageUsage},
+        memory::allocator::{AllocationCreateInfo, MemoryTypeFilter},
+        sync::GpuFuture,
+        Validated,
+    };
+
+    #[test]
+    fn select_physical_device_score() {
+        let instance = instance!();
+        let physical_devices: Vec<_> = match instance.enumerate_physical_devices() {
+            Ok(x) => x.collect(),
+            Err(_) => return,
+        };
+
+        assert!(matches!(
+            select_physical_device(&instance, |_| None),
+            Err(PhysicalDeviceSelectionError::NoSuitableDevice),
+        ));
+
+        if physical_devices.is_empty() {
+            return;
+        }
+
+        // Ties are won by the device that was enumerated first.
+        let selected = select_physical_device(&instance, |_| Some(0)).unwrap();
+        assert_eq!(selected, physical_devices[0]);
+
+        let last = physical_devices.last().unwrap();
+        let selected =
+            select_physical_device(&instance, |p| Some((p == last.as_ref()) as u32)).unwrap();
+        assert_eq!(&selected, last);
+
+        let selected = select_physical_device(&instance, default_physical_device_score).unwrap();
+        let best_score = physical_devices
+            .iter()
+            .filter_map(|p| default_physical_device_score(p))
+            .max();
+        assert_eq!(default_physical_device_score(&selected), best_score);
+    }
+
+    #[test]
+    fn physical_device_id() {
+        let instance = instance!();
+        let physical_devices: Vec<_> = match instance.enumerate_physical_devices() {
+            Ok(x) => x.collect(),
+            Err(_) => return,
+        };
+
+        assert!(matches!(
+            PhysicalDeviceId::Index(physical_devices.len()).find(&instance),
+            Err(PhysicalDeviceSelectionError::NoSuitableDevice),
+        ));
+        assert!(matches!(
+            PhysicalDeviceId::Uuid([0xff; 16]).find(&instance),
+            Err(PhysicalDeviceSelectionError::NoSuitableDevice),
+        ));
+
+        for (index, physical_device) in physical_devices.iter().enumerate() {
+            let properties = physical_device.properties();
+
+            assert_eq!(
+                &PhysicalDeviceId::Index(index).find(&instance).unwrap(),
+                physical_device,
+            );
+            assert!(!PhysicalDeviceId::Index(index).matches(physical_device));
+            let name = properties.device_name.clone();
+            assert!(PhysicalDeviceId::Name(name).matches(physical_device));
+
+            if let Some(uuid) = properties.device_uuid {
+                assert!(PhysicalDeviceId::Uuid(uuid).matches(physical_device));
+            }
+
+            if let (Some(domain), Some(bus), Some(device), Some(function)) = (
+                properties.pci_domain,
+                properties.pci_bus,
+                properties.pci_device,
+                properties.pci_function,
+            ) {
+                let id = PhysicalDeviceId::Pci {
+                    domain,
+                    bus,
+                    device,
+                    function,
+                };
+                assert!(id.matches(physical_device));
+            }
+        }
+    }
+
+    #[test]
+    fn context_physical_device_id() {
+        let instance = instance!();
+        let last = match instance.enumerate_physical_devices() {
+            Ok(x) => match x.last() {
+                Some(x) => x,
+                None => return,
+            },
+            Err(_) => return,
+        };
+        let last_index = instance.enumerate_physical_devices().unwrap().count() - 1;
+
+        // The context creates its own instance, so the devices are compared by name.
+        let context = context!(physical_device_id: Some(PhysicalDeviceId::Index(last_index)));
+        assert_eq!(context.device_name(), last.properties().device_name);
+    }
+
+    #[test]
+    fn download_buffer() {
+        let context = context!();
+        let buffer = Buffer::from_iter(
+            context.memory_allocator().clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            [1u32, 2, 3, 0xdeadbeef],
+        )
+        .unwrap();
+
+        let bytes = context.download_buffer(buffer.slice(1..)).unwrap();
+        let expected: Vec<u8> = [2u32, 3, 0xdeadbeef]
+            .iter()
+            .flat_map(|x| x.to_ne_bytes())
+            .collect();
+        assert_eq!(bytes, expected);
+    }
+
+    #[test]
+    fn download_image() {
+        let context = context!();
+        let image = Image::new(
+            context.memory_allocator().clone(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [3, 5, 1],
+                usage: ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &context.command_buffer_allocator,
+            context.graphics_queue().queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder
+            .clear_color_image(ClearColorImageInfo {
+                clear_value: [1.0, 0.0, 0.0, 1.0].into(),
+                ..ClearColorImageInfo::image(image.clone())
+            })
+            .unwrap();
+        builder
+            .build()
+            .unwrap()
+            .execute(context.graphics_queue().clone())
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        // The rows are tightly packed, so the size doesn't depend on the row pitch.
+        let pixels = context.download_image(image).unwrap();
+        assert_eq!(pixels.len(), 3 * 5 * 4);
+        assert!(pixels.chunks(4).all(|p| p == [255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn download_image_multiple_aspects() {
+        let context = context!();
+        let format = Format::D32_SFLOAT_S8_UINT;
+
+        if !context
+            .device()
+            .physical_device()
+            .format_properties(format)
+            .unwrap()
+            .optimal_tiling_features
+            .contains(FormatFeatures::TRANSFER_SRC)
+        {
+            return;
+        }
+
+        let image = Image::new(
+            context.memory_allocator().clone(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format,
+                extent: [4, 4, 1],
+                usage: ImageUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        match context.download_image(image) {
+            Err(DownloadError::Vulkan(Validated::ValidationError(err))) => {
+                assert_eq!(err.context, "image.format()");
+            }
+            _ => panic!(),
+        }
+    }
 }
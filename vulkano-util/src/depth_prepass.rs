@@ -0,0 +1,318 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::renderer::RendererError;
+use std::sync::Arc;
+use vulkano::{
+    command_buffer::{
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, RenderPassBeginInfo,
+        SubpassBeginInfo, SubpassContents, SubpassEndInfo,
+    },
+    device::DeviceOwned,
+    format::{ClearValue, Format},
+    image::{view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage},
+    memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    pipeline::{
+        graphics::{
+            depth_stencil::{CompareOp, DepthState, DepthStencilState},
+            GraphicsPipelineCreateInfo,
+        },
+        StateMode,
+    },
+    render_pass::{Framebuffer, FramebufferCreateInfo, RenderPass, Subpass},
+    shader::ShaderStage,
+    Validated, ValidationError, VulkanError,
+};
+
+/// A helper for rendering with a depth pre-pass.
+///
+/// The scene is first drawn with a depth-only pipeline that fills the depth attachment, then
+/// drawn again with the main pipeline, which only tests for equal depth without writing it. This
+/// way the fragment shader of the main pipeline runs at most once per pixel.
+///
+/// The helper owns a render pass with two subpasses sharing the same depth attachment, and the
+/// depth image itself, which is recreated when the color attachment changes size. Enabling the
+/// pre-pass for an existing pipeline takes two calls:
+///
+/// - Create the depth-only pipeline from [`DepthPrepass::prepass_pipeline_create_info`].
+/// - Create the main pipeline from [`DepthPrepass::main_pipeline_create_info`].
+///
+/// Then, each frame, call [`DepthPrepass::begin_prepass`], draw the geometry with the depth-only
+/// pipeline, call [`DepthPrepass::begin_main_pass`], draw the same geometry with the main
+/// pipeline, and end the render pass.
+///
+/// The vertex shader must compute the exact same positions in both pipelines, otherwise the
+/// equal-depth test fails. Declaring the output position `invariant` in the shader guarantees
+/// this.
+pub struct DepthPrepass {
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    render_pass: Arc<RenderPass>,
+    depth_format: Format,
+    depth_view: Option<Arc<ImageView>>,
+}
+
+impl DepthPrepass {
+    /// Creates a new `DepthPrepass` rendering to a color attachment of format `color_format`, with
+    /// a depth attachment of format `depth_format`.
+    ///
+    /// The color attachment is cleared at the start of the render pass and stored at the end.
+    pub fn new(
+        memory_allocator: Arc<StandardMemoryAllocator>,
+        color_format: Format,
+        depth_format: Format,
+    ) -> Result<Self, Validated<VulkanError>> {
+        let render_pass = vulkano::ordered_passes_renderpass!(
+            memory_allocator.device().clone(),
+            attachments: {
+                color: {
+                    format: color_format,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: Store,
+                },
+                depth: {
+                    format: depth_format,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            passes: [
+                { color: [], depth_stencil: {depth}, input: [] },
+                { color: [color], depth_stencil: {depth}, input: [] },
+            ],
+        )?;
+
+        Ok(DepthPrepass {
+            memory_allocator,
+            render_pass,
+            depth_format,
+            depth_view: None,
+        })
+    }
+
+    /// Returns the render pass used for both passes.
+    #[inline]
+    pub fn render_pass(&self) -> &Arc<RenderPass> {
+        &self.render_pass
+    }
+
+    /// Returns the subpass in which the depth-only pipeline is used.
+    #[inline]
+    pub fn prepass_subpass(&self) -> Subpass {
+        Subpass::from(self.render_pass.clone(), 0).unwrap()
+    }
+
+    /// Returns the subpass in which the main pipeline is used.
+    #[inline]
+    pub fn main_subpass(&self) -> Subpass {
+        Subpass::from(self.render_pass.clone(), 1).unwrap()
+    }
+
+    /// Returns the create info of the depth-only pipeline, derived from `main`, the create info of
+    /// the main pipeline.
+    ///
+    /// The vertex state and all shader stages before rasterization are kept, the fragment shader
+    /// and the color blend state are removed, and depth testing and writing are enabled.
+    pub fn prepass_pipeline_create_info(
+        &self,
+        main: &GraphicsPipelineCreateInfo,
+    ) -> GraphicsPipelineCreateInfo {
+        let mut create_info = main.clone();
+        create_info.stages.retain(|stage| {
+            ShaderStage::from(&stage.entry_point.info().execution) != ShaderStage::Fragment
+        });
+        create_info.depth_stencil_state = Some(DepthStencilState::simple_depth_test());
+        create_info.color_blend_state = None;
+        create_info.subpass = Some(self.prepass_subpass().into());
+        create_info.base_pipeline = None;
+        create_info.base_pipeline_index = None;
+
+        create_info
+    }
+
+    /// Returns the create info of the main pipeline, derived from `main`.
+    ///
+    /// The depth test is set to [`CompareOp::Equal`] with depth writes disabled, so that only the
+    /// fragments that were kept by the pre-pass are shaded.
+    pub fn main_pipeline_create_info(
+        &self,
+        main: &GraphicsPipelineCreateInfo,
+    ) -> GraphicsPipelineCreateInfo {
+        let mut create_info = main.clone();
+        create_info.depth_stencil_state = Some(Self::main_depth_stencil_state());
+        create_info.subpass = Some(self.main_subpass().into());
+
+        create_info
+    }
+
+    /// Returns the depth/stencil state of the main pipeline.
+    #[inline]
+    pub fn main_depth_stencil_state() -> DepthStencilState {
+        DepthStencilState {
+            depth: Some(DepthState {
+                enable_dynamic: false,
+                compare_op: StateMode::Fixed(CompareOp::Equal),
+                write_enable: StateMode::Fixed(false),
+            }),
+            ..DepthStencilState::disabled()
+        }
+    }
+
+    /// Returns the depth attachment of size `extent`, creating it if it doesn't exist yet or if
+    /// its size differs.
+    ///
+    /// Returns an error if the depth image or its view could not be created.
+    pub fn depth_view(&mut self, extent: [u32; 2]) -> Result<Arc<ImageView>, RendererError> {
+        match &self.depth_view {
+            Some(view) if view.image().extent()[0..2] == extent => Ok(view.clone()),
+            _ => {
+                let image = Image::new(
+                    self.memory_allocator.clone(),
+                    ImageCreateInfo {
+                        image_type: ImageType::Dim2d,
+                        format: self.depth_format,
+                        extent: [extent[0], extent[1], 1],
+                        usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT
+                            | ImageUsage::TRANSIENT_ATTACHMENT,
+                        ..Default::default()
+                    },
+                    AllocationCreateInfo::default(),
+                )
+                .map_err(RendererError::AllocateImage)?;
+                let view = ImageView::new_default(image)?;
+                self.depth_view = Some(view.clone());
+
+                Ok(view)
+            }
+        }
+    }
+
+    /// Creates a framebuffer rendering to `color_view`, using the shared depth attachment.
+    pub fn framebuffer(
+        &mut self,
+        color_view: Arc<ImageView>,
+    ) -> Result<Arc<Framebuffer>, RendererError> {
+        let extent = color_view.image().extent();
+        let depth_view = self.depth_view([extent[0], extent[1]])?;
+
+        Ok(Framebuffer::new(
+            self.render_pass.clone(),
+            FramebufferCreateInfo {
+                attachments: vec![color_view, depth_view],
+                ..Default::default()
+            },
+        )?)
+    }
+
+    /// Begins the render pass on `framebuffer`, clearing the color attachment to `clear_color` and
+    /// the depth attachment to 1.0, and enters the pre-pass subpass.
+    pub fn begin_prepass<L, A>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+        framebuffer: Arc<Framebuffer>,
+        clear_color: ClearValue,
+    ) -> Result<(), Box<ValidationError>>
+    where
+        A: CommandBufferAllocator,
+    {
+        builder.begin_render_pass(
+            RenderPassBeginInfo {
+                clear_values: vec![Some(clear_color), Some(1.0f32.into())],
+                ..RenderPassBeginInfo::framebuffer(framebuffer)
+            },
+            SubpassBeginInfo {
+                contents: SubpassContents::Inline,
+                ..Default::default()
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Ends the pre-pass subpass and enters the main subpass.
+    pub fn begin_main_pass<L, A>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+    ) -> Result<(), Box<ValidationError>>
+    where
+        A: CommandBufferAllocator,
+    {
+        builder.next_subpass(
+            SubpassEndInfo::default(),
+            SubpassBeginInfo {
+                contents: SubpassContents::Inline,
+                ..Default::default()
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DepthPrepass;
+    use std::sync::Arc;
+    use vulkano::{
+        format::Format,
+        image::{view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage},
+        memory::allocator::AllocationCreateInfo,
+    };
+
+    #[test]
+    fn shared_depth_view() {
+        let context = context!();
+        let mut prepass = DepthPrepass::new(
+            context.memory_allocator().clone(),
+            Format::R8G8B8A8_UNORM,
+            Format::D16_UNORM,
+        )
+        .unwrap();
+        assert_eq!(prepass.render_pass().subpasses().len(), 2);
+        assert_eq!(prepass.prepass_subpass().num_color_attachments(), 0);
+        assert_eq!(prepass.main_subpass().num_color_attachments(), 1);
+
+        let color_view = |extent: [u32; 2]| {
+            let image = Image::new(
+                context.memory_allocator().clone(),
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format: Format::R8G8B8A8_UNORM,
+                    extent: [extent[0], extent[1], 1],
+                    usage: ImageUsage::COLOR_ATTACHMENT,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap();
+
+            ImageView::new_default(image).unwrap()
+        };
+
+        let framebuffer1 = prepass.framebuffer(color_view([64, 32])).unwrap();
+        let framebuffer2 = prepass.framebuffer(color_view([64, 32])).unwrap();
+        assert_eq!(framebuffer1.extent(), [64, 32]);
+        assert!(Arc::ptr_eq(
+            &framebuffer1.attachments()[1],
+            &framebuffer2.attachments()[1],
+        ));
+
+        // A color attachment of another size gets a new depth attachment.
+        let framebuffer3 = prepass.framebuffer(color_view([32, 32])).unwrap();
+        assert!(!Arc::ptr_eq(
+            &framebuffer1.attachments()[1],
+            &framebuffer3.attachments()[1],
+        ));
+        assert!(Arc::ptr_eq(
+            &framebuffer3.attachments()[1],
+            &prepass.depth_view([32, 32]).unwrap(),
+        ));
+    }
+}
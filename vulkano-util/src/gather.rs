@@ -0,0 +1,354 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::sync::Arc;
+use vulkano::{
+    buffer::{BufferContents, Subbuffer},
+    command_buffer::{allocator::CommandBufferAllocator, AutoCommandBufferBuilder},
+    descriptor_set::{
+        allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
+    },
+    device::Device,
+    pipeline::{
+        compute::ComputePipelineCreateInfo, layout::PipelineDescriptorSetLayoutCreateInfo,
+        ComputePipeline, Pipeline, PipelineBindPoint, PipelineLayout,
+        PipelineShaderStageCreateInfo,
+    },
+    shader::{spirv::bytes_to_words, ShaderModule, ShaderModuleCreateInfo},
+    DeviceSize, Validated, ValidationError, VulkanError,
+};
+
+/// Gathers selected elements of a buffer into a dense buffer, entirely on the device.
+///
+/// The gather is performed by a small compute shader. Each invocation reads one index from the
+/// index buffer and copies one 32-bit word of the element it selects, so the indices never have
+/// to be read back to the host, and can themselves be written by an earlier command, such as a
+/// culling or compaction pass.
+pub struct BufferGather {
+    pipeline: Arc<ComputePipeline>,
+    descriptor_set_allocator: StandardDescriptorSetAllocator,
+}
+
+impl BufferGather {
+    /// Creates a new `BufferGather`, which creates the compute pipeline that performs the gather.
+    pub fn new(device: Arc<Device>) -> Result<Self, Validated<VulkanError>> {
+        let code = bytes_to_words(GATHER_SPV).unwrap();
+        let module =
+            unsafe { ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&code)) }?;
+        let cs = module.entry_point("main").unwrap();
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
+                .into_pipeline_layout_create_info(device.clone())
+                .map_err(|err| err.error)?,
+        )?;
+        let pipeline = ComputePipeline::new(
+            device.clone(),
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )?;
+        let descriptor_set_allocator =
+            StandardDescriptorSetAllocator::new(device, Default::default());
+
+        Ok(BufferGather {
+            pipeline,
+            descriptor_set_allocator,
+        })
+    }
+
+    /// Returns the compute pipeline that performs the gather.
+    #[inline]
+    pub fn pipeline(&self) -> &Arc<ComputePipeline> {
+        &self.pipeline
+    }
+
+    /// Records commands to `builder` that gather the elements of `source` listed in `indices`
+    /// into the start of `destination`, in the order they are listed.
+    ///
+    /// After the commands have executed, element `i` of `destination` holds the element
+    /// `indices[i]` of `source`. If `indices[i]` is not less than `source.len()`, element `i` of
+    /// `destination` is left untouched, as are the elements beyond `indices.len()`.
+    ///
+    /// All three buffers are read or written as storage buffers, so they must have been created
+    /// with the [`STORAGE_BUFFER`] usage, and their offsets must be multiples of the
+    /// [`min_storage_buffer_offset_alignment`] device limit. The size of `T` must be a multiple
+    /// of 4 bytes, and neither `source` nor the gathered elements may be larger than `u32::MAX`
+    /// 32-bit words. As with any command recorded to an [`AutoCommandBufferBuilder`], the barriers
+    /// between the gather and earlier or later commands that access the buffers are inserted
+    /// automatically.
+    ///
+    /// The gather's pipeline, descriptor set and push constants remain bound to the compute bind
+    /// point of `builder` afterwards, so they must be bound again before dispatching another
+    /// compute pipeline.
+    ///
+    /// If `indices` is empty, no commands are recorded.
+    ///
+    /// [`STORAGE_BUFFER`]: vulkano::buffer::BufferUsage::STORAGE_BUFFER
+    /// [`min_storage_buffer_offset_alignment`]: vulkano::device::Properties::min_storage_buffer_offset_alignment
+    pub fn gather<T, L, A>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+        source: Subbuffer<[T]>,
+        indices: Subbuffer<[u32]>,
+        destination: Subbuffer<[T]>,
+    ) -> Result<(), Validated<VulkanError>>
+    where
+        [T]: BufferContents,
+        A: CommandBufferAllocator,
+    {
+        let element_size = <[T]>::LAYOUT.element_size().unwrap();
+
+        if element_size % 4 != 0 {
+            return Err(Box::new(ValidationError {
+                problem: "the size of `T` is not a multiple of 4 bytes".into(),
+                ..Default::default()
+            })
+            .into());
+        }
+
+        if indices.len() > destination.len() {
+            return Err(Box::new(ValidationError {
+                problem: "`indices.len()` is greater than `destination.len()`".into(),
+                ..Default::default()
+            })
+            .into());
+        }
+
+        if indices.len() == 0 {
+            return Ok(());
+        }
+
+        let element_words = element_size / 4;
+        let word_count = indices
+            .len()
+            .checked_mul(element_words)
+            .filter(|&word_count| word_count <= u32::MAX as DeviceSize)
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "the total size of the gathered elements, in 32-bit words, is \
+                        greater than `u32::MAX`"
+                        .into(),
+                    ..Default::default()
+                })
+            })?;
+
+        // The shader computes the offsets of the words of `source` in 32 bits as well.
+        source
+            .len()
+            .checked_mul(element_words)
+            .filter(|&source_words| source_words <= u32::MAX as DeviceSize)
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "the size of `source`, in 32-bit words, is greater than `u32::MAX`"
+                        .into(),
+                    ..Default::default()
+                })
+            })?;
+
+        // The push constants of the shader: the number of words per element, the number of
+        // indices, and the number of elements of `source`.
+        let push_constants: [u32; 3] = [
+            element_words as u32,
+            indices.len() as u32,
+            source.len() as u32,
+        ];
+
+        let layout = self.pipeline.layout();
+        let descriptor_set = PersistentDescriptorSet::new(
+            &self.descriptor_set_allocator,
+            layout.set_layouts()[0].clone(),
+            [
+                WriteDescriptorSet::buffer(0, source),
+                WriteDescriptorSet::buffer(1, indices),
+                WriteDescriptorSet::buffer(2, destination),
+            ],
+            [],
+        )?;
+
+        builder
+            .bind_pipeline_compute(self.pipeline.clone())?
+            .bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                layout.clone(),
+                0,
+                descriptor_set,
+            )?
+            .push_constants(layout.clone(), 0, push_constants)?
+            .dispatch_for([word_count as u32, 1, 1])?;
+
+        Ok(())
+    }
+}
+
+// Compiled from `shaders/gather.comp` with naga 0.14.2, using its GLSL front end
+// (`naga::front::glsl::Frontend` with `ShaderStage::Compute`) and its SPIR-V back end
+// (`naga::back::spv::write_vec` with `lang_version: (1, 0)`, `WriterFlags::empty()` and the
+// `main` entry point). The result is a SPIR-V 1.0 module without debug information.
+const GATHER_SPV: &[u8] = include_bytes!("shaders/gather.spv");
+
+#[cfg(test)]
+mod tests {
+    use super::{BufferGather, GATHER_SPV};
+    use std::sync::Arc;
+    use vulkano::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            CommandBufferUsage, PrimaryCommandBufferAbstract,
+        },
+        descriptor_set::layout::DescriptorType,
+        memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+        shader::{
+            reflect::entry_points,
+            spirv::{bytes_to_words, Spirv},
+            ShaderExecution,
+        },
+        sync::GpuFuture,
+        Validated,
+    };
+
+    #[test]
+    fn gather_shader_interface() {
+        let code = bytes_to_words(GATHER_SPV).unwrap();
+        let spirv = Spirv::new(&code).unwrap();
+        let entry_points: Vec<_> = entry_points(&spirv).collect();
+        assert_eq!(entry_points.len(), 1);
+
+        let entry_point = &entry_points[0];
+        assert_eq!(entry_point.name, "main");
+        assert!(matches!(entry_point.execution, ShaderExecution::Compute(_)));
+        assert_eq!(entry_point.push_constant_requirements.unwrap().size, 12);
+
+        let mut bindings: Vec<_> = entry_point
+            .descriptor_binding_requirements
+            .iter()
+            .map(|(&binding, reqs)| {
+                assert!(reqs
+                    .descriptor_types
+                    .contains(&DescriptorType::StorageBuffer));
+                binding
+            })
+            .collect();
+        bindings.sort();
+        assert_eq!(bindings, [(0, 0), (0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn gather() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let gather = BufferGather::new(device).unwrap();
+
+        let buffer = |data: Vec<[u32; 2]>| {
+            Buffer::from_iter(
+                memory_allocator.clone(),
+                BufferCreateInfo {
+                    usage: BufferUsage::STORAGE_BUFFER,
+                    ..Default::default()
+                },
+                AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                        | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                    ..Default::default()
+                },
+                data,
+            )
+            .unwrap()
+        };
+        let source = buffer((0..8).map(|i| [i, i * 10]).collect());
+        let destination = buffer(vec![[u32::MAX; 2]; 5]);
+        let indices = Buffer::from_iter(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            [6u32, 1, 2, 100],
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        gather
+            .gather(&mut builder, source, indices, destination.clone())
+            .unwrap();
+
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert_eq!(
+            &*destination.read().unwrap(),
+            &[
+                [6, 60],
+                [1, 10],
+                [2, 20],
+                [u32::MAX, u32::MAX],
+                [u32::MAX, u32::MAX],
+            ],
+        );
+    }
+
+    #[test]
+    fn gather_too_many_indices() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let gather = BufferGather::new(device).unwrap();
+
+        let buffer = |len: u64| {
+            Buffer::new_slice::<u32>(
+                memory_allocator.clone(),
+                BufferCreateInfo {
+                    usage: BufferUsage::STORAGE_BUFFER,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+                len,
+            )
+            .unwrap()
+        };
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &command_buffer_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        match gather.gather(&mut builder, buffer(4), buffer(3), buffer(2)) {
+            Err(Validated::ValidationError(err)) => {
+                assert_eq!(
+                    err.problem,
+                    "`indices.len()` is greater than `destination.len()`",
+                );
+            }
+            _ => panic!(),
+        }
+    }
+}
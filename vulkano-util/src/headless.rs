@@ -0,0 +1,341 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{context::VulkanoContext, renderer::RendererError};
+use std::sync::Arc;
+use vulkano::{
+    device::Queue,
+    format::Format,
+    image::{view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage},
+    memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    sync::{self, GpuFuture},
+};
+
+/// A renderer that draws into a ring of offscreen images instead of a window.
+///
+/// This offers the same [`acquire`](Self::acquire) and [`present`](Self::present) flow as
+/// [`VulkanoWindowRenderer`](crate::renderer::VulkanoWindowRenderer), which makes it useful for
+/// rendering without a display, such as for video encoding or in tests. Each call to `acquire`
+/// advances to the next image of the ring, which can be accessed with
+/// [`image_view`](Self::image_view) until the matching `present`.
+///
+/// ## Examples
+///
+/// Clearing the images and reading one of them back:
+///
+/// ```no_run
+/// use vulkano::{
+///     command_buffer::{
+///         allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+///         ClearColorImageInfo, CommandBufferUsage,
+///     },
+///     format::Format,
+///     image::ImageUsage,
+///     sync::GpuFuture,
+/// };
+/// use vulkano_util::{
+///     context::{VulkanoConfig, VulkanoContext},
+///     headless::VulkanoHeadlessRenderer,
+/// };
+///
+/// let context = VulkanoContext::new(VulkanoConfig::default());
+/// let command_buffer_allocator =
+///     StandardCommandBufferAllocator::new(context.device().clone(), Default::default());
+/// let mut renderer = VulkanoHeadlessRenderer::new(
+///     &context,
+///     [64, 64],
+///     Format::R8G8B8A8_UNORM,
+///     ImageUsage::TRANSFER_DST | ImageUsage::TRANSFER_SRC,
+///     3,
+/// )
+/// .unwrap();
+///
+/// for _ in 0..renderer.image_count() {
+///     let before_future = renderer.acquire();
+///
+///     let mut builder = AutoCommandBufferBuilder::primary(
+///         &command_buffer_allocator,
+///         renderer.graphics_queue().queue_family_index(),
+///         CommandBufferUsage::OneTimeSubmit,
+///     )
+///     .unwrap();
+///     builder
+///         .clear_color_image(ClearColorImageInfo {
+///             clear_value: [1.0, 0.0, 0.0, 1.0].into(),
+///             ..ClearColorImageInfo::image(renderer.image_view().image().clone())
+///         })
+///         .unwrap();
+///     let command_buffer = builder.build().unwrap();
+///
+///     let after_future = before_future
+///         .then_execute(renderer.graphics_queue(), command_buffer)
+///         .unwrap()
+///         .boxed();
+///     renderer.present(after_future, true).unwrap();
+/// }
+///
+/// let pixels = context
+///     .download_image(renderer.image_view().image().clone())
+///     .unwrap();
+/// assert!(pixels.chunks(4).all(|p| p == [255, 0, 0, 255]));
+/// ```
+pub struct VulkanoHeadlessRenderer {
+    graphics_queue: Arc<Queue>,
+    compute_queue: Arc<Queue>,
+    memory_allocator: Arc<StandardMemoryAllocator>,
+    image_views: Vec<Arc<ImageView>>,
+    image_index: u32,
+    previous_frame_end: Option<Box<dyn GpuFuture>>,
+}
+
+impl VulkanoHeadlessRenderer {
+    /// Creates a new [`VulkanoHeadlessRenderer`] with a ring of `image_count` images of the given
+    /// `extent`, `format` and `usage`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `image_count` is 0.
+    pub fn new(
+        vulkano_context: &VulkanoContext,
+        extent: [u32; 2],
+        format: Format,
+        usage: ImageUsage,
+        image_count: u32,
+    ) -> Result<VulkanoHeadlessRenderer, RendererError> {
+        assert!(image_count != 0);
+
+        let memory_allocator = vulkano_context.memory_allocator().clone();
+        let image_views = (0..image_count)
+            .map(|_| {
+                let image = Image::new(
+                    memory_allocator.clone(),
+                    ImageCreateInfo {
+                        image_type: ImageType::Dim2d,
+                        format,
+                        extent: [extent[0], extent[1], 1],
+                        usage,
+                        ..Default::default()
+                    },
+                    AllocationCreateInfo::default(),
+                )
+                .map_err(RendererError::AllocateImage)?;
+
+                Ok(ImageView::new_default(image)?)
+            })
+            .collect::<Result<Vec<_>, RendererError>>()?;
+
+        Ok(VulkanoHeadlessRenderer {
+            graphics_queue: vulkano_context.graphics_queue().clone(),
+            compute_queue: vulkano_context.compute_queue().clone(),
+            memory_allocator,
+            // The first call to `acquire` wraps around to the first image.
+            image_index: image_count - 1,
+            image_views,
+            previous_frame_end: Some(sync::now(vulkano_context.device().clone()).boxed()),
+        })
+    }
+
+    /// Graphics queue of this renderer. You also can access this through [`VulkanoContext`].
+    #[inline]
+    pub fn graphics_queue(&self) -> Arc<Queue> {
+        self.graphics_queue.clone()
+    }
+
+    /// Compute queue of this renderer. You also can access this through [`VulkanoContext`].
+    #[inline]
+    pub fn compute_queue(&self) -> Arc<Queue> {
+        self.compute_queue.clone()
+    }
+
+    /// Returns the memory allocator that the images were allocated with.
+    #[inline]
+    pub fn memory_allocator(&self) -> &Arc<StandardMemoryAllocator> {
+        &self.memory_allocator
+    }
+
+    /// Returns the format of the images.
+    #[inline]
+    pub fn format(&self) -> Format {
+        self.image_views[0].format()
+    }
+
+    /// Returns the extent of the images.
+    #[inline]
+    pub fn image_extent(&self) -> [u32; 2] {
+        let extent = self.image_views[0].image().extent();
+
+        [extent[0], extent[1]]
+    }
+
+    /// Returns the number of images in the ring.
+    #[inline]
+    pub fn image_count(&self) -> u32 {
+        self.image_views.len() as u32
+    }
+
+    /// Returns the index of the image that was acquired by the last call to
+    /// [`acquire`](Self::acquire).
+    ///
+    /// Unlike with a swapchain, the images are always acquired in order, wrapping around after
+    /// the last one.
+    #[inline]
+    pub fn image_index(&self) -> u32 {
+        self.image_index
+    }
+
+    /// Returns the image view of the image that was acquired by the last call to
+    /// [`acquire`](Self::acquire).
+    #[inline]
+    pub fn image_view(&self) -> &Arc<ImageView> {
+        &self.image_views[self.image_index as usize]
+    }
+
+    /// Returns the image views of all the images in the ring.
+    #[inline]
+    pub fn image_views(&self) -> &[Arc<ImageView>] {
+        &self.image_views
+    }
+
+    /// Begin your rendering by calling `acquire`.
+    ///
+    /// This advances to the next image of the ring, and returns a
+    /// [`GpuFuture`](vulkano::sync::GpuFuture) representing the time after which the previous
+    /// frame ended. Because each frame waits on the previous one, an image is never rendered to
+    /// while it is still in use by an earlier frame. Execute your command buffers after calling
+    /// this function and finish rendering by calling [`present`](Self::present).
+    #[inline]
+    pub fn acquire(&mut self) -> Box<dyn GpuFuture> {
+        self.image_index = (self.image_index + 1) % self.image_count();
+
+        self.previous_frame_end
+            .take()
+            .unwrap_or_else(|| sync::now(self.graphics_queue.device().clone()).boxed())
+    }
+
+    /// Finishes rendering by flushing `after_future` and signaling a fence. Pass your last
+    /// future as an input to this function.
+    ///
+    /// If `wait_future` is `true`, this waits until the rendering has finished, after which the
+    /// contents of the current image can be read on the host.
+    #[inline]
+    pub fn present(
+        &mut self,
+        after_future: Box<dyn GpuFuture>,
+        wait_future: bool,
+    ) -> Result<(), RendererError> {
+        match after_future.then_signal_fence_and_flush() {
+            Ok(mut future) => {
+                let result = if wait_future {
+                    future.wait(None)
+                } else {
+                    future.cleanup_finished();
+
+                    Ok(())
+                };
+
+                self.previous_frame_end = Some(future.boxed());
+
+                result.map_err(RendererError::from)
+            }
+            Err(err) => {
+                self.previous_frame_end =
+                    Some(sync::now(self.graphics_queue.device().clone()).boxed());
+
+                Err(RendererError::from(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VulkanoHeadlessRenderer;
+    use vulkano::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            ClearColorImageInfo, CommandBufferUsage,
+        },
+        format::Format,
+        image::ImageUsage,
+        sync::GpuFuture,
+    };
+
+    #[test]
+    fn image_ring() {
+        let context = context!();
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(context.device().clone(), Default::default());
+        let mut renderer = VulkanoHeadlessRenderer::new(
+            &context,
+            [32, 16],
+            Format::R8G8B8A8_UNORM,
+            ImageUsage::TRANSFER_DST | ImageUsage::TRANSFER_SRC,
+            3,
+        )
+        .unwrap();
+
+        assert_eq!(renderer.image_count(), 3);
+        assert_eq!(renderer.image_views().len(), 3);
+        assert_eq!(renderer.format(), Format::R8G8B8A8_UNORM);
+        assert_eq!(renderer.image_extent(), [32, 16]);
+
+        let mut indices = Vec::new();
+
+        // Clear each image of the ring to a different color, and read it back.
+        for frame in 0..7u8 {
+            let before_future = renderer.acquire();
+            indices.push(renderer.image_index());
+            assert_eq!(
+                renderer.image_view(),
+                &renderer.image_views()[renderer.image_index() as usize],
+            );
+
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &command_buffer_allocator,
+                renderer.graphics_queue().queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+            builder
+                .clear_color_image(ClearColorImageInfo {
+                    clear_value: [0.0, frame as f32 / 255.0, 1.0, 1.0].into(),
+                    ..ClearColorImageInfo::image(renderer.image_view().image().clone())
+                })
+                .unwrap();
+            let after_future = before_future
+                .then_execute(renderer.graphics_queue(), builder.build().unwrap())
+                .unwrap()
+                .boxed();
+            renderer.present(after_future, true).unwrap();
+
+            let pixels = context
+                .download_image(renderer.image_view().image().clone())
+                .unwrap();
+            assert_eq!(pixels.len(), 32 * 16 * 4);
+            assert!(pixels.chunks(4).all(|p| p == [0, frame, 255, 255]));
+        }
+
+        assert_eq!(indices, [0, 1, 2, 0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn zero_images() {
+        let context = context!();
+
+        assert_should_panic!({
+            let _ = VulkanoHeadlessRenderer::new(
+                &context,
+                [32, 16],
+                Format::R8G8B8A8_UNORM,
+                ImageUsage::COLOR_ATTACHMENT,
+                0,
+            );
+        });
+    }
+}
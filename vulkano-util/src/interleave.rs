@@ -0,0 +1,283 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::{mem::size_of, slice, sync::Arc};
+use vulkano::{
+    buffer::{Buffer, BufferAllocateError, BufferCreateInfo, BufferUsage, Subbuffer},
+    format::Format,
+    memory::allocator::{AllocationCreateInfo, MemoryAllocator, MemoryTypeFilter},
+    pipeline::graphics::vertex_input::{
+        VertexInputAttributeDescription, VertexInputBindingDescription, VertexInputRate,
+        VertexInputState,
+    },
+    Validated, ValidationError,
+};
+
+/// Interleaves several vertex streams into a single vertex buffer.
+///
+/// The first argument is the memory allocator to allocate the buffer with, the remaining
+/// arguments are the streams. Each stream can be any type that implements [`VertexStream`], such
+/// as a `Vec`, an array or a slice of a [`VertexAttribute`] type. This expands to a call to
+/// [`interleave`], see there for details.
+///
+/// ## Examples
+///
+/// ```no_run
+/// # use std::sync::Arc;
+/// # use vulkano::memory::allocator::StandardMemoryAllocator;
+/// use vulkano_util::interleave;
+///
+/// # let memory_allocator: Arc<StandardMemoryAllocator> = return;
+/// let positions = vec![[0.0f32, -0.5, 0.0], [0.5, 0.5, 0.0], [-0.5, 0.5, 0.0]];
+/// let normals = vec![[0.0f32, 0.0, 1.0]; 3];
+/// let uvs = vec![[0.5f32, 0.0], [1.0, 1.0], [0.0, 1.0]];
+///
+/// let (vertex_buffer, vertex_input_state) =
+///     interleave!(memory_allocator, positions, normals, uvs).unwrap();
+/// ```
+#[macro_export]
+macro_rules! interleave {
+    ($allocator:expr, $($stream:expr),+ $(,)?) => {
+        $crate::interleave::interleave(
+            $allocator,
+            &[$(&$stream as &dyn $crate::interleave::VertexStream),+],
+        )
+    };
+}
+
+/// Interleaves `streams` into a single vertex buffer, and returns it together with a
+/// [`VertexInputState`] that describes its layout.
+///
+/// All streams are placed in binding 0, one after the other in each vertex, and stream `i` is
+/// assigned to location `i`. The streams must all have the same, non-zero, length. The buffer is
+/// created with [`BufferUsage::VERTEX_BUFFER`] and in host-writable memory, preferably
+/// device-local.
+///
+/// This is the same as [`interleave_bytes`], followed by uploading the bytes to a new buffer.
+pub fn interleave(
+    allocator: Arc<dyn MemoryAllocator>,
+    streams: &[&dyn VertexStream],
+) -> Result<(Subbuffer<[u8]>, VertexInputState), Validated<BufferAllocateError>> {
+    let (bytes, vertex_input_state) = interleave_bytes(streams)?;
+    let buffer = Buffer::from_iter(
+        allocator,
+        BufferCreateInfo {
+            usage: BufferUsage::VERTEX_BUFFER,
+            ..Default::default()
+        },
+        AllocationCreateInfo {
+            memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+            ..Default::default()
+        },
+        bytes,
+    )?;
+
+    Ok((buffer, vertex_input_state))
+}
+
+/// Interleaves `streams` into a vector of bytes, and returns it together with a
+/// [`VertexInputState`] that describes its layout.
+///
+/// See [`interleave`] for how the streams are laid out.
+///
+/// ## Examples
+///
+/// ```
+/// use vulkano::format::Format;
+/// use vulkano_util::interleave::interleave_bytes;
+///
+/// let positions = [[0.0f32, 1.0], [2.0, 3.0]];
+/// let colors = [[255u8, 0, 0, 255], [0, 255, 0, 255]];
+///
+/// let (bytes, vertex_input_state) = interleave_bytes(&[&positions, &colors]).unwrap();
+///
+/// assert_eq!(bytes.len(), 2 * 12);
+/// assert_eq!(vertex_input_state.bindings[&0].stride, 12);
+/// assert_eq!(vertex_input_state.attributes[&1].format, Format::R8G8B8A8_UNORM);
+/// assert_eq!(vertex_input_state.attributes[&1].offset, 8);
+/// assert_eq!(&bytes[20..24], &[0, 255, 0, 255]);
+///
+/// // All streams must have the same length.
+/// assert!(interleave_bytes(&[&positions, &&colors[..1]]).is_err());
+/// ```
+pub fn interleave_bytes(
+    streams: &[&dyn VertexStream],
+) -> Result<(Vec<u8>, VertexInputState), Box<ValidationError>> {
+    let vertex_count = match streams.first() {
+        Some(stream) => stream.len(),
+        None => {
+            return Err(Box::new(ValidationError {
+                context: "streams".into(),
+                problem: "is empty".into(),
+                ..Default::default()
+            }));
+        }
+    };
+
+    if vertex_count == 0 {
+        return Err(Box::new(ValidationError {
+            context: "streams[0]".into(),
+            problem: "is empty".into(),
+            ..Default::default()
+        }));
+    }
+
+    for (index, stream) in streams.iter().enumerate().skip(1) {
+        if stream.len() != vertex_count {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "`streams[{}]` has a length of {}, but `streams[0]` has a length of {}",
+                    index,
+                    stream.len(),
+                    vertex_count,
+                )
+                .into(),
+                ..Default::default()
+            }));
+        }
+    }
+
+    let mut vertex_input_state = VertexInputState::new();
+    let mut stride = 0;
+
+    for (location, stream) in streams.iter().enumerate() {
+        vertex_input_state = vertex_input_state.attribute(
+            location as u32,
+            VertexInputAttributeDescription {
+                binding: 0,
+                format: stream.format(),
+                offset: stride as u32,
+            },
+        );
+        stride += stream.element_size();
+    }
+
+    vertex_input_state = vertex_input_state.binding(
+        0,
+        VertexInputBindingDescription {
+            stride: stride as u32,
+            input_rate: VertexInputRate::Vertex,
+        },
+    );
+
+    let mut bytes = Vec::with_capacity(vertex_count * stride);
+
+    for vertex_index in 0..vertex_count {
+        for stream in streams {
+            bytes.extend_from_slice(stream.element_bytes(vertex_index));
+        }
+    }
+
+    Ok((bytes, vertex_input_state))
+}
+
+/// A type that can be used as a vertex attribute by [`interleave`].
+///
+/// # Safety
+///
+/// - The type must not contain any padding, so that all of its bytes are initialized.
+/// - The type must have the same size and layout as a single element of `FORMAT`.
+/// - The size of the type must be a multiple of 4, so that all attributes stay aligned when
+///   streams are interleaved.
+pub unsafe trait VertexAttribute: Copy + 'static {
+    /// The format of the attribute in the vertex buffer.
+    const FORMAT: Format;
+}
+
+macro_rules! impl_vertex_attribute {
+    ($($ty:ty => $format:ident,)*) => {
+        $(
+            unsafe impl VertexAttribute for $ty {
+                const FORMAT: Format = Format::$format;
+            }
+        )*
+    };
+}
+
+impl_vertex_attribute! {
+    f32 => R32_SFLOAT,
+    [f32; 2] => R32G32_SFLOAT,
+    [f32; 3] => R32G32B32_SFLOAT,
+    [f32; 4] => R32G32B32A32_SFLOAT,
+    u32 => R32_UINT,
+    [u32; 2] => R32G32_UINT,
+    [u32; 3] => R32G32B32_UINT,
+    [u32; 4] => R32G32B32A32_UINT,
+    i32 => R32_SINT,
+    [i32; 2] => R32G32_SINT,
+    [i32; 3] => R32G32B32_SINT,
+    [i32; 4] => R32G32B32A32_SINT,
+    [u8; 4] => R8G8B8A8_UNORM,
+}
+
+/// A stream of vertex attributes that can be interleaved by [`interleave`].
+///
+/// This is implemented for `Vec`s, arrays and slices of any type that implements
+/// [`VertexAttribute`].
+pub trait VertexStream {
+    /// Returns the number of elements in the stream.
+    fn len(&self) -> usize;
+
+    /// Returns whether the stream has no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the format of the elements.
+    fn format(&self) -> Format;
+
+    /// Returns the size of each element in bytes.
+    fn element_size(&self) -> usize;
+
+    /// Returns the bytes of the element at `index`.
+    fn element_bytes(&self, index: usize) -> &[u8];
+}
+
+macro_rules! impl_vertex_stream {
+    ($([$($param:tt)*] $ty:ty;)*) => {
+        $(
+            impl<$($param)*> VertexStream for $ty
+            where
+                T: VertexAttribute,
+            {
+                #[inline]
+                fn len(&self) -> usize {
+                    <[T]>::len(self)
+                }
+
+                #[inline]
+                fn format(&self) -> Format {
+                    T::FORMAT
+                }
+
+                #[inline]
+                fn element_size(&self) -> usize {
+                    size_of::<T>()
+                }
+
+                #[inline]
+                fn element_bytes(&self, index: usize) -> &[u8] {
+                    element_bytes(&self[index])
+                }
+            }
+        )*
+    };
+}
+
+impl_vertex_stream! {
+    [T] Vec<T>;
+    [T, const N: usize] [T; N];
+    ['a, T] &'a [T];
+}
+
+fn element_bytes<T: VertexAttribute>(element: &T) -> &[u8] {
+    // SAFETY: `VertexAttribute` guarantees that `T` has no padding.
+    unsafe { slice::from_raw_parts((element as *const T).cast(), size_of::<T>()) }
+}
@@ -10,6 +10,17 @@
 #![allow(clippy::missing_safety_doc)]
 #![warn(rust_2018_idioms, rust_2021_compatibility)]
 
+#[macro_use]
+mod tests;
+
+pub mod bindless;
 pub mod context;
+pub mod depth_prepass;
+pub mod gather;
+pub mod headless;
+pub mod interleave;
+pub mod profiler;
 pub mod renderer;
+pub mod static_commands;
+pub mod watchdog;
 pub mod window;
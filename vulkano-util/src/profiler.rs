@@ -0,0 +1,403 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::{
+    ops::{Deref, DerefMut},
+    sync::Arc,
+};
+use vulkano::{
+    command_buffer::{
+        allocator::{CommandBufferAllocator, StandardCommandBufferAllocator},
+        AutoCommandBufferBuilder,
+    },
+    device::Device,
+    instance::debug::DebugUtilsLabel,
+    query::{QueryPool, QueryPoolCreateInfo, QueryResultFlags, QueryType},
+    sync::PipelineStage,
+    ValidationError,
+};
+
+/// Records a GPU profiler scope around a block of commands.
+///
+/// This inserts a debug label and a pair of timestamps around the commands recorded by `$body`,
+/// using [`ProfiledCommandBufferBuilder::begin_scope`] and
+/// [`ProfiledCommandBufferBuilder::end_scope`]. `$builder` must be a
+/// [`ProfiledCommandBufferBuilder`], which `$body` can use to record the commands.
+///
+/// Evaluates to `Ok` with the value of `$body`, or to `Err` if the scope could not be recorded
+/// into `$builder`.
+///
+/// ## Examples
+///
+/// ```no_run
+/// # use vulkano::{command_buffer::AutoCommandBufferBuilder, ValidationError};
+/// # use vulkano_util::{gpu_scope, profiler::GpuProfiler};
+/// # fn example<L>(
+/// #     profiler: &mut GpuProfiler,
+/// #     builder: &mut AutoCommandBufferBuilder<L>,
+/// # ) -> Result<(), Box<ValidationError>> {
+/// profiler.begin_frame(builder)?;
+/// let mut builder = profiler.record(builder);
+///
+/// gpu_scope!(builder, "fractal", {
+///     builder.dispatch([64, 64, 1])?;
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! gpu_scope {
+    ($builder:expr, $name:expr, $body:block) => {{
+        match $builder.begin_scope($name) {
+            Ok(scope) => {
+                let result = $body;
+                $builder.end_scope(scope).map(|()| result)
+            }
+            Err(err) => Err(err),
+        }
+    }};
+}
+
+/// A simple GPU profiler that measures the time spent by the GPU in named scopes.
+///
+/// Each scope is marked with a debug label, if the [`ext_debug_utils`] extension is enabled on the
+/// instance, and with a pair of timestamp queries, if the queue family supports timestamps. When
+/// timestamps are not supported, the profiler only inserts the labels and reports no timings.
+///
+/// Call [`GpuProfiler::begin_frame`] at the start of each frame, outside of a render pass, then
+/// wrap the commands to measure with [`gpu_scope!`], using the builder returned by
+/// [`GpuProfiler::record`]. The timings of the previous frame are
+/// collected at the start of the next one and can be read with [`GpuProfiler::timings`].
+///
+/// [`ext_debug_utils`]: vulkano::instance::InstanceExtensions::ext_debug_utils
+pub struct GpuProfiler {
+    query_pool: Option<Arc<QueryPool>>,
+    labels_enabled: bool,
+    timestamp_period: f32,
+    timestamp_mask: u64,
+    next_query: u32,
+    scopes: Vec<(String, Option<u32>)>,
+    timings: Vec<(String, f64)>,
+}
+
+impl GpuProfiler {
+    /// Creates a new `GpuProfiler` for command buffers of the queue family `queue_family_index`,
+    /// able to measure up to `max_scopes` scopes per frame.
+    ///
+    /// Scopes beyond `max_scopes` in a single frame are only labeled, not timed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the query pool could not be created.
+    pub fn new(device: Arc<Device>, queue_family_index: u32, max_scopes: u32) -> Self {
+        let labels_enabled = device.instance().enabled_extensions().ext_debug_utils;
+        let timestamp_period = device.physical_device().properties().timestamp_period;
+        let timestamp_valid_bits = device.physical_device().queue_family_properties()
+            [queue_family_index as usize]
+            .timestamp_valid_bits;
+
+        let query_pool = timestamp_valid_bits.filter(|_| max_scopes != 0).map(|_| {
+            QueryPool::new(
+                device.clone(),
+                QueryPoolCreateInfo {
+                    query_count: max_scopes * 2,
+                    ..QueryPoolCreateInfo::query_type(QueryType::Timestamp)
+                },
+            )
+            .expect("failed to create timestamp query pool")
+        });
+        let timestamp_mask = match timestamp_valid_bits {
+            Some(bits) if bits < 64 => (1 << bits) - 1,
+            _ => u64::MAX,
+        };
+
+        GpuProfiler {
+            query_pool,
+            labels_enabled,
+            timestamp_period,
+            timestamp_mask,
+            next_query: 0,
+            scopes: Vec::new(),
+            timings: Vec::new(),
+        }
+    }
+
+    /// Returns whether the profiler measures timings, or only inserts debug labels.
+    #[inline]
+    pub fn timestamps_supported(&self) -> bool {
+        self.query_pool.is_some()
+    }
+
+    /// Returns the GPU time in milliseconds spent in each scope of the last frame whose results
+    /// were available, in the order the scopes were recorded.
+    #[inline]
+    pub fn timings(&self) -> &[(String, f64)] {
+        &self.timings
+    }
+
+    /// Starts a new frame.
+    ///
+    /// This collects the timings of the scopes recorded since the previous call, if they are
+    /// available, and resets the queries so they can be recorded again. It must be called outside
+    /// of a render pass instance.
+    pub fn begin_frame<L, A>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+    ) -> Result<(), Box<ValidationError>>
+    where
+        A: CommandBufferAllocator,
+    {
+        self.collect_timings();
+        self.scopes.clear();
+        self.next_query = 0;
+
+        if let Some(query_pool) = &self.query_pool {
+            unsafe {
+                builder.reset_query_pool(query_pool.clone(), 0..query_pool.query_count())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Wraps `builder`, so that scopes can be recorded into it with the [`gpu_scope!`] macro.
+    #[inline]
+    pub fn record<'a, L, A>(
+        &'a mut self,
+        builder: &'a mut AutoCommandBufferBuilder<L, A>,
+    ) -> ProfiledCommandBufferBuilder<'a, L, A>
+    where
+        A: CommandBufferAllocator,
+    {
+        ProfiledCommandBufferBuilder {
+            profiler: self,
+            builder,
+        }
+    }
+
+    /// Begins a scope named `name`. The returned value must be passed to
+    /// [`GpuProfiler::end_scope`] once the commands of the scope are recorded.
+    ///
+    /// Prefer using the [`gpu_scope!`] macro, which calls both.
+    pub fn begin_scope<L, A>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+        name: &str,
+    ) -> Result<GpuProfilerScope, Box<ValidationError>>
+    where
+        A: CommandBufferAllocator,
+    {
+        if self.labels_enabled {
+            builder.begin_debug_utils_label(DebugUtilsLabel {
+                label_name: name.to_owned(),
+                ..Default::default()
+            })?;
+        }
+
+        let query = match &self.query_pool {
+            Some(query_pool) if self.next_query + 2 <= query_pool.query_count() => {
+                let query = self.next_query;
+
+                unsafe {
+                    builder.write_timestamp(query_pool.clone(), query, PipelineStage::TopOfPipe)?;
+                }
+
+                self.next_query += 2;
+                Some(query)
+            }
+            _ => None,
+        };
+
+        self.scopes.push((name.to_owned(), query));
+
+        Ok(GpuProfilerScope { query })
+    }
+
+    /// Ends a scope begun with [`GpuProfiler::begin_scope`].
+    pub fn end_scope<L, A>(
+        &mut self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+        scope: GpuProfilerScope,
+    ) -> Result<(), Box<ValidationError>>
+    where
+        A: CommandBufferAllocator,
+    {
+        if let (Some(query_pool), Some(query)) = (&self.query_pool, scope.query) {
+            unsafe {
+                builder.write_timestamp(
+                    query_pool.clone(),
+                    query + 1,
+                    PipelineStage::BottomOfPipe,
+                )?;
+            }
+        }
+
+        if self.labels_enabled {
+            unsafe {
+                builder.end_debug_utils_label()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads back the timestamps of the recorded scopes, leaving the previous timings untouched if
+    /// the results are not available yet.
+    fn collect_timings(&mut self) {
+        let query_pool = match &self.query_pool {
+            Some(query_pool) if self.next_query != 0 => query_pool,
+            _ => return,
+        };
+
+        // Each query is followed by its availability.
+        let mut results = vec![0u64; self.next_query as usize * 2];
+        let all_available = query_pool
+            .get_results(
+                0..self.next_query,
+                &mut results,
+                QueryResultFlags::WITH_AVAILABILITY,
+            )
+            .unwrap_or(false);
+
+        if !all_available {
+            return;
+        }
+
+        let ns_to_ms = self.timestamp_period as f64 / 1_000_000.0;
+        self.timings = self
+            .scopes
+            .iter()
+            .filter_map(|(name, query)| {
+                let query = (*query)? as usize;
+                let begin = results[query * 2];
+                let end = results[(query + 1) * 2];
+                let ticks = end.wrapping_sub(begin) & self.timestamp_mask;
+
+                Some((name.clone(), ticks as f64 * ns_to_ms))
+            })
+            .collect();
+    }
+}
+
+/// A scope begun with [`GpuProfiler::begin_scope`].
+#[derive(Debug)]
+#[must_use = "the scope must be ended with `GpuProfiler::end_scope`"]
+pub struct GpuProfilerScope {
+    query: Option<u32>,
+}
+
+/// A command buffer builder that records scopes into a [`GpuProfiler`], returned by
+/// [`GpuProfiler::record`].
+///
+/// It dereferences to the wrapped [`AutoCommandBufferBuilder`], so that commands can be recorded
+/// into it directly.
+pub struct ProfiledCommandBufferBuilder<'a, L, A = StandardCommandBufferAllocator>
+where
+    A: CommandBufferAllocator,
+{
+    profiler: &'a mut GpuProfiler,
+    builder: &'a mut AutoCommandBufferBuilder<L, A>,
+}
+
+impl<'a, L, A> ProfiledCommandBufferBuilder<'a, L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Begins a scope named `name`, as with [`GpuProfiler::begin_scope`].
+    #[inline]
+    pub fn begin_scope(&mut self, name: &str) -> Result<GpuProfilerScope, Box<ValidationError>> {
+        self.profiler.begin_scope(self.builder, name)
+    }
+
+    /// Ends a scope, as with [`GpuProfiler::end_scope`].
+    #[inline]
+    pub fn end_scope(&mut self, scope: GpuProfilerScope) -> Result<(), Box<ValidationError>> {
+        self.profiler.end_scope(self.builder, scope)
+    }
+}
+
+impl<'a, L, A> Deref for ProfiledCommandBufferBuilder<'a, L, A>
+where
+    A: CommandBufferAllocator,
+{
+    type Target = AutoCommandBufferBuilder<L, A>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.builder
+    }
+}
+
+impl<'a, L, A> DerefMut for ProfiledCommandBufferBuilder<'a, L, A>
+where
+    A: CommandBufferAllocator,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GpuProfiler;
+    use vulkano::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            CommandBufferUsage, PrimaryCommandBufferAbstract,
+        },
+        sync::GpuFuture,
+    };
+
+    #[test]
+    fn scopes() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut profiler = GpuProfiler::new(device, queue.queue_family_index(), 2);
+
+        for _ in 0..2 {
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &command_buffer_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+            profiler.begin_frame(&mut builder).unwrap();
+
+            let mut profiled_builder = profiler.record(&mut builder);
+            assert_eq!(gpu_scope!(profiled_builder, "first", { 1 }).unwrap(), 1);
+            gpu_scope!(profiled_builder, "second", {}).unwrap();
+            // Scopes beyond `max_scopes` are only labeled.
+            gpu_scope!(profiled_builder, "third", {}).unwrap();
+
+            builder
+                .build()
+                .unwrap()
+                .execute(queue.clone())
+                .unwrap()
+                .then_signal_fence_and_flush()
+                .unwrap()
+                .wait(None)
+                .unwrap();
+        }
+
+        let names: Vec<_> = profiler
+            .timings()
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect();
+
+        if profiler.timestamps_supported() {
+            assert_eq!(names, ["first", "second"]);
+        } else {
+            assert!(names.is_empty());
+        }
+    }
+}
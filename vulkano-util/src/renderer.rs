@@ -9,13 +9,24 @@
 
 use crate::{context::VulkanoContext, window::WindowDescriptor};
 use ahash::HashMap;
-use std::sync::Arc;
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::{Display, Error as FmtError, Formatter},
+    mem,
+    num::NonZeroU64,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use vulkano::{
     device::{Device, Queue},
-    format::Format,
-    image::{view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage},
+    format::{Format, NumericFormat},
+    image::{view::ImageView, Image, ImageAllocateError, ImageCreateInfo, ImageType, ImageUsage},
     memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
-    swapchain::{self, PresentMode, Surface, Swapchain, SwapchainCreateInfo, SwapchainPresentInfo},
+    swapchain::{
+        self, ColorSpace, PresentMode, Surface, Swapchain, SwapchainAcquireFuture,
+        SwapchainCreateInfo, SwapchainPresentInfo,
+    },
     sync::{self, GpuFuture},
     Validated, VulkanError,
 };
@@ -37,20 +48,31 @@ pub struct VulkanoWindowRenderer {
     compute_queue: Arc<Queue>,
     swapchain: Arc<Swapchain>,
     final_views: Vec<Arc<ImageView>>,
+    /// The extent that the surface required when the swapchain was last created, if any.
+    mandated_extent: Option<[u32; 2]>,
     memory_allocator: Arc<StandardMemoryAllocator>,
     /// Additional image views that you can add which are resized with the window.
     /// Use associated functions to get access to these.
     additional_image_views: HashMap<usize, Arc<ImageView>>,
     recreate_swapchain: bool,
+    /// Whether the swapchain was recreated during the last call to `acquire`.
+    swapchain_recreated: bool,
     previous_frame_end: Option<Box<dyn GpuFuture>>,
     image_index: u32,
+    acquired_suboptimal: bool,
     present_mode: vulkano::swapchain::PresentMode,
+    swapchain_error_mode: SwapchainErrorMode,
+    present_tracker: PresentTracker,
 }
 
 impl VulkanoWindowRenderer {
     /// Creates a new [`VulkanoWindowRenderer`] which is used to orchestrate your rendering with
     /// Vulkano. Pass [`WindowDescriptor`] and optionally a function modifying the
     /// [`SwapchainCreateInfo`](vulkano::swapchain::SwapchainCreateInfo) parameters.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the graphics queue of `vulkano_context` can't present to the window.
     pub fn new(
         vulkano_context: &VulkanoContext,
         window: winit::window::Window,
@@ -60,8 +82,9 @@ impl VulkanoWindowRenderer {
         let window = Arc::new(window);
 
         // Create swap chain & frame(s) to which we'll render
-        let (swap_chain, final_views) = Self::create_swapchain(
+        let (swap_chain, final_views, mandated_extent) = Self::create_swapchain(
             vulkano_context.device().clone(),
+            vulkano_context.graphics_queue().queue_family_index(),
             &window,
             descriptor,
             swapchain_create_info_modify,
@@ -75,12 +98,17 @@ impl VulkanoWindowRenderer {
             compute_queue: vulkano_context.compute_queue().clone(),
             swapchain: swap_chain,
             final_views,
+            mandated_extent,
             memory_allocator: vulkano_context.memory_allocator().clone(),
             additional_image_views: HashMap::default(),
             recreate_swapchain: false,
+            swapchain_recreated: false,
             previous_frame_end,
             image_index: 0,
+            acquired_suboptimal: false,
             present_mode: descriptor.present_mode,
+            swapchain_error_mode: SwapchainErrorMode::default(),
+            present_tracker: PresentTracker::default(),
         }
     }
 
@@ -88,25 +116,49 @@ impl VulkanoWindowRenderer {
     /// can be modified with the `swapchain_create_info_modify` function passed as an input.
     fn create_swapchain(
         device: Arc<Device>,
+        queue_family_index: u32,
         window: &Arc<Window>,
         window_descriptor: &WindowDescriptor,
         swapchain_create_info_modify: fn(&mut SwapchainCreateInfo),
-    ) -> (Arc<Swapchain>, Vec<Arc<ImageView>>) {
+    ) -> (Arc<Swapchain>, Vec<Arc<ImageView>>, Option<[u32; 2]>) {
         let surface = Surface::from_window(device.instance().clone(), window.clone()).unwrap();
+        assert!(
+            device
+                .physical_device()
+                .surface_support(queue_family_index, &surface)
+                .unwrap(),
+            "the graphics queue of {} can't present to the window's surface; select another \
+            physical device with `VulkanoConfig::physical_device_id` or \
+            `VulkanoConfig::device_filter_fn`",
+            device.physical_device().properties().device_name,
+        );
         let surface_capabilities = device
             .physical_device()
             .surface_capabilities(&surface, Default::default())
             .unwrap();
-        let image_format = device
-            .physical_device()
-            .surface_formats(&surface, Default::default())
-            .unwrap()[0]
-            .0;
+        let (image_format, image_color_space) = select_swapchain_format(
+            &device
+                .physical_device()
+                .surface_formats(&surface, Default::default())
+                .unwrap(),
+            window_descriptor.srgb_swapchain,
+        );
+        // Fall back to another present mode if the requested one is not supported
+        let present_mode = select_present_mode(
+            window_descriptor.present_mode,
+            device
+                .physical_device()
+                .surface_present_modes(&surface)
+                .unwrap(),
+        );
         let (swapchain, images) = Swapchain::new(device, surface, {
             let mut create_info = SwapchainCreateInfo {
                 min_image_count: surface_capabilities.min_image_count.max(2),
                 image_format,
-                image_extent: window.inner_size().into(),
+                image_color_space,
+                image_extent: surface_capabilities
+                    .current_extent
+                    .unwrap_or_else(|| window.inner_size().into()),
                 image_usage: ImageUsage::COLOR_ATTACHMENT,
                 composite_alpha: surface_capabilities
                     .supported_composite_alpha
@@ -116,7 +168,7 @@ impl VulkanoWindowRenderer {
                 ..Default::default()
             };
             // Get present mode from window descriptor
-            create_info.present_mode = window_descriptor.present_mode;
+            create_info.present_mode = present_mode;
             swapchain_create_info_modify(&mut create_info);
             create_info
         })
@@ -126,10 +178,13 @@ impl VulkanoWindowRenderer {
             .map(|image| ImageView::new_default(image).unwrap())
             .collect::<Vec<_>>();
 
-        (swapchain, images)
+        (swapchain, images, surface_capabilities.current_extent)
     }
 
     /// Set window renderer present mode. This triggers a swapchain recreation.
+    ///
+    /// If the surface doesn't support `present_mode`, a fallback is chosen with
+    /// [`select_present_mode`].
     #[inline]
     pub fn set_present_mode(&mut self, present_mode: PresentMode) {
         if self.present_mode != present_mode {
@@ -138,18 +193,109 @@ impl VulkanoWindowRenderer {
         }
     }
 
+    /// Returns the present mode that the swapchain currently uses.
+    ///
+    /// This can differ from the requested present mode if the surface doesn't support it.
+    #[inline]
+    pub fn present_mode(&self) -> PresentMode {
+        self.swapchain.present_mode()
+    }
+
+    /// Sets how swapchain errors that can be recovered from are handled by
+    /// [`acquire`](Self::acquire) and [`present`](Self::present).
+    #[inline]
+    pub fn set_swapchain_error_mode(&mut self, swapchain_error_mode: SwapchainErrorMode) {
+        self.swapchain_error_mode = swapchain_error_mode;
+    }
+
+    /// Returns how swapchain errors that can be recovered from are handled.
+    #[inline]
+    pub fn swapchain_error_mode(&self) -> SwapchainErrorMode {
+        self.swapchain_error_mode
+    }
+
     /// Return swapchain image format.
     #[inline]
     pub fn swapchain_format(&self) -> Format {
         self.final_views[self.image_index as usize].format()
     }
 
-    /// Returns the index of last swapchain image that is the next render target.
+    /// Returns whether the swapchain images have an sRGB format.
+    ///
+    /// If this is `true`, the values that shaders write to the swapchain images are treated as
+    /// linear, and are converted to sRGB by the hardware. If this is `false`, the values are
+    /// stored as they are, so shaders must apply the gamma correction themselves to get the same
+    /// result. This is chosen with [`WindowDescriptor::srgb_swapchain`].
+    #[inline]
+    pub fn swapchain_is_srgb(&self) -> bool {
+        self.swapchain_format().numeric_format_color() == Some(NumericFormat::SRGB)
+    }
+
+    /// Returns the index of the swapchain image that was acquired by the last call to
+    /// [`acquire`](Self::acquire).
+    ///
+    /// The index is updated by every successful `acquire`, and stays valid until the matching
+    /// [`present`](Self::present). It is always less than
+    /// [`swapchain_image_count`](Self::swapchain_image_count), so it can be used to pick
+    /// per-image resources such as uniform buffers or descriptor sets, without keeping a separate
+    /// frame counter. Images are not necessarily acquired in order, so don't rely on the index
+    /// increasing by one each frame.
+    ///
+    /// ```no_run
+    /// # use vulkano_util::renderer::VulkanoWindowRenderer;
+    /// # let renderer: &mut VulkanoWindowRenderer = return;
+    /// let per_image_data = vec![0u32; renderer.swapchain_image_count() as usize];
+    ///
+    /// let before_future = renderer.acquire().unwrap();
+    /// let data = &per_image_data[renderer.image_index() as usize];
+    /// // Record and execute command buffers that use `data`...
+    /// renderer.present(before_future, false).unwrap();
+    /// ```
     #[inline]
     pub fn image_index(&self) -> u32 {
         self.image_index
     }
 
+    /// Returns the number of images in the swapchain.
+    ///
+    /// This can change when the swapchain is recreated, for example after a resize or a change
+    /// of present mode.
+    #[inline]
+    pub fn swapchain_image_count(&self) -> u32 {
+        self.final_views.len() as u32
+    }
+
+    /// Returns whether the swapchain was recreated during the last call to
+    /// [`acquire`](Self::acquire).
+    ///
+    /// When this returns `true`, the swapchain image views and the additional image views have
+    /// been replaced, so any resources that refer to them, such as framebuffers or descriptor
+    /// sets, must be rebuilt before rendering. The new [`swapchain_image_count`] and
+    /// [`swapchain_format`] can be queried at that point.
+    ///
+    /// The flag is reset by the next call to `acquire`, so it is `true` for exactly one frame
+    /// after each recreation.
+    ///
+    /// ```no_run
+    /// # use vulkano_util::renderer::VulkanoWindowRenderer;
+    /// # let renderer: &mut VulkanoWindowRenderer = return;
+    /// let before_future = renderer.acquire().unwrap();
+    ///
+    /// if renderer.swapchain_recreated() {
+    ///     // Rebuild framebuffers for the new swapchain images.
+    ///     let _count = renderer.swapchain_image_count();
+    ///     let _format = renderer.swapchain_format();
+    /// }
+    /// # renderer.present(before_future, false).unwrap();
+    /// ```
+    ///
+    /// [`swapchain_image_count`]: Self::swapchain_image_count
+    /// [`swapchain_format`]: Self::swapchain_format
+    #[inline]
+    pub fn swapchain_recreated(&self) -> bool {
+        self.swapchain_recreated
+    }
+
     /// Graphics queue of this window. You also can access this through [`VulkanoContext`].
     #[inline]
     pub fn graphics_queue(&self) -> Arc<Queue> {
@@ -189,12 +335,46 @@ impl VulkanoWindowRenderer {
             .unwrap()
     }
 
+    /// Returns the extent that the surface requires the swapchain images to have, if any.
+    ///
+    /// On some platforms the surface dictates the size of the swapchain images, and the size of
+    /// the window is ignored. In that case the swapchain is always created with this extent, which
+    /// may differ from [`window_size`](Self::window_size). Returns `None` if the swapchain image
+    /// size follows the window size.
+    ///
+    /// The extent is queried from the surface whenever the swapchain is created or recreated.
+    #[inline]
+    pub fn mandated_extent(&self) -> Option<[u32; 2]> {
+        self.mandated_extent
+    }
+
+    fn surface_current_extent(&self) -> Result<Option<[u32; 2]>, Validated<VulkanError>> {
+        Ok(self
+            .graphics_queue
+            .device()
+            .physical_device()
+            .surface_capabilities(self.swapchain.surface(), Default::default())?
+            .current_extent)
+    }
+
     /// Return the current swapchain image view.
     #[inline]
     pub fn swapchain_image_view(&self) -> Arc<ImageView> {
         self.final_views[self.image_index as usize].clone()
     }
 
+    /// Returns the image views of all the swapchain images, in the order of their
+    /// [`image_index`](Self::image_index).
+    ///
+    /// The number of views is equal to [`swapchain_image_count`](Self::swapchain_image_count).
+    /// This is useful to create per-image resources such as framebuffers up front. The views are
+    /// replaced when the swapchain is recreated, see
+    /// [`swapchain_recreated`](Self::swapchain_recreated).
+    #[inline]
+    pub fn swapchain_image_views(&self) -> &[Arc<ImageView>] {
+        &self.final_views
+    }
+
     /// Return scale factor accounted window size.
     #[inline]
     pub fn resolution(&self) -> [f32; 2] {
@@ -213,32 +393,49 @@ impl VulkanoWindowRenderer {
     }
 
     /// Resize swapchain and camera view images at the beginning of next frame based on window
-    /// size.
+    /// size. Errors that happen while recreating the swapchain are returned by the next call to
+    /// [`acquire`](Self::acquire).
     #[inline]
     pub fn resize(&mut self) {
         self.recreate_swapchain = true;
     }
 
     /// Add interim image view that resizes with window.
+    ///
+    /// Returns an error if the image or its view could not be created.
     #[inline]
-    pub fn add_additional_image_view(&mut self, key: usize, format: Format, usage: ImageUsage) {
+    pub fn add_additional_image_view(
+        &mut self,
+        key: usize,
+        format: Format,
+        usage: ImageUsage,
+    ) -> Result<(), RendererError> {
+        let image = self.create_additional_image_view(format, usage)?;
+        self.additional_image_views.insert(key, image);
+
+        Ok(())
+    }
+
+    fn create_additional_image_view(
+        &self,
+        format: Format,
+        usage: ImageUsage,
+    ) -> Result<Arc<ImageView>, RendererError> {
         let final_view_image = self.final_views[0].image();
-        let image = ImageView::new_default(
-            Image::new(
-                self.memory_allocator.clone(),
-                ImageCreateInfo {
-                    image_type: ImageType::Dim2d,
-                    format,
-                    extent: final_view_image.extent(),
-                    usage,
-                    ..Default::default()
-                },
-                AllocationCreateInfo::default(),
-            )
-            .unwrap(),
+        let image = Image::new(
+            self.memory_allocator.clone(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format,
+                extent: final_view_image.extent(),
+                usage,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
         )
-        .unwrap();
-        self.additional_image_views.insert(key, image);
+        .map_err(RendererError::AllocateImage)?;
+
+        Ok(ImageView::new_default(image)?)
     }
 
     /// Get additional image view by key.
@@ -258,121 +455,670 @@ impl VulkanoWindowRenderer {
     /// swapchain image has been acquired and previous frame ended.
     /// Execute your command buffers after calling this function and finish rendering by calling
     /// [`VulkanoWindowRenderer::present`].
+    ///
+    /// If the swapchain is out of date, it is marked for recreation and
+    /// [`RendererError::OutOfDate`] is returned, in which case the frame should be skipped. With
+    /// [`SwapchainErrorMode::Recover`], the swapchain is first recreated and the image acquired
+    /// again, so this only happens if the swapchain can't be recreated, for example while the
+    /// window is minimized.
+    ///
+    /// Check [`swapchain_recreated`](Self::swapchain_recreated) afterwards to find out whether
+    /// resources that depend on the swapchain images must be rebuilt.
     #[inline]
-    pub fn acquire(&mut self) -> Result<Box<dyn GpuFuture>, VulkanError> {
+    pub fn acquire(&mut self) -> Result<Box<dyn GpuFuture>, RendererError> {
+        self.swapchain_recreated = false;
+
         // Recreate swap chain if needed (when resizing of window occurs or swapchain is outdated)
         // Also resize render views if needed
         if self.recreate_swapchain {
-            self.recreate_swapchain_and_views();
+            self.recreate_swapchain_and_views()?;
+        }
+
+        if self.present_tracking_supported() {
+            self.poll_presents();
         }
 
         // Acquire next image in the swapchain
-        let (image_index, suboptimal, acquire_future) =
-            match swapchain::acquire_next_image(self.swapchain.clone(), None)
-                .map_err(Validated::unwrap)
+        let result = match self.acquire_next_image() {
+            Err(RendererError::OutOfDate)
+                if self.swapchain_error_mode == SwapchainErrorMode::Recover =>
             {
-                Ok(r) => r,
-                Err(VulkanError::OutOfDate) => {
-                    self.recreate_swapchain = true;
-                    return Err(VulkanError::OutOfDate);
-                }
-                Err(e) => panic!("failed to acquire next image: {e}"),
-            };
+                self.recreate_swapchain_and_views()?;
+                self.acquire_next_image()
+            }
+            result => result,
+        };
+        let (image_index, suboptimal, acquire_future) = result?;
+
         if suboptimal {
             self.recreate_swapchain = true;
         }
+        self.acquired_suboptimal = suboptimal;
         // Update our image index
         self.image_index = image_index;
 
-        let future = self.previous_frame_end.take().unwrap().join(acquire_future);
+        let previous_frame_end = self
+            .previous_frame_end
+            .take()
+            .unwrap_or_else(|| sync::now(self.graphics_queue.device().clone()).boxed());
+        let future = previous_frame_end.join(acquire_future);
 
         Ok(future.boxed())
     }
 
+    fn acquire_next_image(&mut self) -> Result<(u32, bool, SwapchainAcquireFuture), RendererError> {
+        swapchain::acquire_next_image(self.swapchain.clone(), None).map_err(|err| {
+            let err = RendererError::from(err);
+
+            if let RendererError::OutOfDate = err {
+                self.recreate_swapchain = true;
+            }
+
+            err
+        })
+    }
+
     /// Finishes rendering by presenting the swapchain. Pass your last future as an input to this
     /// function.
     ///
     /// Depending on your implementation, you may want to wait on your future. For example, a
     /// compute shader dispatch using an image that's being later drawn should probably be waited
     /// on.
+    ///
+    /// If `wait_future` is `false`, this returns without blocking, so that several frames can be
+    /// in flight at once. The work of each frame still starts only after the previous frame's
+    /// work, because [`acquire`](Self::acquire) joins the future of the previous frame. However,
+    /// the host may record the next frame while the device is still executing earlier ones, so
+    /// resources that are written by the host every frame, such as uniform buffers, should be
+    /// duplicated per swapchain image and picked with [`image_index`](Self::image_index).
+    /// Writing to a buffer that is still in use by the device returns an error rather than
+    /// corrupting a frame in flight.
+    ///
+    /// If the swapchain is out of date or suboptimal, it is recreated at the next call to
+    /// [`acquire`](Self::acquire). With [`SwapchainErrorMode::Return`], this is also reported by
+    /// returning [`RendererError::OutOfDate`] or [`RendererError::Suboptimal`]. In the latter
+    /// case, the image has still been presented.
     #[inline]
-    pub fn present(&mut self, after_future: Box<dyn GpuFuture>, wait_future: bool) {
+    pub fn present(
+        &mut self,
+        after_future: Box<dyn GpuFuture>,
+        wait_future: bool,
+    ) -> Result<(), RendererError> {
+        let suboptimal = mem::take(&mut self.acquired_suboptimal);
+        let present_id = self.take_present_id();
         let future = after_future
             .then_swapchain_present(
                 self.graphics_queue.clone(),
-                SwapchainPresentInfo::swapchain_image_index(
-                    self.swapchain.clone(),
-                    self.image_index,
-                ),
+                SwapchainPresentInfo {
+                    present_id,
+                    ..SwapchainPresentInfo::swapchain_image_index(
+                        self.swapchain.clone(),
+                        self.image_index,
+                    )
+                },
             )
             .then_signal_fence_and_flush();
-        match future.map_err(Validated::unwrap) {
+        let result = match future {
             Ok(mut future) => {
-                if wait_future {
-                    match future.wait(None) {
-                        Ok(x) => x,
-                        Err(e) => println!("{e}"),
-                    }
+                if let Some(present_id) = present_id {
+                    self.present_tracker.submitted(present_id);
+                }
+
+                let result = if wait_future {
                     // wait allows you to organize resource waiting yourself.
+                    future.wait(None)
                 } else {
                     future.cleanup_finished();
-                }
+
+                    Ok(())
+                };
 
                 self.previous_frame_end = Some(future.boxed());
+
+                result.map_err(RendererError::from)
             }
-            Err(VulkanError::OutOfDate) => {
-                self.recreate_swapchain = true;
+            Err(err) => {
                 self.previous_frame_end =
                     Some(sync::now(self.graphics_queue.device().clone()).boxed());
+
+                Err(RendererError::from(err))
             }
-            Err(e) => {
-                println!("failed to flush future: {e}");
-                self.previous_frame_end =
-                    Some(sync::now(self.graphics_queue.device().clone()).boxed());
+        };
+
+        match result {
+            Err(RendererError::OutOfDate) => {
+                self.recreate_swapchain = true;
+
+                match self.swapchain_error_mode {
+                    SwapchainErrorMode::Recover => Ok(()),
+                    SwapchainErrorMode::Return => Err(RendererError::OutOfDate),
+                }
+            }
+            Ok(()) if suboptimal && self.swapchain_error_mode == SwapchainErrorMode::Return => {
+                Err(RendererError::Suboptimal)
             }
+            result => result,
         }
     }
 
+    /// Returns how many presents have been submitted, but have not yet been observed to be
+    /// completed, that is, how many frames are queued ahead of the one currently being displayed.
+    ///
+    /// This polls for completed presents without blocking, and updates
+    /// [`present_latency`](Self::present_latency) accordingly.
+    ///
+    /// Returns `None` if the [`present_id`] and [`present_wait`] features are not both enabled
+    /// on the device, in which case presents can't be tracked.
+    ///
+    /// [`present_id`]: vulkano::device::Features::present_id
+    /// [`present_wait`]: vulkano::device::Features::present_wait
+    pub fn frames_in_flight(&mut self) -> Option<usize> {
+        if !self.present_tracking_supported() {
+            return None;
+        }
+
+        self.poll_presents();
+
+        Some(self.present_tracker.frames_in_flight())
+    }
+
+    /// Returns the time between the most recent completed present being submitted with
+    /// [`present`](Self::present), and its completion being observed.
+    ///
+    /// Completion is observed by [`frames_in_flight`](Self::frames_in_flight) and
+    /// [`acquire`](Self::acquire), so the estimate is only as precise as the rate at which those
+    /// are called.
+    ///
+    /// Returns `None` if no present has been observed to be completed yet, or if presents can't
+    /// be tracked on the device.
+    #[inline]
+    pub fn present_latency(&self) -> Option<Duration> {
+        self.present_tracker.latency()
+    }
+
+    fn present_tracking_supported(&self) -> bool {
+        let features = self.graphics_queue.device().enabled_features();
+
+        features.present_id && features.present_wait
+    }
+
+    /// Returns the id to use for the next present, or `None` if presents aren't tracked.
+    fn take_present_id(&mut self) -> Option<NonZeroU64> {
+        if !self.present_tracking_supported() {
+            return None;
+        }
+
+        Some(self.present_tracker.next_present_id())
+    }
+
+    /// Removes the presents that have completed from the tracked presents, without blocking.
+    fn poll_presents(&mut self) {
+        let swapchain = &self.swapchain;
+
+        self.present_tracker.poll(|present_id| {
+            swapchain::wait_for_present(swapchain.clone(), present_id, Some(Duration::ZERO))
+        });
+    }
+
     /// Recreates swapchain images and image views which follow the window size.
-    fn recreate_swapchain_and_views(&mut self) {
-        let image_extent: [u32; 2] = self.window().inner_size().into();
+    fn recreate_swapchain_and_views(&mut self) -> Result<(), RendererError> {
+        self.mandated_extent = self.surface_current_extent()?;
+        let image_extent = self
+            .mandated_extent
+            .unwrap_or_else(|| self.window().inner_size().into());
 
         if image_extent.contains(&0) {
-            return;
+            return Ok(());
         }
 
-        let (new_swapchain, new_images) = self
-            .swapchain
-            .recreate(SwapchainCreateInfo {
-                image_extent,
-                // Use present mode from current state
-                present_mode: self.present_mode,
-                ..self.swapchain.create_info()
-            })
-            .expect("failed to recreate swapchain");
+        let present_mode = select_present_mode(
+            self.present_mode,
+            self.graphics_queue
+                .device()
+                .physical_device()
+                .surface_present_modes(self.swapchain.surface())?,
+        );
+
+        let (new_swapchain, new_images) = self.swapchain.recreate(SwapchainCreateInfo {
+            image_extent,
+            // Use present mode from current state
+            present_mode,
+            ..self.swapchain.create_info()
+        })?;
 
         self.swapchain = new_swapchain;
-        let new_images = new_images
+        // Presents to the old swapchain can't be waited on anymore.
+        self.present_tracker.clear();
+        self.final_views = new_images
             .into_iter()
-            .map(|image| ImageView::new_default(image).unwrap())
-            .collect::<Vec<_>>();
-        self.final_views = new_images;
+            .map(ImageView::new_default)
+            .collect::<Result<Vec<_>, _>>()?;
         // Resize images that follow swapchain size
         let resizable_views = self
             .additional_image_views
             .iter()
-            .map(|c| *c.0)
-            .collect::<Vec<usize>>();
-        for i in resizable_views {
-            let format = self.get_additional_image_view(i).format();
-            let usage = self.get_additional_image_view(i).usage();
-            self.remove_additional_image_view(i);
-            self.add_additional_image_view(i, format, usage);
+            .map(|(&key, view)| (key, view.format(), view.usage()))
+            .collect::<Vec<_>>();
+        for (key, format, usage) in resizable_views {
+            let image = self.create_additional_image_view(format, usage)?;
+            self.additional_image_views.insert(key, image);
         }
         #[cfg(target_os = "ios")]
         unsafe {
             self.surface.update_ios_sublayer_on_resize();
         }
         self.recreate_swapchain = false;
+        self.swapchain_recreated = true;
+
+        Ok(())
+    }
+}
+
+/// Keeps track of the presents that were submitted with a present id.
+#[derive(Debug)]
+struct PresentTracker {
+    /// The id to use for the next present.
+    next_present_id: u64,
+    /// Presents that have been submitted but not yet observed to be completed, with the time at
+    /// which they were submitted.
+    pending_presents: VecDeque<(u64, Instant)>,
+    latency: Option<Duration>,
+}
+
+impl Default for PresentTracker {
+    fn default() -> Self {
+        PresentTracker {
+            next_present_id: 1,
+            pending_presents: VecDeque::new(),
+            latency: None,
+        }
+    }
+}
+
+impl PresentTracker {
+    fn next_present_id(&mut self) -> NonZeroU64 {
+        let present_id = NonZeroU64::new(self.next_present_id).unwrap();
+        self.next_present_id += 1;
+
+        present_id
+    }
+
+    fn submitted(&mut self, present_id: NonZeroU64) {
+        self.pending_presents
+            .push_back((present_id.get(), Instant::now()));
+    }
+
+    fn frames_in_flight(&self) -> usize {
+        self.pending_presents.len()
+    }
+
+    fn latency(&self) -> Option<Duration> {
+        self.latency
+    }
+
+    /// Removes the presents that `wait` reports as completed, in submission order, stopping at
+    /// the first one that isn't.
+    fn poll(&mut self, mut wait: impl FnMut(u64) -> Result<bool, Validated<VulkanError>>) {
+        while let Some(&(present_id, submitted)) = self.pending_presents.front() {
+            match wait(present_id) {
+                Ok(_) => {
+                    self.latency = Some(submitted.elapsed());
+                    self.pending_presents.pop_front();
+                }
+                // The present can no longer be waited on, because the swapchain was retired.
+                Err(Validated::Error(VulkanError::OutOfDate)) => {
+                    self.pending_presents.pop_front();
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.pending_presents.clear();
+    }
+}
+
+/// Chooses a swapchain image format out of the `supported` surface formats.
+///
+/// Formats in the [`ColorSpace::SrgbNonLinear`] color space are preferred. If `prefer_srgb` is
+/// `true`, a format with an sRGB numeric format is chosen if possible, so that the hardware
+/// converts the written values from linear to sRGB. Otherwise, a UNORM format is chosen if
+/// possible, and the values are stored as they are. If no such format is supported, the first
+/// supported format is returned.
+///
+/// # Panics
+///
+/// - Panics if `supported` is empty.
+///
+/// ```
+/// use vulkano::{format::Format, swapchain::ColorSpace};
+/// use vulkano_util::renderer::select_swapchain_format;
+///
+/// let supported = [
+///     (Format::B8G8R8A8_UNORM, ColorSpace::SrgbNonLinear),
+///     (Format::B8G8R8A8_SRGB, ColorSpace::SrgbNonLinear),
+/// ];
+///
+/// assert_eq!(select_swapchain_format(&supported, true).0, Format::B8G8R8A8_SRGB);
+/// assert_eq!(select_swapchain_format(&supported, false).0, Format::B8G8R8A8_UNORM);
+/// ```
+pub fn select_swapchain_format(
+    supported: &[(Format, ColorSpace)],
+    prefer_srgb: bool,
+) -> (Format, ColorSpace) {
+    let preferred_numeric_format = if prefer_srgb {
+        NumericFormat::SRGB
+    } else {
+        NumericFormat::UNORM
+    };
+
+    supported
+        .iter()
+        .copied()
+        .find(|&(format, color_space)| {
+            color_space == ColorSpace::SrgbNonLinear
+                && format.numeric_format_color() == Some(preferred_numeric_format)
+        })
+        .unwrap_or(supported[0])
+}
+
+/// Returns `requested` if it is one of the `supported` present modes, or a fallback otherwise.
+///
+/// [`PresentMode::Mailbox`] and [`PresentMode::Immediate`] fall back to each other, so that a
+/// request to disable vsync is honored if possible. Any other mode falls back to
+/// [`PresentMode::Fifo`], which every surface supports.
+///
+/// ```
+/// use vulkano::swapchain::PresentMode;
+/// use vulkano_util::renderer::select_present_mode;
+///
+/// let supported = [PresentMode::Fifo, PresentMode::Immediate];
+///
+/// assert_eq!(select_present_mode(PresentMode::Immediate, supported), PresentMode::Immediate);
+/// assert_eq!(select_present_mode(PresentMode::Mailbox, supported), PresentMode::Immediate);
+/// assert_eq!(select_present_mode(PresentMode::FifoRelaxed, supported), PresentMode::Fifo);
+/// ```
+pub fn select_present_mode(
+    requested: PresentMode,
+    supported: impl IntoIterator<Item = PresentMode>,
+) -> PresentMode {
+    let supported: Vec<_> = supported.into_iter().collect();
+
+    let fallbacks: &[PresentMode] = match requested {
+        PresentMode::Mailbox => &[PresentMode::Immediate],
+        PresentMode::Immediate => &[PresentMode::Mailbox],
+        _ => &[],
+    };
+
+    [requested]
+        .iter()
+        .chain(fallbacks)
+        .copied()
+        .find(|present_mode| supported.contains(present_mode))
+        .unwrap_or(PresentMode::Fifo)
+}
+
+/// How [`VulkanoWindowRenderer`] handles swapchain errors that can be recovered from by
+/// recreating the swapchain.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SwapchainErrorMode {
+    /// The swapchain is recreated as needed, and errors are only returned when a frame can't be
+    /// rendered at all.
+    #[default]
+    Recover,
+
+    /// The swapchain is still recreated as needed, but every out-of-date or suboptimal swapchain
+    /// is also reported to the caller, so that it can react to it.
+    Return,
+}
+
+/// Error that can happen when rendering with [`VulkanoWindowRenderer`] or
+/// [`VulkanoHeadlessRenderer`](crate::headless::VulkanoHeadlessRenderer).
+#[derive(Clone, Debug)]
+pub enum RendererError {
+    /// The swapchain is out of date, and will be recreated. The frame should be skipped.
+    OutOfDate,
+
+    /// The image was presented, but the swapchain no longer matches the surface exactly, and will
+    /// be recreated.
+    Suboptimal,
+
+    /// Allocating an image that follows the window size failed.
+    AllocateImage(Validated<ImageAllocateError>),
+
+    /// Another error happened.
+    Vulkan(Validated<VulkanError>),
+}
+
+impl Error for RendererError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::AllocateImage(err) => Some(err),
+            Self::Vulkan(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for RendererError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        match self {
+            Self::OutOfDate => write!(f, "the swapchain is out of date"),
+            Self::Suboptimal => write!(f, "the swapchain is suboptimal"),
+            Self::AllocateImage(_) => write!(f, "allocating an image failed"),
+            Self::Vulkan(_) => write!(f, "a Vulkan error occurred"),
+        }
+    }
+}
+
+impl From<Validated<VulkanError>> for RendererError {
+    fn from(err: Validated<VulkanError>) -> Self {
+        match err {
+            Validated::Error(VulkanError::OutOfDate) => Self::OutOfDate,
+            err => Self::Vulkan(err),
+        }
+    }
+}
+
+impl From<VulkanError> for RendererError {
+    fn from(err: VulkanError) -> Self {
+        Self::from(Validated::Error(err))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{select_present_mode, PresentTracker, VulkanoWindowRenderer};
+    use crate::{
+        context::{VulkanoConfig, VulkanoContext},
+        tests::with_event_loop,
+        window::{VulkanoWindows, WindowDescriptor},
+    };
+    use std::{panic, sync::Arc, thread, time::Duration};
+    use vulkano::{
+        format::Format, image::ImageUsage, swapchain::PresentMode, Validated, VulkanError,
+    };
+
+    /// Runs `f` with the renderer of a new window, or returns if no window can be created.
+    fn with_window_renderer(f: impl FnOnce(&mut VulkanoWindowRenderer) + Send + 'static) {
+        with_event_loop(|event_loop| {
+            let context =
+                match panic::catch_unwind(|| VulkanoContext::new(VulkanoConfig::default())) {
+                    Ok(x) => x,
+                    Err(_) => return,
+                };
+            let mut windows = VulkanoWindows::default();
+            windows.create_window(event_loop, &context, &WindowDescriptor::default(), |_| {});
+
+            f(windows.get_primary_renderer_mut().unwrap());
+        });
+    }
+
+    #[test]
+    fn mandated_extent() {
+        with_window_renderer(|renderer| {
+            if let Some(extent) = renderer.mandated_extent() {
+                assert_eq!(renderer.swapchain_image_size(), extent);
+            }
+        });
+    }
+
+    #[test]
+    fn acquired_image_index() {
+        with_window_renderer(|renderer| {
+            let image_count = renderer.swapchain_image_count();
+            assert_ne!(image_count, 0);
+
+            for _ in 0..image_count * 2 {
+                let before_future = renderer.acquire().unwrap();
+                assert!(renderer.image_index() < image_count);
+                renderer.present(before_future, true).unwrap();
+            }
+        });
+    }
+
+    #[test]
+    fn frames_in_flight() {
+        with_window_renderer(|renderer| {
+            let image_count = renderer.swapchain_image_count();
+            assert_eq!(renderer.swapchain_image_views().len(), image_count as usize);
+
+            // Presenting without waiting lets several frames be in flight at once.
+            for _ in 0..image_count * 2 {
+                let before_future = renderer.acquire().unwrap();
+                assert!(Arc::ptr_eq(
+                    &renderer.swapchain_image_view(),
+                    &renderer.swapchain_image_views()[renderer.image_index() as usize],
+                ));
+                renderer.present(before_future, false).unwrap();
+            }
+
+            let before_future = renderer.acquire().unwrap();
+            renderer.present(before_future, true).unwrap();
+        });
+    }
+
+    #[test]
+    fn swapchain_recreation() {
+        with_window_renderer(|renderer| {
+            renderer
+                .add_additional_image_view(0, Format::R8G8B8A8_UNORM, ImageUsage::SAMPLED)
+                .unwrap();
+            let additional_image_view = renderer.get_additional_image_view(0);
+            let swapchain_image_views = renderer.swapchain_image_views().to_vec();
+
+            let before_future = renderer.acquire().unwrap();
+            assert!(!renderer.swapchain_recreated());
+            renderer.present(before_future, true).unwrap();
+
+            renderer.resize();
+
+            // The views are replaced, so resources that refer to them must be rebuilt.
+            let before_future = renderer.acquire().unwrap();
+            assert!(renderer.swapchain_recreated());
+            assert!(!Arc::ptr_eq(
+                &renderer.get_additional_image_view(0),
+                &additional_image_view,
+            ));
+            assert!(!Arc::ptr_eq(
+                &renderer.swapchain_image_views()[0],
+                &swapchain_image_views[0],
+            ));
+            renderer.present(before_future, true).unwrap();
+
+            // The flag is only set for one frame.
+            let before_future = renderer.acquire().unwrap();
+            assert!(!renderer.swapchain_recreated());
+            renderer.present(before_future, true).unwrap();
+        });
+    }
+
+    #[test]
+    fn present_mode_fallback() {
+        let all = [
+            PresentMode::Fifo,
+            PresentMode::FifoRelaxed,
+            PresentMode::Mailbox,
+            PresentMode::Immediate,
+        ];
+
+        // A supported mode is always used as it is.
+        for present_mode in all {
+            assert_eq!(select_present_mode(present_mode, all), present_mode);
+        }
+
+        let fifo_only = [PresentMode::Fifo];
+
+        for present_mode in all {
+            assert_eq!(
+                select_present_mode(present_mode, fifo_only),
+                PresentMode::Fifo,
+            );
+        }
+
+        let mailbox = [PresentMode::Fifo, PresentMode::Mailbox];
+        assert_eq!(
+            select_present_mode(PresentMode::Immediate, mailbox),
+            PresentMode::Mailbox,
+        );
+        assert_eq!(
+            select_present_mode(PresentMode::FifoRelaxed, mailbox),
+            PresentMode::Fifo,
+        );
+
+        let immediate = [PresentMode::Immediate, PresentMode::Fifo];
+        assert_eq!(
+            select_present_mode(PresentMode::Mailbox, immediate),
+            PresentMode::Immediate,
+        );
+
+        // `Fifo` is returned even if the list is empty, because every surface supports it.
+        assert_eq!(
+            select_present_mode(PresentMode::Mailbox, []),
+            PresentMode::Fifo,
+        );
+    }
+
+    #[test]
+    fn present_tracking() {
+        let mut tracker = PresentTracker::default();
+        assert_eq!(tracker.frames_in_flight(), 0);
+        assert_eq!(tracker.latency(), None);
+
+        let present_ids: Vec<_> = (0..4).map(|_| tracker.next_present_id().get()).collect();
+        assert_eq!(present_ids, [1, 2, 3, 4]);
+
+        for &present_id in &present_ids[..3] {
+            tracker.submitted(present_id.try_into().unwrap());
+        }
+        assert_eq!(tracker.frames_in_flight(), 3);
+
+        thread::sleep(Duration::from_millis(1));
+
+        // Only the first present has completed, so polling stops at the second.
+        let mut waited = Vec::new();
+        tracker.poll(|present_id| {
+            waited.push(present_id);
+
+            match present_id {
+                1 => Ok(false),
+                _ => Err(Validated::Error(VulkanError::Timeout)),
+            }
+        });
+        assert_eq!(waited, [1, 2]);
+        assert_eq!(tracker.frames_in_flight(), 2);
+        assert!(tracker.latency().unwrap() >= Duration::from_millis(1));
+
+        // Presents to a retired swapchain are dropped without updating the latency.
+        let latency = tracker.latency();
+        tracker.poll(|_| Err(Validated::Error(VulkanError::OutOfDate)));
+        assert_eq!(tracker.frames_in_flight(), 0);
+        assert_eq!(tracker.latency(), latency);
+
+        let present_id = tracker.next_present_id();
+        tracker.submitted(present_id);
+        tracker.clear();
+        assert_eq!(tracker.frames_in_flight(), 0);
     }
 }
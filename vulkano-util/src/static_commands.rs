@@ -0,0 +1,291 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::sync::Arc;
+use vulkano::{
+    command_buffer::{
+        allocator::{CommandBufferAllocator, StandardCommandBufferAllocator},
+        AutoCommandBufferBuilder, CommandBufferInheritanceInfo, CommandBufferUsage,
+        SecondaryAutoCommandBuffer, SecondaryCommandBufferAbstract,
+    },
+    render_pass::Subpass,
+    Validated, ValidationError, VulkanError,
+};
+
+/// A helper for replaying a static stream of commands every frame, re-recording only the small
+/// part that changes.
+///
+/// The static commands are recorded once into a secondary command buffer with
+/// [`CommandBufferUsage::SimultaneousUse`], so that the same command buffer can be executed in the
+/// primary command buffers of several frames in flight at once. Each frame,
+/// [`StaticCommands::execute`] records the dynamic portion into a new, usually tiny, secondary
+/// command buffer, and executes both inside the current subpass. The cost of recording a frame
+/// then no longer depends on the amount of unchanged geometry.
+///
+/// Secondary command buffers don't inherit any state from each other or from the primary command
+/// buffer, which means that push constants can't be changed for commands that were already
+/// recorded. There are two ways of passing per-frame data to the static commands:
+///
+/// - Re-record the draws that need the data in the dynamic portion. It must bind the pipeline,
+///   descriptor sets and vertex buffers it uses itself, then push the new constants.
+/// - Have the static commands read the data from a buffer, and write the new values to it on the
+///   host before submitting the frame. Since the static commands are executed by every frame in
+///   flight, each frame needs to wait until the previous use of the buffer has completed, or the
+///   buffer must hold one copy of the data per frame in flight, indexed with a push constant in
+///   the dynamic portion.
+///
+/// The primary command buffer must have begun the subpass with
+/// [`SubpassContents::SecondaryCommandBuffers`].
+///
+/// [`SubpassContents::SecondaryCommandBuffers`]: vulkano::command_buffer::SubpassContents::SecondaryCommandBuffers
+pub struct StaticCommands {
+    queue_family_index: u32,
+    subpass: Subpass,
+    static_commands: Arc<SecondaryAutoCommandBuffer>,
+}
+
+impl StaticCommands {
+    /// Records the static commands for use in `subpass`, by calling `record` on a secondary
+    /// command buffer builder.
+    pub fn new(
+        allocator: &StandardCommandBufferAllocator,
+        queue_family_index: u32,
+        subpass: Subpass,
+        record: impl FnOnce(
+            &mut AutoCommandBufferBuilder<SecondaryAutoCommandBuffer>,
+        ) -> Result<(), Box<ValidationError>>,
+    ) -> Result<Self, Validated<VulkanError>> {
+        let static_commands = Self::record(
+            allocator,
+            queue_family_index,
+            subpass.clone(),
+            CommandBufferUsage::SimultaneousUse,
+            record,
+        )?;
+
+        Ok(StaticCommands {
+            queue_family_index,
+            subpass,
+            static_commands,
+        })
+    }
+
+    /// Returns the subpass that the commands were recorded for.
+    #[inline]
+    pub fn subpass(&self) -> &Subpass {
+        &self.subpass
+    }
+
+    /// Returns the secondary command buffer holding the static commands.
+    #[inline]
+    pub fn static_commands(&self) -> &Arc<SecondaryAutoCommandBuffer> {
+        &self.static_commands
+    }
+
+    /// Records the dynamic commands by calling `record` on a new secondary command buffer
+    /// builder, then executes the static commands followed by the dynamic ones in `builder`.
+    ///
+    /// `builder` must be inside the subpass that the static commands were recorded for, and must
+    /// be recorded for the same queue family.
+    pub fn execute<L, A>(
+        &self,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+        allocator: &StandardCommandBufferAllocator,
+        record: impl FnOnce(
+            &mut AutoCommandBufferBuilder<SecondaryAutoCommandBuffer>,
+        ) -> Result<(), Box<ValidationError>>,
+    ) -> Result<(), Validated<VulkanError>>
+    where
+        A: CommandBufferAllocator,
+    {
+        let dynamic_commands = Self::record(
+            allocator,
+            self.queue_family_index,
+            self.subpass.clone(),
+            CommandBufferUsage::OneTimeSubmit,
+            record,
+        )?;
+
+        builder.execute_commands_from_vec(vec![
+            self.static_commands.clone() as Arc<dyn SecondaryCommandBufferAbstract>,
+            dynamic_commands,
+        ])?;
+
+        Ok(())
+    }
+
+    fn record(
+        allocator: &StandardCommandBufferAllocator,
+        queue_family_index: u32,
+        subpass: Subpass,
+        usage: CommandBufferUsage,
+        record: impl FnOnce(
+            &mut AutoCommandBufferBuilder<SecondaryAutoCommandBuffer>,
+        ) -> Result<(), Box<ValidationError>>,
+    ) -> Result<Arc<SecondaryAutoCommandBuffer>, Validated<VulkanError>> {
+        let mut builder = AutoCommandBufferBuilder::secondary(
+            allocator,
+            queue_family_index,
+            usage,
+            CommandBufferInheritanceInfo {
+                render_pass: Some(subpass.into()),
+                ..Default::default()
+            },
+        )?;
+        record(&mut builder)?;
+
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StaticCommands;
+    use vulkano::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, ClearAttachment,
+            ClearRect, CommandBufferUsage, PrimaryCommandBufferAbstract, RenderPassBeginInfo,
+            SecondaryCommandBufferAbstract, SubpassBeginInfo, SubpassContents, SubpassEndInfo,
+        },
+        format::Format,
+        image::{view::ImageView, Image, ImageCreateInfo, ImageType, ImageUsage},
+        memory::allocator::AllocationCreateInfo,
+        render_pass::{Framebuffer, FramebufferCreateInfo, Subpass},
+        sync::GpuFuture,
+    };
+
+    #[test]
+    fn replay_across_frames() {
+        let context = context!();
+        let device = context.device();
+        let queue = context.graphics_queue();
+        let command_buffer_allocator =
+            StandardCommandBufferAllocator::new(device.clone(), Default::default());
+
+        let render_pass = vulkano::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: Store,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+        let image = Image::new(
+            context.memory_allocator().clone(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [4, 4, 1],
+                usage: ImageUsage::COLOR_ATTACHMENT | ImageUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass.clone(),
+            FramebufferCreateInfo {
+                attachments: vec![ImageView::new_default(image.clone()).unwrap()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let clear = |color: [f32; 4], offset: [u32; 2], extent: [u32; 2]| {
+            move |builder: &mut AutoCommandBufferBuilder<_>| {
+                builder.clear_attachments(
+                    [ClearAttachment::Color {
+                        color_attachment: 0,
+                        clear_value: color.into(),
+                    }]
+                    .into_iter()
+                    .collect(),
+                    [ClearRect {
+                        offset,
+                        extent,
+                        array_layers: 0..1,
+                    }]
+                    .into_iter()
+                    .collect(),
+                )?;
+
+                Ok(())
+            }
+        };
+
+        let static_commands = StaticCommands::new(
+            &command_buffer_allocator,
+            queue.queue_family_index(),
+            Subpass::from(render_pass, 0).unwrap(),
+            clear([1.0, 0.0, 0.0, 1.0], [0, 0], [4, 4]),
+        )
+        .unwrap();
+        assert_eq!(
+            static_commands.static_commands().usage(),
+            CommandBufferUsage::SimultaneousUse,
+        );
+
+        // Each frame, the dynamic commands draw over a different pixel after the static ones.
+        for frame in 0..2 {
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &command_buffer_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+            builder
+                .begin_render_pass(
+                    RenderPassBeginInfo {
+                        clear_values: vec![Some([0.0; 4].into())],
+                        ..RenderPassBeginInfo::framebuffer(framebuffer.clone())
+                    },
+                    SubpassBeginInfo {
+                        contents: SubpassContents::SecondaryCommandBuffers,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+            static_commands
+                .execute(
+                    &mut builder,
+                    &command_buffer_allocator,
+                    clear([0.0, 0.0, 1.0, 1.0], [frame, 0], [1, 1]),
+                )
+                .unwrap();
+            builder.end_render_pass(SubpassEndInfo::default()).unwrap();
+            builder
+                .build()
+                .unwrap()
+                .execute(queue.clone())
+                .unwrap()
+                .then_signal_fence_and_flush()
+                .unwrap()
+                .wait(None)
+                .unwrap();
+
+            let pixels = context.download_image(image.clone()).unwrap();
+
+            for (index, pixel) in pixels.chunks(4).enumerate() {
+                if index == frame as usize {
+                    assert_eq!(pixel, [0, 0, 255, 255]);
+                } else {
+                    assert_eq!(pixel, [255, 0, 0, 255]);
+                }
+            }
+        }
+    }
+}
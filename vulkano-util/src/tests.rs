@@ -0,0 +1,202 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+#![cfg(test)]
+
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Mutex, OnceLock},
+    thread,
+};
+use winit::event_loop::{EventLoop, EventLoopBuilder};
+
+type EventLoopJob = Box<dyn FnOnce(&EventLoop<()>) + Send>;
+
+/// Runs `f` with an event loop, or returns `None` if no event loop can be created, for example
+/// because there is no display.
+///
+/// Winit only allows one event loop per process, so it is owned by a dedicated thread and the
+/// tests that create windows run on that thread one after the other.
+pub(crate) fn with_event_loop<R: Send + 'static>(
+    f: impl FnOnce(&EventLoop<()>) -> R + Send + 'static,
+) -> Option<R> {
+    static JOBS: OnceLock<Option<Mutex<mpsc::Sender<EventLoopJob>>>> = OnceLock::new();
+
+    let jobs = JOBS
+        .get_or_init(|| {
+            let (job_sender, job_receiver) = mpsc::channel::<EventLoopJob>();
+            let (ready_sender, ready_receiver) = mpsc::channel();
+
+            thread::spawn(move || {
+                let event_loop = panic::catch_unwind(|| {
+                    let mut builder = EventLoopBuilder::new();
+                    #[cfg(all(
+                        unix,
+                        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+                    ))]
+                    winit::platform::x11::EventLoopBuilderExtX11::with_any_thread(
+                        &mut builder,
+                        true,
+                    );
+                    #[cfg(windows)]
+                    winit::platform::windows::EventLoopBuilderExtWindows::with_any_thread(
+                        &mut builder,
+                        true,
+                    );
+
+                    builder.build()
+                });
+                let event_loop = match event_loop {
+                    Ok(x) => x,
+                    Err(_) => return,
+                };
+                ready_sender.send(()).unwrap();
+
+                for job in job_receiver {
+                    job(&event_loop);
+                }
+            });
+
+            ready_receiver.recv().ok().map(|()| Mutex::new(job_sender))
+        })
+        .as_ref()?;
+
+    let (result_sender, result_receiver) = mpsc::channel();
+    jobs.lock()
+        .unwrap()
+        .send(Box::new(move |event_loop| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| f(event_loop)));
+            result_sender.send(result).unwrap();
+        }))
+        .unwrap();
+
+    match result_receiver.recv().unwrap() {
+        Ok(x) => Some(x),
+        Err(err) => panic::resume_unwind(err),
+    }
+}
+
+/// Creates an instance or returns if initialization fails.
+macro_rules! instance {
+    () => {{
+        use vulkano::{instance::Instance, VulkanLibrary};
+
+        let library = match VulkanLibrary::new() {
+            Ok(x) => x,
+            Err(_) => return,
+        };
+
+        match Instance::new(library, Default::default()) {
+            Ok(x) => x,
+            Err(_) => return,
+        }
+    }};
+}
+
+/// Creates a `VulkanoContext` without any device extensions, or returns if initialization fails.
+/// Additional fields of the `VulkanoConfig` can be given.
+macro_rules! context {
+    ($($field:ident: $value:expr),* $(,)?) => {{
+        use std::{panic, sync::Arc};
+        use vulkano::device::DeviceExtensions;
+        use $crate::context::{VulkanoConfig, VulkanoContext};
+
+        let result = panic::catch_unwind(|| {
+            VulkanoContext::new(VulkanoConfig {
+                device_filter_fn: Arc::new(|_| true),
+                device_extensions: DeviceExtensions::empty(),
+                $($field: $value,)*
+                ..Default::default()
+            })
+        });
+
+        match result {
+            Ok(x) => x,
+            Err(_) => return,
+        }
+    }};
+}
+
+/// Creates a device and a queue for graphics operations.
+macro_rules! gfx_dev_and_queue {
+    ($($feature:ident),*) => ({
+        use vulkano::device::physical::PhysicalDeviceType;
+        use vulkano::device::{Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo};
+        use vulkano::device::{Features, QueueFlags};
+
+        let instance = instance!();
+        let enabled_extensions = DeviceExtensions::empty();
+        let enabled_features = Features {
+            $(
+                $feature: true,
+            )*
+            .. Features::empty()
+        };
+
+        let select = match instance.enumerate_physical_devices() {
+            Ok(x) => x,
+            Err(_) => return,
+        }
+            .filter(|p| {
+                p.supported_extensions().contains(&enabled_extensions) &&
+                p.supported_features().contains(&enabled_features)
+            })
+            .filter_map(|p| {
+                p.queue_family_properties().iter()
+                    .position(|q| {
+                        q.queue_flags.contains(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+                    })
+                    .map(|i| (p, i as u32))
+            })
+            .min_by_key(|(p, _)| {
+                match p.properties().device_type {
+                    PhysicalDeviceType::DiscreteGpu => 0,
+                    PhysicalDeviceType::IntegratedGpu => 1,
+                    PhysicalDeviceType::VirtualGpu => 2,
+                    PhysicalDeviceType::Cpu => 3,
+                    PhysicalDeviceType::Other => 4,
+                    _ => 5,
+                }
+            });
+
+        let (physical_device, queue_family_index) = match select {
+            Some(x) => x,
+            None => return,
+        };
+
+        let (device, mut queues) = match Device::new(
+            physical_device,
+            DeviceCreateInfo {
+                queue_create_infos: vec![QueueCreateInfo {
+                    queue_family_index,
+                    ..Default::default()
+                }],
+                enabled_extensions,
+                enabled_features,
+                ..Default::default()
+            }
+        ) {
+            Ok(r) => r,
+            Err(_) => return,
+        };
+
+        (device, queues.next().unwrap())
+    });
+}
+
+macro_rules! assert_should_panic {
+    ($code:block) => {{
+        let res = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| $code));
+
+        match res {
+            Ok(_) => panic!("Test expected to panic but didn't"),
+            Err(_) => {}
+        }
+    }};
+}
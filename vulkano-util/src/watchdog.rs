@@ -0,0 +1,319 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::{
+    fmt::{Display, Error as FmtError, Formatter},
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+use vulkano::{
+    device::{DeviceFaultInfo, DeviceOwned},
+    sync::future::{FenceSignalFuture, GpuFuture},
+    VulkanError,
+};
+
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+/// An opt-in watchdog that reports submissions which take suspiciously long to complete on the
+/// GPU.
+///
+/// Each submission passed to [`GpuWatchdog::watch`] is monitored from a separate thread, which
+/// polls the fence of the submission. If the fence has not signaled once the timeout has elapsed,
+/// or if the device is lost while waiting, a [`GpuHangReport`] is passed to the `on_hang`
+/// callback.
+/// This is useful for diagnosing shaders that never terminate, for example a compute shader
+/// stuck in an infinite loop, without blocking the thread that submitted the work.
+///
+/// Submissions that are not watched are not affected in any way.
+///
+/// ## Examples
+///
+/// ```ignore
+/// let watchdog = GpuWatchdog::new(Duration::from_secs(2), |report| eprintln!("{}", report));
+///
+/// let future = Arc::new(
+///     sync::now(device.clone())
+///         .then_execute(queue.clone(), command_buffer)
+///         .unwrap()
+///         .then_signal_fence_and_flush()
+///         .unwrap(),
+/// );
+/// watchdog.watch(&future, "particle simulation");
+/// ```
+pub struct GpuWatchdog {
+    timeout: Duration,
+    poll_interval: Duration,
+    on_hang: Arc<dyn Fn(&GpuHangReport) + Send + Sync>,
+}
+
+impl GpuWatchdog {
+    /// Creates a new `GpuWatchdog` that passes submissions that have not completed after
+    /// `timeout` to `on_hang`.
+    ///
+    /// `on_hang` is called from the watchdog thread of the submission.
+    pub fn new(
+        timeout: Duration,
+        on_hang: impl Fn(&GpuHangReport) + Send + Sync + 'static,
+    ) -> Self {
+        GpuWatchdog {
+            timeout,
+            poll_interval: (timeout / 20).clamp(MIN_POLL_INTERVAL, Duration::from_millis(100)),
+            on_hang: Arc::new(on_hang),
+        }
+    }
+
+    /// Sets how often the fence of a watched submission is checked.
+    ///
+    /// Values below 1 millisecond are raised to 1 millisecond, so that the watchdog thread does
+    /// not spin. The default value is a twentieth of the timeout, clamped between 1 and 100
+    /// milliseconds.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval.max(MIN_POLL_INTERVAL);
+        self
+    }
+
+    /// Returns the timeout after which a submission is reported.
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Starts watching `future` on a new thread.
+    ///
+    /// `future` must already have been flushed, for example by creating it with
+    /// [`then_signal_fence_and_flush`](GpuFuture::then_signal_fence_and_flush); otherwise its
+    /// fence never signals and it is reported as hung. `label` identifies the submission in the
+    /// report.
+    ///
+    /// At most one report is made per watched submission. The thread keeps a reference to
+    /// `future` until the fence has signaled or a report has been made.
+    pub fn watch<F>(&self, future: &Arc<FenceSignalFuture<F>>, label: impl Into<String>)
+    where
+        F: GpuFuture + Send + Sync + 'static,
+    {
+        let future = future.clone();
+        let label = label.into();
+        let timeout = self.timeout;
+        let poll_interval = self.poll_interval;
+        let on_hang = self.on_hang.clone();
+        let start = Instant::now();
+
+        thread::spawn(move || loop {
+            let device_lost = match future.is_signaled() {
+                Ok(true) => return,
+                Ok(false) => false,
+                Err(VulkanError::DeviceLost) => true,
+                Err(_) => return,
+            };
+
+            let elapsed = start.elapsed();
+
+            if device_lost || elapsed >= timeout {
+                let report = GpuHangReport::new(&*future, label, elapsed, device_lost);
+                on_hang(&report);
+                return;
+            }
+
+            thread::sleep(poll_interval.min(timeout - elapsed));
+        });
+    }
+}
+
+/// Diagnostics about a watched submission that is likely to have hung, made by a
+/// [`GpuWatchdog`].
+#[derive(Clone, Debug)]
+pub struct GpuHangReport {
+    /// The label that the submission was watched with.
+    pub label: String,
+
+    /// The time that had elapsed since the submission started being watched.
+    pub elapsed: Duration,
+
+    /// Whether the device was lost while waiting for the submission. If `false`, the submission
+    /// had merely not completed before the timeout.
+    pub device_lost: bool,
+
+    /// The name of the physical device.
+    pub device_name: String,
+
+    /// The name and version of the driver, if known.
+    pub driver: Option<String>,
+
+    /// The index of the queue family that the submission was made to, if known.
+    pub queue_family_index: Option<u32>,
+
+    /// Information about the fault that caused the device to be lost, if the device was lost
+    /// and the [`device_fault`](vulkano::device::Features::device_fault) feature is enabled on
+    /// the device.
+    pub fault_info: Option<DeviceFaultInfo>,
+}
+
+impl GpuHangReport {
+    fn new<F>(
+        future: &FenceSignalFuture<F>,
+        label: String,
+        elapsed: Duration,
+        device_lost: bool,
+    ) -> Self
+    where
+        F: GpuFuture,
+    {
+        let device = future.device();
+        let properties = device.physical_device().properties();
+        let driver = match (&properties.driver_name, &properties.driver_info) {
+            (Some(name), Some(info)) => Some(format!("{} {}", name, info)),
+            (Some(name), None) => Some(name.clone()),
+            (None, _) => None,
+        };
+
+        GpuHangReport {
+            label,
+            elapsed,
+            device_lost,
+            device_name: properties.device_name.clone(),
+            driver,
+            queue_family_index: future.queue().map(|queue| queue.queue_family_index()),
+            fault_info: (device_lost && device.enabled_features().device_fault)
+                .then(|| device.fault_info().ok())
+                .flatten(),
+        }
+    }
+}
+
+impl Display for GpuHangReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        if self.device_lost {
+            write!(
+                f,
+                "GPU watchdog: the device was lost while waiting for `{}` after {:.2?}; a shader \
+                may have hung",
+                self.label, self.elapsed,
+            )?;
+        } else {
+            write!(
+                f,
+                "GPU watchdog: `{}` has not completed after {:.2?}; a shader may have hung",
+                self.label, self.elapsed,
+            )?;
+        }
+
+        write!(f, " (device: {}", self.device_name)?;
+
+        if let Some(driver) = &self.driver {
+            write!(f, ", driver: {}", driver)?;
+        }
+
+        if let Some(queue_family_index) = self.queue_family_index {
+            write!(f, ", queue family: {}", queue_family_index)?;
+        }
+
+        write!(f, ")")?;
+
+        if let Some(fault_info) = &self.fault_info {
+            write!(f, "; fault: {}", fault_info.description)?;
+
+            for address_info in &fault_info.address_infos {
+                write!(
+                    f,
+                    "; {:?} at {:#x} (precision {:#x})",
+                    address_info.address_type,
+                    address_info.reported_address,
+                    address_info.address_precision,
+                )?;
+            }
+
+            for vendor_info in &fault_info.vendor_infos {
+                write!(
+                    f,
+                    "; {} (code {:#x}, data {:#x})",
+                    vendor_info.description,
+                    vendor_info.vendor_fault_code,
+                    vendor_info.vendor_fault_data,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GpuWatchdog, MIN_POLL_INTERVAL};
+    use std::{
+        sync::{mpsc, Arc, Mutex},
+        time::Duration,
+    };
+    use vulkano::{
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+        },
+        sync::{self, GpuFuture},
+    };
+
+    #[test]
+    fn poll_interval_is_clamped() {
+        let watchdog = GpuWatchdog::new(Duration::ZERO, |_| {});
+        assert_eq!(watchdog.poll_interval, MIN_POLL_INTERVAL);
+
+        let watchdog = GpuWatchdog::new(Duration::from_secs(1), |_| {});
+        assert_eq!(watchdog.poll_interval, Duration::from_millis(50));
+        let watchdog = watchdog.poll_interval(Duration::ZERO);
+        assert_eq!(watchdog.poll_interval, MIN_POLL_INTERVAL);
+    }
+
+    #[test]
+    fn report_unfinished_submission() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let command_buffer = || {
+            AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap()
+            .build()
+            .unwrap()
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        let watchdog = GpuWatchdog::new(Duration::from_millis(50), move |report| {
+            sender.lock().unwrap().send(report.clone()).unwrap();
+        });
+
+        // A submission that has completed is not reported.
+        let future = Arc::new(
+            sync::now(device.clone())
+                .then_execute(queue.clone(), command_buffer())
+                .unwrap()
+                .then_signal_fence_and_flush()
+                .unwrap(),
+        );
+        future.wait(None).unwrap();
+        watchdog.watch(&future, "completed");
+
+        // A submission that is never flushed never completes.
+        let future = Arc::new(
+            sync::now(device)
+                .then_execute(queue.clone(), command_buffer())
+                .unwrap()
+                .then_signal_fence(),
+        );
+        watchdog.watch(&future, "unflushed");
+
+        let report = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(report.label, "unflushed");
+        assert!(!report.device_lost);
+        assert!(report.elapsed >= Duration::from_millis(50));
+        assert!(report.fault_info.is_none());
+        assert!(receiver.recv_timeout(Duration::from_millis(200)).is_err());
+    }
+}
@@ -347,7 +347,8 @@ pub struct WindowDescriptor {
     pub title: String,
     /// The window's [`PresentMode`].
     ///
-    /// Used to select whether or not VSync is used
+    /// Used to select whether or not VSync is used. If the surface doesn't support it, a fallback
+    /// is chosen with [`select_present_mode`](crate::renderer::select_present_mode).
     pub present_mode: PresentMode,
     /// Sets whether the window is resizable.
     pub resizable: bool,
@@ -361,6 +362,14 @@ pub struct WindowDescriptor {
     pub mode: WindowMode,
     /// Sets whether the background of the window should be transparent.
     pub transparent: bool,
+    /// Sets whether to prefer an sRGB swapchain format.
+    ///
+    /// With an sRGB format, the hardware converts the values written by shaders from linear to
+    /// sRGB. Set this to `false` to prefer a UNORM format instead, for example if shaders apply
+    /// gamma correction themselves. See
+    /// [`select_swapchain_format`](crate::renderer::select_swapchain_format) and
+    /// [`VulkanoWindowRenderer::swapchain_is_srgb`](crate::renderer::VulkanoWindowRenderer::swapchain_is_srgb).
+    pub srgb_swapchain: bool,
 }
 
 impl Default for WindowDescriptor {
@@ -380,6 +389,7 @@ impl Default for WindowDescriptor {
             cursor_visible: true,
             mode: WindowMode::Windowed,
             transparent: false,
+            srgb_swapchain: true,
         }
     }
 }
@@ -81,6 +81,7 @@
 
 pub use self::{subbuffer::*, sys::*, usage::*};
 use crate::{
+    command_buffer::{allocator::CommandBufferAllocator, AutoCommandBufferBuilder},
     device::{physical::PhysicalDevice, Device, DeviceOwned},
     macros::{vulkan_bitflags, vulkan_enum},
     memory::{
@@ -88,7 +89,7 @@ use crate::{
             AllocationCreateInfo, AllocationType, DeviceLayout, MemoryAllocator,
             MemoryAllocatorError,
         },
-        DedicatedAllocation, ExternalMemoryHandleType, ExternalMemoryHandleTypes,
+        DedicatedAllocation, DeviceAlignment, ExternalMemoryHandleType, ExternalMemoryHandleTypes,
         ExternalMemoryProperties, MemoryRequirements, ResourceMemory,
     },
     range_map::RangeMap,
@@ -344,6 +345,51 @@ impl Buffer {
         Buffer::new_unsized(allocator, create_info, allocation_info, len)
     }
 
+    /// Creates a new `Buffer` for a slice, and records a command into `builder` that fills it
+    /// with zeros. Returns a [`Subbuffer`] spanning the whole buffer.
+    ///
+    /// `create_info.usage` must include [`BufferUsage::TRANSFER_DST`].
+    ///
+    /// > **Note**: The buffer only contains zeros once the command buffer built from `builder`
+    /// > has been executed. Any access to the buffer must happen after that, either later in the
+    /// > same command buffer or in a submission that waits for it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `create_info.size` is not zero.
+    /// - Panics if `len` is zero.
+    pub fn new_slice_zeroed<T, L, A>(
+        allocator: Arc<dyn MemoryAllocator>,
+        create_info: BufferCreateInfo,
+        allocation_info: AllocationCreateInfo,
+        len: DeviceSize,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+    ) -> Result<Subbuffer<[T]>, Validated<BufferAllocateError>>
+    where
+        T: BufferContents,
+        A: CommandBufferAllocator,
+    {
+        let len = NonZeroDeviceSize::new(len).expect("empty slices are not valid buffer contents");
+        let layout = <[T]>::LAYOUT.layout_for_len(len).unwrap();
+        // `fill_buffer` writes whole words, so pad the buffer to a multiple of 4 bytes.
+        let padded_layout = layout
+            .align_to(DeviceAlignment::of::<u32>())
+            .unwrap()
+            .pad_to_alignment();
+        let buffer = Subbuffer::new(Buffer::new(
+            allocator,
+            create_info,
+            allocation_info,
+            padded_layout,
+        )?);
+
+        builder
+            .fill_buffer(buffer.clone().reinterpret(), 0)
+            .map_err(|err| err.add_context("fill_buffer"))?;
+
+        Ok(unsafe { buffer.slice(..layout.size()).reinterpret_unchecked() })
+    }
+
     /// Creates a new uninitialized `Buffer` for unsized data. Returns a [`Subbuffer`] spanning the
     /// whole buffer.
     ///
@@ -385,8 +431,6 @@ impl Buffer {
         layout: DeviceLayout,
     ) -> Result<Arc<Self>, Validated<BufferAllocateError>> {
         assert!(layout.alignment().as_devicesize() <= 64);
-        // TODO: Enable once sparse binding materializes
-        // assert!(!allocate_info.flags.contains(BufferCreateFlags::SPARSE_BINDING));
 
         assert_eq!(
             create_info.size, 0,
@@ -813,17 +857,20 @@ vulkan_bitflags! {
     /// Flags specifying additional properties of a buffer.
     BufferCreateFlags = BufferCreateFlags(u32);
 
-    /* TODO: enable
     /// The buffer will be backed by sparse memory binding (through queue commands) instead of
     /// regular binding (through [`bind_memory`]).
     ///
+    /// A buffer with this flag can be turned into a [`Buffer`] with [`assume_bound`], after
+    /// which memory is bound to it with [`bind_sparse`].
+    ///
     /// The [`sparse_binding`] feature must be enabled on the device.
     ///
     /// [`bind_memory`]: sys::RawBuffer::bind_memory
+    /// [`assume_bound`]: sys::RawBuffer::assume_bound
+    /// [`bind_sparse`]: crate::device::QueueGuard::bind_sparse
     /// [`sparse_binding`]: crate::device::Features::sparse_binding
-    SPARSE_BINDING = SPARSE_BINDING,*/
+    SPARSE_BINDING = SPARSE_BINDING,
 
-    /* TODO: enable
     /// The buffer can be used without being fully resident in memory at the time of use.
     ///
     /// This requires the `sparse_binding` flag as well.
@@ -831,9 +878,8 @@ vulkan_bitflags! {
     /// The [`sparse_residency_buffer`] feature must be enabled on the device.
     ///
     /// [`sparse_residency_buffer`]: crate::device::Features::sparse_residency_buffer
-    SPARSE_RESIDENCY = SPARSE_RESIDENCY,*/
+    SPARSE_RESIDENCY = SPARSE_RESIDENCY,
 
-    /* TODO: enable
     /// The buffer's memory can alias with another buffer or a different part of the same buffer.
     ///
     /// This requires the `sparse_binding` flag as well.
@@ -841,7 +887,7 @@ vulkan_bitflags! {
     /// The [`sparse_residency_aliased`] feature must be enabled on the device.
     ///
     /// [`sparse_residency_aliased`]: crate::device::Features::sparse_residency_aliased
-    SPARSE_ALIASED = SPARSE_ALIASED,*/
+    SPARSE_ALIASED = SPARSE_ALIASED,
 
     /* TODO: enable
     /// The buffer is protected, and can only be used in combination with protected memory and other
@@ -975,6 +1021,104 @@ impl IndexType {
             IndexType::U32 => 4,
         }
     }
+
+    /// Returns the index value that restarts the primitive when primitive restart is enabled.
+    /// This is the maximum value that can be represented by indices of this type.
+    #[inline]
+    pub fn primitive_restart_index(self) -> u32 {
+        match self {
+            IndexType::U8 => u8::MAX as u32,
+            IndexType::U16 => u16::MAX as u32,
+            IndexType::U32 => u32::MAX,
+        }
+    }
+}
+
+/// An integer type that can be stored in an [`IndexBuffer`].
+pub trait Index: BufferContents + Copy + TryFrom<u32> {
+    /// The `IndexType` that corresponds to this type.
+    const INDEX_TYPE: IndexType;
+
+    /// The index value that restarts the primitive when primitive restart is enabled.
+    const PRIMITIVE_RESTART_INDEX: Self;
+}
+
+impl Index for u8 {
+    const INDEX_TYPE: IndexType = IndexType::U8;
+    const PRIMITIVE_RESTART_INDEX: Self = u8::MAX;
+}
+
+impl Index for u16 {
+    const INDEX_TYPE: IndexType = IndexType::U16;
+    const PRIMITIVE_RESTART_INDEX: Self = u16::MAX;
+}
+
+impl Index for u32 {
+    const INDEX_TYPE: IndexType = IndexType::U32;
+    const PRIMITIVE_RESTART_INDEX: Self = u32::MAX;
+}
+
+/// Builds the contents of an index buffer for drawing strips with primitive restart enabled.
+///
+/// The indices of each strip in `strips` are converted to `T`, and the primitive restart index of
+/// `T` is inserted between consecutive strips. The result can be uploaded into a buffer and bound
+/// as an [`IndexBuffer`], for use with a pipeline that has
+/// [`primitive_restart_enable`](crate::pipeline::graphics::input_assembly::InputAssemblyState::primitive_restart_enable)
+/// set and a strip or fan topology.
+///
+/// An error is returned if an index can not be represented by `T`, or if it equals the primitive
+/// restart index of `T`, which can not be used as a regular index.
+///
+/// # Examples
+///
+/// ```
+/// use vulkano::buffer::build_strip_indices;
+///
+/// let indices = build_strip_indices::<u16>(&[[0, 1, 2, 3], [4, 5, 6, 7]]).unwrap();
+/// assert_eq!(indices, [0, 1, 2, 3, 0xFFFF, 4, 5, 6, 7]);
+///
+/// // 0xFFFF is reserved for primitive restart, so a `u32` index buffer is needed.
+/// assert!(build_strip_indices::<u16>(&[[0, 1, 0xFFFF]]).is_err());
+/// assert!(build_strip_indices::<u32>(&[[0, 1, 0xFFFF]]).is_ok());
+/// ```
+pub fn build_strip_indices<T: Index>(
+    strips: &[impl AsRef<[u32]>],
+) -> Result<Vec<T>, Box<ValidationError>> {
+    let restart_index = T::INDEX_TYPE.primitive_restart_index();
+    let len = strips
+        .iter()
+        .map(|strip| strip.as_ref().len())
+        .sum::<usize>()
+        + strips.len().saturating_sub(1);
+    let mut indices = Vec::with_capacity(len);
+
+    for (strip_index, strip) in strips.iter().enumerate() {
+        if strip_index != 0 {
+            indices.push(T::PRIMITIVE_RESTART_INDEX);
+        }
+
+        for (index_index, &index) in strip.as_ref().iter().enumerate() {
+            let value = T::try_from(index)
+                .ok()
+                .filter(|_| index != restart_index)
+                .ok_or_else(|| {
+                    Box::new(ValidationError {
+                        context: format!("strips[{}][{}]", strip_index, index_index).into(),
+                        problem: format!(
+                            "is {}, which is not less than the primitive restart index of \
+                            `IndexType::{:?}`",
+                            index,
+                            T::INDEX_TYPE,
+                        )
+                        .into(),
+                        ..Default::default()
+                    })
+                })?;
+            indices.push(value);
+        }
+    }
+
+    Ok(indices)
 }
 
 /// A buffer holding index values, which index into buffers holding vertex data.
@@ -1034,3 +1178,77 @@ impl From<Subbuffer<[u32]>> for IndexBuffer {
         Self::U32(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{build_strip_indices, Buffer, BufferCreateInfo, BufferUsage};
+    use crate::memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator};
+    use std::sync::Arc;
+
+    #[test]
+    fn device_address() {
+        let (device, _) = gfx_dev_and_queue!(buffer_device_address);
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device));
+
+        // The allocator automatically allocates memory with `MemoryAllocateFlags::DEVICE_ADDRESS`
+        // for buffers with the `SHADER_DEVICE_ADDRESS` usage.
+        let buffer = Buffer::new_slice::<u32>(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER | BufferUsage::SHADER_DEVICE_ADDRESS,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            16,
+        )
+        .unwrap();
+
+        let address = buffer.buffer().device_address().unwrap();
+        assert_eq!(buffer.buffer().device_address().unwrap(), address);
+        assert_eq!(
+            buffer.clone().slice(4..).device_address().unwrap().get(),
+            buffer.device_address().unwrap().get() + 16,
+        );
+
+        // The usage is required.
+        let buffer = Buffer::new_slice::<u32>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            16,
+        )
+        .unwrap();
+        assert!(buffer.buffer().device_address().is_err());
+    }
+
+    #[test]
+    fn strip_indices() {
+        let strip_a = vec![0, 1, 2, 3];
+        let strip_b = vec![4, 5, 6];
+
+        assert_eq!(
+            build_strip_indices::<u16>(&[&strip_a, &strip_b]).unwrap(),
+            [0, 1, 2, 3, 0xFFFF, 4, 5, 6],
+        );
+        assert_eq!(
+            build_strip_indices::<u32>(&[&strip_a, &strip_b]).unwrap(),
+            [0, 1, 2, 3, 0xFFFFFFFF, 4, 5, 6],
+        );
+        assert_eq!(
+            build_strip_indices::<u8>(&[&strip_a]).unwrap(),
+            [0, 1, 2, 3],
+        );
+        assert!(build_strip_indices::<u32>(&[] as &[&[u32]])
+            .unwrap()
+            .is_empty());
+
+        // Indices that can not be represented, or that equal the restart index.
+        assert!(build_strip_indices::<u8>(&[&strip_a, &[0xFF][..]]).is_err());
+        assert!(build_strip_indices::<u8>(&[&[0x100][..]]).is_err());
+        assert!(build_strip_indices::<u16>(&[&[0, 0xFFFF, 1][..]]).is_err());
+        assert!(build_strip_indices::<u32>(&[&[0, 0xFFFF, 1][..]]).is_ok());
+    }
+}
@@ -92,12 +92,12 @@ impl<T: ?Sized> Subbuffer<T> {
 
     /// Returns the offset of the subbuffer, in bytes, relative to the [`DeviceMemory`] block.
     fn memory_offset(&self) -> DeviceSize {
-        let allocation = match self.buffer().memory() {
-            BufferMemory::Normal(a) => a,
-            BufferMemory::Sparse => unreachable!(),
-        };
-
-        allocation.offset() + self.offset
+        match self.buffer().memory() {
+            BufferMemory::Normal(allocation) => allocation.offset() + self.offset,
+            // Sparse memory is always bound in whole pages, so the buffer starts at the beginning
+            // of a page.
+            BufferMemory::Sparse => self.offset,
+        }
     }
 
     /// Returns the size of the subbuffer in bytes.
@@ -133,7 +133,7 @@ impl<T: ?Sized> Subbuffer<T> {
                 // SAFETY: `self.range()` is in bounds of the allocation.
                 unsafe { allocation.mapped_slice_unchecked(self.range()) }
             }
-            BufferMemory::Sparse => unreachable!(),
+            BufferMemory::Sparse => Err(HostAccessError::NotHostMapped),
         }
     }
 
@@ -308,7 +308,7 @@ where
     pub fn read(&self) -> Result<BufferReadGuard<'_, T>, HostAccessError> {
         let allocation = match self.buffer().memory() {
             BufferMemory::Normal(a) => a,
-            BufferMemory::Sparse => todo!("`Subbuffer::read` doesn't support sparse binding yet"),
+            BufferMemory::Sparse => return Err(HostAccessError::NotHostMapped),
         };
 
         let range = if let Some(atom_size) = allocation.atom_size() {
@@ -394,7 +394,7 @@ where
     pub fn write(&self) -> Result<BufferWriteGuard<'_, T>, HostAccessError> {
         let allocation = match self.buffer().memory() {
             BufferMemory::Normal(a) => a,
-            BufferMemory::Sparse => todo!("`Subbuffer::write` doesn't support sparse binding yet"),
+            BufferMemory::Sparse => return Err(HostAccessError::NotHostMapped),
         };
 
         let range = if let Some(atom_size) = allocation.atom_size() {
@@ -589,6 +589,69 @@ impl Subbuffer<[u8]> {
 
         self
     }
+
+    /// Splits the subbuffer into consecutive chunks of `stride` bytes, each of which can be bound
+    /// as a storage buffer, or used as a dynamic offset into one. The last chunk is shorter if
+    /// the size of the subbuffer is not a multiple of `stride`.
+    ///
+    /// Returns an error if `stride` is zero, or if `stride` or the offset of the subbuffer is not
+    /// a multiple of the [`min_storage_buffer_offset_alignment`] device property.
+    ///
+    /// [`min_storage_buffer_offset_alignment`]: crate::device::Properties::min_storage_buffer_offset_alignment
+    pub fn aligned_chunks(
+        self,
+        stride: DeviceSize,
+    ) -> Result<impl Iterator<Item = Subbuffer<[u8]>>, Box<ValidationError>> {
+        let alignment = self
+            .device()
+            .physical_device()
+            .properties()
+            .min_storage_buffer_offset_alignment;
+
+        if stride == 0 {
+            return Err(Box::new(ValidationError {
+                context: "stride".into(),
+                problem: "is zero".into(),
+                ..Default::default()
+            }));
+        }
+
+        if !is_aligned(stride, alignment) {
+            return Err(Box::new(ValidationError {
+                context: "stride".into(),
+                problem: format!(
+                    "is not a multiple of the `min_storage_buffer_offset_alignment` device \
+                    property ({})",
+                    alignment.as_devicesize(),
+                )
+                .into(),
+                ..Default::default()
+            }));
+        }
+
+        if !is_aligned(self.offset(), alignment) {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "the offset of the subbuffer ({}) is not a multiple of the \
+                    `min_storage_buffer_offset_alignment` device property ({})",
+                    self.offset(),
+                    alignment.as_devicesize(),
+                )
+                .into(),
+                ..Default::default()
+            }));
+        }
+
+        let size = self.size();
+
+        Ok((0..size.div_ceil(stride)).map(move |index| {
+            let start = index * stride;
+            let end = cmp::min(start + stride, size);
+
+            // SAFETY: `start..end` is non-empty and in bounds of the subbuffer.
+            unsafe { self.clone().slice_unchecked(start..end) }
+        }))
+    }
 }
 
 impl From<Arc<Buffer>> for Subbuffer<[u8]> {
@@ -1322,4 +1385,44 @@ mod tests {
             assert_should_panic!({ buffer.align_to(layout) });
         }
     }
+
+    #[test]
+    fn aligned_chunks() {
+        let (device, _) = gfx_dev_and_queue!();
+        let alignment = device
+            .physical_device()
+            .properties()
+            .min_storage_buffer_offset_alignment
+            .as_devicesize();
+        let allocator = Arc::new(StandardMemoryAllocator::new_default(device));
+
+        let buffer = Buffer::new_slice::<u8>(
+            allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+            alignment * 3 + 1,
+        )
+        .unwrap();
+
+        {
+            let chunks: Vec<_> = buffer.clone().aligned_chunks(alignment).unwrap().collect();
+            assert_eq!(chunks.len(), 4);
+
+            for (index, chunk) in chunks.iter().enumerate() {
+                assert_eq!(chunk.offset(), index as DeviceSize * alignment);
+            }
+
+            assert_eq!(chunks[0].size(), alignment);
+            assert_eq!(chunks[3].size(), 1);
+        }
+
+        assert!(buffer.clone().aligned_chunks(0).is_err());
+
+        if alignment > 1 {
+            assert!(buffer.aligned_chunks(alignment + 1).is_err());
+        }
+    }
 }
@@ -307,9 +307,13 @@ impl RawBuffer {
         // VUID-VkBindBufferMemoryInfo-buffer-07459
         // Ensured by taking ownership of `RawBuffer`.
 
-        // VUID-VkBindBufferMemoryInfo-buffer-01030
-        // Currently ensured by not having sparse binding flags, but this needs to be checked once
-        // those are enabled.
+        if self.flags.intersects(BufferCreateFlags::SPARSE_BINDING) {
+            return Err(Box::new(ValidationError {
+                problem: "`self.flags()` contains `BufferCreateFlags::SPARSE_BINDING`".into(),
+                vuids: &["VUID-VkBindBufferMemoryInfo-buffer-01030"],
+                ..Default::default()
+            }));
+        }
 
         // VUID-VkBindBufferMemoryInfo-memoryOffset-01031
         // Assume that `allocation` was created correctly.
@@ -547,6 +551,30 @@ impl RawBuffer {
         Ok(Buffer::from_raw(self, BufferMemory::Normal(allocation)))
     }
 
+    /// Converts the raw buffer into a full buffer without binding any memory to it, so that
+    /// memory can be bound with [`bind_sparse`] instead.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `self.flags()` does not contain [`BufferCreateFlags::SPARSE_BINDING`].
+    ///
+    /// # Safety
+    ///
+    /// - If `self.flags()` does not contain [`BufferCreateFlags::SPARSE_RESIDENCY`], then the
+    ///   whole buffer must be bound to memory before it is accessed by the device.
+    /// - If `self.flags()` contains [`BufferCreateFlags::SPARSE_RESIDENCY`], then the regions of
+    ///   the buffer that are accessed by the device must be bound to memory, unless the
+    ///   [`residency_non_resident_strict`] property is `true`.
+    ///
+    /// [`bind_sparse`]: crate::device::QueueGuard::bind_sparse
+    /// [`residency_non_resident_strict`]: crate::device::Properties::residency_non_resident_strict
+    #[inline]
+    pub unsafe fn assume_bound(self) -> Buffer {
+        assert!(self.flags.intersects(BufferCreateFlags::SPARSE_BINDING));
+
+        Buffer::from_raw(self, BufferMemory::Sparse)
+    }
+
     /// Returns the memory requirements for this buffer.
     pub fn memory_requirements(&self) -> &MemoryRequirements {
         &self.memory_requirements
@@ -705,44 +733,56 @@ impl BufferCreateInfo {
             }));
         }
 
-        /* Enable when sparse binding is properly handled
-        if let Some(sparse_level) = sparse {
+        if flags.intersects(BufferCreateFlags::SPARSE_BINDING) {
             if !device.enabled_features().sparse_binding {
                 return Err(Box::new(ValidationError {
-                    context: "sparse".into(),
-                    problem: "is `Some`".into(),
+                    context: "flags".into(),
+                    problem: "contains `BufferCreateFlags::SPARSE_BINDING`".into(),
                     requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
                         "sparse_binding",
                     )])]),
                     vuids: &["VUID-VkBufferCreateInfo-flags-00915"],
                 }));
             }
+        } else if flags
+            .intersects(BufferCreateFlags::SPARSE_RESIDENCY | BufferCreateFlags::SPARSE_ALIASED)
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `BufferCreateFlags::SPARSE_RESIDENCY` or \
+                    `BufferCreateFlags::SPARSE_ALIASED`, but does not also contain \
+                    `BufferCreateFlags::SPARSE_BINDING`"
+                    .into(),
+                vuids: &["VUID-VkBufferCreateInfo-flags-00918"],
+                ..Default::default()
+            }));
+        }
 
-            if sparse_level.sparse_residency && !device.enabled_features().sparse_residency_buffer {
-                return Err(Box::new(ValidationError {
-                    context: "sparse".into(),
-                    problem: "contains `BufferCreateFlags::SPARSE_RESIDENCY`".into(),
-                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
-                        "sparse_residency_buffer",
-                    )])]),
-                    vuids: &["VUID-VkBufferCreateInfo-flags-00916"],
-                }));
-            }
-
-            if sparse_level.sparse_aliased && !device.enabled_features().sparse_residency_aliased {
-                return Err(Box::new(ValidationError {
-                    context: "sparse".into(),
-                    problem: "contains `BufferCreateFlags::SPARSE_ALIASED`".into(),
-                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
-                        "sparse_residency_aliased",
-                    )])]),
-                    vuids: &["VUID-VkBufferCreateInfo-flags-00917"],
-                }));
-            }
+        if flags.intersects(BufferCreateFlags::SPARSE_RESIDENCY)
+            && !device.enabled_features().sparse_residency_buffer
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `BufferCreateFlags::SPARSE_RESIDENCY`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "sparse_residency_buffer",
+                )])]),
+                vuids: &["VUID-VkBufferCreateInfo-flags-00916"],
+            }));
+        }
 
-            // TODO:
-            // VUID-VkBufferCreateInfo-flags-00918
-        }*/
+        if flags.intersects(BufferCreateFlags::SPARSE_ALIASED)
+            && !device.enabled_features().sparse_residency_aliased
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `BufferCreateFlags::SPARSE_ALIASED`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "sparse_residency_aliased",
+                )])]),
+                vuids: &["VUID-VkBufferCreateInfo-flags-00917"],
+            }));
+        }
 
         match sharing {
             Sharing::Exclusive => (),
@@ -832,7 +872,11 @@ impl BufferCreateInfo {
 #[cfg(test)]
 mod tests {
     use super::{BufferCreateInfo, BufferUsage, RawBuffer};
-    use crate::device::{Device, DeviceOwned};
+    use crate::{
+        buffer::BufferCreateFlags,
+        device::{Device, DeviceOwned},
+        Requires, Validated,
+    };
 
     #[test]
     fn create() {
@@ -853,23 +897,24 @@ mod tests {
         assert_eq!(&**buf.device() as *const Device, &*device as *const Device);
     }
 
-    /* Re-enable when sparse binding is properly implemented
     #[test]
     fn missing_feature_sparse_binding() {
         let (device, _) = gfx_dev_and_queue!();
         match RawBuffer::new(
             device,
             BufferCreateInfo {
+                flags: BufferCreateFlags::SPARSE_BINDING,
                 size: 128,
-                sparse: Some(BufferCreateFlags::empty()),
-                usage: BufferUsage::transfer_dst,
+                usage: BufferUsage::TRANSFER_DST,
                 ..Default::default()
             },
         ) {
-            Err(BufferError::RequirementNotMet {
-                requires_one_of: RequiresOneOf { features, .. },
-                ..
-            }) if features.contains(&"sparse_binding") => (),
+            Err(Validated::ValidationError(err))
+                if err.requires_one_of.0.iter().any(|requires_all_of| {
+                    requires_all_of
+                        .0
+                        .contains(&Requires::Feature("sparse_binding"))
+                }) => {}
             _ => panic!(),
         }
     }
@@ -880,20 +925,18 @@ mod tests {
         match RawBuffer::new(
             device,
             BufferCreateInfo {
+                flags: BufferCreateFlags::SPARSE_BINDING | BufferCreateFlags::SPARSE_RESIDENCY,
                 size: 128,
-                sparse: Some(BufferCreateFlags {
-                    sparse_residency: true,
-                    sparse_aliased: false,
-                    ..Default::default()
-                }),
-                usage: BufferUsage::transfer_dst,
+                usage: BufferUsage::TRANSFER_DST,
                 ..Default::default()
             },
         ) {
-            Err(BufferError::RequirementNotMet {
-                requires_one_of: RequiresOneOf { features, .. },
-                ..
-            }) if features.contains(&"sparse_residency_buffer") => (),
+            Err(Validated::ValidationError(err))
+                if err.requires_one_of.0.iter().any(|requires_all_of| {
+                    requires_all_of
+                        .0
+                        .contains(&Requires::Feature("sparse_residency_buffer"))
+                }) => {}
             _ => panic!(),
         }
     }
@@ -904,24 +947,21 @@ mod tests {
         match RawBuffer::new(
             device,
             BufferCreateInfo {
+                flags: BufferCreateFlags::SPARSE_BINDING | BufferCreateFlags::SPARSE_ALIASED,
                 size: 128,
-                sparse: Some(BufferCreateFlags {
-                    sparse_residency: false,
-                    sparse_aliased: true,
-                    ..Default::default()
-                }),
-                usage: BufferUsage::transfer_dst,
+                usage: BufferUsage::TRANSFER_DST,
                 ..Default::default()
             },
         ) {
-            Err(BufferError::RequirementNotMet {
-                requires_one_of: RequiresOneOf { features, .. },
-                ..
-            }) if features.contains(&"sparse_residency_aliased") => (),
+            Err(Validated::ValidationError(err))
+                if err.requires_one_of.0.iter().any(|requires_all_of| {
+                    requires_all_of
+                        .0
+                        .contains(&Requires::Feature("sparse_residency_aliased"))
+                }) => {}
             _ => panic!(),
         }
     }
-    */
 
     #[test]
     fn create_empty_buffer() {
@@ -73,26 +73,24 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_video_decode_queue)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be bound as a transform feedback buffer, to capture the outputs of the
+    /// vertex processing stages.
     TRANSFORM_FEEDBACK_BUFFER = TRANSFORM_FEEDBACK_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_transform_feedback)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as a counter buffer when beginning and ending transform feedback.
     TRANSFORM_FEEDBACK_COUNTER_BUFFER = TRANSFORM_FEEDBACK_COUNTER_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_transform_feedback)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be used as the predicate of a conditional rendering block.
     CONDITIONAL_RENDERING = CONDITIONAL_RENDERING_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_conditional_rendering)]),
-    ]),*/
+    ]),
 
     /// The buffer can be used as input data for an acceleration structure build operation.
     ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY = ACCELERATION_STRUCTURE_BUILD_INPUT_READ_ONLY_KHR
@@ -128,26 +126,25 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_video_encode_queue)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be bound as a descriptor buffer that contains sampler and combined image
+    /// sampler descriptors.
     SAMPLER_DESCRIPTOR_BUFFER = SAMPLER_DESCRIPTOR_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_descriptor_buffer)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be bound as a descriptor buffer that contains resource descriptors, such as
+    /// buffers, images and acceleration structures.
     RESOURCE_DESCRIPTOR_BUFFER = RESOURCE_DESCRIPTOR_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_descriptor_buffer)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The buffer can be bound as a descriptor buffer that holds the data of push descriptors.
     PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER = PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_descriptor_buffer)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
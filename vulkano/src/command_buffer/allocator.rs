@@ -23,7 +23,7 @@ use super::{
 use crate::{
     device::{Device, DeviceOwned},
     instance::InstanceOwnedDebugWrapper,
-    Validated, VulkanError,
+    Validated, ValidationError, VulkanError,
 };
 use crossbeam_queue::ArrayQueue;
 use smallvec::{IntoIter, SmallVec};
@@ -173,6 +173,34 @@ impl StandardCommandBufferAllocator {
         }
     }
 
+    /// Trims the [`CommandPool`]s of the given queue family index on the current thread, which
+    /// returns memory that the pools are no longer using back to the system. This includes the
+    /// pool that's currently in use, as well as the pools that were kept for reuse.
+    ///
+    /// This is useful after a phase that recorded many or large command buffers, such as loading,
+    /// is followed by a steady state that needs less memory. Command buffers allocated from the
+    /// pools are not affected.
+    ///
+    /// This has no effect if the entry wasn't initialized yet or if the entry was [cleared].
+    ///
+    /// The device API version must be at least 1.1, or the [`khr_maintenance1`] extension must be
+    /// enabled on the device.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `queue_family_index` is not less than the number of queue families.
+    ///
+    /// [cleared]: Self::clear
+    /// [`khr_maintenance1`]: crate::device::DeviceExtensions::khr_maintenance1
+    #[inline]
+    pub fn trim(&self, queue_family_index: u32) -> Result<(), Box<ValidationError>> {
+        if let Some(entry) = unsafe { &mut *self.entry(queue_family_index) }.as_mut() {
+            entry.trim()
+        } else {
+            Ok(())
+        }
+    }
+
     /// Clears the entry for the given queue family index and the current thread. This does not
     /// mean that the pools are dropped immediately. A pool is kept alive for as long as command
     /// buffers allocated from it exist.
@@ -315,6 +343,26 @@ struct Entry {
 unsafe impl Send for Entry {}
 
 impl Entry {
+    fn trim(&mut self) -> Result<(), Box<ValidationError>> {
+        self.pool.inner.inner.trim()?;
+
+        // Take the pools out of the reserve while trimming them, so that no other thread can
+        // start using them in the meantime.
+        let reserve: SmallVec<[_; MAX_POOLS]> = (0..self.reserve.len())
+            .map_while(|_| self.reserve.pop())
+            .collect();
+
+        for inner in &reserve {
+            unsafe { inner.inner.trim_unchecked() };
+        }
+
+        for inner in reserve {
+            let _ = self.reserve.push(inner);
+        }
+
+        Ok(())
+    }
+
     fn try_reset_pool(
         &mut self,
         flags: CommandPoolResetFlags,
@@ -716,4 +764,31 @@ mod tests {
         .join()
         .unwrap();
     }
+
+    #[test]
+    fn trim() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+
+        // Nothing to trim yet.
+        assert!(allocator.trim(queue.queue_family_index()).is_ok());
+
+        let _alloc = allocator
+            .allocate(queue.queue_family_index(), CommandBufferLevel::Primary, 1)
+            .unwrap()
+            .next()
+            .unwrap()
+            .into_alloc();
+
+        let result = allocator.trim(queue.queue_family_index());
+
+        if device.api_version() >= crate::Version::V1_1
+            || device.enabled_extensions().khr_maintenance1
+        {
+            assert!(result.is_ok());
+        } else {
+            assert!(result.is_err());
+        }
+    }
 }
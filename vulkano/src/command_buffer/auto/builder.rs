@@ -19,23 +19,26 @@ use crate::{
         CommandBufferBufferRangeUsage, CommandBufferBufferUsage, CommandBufferImageRangeUsage,
         CommandBufferImageUsage, CommandBufferInheritanceInfo,
         CommandBufferInheritanceRenderPassType, CommandBufferLevel, CommandBufferResourcesUsage,
-        CommandBufferUsage, RenderingInfo, ResourceUseRef, SecondaryAutoCommandBuffer,
-        SecondaryCommandBufferBufferUsage, SecondaryCommandBufferImageUsage,
-        SecondaryCommandBufferResourcesUsage, SubpassContents,
+        CommandBufferUsage, DescriptorBufferBindingInfo, RenderingInfo, ResourceUseRef,
+        SecondaryAutoCommandBuffer, SecondaryCommandBufferBufferUsage,
+        SecondaryCommandBufferImageUsage, SecondaryCommandBufferResourcesUsage, SubpassContents,
     },
     descriptor_set::{DescriptorSetResources, DescriptorSetWithOffsets},
     device::{Device, DeviceOwned},
-    image::{view::ImageView, Image, ImageAspects, ImageLayout, ImageSubresourceRange},
+    image::{
+        view::ImageView, Image, ImageAspects, ImageLayout, ImageSubresourceRange, SampleCount,
+    },
     pipeline::{
         graphics::{
-            color_blend::LogicOp,
+            color_blend::{ColorComponents, LogicOp},
             depth_stencil::{CompareOp, StencilOps},
             input_assembly::PrimitiveTopology,
-            rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            rasterization::{CullMode, DepthBias, FrontFace, LineStipple, PolygonMode},
             subpass::PipelineRenderingCreateInfo,
             viewport::{Scissor, Viewport},
         },
         ComputePipeline, DynamicState, GraphicsPipeline, PipelineBindPoint, PipelineLayout,
+        SubpassShadingPipeline,
     },
     query::{QueryControlFlags, QueryPool},
     range_map::RangeMap,
@@ -360,6 +363,15 @@ where
             .into());
         }
 
+        if self.builder_state.conditional_rendering.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering is still active".into(),
+                vuids: &["VUID-vkEndCommandBuffer-None-01978"],
+                ..Default::default()
+            })
+            .into());
+        }
+
         // TODO:
         // VUID-vkEndCommandBuffer-commandBuffer-01815
 
@@ -389,6 +401,15 @@ where
             .into());
         }
 
+        if self.builder_state.conditional_rendering.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering is still active".into(),
+                vuids: &["VUID-vkEndCommandBuffer-None-01978"],
+                ..Default::default()
+            })
+            .into());
+        }
+
         let submit_state = match self.inner.usage() {
             CommandBufferUsage::MultipleSubmit => SubmitState::ExclusiveUse {
                 in_use: AtomicBool::new(false),
@@ -1325,17 +1346,23 @@ pub(in crate::command_buffer) struct CommandBufferBuilderState {
     pub(in crate::command_buffer) render_pass: Option<RenderPassState>,
 
     // Bind/push
+    pub(in crate::command_buffer) descriptor_buffers: SmallVec<[DescriptorBufferBindingInfo; 2]>,
     pub(in crate::command_buffer) descriptor_sets: HashMap<PipelineBindPoint, DescriptorSetState>,
     pub(in crate::command_buffer) index_buffer: Option<IndexBuffer>,
     pub(in crate::command_buffer) pipeline_compute: Option<Arc<ComputePipeline>>,
     pub(in crate::command_buffer) pipeline_graphics: Option<Arc<GraphicsPipeline>>,
+    pub(in crate::command_buffer) pipeline_subpass_shading: Option<Arc<SubpassShadingPipeline>>,
     pub(in crate::command_buffer) vertex_buffers: HashMap<u32, Subbuffer<[u8]>>,
     pub(in crate::command_buffer) push_constants: RangeSet<u32>,
     pub(in crate::command_buffer) push_constants_pipeline_layout: Option<Arc<PipelineLayout>>,
+    pub(in crate::command_buffer) transform_feedback_buffers: HashMap<u32, Subbuffer<[u8]>>,
 
     // Dynamic state
+    pub(in crate::command_buffer) attachment_feedback_loop_enable: Option<ImageAspects>,
     pub(in crate::command_buffer) blend_constants: Option<[f32; 4]>,
+    pub(in crate::command_buffer) color_blend_enable: HashMap<u32, bool>,
     pub(in crate::command_buffer) color_write_enable: Option<SmallVec<[bool; 4]>>,
+    pub(in crate::command_buffer) color_write_mask: HashMap<u32, ColorComponents>,
     pub(in crate::command_buffer) cull_mode: Option<CullMode>,
     pub(in crate::command_buffer) depth_bias: Option<DepthBias>,
     pub(in crate::command_buffer) depth_bias_enable: Option<bool>,
@@ -1350,8 +1377,10 @@ pub(in crate::command_buffer) struct CommandBufferBuilderState {
     pub(in crate::command_buffer) line_width: Option<f32>,
     pub(in crate::command_buffer) logic_op: Option<LogicOp>,
     pub(in crate::command_buffer) patch_control_points: Option<u32>,
+    pub(in crate::command_buffer) polygon_mode: Option<PolygonMode>,
     pub(in crate::command_buffer) primitive_restart_enable: Option<bool>,
     pub(in crate::command_buffer) primitive_topology: Option<PrimitiveTopology>,
+    pub(in crate::command_buffer) rasterization_samples: Option<SampleCount>,
     pub(in crate::command_buffer) rasterizer_discard_enable: Option<bool>,
     pub(in crate::command_buffer) scissor: HashMap<u32, Scissor>,
     pub(in crate::command_buffer) scissor_with_count: Option<SmallVec<[Scissor; 2]>>,
@@ -1365,12 +1394,20 @@ pub(in crate::command_buffer) struct CommandBufferBuilderState {
 
     // Active queries
     pub(in crate::command_buffer) queries: HashMap<ash::vk::QueryType, QueryState>,
+
+    // Conditional rendering
+    pub(in crate::command_buffer) conditional_rendering: Option<ConditionalRenderingState>,
+
+    // Transform feedback
+    pub(in crate::command_buffer) transform_feedback_active: bool,
 }
 
 impl CommandBufferBuilderState {
     pub(in crate::command_buffer) fn reset_non_render_pass_states(&mut self) {
         *self = Self {
             render_pass: take(&mut self.render_pass),
+            conditional_rendering: take(&mut self.conditional_rendering),
+            transform_feedback_active: self.transform_feedback_active,
             ..Default::default()
         }
     }
@@ -1420,35 +1457,38 @@ impl CommandBufferBuilderState {
                 DynamicState::ViewportWithCount => self.viewport_with_count = None,
                 DynamicState::TessellationDomainOrigin => (), // TODO:
                 DynamicState::DepthClampEnable => (),         // TODO:
-                DynamicState::PolygonMode => (),              // TODO:
-                DynamicState::RasterizationSamples => (),     // TODO:
-                DynamicState::SampleMask => (),               // TODO:
-                DynamicState::AlphaToCoverageEnable => (),    // TODO:
-                DynamicState::AlphaToOneEnable => (),         // TODO:
-                DynamicState::LogicOpEnable => (),            // TODO:
-                DynamicState::ColorBlendEnable => (),         // TODO:
-                DynamicState::ColorBlendEquation => (),       // TODO:
-                DynamicState::ColorWriteMask => (),           // TODO:
-                DynamicState::RasterizationStream => (),      // TODO:
+                DynamicState::PolygonMode => self.polygon_mode = None,
+                DynamicState::RasterizationSamples => self.rasterization_samples = None,
+                DynamicState::SampleMask => (),            // TODO:
+                DynamicState::AlphaToCoverageEnable => (), // TODO:
+                DynamicState::AlphaToOneEnable => (),      // TODO:
+                DynamicState::LogicOpEnable => (),         // TODO:
+                DynamicState::ColorBlendEnable => self.color_blend_enable.clear(),
+                DynamicState::ColorBlendEquation => (), // TODO:
+                DynamicState::ColorWriteMask => self.color_write_mask.clear(),
+                DynamicState::RasterizationStream => (), // TODO:
                 DynamicState::ConservativeRasterizationMode => (), // TODO:
                 DynamicState::ExtraPrimitiveOverestimationSize => (), // TODO:
-                DynamicState::DepthClipEnable => (),          // TODO:
-                DynamicState::SampleLocationsEnable => (),    // TODO:
-                DynamicState::ColorBlendAdvanced => (),       // TODO:
-                DynamicState::ProvokingVertexMode => (),      // TODO:
-                DynamicState::LineRasterizationMode => (),    // TODO:
-                DynamicState::LineStippleEnable => (),        // TODO:
+                DynamicState::DepthClipEnable => (),     // TODO:
+                DynamicState::SampleLocationsEnable => (), // TODO:
+                DynamicState::ColorBlendAdvanced => (),  // TODO:
+                DynamicState::ProvokingVertexMode => (), // TODO:
+                DynamicState::LineRasterizationMode => (), // TODO:
+                DynamicState::LineStippleEnable => (),   // TODO:
                 DynamicState::DepthClipNegativeOneToOne => (), // TODO:
-                DynamicState::ViewportWScalingEnable => (),   // TODO:
-                DynamicState::ViewportSwizzle => (),          // TODO:
-                DynamicState::CoverageToColorEnable => (),    // TODO:
-                DynamicState::CoverageToColorLocation => (),  // TODO:
-                DynamicState::CoverageModulationMode => (),   // TODO:
+                DynamicState::ViewportWScalingEnable => (), // TODO:
+                DynamicState::ViewportSwizzle => (),     // TODO:
+                DynamicState::CoverageToColorEnable => (), // TODO:
+                DynamicState::CoverageToColorLocation => (), // TODO:
+                DynamicState::CoverageModulationMode => (), // TODO:
                 DynamicState::CoverageModulationTableEnable => (), // TODO:
-                DynamicState::CoverageModulationTable => (),  // TODO:
-                DynamicState::ShadingRateImageEnable => (),   // TODO:
+                DynamicState::CoverageModulationTable => (), // TODO:
+                DynamicState::ShadingRateImageEnable => (), // TODO:
                 DynamicState::RepresentativeFragmentTestEnable => (), // TODO:
-                DynamicState::CoverageReductionMode => (),    // TODO:
+                DynamicState::CoverageReductionMode => (), // TODO:
+                DynamicState::AttachmentFeedbackLoopEnable => {
+                    self.attachment_feedback_loop_enable = None
+                }
             }
         }
     }
@@ -1736,13 +1776,20 @@ pub(in crate::command_buffer) struct DescriptorSetState {
 pub(in crate::command_buffer) enum SetOrPush {
     Set(DescriptorSetWithOffsets),
     Push(DescriptorSetResources),
+    Buffer {
+        buffer_index: u32,
+        offset: DeviceSize,
+    },
 }
 
 impl SetOrPush {
-    pub(in crate::command_buffer) fn resources(&self) -> &DescriptorSetResources {
+    /// Returns the resources of the set, or `None` if the descriptors are read from a descriptor
+    /// buffer, whose contents are not tracked.
+    pub(in crate::command_buffer) fn resources(&self) -> Option<&DescriptorSetResources> {
         match self {
-            Self::Set(set) => set.as_ref().0.resources(),
-            Self::Push(resources) => resources,
+            Self::Set(set) => Some(set.as_ref().0.resources()),
+            Self::Push(resources) => Some(resources),
+            Self::Buffer { .. } => None,
         }
     }
 
@@ -1750,7 +1797,7 @@ impl SetOrPush {
     pub(in crate::command_buffer) fn dynamic_offsets(&self) -> &[u32] {
         match self {
             Self::Set(set) => set.as_ref().1,
-            Self::Push(_) => &[],
+            Self::Push(_) | Self::Buffer { .. } => &[],
         }
     }
 }
@@ -1773,3 +1820,7 @@ pub(in crate::command_buffer) struct QueryState {
     pub(in crate::command_buffer) flags: QueryControlFlags,
     pub(in crate::command_buffer) in_subpass: bool,
 }
+
+pub(in crate::command_buffer) struct ConditionalRenderingState {
+    pub(in crate::command_buffer) in_subpass: bool,
+}
@@ -67,8 +67,8 @@
 
 pub use self::builder::*;
 pub(in crate::command_buffer) use self::builder::{
-    BeginRenderPassState, BeginRenderingState, QueryState, RenderPassState,
-    RenderPassStateAttachments, RenderPassStateType, SetOrPush,
+    BeginRenderPassState, BeginRenderingState, ConditionalRenderingState, QueryState,
+    RenderPassState, RenderPassStateAttachments, RenderPassStateType, SetOrPush,
 };
 use super::{
     allocator::{CommandBufferAllocator, StandardCommandBufferAllocator},
@@ -322,23 +322,62 @@ mod tests {
         buffer::{Buffer, BufferCreateInfo, BufferUsage},
         command_buffer::{
             allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, BufferCopy,
-            CommandBufferUsage, CopyBufferInfoTyped, PrimaryCommandBufferAbstract,
+            CommandBufferInheritanceInfo, CommandBufferInheritanceRenderingInfo,
+            CommandBufferUsage, ConditionalRenderingFlags, CopyBufferInfoTyped,
+            CopyImageToBufferInfo, DescriptorBufferBindingInfo, DrawIndirectCommand, DrawInfo,
+            PrimaryCommandBufferAbstract, RenderPassBeginInfo, RenderingAttachmentInfo,
+            RenderingInfo, SecondaryCommandBufferAbstract, SubpassBeginInfo, SubpassContents,
         },
         descriptor_set::{
             allocator::StandardDescriptorSetAllocator,
             layout::{
-                DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
-                DescriptorType,
+                DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateFlags,
+                DescriptorSetLayoutCreateInfo, DescriptorType,
             },
             PersistentDescriptorSet, WriteDescriptorSet,
         },
         device::{Device, DeviceCreateInfo, QueueCreateInfo},
-        image::sampler::{Sampler, SamplerCreateInfo},
+        format::Format,
+        image::{
+            sampler::{Sampler, SamplerCreateInfo},
+            view::ImageView,
+            Image, ImageCreateInfo, ImageLayout, ImageType, ImageUsage,
+        },
         memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
-        pipeline::{layout::PipelineLayoutCreateInfo, PipelineBindPoint, PipelineLayout},
-        shader::ShaderStages,
+        pipeline::{
+            compute::ComputePipelineCreateInfo,
+            graphics::{
+                color_blend::ColorBlendState,
+                depth_stencil::DepthStencilState,
+                input_assembly::{InputAssemblyState, PrimitiveTopology, PrimitiveTopologyClass},
+                multisample::MultisampleState,
+                rasterization::{PolygonMode, RasterizationState},
+                subpass::PipelineRenderingCreateInfo,
+                vertex_input::{
+                    VertexInputAttributeDescription, VertexInputBindingDescription,
+                    VertexInputRate, VertexInputState,
+                },
+                viewport::{Viewport, ViewportState},
+                GraphicsPipelineCreateInfo,
+            },
+            layout::PipelineLayoutCreateInfo,
+            ComputePipeline, DynamicState, GraphicsPipeline, PipelineBindPoint,
+            PipelineCreateFlags, PipelineLayout, PipelineShaderStageCreateInfo, StateMode,
+        },
+        query::{
+            QueryControlFlags, QueryPipelineStatisticFlags, QueryPool, QueryPoolCreateInfo,
+            QueryResultFlags, QueryType,
+        },
+        render_pass::{
+            AttachmentDescription, AttachmentLoadOp, AttachmentReference, AttachmentStoreOp,
+            Framebuffer, FramebufferCreateInfo, RenderPass, RenderPassCreateInfo, Subpass,
+            SubpassDescription,
+        },
+        shader::{ShaderModule, ShaderModuleCreateInfo, ShaderStages},
         sync::GpuFuture,
+        Version, VulkanObject,
     };
+    use smallvec::smallvec;
     use std::sync::Arc;
 
     #[test]
@@ -355,6 +394,39 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn submission_keeps_command_buffer_alive() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let cb = AutoCommandBufferBuilder::primary(
+            &allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::MultipleSubmit,
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        assert!(!cb.state.lock().is_submit_pending());
+        assert_eq!(Arc::strong_count(&cb), 1);
+
+        // While the submission is pending, the command buffer can't be freed, because it is kept
+        // alive until the submission has completed.
+        let future = cb
+            .clone()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        assert!(cb.state.lock().is_submit_pending());
+        assert!(Arc::strong_count(&cb) > 1);
+
+        future.wait(None).unwrap();
+        drop(future);
+        assert!(!cb.state.lock().is_submit_pending());
+        assert_eq!(Arc::strong_count(&cb), 1);
+    }
+
     #[test]
     fn copy_buffer_dimensions() {
         let instance = instance!();
@@ -443,6 +515,51 @@ mod tests {
         assert_eq!(*result, [0_u32, 1, 2, 3, 4]);
     }
 
+    #[test]
+    fn zeroed_buffer() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // An odd length, so that the buffer isn't a multiple of 4 bytes.
+        let buffer = Buffer::new_slice_zeroed::<u8, _, _>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            13,
+            &mut cbb,
+        )
+        .unwrap();
+        assert_eq!(buffer.len(), 13);
+
+        buffer.write().unwrap().fill(0xFF);
+
+        let cb = cbb.build().unwrap();
+
+        let future = cb
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        future.wait(None).unwrap();
+
+        assert!(buffer.read().unwrap().iter().all(|&x| x == 0));
+    }
+
     #[test]
     fn secondary_nonconcurrent_conflict() {
         let (device, queue) = gfx_dev_and_queue!();
@@ -503,6 +620,268 @@ mod tests {
         }
     }
 
+    #[test]
+    fn secondary_execute_many() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        let secondaries: Vec<_> = (0..3)
+            .map(|_| {
+                AutoCommandBufferBuilder::secondary(
+                    &cb_allocator,
+                    queue.queue_family_index(),
+                    CommandBufferUsage::MultipleSubmit,
+                    Default::default(),
+                )
+                .unwrap()
+                .build()
+                .unwrap()
+            })
+            .collect();
+
+        {
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+
+            builder
+                .execute_commands_many(
+                    secondaries
+                        .iter()
+                        .map(|cb| cb.clone() as Arc<dyn SecondaryCommandBufferAbstract>),
+                )
+                .unwrap();
+        }
+
+        {
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+
+            // The same non-concurrent secondary command buffer can't be executed twice.
+            assert!(builder
+                .execute_commands_many([
+                    secondaries[0].clone() as Arc<dyn SecondaryCommandBufferAbstract>,
+                    secondaries[0].clone(),
+                ])
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn secondary_incompatible_subpass() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let render_pass = crate::ordered_passes_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            passes: [
+                { color: [color], depth_stencil: {}, input: [] },
+                { color: [color], depth_stencil: {}, input: [] },
+            ],
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let view = ImageView::new_default(
+            Image::new(
+                memory_allocator,
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format: Format::R8G8B8A8_UNORM,
+                    extent: [32, 32, 1],
+                    usage: ImageUsage::COLOR_ATTACHMENT,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass.clone(),
+            FramebufferCreateInfo {
+                attachments: vec![view],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        // Record a secondary command buffer for the second subpass.
+        let secondary = AutoCommandBufferBuilder::secondary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::MultipleSubmit,
+            CommandBufferInheritanceInfo {
+                render_pass: Some(Subpass::from(render_pass, 1).unwrap().into()),
+                ..Default::default()
+            },
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![Some([0.0; 4].into())],
+                    ..RenderPassBeginInfo::framebuffer(framebuffer)
+                },
+                SubpassBeginInfo {
+                    contents: SubpassContents::SecondaryCommandBuffers,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        // Executing it in the first subpass is an error.
+        assert!(builder.execute_commands(secondary.clone()).is_err());
+
+        builder
+            .next_subpass(
+                Default::default(),
+                SubpassBeginInfo {
+                    contents: SubpassContents::SecondaryCommandBuffers,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+        // Executing it in the second subpass is fine.
+        builder.execute_commands(secondary).unwrap();
+    }
+
+    #[test]
+    fn attachment_feedback_loop_usage() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_attachment_feedback_loop_layout],
+            features: [attachment_feedback_loop_layout],
+        );
+
+        let render_pass = |attachment_layout, reference_layout| {
+            RenderPass::new(
+                device.clone(),
+                RenderPassCreateInfo {
+                    attachments: vec![AttachmentDescription {
+                        format: Format::R8G8B8A8_UNORM,
+                        load_op: AttachmentLoadOp::Load,
+                        store_op: AttachmentStoreOp::Store,
+                        initial_layout: attachment_layout,
+                        final_layout: attachment_layout,
+                        ..Default::default()
+                    }],
+                    subpasses: vec![SubpassDescription {
+                        color_attachments: vec![Some(AttachmentReference {
+                            attachment: 0,
+                            layout: reference_layout,
+                            ..Default::default()
+                        })],
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+
+        // The image is not created with `ImageUsage::ATTACHMENT_FEEDBACK_LOOP`.
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let view = ImageView::new_default(
+            Image::new(
+                memory_allocator,
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format: Format::R8G8B8A8_UNORM,
+                    extent: [32, 32, 1],
+                    usage: ImageUsage::COLOR_ATTACHMENT,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let begin_render_pass = |render_pass| {
+            let framebuffer = Framebuffer::new(
+                render_pass,
+                FramebufferCreateInfo {
+                    attachments: vec![view.clone()],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap()
+            .begin_render_pass(
+                RenderPassBeginInfo::framebuffer(framebuffer),
+                Default::default(),
+            )
+            .map(|_| ())
+        };
+
+        begin_render_pass(render_pass(
+            ImageLayout::ColorAttachmentOptimal,
+            ImageLayout::ColorAttachmentOptimal,
+        ))
+        .unwrap();
+
+        match begin_render_pass(render_pass(
+            ImageLayout::AttachmentFeedbackLoopOptimal,
+            ImageLayout::ColorAttachmentOptimal,
+        )) {
+            Err(err) => {
+                assert_eq!(
+                    err.vuids,
+                    ["VUID-vkCmdBeginRenderPass2-initialLayout-07002"]
+                );
+            }
+            Ok(_) => panic!(),
+        }
+
+        match begin_render_pass(render_pass(
+            ImageLayout::ColorAttachmentOptimal,
+            ImageLayout::AttachmentFeedbackLoopOptimal,
+        )) {
+            Err(err) => {
+                assert_eq!(
+                    err.vuids,
+                    ["VUID-vkCmdBeginRenderPass2-initialLayout-07003"]
+                );
+            }
+            Ok(_) => panic!(),
+        }
+    }
+
     #[test]
     fn buffer_self_copy_overlapping() {
         let (device, queue) = gfx_dev_and_queue!();
@@ -773,7 +1152,8 @@ mod tests {
             )
             .unwrap();
 
-            let ds_allocator = StandardDescriptorSetAllocator::new(device.clone());
+            let ds_allocator =
+                StandardDescriptorSetAllocator::new(device.clone(), Default::default());
 
             let set = PersistentDescriptorSet::new(
                 &ds_allocator,
@@ -876,4 +1256,2488 @@ mod tests {
                 .map_or(false, |state| state.descriptor_sets.contains_key(&1)));
         }
     }
+
+    #[test]
+    fn descriptor_set_incompatible_layout() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let sampler_set_layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all_graphics(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let buffer_set_layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all_graphics(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::UniformBuffer)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let pipeline_layout = PipelineLayout::new(
+            device.clone(),
+            PipelineLayoutCreateInfo {
+                set_layouts: vec![buffer_set_layout],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+        let set = PersistentDescriptorSet::new(
+            &ds_allocator,
+            sampler_set_layout,
+            [WriteDescriptorSet::sampler(
+                0,
+                Sampler::new(device.clone(), SamplerCreateInfo::simple_repeat_linear()).unwrap(),
+            )],
+            [],
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // The set has a sampler binding, but the pipeline layout expects a uniform buffer.
+        assert!(builder
+            .bind_descriptor_sets(
+                PipelineBindPoint::Graphics,
+                pipeline_layout.clone(),
+                0,
+                set.clone(),
+            )
+            .is_err());
+        // The pipeline layout only has one set.
+        assert!(builder
+            .bind_descriptor_sets(PipelineBindPoint::Graphics, pipeline_layout, 1, set)
+            .is_err());
+    }
+
+    #[test]
+    fn draw_indirect_count() {
+        let (device, queue) = gfx_dev_and_queue!(draw_indirect_count, pipeline_statistics_query);
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+
+        let indirect_buffer = |usage| {
+            Buffer::from_iter(
+                memory_allocator.clone(),
+                BufferCreateInfo {
+                    usage,
+                    ..Default::default()
+                },
+                AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                        | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                    ..Default::default()
+                },
+                [DrawIndirectCommand {
+                    vertex_count: 3,
+                    instance_count: 1,
+                    first_vertex: 0,
+                    first_instance: 0,
+                }; 2],
+            )
+            .unwrap()
+        };
+        let count_buffer = |usage, draw_count: u32| {
+            Buffer::from_data(
+                memory_allocator.clone(),
+                BufferCreateInfo {
+                    usage,
+                    ..Default::default()
+                },
+                AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                        | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                    ..Default::default()
+                },
+                draw_count,
+            )
+            .unwrap()
+        };
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // The count buffer must have the `INDIRECT_BUFFER` usage.
+        assert!(builder
+            .draw_indirect_count(
+                indirect_buffer(BufferUsage::INDIRECT_BUFFER),
+                count_buffer(BufferUsage::UNIFORM_BUFFER, 2),
+                2,
+            )
+            .is_err());
+        // `max_draw_count` commands must fit in the indirect buffer.
+        assert!(builder
+            .draw_indirect_count(
+                indirect_buffer(BufferUsage::INDIRECT_BUFFER),
+                count_buffer(BufferUsage::INDIRECT_BUFFER, 2),
+                3,
+            )
+            .is_err());
+
+        let render_pass = crate::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                depth: {
+                    format: Format::D16_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [],
+                depth_stencil: {depth},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass.clone(), 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            layout(location = 0) in vec4 position;
+
+            void main() {
+                gl_Position = position;
+            }
+            */
+            const MODULE: [u32; 93] = [
+                119734787, 65536, 0, 17, 0, 131089, 1, 196622, 0, 1, 458767, 0, 1, 1852399981, 0,
+                7, 10, 262215, 7, 30, 0, 327752, 8, 0, 11, 0, 196679, 8, 2, 131091, 2, 196641, 3,
+                2, 196630, 4, 32, 262167, 5, 4, 4, 262176, 6, 1, 5, 262203, 6, 7, 1, 196638, 8, 5,
+                262176, 9, 3, 8, 262203, 9, 10, 3, 262165, 11, 32, 1, 262187, 11, 12, 0, 262176,
+                13, 3, 5, 327734, 2, 1, 0, 3, 131320, 14, 262205, 5, 15, 7, 327745, 13, 16, 10, 12,
+                196670, 16, 15, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                stages: [PipelineShaderStageCreateInfo::new(vs)]
+                    .into_iter()
+                    .collect(),
+                vertex_input_state: Some(
+                    VertexInputState::new()
+                        .binding(
+                            0,
+                            VertexInputBindingDescription {
+                                stride: 16,
+                                input_rate: VertexInputRate::Vertex,
+                            },
+                        )
+                        .attribute(
+                            0,
+                            VertexInputAttributeDescription {
+                                binding: 0,
+                                format: Format::R32G32B32A32_SFLOAT,
+                                offset: 0,
+                            },
+                        ),
+                ),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState::default()),
+                depth_stencil_state: Some(DepthStencilState::simple_depth_test()),
+                subpass: Some(subpass.into()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )
+        .unwrap();
+
+        let view = ImageView::new_default(
+            Image::new(
+                memory_allocator.clone(),
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format: Format::D16_UNORM,
+                    extent: [32, 32, 1],
+                    usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                attachments: vec![view],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let vertex_buffer = Buffer::from_iter(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            [
+                [-1.0f32, -1.0, 0.5, 1.0],
+                [3.0, -1.0, 0.5, 1.0],
+                [-1.0, 3.0, 0.5, 1.0],
+            ],
+        )
+        .unwrap();
+
+        let query_pool = QueryPool::new(
+            device.clone(),
+            QueryPoolCreateInfo {
+                query_count: 2,
+                ..QueryPoolCreateInfo::query_type(QueryType::PipelineStatistics(
+                    QueryPipelineStatisticFlags::INPUT_ASSEMBLY_VERTICES,
+                ))
+            },
+        )
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe {
+            builder.reset_query_pool(query_pool.clone(), 0..2).unwrap();
+        }
+
+        builder
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![Some(1.0.into())],
+                    ..RenderPassBeginInfo::framebuffer(framebuffer)
+                },
+                Default::default(),
+            )
+            .unwrap()
+            .bind_pipeline_graphics(pipeline)
+            .unwrap()
+            .set_viewport(0, [Viewport::for_extent([32, 32])].into_iter().collect())
+            .unwrap()
+            .bind_vertex_buffers(0, vertex_buffer)
+            .unwrap();
+
+        // The draw count is read from the count buffer, and is clamped to `max_draw_count`.
+        for (query, draw_count) in [(0, 1), (1, 5)] {
+            unsafe {
+                builder
+                    .begin_query(query_pool.clone(), query, QueryControlFlags::empty())
+                    .unwrap();
+            }
+
+            builder
+                .draw_indirect_count(
+                    indirect_buffer(BufferUsage::INDIRECT_BUFFER),
+                    count_buffer(BufferUsage::INDIRECT_BUFFER, draw_count),
+                    2,
+                )
+                .unwrap()
+                .end_query(query_pool.clone(), query)
+                .unwrap();
+        }
+
+        builder.end_render_pass(Default::default()).unwrap();
+
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let mut results = [0u64; 2];
+        assert!(query_pool
+            .get_results(0..2, &mut results, QueryResultFlags::WAIT)
+            .unwrap());
+        assert_eq!(results, [3, 6]);
+    }
+
+    #[test]
+    fn primitive_restart_dynamic_topology() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        // Dynamic primitive topology is core in Vulkan 1.3, and list restart is left disabled.
+        if device.api_version() < Version::V1_3 {
+            return;
+        }
+
+        let render_pass = crate::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass.clone(), 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        // Primitive restart is fixed, but the topology it applies to is only known at draw time.
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                stages: [PipelineShaderStageCreateInfo::new(vs)]
+                    .into_iter()
+                    .collect(),
+                vertex_input_state: Some(VertexInputState::default()),
+                input_assembly_state: Some(
+                    InputAssemblyState::new()
+                        .topology_dynamic(PrimitiveTopologyClass::Triangle)
+                        .primitive_restart_enable(),
+                ),
+                viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState::default()),
+                color_blend_state: Some(ColorBlendState::new(subpass.num_color_attachments())),
+                subpass: Some(subpass.into()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let view = ImageView::new_default(
+            Image::new(
+                memory_allocator.clone(),
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format: Format::R8G8B8A8_UNORM,
+                    extent: [32, 32, 1],
+                    usage: ImageUsage::COLOR_ATTACHMENT,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                attachments: vec![view],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let index_buffer = Buffer::from_iter(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::INDEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            [0u32, 1, 2],
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![Some([0.0; 4].into())],
+                    ..RenderPassBeginInfo::framebuffer(framebuffer)
+                },
+                Default::default(),
+            )
+            .unwrap()
+            .bind_pipeline_graphics(pipeline)
+            .unwrap()
+            .set_viewport(
+                0,
+                [Viewport {
+                    offset: [0.0, 0.0],
+                    extent: [32.0, 32.0],
+                    depth_range: 0.0..=1.0,
+                }]
+                .into_iter()
+                .collect(),
+            )
+            .unwrap()
+            .bind_index_buffer(index_buffer)
+            .unwrap();
+
+        // Restarting a list requires the `primitive_topology_list_restart` feature.
+        builder
+            .set_primitive_topology(PrimitiveTopology::TriangleList)
+            .unwrap();
+        assert!(builder.draw_indexed(3, 1, 0, 0, 0).is_err());
+
+        builder
+            .set_primitive_topology(PrimitiveTopology::TriangleStrip)
+            .unwrap();
+        builder.draw_indexed(3, 1, 0, 0, 0).unwrap();
+    }
+
+    #[test]
+    fn occlusion_query() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let render_pass = crate::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                depth: {
+                    format: Format::D16_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [],
+                depth_stencil: {depth},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass.clone(), 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            layout(location = 0) in vec4 position;
+
+            void main() {
+                gl_Position = position;
+            }
+            */
+            const MODULE: [u32; 93] = [
+                119734787, 65536, 0, 17, 0, 131089, 1, 196622, 0, 1, 458767, 0, 1, 1852399981, 0,
+                7, 10, 262215, 7, 30, 0, 327752, 8, 0, 11, 0, 196679, 8, 2, 131091, 2, 196641, 3,
+                2, 196630, 4, 32, 262167, 5, 4, 4, 262176, 6, 1, 5, 262203, 6, 7, 1, 196638, 8, 5,
+                262176, 9, 3, 8, 262203, 9, 10, 3, 262165, 11, 32, 1, 262187, 11, 12, 0, 262176,
+                13, 3, 5, 327734, 2, 1, 0, 3, 131320, 14, 262205, 5, 15, 7, 327745, 13, 16, 10, 12,
+                196670, 16, 15, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        // The depth buffer is cleared to 0.0, so every fragment of the triangle fails the depth
+        // test and is occluded.
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                stages: [PipelineShaderStageCreateInfo::new(vs)]
+                    .into_iter()
+                    .collect(),
+                vertex_input_state: Some(
+                    VertexInputState::new()
+                        .binding(
+                            0,
+                            VertexInputBindingDescription {
+                                stride: 16,
+                                input_rate: VertexInputRate::Vertex,
+                            },
+                        )
+                        .attribute(
+                            0,
+                            VertexInputAttributeDescription {
+                                binding: 0,
+                                format: Format::R32G32B32A32_SFLOAT,
+                                offset: 0,
+                            },
+                        ),
+                ),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState::default()),
+                depth_stencil_state: Some(DepthStencilState::simple_depth_test()),
+                subpass: Some(subpass.into()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let view = ImageView::new_default(
+            Image::new(
+                memory_allocator.clone(),
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format: Format::D16_UNORM,
+                    extent: [32, 32, 1],
+                    usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                attachments: vec![view],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let vertex_buffer = Buffer::from_iter(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            [
+                [-1.0f32, -1.0, 0.5, 1.0],
+                [3.0, -1.0, 0.5, 1.0],
+                [-1.0, 3.0, 0.5, 1.0],
+            ],
+        )
+        .unwrap();
+
+        let query_pool = QueryPool::new(
+            device.clone(),
+            QueryPoolCreateInfo {
+                query_count: 1,
+                ..QueryPoolCreateInfo::query_type(QueryType::Occlusion)
+            },
+        )
+        .unwrap();
+        let timestamp_query_pool = QueryPool::new(
+            device.clone(),
+            QueryPoolCreateInfo {
+                query_count: 1,
+                ..QueryPoolCreateInfo::query_type(QueryType::Timestamp)
+            },
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe {
+            builder.reset_query_pool(query_pool.clone(), 0..1).unwrap();
+
+            // The query index must be in range, and the pool must be of a type that can be used
+            // with `begin_query`.
+            assert!(builder
+                .begin_query(query_pool.clone(), 1, QueryControlFlags::empty())
+                .is_err());
+            assert!(builder
+                .begin_query(timestamp_query_pool, 0, QueryControlFlags::empty())
+                .is_err());
+
+            // Precise occlusion queries require the `occlusion_query_precise` feature.
+            if !device.enabled_features().occlusion_query_precise {
+                assert!(builder
+                    .begin_query(query_pool.clone(), 0, QueryControlFlags::PRECISE)
+                    .is_err());
+            }
+        }
+
+        builder
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![Some(0.0.into())],
+                    ..RenderPassBeginInfo::framebuffer(framebuffer)
+                },
+                Default::default(),
+            )
+            .unwrap()
+            .bind_pipeline_graphics(pipeline)
+            .unwrap()
+            .set_viewport(
+                0,
+                [Viewport {
+                    offset: [0.0, 0.0],
+                    extent: [32.0, 32.0],
+                    depth_range: 0.0..=1.0,
+                }]
+                .into_iter()
+                .collect(),
+            )
+            .unwrap()
+            .bind_vertex_buffers(0, vertex_buffer)
+            .unwrap();
+
+        unsafe {
+            builder
+                .begin_query(query_pool.clone(), 0, QueryControlFlags::empty())
+                .unwrap();
+
+            // Queries of the same type can't be nested.
+            assert!(builder
+                .begin_query(query_pool.clone(), 0, QueryControlFlags::empty())
+                .is_err());
+        }
+
+        builder.draw(3, 1, 0, 0).unwrap();
+        builder.end_query(query_pool.clone(), 0).unwrap();
+        builder.end_render_pass(Default::default()).unwrap();
+
+        // The query is no longer active.
+        assert!(builder.end_query(query_pool.clone(), 0).is_err());
+
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let mut results = [0u64; 2];
+        assert!(query_pool
+            .get_results(
+                0..1,
+                &mut results,
+                QueryResultFlags::WAIT | QueryResultFlags::WITH_AVAILABILITY,
+            )
+            .unwrap());
+        assert_eq!(results[0], 0);
+        assert_ne!(results[1], 0);
+    }
+
+    #[test]
+    fn primitives_generated_query() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_primitives_generated_query],
+            features: [primitives_generated_query],
+        );
+
+        let render_pass = crate::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                depth: {
+                    format: Format::D16_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [],
+                depth_stencil: {depth},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass.clone(), 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            layout(location = 0) in vec4 position;
+
+            void main() {
+                gl_Position = position;
+            }
+            */
+            const MODULE: [u32; 93] = [
+                119734787, 65536, 0, 17, 0, 131089, 1, 196622, 0, 1, 458767, 0, 1, 1852399981, 0,
+                7, 10, 262215, 7, 30, 0, 327752, 8, 0, 11, 0, 196679, 8, 2, 131091, 2, 196641, 3,
+                2, 196630, 4, 32, 262167, 5, 4, 4, 262176, 6, 1, 5, 262203, 6, 7, 1, 196638, 8, 5,
+                262176, 9, 3, 8, 262203, 9, 10, 3, 262165, 11, 32, 1, 262187, 11, 12, 0, 262176,
+                13, 3, 5, 327734, 2, 1, 0, 3, 131320, 14, 262205, 5, 15, 7, 327745, 13, 16, 10, 12,
+                196670, 16, 15, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let create_info = GraphicsPipelineCreateInfo {
+            stages: [PipelineShaderStageCreateInfo::new(vs)]
+                .into_iter()
+                .collect(),
+            vertex_input_state: Some(
+                VertexInputState::new()
+                    .binding(
+                        0,
+                        VertexInputBindingDescription {
+                            stride: 16,
+                            input_rate: VertexInputRate::Vertex,
+                        },
+                    )
+                    .attribute(
+                        0,
+                        VertexInputAttributeDescription {
+                            binding: 0,
+                            format: Format::R32G32B32A32_SFLOAT,
+                            offset: 0,
+                        },
+                    ),
+            ),
+            input_assembly_state: Some(InputAssemblyState::default()),
+            viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+            rasterization_state: Some(RasterizationState::default()),
+            multisample_state: Some(MultisampleState::default()),
+            depth_stencil_state: Some(DepthStencilState::simple_depth_test()),
+            subpass: Some(subpass.into()),
+            ..GraphicsPipelineCreateInfo::layout(layout)
+        };
+        let pipeline = GraphicsPipeline::new(device.clone(), None, create_info.clone()).unwrap();
+        let discard_pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                rasterization_state: Some(RasterizationState {
+                    rasterizer_discard_enable: StateMode::Fixed(true),
+                    ..Default::default()
+                }),
+                multisample_state: None,
+                ..create_info
+            },
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let view = ImageView::new_default(
+            Image::new(
+                memory_allocator.clone(),
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format: Format::D16_UNORM,
+                    extent: [32, 32, 1],
+                    usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                attachments: vec![view],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let vertex_buffer = Buffer::from_iter(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            [
+                [-1.0f32, -1.0, 0.5, 1.0],
+                [0.0, -1.0, 0.5, 1.0],
+                [-1.0, 0.0, 0.5, 1.0],
+                [0.0, 0.0, 0.5, 1.0],
+                [1.0, 0.0, 0.5, 1.0],
+                [0.0, 1.0, 0.5, 1.0],
+            ],
+        )
+        .unwrap();
+
+        let query_pool = QueryPool::new(
+            device.clone(),
+            QueryPoolCreateInfo {
+                query_count: 1,
+                ..QueryPoolCreateInfo::query_type(QueryType::PrimitivesGenerated)
+            },
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe {
+            builder.reset_query_pool(query_pool.clone(), 0..1).unwrap();
+        }
+
+        builder
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![Some(1.0.into())],
+                    ..RenderPassBeginInfo::framebuffer(framebuffer)
+                },
+                Default::default(),
+            )
+            .unwrap()
+            .set_viewport(0, [Viewport::for_extent([32, 32])].into_iter().collect())
+            .unwrap()
+            .bind_vertex_buffers(0, vertex_buffer)
+            .unwrap();
+
+        unsafe {
+            builder
+                .begin_query(query_pool.clone(), 0, QueryControlFlags::empty())
+                .unwrap();
+        }
+
+        // Rasterizer discard requires the `primitives_generated_query_with_rasterizer_discard`
+        // feature while the query is active.
+        builder.bind_pipeline_graphics(discard_pipeline).unwrap();
+
+        match builder.draw(6, 1, 0, 0) {
+            Err(err) => {
+                assert_eq!(
+                    err.vuids,
+                    ["VUID-vkCmdDraw-primitivesGeneratedQueryWithRasterizerDiscard-06708"]
+                );
+            }
+            Ok(_) => panic!(),
+        }
+
+        builder
+            .bind_pipeline_graphics(pipeline)
+            .unwrap()
+            .draw(6, 1, 0, 0)
+            .unwrap()
+            .end_query(query_pool.clone(), 0)
+            .unwrap()
+            .end_render_pass(Default::default())
+            .unwrap();
+
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        let mut results = [0u32; 1];
+        assert!(query_pool
+            .get_results(0..1, &mut results, QueryResultFlags::WAIT)
+            .unwrap());
+        assert_eq!(results[0], 2);
+    }
+
+    #[test]
+    fn conditional_rendering() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_conditional_rendering],
+            features: [conditional_rendering],
+        );
+
+        let render_pass = crate::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                depth: {
+                    format: Format::D16_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: Store,
+                },
+            },
+            pass: {
+                color: [],
+                depth_stencil: {depth},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass.clone(), 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            layout(location = 0) in vec4 position;
+
+            void main() {
+                gl_Position = position;
+            }
+            */
+            const MODULE: [u32; 93] = [
+                119734787, 65536, 0, 17, 0, 131089, 1, 196622, 0, 1, 458767, 0, 1, 1852399981, 0,
+                7, 10, 262215, 7, 30, 0, 327752, 8, 0, 11, 0, 196679, 8, 2, 131091, 2, 196641, 3,
+                2, 196630, 4, 32, 262167, 5, 4, 4, 262176, 6, 1, 5, 262203, 6, 7, 1, 196638, 8, 5,
+                262176, 9, 3, 8, 262203, 9, 10, 3, 262165, 11, 32, 1, 262187, 11, 12, 0, 262176,
+                13, 3, 5, 327734, 2, 1, 0, 3, 131320, 14, 262205, 5, 15, 7, 327745, 13, 16, 10, 12,
+                196670, 16, 15, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        // The depth buffer is cleared to 1.0, and the triangle writes a depth of 0.5 wherever it
+        // is drawn.
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                stages: [PipelineShaderStageCreateInfo::new(vs)]
+                    .into_iter()
+                    .collect(),
+                vertex_input_state: Some(
+                    VertexInputState::new()
+                        .binding(
+                            0,
+                            VertexInputBindingDescription {
+                                stride: 16,
+                                input_rate: VertexInputRate::Vertex,
+                            },
+                        )
+                        .attribute(
+                            0,
+                            VertexInputAttributeDescription {
+                                binding: 0,
+                                format: Format::R32G32B32A32_SFLOAT,
+                                offset: 0,
+                            },
+                        ),
+                ),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState::default()),
+                depth_stencil_state: Some(DepthStencilState::simple_depth_test()),
+                subpass: Some(subpass.into()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let image = Image::new(
+            memory_allocator.clone(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::D16_UNORM,
+                extent: [32, 32, 1],
+                usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT | ImageUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                attachments: vec![ImageView::new_default(image.clone()).unwrap()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let vertex_buffer = Buffer::from_iter(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            [
+                [-1.0f32, -1.0, 0.5, 1.0],
+                [3.0, -1.0, 0.5, 1.0],
+                [-1.0, 3.0, 0.5, 1.0],
+            ],
+        )
+        .unwrap();
+        let create_predicate = |usage| {
+            Buffer::from_data(
+                memory_allocator.clone(),
+                BufferCreateInfo {
+                    usage,
+                    ..Default::default()
+                },
+                AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                        | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                    ..Default::default()
+                },
+                0u32,
+            )
+            .unwrap()
+        };
+        let predicate = create_predicate(BufferUsage::CONDITIONAL_RENDERING);
+        let depth_buffer = Buffer::new_slice::<u16>(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            32 * 32,
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+
+        // The predicate buffer must have the `CONDITIONAL_RENDERING` usage.
+        {
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+            assert!(builder
+                .begin_conditional_rendering(
+                    create_predicate(BufferUsage::UNIFORM_BUFFER),
+                    ConditionalRenderingFlags::empty(),
+                )
+                .is_err());
+
+            // Conditional rendering must not be left active at the end of the command buffer.
+            builder
+                .begin_conditional_rendering(predicate.clone(), ConditionalRenderingFlags::empty())
+                .unwrap();
+            assert!(builder.build().is_err());
+        }
+
+        // Conditional rendering that was begun in a subpass must be ended in the same subpass.
+        {
+            let render_pass = crate::ordered_passes_renderpass!(
+                device.clone(),
+                attachments: {
+                    depth: {
+                        format: Format::D16_UNORM,
+                        samples: 1,
+                        load_op: Clear,
+                        store_op: DontCare,
+                    },
+                },
+                passes: [
+                    { color: [], depth_stencil: {depth}, input: [] },
+                    { color: [], depth_stencil: {depth}, input: [] },
+                ],
+            )
+            .unwrap();
+            let framebuffer = Framebuffer::new(
+                render_pass,
+                FramebufferCreateInfo {
+                    attachments: vec![ImageView::new_default(image.clone()).unwrap()],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+            builder
+                .begin_render_pass(
+                    RenderPassBeginInfo {
+                        clear_values: vec![Some(1.0.into())],
+                        ..RenderPassBeginInfo::framebuffer(framebuffer)
+                    },
+                    Default::default(),
+                )
+                .unwrap()
+                .begin_conditional_rendering(predicate.clone(), ConditionalRenderingFlags::empty())
+                .unwrap();
+
+            match builder.next_subpass(Default::default(), Default::default()) {
+                Err(err) => {
+                    assert_eq!(
+                        err.vuids,
+                        ["VUID-vkCmdEndConditionalRenderingEXT-None-01987"]
+                    );
+                }
+                Ok(_) => panic!(),
+            }
+            match builder.end_render_pass(Default::default()) {
+                Err(err) => {
+                    assert_eq!(
+                        err.vuids,
+                        ["VUID-vkCmdEndConditionalRenderingEXT-None-01987"]
+                    );
+                }
+                Ok(_) => panic!(),
+            }
+
+            builder
+                .end_conditional_rendering()
+                .unwrap()
+                .next_subpass(Default::default(), Default::default())
+                .unwrap()
+                .end_render_pass(Default::default())
+                .unwrap();
+        }
+
+        // The predicate is zero, so the draw is discarded unless the predicate is inverted.
+        for (flags, drawn) in [
+            (ConditionalRenderingFlags::empty(), false),
+            (ConditionalRenderingFlags::INVERTED, true),
+        ] {
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+
+            // It can't be ended before it is begun.
+            assert!(builder.end_conditional_rendering().is_err());
+
+            builder
+                .begin_render_pass(
+                    RenderPassBeginInfo {
+                        clear_values: vec![Some(1.0.into())],
+                        ..RenderPassBeginInfo::framebuffer(framebuffer.clone())
+                    },
+                    Default::default(),
+                )
+                .unwrap()
+                .bind_pipeline_graphics(pipeline.clone())
+                .unwrap()
+                .set_viewport(0, [Viewport::for_extent([32, 32])].into_iter().collect())
+                .unwrap()
+                .bind_vertex_buffers(0, vertex_buffer.clone())
+                .unwrap()
+                .begin_conditional_rendering(predicate.clone(), flags)
+                .unwrap();
+
+            // Conditional rendering blocks can't be nested.
+            assert!(builder
+                .begin_conditional_rendering(predicate.clone(), flags)
+                .is_err());
+
+            builder.draw(3, 1, 0, 0).unwrap();
+            builder
+                .end_conditional_rendering()
+                .unwrap()
+                .end_render_pass(Default::default())
+                .unwrap()
+                .copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(
+                    image.clone(),
+                    depth_buffer.clone(),
+                ))
+                .unwrap();
+
+            builder
+                .build()
+                .unwrap()
+                .execute(queue.clone())
+                .unwrap()
+                .then_signal_fence_and_flush()
+                .unwrap()
+                .wait(None)
+                .unwrap();
+
+            let depth = depth_buffer.read().unwrap();
+            assert_eq!(depth.iter().all(|&d| d == u16::MAX), !drawn);
+        }
+    }
+
+    #[test]
+    fn transform_feedback() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_transform_feedback],
+            features: [transform_feedback],
+        );
+
+        let render_pass = crate::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                depth: {
+                    format: Format::D16_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [],
+                depth_stencil: {depth},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass.clone(), 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            layout(location = 0) in vec4 position;
+
+            layout(xfb_buffer = 0, xfb_stride = 16) out gl_PerVertex {
+                layout(xfb_offset = 0) vec4 gl_Position;
+            };
+
+            void main() {
+                gl_Position = position;
+            }
+            */
+            const MODULE: [u32; 111] = [
+                119734787, 65536, 0, 17, 0, 131089, 1, 131089, 53, 196622, 0, 1, 458767, 0, 1,
+                1852399981, 0, 7, 10, 196624, 1, 11, 262215, 7, 30, 0, 327752, 8, 0, 11, 0, 327752,
+                8, 0, 35, 0, 196679, 8, 2, 262215, 10, 36, 0, 262215, 10, 37, 16, 131091, 2,
+                196641, 3, 2, 196630, 4, 32, 262167, 5, 4, 4, 262176, 6, 1, 5, 262203, 6, 7, 1,
+                196638, 8, 5, 262176, 9, 3, 8, 262203, 9, 10, 3, 262165, 11, 32, 1, 262187, 11, 12,
+                0, 262176, 13, 3, 5, 327734, 2, 1, 0, 3, 131320, 14, 262205, 5, 15, 7, 327745, 13,
+                16, 10, 12, 196670, 16, 15, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let create_pipeline = || {
+            GraphicsPipeline::new(
+                device.clone(),
+                None,
+                GraphicsPipelineCreateInfo {
+                    stages: [PipelineShaderStageCreateInfo::new(vs.clone())]
+                        .into_iter()
+                        .collect(),
+                    vertex_input_state: Some(
+                        VertexInputState::new()
+                            .binding(
+                                0,
+                                VertexInputBindingDescription {
+                                    stride: 16,
+                                    input_rate: VertexInputRate::Vertex,
+                                },
+                            )
+                            .attribute(
+                                0,
+                                VertexInputAttributeDescription {
+                                    binding: 0,
+                                    format: Format::R32G32B32A32_SFLOAT,
+                                    offset: 0,
+                                },
+                            ),
+                    ),
+                    input_assembly_state: Some(InputAssemblyState::default()),
+                    viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                    rasterization_state: Some(RasterizationState::default()),
+                    multisample_state: Some(MultisampleState::default()),
+                    depth_stencil_state: Some(DepthStencilState::simple_depth_test()),
+                    subpass: Some(subpass.clone().into()),
+                    ..GraphicsPipelineCreateInfo::layout(layout.clone())
+                },
+            )
+            .unwrap()
+        };
+        let pipeline = create_pipeline();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let image = Image::new(
+            memory_allocator.clone(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::D16_UNORM,
+                extent: [32, 32, 1],
+                usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                attachments: vec![ImageView::new_default(image).unwrap()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let positions = [
+            [-1.0f32, -1.0, 0.5, 1.0],
+            [3.0, -1.0, 0.5, 1.0],
+            [-1.0, 3.0, 0.5, 1.0],
+        ];
+        let vertex_buffer = Buffer::from_iter(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            positions,
+        )
+        .unwrap();
+        let create_buffer = |usage| {
+            Buffer::new_slice::<[f32; 4]>(
+                memory_allocator.clone(),
+                BufferCreateInfo {
+                    usage,
+                    ..Default::default()
+                },
+                AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                        | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                    ..Default::default()
+                },
+                3,
+            )
+            .unwrap()
+        };
+        let transform_feedback_buffer = create_buffer(BufferUsage::TRANSFORM_FEEDBACK_BUFFER);
+        let counter_buffer = Buffer::from_data(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            0u32,
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // Transform feedback buffers must have the `TRANSFORM_FEEDBACK_BUFFER` usage.
+        assert!(builder
+            .bind_transform_feedback_buffers(
+                0,
+                smallvec![create_buffer(BufferUsage::VERTEX_BUFFER).into_bytes()],
+            )
+            .is_err());
+
+        builder
+            .bind_transform_feedback_buffers(
+                0,
+                smallvec![transform_feedback_buffer.clone().into_bytes()],
+            )
+            .unwrap();
+
+        // Transform feedback can only be begun inside a render pass instance.
+        assert!(builder
+            .begin_transform_feedback(0, Default::default())
+            .is_err());
+
+        builder
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![Some(1.0.into())],
+                    ..RenderPassBeginInfo::framebuffer(framebuffer)
+                },
+                Default::default(),
+            )
+            .unwrap()
+            .bind_pipeline_graphics(pipeline)
+            .unwrap()
+            .set_viewport(0, [Viewport::for_extent([32, 32])].into_iter().collect())
+            .unwrap()
+            .bind_vertex_buffers(0, vertex_buffer)
+            .unwrap()
+            .begin_transform_feedback(0, Default::default())
+            .unwrap();
+
+        // While transform feedback is active, it can't be begun again, and the bound pipeline
+        // and transform feedback buffers can't be changed.
+        assert!(builder
+            .begin_transform_feedback(0, Default::default())
+            .is_err());
+        assert!(builder.bind_pipeline_graphics(create_pipeline()).is_err());
+        assert!(builder
+            .bind_transform_feedback_buffers(
+                0,
+                smallvec![transform_feedback_buffer.clone().into_bytes()],
+            )
+            .is_err());
+
+        builder.draw(3, 1, 0, 0).unwrap();
+
+        // It must be ended before the render pass instance is ended.
+        assert!(builder.end_render_pass(Default::default()).is_err());
+
+        builder
+            .end_transform_feedback(0, smallvec![Some(counter_buffer.clone())])
+            .unwrap()
+            .end_render_pass(Default::default())
+            .unwrap();
+
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        // All three vertices were captured, and the counter holds the number of bytes written.
+        assert_eq!(&*transform_feedback_buffer.read().unwrap(), &positions);
+        assert_eq!(*counter_buffer.read().unwrap(), 48);
+    }
+
+    #[test]
+    fn draw_multi() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_multi_draw, ext_transform_feedback],
+            features: [multi_draw, transform_feedback],
+        );
+
+        let render_pass = crate::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                depth: {
+                    format: Format::D16_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [],
+                depth_stencil: {depth},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass.clone(), 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            layout(location = 0) in vec4 position;
+
+            layout(xfb_buffer = 0, xfb_stride = 16) out gl_PerVertex {
+                layout(xfb_offset = 0) vec4 gl_Position;
+            };
+
+            void main() {
+                gl_Position = position;
+            }
+            */
+            const MODULE: [u32; 111] = [
+                119734787, 65536, 0, 17, 0, 131089, 1, 131089, 53, 196622, 0, 1, 458767, 0, 1,
+                1852399981, 0, 7, 10, 196624, 1, 11, 262215, 7, 30, 0, 327752, 8, 0, 11, 0, 327752,
+                8, 0, 35, 0, 196679, 8, 2, 262215, 10, 36, 0, 262215, 10, 37, 16, 131091, 2,
+                196641, 3, 2, 196630, 4, 32, 262167, 5, 4, 4, 262176, 6, 1, 5, 262203, 6, 7, 1,
+                196638, 8, 5, 262176, 9, 3, 8, 262203, 9, 10, 3, 262165, 11, 32, 1, 262187, 11, 12,
+                0, 262176, 13, 3, 5, 327734, 2, 1, 0, 3, 131320, 14, 262205, 5, 15, 7, 327745, 13,
+                16, 10, 12, 196670, 16, 15, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                stages: [PipelineShaderStageCreateInfo::new(vs)]
+                    .into_iter()
+                    .collect(),
+                vertex_input_state: Some(
+                    VertexInputState::new()
+                        .binding(
+                            0,
+                            VertexInputBindingDescription {
+                                stride: 16,
+                                input_rate: VertexInputRate::Vertex,
+                            },
+                        )
+                        .attribute(
+                            0,
+                            VertexInputAttributeDescription {
+                                binding: 0,
+                                format: Format::R32G32B32A32_SFLOAT,
+                                offset: 0,
+                            },
+                        ),
+                ),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState::default()),
+                depth_stencil_state: Some(DepthStencilState::simple_depth_test()),
+                subpass: Some(subpass.into()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let image = Image::new(
+            memory_allocator.clone(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::D16_UNORM,
+                extent: [32, 32, 1],
+                usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                attachments: vec![ImageView::new_default(image).unwrap()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let positions: [[f32; 4]; 9] = [
+            [-1.0, -1.0, 0.5, 1.0],
+            [0.0, -1.0, 0.5, 1.0],
+            [-1.0, 0.0, 0.5, 1.0],
+            [0.0, -1.0, 0.5, 1.0],
+            [1.0, -1.0, 0.5, 1.0],
+            [0.0, 0.0, 0.5, 1.0],
+            [-1.0, 0.0, 0.5, 1.0],
+            [0.0, 0.0, 0.5, 1.0],
+            [-1.0, 1.0, 0.5, 1.0],
+        ];
+        let vertex_buffer = Buffer::from_iter(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            positions,
+        )
+        .unwrap();
+        let transform_feedback_buffer = Buffer::new_slice::<[f32; 4]>(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFORM_FEEDBACK_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            9,
+        )
+        .unwrap();
+        let counter_buffer = Buffer::from_data(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            0u32,
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .bind_transform_feedback_buffers(
+                0,
+                smallvec![transform_feedback_buffer.clone().into_bytes()],
+            )
+            .unwrap()
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![Some(1.0.into())],
+                    ..RenderPassBeginInfo::framebuffer(framebuffer)
+                },
+                Default::default(),
+            )
+            .unwrap()
+            .bind_pipeline_graphics(pipeline)
+            .unwrap()
+            .set_viewport(0, [Viewport::for_extent([32, 32])].into_iter().collect())
+            .unwrap()
+            .bind_vertex_buffers(0, vertex_buffer)
+            .unwrap();
+
+        // The vertex range of every draw must be in range of the bound vertex buffers.
+        assert!(builder
+            .draw_multi(
+                &[
+                    DrawInfo {
+                        first_vertex: 0,
+                        vertex_count: 3,
+                    },
+                    DrawInfo {
+                        first_vertex: 8,
+                        vertex_count: 3,
+                    },
+                ],
+                1,
+                0,
+            )
+            .is_err());
+
+        // Issue the three triangles out of order, to check that each draw uses its own range.
+        builder
+            .begin_transform_feedback(0, Default::default())
+            .unwrap()
+            .draw_multi(
+                &[
+                    DrawInfo {
+                        first_vertex: 6,
+                        vertex_count: 3,
+                    },
+                    DrawInfo {
+                        first_vertex: 0,
+                        vertex_count: 3,
+                    },
+                    DrawInfo {
+                        first_vertex: 3,
+                        vertex_count: 3,
+                    },
+                ],
+                1,
+                0,
+            )
+            .unwrap()
+            .end_transform_feedback(0, smallvec![Some(counter_buffer.clone())])
+            .unwrap()
+            .end_render_pass(Default::default())
+            .unwrap();
+
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        // The vertices of all three draws were captured, in the order the draws were given.
+        let captured = transform_feedback_buffer.read().unwrap();
+        assert_eq!(&captured[0..3], &positions[6..9]);
+        assert_eq!(&captured[3..6], &positions[0..3]);
+        assert_eq!(&captured[6..9], &positions[3..6]);
+        assert_eq!(*counter_buffer.read().unwrap(), 144);
+    }
+
+    #[test]
+    fn dynamic_polygon_mode() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_extended_dynamic_state3],
+            features: [extended_dynamic_state3_polygon_mode, fill_mode_non_solid],
+        );
+
+        let render_pass = crate::single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                depth: {
+                    format: Format::D16_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: Store,
+                },
+            },
+            pass: {
+                color: [],
+                depth_stencil: {depth},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass.clone(), 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            layout(location = 0) in vec4 position;
+
+            void main() {
+                gl_Position = position;
+            }
+            */
+            const MODULE: [u32; 93] = [
+                119734787, 65536, 0, 17, 0, 131089, 1, 196622, 0, 1, 458767, 0, 1, 1852399981, 0,
+                7, 10, 262215, 7, 30, 0, 327752, 8, 0, 11, 0, 196679, 8, 2, 131091, 2, 196641, 3,
+                2, 196630, 4, 32, 262167, 5, 4, 4, 262176, 6, 1, 5, 262203, 6, 7, 1, 196638, 8, 5,
+                262176, 9, 3, 8, 262203, 9, 10, 3, 262165, 11, 32, 1, 262187, 11, 12, 0, 262176,
+                13, 3, 5, 327734, 2, 1, 0, 3, 131320, 14, 262205, 5, 15, 7, 327745, 13, 16, 10, 12,
+                196670, 16, 15, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let create_info = |rasterization_state| GraphicsPipelineCreateInfo {
+            stages: [PipelineShaderStageCreateInfo::new(vs.clone())]
+                .into_iter()
+                .collect(),
+            vertex_input_state: Some(
+                VertexInputState::new()
+                    .binding(
+                        0,
+                        VertexInputBindingDescription {
+                            stride: 16,
+                            input_rate: VertexInputRate::Vertex,
+                        },
+                    )
+                    .attribute(
+                        0,
+                        VertexInputAttributeDescription {
+                            binding: 0,
+                            format: Format::R32G32B32A32_SFLOAT,
+                            offset: 0,
+                        },
+                    ),
+            ),
+            input_assembly_state: Some(InputAssemblyState::default()),
+            viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+            rasterization_state: Some(rasterization_state),
+            multisample_state: Some(MultisampleState::default()),
+            depth_stencil_state: Some(DepthStencilState::simple_depth_test()),
+            subpass: Some(subpass.clone().into()),
+            ..GraphicsPipelineCreateInfo::layout(layout.clone())
+        };
+        let fixed_pipeline =
+            GraphicsPipeline::new(device.clone(), None, create_info(RasterizationState::new()))
+                .unwrap();
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            create_info(RasterizationState::new().polygon_mode_dynamic()),
+        )
+        .unwrap();
+        assert_eq!(
+            pipeline.dynamic_state(DynamicState::PolygonMode),
+            Some(true)
+        );
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let image = Image::new(
+            memory_allocator.clone(),
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::D16_UNORM,
+                extent: [32, 32, 1],
+                usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT | ImageUsage::TRANSFER_SRC,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                attachments: vec![ImageView::new_default(image.clone()).unwrap()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let vertex_buffer = Buffer::from_iter(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::VERTEX_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            [
+                [-0.75f32, -0.75, 0.5, 1.0],
+                [0.75, -0.75, 0.5, 1.0],
+                [0.0, 0.75, 0.5, 1.0],
+            ],
+        )
+        .unwrap();
+        let depth_buffer = Buffer::new_slice::<u16>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            32 * 32,
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        builder
+            .begin_render_pass(
+                RenderPassBeginInfo {
+                    clear_values: vec![Some(1.0.into())],
+                    ..RenderPassBeginInfo::framebuffer(framebuffer)
+                },
+                Default::default(),
+            )
+            .unwrap()
+            .bind_pipeline_graphics(fixed_pipeline)
+            .unwrap();
+
+        // The polygon mode of the bound pipeline is fixed.
+        assert!(builder.set_polygon_mode(PolygonMode::Line).is_err());
+
+        builder
+            .bind_pipeline_graphics(pipeline)
+            .unwrap()
+            .set_viewport(0, [Viewport::for_extent([32, 32])].into_iter().collect())
+            .unwrap()
+            .bind_vertex_buffers(0, vertex_buffer)
+            .unwrap();
+
+        // The polygon mode must be set before drawing.
+        assert!(builder.draw(3, 1, 0, 0).is_err());
+
+        builder
+            .set_polygon_mode(PolygonMode::Line)
+            .unwrap()
+            .draw(3, 1, 0, 0)
+            .unwrap()
+            .end_render_pass(Default::default())
+            .unwrap()
+            .copy_image_to_buffer(CopyImageToBufferInfo::image_buffer(
+                image,
+                depth_buffer.clone(),
+            ))
+            .unwrap();
+
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        // Only the edges of the triangle were drawn, so the center keeps the clear value.
+        let depth = depth_buffer.read().unwrap();
+        assert!(depth.iter().any(|&d| d != u16::MAX));
+        assert_eq!(depth[16 * 32 + 16], u16::MAX);
+    }
+
+    #[test]
+    fn dynamic_rendering() {
+        // Dynamic rendering is core in Vulkan 1.3, but the feature must still be enabled.
+        let (device, queue) = gfx_dev_and_queue!(dynamic_rendering);
+
+        if device.api_version() < Version::V1_3 {
+            return;
+        }
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let create_pipeline = |format| {
+            GraphicsPipeline::new(
+                device.clone(),
+                None,
+                GraphicsPipelineCreateInfo {
+                    stages: [PipelineShaderStageCreateInfo::new(vs.clone())]
+                        .into_iter()
+                        .collect(),
+                    vertex_input_state: Some(VertexInputState::default()),
+                    input_assembly_state: Some(InputAssemblyState::default()),
+                    viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                    rasterization_state: Some(RasterizationState::default()),
+                    multisample_state: Some(MultisampleState::default()),
+                    color_blend_state: Some(ColorBlendState::new(1)),
+                    subpass: Some(
+                        PipelineRenderingCreateInfo {
+                            color_attachment_formats: vec![Some(format)],
+                            ..Default::default()
+                        }
+                        .into(),
+                    ),
+                    ..GraphicsPipelineCreateInfo::layout(layout.clone())
+                },
+            )
+            .unwrap()
+        };
+        let pipeline = create_pipeline(Format::R8G8B8A8_UNORM);
+        let mismatched_pipeline = create_pipeline(Format::B8G8R8A8_UNORM);
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let view = ImageView::new_default(
+            Image::new(
+                memory_allocator,
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format: Format::R8G8B8A8_UNORM,
+                    extent: [32, 32, 1],
+                    usage: ImageUsage::COLOR_ATTACHMENT,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder
+            .begin_rendering(RenderingInfo {
+                color_attachments: vec![Some(RenderingAttachmentInfo {
+                    load_op: AttachmentLoadOp::Clear,
+                    store_op: AttachmentStoreOp::Store,
+                    clear_value: Some([0.0, 0.0, 1.0, 1.0].into()),
+                    ..RenderingAttachmentInfo::image_view(view)
+                })],
+                ..Default::default()
+            })
+            .unwrap()
+            .set_viewport(0, [Viewport::for_extent([32, 32])].into_iter().collect())
+            .unwrap();
+
+        // The attachment format must match the format that the pipeline was created with.
+        builder.bind_pipeline_graphics(mismatched_pipeline).unwrap();
+        assert!(builder.draw(3, 1, 0, 0).is_err());
+
+        builder.bind_pipeline_graphics(pipeline).unwrap();
+        builder.draw(3, 1, 0, 0).unwrap();
+        builder.end_rendering().unwrap();
+        builder.build().unwrap();
+    }
+
+    #[test]
+    fn dynamic_rendering_secondary() {
+        let (device, queue) = gfx_dev_and_queue!(dynamic_rendering);
+
+        if device.api_version() < Version::V1_3 {
+            return;
+        }
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                stages: [PipelineShaderStageCreateInfo::new(vs)]
+                    .into_iter()
+                    .collect(),
+                vertex_input_state: Some(VertexInputState::default()),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState::default()),
+                color_blend_state: Some(ColorBlendState::new(1)),
+                subpass: Some(
+                    PipelineRenderingCreateInfo {
+                        color_attachment_formats: vec![Some(Format::R8G8B8A8_UNORM)],
+                        ..Default::default()
+                    }
+                    .into(),
+                ),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let view = ImageView::new_default(
+            Image::new(
+                memory_allocator,
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format: Format::R8G8B8A8_UNORM,
+                    extent: [32, 32, 1],
+                    usage: ImageUsage::COLOR_ATTACHMENT,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let record_secondary = |rendering_info: CommandBufferInheritanceRenderingInfo| {
+            AutoCommandBufferBuilder::secondary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::MultipleSubmit,
+                CommandBufferInheritanceInfo {
+                    render_pass: Some(rendering_info.into()),
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+
+        let mut builder = record_secondary(CommandBufferInheritanceRenderingInfo {
+            color_attachment_formats: vec![Some(Format::R8G8B8A8_UNORM)],
+            ..Default::default()
+        });
+        builder
+            .set_viewport(0, [Viewport::for_extent([32, 32])].into_iter().collect())
+            .unwrap()
+            .bind_pipeline_graphics(pipeline)
+            .unwrap()
+            .draw(3, 1, 0, 0)
+            .unwrap();
+        let secondary = builder.build().unwrap();
+
+        let mismatched_format = record_secondary(CommandBufferInheritanceRenderingInfo {
+            color_attachment_formats: vec![Some(Format::B8G8R8A8_UNORM)],
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+        let extra_depth = record_secondary(CommandBufferInheritanceRenderingInfo {
+            color_attachment_formats: vec![Some(Format::R8G8B8A8_UNORM)],
+            depth_attachment_format: Some(Format::D16_UNORM),
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder
+            .begin_rendering(RenderingInfo {
+                color_attachments: vec![Some(RenderingAttachmentInfo {
+                    load_op: AttachmentLoadOp::Clear,
+                    store_op: AttachmentStoreOp::Store,
+                    clear_value: Some([0.0, 0.0, 1.0, 1.0].into()),
+                    ..RenderingAttachmentInfo::image_view(view)
+                })],
+                contents: SubpassContents::SecondaryCommandBuffers,
+                ..Default::default()
+            })
+            .unwrap();
+
+        // The inherited formats must match the attachments of the render pass instance.
+        assert!(builder.execute_commands(mismatched_format).is_err());
+        assert!(builder.execute_commands(extra_depth).is_err());
+
+        builder.execute_commands(secondary).unwrap();
+        builder.end_rendering().unwrap();
+        let primary = builder.build().unwrap();
+
+        primary
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+    }
+
+    #[test]
+    fn descriptor_buffer_binding() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [ext_descriptor_buffer],
+            features: [descriptor_buffer, buffer_device_address],
+        );
+
+        let set_layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                flags: DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER,
+                bindings: [
+                    (
+                        0,
+                        DescriptorSetLayoutBinding {
+                            stages: ShaderStages::COMPUTE,
+                            ..DescriptorSetLayoutBinding::descriptor_type(
+                                DescriptorType::UniformBuffer,
+                            )
+                        },
+                    ),
+                    (
+                        1,
+                        DescriptorSetLayoutBinding {
+                            stages: ShaderStages::COMPUTE,
+                            ..DescriptorSetLayoutBinding::descriptor_type(
+                                DescriptorType::StorageBuffer,
+                            )
+                        },
+                    ),
+                ]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let pipeline_layout = PipelineLayout::new(
+            device.clone(),
+            PipelineLayoutCreateInfo {
+                set_layouts: vec![set_layout.clone()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1) in;
+
+            layout(set = 0, binding = 0) uniform Input {
+                uint value;
+            } input_data;
+
+            layout(set = 0, binding = 1) buffer Output {
+                uint value;
+            } output_data;
+
+            void main() {
+                output_data.value = input_data.value;
+            }
+            */
+            const MODULE: [u32; 212] = [
+                119734787, 65536, 28, 34, 0, 131089, 1, 720906, 1599492179, 1599227979, 1919906931,
+                1600481121, 1717990754, 1935635045, 1634889588, 1667196263, 1936941420, 0, 393227,
+                1, 1280527431, 1685353262, 808793134, 0, 196622, 0, 1, 327695, 5, 29, 1852399981,
+                0, 393232, 29, 17, 1, 1, 1, 327752, 4, 0, 35, 0, 327752, 5, 0, 35, 0, 262215, 9,
+                34, 0, 262215, 9, 33, 0, 196679, 10, 2, 327752, 10, 0, 35, 0, 262215, 12, 34, 0,
+                262215, 12, 33, 1, 196679, 13, 2, 327752, 13, 0, 35, 0, 131091, 2, 262165, 3, 32,
+                0, 196638, 4, 3, 196638, 5, 3, 262165, 7, 32, 1, 262187, 7, 6, 1, 262187, 7, 8, 0,
+                196638, 10, 4, 262176, 11, 2, 10, 262203, 11, 9, 2, 196638, 13, 5, 262176, 14, 12,
+                13, 262203, 14, 12, 12, 196641, 17, 2, 262176, 18, 2, 4, 262187, 3, 19, 0, 262176,
+                21, 12, 5, 262176, 24, 12, 3, 327734, 2, 16, 0, 17, 131320, 15, 327745, 18, 20, 9,
+                19, 327745, 21, 22, 12, 19, 131321, 23, 131320, 23, 262205, 4, 25, 20, 327761, 3,
+                26, 25, 0, 327745, 24, 27, 22, 19, 196670, 27, 26, 65789, 65592, 327734, 2, 29, 0,
+                17, 131320, 28, 327745, 18, 30, 9, 19, 327745, 21, 31, 12, 19, 131321, 32, 131320,
+                32, 262201, 2, 33, 16, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let pipeline = ComputePipeline::new(
+            device.clone(),
+            None,
+            ComputePipelineCreateInfo {
+                flags: PipelineCreateFlags::DESCRIPTOR_BUFFER,
+                ..ComputePipelineCreateInfo::stage_layout(
+                    PipelineShaderStageCreateInfo::new(cs),
+                    pipeline_layout.clone(),
+                )
+            },
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let input_buffer = Buffer::from_data(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::UNIFORM_BUFFER | BufferUsage::SHADER_DEVICE_ADDRESS,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            42u32,
+        )
+        .unwrap();
+        let output_buffer = Buffer::from_data(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER | BufferUsage::SHADER_DEVICE_ADDRESS,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            0u32,
+        )
+        .unwrap();
+        let descriptor_buffer = Buffer::new_slice::<u8>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::RESOURCE_DESCRIPTOR_BUFFER | BufferUsage::SHADER_DEVICE_ADDRESS,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            4096,
+        )
+        .unwrap();
+
+        // Write the descriptors of both bindings into the descriptor buffer.
+        {
+            let properties = device.physical_device().properties();
+            let fns = device.fns();
+            let mut descriptor_data = descriptor_buffer.write().unwrap();
+
+            for (binding, ty, buffer, size) in [
+                (
+                    0,
+                    ash::vk::DescriptorType::UNIFORM_BUFFER,
+                    &input_buffer,
+                    properties.uniform_buffer_descriptor_size.unwrap(),
+                ),
+                (
+                    1,
+                    ash::vk::DescriptorType::STORAGE_BUFFER,
+                    &output_buffer,
+                    properties.storage_buffer_descriptor_size.unwrap(),
+                ),
+            ] {
+                let mut offset = 0;
+                let address_info = ash::vk::DescriptorAddressInfoEXT {
+                    address: buffer.device_address().unwrap().get(),
+                    range: buffer.size(),
+                    format: ash::vk::Format::UNDEFINED,
+                    ..Default::default()
+                };
+                let get_info = ash::vk::DescriptorGetInfoEXT {
+                    ty,
+                    // Both union fields have the same type.
+                    data: ash::vk::DescriptorDataEXT {
+                        p_uniform_buffer: &address_info,
+                    },
+                    ..Default::default()
+                };
+
+                unsafe {
+                    (fns.ext_descriptor_buffer
+                        .get_descriptor_set_layout_binding_offset_ext)(
+                        device.handle(),
+                        set_layout.handle(),
+                        binding,
+                        &mut offset,
+                    );
+                    (fns.ext_descriptor_buffer.get_descriptor_ext)(
+                        device.handle(),
+                        &get_info,
+                        size,
+                        descriptor_data[offset as usize..][..size]
+                            .as_mut_ptr()
+                            .cast(),
+                    );
+                }
+            }
+        }
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe {
+            // The buffer index must refer to a bound descriptor buffer.
+            assert!(builder
+                .set_descriptor_buffer_offsets(
+                    PipelineBindPoint::Compute,
+                    pipeline_layout.clone(),
+                    0,
+                    smallvec![0],
+                    smallvec![0],
+                )
+                .is_err());
+
+            builder
+                .bind_descriptor_buffers(smallvec![DescriptorBufferBindingInfo::buffer(
+                    descriptor_buffer.clone()
+                )])
+                .unwrap()
+                .set_descriptor_buffer_offsets(
+                    PipelineBindPoint::Compute,
+                    pipeline_layout,
+                    0,
+                    smallvec![0],
+                    smallvec![0],
+                )
+                .unwrap();
+        }
+
+        builder
+            .bind_pipeline_compute(pipeline)
+            .unwrap()
+            .dispatch([1, 1, 1])
+            .unwrap();
+
+        // The buffers that the descriptors refer to are not tracked, so they are kept alive until
+        // the command buffer has finished executing.
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        assert_eq!(*output_buffer.read().unwrap(), 42);
+    }
 }
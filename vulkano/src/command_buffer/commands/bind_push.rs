@@ -19,16 +19,16 @@ use crate::{
         DescriptorSetWithOffsets, DescriptorSetsCollection, DescriptorWriteInfo,
         WriteDescriptorSet,
     },
-    device::{DeviceOwned, QueueFlags},
-    memory::is_aligned,
+    device::{Device, DeviceOwned, QueueFlags},
+    memory::{is_aligned, DeviceAlignment},
     pipeline::{
         graphics::vertex_input::VertexBuffersCollection, ComputePipeline, GraphicsPipeline,
-        PipelineBindPoint, PipelineLayout,
+        PipelineBindPoint, PipelineCreateFlags, PipelineLayout, SubpassShadingPipeline,
     },
     DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanObject,
 };
 use smallvec::SmallVec;
-use std::{cmp::min, ffi::c_void, mem::size_of, sync::Arc};
+use std::{cmp::min, ffi::c_void, mem::size_of, ptr, sync::Arc};
 
 /// # Commands to bind or push state for pipeline execution commands.
 ///
@@ -37,7 +37,68 @@ impl<L, A> AutoCommandBufferBuilder<L, A>
 where
     A: CommandBufferAllocator,
 {
+    /// Binds descriptor buffers, for use with future calls to
+    /// [`set_descriptor_buffer_offsets`](Self::set_descriptor_buffer_offsets).
+    ///
+    /// All previously bound descriptor buffers are unbound. The index of each element of
+    /// `binding_infos` is the buffer index that is given to `set_descriptor_buffer_offsets`.
+    ///
+    /// The [`descriptor_buffer`](crate::device::Features::descriptor_buffer) feature must be
+    /// enabled on the device.
+    ///
+    /// # Safety
+    ///
+    /// The contents of descriptor buffers are not tracked by vulkano. For every descriptor that
+    /// is accessed by a dispatch or draw command that uses these buffers:
+    /// - The descriptor data must have been written to the buffer before the command is executed,
+    ///   and must not be modified while the command is executing.
+    /// - The descriptor data must be valid for the descriptor type of the binding it is read for.
+    /// - The resources that the descriptor refers to must be kept alive until the command buffer
+    ///   has finished executing, and accesses to them must be synchronized manually.
+    pub unsafe fn bind_descriptor_buffers(
+        &mut self,
+        binding_infos: SmallVec<[DescriptorBufferBindingInfo; 2]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_descriptor_buffers(&binding_infos)?;
+
+        Ok(self.bind_descriptor_buffers_unchecked(binding_infos))
+    }
+
+    fn validate_bind_descriptor_buffers(
+        &self,
+        binding_infos: &[DescriptorBufferBindingInfo],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_bind_descriptor_buffers(binding_infos)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_descriptor_buffers_unchecked(
+        &mut self,
+        binding_infos: SmallVec<[DescriptorBufferBindingInfo; 2]>,
+    ) -> &mut Self {
+        self.builder_state.descriptor_buffers = binding_infos.clone();
+
+        self.add_command(
+            "bind_descriptor_buffers",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.bind_descriptor_buffers_unchecked(&binding_infos);
+            },
+        );
+
+        self
+    }
+
     /// Binds descriptor sets for future dispatch or draw calls.
+    ///
+    /// The layouts of `descriptor_sets` must be compatible with the set layouts of
+    /// `pipeline_layout`, starting at `first_set`, otherwise an error is returned immediately.
+    /// The pipeline that is bound to `pipeline_bind_point` at the time of a dispatch or draw call
+    /// is not known yet when binding, so the compatibility of `pipeline_layout` with the layout
+    /// of that pipeline, including its push constant ranges, is checked by the dispatch or draw
+    /// command instead.
     pub fn bind_descriptor_sets(
         &mut self,
         pipeline_bind_point: PipelineBindPoint,
@@ -216,6 +277,14 @@ where
         // VUID-vkCmdBindPipeline-pipeline-00781
         // TODO:
 
+        if self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is active".into(),
+                vuids: &["VUID-vkCmdBindPipeline-None-02323"],
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 
@@ -244,6 +313,43 @@ where
         self
     }
 
+    /// Binds a subpass shading pipeline for future subpass shading calls.
+    pub fn bind_pipeline_subpass_shading(
+        &mut self,
+        pipeline: Arc<SubpassShadingPipeline>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_pipeline_subpass_shading(&pipeline)?;
+
+        unsafe { Ok(self.bind_pipeline_subpass_shading_unchecked(pipeline)) }
+    }
+
+    fn validate_bind_pipeline_subpass_shading(
+        &self,
+        pipeline: &SubpassShadingPipeline,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_bind_pipeline_subpass_shading(pipeline)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_pipeline_subpass_shading_unchecked(
+        &mut self,
+        pipeline: Arc<SubpassShadingPipeline>,
+    ) -> &mut Self {
+        self.builder_state.pipeline_subpass_shading = Some(pipeline.clone());
+        self.add_command(
+            "bind_pipeline_subpass_shading",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.bind_pipeline_subpass_shading_unchecked(&pipeline);
+            },
+        );
+
+        self
+    }
+
     /// Binds vertex buffers for future draw calls.
     pub fn bind_vertex_buffers(
         &mut self,
@@ -445,12 +551,345 @@ where
 
         self
     }
+
+    /// Sets the offsets into the bound descriptor buffers that the descriptors of a range of
+    /// descriptor sets are read from, for future dispatch or draw calls.
+    ///
+    /// For each set number starting at `first_set`, the corresponding element of `buffer_indices`
+    /// selects one of the buffers that were bound with
+    /// [`bind_descriptor_buffers`](Self::bind_descriptor_buffers), and the corresponding element
+    /// of `offsets` is the offset in bytes into that buffer. The descriptor set layouts of
+    /// `pipeline_layout` for these set numbers must have been created with
+    /// [`DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`].
+    ///
+    /// Like [`bind_descriptor_sets`](Self::bind_descriptor_sets), this disturbs previously bound
+    /// descriptor sets whose layouts are not compatible with `pipeline_layout`.
+    ///
+    /// # Safety
+    ///
+    /// - The descriptor data of each set must start at the given offset, and must have been
+    ///   written according to the layout of that set in `pipeline_layout`.
+    /// - The same requirements as for
+    ///   [`bind_descriptor_buffers`](Self::bind_descriptor_buffers) apply to the descriptors.
+    pub unsafe fn set_descriptor_buffer_offsets(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: Arc<PipelineLayout>,
+        first_set: u32,
+        buffer_indices: SmallVec<[u32; 4]>,
+        offsets: SmallVec<[DeviceSize; 4]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_descriptor_buffer_offsets(
+            pipeline_bind_point,
+            &pipeline_layout,
+            first_set,
+            &buffer_indices,
+            &offsets,
+        )?;
+
+        Ok(self.set_descriptor_buffer_offsets_unchecked(
+            pipeline_bind_point,
+            pipeline_layout,
+            first_set,
+            buffer_indices,
+            offsets,
+        ))
+    }
+
+    fn validate_set_descriptor_buffer_offsets(
+        &self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: &PipelineLayout,
+        first_set: u32,
+        buffer_indices: &[u32],
+        offsets: &[DeviceSize],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_descriptor_buffer_offsets(
+            pipeline_bind_point,
+            pipeline_layout,
+            first_set,
+            buffer_indices,
+            offsets,
+        )?;
+
+        for (index, (&buffer_index, &offset)) in buffer_indices.iter().zip(offsets).enumerate() {
+            let binding_info = self
+                .builder_state
+                .descriptor_buffers
+                .get(buffer_index as usize)
+                .ok_or_else(|| {
+                    Box::new(ValidationError {
+                        context: format!("buffer_indices[{}]", index).into(),
+                        problem: "is not less than the number of currently bound descriptor \
+                            buffers"
+                            .into(),
+                        vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-pBufferIndices-08065"],
+                        ..Default::default()
+                    })
+                })?;
+
+            // TODO: Check that the whole set fits in the buffer. This needs
+            // `vkGetDescriptorSetLayoutSizeEXT`.
+            if offset >= binding_info.buffer.size() {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`offsets[{0}]` is not less than the size of the descriptor buffer \
+                        selected by `buffer_indices[{0}]`",
+                        index,
+                    )
+                    .into(),
+                    vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-pOffsets-08063"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_descriptor_buffer_offsets_unchecked(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: Arc<PipelineLayout>,
+        first_set: u32,
+        buffer_indices: SmallVec<[u32; 4]>,
+        offsets: SmallVec<[DeviceSize; 4]>,
+    ) -> &mut Self {
+        if buffer_indices.is_empty() {
+            return self;
+        }
+
+        let state = self.builder_state.invalidate_descriptor_sets(
+            pipeline_bind_point,
+            pipeline_layout.clone(),
+            first_set,
+            buffer_indices.len() as u32,
+        );
+
+        for (set_num, (&buffer_index, &offset)) in buffer_indices.iter().zip(&offsets).enumerate() {
+            state.descriptor_sets.insert(
+                first_set + set_num as u32,
+                SetOrPush::Buffer {
+                    buffer_index,
+                    offset,
+                },
+            );
+        }
+
+        self.add_command(
+            "set_descriptor_buffer_offsets",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_descriptor_buffer_offsets_unchecked(
+                    pipeline_bind_point,
+                    &pipeline_layout,
+                    first_set,
+                    &buffer_indices,
+                    &offsets,
+                );
+            },
+        );
+
+        self
+    }
 }
 
 impl<A> UnsafeCommandBufferBuilder<A>
 where
     A: CommandBufferAllocator,
 {
+    pub unsafe fn bind_descriptor_buffers(
+        &mut self,
+        binding_infos: &[DescriptorBufferBindingInfo],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_descriptor_buffers(binding_infos)?;
+
+        Ok(self.bind_descriptor_buffers_unchecked(binding_infos))
+    }
+
+    fn validate_bind_descriptor_buffers(
+        &self,
+        binding_infos: &[DescriptorBufferBindingInfo],
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().descriptor_buffer {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "descriptor_buffer",
+                )])]),
+                vuids: &["VUID-vkCmdBindDescriptorBuffersEXT-None-08047"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics or compute operations"
+                    .into(),
+                vuids: &["VUID-vkCmdBindDescriptorBuffersEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        if binding_infos.len() as u32 > properties.max_descriptor_buffer_bindings.unwrap_or(0) {
+            return Err(Box::new(ValidationError {
+                context: "binding_infos".into(),
+                problem: "the length exceeds the `max_descriptor_buffer_bindings` limit".into(),
+                vuids: &["VUID-vkCmdBindDescriptorBuffersEXT-bufferCount-08051"],
+                ..Default::default()
+            }));
+        }
+
+        let mut sampler_buffer_count = 0;
+        let mut resource_buffer_count = 0;
+        let mut push_descriptor_buffer_count = 0;
+
+        for (index, binding_info) in binding_infos.iter().enumerate() {
+            binding_info
+                .validate(self.device())
+                .map_err(|err| err.add_context(format!("binding_infos[{}]", index)))?;
+
+            if binding_info
+                .usage
+                .intersects(BufferUsage::SAMPLER_DESCRIPTOR_BUFFER)
+            {
+                sampler_buffer_count += 1;
+            }
+
+            if binding_info
+                .usage
+                .intersects(BufferUsage::RESOURCE_DESCRIPTOR_BUFFER)
+            {
+                resource_buffer_count += 1;
+            }
+
+            if binding_info
+                .usage
+                .intersects(BufferUsage::PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER)
+            {
+                push_descriptor_buffer_count += 1;
+            }
+        }
+
+        if sampler_buffer_count
+            > properties
+                .max_sampler_descriptor_buffer_bindings
+                .unwrap_or(0)
+        {
+            return Err(Box::new(ValidationError {
+                context: "binding_infos".into(),
+                problem: "the number of elements whose `usage` contains \
+                    `BufferUsage::SAMPLER_DESCRIPTOR_BUFFER` exceeds the \
+                    `max_sampler_descriptor_buffer_bindings` limit"
+                    .into(),
+                vuids: &[
+                    "VUID-vkCmdBindDescriptorBuffersEXT-maxSamplerDescriptorBufferBindings-08048",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        if resource_buffer_count
+            > properties
+                .max_resource_descriptor_buffer_bindings
+                .unwrap_or(0)
+        {
+            return Err(Box::new(ValidationError {
+                context: "binding_infos".into(),
+                problem: "the number of elements whose `usage` contains \
+                    `BufferUsage::RESOURCE_DESCRIPTOR_BUFFER` exceeds the \
+                    `max_resource_descriptor_buffer_bindings` limit"
+                    .into(),
+                vuids: &[
+                    "VUID-vkCmdBindDescriptorBuffersEXT-maxResourceDescriptorBufferBindings-08049",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        if push_descriptor_buffer_count > 1 {
+            return Err(Box::new(ValidationError {
+                context: "binding_infos".into(),
+                problem: "contains more than one element whose `usage` contains \
+                    `BufferUsage::PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER`"
+                    .into(),
+                vuids: &["VUID-vkCmdBindDescriptorBuffersEXT-None-08050"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_descriptor_buffers_unchecked(
+        &mut self,
+        binding_infos: &[DescriptorBufferBindingInfo],
+    ) -> &mut Self {
+        if binding_infos.is_empty() {
+            return self;
+        }
+
+        let push_descriptor_buffer_handles_vk: SmallVec<[_; 2]> = binding_infos
+            .iter()
+            .map(
+                |binding_info| ash::vk::DescriptorBufferBindingPushDescriptorBufferHandleEXT {
+                    buffer: binding_info.buffer.buffer().handle(),
+                    ..Default::default()
+                },
+            )
+            .collect();
+        let bufferless_push_descriptors = self
+            .device()
+            .physical_device()
+            .properties()
+            .bufferless_push_descriptors
+            .unwrap_or(false);
+
+        let binding_infos_vk: SmallVec<[_; 2]> = binding_infos
+            .iter()
+            .zip(&push_descriptor_buffer_handles_vk)
+            .map(|(binding_info, push_descriptor_buffer_handle_vk)| {
+                let &DescriptorBufferBindingInfo {
+                    ref buffer,
+                    usage,
+                    _ne: _,
+                } = binding_info;
+
+                let p_next = if usage.intersects(BufferUsage::PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER)
+                    && !bufferless_push_descriptors
+                {
+                    push_descriptor_buffer_handle_vk as *const _ as *mut _
+                } else {
+                    ptr::null_mut()
+                };
+
+                ash::vk::DescriptorBufferBindingInfoEXT {
+                    p_next,
+                    address: buffer.device_address_unchecked().get(),
+                    usage: usage.into(),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        let fns = self.device().fns();
+        (fns.ext_descriptor_buffer.cmd_bind_descriptor_buffers_ext)(
+            self.handle(),
+            binding_infos_vk.len() as u32,
+            binding_infos_vk.as_ptr(),
+        );
+
+        self
+    }
+
     pub unsafe fn bind_descriptor_sets(
         &mut self,
         pipeline_bind_point: PipelineBindPoint,
@@ -509,16 +948,16 @@ where
                     }));
                 }
             }
-            PipelineBindPoint::Graphics => {
+            PipelineBindPoint::Graphics | PipelineBindPoint::SubpassShading => {
                 if !queue_family_properties
                     .queue_flags
                     .intersects(QueueFlags::GRAPHICS)
                 {
                     return Err(Box::new(ValidationError {
                         context: "pipeline_bind_point".into(),
-                        problem: "is `PipelineBindPoint::Graphics`, but \
-                            the queue family of the command buffer does not support \
-                            graphics operations"
+                        problem: "is `PipelineBindPoint::Graphics` or \
+                            `PipelineBindPoint::SubpassShading`, but the queue family of the \
+                            command buffer does not support graphics operations"
                             .into(),
                         vuids: &[
                             "VUID-vkCmdBindDescriptorSets-pipelineBindPoint-00361",
@@ -829,6 +1268,22 @@ where
         // VUID-vkCmdBindPipeline-commonparent
         assert_eq!(self.device(), pipeline.device());
 
+        // TODO: Protected command buffers are not supported yet, so every command buffer is
+        // unprotected.
+        if pipeline
+            .flags()
+            .intersects(PipelineCreateFlags::PROTECTED_ACCESS_ONLY)
+        {
+            return Err(Box::new(ValidationError {
+                context: "pipeline.flags()".into(),
+                problem: "contains `PipelineCreateFlags::PROTECTED_ACCESS_ONLY`, but the \
+                    command buffer is not a protected command buffer"
+                    .into(),
+                vuids: &["VUID-vkCmdBindPipeline-pipelineProtectedAccess-07409"],
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 
@@ -877,6 +1332,31 @@ where
         // VUID-vkCmdBindPipeline-commonparent
         assert_eq!(self.device(), pipeline.device());
 
+        // TODO: Protected command buffers are not supported yet, so every command buffer is
+        // unprotected.
+        if pipeline
+            .flags()
+            .intersects(PipelineCreateFlags::PROTECTED_ACCESS_ONLY)
+        {
+            return Err(Box::new(ValidationError {
+                context: "pipeline.flags()".into(),
+                problem: "contains `PipelineCreateFlags::PROTECTED_ACCESS_ONLY`, but the \
+                    command buffer is not a protected command buffer"
+                    .into(),
+                vuids: &["VUID-vkCmdBindPipeline-pipelineProtectedAccess-07409"],
+                ..Default::default()
+            }));
+        }
+
+        if pipeline.flags().intersects(PipelineCreateFlags::LIBRARY) {
+            return Err(Box::new(ValidationError {
+                context: "pipeline.flags()".into(),
+                problem: "contains `PipelineCreateFlags::LIBRARY`".into(),
+                vuids: &["VUID-vkCmdBindPipeline-pipeline-03382"],
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 
@@ -895,6 +1375,70 @@ where
         self
     }
 
+    pub unsafe fn bind_pipeline_subpass_shading(
+        &mut self,
+        pipeline: &SubpassShadingPipeline,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_pipeline_subpass_shading(pipeline)?;
+
+        Ok(self.bind_pipeline_subpass_shading_unchecked(pipeline))
+    }
+
+    fn validate_bind_pipeline_subpass_shading(
+        &self,
+        pipeline: &SubpassShadingPipeline,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdBindPipeline-pipelineBindPoint-04949"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdBindPipeline-commonparent
+        assert_eq!(self.device(), pipeline.device());
+
+        // TODO: Protected command buffers are not supported yet, so every command buffer is
+        // unprotected.
+        if pipeline
+            .flags()
+            .intersects(PipelineCreateFlags::PROTECTED_ACCESS_ONLY)
+        {
+            return Err(Box::new(ValidationError {
+                context: "pipeline.flags()".into(),
+                problem: "contains `PipelineCreateFlags::PROTECTED_ACCESS_ONLY`, but the \
+                    command buffer is not a protected command buffer"
+                    .into(),
+                vuids: &["VUID-vkCmdBindPipeline-pipelineProtectedAccess-07409"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_pipeline_subpass_shading_unchecked(
+        &mut self,
+        pipeline: &SubpassShadingPipeline,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.v1_0.cmd_bind_pipeline)(
+            self.handle(),
+            ash::vk::PipelineBindPoint::SUBPASS_SHADING_HUAWEI,
+            pipeline.handle(),
+        );
+
+        self
+    }
+
     pub unsafe fn bind_vertex_buffers(
         &mut self,
         first_binding: u32,
@@ -1210,15 +1754,16 @@ where
                     }));
                 }
             }
-            PipelineBindPoint::Graphics => {
+            PipelineBindPoint::Graphics | PipelineBindPoint::SubpassShading => {
                 if !queue_family_properties
                     .queue_flags
                     .intersects(QueueFlags::GRAPHICS)
                 {
                     return Err(Box::new(ValidationError {
                         context: "self".into(),
-                        problem: "`pipeline_bind_point` is `PipelineBindPoint::Graphics`, and the \
-                            queue family does not support graphics operations"
+                        problem: "`pipeline_bind_point` is `PipelineBindPoint::Graphics` or \
+                            `PipelineBindPoint::SubpassShading`, and the queue family does not \
+                            support graphics operations"
                             .into(),
                         vuids: &[
                             "VUID-vkCmdPushDescriptorSetKHR-pipelineBindPoint-00363",
@@ -1233,9 +1778,9 @@ where
         // VUID-vkCmdPushDescriptorSetKHR-commonparent
         assert_eq!(self.device(), pipeline_layout.device());
 
-        if set_num as usize > pipeline_layout.set_layouts().len() {
+        if set_num as usize >= pipeline_layout.set_layouts().len() {
             return Err(Box::new(ValidationError {
-                problem: "`set_num` is greater than the number of descriptor set layouts in \
+                problem: "`set_num` is not less than the number of descriptor set layouts in \
                     `pipeline_layout`"
                     .into(),
                 vuids: &["VUID-vkCmdPushDescriptorSetKHR-set-00364"],
@@ -1352,4 +1897,280 @@ where
 
         self
     }
+
+    pub unsafe fn set_descriptor_buffer_offsets(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: &PipelineLayout,
+        first_set: u32,
+        buffer_indices: &[u32],
+        offsets: &[DeviceSize],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_descriptor_buffer_offsets(
+            pipeline_bind_point,
+            pipeline_layout,
+            first_set,
+            buffer_indices,
+            offsets,
+        )?;
+
+        Ok(self.set_descriptor_buffer_offsets_unchecked(
+            pipeline_bind_point,
+            pipeline_layout,
+            first_set,
+            buffer_indices,
+            offsets,
+        ))
+    }
+
+    fn validate_set_descriptor_buffer_offsets(
+        &self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: &PipelineLayout,
+        first_set: u32,
+        buffer_indices: &[u32],
+        offsets: &[DeviceSize],
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().descriptor_buffer {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "descriptor_buffer",
+                )])]),
+                vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-None-08060"],
+                ..Default::default()
+            }));
+        }
+
+        pipeline_bind_point
+            .validate_device(self.device())
+            .map_err(|err| {
+                err.add_context("pipeline_bind_point").set_vuids(&[
+                    "VUID-vkCmdSetDescriptorBufferOffsetsEXT-pipelineBindPoint-parameter",
+                ])
+            })?;
+
+        let required_queue_flags = match pipeline_bind_point {
+            PipelineBindPoint::Compute => QueueFlags::COMPUTE,
+            PipelineBindPoint::Graphics | PipelineBindPoint::SubpassShading => QueueFlags::GRAPHICS,
+        };
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(required_queue_flags)
+        {
+            return Err(Box::new(ValidationError {
+                context: "pipeline_bind_point".into(),
+                problem: "the queue family of the command buffer does not support \
+                    operations of this pipeline type"
+                    .into(),
+                vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-pipelineBindPoint-08067"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdSetDescriptorBufferOffsetsEXT-commonparent
+        assert_eq!(self.device(), pipeline_layout.device());
+
+        if buffer_indices.len() != offsets.len() {
+            return Err(Box::new(ValidationError {
+                problem: "`buffer_indices` and `offsets` do not have the same length".into(),
+                ..Default::default()
+            }));
+        }
+
+        if first_set + buffer_indices.len() as u32 > pipeline_layout.set_layouts().len() as u32 {
+            return Err(Box::new(ValidationError {
+                problem: "`first_set + buffer_indices.len()` is greater than \
+                    `pipeline_layout.set_layouts().len()`"
+                    .into(),
+                vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-firstSet-08066"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = self.device().physical_device().properties();
+        let max_descriptor_buffer_bindings = properties.max_descriptor_buffer_bindings.unwrap_or(0);
+        let descriptor_buffer_offset_alignment = properties
+            .descriptor_buffer_offset_alignment
+            .and_then(DeviceAlignment::new)
+            .unwrap_or(DeviceAlignment::MIN);
+
+        for (index, (&buffer_index, &offset)) in buffer_indices.iter().zip(offsets).enumerate() {
+            let set_num = first_set + index as u32;
+
+            if !pipeline_layout.set_layouts()[set_num as usize]
+                .flags()
+                .intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`pipeline_layout.set_layouts()[{}].flags()` does not contain \
+                        `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`",
+                        set_num,
+                    )
+                    .into(),
+                    vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-firstSet-09006"],
+                    ..Default::default()
+                }));
+            }
+
+            if buffer_index >= max_descriptor_buffer_bindings {
+                return Err(Box::new(ValidationError {
+                    context: format!("buffer_indices[{}]", index).into(),
+                    problem: "is not less than the `max_descriptor_buffer_bindings` limit".into(),
+                    vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-pBufferIndices-08064"],
+                    ..Default::default()
+                }));
+            }
+
+            if !is_aligned(offset, descriptor_buffer_offset_alignment) {
+                return Err(Box::new(ValidationError {
+                    context: format!("offsets[{}]", index).into(),
+                    problem: "is not a multiple of the `descriptor_buffer_offset_alignment` \
+                        device property"
+                        .into(),
+                    vuids: &["VUID-vkCmdSetDescriptorBufferOffsetsEXT-pOffsets-08061"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_descriptor_buffer_offsets_unchecked(
+        &mut self,
+        pipeline_bind_point: PipelineBindPoint,
+        pipeline_layout: &PipelineLayout,
+        first_set: u32,
+        buffer_indices: &[u32],
+        offsets: &[DeviceSize],
+    ) -> &mut Self {
+        if buffer_indices.is_empty() {
+            return self;
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_descriptor_buffer
+            .cmd_set_descriptor_buffer_offsets_ext)(
+            self.handle(),
+            pipeline_bind_point.into(),
+            pipeline_layout.handle(),
+            first_set,
+            buffer_indices.len() as u32,
+            buffer_indices.as_ptr(),
+            offsets.as_ptr(),
+        );
+
+        self
+    }
+}
+
+/// Parameters to bind a descriptor buffer.
+#[derive(Clone, Debug)]
+pub struct DescriptorBufferBindingInfo {
+    /// The buffer to bind.
+    ///
+    /// The buffer must have been created with [`BufferUsage::SHADER_DEVICE_ADDRESS`], and the
+    /// start of the subbuffer must be a multiple of the
+    /// [`descriptor_buffer_offset_alignment`](crate::device::Properties::descriptor_buffer_offset_alignment)
+    /// device property.
+    ///
+    /// There is no default value.
+    pub buffer: Subbuffer<[u8]>,
+
+    /// The kinds of descriptors that the buffer contains.
+    ///
+    /// This must contain at least one of [`BufferUsage::SAMPLER_DESCRIPTOR_BUFFER`],
+    /// [`BufferUsage::RESOURCE_DESCRIPTOR_BUFFER`] and
+    /// [`BufferUsage::PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER`], and must only contain usages that
+    /// the buffer was created with.
+    ///
+    /// The default value is the descriptor buffer usages that the buffer was created with.
+    pub usage: BufferUsage,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl DescriptorBufferBindingInfo {
+    /// Returns a `DescriptorBufferBindingInfo` with the specified `buffer`.
+    #[inline]
+    pub fn buffer(buffer: Subbuffer<impl ?Sized>) -> Self {
+        let usage = buffer.buffer().usage()
+            & (BufferUsage::SAMPLER_DESCRIPTOR_BUFFER
+                | BufferUsage::RESOURCE_DESCRIPTOR_BUFFER
+                | BufferUsage::PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER);
+
+        Self {
+            buffer: buffer.into_bytes(),
+            usage,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            ref buffer,
+            usage,
+            _ne: _,
+        } = self;
+
+        // VUID-vkCmdBindDescriptorBuffersEXT-commonparent
+        assert_eq!(device, buffer.device().as_ref());
+
+        usage.validate_device(device).map_err(|err| {
+            err.add_context("usage")
+                .set_vuids(&["VUID-VkDescriptorBufferBindingInfoEXT-usage-parameter"])
+        })?;
+
+        if !usage.intersects(
+            BufferUsage::SAMPLER_DESCRIPTOR_BUFFER
+                | BufferUsage::RESOURCE_DESCRIPTOR_BUFFER
+                | BufferUsage::PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER,
+        ) {
+            return Err(Box::new(ValidationError {
+                context: "usage".into(),
+                problem: "does not contain `BufferUsage::SAMPLER_DESCRIPTOR_BUFFER`, \
+                    `BufferUsage::RESOURCE_DESCRIPTOR_BUFFER` or \
+                    `BufferUsage::PUSH_DESCRIPTORS_DESCRIPTOR_BUFFER`"
+                    .into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        if !buffer.buffer().usage().contains(usage) {
+            return Err(Box::new(ValidationError {
+                problem: "`usage` contains usages that `buffer` was not created with".into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        let address = buffer
+            .device_address()
+            .map_err(|err| err.add_context("buffer"))?;
+
+        let descriptor_buffer_offset_alignment = device
+            .physical_device()
+            .properties()
+            .descriptor_buffer_offset_alignment
+            .and_then(DeviceAlignment::new)
+            .unwrap_or(DeviceAlignment::MIN);
+
+        if !is_aligned(address.get(), descriptor_buffer_offset_alignment) {
+            return Err(Box::new(ValidationError {
+                context: "buffer".into(),
+                problem: "the device address is not a multiple of the \
+                    `descriptor_buffer_offset_alignment` device property"
+                    .into(),
+                vuids: &["VUID-VkDescriptorBufferBindingInfoEXT-address-08057"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
 }
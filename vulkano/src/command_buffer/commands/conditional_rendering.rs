@@ -0,0 +1,310 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::{BufferUsage, Subbuffer},
+    command_buffer::{
+        allocator::CommandBufferAllocator,
+        auto::{ConditionalRenderingState, Resource},
+        sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand,
+    },
+    device::{DeviceOwned, QueueFlags},
+    macros::vulkan_bitflags,
+    sync::PipelineStageAccessFlags,
+    Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanObject,
+};
+
+/// # Commands for conditional rendering.
+///
+/// These commands require the [`ext_conditional_rendering`] extension to be enabled on the
+/// device.
+///
+/// [`ext_conditional_rendering`]: crate::device::DeviceExtensions::ext_conditional_rendering
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Begins a conditional rendering block.
+    ///
+    /// Until [`end_conditional_rendering`](Self::end_conditional_rendering) is called, draw,
+    /// dispatch and attachment clear commands are discarded when the `u32` value in `buffer` is
+    /// zero, at the time that the commands are executed. If `flags` contains
+    /// [`ConditionalRenderingFlags::INVERTED`], they are discarded when the value is not zero
+    /// instead.
+    ///
+    /// If the block is begun within a render subpass, then it must also be ended within that same
+    /// subpass.
+    pub fn begin_conditional_rendering(
+        &mut self,
+        buffer: Subbuffer<u32>,
+        flags: ConditionalRenderingFlags,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_begin_conditional_rendering(&buffer, flags)?;
+
+        unsafe { Ok(self.begin_conditional_rendering_unchecked(buffer, flags)) }
+    }
+
+    fn validate_begin_conditional_rendering(
+        &self,
+        buffer: &Subbuffer<u32>,
+        flags: ConditionalRenderingFlags,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_begin_conditional_rendering(buffer, flags)?;
+
+        if self.builder_state.conditional_rendering.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering is already active".into(),
+                vuids: &["VUID-vkCmdBeginConditionalRenderingEXT-None-01980"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn begin_conditional_rendering_unchecked(
+        &mut self,
+        buffer: Subbuffer<u32>,
+        flags: ConditionalRenderingFlags,
+    ) -> &mut Self {
+        self.builder_state.conditional_rendering = Some(ConditionalRenderingState {
+            in_subpass: self.builder_state.render_pass.is_some(),
+        });
+
+        self.add_command(
+            "begin_conditional_rendering",
+            [(
+                ResourceInCommand::ConditionalRenderingBuffer.into(),
+                Resource::Buffer {
+                    buffer: buffer.as_bytes().clone(),
+                    range: 0..buffer.size(),
+                    memory_access:
+                        PipelineStageAccessFlags::ConditionalRendering_ConditionalRenderingRead,
+                },
+            )]
+            .into_iter()
+            .collect(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.begin_conditional_rendering_unchecked(&buffer, flags);
+            },
+        );
+
+        self
+    }
+
+    /// Ends an active conditional rendering block.
+    pub fn end_conditional_rendering(&mut self) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_end_conditional_rendering()?;
+
+        unsafe { Ok(self.end_conditional_rendering_unchecked()) }
+    }
+
+    fn validate_end_conditional_rendering(&self) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_end_conditional_rendering()?;
+
+        let state = self
+            .builder_state
+            .conditional_rendering
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "conditional rendering is not active".into(),
+                    vuids: &["VUID-vkCmdEndConditionalRenderingEXT-None-01985"],
+                    ..Default::default()
+                })
+            })?;
+
+        if self.builder_state.render_pass.is_some() != state.in_subpass {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering was not begun within the current render subpass, \
+                    or outside a render pass instance if none is active"
+                    .into(),
+                vuids: &[
+                    "VUID-vkCmdEndConditionalRenderingEXT-None-01986",
+                    "VUID-vkCmdEndConditionalRenderingEXT-None-01987",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn end_conditional_rendering_unchecked(&mut self) -> &mut Self {
+        self.builder_state.conditional_rendering = None;
+
+        self.add_command(
+            "end_conditional_rendering",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.end_conditional_rendering_unchecked();
+            },
+        );
+
+        self
+    }
+}
+
+impl<A> UnsafeCommandBufferBuilder<A>
+where
+    A: CommandBufferAllocator,
+{
+    pub unsafe fn begin_conditional_rendering(
+        &mut self,
+        buffer: &Subbuffer<u32>,
+        flags: ConditionalRenderingFlags,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_begin_conditional_rendering(buffer, flags)?;
+
+        Ok(self.begin_conditional_rendering_unchecked(buffer, flags))
+    }
+
+    fn validate_begin_conditional_rendering(
+        &self,
+        buffer: &Subbuffer<u32>,
+        flags: ConditionalRenderingFlags,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_extensions().ext_conditional_rendering {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_conditional_rendering",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics or compute operations"
+                    .into(),
+                vuids: &["VUID-vkCmdBeginConditionalRenderingEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        flags.validate_device(device).map_err(|err| {
+            err.add_context("flags")
+                .set_vuids(&["VUID-VkConditionalRenderingBeginInfoEXT-flags-parameter"])
+        })?;
+
+        // VUID-VkConditionalRenderingBeginInfoEXT-buffer-parameter
+        assert_eq!(device, buffer.device());
+
+        if !buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::CONDITIONAL_RENDERING)
+        {
+            return Err(Box::new(ValidationError {
+                context: "buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::CONDITIONAL_RENDERING`".into(),
+                vuids: &["VUID-VkConditionalRenderingBeginInfoEXT-buffer-01982"],
+                ..Default::default()
+            }));
+        }
+
+        if buffer.offset() % 4 != 0 {
+            return Err(Box::new(ValidationError {
+                context: "buffer.offset()".into(),
+                problem: "is not a multiple of 4".into(),
+                vuids: &["VUID-VkConditionalRenderingBeginInfoEXT-offset-01984"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn begin_conditional_rendering_unchecked(
+        &mut self,
+        buffer: &Subbuffer<u32>,
+        flags: ConditionalRenderingFlags,
+    ) -> &mut Self {
+        let conditional_rendering_begin_info_vk = ash::vk::ConditionalRenderingBeginInfoEXT {
+            buffer: buffer.buffer().handle(),
+            offset: buffer.offset(),
+            flags: flags.into(),
+            ..Default::default()
+        };
+
+        let fns = self.device().fns();
+        (fns.ext_conditional_rendering
+            .cmd_begin_conditional_rendering_ext)(
+            self.handle(),
+            &conditional_rendering_begin_info_vk,
+        );
+
+        self
+    }
+
+    pub unsafe fn end_conditional_rendering(&mut self) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_end_conditional_rendering()?;
+
+        Ok(self.end_conditional_rendering_unchecked())
+    }
+
+    fn validate_end_conditional_rendering(&self) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_extensions().ext_conditional_rendering {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_conditional_rendering",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS | QueueFlags::COMPUTE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics or compute operations"
+                    .into(),
+                vuids: &["VUID-vkCmdEndConditionalRenderingEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn end_conditional_rendering_unchecked(&mut self) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_conditional_rendering
+            .cmd_end_conditional_rendering_ext)(self.handle());
+
+        self
+    }
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Flags that control how a conditional rendering block is executed.
+    ConditionalRenderingFlags = ConditionalRenderingFlagsEXT(u32);
+
+    /// Inverts the predicate, so that commands are discarded if the value in the buffer is not
+    /// zero, instead of if it is zero.
+    INVERTED = INVERTED,
+}
@@ -510,6 +510,59 @@ where
         self
     }
 
+    /// Generates the contents of all mip levels of `image` from its first mip level.
+    ///
+    /// Each mip level is blitted to the next one with [`Filter::Linear`], for all array layers of
+    /// the image. The image layouts are transitioned automatically, and the first mip level must
+    /// contain the data to generate the other mip levels from when the command is executed.
+    /// If the image has only one mip level, this does nothing.
+    ///
+    /// The image must have been created with both [`ImageUsage::TRANSFER_SRC`] and
+    /// [`ImageUsage::TRANSFER_DST`], and its format features must contain
+    /// [`FormatFeatures::BLIT_SRC`], [`FormatFeatures::BLIT_DST`] and
+    /// [`FormatFeatures::SAMPLED_IMAGE_FILTER_LINEAR`].
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `image` was not created with `device`.
+    pub fn generate_mipmaps(
+        &mut self,
+        image: Arc<Image>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_generate_mipmaps(&image)?;
+
+        unsafe { Ok(self.generate_mipmaps_unchecked(image)) }
+    }
+
+    fn validate_generate_mipmaps(&self, image: &Arc<Image>) -> Result<(), Box<ValidationError>> {
+        if !image
+            .format_features()
+            .intersects(FormatFeatures::SAMPLED_IMAGE_FILTER_LINEAR)
+        {
+            return Err(Box::new(ValidationError {
+                context: "image.format_features()".into(),
+                problem: "does not contain `FormatFeatures::SAMPLED_IMAGE_FILTER_LINEAR`".into(),
+                vuids: &["VUID-VkBlitImageInfo2-filter-02001"],
+                ..Default::default()
+            }));
+        }
+
+        for dst_mip_level in 1..image.mip_levels() {
+            self.validate_blit_image(&mipmap_blit_info(image, dst_mip_level))?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn generate_mipmaps_unchecked(&mut self, image: Arc<Image>) -> &mut Self {
+        for dst_mip_level in 1..image.mip_levels() {
+            self.blit_image_unchecked(mipmap_blit_info(&image, dst_mip_level));
+        }
+
+        self
+    }
+
     /// Resolves a multisampled image into a single-sampled image.
     ///
     /// # Panics
@@ -2531,20 +2584,10 @@ impl CopyImageInfo {
         let src_image_format = src_image.format();
         let src_image_format_aspects = src_image_format.aspects();
         let src_image_format_planes = src_image_format.planes();
-        let src_image_format_subsampled_extent = src_image_format
-            .ycbcr_chroma_sampling()
-            .map_or(src_image.extent(), |s| {
-                s.subsampled_extent(src_image.extent())
-            });
 
         let dst_image_format = dst_image.format();
         let dst_image_format_aspects = dst_image_format.aspects();
         let dst_image_format_planes = dst_image_format.planes();
-        let dst_image_format_subsampled_extent = dst_image_format
-            .ycbcr_chroma_sampling()
-            .map_or(dst_image.extent(), |s| {
-                s.subsampled_extent(dst_image.extent())
-            });
 
         if device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_maintenance1 {
             if !src_image
@@ -2672,8 +2715,14 @@ impl CopyImageInfo {
             }
 
             let mut src_subresource_format = src_image_format;
-            let mut src_subresource_extent =
+            let src_mip_level_extent =
                 mip_level_extent(src_image.extent(), src_subresource.mip_level).unwrap();
+            let mut src_subresource_extent = src_mip_level_extent;
+            let src_subresource_subsampled_extent = src_image_format
+                .ycbcr_chroma_sampling()
+                .map_or(src_mip_level_extent, |s| {
+                    s.subsampled_extent(src_mip_level_extent)
+                });
 
             if src_image_format_planes.is_empty() {
                 if !src_image_format_aspects.contains(src_subresource.aspects) {
@@ -2695,7 +2744,7 @@ impl CopyImageInfo {
                     }
                     ImageAspects::PLANE_1 => {
                         src_subresource_format = src_image_format_planes[1];
-                        src_subresource_extent = src_image_format_subsampled_extent;
+                        src_subresource_extent = src_subresource_subsampled_extent;
                     }
                     _ => {
                         return Err(Box::new(ValidationError {
@@ -2721,11 +2770,11 @@ impl CopyImageInfo {
                     }
                     ImageAspects::PLANE_1 => {
                         src_subresource_format = src_image_format_planes[1];
-                        src_subresource_extent = src_image_format_subsampled_extent;
+                        src_subresource_extent = src_subresource_subsampled_extent;
                     }
                     ImageAspects::PLANE_2 => {
                         src_subresource_format = src_image_format_planes[2];
-                        src_subresource_extent = src_image_format_subsampled_extent;
+                        src_subresource_extent = src_subresource_subsampled_extent;
                     }
                     _ => {
                         return Err(Box::new(ValidationError {
@@ -2837,7 +2886,7 @@ impl CopyImageInfo {
             if src_subresource.array_layers.end > src_image.array_layers() {
                 return Err(Box::new(ValidationError {
                     problem: format!(
-                        "`regions[{}].src_subresource.array_layers.end` is not less than \
+                        "`regions[{}].src_subresource.array_layers.end` is greater than \
                         `src_image.array_layers()`",
                         region_index
                     )
@@ -2847,7 +2896,7 @@ impl CopyImageInfo {
                 }));
             }
 
-            if src_offset[0] + extent[0] > src_subresource_extent[0] {
+            if src_offset[0].saturating_add(extent[0]) > src_subresource_extent[0] {
                 return Err(Box::new(ValidationError {
                     problem: format!(
                         "`regions[{0}].src_offset[0] + regions[{0}].extent[0]` is greater \
@@ -2861,7 +2910,7 @@ impl CopyImageInfo {
                 }));
             }
 
-            if src_offset[1] + extent[1] > src_subresource_extent[1] {
+            if src_offset[1].saturating_add(extent[1]) > src_subresource_extent[1] {
                 return Err(Box::new(ValidationError {
                     problem: format!(
                         "`regions[{0}].src_offset[1] + regions[{0}].extent[1]` is greater \
@@ -2875,7 +2924,7 @@ impl CopyImageInfo {
                 }));
             }
 
-            if src_offset[2] + extent[2] > src_subresource_extent[2] {
+            if src_offset[2].saturating_add(extent[2]) > src_subresource_extent[2] {
                 return Err(Box::new(ValidationError {
                     problem: format!(
                         "`regions[{0}].src_offset[2] + regions[{0}].extent[2]` is greater \
@@ -3040,8 +3089,14 @@ impl CopyImageInfo {
             }
 
             let mut dst_subresource_format = dst_image_format;
-            let mut dst_subresource_extent =
+            let dst_mip_level_extent =
                 mip_level_extent(dst_image.extent(), dst_subresource.mip_level).unwrap();
+            let mut dst_subresource_extent = dst_mip_level_extent;
+            let dst_subresource_subsampled_extent = dst_image_format
+                .ycbcr_chroma_sampling()
+                .map_or(dst_mip_level_extent, |s| {
+                    s.subsampled_extent(dst_mip_level_extent)
+                });
 
             if dst_image_format_planes.is_empty() {
                 if !dst_image_format_aspects.contains(dst_subresource.aspects) {
@@ -3063,7 +3118,7 @@ impl CopyImageInfo {
                     }
                     ImageAspects::PLANE_1 => {
                         dst_subresource_format = dst_image_format_planes[1];
-                        dst_subresource_extent = dst_image_format_subsampled_extent;
+                        dst_subresource_extent = dst_subresource_subsampled_extent;
                     }
                     _ => {
                         return Err(Box::new(ValidationError {
@@ -3089,11 +3144,11 @@ impl CopyImageInfo {
                     }
                     ImageAspects::PLANE_1 => {
                         dst_subresource_format = dst_image_format_planes[1];
-                        dst_subresource_extent = dst_image_format_subsampled_extent;
+                        dst_subresource_extent = dst_subresource_subsampled_extent;
                     }
                     ImageAspects::PLANE_2 => {
                         dst_subresource_format = dst_image_format_planes[2];
-                        dst_subresource_extent = dst_image_format_subsampled_extent;
+                        dst_subresource_extent = dst_subresource_subsampled_extent;
                     }
                     _ => {
                         return Err(Box::new(ValidationError {
@@ -3205,7 +3260,7 @@ impl CopyImageInfo {
             if dst_subresource.array_layers.end > dst_image.array_layers() {
                 return Err(Box::new(ValidationError {
                     problem: format!(
-                        "`regions[{}].dst_subresource.array_layers.end` is not less than \
+                        "`regions[{}].dst_subresource.array_layers.end` is greater than \
                         `dst_image.array_layers()`",
                         region_index
                     )
@@ -3215,7 +3270,7 @@ impl CopyImageInfo {
                 }));
             }
 
-            if dst_offset[0] + extent[0] > dst_subresource_extent[0] {
+            if dst_offset[0].saturating_add(extent[0]) > dst_subresource_extent[0] {
                 return Err(Box::new(ValidationError {
                     problem: format!(
                         "`regions[{0}].dst_offset[0] + regions[{0}].extent[0]` is greater \
@@ -3229,7 +3284,7 @@ impl CopyImageInfo {
                 }));
             }
 
-            if dst_offset[1] + extent[1] > dst_subresource_extent[1] {
+            if dst_offset[1].saturating_add(extent[1]) > dst_subresource_extent[1] {
                 return Err(Box::new(ValidationError {
                     problem: format!(
                         "`regions[{0}].dst_offset[1] + regions[{0}].extent[1]` is greater \
@@ -3243,7 +3298,7 @@ impl CopyImageInfo {
                 }));
             }
 
-            if dst_offset[2] + extent[2] > dst_subresource_extent[2] {
+            if dst_offset[2].saturating_add(extent[2]) > dst_subresource_extent[2] {
                 return Err(Box::new(ValidationError {
                     problem: format!(
                         "`regions[{0}].dst_offset[2] + regions[{0}].extent[2]` is greater \
@@ -3597,19 +3652,19 @@ impl CopyImageInfo {
                     let &ImageCopy {
                         ref dst_subresource,
                         dst_offset,
+                        extent: dst_extent,
                         ..
                     } = dst_region;
 
-                    // For a single-plane image, the aspects must always be identical anyway
-                    if src_image_format_aspects.intersects(ImageAspects::PLANE_0)
-                        && src_subresource.aspects != dst_subresource.aspects
-                    {
+                    // Different aspects, such as the depth and stencil aspects of the same
+                    // image, or two planes of a multi-planar image, never overlap.
+                    if !src_subresource.aspects.intersects(dst_subresource.aspects) {
                         continue;
                     }
 
                     let dst_subresource_axes = [
                         dst_subresource.mip_level..dst_subresource.mip_level + 1,
-                        src_subresource.array_layers.start..src_subresource.array_layers.end,
+                        dst_subresource.array_layers.start..dst_subresource.array_layers.end,
                     ];
 
                     if src_subresource_axes.iter().zip(dst_subresource_axes).any(
@@ -3624,10 +3679,11 @@ impl CopyImageInfo {
                     // have the same layout.
                     overlap_subresource_indices = Some((src_region_index, dst_region_index));
 
+                    // `dst_region` may not have been validated yet.
                     let dst_extent_axes = [
-                        dst_offset[0]..dst_offset[0] + extent[0],
-                        dst_offset[1]..dst_offset[1] + extent[1],
-                        dst_offset[2]..dst_offset[2] + extent[2],
+                        dst_offset[0]..dst_offset[0].saturating_add(dst_extent[0]),
+                        dst_offset[1]..dst_offset[1].saturating_add(dst_extent[1]),
+                        dst_offset[2]..dst_offset[2].saturating_add(dst_extent[2]),
                     ];
 
                     // There is only overlap if all of the axes overlap.
@@ -5208,6 +5264,37 @@ impl BufferImageCopy {
     }
 }
 
+/// Returns a `BlitImageInfo` that blits mip level `dst_mip_level - 1` of `image` to
+/// `dst_mip_level`, for use by `generate_mipmaps`.
+fn mipmap_blit_info(image: &Arc<Image>, dst_mip_level: u32) -> BlitImageInfo {
+    let src_mip_level = dst_mip_level - 1;
+    let region = ImageBlit {
+        src_subresource: ImageSubresourceLayers {
+            mip_level: src_mip_level,
+            ..image.subresource_layers()
+        },
+        src_offsets: [
+            [0; 3],
+            mip_level_extent(image.extent(), src_mip_level).unwrap(),
+        ],
+        dst_subresource: ImageSubresourceLayers {
+            mip_level: dst_mip_level,
+            ..image.subresource_layers()
+        },
+        dst_offsets: [
+            [0; 3],
+            mip_level_extent(image.extent(), dst_mip_level).unwrap(),
+        ],
+        ..Default::default()
+    };
+
+    BlitImageInfo {
+        regions: smallvec![region],
+        filter: Filter::Linear,
+        ..BlitImageInfo::images(image.clone(), image.clone())
+    }
+}
+
 /// Parameters to blit image data.
 #[derive(Clone, Debug)]
 pub struct BlitImageInfo {
@@ -6805,7 +6892,18 @@ impl ImageResolve {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::format::Format;
+    use crate::{
+        buffer::{Buffer, BufferCreateInfo},
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, CommandBufferUsage,
+            PrimaryCommandBufferAbstract,
+        },
+        format::Format,
+        image::ImageCreateInfo,
+        memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+        sync::GpuFuture,
+    };
+    use std::ops::Range;
 
     /// Computes the minimum required len in elements for buffer with image data in specified
     /// format of specified size.
@@ -6861,4 +6959,264 @@ mod tests {
             29584
         );
     }
+
+    fn copy_image_info(
+        src_image: Arc<Image>,
+        dst_image: Arc<Image>,
+        src_subresource: ImageSubresourceLayers,
+        dst_subresource: ImageSubresourceLayers,
+        extent: [u32; 3],
+    ) -> CopyImageInfo {
+        CopyImageInfo {
+            regions: smallvec![ImageCopy {
+                src_subresource,
+                dst_subresource,
+                extent,
+                ..Default::default()
+            }],
+            ..CopyImageInfo::images(src_image, dst_image)
+        }
+    }
+
+    fn subresource(
+        aspects: ImageAspects,
+        mip_level: u32,
+        array_layers: Range<u32>,
+    ) -> ImageSubresourceLayers {
+        ImageSubresourceLayers {
+            aspects,
+            mip_level,
+            array_layers,
+        }
+    }
+
+    #[test]
+    fn copy_image_mip_level_extent() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+
+        let image = |extent: [u32; 3], mip_levels| {
+            Image::new(
+                memory_allocator.clone(),
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format: Format::R8G8B8A8_UNORM,
+                    extent,
+                    mip_levels,
+                    usage: ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap()
+        };
+        let src_image = image([10, 10, 1], 4);
+        let dst_image = image([10, 10, 1], 1);
+
+        let check = |mip_level, extent| {
+            copy_image_info(
+                src_image.clone(),
+                dst_image.clone(),
+                subresource(ImageAspects::COLOR, mip_level, 0..1),
+                subresource(ImageAspects::COLOR, 0, 0..1),
+                extent,
+            )
+            .validate(&device)
+        };
+
+        // Mip level 1 is 5x5.
+        assert!(check(1, [5, 5, 1]).is_ok());
+        assert!(check(1, [6, 5, 1]).is_err());
+        // Mip level 3 is clamped to 1x1.
+        assert!(check(3, [1, 1, 1]).is_ok());
+        assert!(check(3, [2, 1, 1]).is_err());
+        // There is no mip level 4.
+        assert!(check(4, [1, 1, 1]).is_err());
+
+        // An offset and extent that overflow are rejected rather than wrapping around.
+        let mut copy_image_info = copy_image_info(
+            src_image.clone(),
+            dst_image.clone(),
+            subresource(ImageAspects::COLOR, 0, 0..1),
+            subresource(ImageAspects::COLOR, 0, 0..1),
+            [u32::MAX, 1, 1],
+        );
+        copy_image_info.regions[0].src_offset = [2, 0, 0];
+        assert!(copy_image_info.validate(&device).is_err());
+    }
+
+    #[test]
+    fn copy_image_aspect_mismatch() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+
+        let image = Image::new(
+            memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [16, 16, 1],
+                array_layers: 2,
+                usage: ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        let check = |src_aspects, dst_aspects| {
+            copy_image_info(
+                image.clone(),
+                image.clone(),
+                subresource(src_aspects, 0, 0..1),
+                subresource(dst_aspects, 0, 1..2),
+                [16, 16, 1],
+            )
+            .validate(&device)
+        };
+
+        assert!(check(ImageAspects::COLOR, ImageAspects::COLOR).is_ok());
+        // The format has no depth aspect.
+        assert!(check(ImageAspects::DEPTH, ImageAspects::DEPTH).is_err());
+        // Neither does the destination.
+        assert!(check(ImageAspects::COLOR, ImageAspects::DEPTH).is_err());
+    }
+
+    #[test]
+    fn copy_image_same_image_overlap() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+
+        let image = Image::new(
+            memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [16, 16, 1],
+                array_layers: 2,
+                usage: ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        let check = |src_layers, dst_layers, dst_offset, src_layout, dst_layout| {
+            let mut copy_image_info = copy_image_info(
+                image.clone(),
+                image.clone(),
+                subresource(ImageAspects::COLOR, 0, src_layers),
+                subresource(ImageAspects::COLOR, 0, dst_layers),
+                [8, 8, 1],
+            );
+            copy_image_info.regions[0].dst_offset = dst_offset;
+            copy_image_info.src_image_layout = src_layout;
+            copy_image_info.dst_image_layout = dst_layout;
+            copy_image_info.validate(&device)
+        };
+
+        let general = ImageLayout::General;
+        let (transfer_src, transfer_dst) = (
+            ImageLayout::TransferSrcOptimal,
+            ImageLayout::TransferDstOptimal,
+        );
+
+        // Copying between different array layers never overlaps.
+        assert!(check(0..1, 1..2, [0, 0, 0], transfer_src, transfer_dst).is_ok());
+        // Copying within the same array layer is fine if the regions are disjoint, but the
+        // layouts must then be equal.
+        assert!(check(0..1, 0..1, [8, 8, 0], general, general).is_ok());
+        assert!(check(0..1, 0..1, [8, 8, 0], transfer_src, transfer_dst).is_err());
+        // Overlapping regions are rejected.
+        assert!(check(0..1, 0..1, [4, 4, 0], general, general).is_err());
+    }
+
+    #[test]
+    fn generate_mipmaps() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        let image = |format| {
+            Image::new(
+                memory_allocator.clone(),
+                ImageCreateInfo {
+                    image_type: ImageType::Dim2d,
+                    format,
+                    extent: [4, 4, 1],
+                    mip_levels: 3,
+                    usage: ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST,
+                    ..Default::default()
+                },
+                AllocationCreateInfo::default(),
+            )
+            .unwrap()
+        };
+        let buffer = |usage, data: Vec<u8>| {
+            Buffer::from_iter(
+                memory_allocator.clone(),
+                BufferCreateInfo {
+                    usage,
+                    ..Default::default()
+                },
+                AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_HOST
+                        | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                    ..Default::default()
+                },
+                data,
+            )
+            .unwrap()
+        };
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // Integer formats can't be filtered linearly.
+        assert!(builder
+            .generate_mipmaps(image(Format::R32G32B32A32_UINT))
+            .is_err());
+
+        let image = image(Format::R8G8B8A8_UNORM);
+        let src_buffer = buffer(BufferUsage::TRANSFER_SRC, [255, 0, 0, 255].repeat(16));
+        let dst_buffer = buffer(BufferUsage::TRANSFER_DST, vec![0; 4]);
+
+        builder
+            .copy_buffer_to_image(CopyBufferToImageInfo::buffer_image(
+                src_buffer,
+                image.clone(),
+            ))
+            .unwrap()
+            .generate_mipmaps(image.clone())
+            .unwrap()
+            .copy_image_to_buffer(CopyImageToBufferInfo {
+                regions: smallvec![BufferImageCopy {
+                    image_subresource: ImageSubresourceLayers {
+                        mip_level: 2,
+                        ..image.subresource_layers()
+                    },
+                    image_extent: [1, 1, 1],
+                    ..Default::default()
+                }],
+                ..CopyImageToBufferInfo::image_buffer(image.clone(), dst_buffer.clone())
+            })
+            .unwrap();
+
+        builder
+            .build()
+            .unwrap()
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+
+        // The last mip level is 1x1, and contains the color of the whole first mip level.
+        assert_eq!(*dst_buffer.read().unwrap(), [255, 0, 0, 255]);
+    }
 }
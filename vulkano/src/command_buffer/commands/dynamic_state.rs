@@ -13,12 +13,13 @@ use crate::{
         AutoCommandBufferBuilder,
     },
     device::{DeviceOwned, QueueFlags},
+    image::{ImageAspects, SampleCount},
     pipeline::{
         graphics::{
-            color_blend::LogicOp,
+            color_blend::{ColorComponents, LogicOp},
             depth_stencil::{CompareOp, StencilFaces, StencilOp, StencilOps},
             input_assembly::PrimitiveTopology,
-            rasterization::{CullMode, DepthBias, FrontFace, LineStipple},
+            rasterization::{CullMode, DepthBias, FrontFace, LineStipple, PolygonMode},
             viewport::{Scissor, Viewport},
         },
         DynamicState,
@@ -60,6 +61,52 @@ where
         Ok(())
     }
 
+    /// Sets which aspects of the attachments of the current subpass are used in a feedback loop,
+    /// for future draw calls.
+    ///
+    /// If `aspects` contains [`ImageAspects::COLOR`], the color attachments can be in the
+    /// [`ImageLayout::AttachmentFeedbackLoopOptimal`] layout, and similarly for the depth/stencil
+    /// attachment with [`ImageAspects::DEPTH`] and [`ImageAspects::STENCIL`].
+    ///
+    /// [`ImageLayout::AttachmentFeedbackLoopOptimal`]: crate::image::ImageLayout::AttachmentFeedbackLoopOptimal
+    pub fn set_attachment_feedback_loop_enable(
+        &mut self,
+        aspects: ImageAspects,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_attachment_feedback_loop_enable(aspects)?;
+
+        unsafe { Ok(self.set_attachment_feedback_loop_enable_unchecked(aspects)) }
+    }
+
+    fn validate_set_attachment_feedback_loop_enable(
+        &self,
+        aspects: ImageAspects,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_attachment_feedback_loop_enable(aspects)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::AttachmentFeedbackLoopEnable)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_attachment_feedback_loop_enable_unchecked(
+        &mut self,
+        aspects: ImageAspects,
+    ) -> &mut Self {
+        self.builder_state.attachment_feedback_loop_enable = Some(aspects);
+        self.add_command(
+            "set_attachment_feedback_loop_enable",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_attachment_feedback_loop_enable_unchecked(aspects);
+            },
+        );
+
+        self
+    }
+
     /// Sets the dynamic blend constants for future draw calls.
     pub fn set_blend_constants(
         &mut self,
@@ -95,6 +142,53 @@ where
         self
     }
 
+    /// Sets whether blending is enabled for each color attachment, starting at
+    /// `first_attachment`, for future draw calls.
+    pub fn set_color_blend_enable(
+        &mut self,
+        first_attachment: u32,
+        enables: SmallVec<[bool; 4]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_blend_enable(first_attachment, &enables)?;
+
+        unsafe { Ok(self.set_color_blend_enable_unchecked(first_attachment, enables)) }
+    }
+
+    fn validate_set_color_blend_enable(
+        &self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_color_blend_enable(first_attachment, enables)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::ColorBlendEnable)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_blend_enable_unchecked(
+        &mut self,
+        first_attachment: u32,
+        enables: SmallVec<[bool; 4]>,
+    ) -> &mut Self {
+        for (num, &enable) in enables.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.builder_state.color_blend_enable.insert(num, enable);
+        }
+
+        self.add_command(
+            "set_color_blend_enable",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_color_blend_enable_unchecked(first_attachment, &enables);
+            },
+        );
+
+        self
+    }
+
     /// Sets whether dynamic color writes should be enabled for each attachment in the
     /// framebuffer.
     pub fn set_color_write_enable(
@@ -152,6 +246,55 @@ where
         self
     }
 
+    /// Sets the color write mask for each color attachment, starting at `first_attachment`, for
+    /// future draw calls.
+    pub fn set_color_write_mask(
+        &mut self,
+        first_attachment: u32,
+        color_write_masks: SmallVec<[ColorComponents; 4]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_write_mask(first_attachment, &color_write_masks)?;
+
+        unsafe { Ok(self.set_color_write_mask_unchecked(first_attachment, color_write_masks)) }
+    }
+
+    fn validate_set_color_write_mask(
+        &self,
+        first_attachment: u32,
+        color_write_masks: &[ColorComponents],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_color_write_mask(first_attachment, color_write_masks)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::ColorWriteMask)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_write_mask_unchecked(
+        &mut self,
+        first_attachment: u32,
+        color_write_masks: SmallVec<[ColorComponents; 4]>,
+    ) -> &mut Self {
+        for (num, &color_write_mask) in color_write_masks.iter().enumerate() {
+            let num = num as u32 + first_attachment;
+            self.builder_state
+                .color_write_mask
+                .insert(num, color_write_mask);
+        }
+
+        self.add_command(
+            "set_color_write_mask",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_color_write_mask_unchecked(first_attachment, &color_write_masks);
+            },
+        );
+
+        self
+    }
+
     /// Sets the dynamic cull mode for future draw calls.
     pub fn set_cull_mode(
         &mut self,
@@ -636,6 +779,41 @@ where
         self
     }
 
+    /// Sets the dynamic polygon mode for future draw calls.
+    pub fn set_polygon_mode(
+        &mut self,
+        polygon_mode: PolygonMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_polygon_mode(polygon_mode)?;
+
+        unsafe { Ok(self.set_polygon_mode_unchecked(polygon_mode)) }
+    }
+
+    fn validate_set_polygon_mode(
+        &self,
+        polygon_mode: PolygonMode,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_set_polygon_mode(polygon_mode)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::PolygonMode)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_polygon_mode_unchecked(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        self.builder_state.polygon_mode = Some(polygon_mode);
+        self.add_command(
+            "set_polygon_mode",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_polygon_mode_unchecked(polygon_mode);
+            },
+        );
+
+        self
+    }
+
     /// Sets whether dynamic primitive restart is enabled for future draw calls.
     pub fn set_primitive_restart_enable(
         &mut self,
@@ -709,6 +887,45 @@ where
         self
     }
 
+    /// Sets the dynamic number of rasterization samples for future draw calls.
+    pub fn set_rasterization_samples(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_rasterization_samples(rasterization_samples)?;
+
+        unsafe { Ok(self.set_rasterization_samples_unchecked(rasterization_samples)) }
+    }
+
+    fn validate_set_rasterization_samples(
+        &self,
+        rasterization_samples: SampleCount,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_set_rasterization_samples(rasterization_samples)?;
+
+        self.validate_graphics_pipeline_fixed_state(DynamicState::RasterizationSamples)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_rasterization_samples_unchecked(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> &mut Self {
+        self.builder_state.rasterization_samples = Some(rasterization_samples);
+        self.add_command(
+            "set_rasterization_samples",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.set_rasterization_samples_unchecked(rasterization_samples);
+            },
+        );
+
+        self
+    }
+
     /// Sets whether dynamic rasterizer discard is enabled for future draw calls.
     pub fn set_rasterizer_discard_enable(
         &mut self,
@@ -1175,6 +1392,94 @@ impl<A> UnsafeCommandBufferBuilder<A>
 where
     A: CommandBufferAllocator,
 {
+    pub unsafe fn set_attachment_feedback_loop_enable(
+        &mut self,
+        aspects: ImageAspects,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_attachment_feedback_loop_enable(aspects)?;
+
+        Ok(self.set_attachment_feedback_loop_enable_unchecked(aspects))
+    }
+
+    fn validate_set_attachment_feedback_loop_enable(
+        &self,
+        aspects: ImageAspects,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .device()
+            .enabled_features()
+            .attachment_feedback_loop_dynamic_state
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "attachment_feedback_loop_dynamic_state",
+                )])]),
+                vuids: &["VUID-vkCmdSetAttachmentFeedbackLoopEnableEXT-attachmentFeedbackLoopDynamicState-08862"],
+                ..Default::default()
+            }));
+        }
+
+        aspects.validate_device(self.device()).map_err(|err| {
+            err.add_context("aspects")
+                .set_vuids(&["VUID-vkCmdSetAttachmentFeedbackLoopEnableEXT-aspectMask-parameter"])
+        })?;
+
+        if !(ImageAspects::COLOR | ImageAspects::DEPTH | ImageAspects::STENCIL).contains(aspects) {
+            return Err(Box::new(ValidationError {
+                context: "aspects".into(),
+                problem: "contains aspects other than `ImageAspects::COLOR`, \
+                    `ImageAspects::DEPTH` or `ImageAspects::STENCIL`"
+                    .into(),
+                vuids: &["VUID-vkCmdSetAttachmentFeedbackLoopEnableEXT-aspectMask-08863"],
+                ..Default::default()
+            }));
+        }
+
+        if !aspects.is_empty()
+            && !self
+                .device()
+                .enabled_features()
+                .attachment_feedback_loop_layout
+        {
+            return Err(Box::new(ValidationError {
+                context: "aspects".into(),
+                problem: "is not empty".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "attachment_feedback_loop_layout",
+                )])]),
+                vuids: &["VUID-vkCmdSetAttachmentFeedbackLoopEnableEXT-attachmentFeedbackLoopLayout-08864"],
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetAttachmentFeedbackLoopEnableEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_attachment_feedback_loop_enable_unchecked(
+        &mut self,
+        aspects: ImageAspects,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_attachment_feedback_loop_dynamic_state
+            .cmd_set_attachment_feedback_loop_enable_ext)(self.handle(), aspects.into());
+
+        self
+    }
+
     pub unsafe fn set_blend_constants(
         &mut self,
         constants: [f32; 4],
@@ -1213,6 +1518,82 @@ where
         self
     }
 
+    pub unsafe fn set_color_blend_enable(
+        &mut self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_blend_enable(first_attachment, enables)?;
+
+        Ok(self.set_color_blend_enable_unchecked(first_attachment, enables))
+    }
+
+    fn validate_set_color_blend_enable(
+        &self,
+        _first_attachment: u32,
+        _enables: &[bool],
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_blend_enable
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "extended_dynamic_state3_color_blend_enable",
+                )])]),
+                vuids: &[
+                    "VUID-vkCmdSetColorBlendEnableEXT-extendedDynamicState3ColorBlendEnable-07355",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetColorBlendEnableEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_blend_enable_unchecked(
+        &mut self,
+        first_attachment: u32,
+        enables: &[bool],
+    ) -> &mut Self {
+        let enables = enables
+            .iter()
+            .copied()
+            .map(|v| v as ash::vk::Bool32)
+            .collect::<SmallVec<[_; 4]>>();
+
+        if enables.is_empty() {
+            return self;
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_color_blend_enable_ext)(
+            self.handle(),
+            first_attachment,
+            enables.len() as u32,
+            enables.as_ptr(),
+        );
+
+        self
+    }
+
     pub unsafe fn set_color_write_enable(
         &mut self,
         enables: &[bool],
@@ -1275,6 +1656,81 @@ where
         self
     }
 
+    pub unsafe fn set_color_write_mask(
+        &mut self,
+        first_attachment: u32,
+        color_write_masks: &[ColorComponents],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_color_write_mask(first_attachment, color_write_masks)?;
+
+        Ok(self.set_color_write_mask_unchecked(first_attachment, color_write_masks))
+    }
+
+    fn validate_set_color_write_mask(
+        &self,
+        _first_attachment: u32,
+        _color_write_masks: &[ColorComponents],
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_color_write_mask
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "extended_dynamic_state3_color_write_mask",
+                )])]),
+                vuids: &[
+                    "VUID-vkCmdSetColorWriteMaskEXT-extendedDynamicState3ColorWriteMask-07364",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetColorWriteMaskEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_color_write_mask_unchecked(
+        &mut self,
+        first_attachment: u32,
+        color_write_masks: &[ColorComponents],
+    ) -> &mut Self {
+        let color_write_masks = color_write_masks
+            .iter()
+            .copied()
+            .map(ash::vk::ColorComponentFlags::from)
+            .collect::<SmallVec<[_; 4]>>();
+
+        if color_write_masks.is_empty() {
+            return self;
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3.cmd_set_color_write_mask_ext)(
+            self.handle(),
+            first_attachment,
+            color_write_masks.len() as u32,
+            color_write_masks.as_ptr(),
+        );
+
+        self
+    }
+
     pub unsafe fn set_cull_mode(
         &mut self,
         cull_mode: CullMode,
@@ -2106,6 +2562,79 @@ where
         self
     }
 
+    pub unsafe fn set_polygon_mode(
+        &mut self,
+        polygon_mode: PolygonMode,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_polygon_mode(polygon_mode)?;
+
+        Ok(self.set_polygon_mode_unchecked(polygon_mode))
+    }
+
+    fn validate_set_polygon_mode(
+        &self,
+        polygon_mode: PolygonMode,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_polygon_mode
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "extended_dynamic_state3_polygon_mode",
+                )])]),
+                vuids: &["VUID-vkCmdSetPolygonModeEXT-extendedDynamicState3PolygonMode-07422"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetPolygonModeEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        polygon_mode.validate_device(self.device()).map_err(|err| {
+            err.add_context("polygon_mode")
+                .set_vuids(&["VUID-vkCmdSetPolygonModeEXT-polygonMode-parameter"])
+        })?;
+
+        if polygon_mode != PolygonMode::Fill
+            && !self.device().enabled_features().fill_mode_non_solid
+        {
+            return Err(Box::new(ValidationError {
+                context: "polygon_mode".into(),
+                problem: "is not `PolygonMode::Fill`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "fill_mode_non_solid",
+                )])]),
+                vuids: &["VUID-vkCmdSetPolygonModeEXT-fillModeNonSolid-07424"],
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_polygon_mode_unchecked(&mut self, polygon_mode: PolygonMode) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3.cmd_set_polygon_mode_ext)(
+            self.handle(),
+            polygon_mode.into(),
+        );
+
+        self
+    }
+
     pub unsafe fn set_primitive_restart_enable(
         &mut self,
         enable: bool,
@@ -2275,6 +2804,70 @@ where
         self
     }
 
+    pub unsafe fn set_rasterization_samples(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_set_rasterization_samples(rasterization_samples)?;
+
+        Ok(self.set_rasterization_samples_unchecked(rasterization_samples))
+    }
+
+    fn validate_set_rasterization_samples(
+        &self,
+        rasterization_samples: SampleCount,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self
+            .device()
+            .enabled_features()
+            .extended_dynamic_state3_rasterization_samples
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "extended_dynamic_state3_rasterization_samples",
+                )])]),
+                vuids: &["VUID-vkCmdSetRasterizationSamplesEXT-extendedDynamicState3RasterizationSamples-07414"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSetRasterizationSamplesEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        rasterization_samples
+            .validate_device(self.device())
+            .map_err(|err| {
+                err.add_context("rasterization_samples").set_vuids(&[
+                    "VUID-vkCmdSetRasterizationSamplesEXT-rasterizationSamples-parameter",
+                ])
+            })?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn set_rasterization_samples_unchecked(
+        &mut self,
+        rasterization_samples: SampleCount,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_extended_dynamic_state3
+            .cmd_set_rasterization_samples_ext)(self.handle(), rasterization_samples.into());
+
+        self
+    }
+
     pub unsafe fn set_rasterizer_discard_enable(
         &mut self,
         enable: bool,
@@ -10,6 +10,7 @@
 pub(super) mod acceleration_structure;
 pub(super) mod bind_push;
 pub(super) mod clear;
+pub(super) mod conditional_rendering;
 pub(super) mod copy;
 pub(super) mod debug;
 pub(super) mod dynamic_state;
@@ -18,3 +19,4 @@ pub(super) mod query;
 pub(super) mod render_pass;
 pub(super) mod secondary;
 pub(super) mod sync;
+pub(super) mod transform_feedback;
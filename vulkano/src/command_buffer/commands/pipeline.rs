@@ -12,14 +12,15 @@ use crate::{
     buffer::{view::BufferView, BufferUsage, Subbuffer},
     command_buffer::{
         allocator::CommandBufferAllocator,
-        auto::{RenderPassState, RenderPassStateType, Resource, ResourceUseRef2},
+        auto::{RenderPassState, RenderPassStateType, Resource, ResourceUseRef2, SetOrPush},
         sys::UnsafeCommandBufferBuilder,
-        AutoCommandBufferBuilder, DispatchIndirectCommand, DrawIndexedIndirectCommand,
-        DrawIndirectCommand, ResourceInCommand, SubpassContents,
+        AutoCommandBufferBuilder, CommandBufferLevel, DispatchIndirectCommand,
+        DrawIndexedIndirectCommand, DrawIndexedInfo, DrawIndirectCommand, DrawInfo,
+        ResourceInCommand, SubpassContents,
     },
     descriptor_set::{
-        layout::DescriptorType, DescriptorBindingResources, DescriptorBufferInfo,
-        DescriptorImageViewInfo,
+        layout::{DescriptorBindingFlags, DescriptorSetLayoutCreateFlags, DescriptorType},
+        DescriptorBindingResources, DescriptorBufferInfo, DescriptorImageViewInfo,
     },
     device::{DeviceOwned, QueueFlags},
     format::{FormatFeatures, NumericType},
@@ -29,30 +30,47 @@ use crate::{
             input_assembly::PrimitiveTopology, subpass::PipelineSubpassType,
             vertex_input::VertexInputRate,
         },
-        DynamicState, GraphicsPipeline, PartialStateMode, Pipeline, PipelineLayout,
+        DynamicState, GraphicsPipeline, PartialStateMode, Pipeline, PipelineCreateFlags,
+        PipelineLayout, StateMode,
     },
+    query::QueryType,
     shader::{DescriptorBindingRequirements, DescriptorIdentifier, ShaderStage, ShaderStages},
     sync::{PipelineStageAccess, PipelineStageAccessFlags},
-    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanObject,
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version, VulkanObject,
 };
-use std::{mem::size_of, sync::Arc};
+use smallvec::SmallVec;
+use std::{mem::size_of, ptr, sync::Arc};
 
 macro_rules! vuids {
     ($vuid_type:ident, $($id:literal),+ $(,)?) => {
         match $vuid_type {
             VUIDType::Dispatch => &[$(concat!("VUID-vkCmdDispatch-", $id)),+],
+            VUIDType::DispatchBase => &[$(concat!("VUID-vkCmdDispatchBase-", $id)),+],
             VUIDType::DispatchIndirect => &[$(concat!("VUID-vkCmdDispatchIndirect-", $id)),+],
             VUIDType::Draw => &[$(concat!("VUID-vkCmdDraw-", $id)),+],
             VUIDType::DrawIndirect => &[$(concat!("VUID-vkCmdDrawIndirect-", $id)),+],
             VUIDType::DrawIndexed => &[$(concat!("VUID-vkCmdDrawIndexed-", $id)),+],
             VUIDType::DrawIndexedIndirect => &[$(concat!("VUID-vkCmdDrawIndexedIndirect-", $id)),+],
+            VUIDType::DrawIndirectCount => &[$(concat!("VUID-vkCmdDrawIndirectCount-", $id)),+],
+            VUIDType::DrawIndirectByteCount => {
+                &[$(concat!("VUID-vkCmdDrawIndirectByteCountEXT-", $id)),+]
+            }
+            VUIDType::DrawIndexedIndirectCount => {
+                &[$(concat!("VUID-vkCmdDrawIndexedIndirectCount-", $id)),+]
+            }
+            VUIDType::DrawMulti => &[$(concat!("VUID-vkCmdDrawMultiEXT-", $id)),+],
+            VUIDType::DrawMultiIndexed => {
+                &[$(concat!("VUID-vkCmdDrawMultiIndexedEXT-", $id)),+]
+            }
+            VUIDType::SubpassShading => &[$(concat!("VUID-vkCmdSubpassShadingHUAWEI-", $id)),+],
         }
     };
 }
 
 /// # Commands to execute a bound pipeline.
 ///
-/// Dispatch commands require a compute queue, draw commands require a graphics queue.
+/// Dispatch commands require a compute queue, draw and subpass shading commands require a graphics
+/// queue.
 impl<L, A> AutoCommandBufferBuilder<L, A>
 where
     A: CommandBufferAllocator,
@@ -122,6 +140,134 @@ where
         self
     }
 
+    /// Perform a single compute operation using a compute pipeline, with enough workgroups to
+    /// cover `work_size` invocations in each dimension.
+    ///
+    /// The number of workgroups in each dimension is `work_size` divided by the
+    /// [`local_size`](crate::pipeline::ComputePipeline::local_size) of the bound compute
+    /// pipeline, rounded up. If `work_size` is not a multiple of the local size, the last
+    /// workgroup in that dimension contains invocations beyond `work_size`, which the shader must
+    /// skip, for example by comparing `gl_GlobalInvocationID` against the size of the image it
+    /// writes to.
+    ///
+    /// Otherwise, this behaves the same as [`dispatch`](Self::dispatch).
+    pub fn dispatch_for(&mut self, work_size: [u32; 3]) -> Result<&mut Self, Box<ValidationError>> {
+        let local_size = self
+            .builder_state
+            .pipeline_compute
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "no compute pipeline is currently bound".into(),
+                    vuids: &["VUID-vkCmdDispatch-None-08606"],
+                    ..Default::default()
+                })
+            })?
+            .local_size();
+        let group_counts = [0, 1, 2].map(|i| work_size[i].div_ceil(local_size[i]));
+
+        self.dispatch(group_counts)
+    }
+
+    /// Perform a single compute operation using a compute pipeline, with the workgroup IDs
+    /// starting at `base_group` instead of zero.
+    ///
+    /// If any element of `base_group` is not zero, the bound compute pipeline must have been
+    /// created with [`PipelineCreateFlags::DISPATCH_BASE`]. The device API version must be at
+    /// least 1.1, or the [`khr_device_group`] extension must be enabled on the device.
+    ///
+    /// A compute pipeline must have been bound using
+    /// [`bind_pipeline_compute`](Self::bind_pipeline_compute). Any resources used by the compute
+    /// pipeline, such as descriptor sets, must have been set beforehand.
+    ///
+    /// [`khr_device_group`]: crate::device::DeviceExtensions::khr_device_group
+    pub fn dispatch_base(
+        &mut self,
+        base_group: [u32; 3],
+        group_counts: [u32; 3],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_dispatch_base(base_group, group_counts)?;
+
+        unsafe { Ok(self.dispatch_base_unchecked(base_group, group_counts)) }
+    }
+
+    fn validate_dispatch_base(
+        &self,
+        base_group: [u32; 3],
+        group_counts: [u32; 3],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_dispatch_base(base_group, group_counts)?;
+
+        if self.builder_state.render_pass.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "a render pass instance is active".into(),
+                vuids: &["VUID-vkCmdDispatchBase-renderpass"],
+                ..Default::default()
+            }));
+        }
+
+        let pipeline = self
+            .builder_state
+            .pipeline_compute
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "no compute pipeline is currently bound".into(),
+                    vuids: &["VUID-vkCmdDispatchBase-None-08606"],
+                    ..Default::default()
+                })
+            })?
+            .as_ref();
+
+        if base_group != [0; 3]
+            && !pipeline
+                .flags()
+                .intersects(PipelineCreateFlags::DISPATCH_BASE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`base_group` is not `[0; 3]`, but the currently bound compute pipeline \
+                    was not created with `PipelineCreateFlags::DISPATCH_BASE`"
+                    .into(),
+                vuids: &["VUID-vkCmdDispatchBase-baseGroupX-00427"],
+                ..Default::default()
+            }));
+        }
+
+        const VUID_TYPE: VUIDType = VUIDType::DispatchBase;
+        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn dispatch_base_unchecked(
+        &mut self,
+        base_group: [u32; 3],
+        group_counts: [u32; 3],
+    ) -> &mut Self {
+        let pipeline = self
+            .builder_state
+            .pipeline_compute
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut used_resources = Vec::new();
+        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+
+        self.add_command(
+            "dispatch_base",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.dispatch_base_unchecked(base_group, group_counts);
+            },
+        );
+
+        self
+    }
+
     /// Perform multiple compute operations using a compute pipeline. One dispatch is performed for
     /// each [`DispatchIndirectCommand`] struct in `indirect_buffer`.
     ///
@@ -258,6 +404,8 @@ where
         self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
         self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
         self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_primitive_restart(pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
         self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
         self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
 
@@ -363,6 +511,7 @@ where
         let mut used_resources = Vec::new();
         self.add_descriptor_sets_resources(&mut used_resources, pipeline);
         self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_transform_feedback_buffers_resources(&mut used_resources);
 
         self.add_command(
             "draw",
@@ -435,6 +584,8 @@ where
         self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
         self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
         self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_primitive_restart(pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
         self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
         self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
 
@@ -464,6 +615,7 @@ where
         let mut used_resources = Vec::new();
         self.add_descriptor_sets_resources(&mut used_resources, pipeline);
         self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_transform_feedback_buffers_resources(&mut used_resources);
         self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
 
         self.add_command(
@@ -477,71 +629,67 @@ where
         self
     }
 
-    /// Perform a single draw operation using a graphics pipeline, using an index buffer.
+    /// Perform multiple draw operations using a graphics pipeline, reading the number of draws
+    /// from a buffer.
     ///
-    /// The parameters specify the first index and the number of indices in the index buffer that
-    /// should be used, and the first instance and number of instances. For non-instanced drawing,
-    /// specify `instance_count` as 1 and `first_instance` as 0. The `vertex_offset` is a constant
-    /// value that should be added to each index in the index buffer to produce the final vertex
-    /// number to be used.
+    /// One draw is performed for each [`DrawIndirectCommand`] struct in `indirect_buffer`, up to
+    /// the number of draws that is read from `count_buffer` when the command is executed, and at
+    /// most `max_draw_count`. This makes it possible to generate the draws on the device, for
+    /// example when culling objects in a compute shader. The number of draws in `count_buffer` must
+    /// not be greater than the
+    /// [`max_draw_indirect_count`](crate::device::Properties::max_draw_indirect_count) limit.
     ///
-    /// An index buffer must have been bound using
-    /// [`bind_index_buffer`](Self::bind_index_buffer), and the provided index range must be in
-    /// range of the bound index buffer.
+    /// The [`draw_indirect_count`](crate::device::Features::draw_indirect_count) feature must be
+    /// enabled on the device.
     ///
     /// A graphics pipeline must have been bound using
     /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
     /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
-    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the provided instance
-    /// range must be in range of the bound vertex buffers. The vertex indices in the index buffer
-    /// must be in range of the bound vertex buffers.
-    pub fn draw_indexed(
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the vertex and instance
+    /// ranges of each `DrawIndirectCommand` in the indirect buffer must be in range of the bound
+    /// vertex buffers.
+    pub fn draw_indirect_count(
         &mut self,
-        index_count: u32,
-        instance_count: u32,
-        first_index: u32,
-        vertex_offset: i32,
-        first_instance: u32,
+        indirect_buffer: Subbuffer<[DrawIndirectCommand]>,
+        count_buffer: Subbuffer<u32>,
+        max_draw_count: u32,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        self.validate_draw_indexed(
-            index_count,
-            instance_count,
-            first_index,
-            vertex_offset,
-            first_instance,
+        let stride = size_of::<DrawIndirectCommand>() as u32;
+        self.validate_draw_indirect_count(
+            indirect_buffer.as_bytes(),
+            &count_buffer,
+            max_draw_count,
+            stride,
         )?;
 
         unsafe {
-            Ok(self.draw_indexed_unchecked(
-                index_count,
-                instance_count,
-                first_index,
-                vertex_offset,
-                first_instance,
+            Ok(self.draw_indirect_count_unchecked(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                stride,
             ))
         }
     }
 
-    fn validate_draw_indexed(
+    fn validate_draw_indirect_count(
         &self,
-        index_count: u32,
-        instance_count: u32,
-        first_index: u32,
-        vertex_offset: i32,
-        first_instance: u32,
+        indirect_buffer: &Subbuffer<[u8]>,
+        count_buffer: &Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
     ) -> Result<(), Box<ValidationError>> {
-        self.inner.validate_draw_indexed(
-            index_count,
-            instance_count,
-            first_index,
-            vertex_offset,
-            first_instance,
+        self.inner.validate_draw_indirect_count(
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
         )?;
 
         let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
             Box::new(ValidationError {
                 problem: "a render pass instance is not active".into(),
-                vuids: &["VUID-vkCmdDrawIndexed-renderpass"],
+                vuids: &["VUID-vkCmdDrawIndirectCount-renderpass"],
                 ..Default::default()
             })
         })?;
@@ -553,115 +701,31 @@ where
             .ok_or_else(|| {
                 Box::new(ValidationError {
                     problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawIndexed-None-08606"],
+                    vuids: &["VUID-vkCmdDrawIndirectCount-None-08606"],
                     ..Default::default()
                 })
             })?
             .as_ref();
 
-        const VUID_TYPE: VUIDType = VUIDType::DrawIndexed;
+        const VUID_TYPE: VUIDType = VUIDType::DrawIndirectCount;
         self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
         self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
         self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_primitive_restart(pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
         self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
         self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
 
-        let index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
-            Box::new(ValidationError {
-                problem: "no index buffer is currently bound".into(),
-                vuids: &["VUID-vkCmdDrawIndexed-None-07312"],
-                ..Default::default()
-            })
-        })?;
-
-        let index_buffer_bytes = index_buffer.as_bytes();
-
-        if !self.device().enabled_features().robust_buffer_access2 {
-            if index_buffer.index_type().size()
-                * (first_index as DeviceSize + index_count as DeviceSize)
-                > index_buffer_bytes.size()
-            {
-                return Err(Box::new(ValidationError {
-                    problem: "`first_index + index_count`, \
-                        multiplied by the size of the indices in the bound index buffer, \
-                        is greater than the size of the bound index buffer"
-                        .into(),
-                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
-                        "robust_buffer_access2",
-                    )])]),
-                    vuids: &["VUID-vkCmdDrawIndexed-robustBufferAccess2-07825"],
-                    ..Default::default()
-                }));
-            }
-        }
-
-        let view_mask = match pipeline.subpass() {
-            PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
-            PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
-        };
-
-        if view_mask != 0 {
-            let properties = self.device().physical_device().properties();
-
-            if (first_instance + instance_count).saturating_sub(1)
-                > properties.max_multiview_instance_index.unwrap_or(0)
-            {
-                return Err(Box::new(ValidationError {
-                    problem: "the current render pass instance has a nonzero view mask, but \
-                        `first_instance + instance_count - 1` is greater than the \
-                        `max_multiview_instance_index` limit"
-                        .into(),
-                    vuids: &["VUID-vkCmdDrawIndexed-maxMultiviewInstanceIndex-02688"],
-                    ..Default::default()
-                }));
-            }
-        }
-
-        for (&binding_num, binding_desc) in &pipeline.vertex_input_state().bindings {
-            let vertex_buffer = &self.builder_state.vertex_buffers[&binding_num];
-
-            // Per spec:
-            // https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap22.html#fxvertex-input-address-calculation
-            match binding_desc.input_rate {
-                VertexInputRate::Vertex => (),
-                VertexInputRate::Instance { divisor } => {
-                    let max_vertex_offset = if divisor == 0 {
-                        (first_instance as DeviceSize + 1) * binding_desc.stride as DeviceSize
-                    } else {
-                        (first_instance as DeviceSize
-                            + instance_count as DeviceSize / divisor as DeviceSize)
-                            * binding_desc.stride as DeviceSize
-                    };
-
-                    if max_vertex_offset > vertex_buffer.size() {
-                        return Err(Box::new(ValidationError {
-                            problem: format!(
-                                "the size of the vertex buffer bound to binding {} is less \
-                                than the minimum size required, for the provided \
-                                `first_instance` and `instance_count` values, and the vertex \
-                                input state bindings of the currently bound graphics pipeline",
-                                binding_num
-                            )
-                            .into(),
-                            vuids: &["VUID-vkCmdDrawIndexed-None-02721"],
-                            ..Default::default()
-                        }));
-                    }
-                }
-            };
-        }
-
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn draw_indexed_unchecked(
+    pub unsafe fn draw_indirect_count_unchecked(
         &mut self,
-        index_count: u32,
-        instance_count: u32,
-        first_index: u32,
-        vertex_offset: i32,
-        first_instance: u32,
+        indirect_buffer: Subbuffer<[DrawIndirectCommand]>,
+        count_buffer: Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
     ) -> &mut Self {
         if let RenderPassStateType::BeginRendering(state) =
             &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
@@ -679,18 +743,19 @@ where
         let mut used_resources = Vec::new();
         self.add_descriptor_sets_resources(&mut used_resources, pipeline);
         self.add_vertex_buffers_resources(&mut used_resources, pipeline);
-        self.add_index_buffer_resources(&mut used_resources);
+        self.add_transform_feedback_buffers_resources(&mut used_resources);
+        self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
+        self.add_count_buffer_resources(&mut used_resources, count_buffer.as_bytes());
 
         self.add_command(
-            "draw_indexed",
+            "draw_indirect_count",
             used_resources,
             move |out: &mut UnsafeCommandBufferBuilder<A>| {
-                out.draw_indexed_unchecked(
-                    index_count,
-                    instance_count,
-                    first_index,
-                    vertex_offset,
-                    first_instance,
+                out.draw_indirect_count_unchecked(
+                    &indirect_buffer,
+                    &count_buffer,
+                    max_draw_count,
+                    stride,
                 );
             },
         );
@@ -698,17 +763,573 @@ where
         self
     }
 
-    /// Perform multiple draw operations using a graphics pipeline, using an index buffer.
+    /// Perform a single draw operation using a graphics pipeline, reading the number of vertices
+    /// from a transform feedback counter buffer.
     ///
-    /// One draw is performed for each [`DrawIndexedIndirectCommand`] struct in `indirect_buffer`.
-    /// The maximum number of draw commands in the buffer is limited by the
-    /// [`max_draw_indirect_count`](crate::device::Properties::max_draw_indirect_count) limit.
-    /// This limit is 1 unless the
-    /// [`multi_draw_indirect`](crate::device::Features::multi_draw_indirect) feature has been
-    /// enabled.
+    /// The number of vertices is calculated as `(counter - counter_offset) / vertex_stride`,
+    /// where `counter` is the byte count that was written to `counter_buffer` by a previous call
+    /// to [`end_transform_feedback`](Self::end_transform_feedback). This makes it possible to
+    /// draw vertices that were captured with transform feedback, without reading the number of
+    /// captured vertices back to the host.
     ///
-    /// An index buffer must have been bound using
-    /// [`bind_index_buffer`](Self::bind_index_buffer), and the index ranges of each
+    /// The [`transform_feedback`](crate::device::Features::transform_feedback) feature must be
+    /// enabled on the device, and the
+    /// [`transform_feedback_draw`](crate::device::Properties::transform_feedback_draw) property
+    /// must be supported.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand.
+    pub fn draw_indirect_byte_count(
+        &mut self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: Subbuffer<u32>,
+        counter_offset: u32,
+        vertex_stride: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_indirect_byte_count(
+            instance_count,
+            first_instance,
+            &counter_buffer,
+            counter_offset,
+            vertex_stride,
+        )?;
+
+        unsafe {
+            Ok(self.draw_indirect_byte_count_unchecked(
+                instance_count,
+                first_instance,
+                counter_buffer,
+                counter_offset,
+                vertex_stride,
+            ))
+        }
+    }
+
+    fn validate_draw_indirect_byte_count(
+        &self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: &Subbuffer<u32>,
+        counter_offset: u32,
+        vertex_stride: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_draw_indirect_byte_count(
+            instance_count,
+            first_instance,
+            counter_buffer,
+            counter_offset,
+            vertex_stride,
+        )?;
+
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdDrawIndirectByteCountEXT-renderpass"],
+                ..Default::default()
+            })
+        })?;
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "no graphics pipeline is currently bound".into(),
+                    vuids: &["VUID-vkCmdDrawIndirectByteCountEXT-None-08606"],
+                    ..Default::default()
+                })
+            })?
+            .as_ref();
+
+        const VUID_TYPE: VUIDType = VUIDType::DrawIndirectByteCount;
+        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_primitive_restart(pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indirect_byte_count_unchecked(
+        &mut self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: Subbuffer<u32>,
+        counter_offset: u32,
+        vertex_stride: u32,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut used_resources = Vec::new();
+        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_transform_feedback_buffers_resources(&mut used_resources);
+        self.add_count_buffer_resources(&mut used_resources, counter_buffer.as_bytes());
+
+        self.add_command(
+            "draw_indirect_byte_count",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.draw_indirect_byte_count_unchecked(
+                    instance_count,
+                    first_instance,
+                    &counter_buffer,
+                    counter_offset,
+                    vertex_stride,
+                );
+            },
+        );
+
+        self
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, with the parameters of each
+    /// draw provided as a slice.
+    ///
+    /// One draw is performed for each element of `draw_infos`, using the vertex range given by
+    /// that element. All draws use the same `instance_count` and `first_instance`. This is
+    /// equivalent to calling [`draw`](Self::draw) once for each element, but with lower overhead.
+    /// The maximum number of draws is limited by the
+    /// [`max_multi_draw_count`](crate::device::Properties::max_multi_draw_count) limit.
+    ///
+    /// The [`multi_draw`](crate::device::Features::multi_draw) feature must be enabled on the
+    /// device.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the vertex ranges of
+    /// each element of `draw_infos`, and the provided instance range, must be in range of the
+    /// bound vertex buffers.
+    pub fn draw_multi(
+        &mut self,
+        draw_infos: &[DrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_multi(draw_infos, instance_count, first_instance)?;
+
+        unsafe { Ok(self.draw_multi_unchecked(draw_infos, instance_count, first_instance)) }
+    }
+
+    fn validate_draw_multi(
+        &self,
+        draw_infos: &[DrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_draw_multi(draw_infos, instance_count, first_instance)?;
+
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdDrawMultiEXT-renderpass"],
+                ..Default::default()
+            })
+        })?;
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "no graphics pipeline is currently bound".into(),
+                    vuids: &["VUID-vkCmdDrawMultiEXT-None-08606"],
+                    ..Default::default()
+                })
+            })?
+            .as_ref();
+
+        const VUID_TYPE: VUIDType = VUIDType::DrawMulti;
+        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_primitive_restart(pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+
+        let view_mask = match pipeline.subpass() {
+            PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
+            PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
+        };
+
+        if view_mask != 0 {
+            let properties = self.device().physical_device().properties();
+
+            if (first_instance + instance_count).saturating_sub(1)
+                > properties.max_multiview_instance_index.unwrap_or(0)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "the current render pass instance has a nonzero view mask, but \
+                        `first_instance + instance_count - 1` is greater than the \
+                        `max_multiview_instance_index` limit"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawMultiEXT-maxMultiviewInstanceIndex-02688"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        for (&binding_num, binding_desc) in &pipeline.vertex_input_state().bindings {
+            let vertex_buffer = &self.builder_state.vertex_buffers[&binding_num];
+
+            // Per spec:
+            // https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap22.html#fxvertex-input-address-calculation
+            match binding_desc.input_rate {
+                VertexInputRate::Vertex => {
+                    for (draw_index, draw_info) in draw_infos.iter().enumerate() {
+                        let &DrawInfo {
+                            first_vertex,
+                            vertex_count,
+                        } = draw_info;
+
+                        let max_vertex_offset = (first_vertex as DeviceSize
+                            + vertex_count as DeviceSize)
+                            * binding_desc.stride as DeviceSize;
+
+                        if max_vertex_offset > vertex_buffer.size() {
+                            return Err(Box::new(ValidationError {
+                                context: format!("draw_infos[{}]", draw_index).into(),
+                                problem: format!(
+                                    "the size of the vertex buffer bound to binding {} is less \
+                                    than the minimum size required, for the provided \
+                                    `first_vertex` and `vertex_count` values, and the vertex \
+                                    input state bindings of the currently bound graphics \
+                                    pipeline",
+                                    binding_num
+                                )
+                                .into(),
+                                vuids: &["VUID-vkCmdDrawMultiEXT-None-02721"],
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+                VertexInputRate::Instance { divisor } => {
+                    let max_vertex_offset = if divisor == 0 {
+                        (first_instance as DeviceSize + 1) * binding_desc.stride as DeviceSize
+                    } else {
+                        (first_instance as DeviceSize
+                            + instance_count as DeviceSize / divisor as DeviceSize)
+                            * binding_desc.stride as DeviceSize
+                    };
+
+                    if max_vertex_offset > vertex_buffer.size() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the size of the vertex buffer bound to binding {} is less \
+                                than the minimum size required, for the provided \
+                                `first_instance` and `instance_count` values, and the vertex \
+                                input state bindings of the currently bound graphics pipeline",
+                                binding_num
+                            )
+                            .into(),
+                            vuids: &["VUID-vkCmdDrawMultiEXT-None-02721"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+            };
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_unchecked(
+        &mut self,
+        draw_infos: &[DrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut used_resources = Vec::new();
+        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_transform_feedback_buffers_resources(&mut used_resources);
+
+        let draw_infos: SmallVec<[_; 8]> = draw_infos.into();
+
+        self.add_command(
+            "draw_multi",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.draw_multi_unchecked(&draw_infos, instance_count, first_instance);
+            },
+        );
+
+        self
+    }
+
+    /// Perform a single draw operation using a graphics pipeline, using an index buffer.
+    ///
+    /// The parameters specify the first index and the number of indices in the index buffer that
+    /// should be used, and the first instance and number of instances. For non-instanced drawing,
+    /// specify `instance_count` as 1 and `first_instance` as 0. The `vertex_offset` is a constant
+    /// value that should be added to each index in the index buffer to produce the final vertex
+    /// number to be used.
+    ///
+    /// An index buffer must have been bound using
+    /// [`bind_index_buffer`](Self::bind_index_buffer), and the provided index range must be in
+    /// range of the bound index buffer.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the provided instance
+    /// range must be in range of the bound vertex buffers. The vertex indices in the index buffer
+    /// must be in range of the bound vertex buffers.
+    pub fn draw_indexed(
+        &mut self,
+        index_count: u32,
+        instance_count: u32,
+        first_index: u32,
+        vertex_offset: i32,
+        first_instance: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_indexed(
+            index_count,
+            instance_count,
+            first_index,
+            vertex_offset,
+            first_instance,
+        )?;
+
+        unsafe {
+            Ok(self.draw_indexed_unchecked(
+                index_count,
+                instance_count,
+                first_index,
+                vertex_offset,
+                first_instance,
+            ))
+        }
+    }
+
+    fn validate_draw_indexed(
+        &self,
+        index_count: u32,
+        instance_count: u32,
+        first_index: u32,
+        vertex_offset: i32,
+        first_instance: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_draw_indexed(
+            index_count,
+            instance_count,
+            first_index,
+            vertex_offset,
+            first_instance,
+        )?;
+
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdDrawIndexed-renderpass"],
+                ..Default::default()
+            })
+        })?;
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "no graphics pipeline is currently bound".into(),
+                    vuids: &["VUID-vkCmdDrawIndexed-None-08606"],
+                    ..Default::default()
+                })
+            })?
+            .as_ref();
+
+        const VUID_TYPE: VUIDType = VUIDType::DrawIndexed;
+        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_primitive_restart(pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+
+        let index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "no index buffer is currently bound".into(),
+                vuids: &["VUID-vkCmdDrawIndexed-None-07312"],
+                ..Default::default()
+            })
+        })?;
+
+        let index_buffer_bytes = index_buffer.as_bytes();
+
+        if !self.device().enabled_features().robust_buffer_access2 {
+            if index_buffer.index_type().size()
+                * (first_index as DeviceSize + index_count as DeviceSize)
+                > index_buffer_bytes.size()
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "`first_index + index_count`, \
+                        multiplied by the size of the indices in the bound index buffer, \
+                        is greater than the size of the bound index buffer"
+                        .into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "robust_buffer_access2",
+                    )])]),
+                    vuids: &["VUID-vkCmdDrawIndexed-robustBufferAccess2-07825"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        let view_mask = match pipeline.subpass() {
+            PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
+            PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
+        };
+
+        if view_mask != 0 {
+            let properties = self.device().physical_device().properties();
+
+            if (first_instance + instance_count).saturating_sub(1)
+                > properties.max_multiview_instance_index.unwrap_or(0)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "the current render pass instance has a nonzero view mask, but \
+                        `first_instance + instance_count - 1` is greater than the \
+                        `max_multiview_instance_index` limit"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawIndexed-maxMultiviewInstanceIndex-02688"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        for (&binding_num, binding_desc) in &pipeline.vertex_input_state().bindings {
+            let vertex_buffer = &self.builder_state.vertex_buffers[&binding_num];
+
+            // Per spec:
+            // https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap22.html#fxvertex-input-address-calculation
+            match binding_desc.input_rate {
+                VertexInputRate::Vertex => (),
+                VertexInputRate::Instance { divisor } => {
+                    let max_vertex_offset = if divisor == 0 {
+                        (first_instance as DeviceSize + 1) * binding_desc.stride as DeviceSize
+                    } else {
+                        (first_instance as DeviceSize
+                            + instance_count as DeviceSize / divisor as DeviceSize)
+                            * binding_desc.stride as DeviceSize
+                    };
+
+                    if max_vertex_offset > vertex_buffer.size() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the size of the vertex buffer bound to binding {} is less \
+                                than the minimum size required, for the provided \
+                                `first_instance` and `instance_count` values, and the vertex \
+                                input state bindings of the currently bound graphics pipeline",
+                                binding_num
+                            )
+                            .into(),
+                            vuids: &["VUID-vkCmdDrawIndexed-None-02721"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+            };
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indexed_unchecked(
+        &mut self,
+        index_count: u32,
+        instance_count: u32,
+        first_index: u32,
+        vertex_offset: i32,
+        first_instance: u32,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut used_resources = Vec::new();
+        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_transform_feedback_buffers_resources(&mut used_resources);
+        self.add_index_buffer_resources(&mut used_resources);
+
+        self.add_command(
+            "draw_indexed",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.draw_indexed_unchecked(
+                    index_count,
+                    instance_count,
+                    first_index,
+                    vertex_offset,
+                    first_instance,
+                );
+            },
+        );
+
+        self
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer.
+    ///
+    /// One draw is performed for each [`DrawIndexedIndirectCommand`] struct in `indirect_buffer`.
+    /// The maximum number of draw commands in the buffer is limited by the
+    /// [`max_draw_indirect_count`](crate::device::Properties::max_draw_indirect_count) limit.
+    /// This limit is 1 unless the
+    /// [`multi_draw_indirect`](crate::device::Features::multi_draw_indirect) feature has been
+    /// enabled.
+    ///
+    /// An index buffer must have been bound using
+    /// [`bind_index_buffer`](Self::bind_index_buffer), and the index ranges of each
     /// `DrawIndexedIndirectCommand` in the indirect buffer must be in range of the bound index
     /// buffer.
     ///
@@ -722,26 +1343,315 @@ where
         &mut self,
         indirect_buffer: Subbuffer<[DrawIndexedIndirectCommand]>,
     ) -> Result<&mut Self, Box<ValidationError>> {
-        let draw_count = indirect_buffer.len() as u32;
-        let stride = size_of::<DrawIndexedIndirectCommand>() as u32;
-        self.validate_draw_indexed_indirect(indirect_buffer.as_bytes(), draw_count, stride)?;
+        let draw_count = indirect_buffer.len() as u32;
+        let stride = size_of::<DrawIndexedIndirectCommand>() as u32;
+        self.validate_draw_indexed_indirect(indirect_buffer.as_bytes(), draw_count, stride)?;
+
+        unsafe { Ok(self.draw_indexed_indirect_unchecked(indirect_buffer, draw_count, stride)) }
+    }
+
+    fn validate_draw_indexed_indirect(
+        &self,
+        indirect_buffer: &Subbuffer<[u8]>,
+        draw_count: u32,
+        stride: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_draw_indexed_indirect(indirect_buffer, draw_count, stride)?;
+
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirect-renderpass"],
+                ..Default::default()
+            })
+        })?;
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "no graphics pipeline is currently bound".into(),
+                    vuids: &["VUID-vkCmdDrawIndexedIndirect-None-08606"],
+                    ..Default::default()
+                })
+            })?
+            .as_ref();
+
+        const VUID_TYPE: VUIDType = VUIDType::DrawIndexedIndirect;
+        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_primitive_restart(pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+
+        let _index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "no index buffer is currently bound".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirect-None-07312"],
+                ..Default::default()
+            })
+        })?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indexed_indirect_unchecked(
+        &mut self,
+        indirect_buffer: Subbuffer<[DrawIndexedIndirectCommand]>,
+        draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut used_resources = Vec::new();
+        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_transform_feedback_buffers_resources(&mut used_resources);
+        self.add_index_buffer_resources(&mut used_resources);
+        self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
+
+        self.add_command(
+            "draw_indexed_indirect",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.draw_indexed_indirect_unchecked(&indirect_buffer, draw_count, stride);
+            },
+        );
+
+        self
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer, reading
+    /// the number of draws from a buffer.
+    ///
+    /// One draw is performed for each [`DrawIndexedIndirectCommand`] struct in `indirect_buffer`,
+    /// up to the number of draws that is read from `count_buffer` when the command is executed, and
+    /// at most `max_draw_count`. This makes it possible to generate the draws on the device, for
+    /// example when culling objects in a compute shader. The number of draws in `count_buffer` must
+    /// not be greater than the
+    /// [`max_draw_indirect_count`](crate::device::Properties::max_draw_indirect_count) limit.
+    ///
+    /// The [`draw_indirect_count`](crate::device::Features::draw_indirect_count) feature must be
+    /// enabled on the device.
+    ///
+    /// An index buffer must have been bound using
+    /// [`bind_index_buffer`](Self::bind_index_buffer), and the index ranges of each
+    /// `DrawIndexedIndirectCommand` in the indirect buffer must be in range of the bound index
+    /// buffer.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the instance ranges of
+    /// each `DrawIndexedIndirectCommand` in the indirect buffer must be in range of the bound
+    /// vertex buffers.
+    pub fn draw_indexed_indirect_count(
+        &mut self,
+        indirect_buffer: Subbuffer<[DrawIndexedIndirectCommand]>,
+        count_buffer: Subbuffer<u32>,
+        max_draw_count: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        let stride = size_of::<DrawIndexedIndirectCommand>() as u32;
+        self.validate_draw_indexed_indirect_count(
+            indirect_buffer.as_bytes(),
+            &count_buffer,
+            max_draw_count,
+            stride,
+        )?;
+
+        unsafe {
+            Ok(self.draw_indexed_indirect_count_unchecked(
+                indirect_buffer,
+                count_buffer,
+                max_draw_count,
+                stride,
+            ))
+        }
+    }
+
+    fn validate_draw_indexed_indirect_count(
+        &self,
+        indirect_buffer: &Subbuffer<[u8]>,
+        count_buffer: &Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_draw_indexed_indirect_count(
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
+        )?;
+
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-renderpass"],
+                ..Default::default()
+            })
+        })?;
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "no graphics pipeline is currently bound".into(),
+                    vuids: &["VUID-vkCmdDrawIndexedIndirectCount-None-08606"],
+                    ..Default::default()
+                })
+            })?
+            .as_ref();
+
+        const VUID_TYPE: VUIDType = VUIDType::DrawIndexedIndirectCount;
+        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+        self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_primitive_restart(pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
+        self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
+
+        let _index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "no index buffer is currently bound".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-None-07312"],
+                ..Default::default()
+            })
+        })?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indexed_indirect_count_unchecked(
+        &mut self,
+        indirect_buffer: Subbuffer<[DrawIndexedIndirectCommand]>,
+        count_buffer: Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        if let RenderPassStateType::BeginRendering(state) =
+            &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
+        {
+            state.pipeline_used = true;
+        }
+
+        let pipeline = self
+            .builder_state
+            .pipeline_graphics
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut used_resources = Vec::new();
+        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+        self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_transform_feedback_buffers_resources(&mut used_resources);
+        self.add_index_buffer_resources(&mut used_resources);
+        self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
+        self.add_count_buffer_resources(&mut used_resources, count_buffer.as_bytes());
+
+        self.add_command(
+            "draw_indexed_indirect_count",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.draw_indexed_indirect_count_unchecked(
+                    &indirect_buffer,
+                    &count_buffer,
+                    max_draw_count,
+                    stride,
+                );
+            },
+        );
+
+        self
+    }
+
+    /// Perform multiple draw operations using a graphics pipeline, using an index buffer, with the
+    /// parameters of each draw provided as a slice.
+    ///
+    /// One draw is performed for each element of `draw_infos`, using the index range and vertex
+    /// offset given by that element. If `vertex_offset` is `Some`, then its value is used for
+    /// every draw instead, and the `vertex_offset` member of each element is ignored. All draws
+    /// use the same `instance_count` and `first_instance`. This is equivalent to calling
+    /// [`draw_indexed`](Self::draw_indexed) once for each element, but with lower overhead.
+    /// The maximum number of draws is limited by the
+    /// [`max_multi_draw_count`](crate::device::Properties::max_multi_draw_count) limit.
+    ///
+    /// The [`multi_draw`](crate::device::Features::multi_draw) feature must be enabled on the
+    /// device.
+    ///
+    /// An index buffer must have been bound using
+    /// [`bind_index_buffer`](Self::bind_index_buffer), and the index ranges of each element of
+    /// `draw_infos` must be in range of the bound index buffer.
+    ///
+    /// A graphics pipeline must have been bound using
+    /// [`bind_pipeline_graphics`](Self::bind_pipeline_graphics). Any resources used by the graphics
+    /// pipeline, such as descriptor sets, vertex buffers and dynamic state, must have been set
+    /// beforehand. If the bound graphics pipeline uses vertex buffers, then the provided instance
+    /// range must be in range of the bound vertex buffers. The vertex indices in the index buffer
+    /// must be in range of the bound vertex buffers.
+    pub fn draw_multi_indexed(
+        &mut self,
+        draw_infos: &[DrawIndexedInfo],
+        vertex_offset: Option<i32>,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_multi_indexed(
+            draw_infos,
+            vertex_offset,
+            instance_count,
+            first_instance,
+        )?;
 
-        unsafe { Ok(self.draw_indexed_indirect_unchecked(indirect_buffer, draw_count, stride)) }
+        unsafe {
+            Ok(self.draw_multi_indexed_unchecked(
+                draw_infos,
+                vertex_offset,
+                instance_count,
+                first_instance,
+            ))
+        }
     }
 
-    fn validate_draw_indexed_indirect(
+    fn validate_draw_multi_indexed(
         &self,
-        indirect_buffer: &Subbuffer<[u8]>,
-        draw_count: u32,
-        stride: u32,
+        draw_infos: &[DrawIndexedInfo],
+        vertex_offset: Option<i32>,
+        instance_count: u32,
+        first_instance: u32,
     ) -> Result<(), Box<ValidationError>> {
-        self.inner
-            .validate_draw_indexed_indirect(indirect_buffer, draw_count, stride)?;
+        self.inner.validate_draw_multi_indexed(
+            draw_infos,
+            vertex_offset,
+            instance_count,
+            first_instance,
+        )?;
 
         let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
             Box::new(ValidationError {
                 problem: "a render pass instance is not active".into(),
-                vuids: &["VUID-vkCmdDrawIndexedIndirect-renderpass"],
+                vuids: &["VUID-vkCmdDrawMultiIndexedEXT-renderpass"],
                 ..Default::default()
             })
         })?;
@@ -753,36 +1663,124 @@ where
             .ok_or_else(|| {
                 Box::new(ValidationError {
                     problem: "no graphics pipeline is currently bound".into(),
-                    vuids: &["VUID-vkCmdDrawIndexedIndirect-None-08606"],
+                    vuids: &["VUID-vkCmdDrawMultiIndexedEXT-None-08606"],
                     ..Default::default()
                 })
             })?
             .as_ref();
 
-        const VUID_TYPE: VUIDType = VUIDType::DrawIndexedIndirect;
+        const VUID_TYPE: VUIDType = VUIDType::DrawMultiIndexed;
         self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
         self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
         self.validate_pipeline_graphics_dynamic_state(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_graphics_primitive_restart(pipeline)?;
+        self.validate_pipeline_graphics_queries(VUID_TYPE, pipeline)?;
         self.validate_pipeline_graphics_render_pass(VUID_TYPE, pipeline, render_pass_state)?;
         self.validate_pipeline_graphics_vertex_buffers(VUID_TYPE, pipeline)?;
 
-        let _index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
+        let index_buffer = self.builder_state.index_buffer.as_ref().ok_or_else(|| {
             Box::new(ValidationError {
                 problem: "no index buffer is currently bound".into(),
-                vuids: &["VUID-vkCmdDrawIndexedIndirect-None-07312"],
+                vuids: &["VUID-vkCmdDrawMultiIndexedEXT-None-07312"],
                 ..Default::default()
             })
         })?;
 
+        let index_buffer_bytes = index_buffer.as_bytes();
+
+        if !self.device().enabled_features().robust_buffer_access2 {
+            for (draw_index, draw_info) in draw_infos.iter().enumerate() {
+                let &DrawIndexedInfo {
+                    first_index,
+                    index_count,
+                    vertex_offset: _,
+                } = draw_info;
+
+                if index_buffer.index_type().size()
+                    * (first_index as DeviceSize + index_count as DeviceSize)
+                    > index_buffer_bytes.size()
+                {
+                    return Err(Box::new(ValidationError {
+                        context: format!("draw_infos[{}]", draw_index).into(),
+                        problem: "`first_index + index_count`, \
+                            multiplied by the size of the indices in the bound index buffer, \
+                            is greater than the size of the bound index buffer"
+                            .into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "robust_buffer_access2",
+                        )])]),
+                        vuids: &["VUID-vkCmdDrawMultiIndexedEXT-robustBufferAccess2-07825"],
+                    }));
+                }
+            }
+        }
+
+        let view_mask = match pipeline.subpass() {
+            PipelineSubpassType::BeginRenderPass(subpass) => subpass.render_pass().views_used(),
+            PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
+        };
+
+        if view_mask != 0 {
+            let properties = self.device().physical_device().properties();
+
+            if (first_instance + instance_count).saturating_sub(1)
+                > properties.max_multiview_instance_index.unwrap_or(0)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "the current render pass instance has a nonzero view mask, but \
+                        `first_instance + instance_count - 1` is greater than the \
+                        `max_multiview_instance_index` limit"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawMultiIndexedEXT-maxMultiviewInstanceIndex-02688"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        for (&binding_num, binding_desc) in &pipeline.vertex_input_state().bindings {
+            let vertex_buffer = &self.builder_state.vertex_buffers[&binding_num];
+
+            // Per spec:
+            // https://registry.khronos.org/vulkan/specs/1.3-extensions/html/chap22.html#fxvertex-input-address-calculation
+            match binding_desc.input_rate {
+                VertexInputRate::Vertex => (),
+                VertexInputRate::Instance { divisor } => {
+                    let max_vertex_offset = if divisor == 0 {
+                        (first_instance as DeviceSize + 1) * binding_desc.stride as DeviceSize
+                    } else {
+                        (first_instance as DeviceSize
+                            + instance_count as DeviceSize / divisor as DeviceSize)
+                            * binding_desc.stride as DeviceSize
+                    };
+
+                    if max_vertex_offset > vertex_buffer.size() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the size of the vertex buffer bound to binding {} is less \
+                                than the minimum size required, for the provided \
+                                `first_instance` and `instance_count` values, and the vertex \
+                                input state bindings of the currently bound graphics pipeline",
+                                binding_num
+                            )
+                            .into(),
+                            vuids: &["VUID-vkCmdDrawMultiIndexedEXT-None-02721"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+            };
+        }
+
         Ok(())
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn draw_indexed_indirect_unchecked(
+    pub unsafe fn draw_multi_indexed_unchecked(
         &mut self,
-        indirect_buffer: Subbuffer<[DrawIndexedIndirectCommand]>,
-        draw_count: u32,
-        stride: u32,
+        draw_infos: &[DrawIndexedInfo],
+        vertex_offset: Option<i32>,
+        instance_count: u32,
+        first_instance: u32,
     ) -> &mut Self {
         if let RenderPassStateType::BeginRendering(state) =
             &mut self.builder_state.render_pass.as_mut().unwrap().render_pass
@@ -800,14 +1798,138 @@ where
         let mut used_resources = Vec::new();
         self.add_descriptor_sets_resources(&mut used_resources, pipeline);
         self.add_vertex_buffers_resources(&mut used_resources, pipeline);
+        self.add_transform_feedback_buffers_resources(&mut used_resources);
         self.add_index_buffer_resources(&mut used_resources);
-        self.add_indirect_buffer_resources(&mut used_resources, indirect_buffer.as_bytes());
+
+        let draw_infos: SmallVec<[_; 8]> = draw_infos.into();
 
         self.add_command(
-            "draw_indexed_indirect",
+            "draw_multi_indexed",
             used_resources,
             move |out: &mut UnsafeCommandBufferBuilder<A>| {
-                out.draw_indexed_indirect_unchecked(&indirect_buffer, draw_count, stride);
+                out.draw_multi_indexed_unchecked(
+                    &draw_infos,
+                    vertex_offset,
+                    instance_count,
+                    first_instance,
+                );
+            },
+        );
+
+        self
+    }
+
+    /// Runs the bound subpass shading pipeline once for every pixel of the render area of the
+    /// current subpass.
+    ///
+    /// A subpass shading pipeline must have been bound using
+    /// [`bind_pipeline_subpass_shading`](Self::bind_pipeline_subpass_shading), and it must have
+    /// been created for the current subpass. Any resources used by the pipeline, such as
+    /// descriptor sets, must have been set beforehand.
+    pub fn subpass_shading(&mut self) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_subpass_shading()?;
+
+        unsafe { Ok(self.subpass_shading_unchecked()) }
+    }
+
+    fn validate_subpass_shading(&self) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_subpass_shading()?;
+
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdSubpassShadingHUAWEI-renderpass"],
+                ..Default::default()
+            })
+        })?;
+
+        let pipeline = self
+            .builder_state
+            .pipeline_subpass_shading
+            .as_ref()
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "no subpass shading pipeline is currently bound".into(),
+                    vuids: &["VUID-vkCmdSubpassShadingHUAWEI-None-08606"],
+                    ..Default::default()
+                })
+            })?
+            .as_ref();
+
+        if render_pass_state.contents != SubpassContents::Inline {
+            return Err(Box::new(ValidationError {
+                problem: "the contents of the current subpass instance is not \
+                    `SubpassContents::Inline`"
+                    .into(),
+                // The specification states this in the description of `VkSubpassContents`,
+                // without a VUID.
+                ..Default::default()
+            }));
+        }
+
+        match &render_pass_state.render_pass {
+            RenderPassStateType::BeginRenderPass(state) => {
+                if !pipeline
+                    .subpass()
+                    .render_pass()
+                    .is_compatible_with(state.subpass.render_pass())
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: "the current render pass instance is not compatible with the \
+                            render pass that the currently bound subpass shading pipeline was \
+                            created with"
+                            .into(),
+                        vuids: &["VUID-vkCmdSubpassShadingHUAWEI-None-04931"],
+                        ..Default::default()
+                    }));
+                }
+
+                if pipeline.subpass().index() != state.subpass.index() {
+                    return Err(Box::new(ValidationError {
+                        problem: "the subpass index of the current render pass instance is not \
+                            equal to the index of the subpass that the currently bound \
+                            subpass shading pipeline was created with"
+                            .into(),
+                        vuids: &["VUID-vkCmdSubpassShadingHUAWEI-None-04931"],
+                        ..Default::default()
+                    }));
+                }
+            }
+            RenderPassStateType::BeginRendering(_) => {
+                return Err(Box::new(ValidationError {
+                    problem: "the current render pass instance was begun with \
+                        `begin_rendering`"
+                        .into(),
+                    vuids: &["VUID-vkCmdSubpassShadingHUAWEI-None-04931"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        const VUID_TYPE: VUIDType = VUIDType::SubpassShading;
+        self.validate_pipeline_descriptor_sets(VUID_TYPE, pipeline)?;
+        self.validate_pipeline_push_constants(VUID_TYPE, pipeline.layout())?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn subpass_shading_unchecked(&mut self) -> &mut Self {
+        let pipeline = self
+            .builder_state
+            .pipeline_subpass_shading
+            .as_ref()
+            .unwrap()
+            .as_ref();
+
+        let mut used_resources = Vec::new();
+        self.add_descriptor_sets_resources(&mut used_resources, pipeline);
+
+        self.add_command(
+            "subpass_shading",
+            used_resources,
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.subpass_shading_unchecked();
             },
         );
 
@@ -824,27 +1946,35 @@ where
             set_num: u32,
             binding_num: u32,
             binding_reqs: &DescriptorBindingRequirements,
+            partially_bound: bool,
             elements: &[Option<T>],
             mut extra_check: impl FnMut(u32, u32, u32, &T) -> Result<(), Box<ValidationError>>,
         ) -> Result<(), Box<ValidationError>> {
             let elements_to_check = if let Some(descriptor_count) = binding_reqs.descriptor_count {
                 // The shader has a fixed-sized array, so it will never access more than
                 // the first `descriptor_count` elements.
-                elements.get(..descriptor_count as usize).ok_or_else(|| {
-                    // There are less than `descriptor_count` elements in `elements`
-                    Box::new(ValidationError {
-                        problem: format!(
-                            "the currently bound pipeline accesses the resource bound to \
+                if partially_bound {
+                    // Unwritten descriptors are allowed, as long as the shader doesn't access
+                    // them. We can't know which ones it accesses, so only check the written
+                    // ones.
+                    &elements[..elements.len().min(descriptor_count as usize)]
+                } else {
+                    elements.get(..descriptor_count as usize).ok_or_else(|| {
+                        // There are less than `descriptor_count` elements in `elements`
+                        Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound pipeline accesses the resource bound to \
                             descriptor set {set_num}, binding {binding_num}, \
                             descriptor index {}, but no descriptor was written to the \
                             descriptor set currently bound to set {set_num}",
-                            elements.len()
-                        )
-                        .into(),
-                        vuids: vuids!(vuid_type, "None-02699"),
-                        ..Default::default()
-                    })
-                })?
+                                elements.len()
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "None-02699"),
+                            ..Default::default()
+                        })
+                    })?
+                }
             } else {
                 // The shader has a runtime-sized array, so any element could potentially
                 // be accessed. We must check them all.
@@ -856,6 +1986,7 @@ where
 
                 let element = match element {
                     Some(x) => x,
+                    None if partially_bound => continue,
                     None => {
                         return Err(Box::new(ValidationError {
                             problem: format!(
@@ -910,8 +2041,56 @@ where
         }
 
         for (&(set_num, binding_num), binding_reqs) in pipeline.descriptor_binding_requirements() {
-            let layout_binding =
-                &pipeline.layout().set_layouts()[set_num as usize].bindings()[&binding_num];
+            let set_layout = &pipeline.layout().set_layouts()[set_num as usize];
+            let layout_binding = &set_layout.bindings()[&binding_num];
+
+            if set_layout
+                .flags()
+                .intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER)
+            {
+                // The descriptors are read from a descriptor buffer, whose contents can't be
+                // checked.
+                let (buffer_index, offset) =
+                    match descriptor_set_state.descriptor_sets.get(&set_num) {
+                        Some(&SetOrPush::Buffer {
+                            buffer_index,
+                            offset,
+                        }) => (buffer_index, offset),
+                        _ => {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound pipeline accesses descriptor set \
+                                {set_num}, which uses a descriptor buffer, but no descriptor \
+                                buffer offset was previously set for it"
+                                )
+                                .into(),
+                                // vuids?
+                                ..Default::default()
+                            }));
+                        }
+                    };
+
+                if !self
+                    .builder_state
+                    .descriptor_buffers
+                    .get(buffer_index as usize)
+                    .is_some_and(|binding_info| offset < binding_info.buffer.size())
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "the currently bound pipeline accesses descriptor set {set_num}, \
+                            which uses descriptor buffer {buffer_index}, but the descriptor \
+                            buffers that are currently bound do not contain the offset that was \
+                            set for it"
+                        )
+                        .into(),
+                        // vuids?
+                        ..Default::default()
+                    }));
+                }
+
+                continue;
+            }
 
             let check_buffer =
                 |_set_num: u32,
@@ -1295,7 +2474,8 @@ where
                             descriptor_set_state
                                 .descriptor_sets
                                 .get(&id.set)
-                                .and_then(|set| set.resources().binding(id.binding))
+                                .and_then(SetOrPush::resources)
+                                .and_then(|set_resources| set_resources.binding(id.binding))
                                 .and_then(|res| match res {
                                     DescriptorBindingResources::ImageView(elements) => elements
                                         .get(id.index as usize)
@@ -1354,6 +2534,7 @@ where
             let set_resources = descriptor_set_state
                 .descriptor_sets
                 .get(&set_num)
+                .and_then(SetOrPush::resources)
                 .ok_or_else(|| {
                     Box::new(ValidationError {
                         problem: format!(
@@ -1364,10 +2545,12 @@ where
                         // vuids?
                         ..Default::default()
                     })
-                })?
-                .resources();
+                })?;
 
             let binding_resources = set_resources.binding(binding_num).unwrap();
+            let partially_bound = layout_binding
+                .binding_flags
+                .intersects(DescriptorBindingFlags::PARTIALLY_BOUND);
 
             match binding_resources {
                 DescriptorBindingResources::None(elements) => {
@@ -1376,6 +2559,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        partially_bound,
                         elements,
                         check_none,
                     )?;
@@ -1386,6 +2570,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        partially_bound,
                         elements,
                         check_buffer,
                     )?;
@@ -1396,6 +2581,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        partially_bound,
                         elements,
                         check_buffer_view,
                     )?;
@@ -1406,6 +2592,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        partially_bound,
                         elements,
                         check_image_view,
                     )?;
@@ -1416,6 +2603,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        partially_bound,
                         elements,
                         check_image_view_sampler,
                     )?;
@@ -1426,6 +2614,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        partially_bound,
                         elements,
                         check_sampler,
                     )?;
@@ -1443,6 +2632,7 @@ where
                         set_num,
                         binding_num,
                         binding_reqs,
+                        partially_bound,
                         elements,
                         check_acceleration_structure,
                     )?;
@@ -1787,87 +2977,23 @@ where
                                 dynamic_state
                             ).into(),
                             vuids: vuids!(vuid_type, "None-04875"),
-                            ..Default::default()
-                        }));
-                    }
-                }
-                DynamicState::PrimitiveRestartEnable => {
-                    let primitive_restart_enable =
-                        if let Some(enable) = self.builder_state.primitive_restart_enable {
-                            enable
-                        } else {
-                            return Err(Box::new(ValidationError {
-                                problem: format!(
-                                    "the currently bound graphics pipeline requires the \
-                                    `DynamicState::{:?}` dynamic state, but \
-                                    this state was either not set, or it was overwritten by a \
-                                    more recent `bind_pipeline_graphics` command",
-                                    dynamic_state
-                                ).into(),
-                                vuids: vuids!(vuid_type, "None-04879"),
-                                ..Default::default()
-                            }));
-                        };
-
-                    if primitive_restart_enable {
-                        let topology = match pipeline.input_assembly_state().topology {
-                            PartialStateMode::Fixed(topology) => topology,
-                            PartialStateMode::Dynamic(_) => {
-                                if let Some(topology) = self.builder_state.primitive_topology {
-                                    topology
-                                } else {
-                                    return Err(Box::new(ValidationError {
-                                        problem: "the currently bound graphics pipeline requires \
-                                            the `DynamicState::PrimitiveTopology` dynamic state, \
-                                            but this state was either not set, or it was \
-                                            overwritten by a more recent `bind_pipeline_graphics` \
-                                            command"
-                                            .into(),
-                                        vuids: vuids!(vuid_type, "None-07842"),
-                                        ..Default::default()
-                                    }));
-                                }
-                            }
-                        };
-
-                        match topology {
-                            PrimitiveTopology::PointList
-                            | PrimitiveTopology::LineList
-                            | PrimitiveTopology::TriangleList
-                            | PrimitiveTopology::LineListWithAdjacency
-                            | PrimitiveTopology::TriangleListWithAdjacency => {
-                                if !device.enabled_features().primitive_topology_list_restart {
-                                    return Err(Box::new(ValidationError {
-                                        problem: "primitive restart is enabled for the currently \
-                                            bound graphics pipeline, but the currently set \
-                                            dynamic primitive topology is \
-                                            `PrimitiveTopology::*List`"
-                                            .into(),
-                                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature("primitive_topology_list_restart")])]),
-                                        // vuids?
-                                        ..Default::default()
-                                    }));
-                                }
-                            }
-                            PrimitiveTopology::PatchList => {
-                                if !device
-                                    .enabled_features()
-                                    .primitive_topology_patch_list_restart
-                                {
-                                    return Err(Box::new(ValidationError {
-                                        problem: "primitive restart is enabled for the currently \
-                                            bound graphics pipeline, but the currently set \
-                                            dynamic primitive topology is \
-                                            `PrimitiveTopology::PatchList`"
-                                            .into(),
-                                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature("primitive_topology_patch_list_restart")])]),
-                                        // vuids?
-                                        ..Default::default()
-                                    }));
-                                }
-                            }
-                            _ => (),
-                        }
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::PrimitiveRestartEnable => {
+                    if self.builder_state.primitive_restart_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            ).into(),
+                            vuids: vuids!(vuid_type, "None-04879"),
+                            ..Default::default()
+                        }));
                     }
                 }
                 DynamicState::PrimitiveTopology => {
@@ -2191,15 +3317,77 @@ where
                 DynamicState::ViewportWScaling => todo!(),
                 DynamicState::TessellationDomainOrigin => todo!(),
                 DynamicState::DepthClampEnable => todo!(),
-                DynamicState::PolygonMode => todo!(),
-                DynamicState::RasterizationSamples => todo!(),
+                DynamicState::PolygonMode => {
+                    if self.builder_state.polygon_mode.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            ).into(),
+                            vuids: vuids!(vuid_type, "None-07621"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+                DynamicState::RasterizationSamples => {
+                    if self.builder_state.rasterization_samples.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            ).into(),
+                            vuids: vuids!(vuid_type, "None-07622"),
+                            ..Default::default()
+                        }));
+                    }
+                }
                 DynamicState::SampleMask => todo!(),
                 DynamicState::AlphaToCoverageEnable => todo!(),
                 DynamicState::AlphaToOneEnable => todo!(),
                 DynamicState::LogicOpEnable => todo!(),
-                DynamicState::ColorBlendEnable => todo!(),
+                DynamicState::ColorBlendEnable => {
+                    for num in 0..pipeline.color_blend_state().unwrap().attachments.len() as u32 {
+                        if !self.builder_state.color_blend_enable.contains_key(&num) {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound graphics pipeline requires the \
+                                    `DynamicState::{:?}` dynamic state, but \
+                                    this state was either not set for all color attachments, \
+                                    or it was overwritten by a more recent \
+                                    `bind_pipeline_graphics` command",
+                                    dynamic_state
+                                ).into(),
+                                vuids: vuids!(vuid_type, "firstAttachment-07476"),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
                 DynamicState::ColorBlendEquation => todo!(),
-                DynamicState::ColorWriteMask => todo!(),
+                DynamicState::ColorWriteMask => {
+                    for num in 0..pipeline.color_blend_state().unwrap().attachments.len() as u32 {
+                        if !self.builder_state.color_write_mask.contains_key(&num) {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "the currently bound graphics pipeline requires the \
+                                    `DynamicState::{:?}` dynamic state, but \
+                                    this state was either not set for all color attachments, \
+                                    or it was overwritten by a more recent \
+                                    `bind_pipeline_graphics` command",
+                                    dynamic_state
+                                ).into(),
+                                vuids: vuids!(vuid_type, "firstAttachment-07478"),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
                 DynamicState::RasterizationStream => todo!(),
                 DynamicState::ConservativeRasterizationMode => todo!(),
                 DynamicState::ExtraPrimitiveOverestimationSize => todo!(),
@@ -2220,7 +3408,151 @@ where
                 DynamicState::ShadingRateImageEnable => todo!(),
                 DynamicState::RepresentativeFragmentTestEnable => todo!(),
                 DynamicState::CoverageReductionMode => todo!(),
+                DynamicState::AttachmentFeedbackLoopEnable => {
+                    if self.builder_state.attachment_feedback_loop_enable.is_none() {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::{:?}` dynamic state, but \
+                                this state was either not set, or it was overwritten by a \
+                                more recent `bind_pipeline_graphics` command",
+                                dynamic_state
+                            ).into(),
+                            vuids: vuids!(vuid_type, "None-08877"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_pipeline_graphics_primitive_restart(
+        &self,
+        pipeline: &GraphicsPipeline,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = pipeline.device();
+        let input_assembly_state = pipeline.input_assembly_state();
+
+        // If either state is dynamic and not set, this was already reported by
+        // `validate_pipeline_graphics_dynamic_state`.
+        let primitive_restart_enable = match input_assembly_state.primitive_restart_enable {
+            StateMode::Fixed(enable) => enable,
+            StateMode::Dynamic => match self.builder_state.primitive_restart_enable {
+                Some(enable) => enable,
+                None => return Ok(()),
+            },
+        };
+
+        if !primitive_restart_enable {
+            return Ok(());
+        }
+
+        let (topology, topology_is_dynamic) = match input_assembly_state.topology {
+            PartialStateMode::Fixed(topology) => (topology, false),
+            PartialStateMode::Dynamic(_) => match self.builder_state.primitive_topology {
+                Some(topology) => (topology, true),
+                None => return Ok(()),
+            },
+        };
+
+        // If both states are fixed, this was already checked when creating the pipeline.
+        if !topology_is_dynamic
+            && matches!(
+                input_assembly_state.primitive_restart_enable,
+                StateMode::Fixed(_)
+            )
+        {
+            return Ok(());
+        }
+
+        match topology {
+            PrimitiveTopology::PointList
+            | PrimitiveTopology::LineList
+            | PrimitiveTopology::TriangleList
+            | PrimitiveTopology::LineListWithAdjacency
+            | PrimitiveTopology::TriangleListWithAdjacency => {
+                if !device.enabled_features().primitive_topology_list_restart {
+                    return Err(Box::new(ValidationError {
+                        problem: "primitive restart is enabled, but the current primitive \
+                            topology is `PrimitiveTopology::*List`"
+                            .into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "primitive_topology_list_restart",
+                        )])]),
+                        // vuids?
+                        ..Default::default()
+                    }));
+                }
+            }
+            PrimitiveTopology::PatchList => {
+                if !device
+                    .enabled_features()
+                    .primitive_topology_patch_list_restart
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: "primitive restart is enabled, but the current primitive \
+                            topology is `PrimitiveTopology::PatchList`"
+                            .into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "primitive_topology_patch_list_restart",
+                        )])]),
+                        // vuids?
+                        ..Default::default()
+                    }));
+                }
             }
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn validate_pipeline_graphics_queries(
+        &self,
+        vuid_type: VUIDType,
+        pipeline: &GraphicsPipeline,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = pipeline.device();
+
+        if !self
+            .builder_state
+            .queries
+            .contains_key(&(&QueryType::PrimitivesGenerated).into())
+            || device
+                .enabled_features()
+                .primitives_generated_query_with_rasterizer_discard
+        {
+            return Ok(());
+        }
+
+        // If the state is dynamic and not set, this was already reported by
+        // `validate_pipeline_graphics_dynamic_state`.
+        let rasterizer_discard_enable =
+            match pipeline.rasterization_state().rasterizer_discard_enable {
+                StateMode::Fixed(enable) => enable,
+                StateMode::Dynamic => self
+                    .builder_state
+                    .rasterizer_discard_enable
+                    .unwrap_or(false),
+            };
+
+        if rasterizer_discard_enable {
+            return Err(Box::new(ValidationError {
+                problem: "a `QueryType::PrimitivesGenerated` query is active, and \
+                    rasterizer discard is enabled"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "primitives_generated_query_with_rasterizer_discard",
+                )])]),
+                vuids: vuids!(
+                    vuid_type,
+                    "primitivesGeneratedQueryWithRasterizerDiscard-06708"
+                ),
+                ..Default::default()
+            }));
         }
 
         Ok(())
@@ -2271,6 +3603,104 @@ where
                         ..Default::default()
                     }));
                 }
+
+                // If the state is dynamic and not set, this was already reported by
+                // `validate_pipeline_graphics_dynamic_state`.
+                let feedback_loop_aspects =
+                    match pipeline.dynamic_state(DynamicState::AttachmentFeedbackLoopEnable) {
+                        Some(true) => self
+                            .builder_state
+                            .attachment_feedback_loop_enable
+                            .unwrap_or_default(),
+                        _ => {
+                            let mut aspects = ImageAspects::empty();
+
+                            if pipeline
+                                .flags()
+                                .intersects(PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP)
+                            {
+                                aspects |= ImageAspects::COLOR;
+                            }
+
+                            if pipeline.flags().intersects(
+                                PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP,
+                            ) {
+                                aspects |= ImageAspects::DEPTH | ImageAspects::STENCIL;
+                            }
+
+                            aspects
+                        }
+                    };
+
+                let subpass_desc = state.subpass.subpass_desc();
+
+                // If the state is dynamic and not set, this was already reported by
+                // `validate_pipeline_graphics_dynamic_state`.
+                if let (Some(true), Some(rasterization_samples), Some(samples)) = (
+                    pipeline.dynamic_state(DynamicState::RasterizationSamples),
+                    self.builder_state.rasterization_samples,
+                    state.subpass.num_samples(),
+                ) {
+                    if rasterization_samples != samples {
+                        return Err(Box::new(ValidationError {
+                            problem: format!(
+                                "the currently bound graphics pipeline requires the \
+                                `DynamicState::RasterizationSamples` dynamic state, but \
+                                the dynamic rasterization samples ({:?}) do not equal the \
+                                number of samples in the color and depth/stencil attachments \
+                                of the current subpass ({:?})",
+                                rasterization_samples, samples,
+                            )
+                            .into(),
+                            vuids: vuids!(vuid_type, "rasterizationSamples-07474"),
+                            ..Default::default()
+                        }));
+                    }
+                }
+
+                if !feedback_loop_aspects.intersects(ImageAspects::COLOR)
+                    && subpass_desc
+                        .color_attachments
+                        .iter()
+                        .flatten()
+                        .any(|atch_ref| {
+                            atch_ref.layout == ImageLayout::AttachmentFeedbackLoopOptimal
+                        })
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: "a color attachment of the current subpass is in the \
+                            `ImageLayout::AttachmentFeedbackLoopOptimal` layout, but the \
+                            currently bound graphics pipeline was not created with \
+                            `PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP`, and \
+                            the attachment feedback loop is not enabled for \
+                            `ImageAspects::COLOR` with `set_attachment_feedback_loop_enable`"
+                            .into(),
+                        ..Default::default()
+                    }));
+                }
+
+                if !feedback_loop_aspects.intersects(ImageAspects::DEPTH | ImageAspects::STENCIL)
+                    && subpass_desc
+                        .depth_stencil_attachment
+                        .as_ref()
+                        .is_some_and(|atch_ref| {
+                            atch_ref.layout == ImageLayout::AttachmentFeedbackLoopOptimal
+                                || atch_ref.stencil_layout
+                                    == Some(ImageLayout::AttachmentFeedbackLoopOptimal)
+                        })
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: "the depth/stencil attachment of the current subpass is in the \
+                            `ImageLayout::AttachmentFeedbackLoopOptimal` layout, but the \
+                            currently bound graphics pipeline was not created with \
+                            `PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP`, and \
+                            the attachment feedback loop is not enabled for \
+                            `ImageAspects::DEPTH` or `ImageAspects::STENCIL` with \
+                            `set_attachment_feedback_loop_enable`"
+                            .into(),
+                        ..Default::default()
+                    }));
+                }
             }
             (
                 RenderPassStateType::BeginRendering(_),
@@ -2294,7 +3724,7 @@ where
                 {
                     return Err(Box::new(ValidationError {
                         problem: "`color_attachments.len()` of the current render pass \
-                            instance is not equal to `color_attachment_formats.len()` that the
+                            instance is not equal to `color_attachment_formats.len()` that the \
                             currently bound graphics pipeline was created with"
                             .into(),
                         vuids: vuids!(vuid_type, "colorAttachmentCount-06179"),
@@ -2486,7 +3916,14 @@ where
 
             let descriptor_set_state = &descriptor_sets_state.descriptor_sets[&set];
 
-            match descriptor_set_state.resources().binding(binding).unwrap() {
+            // Resources accessed through descriptor buffers are not tracked, this is the
+            // responsibility of the caller of `bind_descriptor_buffers`.
+            let set_resources = match descriptor_set_state.resources() {
+                Some(set_resources) => set_resources,
+                None => continue,
+            };
+
+            match set_resources.binding(binding).unwrap() {
                 DescriptorBindingResources::None(_) => (),
                 DescriptorBindingResources::Buffer(elements) => {
                     if matches!(
@@ -2650,6 +4087,29 @@ where
         ));
     }
 
+    fn add_transform_feedback_buffers_resources(
+        &self,
+        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
+    ) {
+        if !self.builder_state.transform_feedback_active {
+            return;
+        }
+
+        used_resources.extend(self.builder_state.transform_feedback_buffers.iter().map(
+            |(&binding, transform_feedback_buffer)| {
+                (
+                    ResourceInCommand::TransformFeedbackBuffer { binding }.into(),
+                    Resource::Buffer {
+                        buffer: transform_feedback_buffer.clone(),
+                        range: 0..transform_feedback_buffer.size(),
+                        memory_access:
+                            PipelineStageAccessFlags::TransformFeedback_TransformFeedbackWrite,
+                    },
+                )
+            },
+        ));
+    }
+
     fn add_index_buffer_resources(&self, used_resources: &mut Vec<(ResourceUseRef2, Resource)>) {
         let index_buffer_bytes = self.builder_state.index_buffer.as_ref().unwrap().as_bytes();
         used_resources.push((
@@ -2676,6 +4136,21 @@ where
             },
         ));
     }
+
+    fn add_count_buffer_resources(
+        &self,
+        used_resources: &mut Vec<(ResourceUseRef2, Resource)>,
+        count_buffer: &Subbuffer<[u8]>,
+    ) {
+        used_resources.push((
+            ResourceInCommand::CountBuffer.into(),
+            Resource::Buffer {
+                buffer: count_buffer.clone(),
+                range: 0..count_buffer.size(),
+                memory_access: PipelineStageAccessFlags::DrawIndirect_IndirectCommandRead,
+            },
+        ));
+    }
 }
 
 impl<A> UnsafeCommandBufferBuilder<A>
@@ -2751,6 +4226,131 @@ where
         self
     }
 
+    pub unsafe fn dispatch_base(
+        &mut self,
+        base_group: [u32; 3],
+        group_counts: [u32; 3],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_dispatch_base(base_group, group_counts)?;
+
+        Ok(self.dispatch_base_unchecked(base_group, group_counts))
+    }
+
+    fn validate_dispatch_base(
+        &self,
+        base_group: [u32; 3],
+        group_counts: [u32; 3],
+    ) -> Result<(), Box<ValidationError>> {
+        if !(self.device().api_version() >= Version::V1_1
+            || self.device().enabled_extensions().khr_device_group)
+        {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[
+                    RequiresAllOf(&[Requires::APIVersion(Version::V1_1)]),
+                    RequiresAllOf(&[Requires::DeviceExtension("khr_device_group")]),
+                ]),
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::COMPUTE)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    compute operations"
+                    .into(),
+                vuids: &["VUID-vkCmdDispatchBase-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        let vuids: [(&'static [_], &'static [_]); 3] = [
+            (
+                &["VUID-vkCmdDispatchBase-baseGroupX-00421"],
+                &["VUID-vkCmdDispatchBase-groupCountX-00424"],
+            ),
+            (
+                &["VUID-vkCmdDispatchBase-baseGroupY-00422"],
+                &["VUID-vkCmdDispatchBase-groupCountY-00425"],
+            ),
+            (
+                &["VUID-vkCmdDispatchBase-baseGroupZ-00423"],
+                &["VUID-vkCmdDispatchBase-groupCountZ-00426"],
+            ),
+        ];
+
+        for (i, (vuids_base_group, vuids_group_count)) in vuids.into_iter().enumerate() {
+            let max = properties.max_compute_work_group_count[i];
+
+            if base_group[i] >= max {
+                return Err(Box::new(ValidationError {
+                    context: format!("base_group[{}]", i).into(),
+                    problem: format!(
+                        "is not less than the `max_compute_work_group_count[{}]` limit",
+                        i,
+                    )
+                    .into(),
+                    vuids: vuids_base_group,
+                    ..Default::default()
+                }));
+            }
+
+            if group_counts[i] > max - base_group[i] {
+                return Err(Box::new(ValidationError {
+                    context: format!("group_counts[{}]", i).into(),
+                    problem: format!(
+                        "is greater than the `max_compute_work_group_count[{0}]` limit minus \
+                        `base_group[{0}]`",
+                        i,
+                    )
+                    .into(),
+                    vuids: vuids_group_count,
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn dispatch_base_unchecked(
+        &mut self,
+        base_group: [u32; 3],
+        group_counts: [u32; 3],
+    ) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self.device().api_version() >= Version::V1_1 {
+            (fns.v1_1.cmd_dispatch_base)(
+                self.handle(),
+                base_group[0],
+                base_group[1],
+                base_group[2],
+                group_counts[0],
+                group_counts[1],
+                group_counts[2],
+            );
+        } else {
+            (fns.khr_device_group.cmd_dispatch_base_khr)(
+                self.handle(),
+                base_group[0],
+                base_group[1],
+                base_group[2],
+                group_counts[0],
+                group_counts[1],
+                group_counts[2],
+            );
+        }
+
+        self
+    }
+
     pub unsafe fn dispatch_indirect(
         &mut self,
         indirect_buffer: &Subbuffer<[DispatchIndirectCommand]>,
@@ -2947,48 +4547,419 @@ where
                 }));
             }
 
-            if (stride as DeviceSize) < size_of::<DrawIndirectCommand>() as DeviceSize {
-                return Err(Box::new(ValidationError {
-                    problem: "`draw_count` is greater than 1, but \
-                        `stride` is not greater than `size_of::<DrawIndirectCommand>()`"
-                        .into(),
-                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00476"],
-                    ..Default::default()
-                }));
-            }
+            if (stride as DeviceSize) < size_of::<DrawIndirectCommand>() as DeviceSize {
+                return Err(Box::new(ValidationError {
+                    problem: "`draw_count` is greater than 1, but \
+                        `stride` is not greater than `size_of::<DrawIndirectCommand>()`"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00476"],
+                    ..Default::default()
+                }));
+            }
+
+            if stride as DeviceSize * (draw_count as DeviceSize - 1)
+                + size_of::<DrawIndirectCommand>() as DeviceSize
+                > indirect_buffer.size()
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "`draw_count` is greater than 1, but \
+                        `stride * (draw_count - 1) + size_of::<DrawIndirectCommand>()` is \
+                        greater than `indirect_buffer.size()`"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00488"],
+                    ..Default::default()
+                }));
+            }
+        } else {
+            if size_of::<DrawIndirectCommand>() as DeviceSize > indirect_buffer.size() {
+                return Err(Box::new(ValidationError {
+                    problem: "`draw_count` is 1, but `size_of::<DrawIndirectCommand>()` is \
+                        greater than `indirect_buffer.size()`"
+                        .into(),
+                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00487"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        let properties = self.device().physical_device().properties();
+
+        if draw_count > properties.max_draw_indirect_count {
+            return Err(Box::new(ValidationError {
+                context: "draw_count".into(),
+                problem: "is greater than the `max_draw_indirect_count` limit".into(),
+                vuids: &["VUID-vkCmdDrawIndirect-drawCount-02719"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indirect_unchecked(
+        &mut self,
+        indirect_buffer: &Subbuffer<[DrawIndirectCommand]>,
+        draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.v1_0.cmd_draw_indirect)(
+            self.handle(),
+            indirect_buffer.buffer().handle(),
+            indirect_buffer.offset(),
+            draw_count,
+            stride,
+        );
+
+        self
+    }
+
+    pub unsafe fn draw_indirect_count(
+        &mut self,
+        indirect_buffer: &Subbuffer<[DrawIndirectCommand]>,
+        count_buffer: &Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_indirect_count(
+            indirect_buffer.as_bytes(),
+            count_buffer,
+            max_draw_count,
+            stride,
+        )?;
+
+        Ok(self.draw_indirect_count_unchecked(
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
+        ))
+    }
+
+    fn validate_draw_indirect_count(
+        &self,
+        indirect_buffer: &Subbuffer<[u8]>,
+        count_buffer: &Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().draw_indirect_count {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "draw_indirect_count",
+                )])]),
+                vuids: &["VUID-vkCmdDrawIndirectCount-None-04445"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawIndirectCount-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdDrawIndirectCount-commonparent
+        assert_eq!(self.device(), indirect_buffer.device());
+        assert_eq!(self.device(), count_buffer.device());
+
+        if !indirect_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(Box::new(ValidationError {
+                context: "indirect_buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                vuids: &["VUID-vkCmdDrawIndirectCount-buffer-02709"],
+                ..Default::default()
+            }));
+        }
+
+        if !count_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(Box::new(ValidationError {
+                context: "count_buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                vuids: &["VUID-vkCmdDrawIndirectCount-countBuffer-02714"],
+                ..Default::default()
+            }));
+        }
+
+        if stride % 4 != 0 {
+            return Err(Box::new(ValidationError {
+                context: "stride".into(),
+                problem: "is not a multiple of 4".into(),
+                vuids: &["VUID-vkCmdDrawIndirectCount-stride-03110"],
+                ..Default::default()
+            }));
+        }
+
+        if (stride as DeviceSize) < size_of::<DrawIndirectCommand>() as DeviceSize {
+            return Err(Box::new(ValidationError {
+                context: "stride".into(),
+                problem: "is less than `size_of::<DrawIndirectCommand>()`".into(),
+                vuids: &["VUID-vkCmdDrawIndirectCount-stride-03110"],
+                ..Default::default()
+            }));
+        }
+
+        if max_draw_count >= 1
+            && stride as DeviceSize * (max_draw_count as DeviceSize - 1)
+                + size_of::<DrawIndirectCommand>() as DeviceSize
+                > indirect_buffer.size()
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`max_draw_count` is not 0, but \
+                    `stride * (max_draw_count - 1) + size_of::<DrawIndirectCommand>()` is \
+                    greater than `indirect_buffer.size()`"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawIndirectCount-maxDrawCount-03111"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdDrawIndirectCount-countBuffer-02717
+        // Can't be checked, the value is only read when the command is executed.
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indirect_count_unchecked(
+        &mut self,
+        indirect_buffer: &Subbuffer<[DrawIndirectCommand]>,
+        count_buffer: &Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self.device().api_version() >= Version::V1_2 {
+            (fns.v1_2.cmd_draw_indirect_count)(
+                self.handle(),
+                indirect_buffer.buffer().handle(),
+                indirect_buffer.offset(),
+                count_buffer.buffer().handle(),
+                count_buffer.offset(),
+                max_draw_count,
+                stride,
+            );
+        } else {
+            (fns.khr_draw_indirect_count.cmd_draw_indirect_count_khr)(
+                self.handle(),
+                indirect_buffer.buffer().handle(),
+                indirect_buffer.offset(),
+                count_buffer.buffer().handle(),
+                count_buffer.offset(),
+                max_draw_count,
+                stride,
+            );
+        }
+
+        self
+    }
+
+    pub unsafe fn draw_indirect_byte_count(
+        &mut self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: &Subbuffer<u32>,
+        counter_offset: u32,
+        vertex_stride: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_indirect_byte_count(
+            instance_count,
+            first_instance,
+            counter_buffer,
+            counter_offset,
+            vertex_stride,
+        )?;
+
+        Ok(self.draw_indirect_byte_count_unchecked(
+            instance_count,
+            first_instance,
+            counter_buffer,
+            counter_offset,
+            vertex_stride,
+        ))
+    }
+
+    fn validate_draw_indirect_byte_count(
+        &self,
+        _instance_count: u32,
+        _first_instance: u32,
+        counter_buffer: &Subbuffer<u32>,
+        _counter_offset: u32,
+        vertex_stride: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_features().transform_feedback {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "transform_feedback",
+                )])]),
+                vuids: &["VUID-vkCmdDrawIndirectByteCountEXT-transformFeedback-02287"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = device.physical_device().properties();
+
+        if !properties.transform_feedback_draw.unwrap_or(false) {
+            return Err(Box::new(ValidationError {
+                problem: "the `transform_feedback_draw` property is not supported by the \
+                    physical device"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawIndirectByteCountEXT-transformFeedbackDraw-02288"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawIndirectByteCountEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdDrawIndirectByteCountEXT-commonparent
+        assert_eq!(device, counter_buffer.device());
+
+        if !counter_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(Box::new(ValidationError {
+                context: "counter_buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                vuids: &["VUID-vkCmdDrawIndirectByteCountEXT-counterBuffer-02290"],
+                ..Default::default()
+            }));
+        }
+
+        if counter_buffer.offset() % 4 != 0 {
+            return Err(Box::new(ValidationError {
+                context: "counter_buffer.offset()".into(),
+                problem: "is not a multiple of 4".into(),
+                vuids: &["VUID-vkCmdDrawIndirectByteCountEXT-counterBufferOffset-04568"],
+                ..Default::default()
+            }));
+        }
+
+        if vertex_stride == 0
+            || vertex_stride
+                > properties
+                    .max_transform_feedback_buffer_data_stride
+                    .unwrap_or(0)
+        {
+            return Err(Box::new(ValidationError {
+                context: "vertex_stride".into(),
+                problem: "is 0, or is greater than the \
+                    `max_transform_feedback_buffer_data_stride` limit"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawIndirectByteCountEXT-vertexStride-02289"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indirect_byte_count_unchecked(
+        &mut self,
+        instance_count: u32,
+        first_instance: u32,
+        counter_buffer: &Subbuffer<u32>,
+        counter_offset: u32,
+        vertex_stride: u32,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_transform_feedback.cmd_draw_indirect_byte_count_ext)(
+            self.handle(),
+            instance_count,
+            first_instance,
+            counter_buffer.buffer().handle(),
+            counter_buffer.offset(),
+            counter_offset,
+            vertex_stride,
+        );
+
+        self
+    }
+
+    pub unsafe fn draw_multi(
+        &mut self,
+        draw_infos: &[DrawInfo],
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_multi(draw_infos, instance_count, first_instance)?;
+
+        Ok(self.draw_multi_unchecked(draw_infos, instance_count, first_instance))
+    }
 
-            if stride as DeviceSize * (draw_count as DeviceSize - 1)
-                + size_of::<DrawIndirectCommand>() as DeviceSize
-                > indirect_buffer.size()
-            {
-                return Err(Box::new(ValidationError {
-                    problem: "`draw_count` is greater than 1, but \
-                        `stride * (draw_count - 1) + size_of::<DrawIndirectCommand>()` is \
-                        greater than `indirect_buffer.size()`"
-                        .into(),
-                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00488"],
-                    ..Default::default()
-                }));
-            }
-        } else {
-            if size_of::<DrawIndirectCommand>() as DeviceSize > indirect_buffer.size() {
-                return Err(Box::new(ValidationError {
-                    problem: "`draw_count` is 1, but `size_of::<DrawIndirectCommand>()` is \
-                        greater than `indirect_buffer.size()`"
-                        .into(),
-                    vuids: &["VUID-vkCmdDrawIndirect-drawCount-00487"],
-                    ..Default::default()
-                }));
-            }
+    fn validate_draw_multi(
+        &self,
+        draw_infos: &[DrawInfo],
+        _instance_count: u32,
+        _first_instance: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_features().multi_draw {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "multi_draw",
+                )])]),
+                vuids: &["VUID-vkCmdDrawMultiEXT-None-04933"],
+                ..Default::default()
+            }));
         }
 
-        let properties = self.device().physical_device().properties();
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawMultiEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
 
-        if draw_count > properties.max_draw_indirect_count {
+        let properties = device.physical_device().properties();
+
+        if draw_infos.len() >= properties.max_multi_draw_count.unwrap_or(0) as usize {
             return Err(Box::new(ValidationError {
-                context: "draw_count".into(),
-                problem: "is greater than the `max_draw_indirect_count` limit".into(),
-                vuids: &["VUID-vkCmdDrawIndirect-drawCount-02719"],
+                context: "draw_infos".into(),
+                problem: "the length is not less than the `max_multi_draw_count` limit".into(),
+                vuids: &["VUID-vkCmdDrawMultiEXT-drawCount-04934"],
                 ..Default::default()
             }));
         }
@@ -2997,19 +4968,20 @@ where
     }
 
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub unsafe fn draw_indirect_unchecked(
+    pub unsafe fn draw_multi_unchecked(
         &mut self,
-        indirect_buffer: &Subbuffer<[DrawIndirectCommand]>,
-        draw_count: u32,
-        stride: u32,
+        draw_infos: &[DrawInfo],
+        instance_count: u32,
+        first_instance: u32,
     ) -> &mut Self {
         let fns = self.device().fns();
-        (fns.v1_0.cmd_draw_indirect)(
+        (fns.ext_multi_draw.cmd_draw_multi_ext)(
             self.handle(),
-            indirect_buffer.buffer().handle(),
-            indirect_buffer.offset(),
-            draw_count,
-            stride,
+            draw_infos.len() as u32,
+            draw_infos.as_ptr() as *const ash::vk::MultiDrawInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<DrawInfo>() as u32,
         );
 
         self
@@ -3220,14 +5192,312 @@ where
 
         self
     }
+
+    pub unsafe fn draw_indexed_indirect_count(
+        &mut self,
+        indirect_buffer: &Subbuffer<[DrawIndexedIndirectCommand]>,
+        count_buffer: &Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_indexed_indirect_count(
+            indirect_buffer.as_bytes(),
+            count_buffer,
+            max_draw_count,
+            stride,
+        )?;
+
+        Ok(self.draw_indexed_indirect_count_unchecked(
+            indirect_buffer,
+            count_buffer,
+            max_draw_count,
+            stride,
+        ))
+    }
+
+    fn validate_draw_indexed_indirect_count(
+        &self,
+        indirect_buffer: &Subbuffer<[u8]>,
+        count_buffer: &Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        if !self.device().enabled_features().draw_indirect_count {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "draw_indirect_count",
+                )])]),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-None-04445"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdDrawIndexedIndirectCount-commonparent
+        assert_eq!(self.device(), indirect_buffer.device());
+        assert_eq!(self.device(), count_buffer.device());
+
+        if !indirect_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(Box::new(ValidationError {
+                context: "indirect_buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-buffer-02709"],
+                ..Default::default()
+            }));
+        }
+
+        if !count_buffer
+            .buffer()
+            .usage()
+            .intersects(BufferUsage::INDIRECT_BUFFER)
+        {
+            return Err(Box::new(ValidationError {
+                context: "count_buffer.usage()".into(),
+                problem: "does not contain `BufferUsage::INDIRECT_BUFFER`".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-countBuffer-02714"],
+                ..Default::default()
+            }));
+        }
+
+        if stride % 4 != 0 {
+            return Err(Box::new(ValidationError {
+                context: "stride".into(),
+                problem: "is not a multiple of 4".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-stride-03142"],
+                ..Default::default()
+            }));
+        }
+
+        if (stride as DeviceSize) < size_of::<DrawIndexedIndirectCommand>() as DeviceSize {
+            return Err(Box::new(ValidationError {
+                context: "stride".into(),
+                problem: "is less than `size_of::<DrawIndexedIndirectCommand>()`".into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-stride-03142"],
+                ..Default::default()
+            }));
+        }
+
+        if max_draw_count >= 1
+            && stride as DeviceSize * (max_draw_count as DeviceSize - 1)
+                + size_of::<DrawIndexedIndirectCommand>() as DeviceSize
+                > indirect_buffer.size()
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`max_draw_count` is not 0, but \
+                    `stride * (max_draw_count - 1) + size_of::<DrawIndexedIndirectCommand>()` is \
+                    greater than `indirect_buffer.size()`"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawIndexedIndirectCount-maxDrawCount-03143"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-vkCmdDrawIndexedIndirectCount-countBuffer-02717
+        // Can't be checked, the value is only read when the command is executed.
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_indexed_indirect_count_unchecked(
+        &mut self,
+        indirect_buffer: &Subbuffer<[DrawIndexedIndirectCommand]>,
+        count_buffer: &Subbuffer<u32>,
+        max_draw_count: u32,
+        stride: u32,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+
+        if self.device().api_version() >= Version::V1_2 {
+            (fns.v1_2.cmd_draw_indexed_indirect_count)(
+                self.handle(),
+                indirect_buffer.buffer().handle(),
+                indirect_buffer.offset(),
+                count_buffer.buffer().handle(),
+                count_buffer.offset(),
+                max_draw_count,
+                stride,
+            );
+        } else {
+            (fns.khr_draw_indirect_count
+                .cmd_draw_indexed_indirect_count_khr)(
+                self.handle(),
+                indirect_buffer.buffer().handle(),
+                indirect_buffer.offset(),
+                count_buffer.buffer().handle(),
+                count_buffer.offset(),
+                max_draw_count,
+                stride,
+            );
+        }
+
+        self
+    }
+
+    pub unsafe fn draw_multi_indexed(
+        &mut self,
+        draw_infos: &[DrawIndexedInfo],
+        vertex_offset: Option<i32>,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_draw_multi_indexed(
+            draw_infos,
+            vertex_offset,
+            instance_count,
+            first_instance,
+        )?;
+
+        Ok(self.draw_multi_indexed_unchecked(
+            draw_infos,
+            vertex_offset,
+            instance_count,
+            first_instance,
+        ))
+    }
+
+    fn validate_draw_multi_indexed(
+        &self,
+        draw_infos: &[DrawIndexedInfo],
+        _vertex_offset: Option<i32>,
+        _instance_count: u32,
+        _first_instance: u32,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_features().multi_draw {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "multi_draw",
+                )])]),
+                vuids: &["VUID-vkCmdDrawMultiIndexedEXT-None-04937"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdDrawMultiIndexedEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = device.physical_device().properties();
+
+        if draw_infos.len() >= properties.max_multi_draw_count.unwrap_or(0) as usize {
+            return Err(Box::new(ValidationError {
+                context: "draw_infos".into(),
+                problem: "the length is not less than the `max_multi_draw_count` limit".into(),
+                vuids: &["VUID-vkCmdDrawMultiIndexedEXT-drawCount-04939"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn draw_multi_indexed_unchecked(
+        &mut self,
+        draw_infos: &[DrawIndexedInfo],
+        vertex_offset: Option<i32>,
+        instance_count: u32,
+        first_instance: u32,
+    ) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.ext_multi_draw.cmd_draw_multi_indexed_ext)(
+            self.handle(),
+            draw_infos.len() as u32,
+            draw_infos.as_ptr() as *const ash::vk::MultiDrawIndexedInfoEXT,
+            instance_count,
+            first_instance,
+            size_of::<DrawIndexedInfo>() as u32,
+            vertex_offset
+                .as_ref()
+                .map_or(ptr::null(), |vertex_offset| vertex_offset),
+        );
+
+        self
+    }
+
+    pub unsafe fn subpass_shading(&mut self) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_subpass_shading()?;
+
+        Ok(self.subpass_shading_unchecked())
+    }
+
+    fn validate_subpass_shading(&self) -> Result<(), Box<ValidationError>> {
+        if self.level() != CommandBufferLevel::Primary {
+            return Err(Box::new(ValidationError {
+                problem: "this command buffer is not a primary command buffer".into(),
+                vuids: &["VUID-vkCmdSubpassShadingHUAWEI-bufferlevel"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdSubpassShadingHUAWEI-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn subpass_shading_unchecked(&mut self) -> &mut Self {
+        let fns = self.device().fns();
+        (fns.huawei_subpass_shading.cmd_subpass_shading_huawei)(self.handle());
+
+        self
+    }
 }
 
 #[derive(Clone, Copy)]
 enum VUIDType {
     Dispatch,
+    DispatchBase,
     DispatchIndirect,
     Draw,
     DrawIndirect,
     DrawIndexed,
     DrawIndexedIndirect,
+    DrawIndirectCount,
+    DrawIndirectByteCount,
+    DrawIndexedIndirectCount,
+    DrawMulti,
+    DrawMultiIndexed,
+    SubpassShading,
 }
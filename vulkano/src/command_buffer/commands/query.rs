@@ -30,6 +30,8 @@ where
     /// Begins a query.
     ///
     /// The query will be active until [`end_query`](Self::end_query) is called for the same query.
+    /// Only one query of each type can be active at a time. If the query is begun within a render
+    /// subpass, then it must also be ended within that same subpass.
     ///
     /// # Safety
     ///
@@ -152,6 +154,18 @@ where
             }));
         }
 
+        if self.builder_state.render_pass.is_some()
+            && !self.builder_state.queries[&query_pool.query_type().into()].in_subpass
+        {
+            return Err(Box::new(ValidationError {
+                problem: "a render subpass is active, but the query was not begun within \
+                    this subpass"
+                    .into(),
+                vuids: &["VUID-vkCmdEndQuery-None-07008"],
+                ..Default::default()
+            }));
+        }
+
         if let Some(render_pass_state) = &self.builder_state.render_pass {
             if query + render_pass_state.rendering_info.view_mask.count_ones()
                 > query_pool.query_count()
@@ -461,9 +475,9 @@ where
         // VUID-vkCmdBeginQuery-commonparent
         assert_eq!(device, query_pool.device());
 
-        if query > query_pool.query_count() {
+        if query >= query_pool.query_count() {
             return Err(Box::new(ValidationError {
-                problem: "`query` is greater than `query_pool.query_count()`".into(),
+                problem: "`query` is not less than `query_pool.query_count()`".into(),
                 vuids: &["VUID-vkCmdBeginQuery-query-00802"],
                 ..Default::default()
             }));
@@ -520,6 +534,32 @@ where
                     }));
                 }
             }
+            QueryType::PrimitivesGenerated => {
+                if !device.enabled_features().primitives_generated_query {
+                    return Err(Box::new(ValidationError {
+                        context: "query_pool.query_type()".into(),
+                        problem: "is `QueryType::PrimitivesGenerated`".into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "primitives_generated_query",
+                        )])]),
+                        vuids: &["VUID-vkCmdBeginQuery-queryType-06688"],
+                    }));
+                }
+
+                if !queue_family_properties
+                    .queue_flags
+                    .intersects(QueueFlags::GRAPHICS)
+                {
+                    return Err(Box::new(ValidationError {
+                        problem: "`query_pool.query_type()` is `QueryType::PrimitivesGenerated`, \
+                            but the queue family of the command buffer does not support \
+                            graphics operations"
+                            .into(),
+                        vuids: &["VUID-vkCmdBeginQuery-queryType-06687"],
+                        ..Default::default()
+                    }));
+                }
+            }
             QueryType::Timestamp
             | QueryType::AccelerationStructureCompactedSize
             | QueryType::AccelerationStructureSerializationSize
@@ -614,9 +654,9 @@ where
         // VUID-vkCmdEndQuery-commonparent
         assert_eq!(device, query_pool.device());
 
-        if query > query_pool.query_count() {
+        if query >= query_pool.query_count() {
             return Err(Box::new(ValidationError {
-                problem: "`query` is greater than `query_pool.query_count()`".into(),
+                problem: "`query` is not less than `query_pool.query_count()`".into(),
                 vuids: &["VUID-vkCmdEndQuery-query-00810"],
                 ..Default::default()
             }));
@@ -849,9 +889,9 @@ where
             }));
         }
 
-        if query > query_pool.query_count() {
+        if query >= query_pool.query_count() {
             return Err(Box::new(ValidationError {
-                problem: "`query` is greater than `query_pool.query_count()`".into(),
+                problem: "`query` is not less than `query_pool.query_count()`".into(),
                 vuids: &["VUID-vkCmdWriteTimestamp2-query-04903"],
                 ..Default::default()
             }));
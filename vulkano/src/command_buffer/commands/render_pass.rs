@@ -23,8 +23,9 @@ use crate::{
     pipeline::graphics::subpass::PipelineRenderingCreateInfo,
     render_pass::{
         AttachmentDescription, AttachmentLoadOp, AttachmentStoreOp, Framebuffer, RenderPass,
-        ResolveMode, SubpassDescription,
+        RenderPassCreateFlags, ResolveMode, SubpassDescription,
     },
+    swapchain::SurfaceTransform,
     sync::PipelineStageAccessFlags,
     Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version, VulkanObject,
 };
@@ -98,6 +99,7 @@ where
             render_area_offset,
             render_area_extent,
             clear_values: _,
+            transform: _,
             _ne: _,
         } = &render_pass_begin_info;
 
@@ -218,6 +220,29 @@ where
             }));
         }
 
+        if self
+            .builder_state
+            .conditional_rendering
+            .as_ref()
+            .map_or(false, |state| state.in_subpass)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering that was begun in the current subpass is \
+                    still active"
+                    .into(),
+                vuids: &["VUID-vkCmdEndConditionalRenderingEXT-None-01987"],
+                ..Default::default()
+            }));
+        }
+
+        if self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is active".into(),
+                vuids: &["VUID-vkCmdNextSubpass2-None-02350"],
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 
@@ -319,6 +344,29 @@ where
             }));
         }
 
+        if self
+            .builder_state
+            .conditional_rendering
+            .as_ref()
+            .map_or(false, |state| state.in_subpass)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering that was begun in the current subpass is \
+                    still active"
+                    .into(),
+                vuids: &["VUID-vkCmdEndConditionalRenderingEXT-None-01987"],
+                ..Default::default()
+            }));
+        }
+
+        if self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is active".into(),
+                vuids: &["VUID-vkCmdEndRenderPass2-None-02352"],
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 
@@ -630,6 +678,29 @@ where
             }));
         }
 
+        if self
+            .builder_state
+            .conditional_rendering
+            .as_ref()
+            .map_or(false, |state| state.in_subpass)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "conditional rendering that was begun in the current render pass \
+                    instance is still active"
+                    .into(),
+                vuids: &["VUID-vkCmdEndConditionalRenderingEXT-None-01987"],
+                ..Default::default()
+            }));
+        }
+
+        if self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is active".into(),
+                vuids: &["VUID-vkCmdEndRendering-None-06781"],
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 
@@ -955,6 +1026,7 @@ where
             render_area_offset: _,
             render_area_extent: _,
             clear_values: _,
+            transform: _,
             _ne: _,
         } = render_pass_begin_info;
 
@@ -1087,6 +1159,25 @@ where
                             }));
                         }
                     }
+                    ImageLayout::AttachmentFeedbackLoopOptimal => {
+                        if !image_view
+                            .usage()
+                            .intersects(ImageUsage::ATTACHMENT_FEEDBACK_LOOP)
+                        {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "`framebuffer.attachments()[{0}]` is used in `render_pass` \
+                                    with the `ImageLayout::AttachmentFeedbackLoopOptimal` layout, \
+                                    but `framebuffer.attachments()[{0}].usage()` does not \
+                                    contain `ImageUsage::ATTACHMENT_FEEDBACK_LOOP`",
+                                    attachment_index,
+                                )
+                                .into(),
+                                vuids: &["VUID-vkCmdBeginRenderPass2-initialLayout-07002"],
+                                ..Default::default()
+                            }));
+                        }
+                    }
                     ImageLayout::Undefined
                     | ImageLayout::General
                     | ImageLayout::Preinitialized
@@ -1098,6 +1189,7 @@ where
         for subpass_desc in render_pass.subpasses() {
             let SubpassDescription {
                 flags: _,
+                pipeline_bind_point: _,
                 view_mask: _,
                 input_attachments,
                 color_attachments,
@@ -1223,6 +1315,25 @@ where
                             }));
                         }
                     }
+                    ImageLayout::AttachmentFeedbackLoopOptimal => {
+                        if !image_view
+                            .usage()
+                            .intersects(ImageUsage::ATTACHMENT_FEEDBACK_LOOP)
+                        {
+                            return Err(Box::new(ValidationError {
+                                problem: format!(
+                                    "`framebuffer.attachments()[{0}]` is used in `render_pass` \
+                                    with the `ImageLayout::AttachmentFeedbackLoopOptimal` layout, \
+                                    but `framebuffer.attachments()[{0}].usage()` does not \
+                                    contain `ImageUsage::ATTACHMENT_FEEDBACK_LOOP`",
+                                    atch_ref.attachment,
+                                )
+                                .into(),
+                                vuids: &["VUID-vkCmdBeginRenderPass2-initialLayout-07003"],
+                                ..Default::default()
+                            }));
+                        }
+                    }
                     ImageLayout::Undefined
                     | ImageLayout::General
                     | ImageLayout::Preinitialized
@@ -1259,6 +1370,7 @@ where
             render_area_offset,
             render_area_extent,
             ref clear_values,
+            transform,
             _ne: _,
         } = render_pass_begin_info;
 
@@ -1268,7 +1380,7 @@ where
             .map(|clear_value| clear_value.map(Into::into).unwrap_or_default())
             .collect();
 
-        let render_pass_begin_info = ash::vk::RenderPassBeginInfo {
+        let mut render_pass_begin_info = ash::vk::RenderPassBeginInfo {
             render_pass: render_pass.handle(),
             framebuffer: framebuffer.handle(),
             render_area: ash::vk::Rect2D {
@@ -1285,6 +1397,22 @@ where
             p_clear_values: clear_values_vk.as_ptr(),
             ..Default::default()
         };
+        let mut render_pass_transform_begin_info_vk = None;
+
+        if render_pass
+            .flags()
+            .intersects(RenderPassCreateFlags::TRANSFORM)
+        {
+            let next = render_pass_transform_begin_info_vk.insert(
+                ash::vk::RenderPassTransformBeginInfoQCOM {
+                    transform: transform.into(),
+                    ..Default::default()
+                },
+            );
+
+            next.p_next = render_pass_begin_info.p_next as *mut _;
+            render_pass_begin_info.p_next = next as *const _ as *const _;
+        }
 
         let &SubpassBeginInfo { contents, _ne: _ } = subpass_begin_info;
 
@@ -1854,6 +1982,22 @@ pub struct RenderPassBeginInfo {
     /// The default value is empty, which must be overridden if the framebuffer has attachments.
     pub clear_values: Vec<Option<ClearValue>>,
 
+    /// The transform that the implementation applies while rendering.
+    ///
+    /// If this is not [`SurfaceTransform::Identity`], then `render_pass` must have been created
+    /// with the [`RenderPassCreateFlags::TRANSFORM`] flag, and the value must be one of
+    /// [`SurfaceTransform::Identity`], [`SurfaceTransform::Rotate90`],
+    /// [`SurfaceTransform::Rotate180`] or [`SurfaceTransform::Rotate270`].
+    ///
+    /// If `render_pass` was created with the [`RenderPassCreateFlags::TRANSFORM`] flag, then
+    /// `render_area_offset` must be `[0, 0]`, and `render_area_extent`, transformed by this value,
+    /// must equal the extent of `framebuffer`. For [`SurfaceTransform::Rotate90`] and
+    /// [`SurfaceTransform::Rotate270`], this means that the width and height of
+    /// `render_area_extent` must be swapped compared to the framebuffer.
+    ///
+    /// The default value is [`SurfaceTransform::Identity`].
+    pub transform: SurfaceTransform,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1868,6 +2012,7 @@ impl RenderPassBeginInfo {
             render_area_offset: [0, 0],
             render_area_extent,
             clear_values: Vec::new(),
+            transform: SurfaceTransform::Identity,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1879,6 +2024,7 @@ impl RenderPassBeginInfo {
             render_area_offset,
             render_area_extent,
             ref clear_values,
+            transform,
             _ne,
         } = self;
 
@@ -1912,24 +2058,94 @@ impl RenderPassBeginInfo {
             }));
         }
 
-        if render_area_offset[0] + render_area_extent[0] > framebuffer.extent()[0] {
-            return Err(Box::new(ValidationError {
-                problem: "`render_area_offset[0] + render_area_extent[0]` is greater than \
-                    `framebuffer.extent()[0]`"
-                    .into(),
-                vuids: &["VUID-VkRenderPassBeginInfo-pNext-02852"],
-                ..Default::default()
-            }));
+        if transform != SurfaceTransform::Identity {
+            transform.validate_device(device).map_err(|err| {
+                err.add_context("transform")
+                    .set_vuids(&["VUID-VkRenderPassTransformBeginInfoQCOM-transform-parameter"])
+            })?;
+
+            if !render_pass
+                .flags()
+                .intersects(RenderPassCreateFlags::TRANSFORM)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: "`transform` is not `SurfaceTransform::Identity`, but \
+                        `render_pass.flags()` does not contain `RenderPassCreateFlags::TRANSFORM`"
+                        .into(),
+                    vuids: &["VUID-VkRenderPassTransformBeginInfoQCOM-flags-02872"],
+                    ..Default::default()
+                }));
+            }
+
+            if !matches!(
+                transform,
+                SurfaceTransform::Rotate90
+                    | SurfaceTransform::Rotate180
+                    | SurfaceTransform::Rotate270
+            ) {
+                return Err(Box::new(ValidationError {
+                    context: "transform".into(),
+                    problem: "is not `SurfaceTransform::Identity`, \
+                        `SurfaceTransform::Rotate90`, `SurfaceTransform::Rotate180` or \
+                        `SurfaceTransform::Rotate270`"
+                        .into(),
+                    vuids: &["VUID-VkRenderPassTransformBeginInfoQCOM-transform-02871"],
+                    ..Default::default()
+                }));
+            }
         }
 
-        if render_area_offset[1] + render_area_extent[1] > framebuffer.extent()[1] {
-            return Err(Box::new(ValidationError {
-                problem: "`render_area_offset[1] + render_area_extent[1]` is greater than \
-                    `framebuffer.extent()[1]`"
-                    .into(),
-                vuids: &["VUID-VkRenderPassBeginInfo-pNext-02853"],
-                ..Default::default()
-            }));
+        if render_pass
+            .flags()
+            .intersects(RenderPassCreateFlags::TRANSFORM)
+        {
+            if render_area_offset != [0, 0] {
+                return Err(Box::new(ValidationError {
+                    problem: "`render_pass.flags()` contains `RenderPassCreateFlags::TRANSFORM`, \
+                        but `render_area_offset` is not `[0, 0]`"
+                        .into(),
+                    vuids: &["VUID-VkRenderPassBeginInfo-pNext-02869"],
+                    ..Default::default()
+                }));
+            }
+
+            let transformed_extent = match transform {
+                SurfaceTransform::Rotate90 | SurfaceTransform::Rotate270 => {
+                    [render_area_extent[1], render_area_extent[0]]
+                }
+                _ => render_area_extent,
+            };
+
+            if transformed_extent != framebuffer.extent() {
+                return Err(Box::new(ValidationError {
+                    problem: "`render_pass.flags()` contains `RenderPassCreateFlags::TRANSFORM`, \
+                        but `render_area_extent`, transformed by `transform`, is not equal to \
+                        `framebuffer.extent()`"
+                        .into(),
+                    vuids: &["VUID-VkRenderPassBeginInfo-pNext-02870"],
+                    ..Default::default()
+                }));
+            }
+        } else {
+            if render_area_offset[0] + render_area_extent[0] > framebuffer.extent()[0] {
+                return Err(Box::new(ValidationError {
+                    problem: "`render_area_offset[0] + render_area_extent[0]` is greater than \
+                        `framebuffer.extent()[0]`"
+                        .into(),
+                    vuids: &["VUID-VkRenderPassBeginInfo-pNext-02852"],
+                    ..Default::default()
+                }));
+            }
+
+            if render_area_offset[1] + render_area_extent[1] > framebuffer.extent()[1] {
+                return Err(Box::new(ValidationError {
+                    problem: "`render_area_offset[1] + render_area_extent[1]` is greater than \
+                        `framebuffer.extent()[1]`"
+                        .into(),
+                    vuids: &["VUID-VkRenderPassBeginInfo-pNext-02853"],
+                    ..Default::default()
+                }));
+            }
         }
 
         if clear_values.len() != render_pass.attachments().len() {
@@ -36,6 +36,11 @@ where
     /// If the `flags` that `command_buffer` was created with are more restrictive than those of
     /// `self`, then `self` will be restricted to match. E.g. executing a secondary command buffer
     /// with `Flags::OneTimeSubmit` will set `self`'s flags to `Flags::OneTimeSubmit` also.
+    ///
+    /// If a render pass instance is active, `command_buffer` must have been recorded with
+    /// inheritance info that is compatible with it: the render pass must be compatible, the
+    /// subpass index must match the current subpass, and if a framebuffer was specified, it must
+    /// be the framebuffer of the current render pass instance.
     pub fn execute_commands(
         &mut self,
         command_buffer: Arc<dyn SecondaryCommandBufferAbstract>,
@@ -48,13 +53,29 @@ where
 
     /// Executes multiple secondary command buffers in a vector.
     ///
+    /// This is the same as [`execute_commands_many`](Self::execute_commands_many).
+    #[inline]
+    pub fn execute_commands_from_vec(
+        &mut self,
+        command_buffers: Vec<Arc<dyn SecondaryCommandBufferAbstract>>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.execute_commands_many(command_buffers)
+    }
+
+    /// Executes multiple secondary command buffers, in the order they are provided, with a single
+    /// command.
+    ///
+    /// All command buffers are validated together before any of them is recorded, with the same
+    /// requirements as [`execute_commands`](Self::execute_commands). This is useful to execute
+    /// secondary command buffers that were recorded in parallel.
+    ///
     /// This requires that the secondary command buffers do not have resource conflicts; an error
     /// will be returned if there are any. Use `execute_commands` if you want to ensure that
     /// resource conflicts are automatically resolved.
     // TODO ^ would be nice if this just worked without errors
-    pub fn execute_commands_from_vec(
+    pub fn execute_commands_many(
         &mut self,
-        command_buffers: Vec<Arc<dyn SecondaryCommandBufferAbstract>>,
+        command_buffers: impl IntoIterator<Item = Arc<dyn SecondaryCommandBufferAbstract>>,
     ) -> Result<&mut Self, Box<ValidationError>> {
         let command_buffers: SmallVec<[_; 4]> = command_buffers
             .into_iter()
@@ -77,7 +98,7 @@ where
             if render_pass_state.contents != SubpassContents::SecondaryCommandBuffers {
                 return Err(Box::new(ValidationError {
                     problem: "a render pass instance is active, but its current subpass contents \
-                        is `SubpassContents::SecondaryCommandBuffers`"
+                        is not `SubpassContents::SecondaryCommandBuffers`"
                         .into(),
                     vuids: &[
                         "VUID-vkCmdExecuteCommands-contents-06018",
@@ -86,6 +107,11 @@ where
                     ..Default::default()
                 }));
             }
+
+            // TODO: If the render pass instance was begun with a transform, the secondary command
+            // buffers must have been recorded with the same transform, using
+            // `VkCommandBufferInheritanceRenderPassTransformInfoQCOM`. This can't be specified
+            // in `CommandBufferInheritanceRenderPassInfo` yet.
         }
 
         if !self.builder_state.queries.is_empty()
@@ -202,6 +228,30 @@ where
                             }));
                         }
 
+                        for (color_attachment_index, inherited_format) in attachments
+                            .color_attachments
+                            .iter()
+                            .zip(inheritance_info.color_attachment_formats.iter())
+                            .enumerate()
+                            .filter_map(|(i, (a, f))| a.is_none().then_some((i, f)))
+                        {
+                            if inherited_format.is_some() {
+                                return Err(Box::new(ValidationError {
+                                    context: format!(
+                                        "command_buffers[{}].inheritance_info().render_pass\
+                                        .color_attachment_formats[{}]",
+                                        command_buffer_index, color_attachment_index
+                                    )
+                                    .into(),
+                                    problem: "is `Some`, but the corresponding color attachment \
+                                        in the current subpass instance is `None`"
+                                        .into(),
+                                    vuids: &["VUID-vkCmdExecuteCommands-imageView-07606"],
+                                    ..Default::default()
+                                }));
+                            }
+                        }
+
                         for (color_attachment_index, image_view, inherited_format) in (attachments
                             .color_attachments
                             .iter())
@@ -285,6 +335,20 @@ where
                                     ..Default::default()
                                 }));
                             }
+                        } else if inheritance_info.depth_attachment_format.is_some() {
+                            return Err(Box::new(ValidationError {
+                                context: format!(
+                                    "command_buffers[{}].inheritance_info().render_pass\
+                                    .depth_attachment_format",
+                                    command_buffer_index
+                                )
+                                .into(),
+                                problem: "is `Some`, but the current subpass instance does not \
+                                    have a depth attachment"
+                                    .into(),
+                                vuids: &["VUID-vkCmdExecuteCommands-pDepthAttachment-06774"],
+                                ..Default::default()
+                            }));
                         }
 
                         if let Some((image_view, format)) = attachments
@@ -325,6 +389,20 @@ where
                                     ..Default::default()
                                 }));
                             }
+                        } else if inheritance_info.stencil_attachment_format.is_some() {
+                            return Err(Box::new(ValidationError {
+                                context: format!(
+                                    "command_buffers[{}].inheritance_info().render_pass\
+                                    .stencil_attachment_format",
+                                    command_buffer_index
+                                )
+                                .into(),
+                                problem: "is `Some`, but the current subpass instance does not \
+                                    have a stencil attachment"
+                                    .into(),
+                                vuids: &["VUID-vkCmdExecuteCommands-pStencilAttachment-06775"],
+                                ..Default::default()
+                            }));
                         }
 
                         if inheritance_info.view_mask != render_pass_state.rendering_info.view_mask
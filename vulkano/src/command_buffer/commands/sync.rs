@@ -11,8 +11,8 @@ use crate::{
     command_buffer::{allocator::CommandBufferAllocator, sys::UnsafeCommandBufferBuilder},
     device::{DeviceOwned, QueueFlags},
     sync::{
-        event::Event, BufferMemoryBarrier, DependencyFlags, DependencyInfo, ImageMemoryBarrier,
-        MemoryBarrier, PipelineStages,
+        event::Event, BarrierBatch, BufferMemoryBarrier, DependencyFlags, DependencyInfo,
+        ImageMemoryBarrier, MemoryBarrier, PipelineStages,
     },
     Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version, VulkanObject,
 };
@@ -32,6 +32,29 @@ where
         Ok(self.pipeline_barrier_unchecked(dependency_info))
     }
 
+    /// Records the barriers of `batch`, with one pipeline barrier command for each of its
+    /// [`dependency_infos`](BarrierBatch::dependency_infos).
+    pub unsafe fn pipeline_barrier_batch(
+        &mut self,
+        batch: &BarrierBatch,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        for (index, dependency_info) in batch.dependency_infos().iter().enumerate() {
+            self.validate_pipeline_barrier(dependency_info)
+                .map_err(|err| err.add_context(format!("batch.dependency_infos()[{}]", index)))?;
+        }
+
+        Ok(self.pipeline_barrier_batch_unchecked(batch))
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn pipeline_barrier_batch_unchecked(&mut self, batch: &BarrierBatch) -> &mut Self {
+        for dependency_info in batch.dependency_infos() {
+            self.pipeline_barrier_unchecked(dependency_info);
+        }
+
+        self
+    }
+
     fn validate_pipeline_barrier(
         &self,
         dependency_info: &DependencyInfo,
@@ -0,0 +1,686 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{
+    buffer::{BufferUsage, Subbuffer},
+    command_buffer::{
+        allocator::CommandBufferAllocator, auto::Resource, sys::UnsafeCommandBufferBuilder,
+        AutoCommandBufferBuilder, ResourceInCommand,
+    },
+    device::{DeviceOwned, QueueFlags},
+    sync::PipelineStageAccessFlags,
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanObject,
+};
+use smallvec::SmallVec;
+
+/// # Commands for transform feedback.
+///
+/// These commands require the [`transform_feedback`] feature to be enabled on the device.
+///
+/// [`transform_feedback`]: crate::device::Features::transform_feedback
+impl<L, A> AutoCommandBufferBuilder<L, A>
+where
+    A: CommandBufferAllocator,
+{
+    /// Binds transform feedback buffers for future draw calls.
+    ///
+    /// While transform feedback is active, the outputs of the last vertex processing stage that
+    /// are decorated with `XfbBuffer` are written to the buffer bound to that binding.
+    pub fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        transform_feedback_buffers: SmallVec<[Subbuffer<[u8]>; 4]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_transform_feedback_buffers(first_binding, &transform_feedback_buffers)?;
+
+        unsafe {
+            Ok(self.bind_transform_feedback_buffers_unchecked(
+                first_binding,
+                transform_feedback_buffers,
+            ))
+        }
+    }
+
+    fn validate_bind_transform_feedback_buffers(
+        &self,
+        first_binding: u32,
+        transform_feedback_buffers: &[Subbuffer<[u8]>],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_bind_transform_feedback_buffers(first_binding, transform_feedback_buffers)?;
+
+        if self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is active".into(),
+                vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-None-02365"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_transform_feedback_buffers_unchecked(
+        &mut self,
+        first_binding: u32,
+        transform_feedback_buffers: SmallVec<[Subbuffer<[u8]>; 4]>,
+    ) -> &mut Self {
+        for (i, buffer) in transform_feedback_buffers.iter().enumerate() {
+            self.builder_state
+                .transform_feedback_buffers
+                .insert(first_binding + i as u32, buffer.clone());
+        }
+
+        self.add_command(
+            "bind_transform_feedback_buffers",
+            Default::default(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.bind_transform_feedback_buffers_unchecked(
+                    first_binding,
+                    &transform_feedback_buffers,
+                );
+            },
+        );
+
+        self
+    }
+
+    /// Begins transform feedback.
+    ///
+    /// Transform feedback will be active until
+    /// [`end_transform_feedback`](Self::end_transform_feedback) is called, which must happen
+    /// within the same render subpass.
+    ///
+    /// `counter_buffers` are bound to the counter buffer indices starting at
+    /// `first_counter_buffer`. If a counter buffer is provided, then capturing for the
+    /// corresponding transform feedback buffer resumes at the byte offset that was stored in it
+    /// by a previous call to `end_transform_feedback`. Otherwise, capturing starts at the
+    /// beginning of the transform feedback buffer.
+    pub fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: SmallVec<[Option<Subbuffer<u32>>; 4]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_begin_transform_feedback(first_counter_buffer, &counter_buffers)?;
+
+        unsafe {
+            Ok(self.begin_transform_feedback_unchecked(first_counter_buffer, counter_buffers))
+        }
+    }
+
+    fn validate_begin_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_begin_transform_feedback(first_counter_buffer, counter_buffers)?;
+
+        let render_pass_state = self.builder_state.render_pass.as_ref().ok_or_else(|| {
+            Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-renderpass"],
+                ..Default::default()
+            })
+        })?;
+
+        if self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is already active".into(),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-None-02367"],
+                ..Default::default()
+            }));
+        }
+
+        if render_pass_state.rendering_info.view_mask != 0 {
+            return Err(Box::new(ValidationError {
+                problem: "the current render subpass has a non-zero `view_mask`".into(),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-None-02373"],
+                ..Default::default()
+            }));
+        }
+
+        // TODO:
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-04128
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-06233
+        // VUID-vkCmdBeginTransformFeedbackEXT-None-06234
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn begin_transform_feedback_unchecked(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: SmallVec<[Option<Subbuffer<u32>>; 4]>,
+    ) -> &mut Self {
+        self.builder_state.transform_feedback_active = true;
+
+        self.add_command(
+            "begin_transform_feedback",
+            counter_buffers
+                .iter()
+                .enumerate()
+                .filter_map(|(index, counter_buffer)| {
+                    counter_buffer.as_ref().map(|counter_buffer| {
+                        (
+                            ResourceInCommand::TransformFeedbackCounterBuffer {
+                                index: first_counter_buffer + index as u32,
+                            }
+                            .into(),
+                            Resource::Buffer {
+                                buffer: counter_buffer.as_bytes().clone(),
+                                range: 0..counter_buffer.size(),
+                                memory_access: PipelineStageAccessFlags::TransformFeedback_TransformFeedbackCounterRead,
+                            },
+                        )
+                    })
+                })
+                .collect(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.begin_transform_feedback_unchecked(first_counter_buffer, &counter_buffers);
+            },
+        );
+
+        self
+    }
+
+    /// Ends transform feedback.
+    ///
+    /// If counter buffers are provided, then for each transform feedback buffer, the byte offset
+    /// just after the last data that was captured is written to the corresponding counter buffer.
+    /// This value can be used to resume capturing with
+    /// [`begin_transform_feedback`](Self::begin_transform_feedback), or to draw the captured
+    /// vertices with [`draw_indirect_byte_count`](Self::draw_indirect_byte_count).
+    pub fn end_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: SmallVec<[Option<Subbuffer<u32>>; 4]>,
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_end_transform_feedback(first_counter_buffer, &counter_buffers)?;
+
+        unsafe { Ok(self.end_transform_feedback_unchecked(first_counter_buffer, counter_buffers)) }
+    }
+
+    fn validate_end_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<(), Box<ValidationError>> {
+        self.inner
+            .validate_end_transform_feedback(first_counter_buffer, counter_buffers)?;
+
+        if self.builder_state.render_pass.is_none() {
+            return Err(Box::new(ValidationError {
+                problem: "a render pass instance is not active".into(),
+                vuids: &["VUID-vkCmdEndTransformFeedbackEXT-renderpass"],
+                ..Default::default()
+            }));
+        }
+
+        if !self.builder_state.transform_feedback_active {
+            return Err(Box::new(ValidationError {
+                problem: "transform feedback is not active".into(),
+                vuids: &["VUID-vkCmdEndTransformFeedbackEXT-None-02375"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn end_transform_feedback_unchecked(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: SmallVec<[Option<Subbuffer<u32>>; 4]>,
+    ) -> &mut Self {
+        self.builder_state.transform_feedback_active = false;
+
+        self.add_command(
+            "end_transform_feedback",
+            counter_buffers
+                .iter()
+                .enumerate()
+                .filter_map(|(index, counter_buffer)| {
+                    counter_buffer.as_ref().map(|counter_buffer| {
+                        (
+                            ResourceInCommand::TransformFeedbackCounterBuffer {
+                                index: first_counter_buffer + index as u32,
+                            }
+                            .into(),
+                            Resource::Buffer {
+                                buffer: counter_buffer.as_bytes().clone(),
+                                range: 0..counter_buffer.size(),
+                                memory_access: PipelineStageAccessFlags::TransformFeedback_TransformFeedbackCounterWrite,
+                            },
+                        )
+                    })
+                })
+                .collect(),
+            move |out: &mut UnsafeCommandBufferBuilder<A>| {
+                out.end_transform_feedback_unchecked(first_counter_buffer, &counter_buffers);
+            },
+        );
+
+        self
+    }
+}
+
+impl<A> UnsafeCommandBufferBuilder<A>
+where
+    A: CommandBufferAllocator,
+{
+    pub unsafe fn bind_transform_feedback_buffers(
+        &mut self,
+        first_binding: u32,
+        transform_feedback_buffers: &[Subbuffer<[u8]>],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_bind_transform_feedback_buffers(first_binding, transform_feedback_buffers)?;
+
+        Ok(self
+            .bind_transform_feedback_buffers_unchecked(first_binding, transform_feedback_buffers))
+    }
+
+    fn validate_bind_transform_feedback_buffers(
+        &self,
+        first_binding: u32,
+        transform_feedback_buffers: &[Subbuffer<[u8]>],
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_features().transform_feedback {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "transform_feedback",
+                )])]),
+                vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-transformFeedback-02355"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let properties = device.physical_device().properties();
+        let max_transform_feedback_buffers = properties.max_transform_feedback_buffers.unwrap_or(0);
+
+        if first_binding >= max_transform_feedback_buffers {
+            return Err(Box::new(ValidationError {
+                context: "first_binding".into(),
+                problem: "is not less than the `max_transform_feedback_buffers` limit".into(),
+                vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-firstBinding-02356"],
+                ..Default::default()
+            }));
+        }
+
+        if first_binding + transform_feedback_buffers.len() as u32 > max_transform_feedback_buffers
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`first_binding + transform_feedback_buffers.len()` is greater than the \
+                    `max_transform_feedback_buffers` limit"
+                    .into(),
+                vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-firstBinding-02357"],
+                ..Default::default()
+            }));
+        }
+
+        for (index, buffer) in transform_feedback_buffers.iter().enumerate() {
+            // VUID-vkCmdBindTransformFeedbackBuffersEXT-commonparent
+            assert_eq!(device, buffer.device());
+
+            if !buffer
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_BUFFER)
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("transform_feedback_buffers[{}].usage()", index).into(),
+                    problem: "does not contain `BufferUsage::TRANSFORM_FEEDBACK_BUFFER`".into(),
+                    vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-pBuffers-02360"],
+                    ..Default::default()
+                }));
+            }
+
+            if buffer.offset() % 4 != 0 {
+                return Err(Box::new(ValidationError {
+                    context: format!("transform_feedback_buffers[{}].offset()", index).into(),
+                    problem: "is not a multiple of 4".into(),
+                    vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-pOffsets-02359"],
+                    ..Default::default()
+                }));
+            }
+
+            if buffer.size()
+                > properties
+                    .max_transform_feedback_buffer_size
+                    .unwrap_or(DeviceSize::MAX)
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("transform_feedback_buffers[{}].size()", index).into(),
+                    problem: "is greater than the `max_transform_feedback_buffer_size` limit"
+                        .into(),
+                    vuids: &["VUID-vkCmdBindTransformFeedbackBuffersEXT-pSize-02361"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn bind_transform_feedback_buffers_unchecked(
+        &mut self,
+        first_binding: u32,
+        transform_feedback_buffers: &[Subbuffer<[u8]>],
+    ) -> &mut Self {
+        if transform_feedback_buffers.is_empty() {
+            return self;
+        }
+
+        let mut buffers_vk: SmallVec<[_; 4]> =
+            SmallVec::with_capacity(transform_feedback_buffers.len());
+        let mut offsets_vk: SmallVec<[_; 4]> =
+            SmallVec::with_capacity(transform_feedback_buffers.len());
+        let mut sizes_vk: SmallVec<[_; 4]> =
+            SmallVec::with_capacity(transform_feedback_buffers.len());
+
+        for buffer in transform_feedback_buffers {
+            buffers_vk.push(buffer.buffer().handle());
+            offsets_vk.push(buffer.offset());
+            sizes_vk.push(buffer.size());
+        }
+
+        let fns = self.device().fns();
+        (fns.ext_transform_feedback
+            .cmd_bind_transform_feedback_buffers_ext)(
+            self.handle(),
+            first_binding,
+            buffers_vk.len() as u32,
+            buffers_vk.as_ptr(),
+            offsets_vk.as_ptr(),
+            sizes_vk.as_ptr(),
+        );
+
+        self
+    }
+
+    pub unsafe fn begin_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_begin_transform_feedback(first_counter_buffer, counter_buffers)?;
+
+        Ok(self.begin_transform_feedback_unchecked(first_counter_buffer, counter_buffers))
+    }
+
+    fn validate_begin_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_features().transform_feedback {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "transform_feedback",
+                )])]),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-transformFeedback-02366"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let max_transform_feedback_buffers = device
+            .physical_device()
+            .properties()
+            .max_transform_feedback_buffers
+            .unwrap_or(0);
+
+        if first_counter_buffer >= max_transform_feedback_buffers {
+            return Err(Box::new(ValidationError {
+                context: "first_counter_buffer".into(),
+                problem: "is not less than the `max_transform_feedback_buffers` limit".into(),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-firstCounterBuffer-02368"],
+                ..Default::default()
+            }));
+        }
+
+        if first_counter_buffer + counter_buffers.len() as u32 > max_transform_feedback_buffers {
+            return Err(Box::new(ValidationError {
+                problem: "`first_counter_buffer + counter_buffers.len()` is greater than the \
+                    `max_transform_feedback_buffers` limit"
+                    .into(),
+                vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-firstCounterBuffer-02369"],
+                ..Default::default()
+            }));
+        }
+
+        for (index, counter_buffer) in counter_buffers.iter().enumerate() {
+            let counter_buffer = match counter_buffer {
+                Some(counter_buffer) => counter_buffer,
+                None => continue,
+            };
+
+            // VUID-vkCmdBeginTransformFeedbackEXT-commonparent
+            assert_eq!(device, counter_buffer.device());
+
+            if !counter_buffer
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER)
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("counter_buffers[{}].usage()", index).into(),
+                    problem: "does not contain `BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER`"
+                        .into(),
+                    vuids: &["VUID-vkCmdBeginTransformFeedbackEXT-pCounterBuffers-02372"],
+                    ..Default::default()
+                }));
+            }
+
+            if counter_buffer.offset() % 4 != 0 {
+                return Err(Box::new(ValidationError {
+                    context: format!("counter_buffers[{}].offset()", index).into(),
+                    problem: "is not a multiple of 4".into(),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn begin_transform_feedback_unchecked(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> &mut Self {
+        let (counter_buffers_vk, counter_buffer_offsets_vk): (SmallVec<[_; 4]>, SmallVec<[_; 4]>) =
+            counter_buffers
+                .iter()
+                .map(|counter_buffer| match counter_buffer {
+                    Some(counter_buffer) => {
+                        (counter_buffer.buffer().handle(), counter_buffer.offset())
+                    }
+                    None => (ash::vk::Buffer::null(), 0),
+                })
+                .unzip();
+
+        let fns = self.device().fns();
+        (fns.ext_transform_feedback.cmd_begin_transform_feedback_ext)(
+            self.handle(),
+            first_counter_buffer,
+            counter_buffers_vk.len() as u32,
+            counter_buffers_vk.as_ptr(),
+            counter_buffer_offsets_vk.as_ptr(),
+        );
+
+        self
+    }
+
+    pub unsafe fn end_transform_feedback(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<&mut Self, Box<ValidationError>> {
+        self.validate_end_transform_feedback(first_counter_buffer, counter_buffers)?;
+
+        Ok(self.end_transform_feedback_unchecked(first_counter_buffer, counter_buffers))
+    }
+
+    fn validate_end_transform_feedback(
+        &self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.device();
+
+        if !device.enabled_features().transform_feedback {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "transform_feedback",
+                )])]),
+                vuids: &["VUID-vkCmdEndTransformFeedbackEXT-transformFeedback-02374"],
+                ..Default::default()
+            }));
+        }
+
+        if !self
+            .queue_family_properties()
+            .queue_flags
+            .intersects(QueueFlags::GRAPHICS)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the command buffer does not support \
+                    graphics operations"
+                    .into(),
+                vuids: &["VUID-vkCmdEndTransformFeedbackEXT-commandBuffer-cmdpool"],
+                ..Default::default()
+            }));
+        }
+
+        let max_transform_feedback_buffers = device
+            .physical_device()
+            .properties()
+            .max_transform_feedback_buffers
+            .unwrap_or(0);
+
+        if first_counter_buffer >= max_transform_feedback_buffers {
+            return Err(Box::new(ValidationError {
+                context: "first_counter_buffer".into(),
+                problem: "is not less than the `max_transform_feedback_buffers` limit".into(),
+                vuids: &["VUID-vkCmdEndTransformFeedbackEXT-firstCounterBuffer-02376"],
+                ..Default::default()
+            }));
+        }
+
+        if first_counter_buffer + counter_buffers.len() as u32 > max_transform_feedback_buffers {
+            return Err(Box::new(ValidationError {
+                problem: "`first_counter_buffer + counter_buffers.len()` is greater than the \
+                    `max_transform_feedback_buffers` limit"
+                    .into(),
+                vuids: &["VUID-vkCmdEndTransformFeedbackEXT-firstCounterBuffer-02377"],
+                ..Default::default()
+            }));
+        }
+
+        for (index, counter_buffer) in counter_buffers.iter().enumerate() {
+            let counter_buffer = match counter_buffer {
+                Some(counter_buffer) => counter_buffer,
+                None => continue,
+            };
+
+            // VUID-vkCmdEndTransformFeedbackEXT-commonparent
+            assert_eq!(device, counter_buffer.device());
+
+            if !counter_buffer
+                .buffer()
+                .usage()
+                .intersects(BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER)
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("counter_buffers[{}].usage()", index).into(),
+                    problem: "does not contain `BufferUsage::TRANSFORM_FEEDBACK_COUNTER_BUFFER`"
+                        .into(),
+                    vuids: &["VUID-vkCmdEndTransformFeedbackEXT-pCounterBuffers-02380"],
+                    ..Default::default()
+                }));
+            }
+
+            if counter_buffer.offset() % 4 != 0 {
+                return Err(Box::new(ValidationError {
+                    context: format!("counter_buffers[{}].offset()", index).into(),
+                    problem: "is not a multiple of 4".into(),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn end_transform_feedback_unchecked(
+        &mut self,
+        first_counter_buffer: u32,
+        counter_buffers: &[Option<Subbuffer<u32>>],
+    ) -> &mut Self {
+        let (counter_buffers_vk, counter_buffer_offsets_vk): (SmallVec<[_; 4]>, SmallVec<[_; 4]>) =
+            counter_buffers
+                .iter()
+                .map(|counter_buffer| match counter_buffer {
+                    Some(counter_buffer) => {
+                        (counter_buffer.buffer().handle(), counter_buffer.offset())
+                    }
+                    None => (ash::vk::Buffer::null(), 0),
+                })
+                .unzip();
+
+        let fns = self.device().fns();
+        (fns.ext_transform_feedback.cmd_end_transform_feedback_ext)(
+            self.handle(),
+            first_counter_buffer,
+            counter_buffers_vk.len() as u32,
+            counter_buffers_vk.as_ptr(),
+            counter_buffer_offsets_vk.as_ptr(),
+        );
+
+        self
+    }
+}
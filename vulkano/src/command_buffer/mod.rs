@@ -111,8 +111,8 @@
 pub use self::{
     auto::{AutoCommandBufferBuilder, PrimaryAutoCommandBuffer, SecondaryAutoCommandBuffer},
     commands::{
-        acceleration_structure::*, clear::*, copy::*, debug::*, dynamic_state::*, pipeline::*,
-        query::*, render_pass::*, secondary::*, sync::*,
+        acceleration_structure::*, bind_push::*, clear::*, conditional_rendering::*, copy::*,
+        debug::*, dynamic_state::*, pipeline::*, query::*, render_pass::*, secondary::*, sync::*,
     },
     traits::{
         CommandBufferExecError, CommandBufferExecFuture, PrimaryCommandBufferAbstract,
@@ -161,6 +161,25 @@ pub struct DrawIndexedIndirectCommand {
     pub first_instance: u32,
 }
 
+/// The parameters of a single draw in a
+/// [`draw_multi`](AutoCommandBufferBuilder::draw_multi) command.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct DrawInfo {
+    pub first_vertex: u32,
+    pub vertex_count: u32,
+}
+
+/// The parameters of a single draw in a
+/// [`draw_multi_indexed`](AutoCommandBufferBuilder::draw_multi_indexed) command.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
+pub struct DrawIndexedInfo {
+    pub first_index: u32,
+    pub index_count: u32,
+    pub vertex_offset: i32,
+}
+
 #[repr(C)]
 #[derive(Clone, Copy, Debug, Default, Zeroable, Pod, PartialEq, Eq)]
 pub struct DispatchIndirectCommand {
@@ -438,6 +457,10 @@ impl From<Subpass> for CommandBufferInheritanceRenderPassInfo {
 }
 
 /// The dynamic rendering context that a secondary command buffer is created for.
+///
+/// When the secondary command buffer is executed, the formats, sample count and view mask must
+/// match the attachments and `view_mask` of the [`RenderingInfo`] that the current render pass
+/// instance was begun with. Attachments that are `None` there must also be `None` here.
 #[derive(Clone, Debug)]
 pub struct CommandBufferInheritanceRenderingInfo {
     /// If not `0`, indicates that multiview rendering will be enabled, and specifies the view
@@ -884,6 +907,8 @@ pub enum ResourceInCommand {
     AccelerationStructure { index: u32 },
     ColorAttachment { index: u32 },
     ColorResolveAttachment { index: u32 },
+    ConditionalRenderingBuffer,
+    CountBuffer,
     DepthStencilAttachment,
     DepthStencilResolveAttachment,
     DescriptorSet { set: u32, binding: u32, index: u32 },
@@ -900,6 +925,8 @@ pub enum ResourceInCommand {
     ScratchData,
     SecondaryCommandBuffer { index: u32 },
     Source,
+    TransformFeedbackBuffer { binding: u32 },
+    TransformFeedbackCounterBuffer { index: u32 },
     VertexBuffer { binding: u32 },
 }
 
@@ -14,12 +14,16 @@
 //!
 //! In vulkano, creating a descriptor set requires passing an implementation of the
 //! [`DescriptorSetAllocator`] trait, which you can implement yourself or use the vulkano-provided
-//! [`StandardDescriptorSetAllocator`].
+//! [`StandardDescriptorSetAllocator`]. For descriptor sets that are only used for a single frame,
+//! the [`TransientDescriptorPool`] recycles all of a frame's descriptor sets at once.
 
 use self::sorted_map::SortedMap;
 use super::{
     layout::DescriptorSetLayout,
-    pool::{DescriptorPool, DescriptorPoolCreateInfo, DescriptorSetAllocateInfo},
+    pool::{
+        DescriptorPool, DescriptorPoolCreateFlags, DescriptorPoolCreateInfo,
+        DescriptorSetAllocateInfo,
+    },
     sys::UnsafeDescriptorSet,
 };
 use crate::{
@@ -28,7 +32,9 @@ use crate::{
     instance::InstanceOwnedDebugWrapper,
     Validated, VulkanError,
 };
+use ahash::HashMap;
 use crossbeam_queue::ArrayQueue;
+use parking_lot::Mutex;
 use std::{cell::UnsafeCell, mem::ManuallyDrop, num::NonZeroU64, sync::Arc, thread};
 use thread_local::ThreadLocal;
 
@@ -95,6 +101,7 @@ pub trait DescriptorSetAlloc: Send + Sync {
 pub struct StandardDescriptorSetAllocator {
     device: InstanceOwnedDebugWrapper<Arc<Device>>,
     pools: ThreadLocal<UnsafeCell<SortedMap<NonZeroU64, Entry>>>,
+    create_info: StandardDescriptorSetAllocatorCreateInfo,
 }
 
 #[derive(Debug)]
@@ -111,10 +118,14 @@ unsafe impl Send for Entry {}
 impl StandardDescriptorSetAllocator {
     /// Creates a new `StandardDescriptorSetAllocator`.
     #[inline]
-    pub fn new(device: Arc<Device>) -> StandardDescriptorSetAllocator {
+    pub fn new(
+        device: Arc<Device>,
+        create_info: StandardDescriptorSetAllocatorCreateInfo,
+    ) -> StandardDescriptorSetAllocator {
         StandardDescriptorSetAllocator {
             device: InstanceOwnedDebugWrapper(device),
             pools: ThreadLocal::new(),
+            create_info,
         }
     }
 
@@ -155,36 +166,21 @@ unsafe impl DescriptorSetAllocator for StandardDescriptorSetAllocator {
         layout: &Arc<DescriptorSetLayout>,
         variable_descriptor_count: u32,
     ) -> Result<StandardDescriptorSetAlloc, VulkanError> {
-        assert!(
-            !layout
-                .flags()
-                .intersects(DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR),
-            "the provided descriptor set layout is for push descriptors, and cannot be used to \
-            build a descriptor set object",
-        );
+        assert_allocatable(layout, variable_descriptor_count);
 
         let max_count = layout.variable_descriptor_count();
-
-        assert!(
-            variable_descriptor_count <= max_count,
-            "the provided variable_descriptor_count ({}) is greater than the maximum number of \
-            variable count descriptors in the set ({})",
-            variable_descriptor_count,
-            max_count,
-        );
-
         let pools = self.pools.get_or(Default::default);
         let entry = unsafe { &mut *pools.get() }.get_or_try_insert(layout.id(), || {
             if max_count == 0 {
-                FixedEntry::new(layout.clone()).map(Entry::Fixed)
+                FixedEntry::new(layout.clone(), &self.create_info).map(Entry::Fixed)
             } else {
-                VariableEntry::new(layout.clone()).map(Entry::Variable)
+                VariableEntry::new(layout.clone(), &self.create_info).map(Entry::Variable)
             }
         })?;
 
         match entry {
-            Entry::Fixed(entry) => entry.allocate(),
-            Entry::Variable(entry) => entry.allocate(variable_descriptor_count),
+            Entry::Fixed(entry) => entry.allocate(&self.create_info),
+            Entry::Variable(entry) => entry.allocate(variable_descriptor_count, &self.create_info),
         }
     }
 }
@@ -209,6 +205,47 @@ unsafe impl DeviceOwned for StandardDescriptorSetAllocator {
     }
 }
 
+/// Parameters to create a new [`StandardDescriptorSetAllocator`].
+#[derive(Clone, Debug)]
+pub struct StandardDescriptorSetAllocatorCreateInfo {
+    /// How many descriptor sets should be allocated per pool.
+    ///
+    /// Each time a thread allocates using some descriptor set layout, and either no pools were
+    /// initialized yet or all pools are full, a new pool is created for that thread and descriptor
+    /// set layout combination. This option tells the allocator how many descriptor sets should be
+    /// allocated for that pool. For descriptor set layouts with a fixed descriptor count, the
+    /// descriptor sets are allocated all at once, and the number doubles each time a new pool is
+    /// created. For descriptor set layouts with a variable descriptor count, this is the maximum
+    /// number of descriptor sets that are allocated from each pool.
+    ///
+    /// The default value is `256`.
+    pub set_count: usize,
+
+    /// The maximum number of descriptors of each type that each pool can hold.
+    ///
+    /// By default, a pool holds enough descriptors for `set_count` descriptor sets, which can be
+    /// very large for layouts containing large arrays of descriptors. If a descriptor type is
+    /// present in this map, pools hold at most this many descriptors of that type instead, and
+    /// fewer descriptor sets are allocated from each pool accordingly. A pool always holds enough
+    /// descriptors for at least one descriptor set.
+    ///
+    /// The default value is empty.
+    pub descriptor_counts: HashMap<DescriptorType, u32>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for StandardDescriptorSetAllocatorCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        StandardDescriptorSetAllocatorCreateInfo {
+            set_count: MAX_SETS,
+            descriptor_counts: HashMap::default(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
 #[derive(Debug)]
 struct FixedEntry {
     // The `FixedPool` struct contains an actual Vulkan pool. Every time it is full we create
@@ -221,20 +258,34 @@ struct FixedEntry {
 }
 
 impl FixedEntry {
-    fn new(layout: Arc<DescriptorSetLayout>) -> Result<Self, VulkanError> {
+    fn new(
+        layout: Arc<DescriptorSetLayout>,
+        create_info: &StandardDescriptorSetAllocatorCreateInfo,
+    ) -> Result<Self, VulkanError> {
+        let set_count = create_info.set_count.max(1);
+
         Ok(FixedEntry {
-            pool: FixedPool::new(&layout, MAX_SETS)?,
-            set_count: MAX_SETS,
+            pool: FixedPool::new(&layout, set_count, &create_info.descriptor_counts)?,
+            set_count,
             layout,
         })
     }
 
-    fn allocate(&mut self) -> Result<StandardDescriptorSetAlloc, VulkanError> {
+    fn allocate(
+        &mut self,
+        create_info: &StandardDescriptorSetAllocatorCreateInfo,
+    ) -> Result<StandardDescriptorSetAlloc, VulkanError> {
         let inner = if let Some(inner) = self.pool.reserve.pop() {
             inner
         } else {
-            self.set_count *= 2;
-            self.pool = FixedPool::new(&self.layout, self.set_count)?;
+            // Once the configured descriptor counts limit the size of the pools, growing the
+            // number of sets no longer has any effect.
+            if self.pool.reserve.capacity() >= self.set_count {
+                self.set_count = self.set_count.saturating_mul(2).min(u32::MAX as usize);
+            }
+
+            self.pool =
+                FixedPool::new(&self.layout, self.set_count, &create_info.descriptor_counts)?;
 
             self.pool.reserve.pop().unwrap()
         };
@@ -257,7 +308,25 @@ struct FixedPool {
 }
 
 impl FixedPool {
-    fn new(layout: &Arc<DescriptorSetLayout>, set_count: usize) -> Result<Arc<Self>, VulkanError> {
+    fn new(
+        layout: &Arc<DescriptorSetLayout>,
+        set_count: usize,
+        descriptor_counts: &HashMap<DescriptorType, u32>,
+    ) -> Result<Arc<Self>, VulkanError> {
+        // Limit the number of sets so that the pool doesn't exceed the configured descriptor
+        // counts, while always fitting at least one set.
+        let set_count = layout
+            .descriptor_counts()
+            .iter()
+            .filter(|&(_, &count)| count != 0)
+            .filter_map(|(ty, &count)| {
+                descriptor_counts
+                    .get(ty)
+                    .map(|&max_count| (max_count / count) as usize)
+            })
+            .fold(set_count, usize::min)
+            .max(1);
+
         let inner = DescriptorPool::new(
             layout.device().clone(),
             DescriptorPoolCreateInfo {
@@ -270,6 +339,7 @@ impl FixedPool {
                         (ty, count * set_count as u32)
                     })
                     .collect(),
+                flags: pool_create_flags(layout),
                 ..Default::default()
             },
         )
@@ -326,11 +396,14 @@ struct VariableEntry {
 }
 
 impl VariableEntry {
-    fn new(layout: Arc<DescriptorSetLayout>) -> Result<Self, VulkanError> {
+    fn new(
+        layout: Arc<DescriptorSetLayout>,
+        create_info: &StandardDescriptorSetAllocatorCreateInfo,
+    ) -> Result<Self, VulkanError> {
         let reserve = Arc::new(ArrayQueue::new(MAX_POOLS));
 
         Ok(VariableEntry {
-            pool: VariablePool::new(&layout, reserve.clone())?,
+            pool: VariablePool::new(&layout, reserve.clone(), create_info)?,
             reserve,
             layout,
             allocations: 0,
@@ -340,51 +413,69 @@ impl VariableEntry {
     fn allocate(
         &mut self,
         variable_descriptor_count: u32,
+        create_info: &StandardDescriptorSetAllocatorCreateInfo,
     ) -> Result<StandardDescriptorSetAlloc, VulkanError> {
-        if self.allocations >= MAX_SETS {
-            self.pool = if let Some(inner) = self.reserve.pop() {
-                Arc::new(VariablePool {
-                    inner: ManuallyDrop::new(inner),
-                    reserve: self.reserve.clone(),
-                })
-            } else {
-                VariablePool::new(&self.layout, self.reserve.clone())?
-            };
-            self.allocations = 0;
+        if self.allocations >= create_info.set_count.max(1) {
+            self.next_pool(create_info)?;
         }
 
-        let allocate_info = DescriptorSetAllocateInfo {
-            layout: &self.layout,
-            variable_descriptor_count,
-        };
+        let inner = loop {
+            let allocate_info = DescriptorSetAllocateInfo {
+                layout: &self.layout,
+                variable_descriptor_count,
+            };
 
-        let mut sets = unsafe {
-            self.pool
-                .inner
-                .allocate_descriptor_sets([allocate_info])
-                .map_err(|err| match err {
-                    VulkanError::OutOfHostMemory | VulkanError::OutOfDeviceMemory => err,
-                    VulkanError::FragmentedPool => {
-                        // This can't happen as we don't free individual sets.
-                        unreachable!();
-                    }
-                    VulkanError::OutOfPoolMemory => {
-                        // We created the pool to fit the maximum variable descriptor count.
-                        unreachable!();
-                    }
-                    _ => {
-                        // Shouldn't ever be returned.
-                        unreachable!();
-                    }
-                })?
+            let result = unsafe { self.pool.inner.allocate_descriptor_sets([allocate_info]) }
+                .map(|mut sets| sets.next().unwrap());
+
+            match result {
+                Ok(inner) => break inner,
+                Err(err @ (VulkanError::OutOfHostMemory | VulkanError::OutOfDeviceMemory)) => {
+                    return Err(err);
+                }
+                Err(VulkanError::OutOfPoolMemory) if self.allocations != 0 => {
+                    // The pool was created with the configured descriptor counts, which don't
+                    // necessarily fit `set_count` sets. A new pool always fits at least one set.
+                    self.next_pool(create_info)?;
+                }
+                Err(VulkanError::FragmentedPool) => {
+                    // This can't happen as we don't free individual sets.
+                    unreachable!();
+                }
+                Err(VulkanError::OutOfPoolMemory) => {
+                    // We created the pool to fit the maximum variable descriptor count.
+                    unreachable!();
+                }
+                Err(_) => {
+                    // Shouldn't ever be returned.
+                    unreachable!();
+                }
+            }
         };
         self.allocations += 1;
 
         Ok(StandardDescriptorSetAlloc {
-            inner: ManuallyDrop::new(sets.next().unwrap()),
+            inner: ManuallyDrop::new(inner),
             parent: AllocParent::Variable(self.pool.clone()),
         })
     }
+
+    fn next_pool(
+        &mut self,
+        create_info: &StandardDescriptorSetAllocatorCreateInfo,
+    ) -> Result<(), VulkanError> {
+        self.pool = if let Some(inner) = self.reserve.pop() {
+            Arc::new(VariablePool {
+                inner: ManuallyDrop::new(inner),
+                reserve: self.reserve.clone(),
+            })
+        } else {
+            VariablePool::new(&self.layout, self.reserve.clone(), create_info)?
+        };
+        self.allocations = 0;
+
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -399,19 +490,28 @@ impl VariablePool {
     fn new(
         layout: &Arc<DescriptorSetLayout>,
         reserve: Arc<ArrayQueue<DescriptorPool>>,
+        create_info: &StandardDescriptorSetAllocatorCreateInfo,
     ) -> Result<Arc<Self>, VulkanError> {
+        let set_count = create_info.set_count.max(1);
+
         DescriptorPool::new(
             layout.device().clone(),
             DescriptorPoolCreateInfo {
-                max_sets: MAX_SETS as u32,
+                max_sets: set_count as u32,
                 pool_sizes: layout
                     .descriptor_counts()
                     .iter()
                     .map(|(&ty, &count)| {
                         assert!(ty != DescriptorType::InlineUniformBlock);
-                        (ty, count * MAX_SETS as u32)
+                        let pool_size = create_info
+                            .descriptor_counts
+                            .get(&ty)
+                            .map_or(count * set_count as u32, |&max_count| max_count.max(count));
+
+                        (ty, pool_size)
                     })
                     .collect(),
+                flags: pool_create_flags(layout),
                 ..Default::default()
             },
         )
@@ -489,6 +589,322 @@ impl Drop for StandardDescriptorSetAlloc {
     }
 }
 
+/// Descriptor set allocator for transient descriptor sets, which are only used for a single frame.
+///
+/// Descriptor sets are allocated one after another from a few large [`DescriptorPool`]s and are
+/// never freed individually, which makes allocating very cheap. Instead, [`reset`] is called once
+/// per frame, which moves on to the next of `frame_count` sets of pools and recycles all the
+/// descriptor sets of that frame at once, by resetting its pools.
+///
+/// `frame_count` should be the number of frames in flight, so that by the time a frame comes
+/// around again, the command buffers that used its descriptor sets have finished executing. A pool
+/// is only ever reset once all descriptor sets allocated from it have been dropped. If some are
+/// still alive when its frame comes around again, the pool is left alone and new pools are created
+/// for the frame instead, so calling [`reset`] too often is not unsafe, only wasteful.
+///
+/// Unlike the [`StandardDescriptorSetAllocator`], all threads allocate from the same pools, so
+/// allocating needs to take a lock.
+///
+/// [`DescriptorPool`]: crate::descriptor_set::pool::DescriptorPool
+/// [`reset`]: Self::reset
+#[derive(Debug)]
+pub struct TransientDescriptorPool {
+    device: InstanceOwnedDebugWrapper<Arc<Device>>,
+    state: Mutex<TransientState>,
+    create_info: TransientDescriptorPoolCreateInfo,
+}
+
+#[derive(Debug)]
+struct TransientState {
+    // The pools of each frame. Index 0 holds regular pools, index 1 holds update-after-bind pools.
+    frames: Vec<[TransientFramePools; 2]>,
+    // The index of the frame that is currently being allocated for.
+    current_frame: usize,
+    // The largest number of descriptors of each type of all layouts allocated so far. New pools
+    // are created with enough descriptors for `set_count` descriptor sets of this size.
+    descriptor_counts: HashMap<DescriptorType, u32>,
+}
+
+// This is needed because `Arc<DescriptorPool>` is `!Send`, as `DescriptorPool` is `!Sync`. That's
+// fine however because we only access the `DescriptorPool`s while holding the lock.
+unsafe impl Send for TransientState {}
+
+#[derive(Debug, Default)]
+struct TransientFramePools {
+    pools: Vec<Arc<DescriptorPool>>,
+    // The index of the pool that is currently being allocated from. The pools before it are full.
+    current: usize,
+}
+
+impl TransientDescriptorPool {
+    /// Creates a new `TransientDescriptorPool`.
+    #[inline]
+    pub fn new(
+        device: Arc<Device>,
+        create_info: TransientDescriptorPoolCreateInfo,
+    ) -> TransientDescriptorPool {
+        let frame_count = create_info.frame_count.max(1);
+
+        TransientDescriptorPool {
+            device: InstanceOwnedDebugWrapper(device),
+            state: Mutex::new(TransientState {
+                frames: (0..frame_count).map(|_| Default::default()).collect(),
+                current_frame: 0,
+                descriptor_counts: HashMap::default(),
+            }),
+            create_info,
+        }
+    }
+
+    /// Returns the index of the frame that descriptor sets are currently being allocated for.
+    #[inline]
+    pub fn current_frame(&self) -> usize {
+        self.state.lock().current_frame
+    }
+
+    /// Moves on to the next frame and recycles all the descriptor sets that were allocated the
+    /// last time that frame was current.
+    ///
+    /// This should be called once per frame, for example after acquiring the next swapchain
+    /// image. Descriptor sets that are still alive stay valid; the pools they were allocated from
+    /// are replaced with new ones rather than being reset.
+    pub fn reset(&self) {
+        let mut state = self.state.lock();
+        state.current_frame = (state.current_frame + 1) % state.frames.len();
+        let current_frame = state.current_frame;
+
+        for frame_pools in &mut state.frames[current_frame] {
+            frame_pools
+                .pools
+                .retain(|pool| Arc::strong_count(pool) == 1);
+
+            for pool in &frame_pools.pools {
+                // SAFETY: We hold the only reference to the pool, so all descriptor sets that were
+                // allocated from it have been dropped.
+                unsafe { pool.reset() }.unwrap();
+            }
+
+            frame_pools.current = 0;
+        }
+    }
+}
+
+unsafe impl DescriptorSetAllocator for TransientDescriptorPool {
+    type Alloc = TransientDescriptorSetAlloc;
+
+    /// Allocates a descriptor set from the pools of the current frame.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if the provided `layout` is for push descriptors rather than regular descriptor
+    ///   sets.
+    /// - Panics if the provided `variable_descriptor_count` is greater than the maximum number of
+    ///   variable count descriptors in the set.
+    fn allocate(
+        &self,
+        layout: &Arc<DescriptorSetLayout>,
+        variable_descriptor_count: u32,
+    ) -> Result<TransientDescriptorSetAlloc, VulkanError> {
+        assert_allocatable(layout, variable_descriptor_count);
+
+        let mut state = self.state.lock();
+        let state = &mut *state;
+
+        for (&ty, &count) in layout.descriptor_counts() {
+            assert!(ty != DescriptorType::InlineUniformBlock);
+            let max_count = state.descriptor_counts.entry(ty).or_insert(0);
+            *max_count = (*max_count).max(count);
+        }
+
+        let update_after_bind = layout
+            .flags()
+            .intersects(DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL);
+        let frame_pools = &mut state.frames[state.current_frame][update_after_bind as usize];
+        let allocate_info = DescriptorSetAllocateInfo {
+            layout,
+            variable_descriptor_count,
+        };
+
+        while let Some(pool) = frame_pools.pools.get(frame_pools.current) {
+            match unsafe { pool.allocate_descriptor_sets([allocate_info.clone()]) } {
+                Ok(mut sets) => {
+                    return Ok(TransientDescriptorSetAlloc {
+                        inner: sets.next().unwrap(),
+                        _pool: pool.clone(),
+                    });
+                }
+                Err(VulkanError::OutOfPoolMemory | VulkanError::FragmentedPool) => {
+                    // The pool is full, or was created before a layout with more descriptors was
+                    // seen.
+                    frame_pools.current += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        let set_count = self.create_info.set_count.max(1) as u32;
+        let pool = DescriptorPool::new(
+            self.device.clone(),
+            DescriptorPoolCreateInfo {
+                max_sets: set_count,
+                pool_sizes: state
+                    .descriptor_counts
+                    .iter()
+                    .map(|(&ty, &count)| {
+                        let pool_size = self
+                            .create_info
+                            .descriptor_counts
+                            .get(&ty)
+                            .map_or(count.saturating_mul(set_count), |&max_count| {
+                                max_count.max(count)
+                            });
+
+                        (ty, pool_size)
+                    })
+                    .collect(),
+                flags: pool_create_flags(layout),
+                ..Default::default()
+            },
+        )
+        .map(Arc::new)
+        .map_err(Validated::unwrap)?;
+
+        let inner = unsafe { pool.allocate_descriptor_sets([allocate_info]) }
+            .map(|mut sets| sets.next().unwrap())
+            .map_err(|err| match err {
+                VulkanError::OutOfHostMemory | VulkanError::OutOfDeviceMemory => err,
+                VulkanError::FragmentedPool | VulkanError::OutOfPoolMemory => {
+                    // We created the pool to fit at least one set of the layout.
+                    unreachable!();
+                }
+                _ => {
+                    // Shouldn't ever be returned.
+                    unreachable!();
+                }
+            })?;
+        frame_pools.pools.push(pool.clone());
+
+        Ok(TransientDescriptorSetAlloc { inner, _pool: pool })
+    }
+}
+
+unsafe impl DeviceOwned for TransientDescriptorPool {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+/// Parameters to create a new [`TransientDescriptorPool`].
+#[derive(Clone, Debug)]
+pub struct TransientDescriptorPoolCreateInfo {
+    /// The number of frames that the pools are cycled through, which should be the number of
+    /// frames in flight.
+    ///
+    /// The default value is `2`.
+    pub frame_count: usize,
+
+    /// How many descriptor sets each pool can hold.
+    ///
+    /// When all pools of the current frame are full, a new pool is created for the frame, with
+    /// enough descriptors for this many descriptor sets of the largest layout allocated so far.
+    ///
+    /// The default value is `256`.
+    pub set_count: usize,
+
+    /// The maximum number of descriptors of each type that each pool can hold.
+    ///
+    /// If a descriptor type is present in this map, pools hold this many descriptors of that type
+    /// instead of enough for `set_count` descriptor sets. A pool always holds enough descriptors
+    /// for at least one descriptor set.
+    ///
+    /// The default value is empty.
+    pub descriptor_counts: HashMap<DescriptorType, u32>,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl Default for TransientDescriptorPoolCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        TransientDescriptorPoolCreateInfo {
+            frame_count: 2,
+            set_count: MAX_SETS,
+            descriptor_counts: HashMap::default(),
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+}
+
+/// A descriptor set allocated from a [`TransientDescriptorPool`].
+///
+/// The descriptor set is not freed when this is dropped, but when its frame comes around again.
+#[derive(Debug)]
+pub struct TransientDescriptorSetAlloc {
+    // The actual descriptor set.
+    inner: UnsafeDescriptorSet,
+    // The pool where we allocated from, which must be kept alive while the set is in use.
+    _pool: Arc<DescriptorPool>,
+}
+
+// This is needed because of the blanket impl of `Send` on `Arc<T>`, which requires that `T` is
+// `Send + Sync`. `DescriptorPool` is `Send + !Sync`. That's fine however because we never access
+// the `DescriptorPool` through this reference.
+unsafe impl Send for TransientDescriptorSetAlloc {}
+unsafe impl Sync for TransientDescriptorSetAlloc {}
+
+impl DescriptorSetAlloc for TransientDescriptorSetAlloc {
+    #[inline]
+    fn inner(&self) -> &UnsafeDescriptorSet {
+        &self.inner
+    }
+
+    #[inline]
+    fn inner_mut(&mut self) -> &mut UnsafeDescriptorSet {
+        &mut self.inner
+    }
+}
+
+fn assert_allocatable(layout: &DescriptorSetLayout, variable_descriptor_count: u32) {
+    assert!(
+        !layout
+            .flags()
+            .intersects(DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR),
+        "the provided descriptor set layout is for push descriptors, and cannot be used to build \
+        a descriptor set object",
+    );
+    assert!(
+        !layout
+            .flags()
+            .intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER),
+        "the provided descriptor set layout is for descriptor buffers, and cannot be used to \
+        build a descriptor set object",
+    );
+
+    let max_count = layout.variable_descriptor_count();
+
+    assert!(
+        variable_descriptor_count <= max_count,
+        "the provided variable_descriptor_count ({}) is greater than the maximum number of \
+        variable count descriptors in the set ({})",
+        variable_descriptor_count,
+        max_count,
+    );
+}
+
+// Descriptor sets with an update-after-bind layout can only be allocated from an update-after-bind
+// pool.
+fn pool_create_flags(layout: &DescriptorSetLayout) -> DescriptorPoolCreateFlags {
+    if layout
+        .flags()
+        .intersects(DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL)
+    {
+        DescriptorPoolCreateFlags::UPDATE_AFTER_BIND
+    } else {
+        DescriptorPoolCreateFlags::empty()
+    }
+}
+
 mod sorted_map {
     use smallvec::SmallVec;
 
@@ -563,7 +979,7 @@ mod tests {
         )
         .unwrap();
 
-        let allocator = StandardDescriptorSetAllocator::new(device);
+        let allocator = StandardDescriptorSetAllocator::new(device, Default::default());
 
         let pool1 =
             if let AllocParent::Fixed(pool) = &allocator.allocate(&layout, 0).unwrap().parent {
@@ -584,4 +1000,125 @@ mod tests {
         .join()
         .unwrap();
     }
+
+    #[test]
+    fn configured_descriptor_counts() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        descriptor_count: 1024,
+                        stages: ShaderStages::FRAGMENT,
+                        ..DescriptorSetLayoutBinding::descriptor_type(
+                            DescriptorType::CombinedImageSampler,
+                        )
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let allocator = StandardDescriptorSetAllocator::new(
+            device.clone(),
+            StandardDescriptorSetAllocatorCreateInfo {
+                descriptor_counts: [(DescriptorType::CombinedImageSampler, 2048)]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            },
+        );
+
+        let pool = |alloc: &StandardDescriptorSetAlloc| {
+            if let AllocParent::Fixed(pool) = &alloc.parent {
+                pool._inner.handle()
+            } else {
+                unreachable!()
+            }
+        };
+
+        // Each pool only fits two sets of the layout.
+        let alloc1 = allocator.allocate(&layout, 0).unwrap();
+        let alloc2 = allocator.allocate(&layout, 0).unwrap();
+        let alloc3 = allocator.allocate(&layout, 0).unwrap();
+        assert_eq!(pool(&alloc1), pool(&alloc2));
+        assert_ne!(pool(&alloc2), pool(&alloc3));
+
+        let allocator = StandardDescriptorSetAllocator::new(
+            device,
+            StandardDescriptorSetAllocatorCreateInfo {
+                descriptor_counts: [(DescriptorType::CombinedImageSampler, 1024)]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            },
+        );
+
+        // Each pool only fits one set, so every allocation creates a new pool.
+        let allocs: Vec<_> = (0..100)
+            .map(|_| allocator.allocate(&layout, 0).unwrap())
+            .collect();
+        assert!(allocs.windows(2).all(|w| pool(&w[0]) != pool(&w[1])));
+    }
+
+    #[test]
+    fn transient_pool_recycles_frames() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::all_graphics(),
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::UniformBuffer)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let allocator = TransientDescriptorPool::new(
+            device,
+            TransientDescriptorPoolCreateInfo {
+                frame_count: 2,
+                set_count: 1,
+                ..Default::default()
+            },
+        );
+
+        let pool = |alloc: &TransientDescriptorSetAlloc| alloc._pool.handle();
+
+        // Each pool only fits one set.
+        let alloc1 = allocator.allocate(&layout, 0).unwrap();
+        let alloc2 = allocator.allocate(&layout, 0).unwrap();
+        assert_ne!(pool(&alloc1), pool(&alloc2));
+        let (pool1, pool2) = (pool(&alloc1), pool(&alloc2));
+        drop(alloc2);
+
+        allocator.reset();
+        assert_eq!(allocator.current_frame(), 1);
+        let alloc3 = allocator.allocate(&layout, 0).unwrap();
+        assert_ne!(pool(&alloc3), pool1);
+        assert_ne!(pool(&alloc3), pool2);
+        drop(alloc3);
+
+        // The first pool of frame 0 is still in use, so it's replaced rather than reset.
+        allocator.reset();
+        assert_eq!(allocator.current_frame(), 0);
+        let alloc4 = allocator.allocate(&layout, 0).unwrap();
+        assert_eq!(pool(&alloc4), pool2);
+        let alloc5 = allocator.allocate(&layout, 0).unwrap();
+        assert_ne!(pool(&alloc5), pool1);
+        assert_ne!(pool(&alloc5), pool2);
+        drop(alloc1);
+    }
 }
@@ -37,6 +37,21 @@ pub struct DescriptorSetLayout {
 }
 
 impl DescriptorSetLayout {
+    /// Returns whether a descriptor set layout with the given `create_info` could be created on
+    /// `device`. `Some` is returned if the descriptor set layout is supported, `None` if it is
+    /// not.
+    ///
+    /// This is the same as [`Device::descriptor_set_layout_support`]. It can be used to check
+    /// whether a large layout, such as one with a big bindless array, fits within the limits of
+    /// the device before trying to create it.
+    #[inline]
+    pub fn supports(
+        device: &Device,
+        create_info: &DescriptorSetLayoutCreateInfo,
+    ) -> Result<Option<DescriptorSetLayoutSupport>, Box<ValidationError>> {
+        device.descriptor_set_layout_support(create_info)
+    }
+
     /// Creates a new `DescriptorSetLayout`.
     #[inline]
     pub fn new(
@@ -334,6 +349,30 @@ impl DescriptorSetLayoutCreateInfo {
                 .set_vuids(&["VUID-VkDescriptorSetLayoutCreateInfo-flags-parameter"])
         })?;
 
+        if flags.intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER) {
+            if !device.enabled_features().descriptor_buffer {
+                return Err(Box::new(ValidationError {
+                    context: "flags".into(),
+                    problem: "contains `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "descriptor_buffer",
+                    )])]),
+                    ..Default::default()
+                }));
+            }
+
+            if flags.intersects(DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL) {
+                return Err(Box::new(ValidationError {
+                    context: "flags".into(),
+                    problem: "contains both `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER` \
+                        and `DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL`"
+                        .into(),
+                    vuids: &["VUID-VkDescriptorSetLayoutCreateInfo-flags-08002"],
+                    ..Default::default()
+                }));
+            }
+        }
+
         // VUID-VkDescriptorSetLayoutCreateInfo-binding-00279
         // Ensured because it is a map
 
@@ -356,6 +395,26 @@ impl DescriptorSetLayoutCreateInfo {
 
             total_descriptor_count += descriptor_count;
 
+            if flags.intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER)
+                && matches!(
+                    descriptor_type,
+                    DescriptorType::UniformBufferDynamic | DescriptorType::StorageBufferDynamic
+                )
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`flags` contains `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`, \
+                        and `bindings[{}].descriptor_type` is \
+                        `DescriptorType::UniformBufferDynamic` or \
+                        `DescriptorType::StorageBufferDynamic`",
+                        binding_num
+                    )
+                    .into(),
+                    vuids: &["VUID-VkDescriptorSetLayoutCreateInfo-flags-08000"],
+                    ..Default::default()
+                }));
+            }
+
             if flags.intersects(DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR) {
                 if matches!(
                     descriptor_type,
@@ -381,11 +440,17 @@ impl DescriptorSetLayoutCreateInfo {
                     }));
                 }
 
-                if binding_flags.intersects(DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT) {
+                if binding_flags.intersects(
+                    DescriptorBindingFlags::UPDATE_AFTER_BIND
+                        | DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING
+                        | DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT,
+                ) {
                     return Err(Box::new(ValidationError {
                         problem: format!(
                             "`flags` contains `DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR`, \
                             and `bindings[{}].flags` contains \
+                            `DescriptorBindingFlags::UPDATE_AFTER_BIND`, \
+                            `DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING` or \
                             `DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT`",
                             binding_num
                         )
@@ -396,6 +461,22 @@ impl DescriptorSetLayoutCreateInfo {
                 }
             }
 
+            if binding_flags.intersects(DescriptorBindingFlags::UPDATE_AFTER_BIND)
+                && !flags.intersects(DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL)
+            {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`bindings[{}].flags` contains \
+                        `DescriptorBindingFlags::UPDATE_AFTER_BIND`, but `flags` does not \
+                        contain `DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL`",
+                        binding_num
+                    )
+                    .into(),
+                    vuids: &["VUID-VkDescriptorSetLayoutCreateInfo-flags-03000"],
+                    ..Default::default()
+                }));
+            }
+
             if binding_flags.intersects(DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT)
                 && Some(binding_num) != highest_binding_num
             {
@@ -455,13 +536,19 @@ vulkan_bitflags! {
     /// Flags that control how a descriptor set layout is created.
     DescriptorSetLayoutCreateFlags = DescriptorSetLayoutCreateFlags(u32);
 
-    /* TODO: enable
-    // TODO: document
+    /// Whether descriptor sets allocated with this layout can be updated after they have been
+    /// bound to a command buffer.
+    ///
+    /// This flag must be set if any binding has the [`DescriptorBindingFlags::UPDATE_AFTER_BIND`]
+    /// flag. Descriptor sets with this layout must be allocated from a descriptor pool that was
+    /// created with the [`DescriptorPoolCreateFlags::UPDATE_AFTER_BIND`] flag.
+    ///
+    /// [`DescriptorPoolCreateFlags::UPDATE_AFTER_BIND`]: crate::descriptor_set::pool::DescriptorPoolCreateFlags::UPDATE_AFTER_BIND
     UPDATE_AFTER_BIND_POOL = UPDATE_AFTER_BIND_POOL
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_2)]),
         RequiresAllOf([DeviceExtension(ext_descriptor_indexing)]),
-    ]), */
+    ]),
 
     /// Whether the descriptor set layout should be created for push descriptors.
     ///
@@ -479,12 +566,27 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_push_descriptor)]),
     ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Whether the descriptor set layout describes the layout of descriptors in a descriptor
+    /// buffer, rather than of a descriptor set.
+    ///
+    /// If set, the layout cannot be used to allocate descriptor sets. Instead, the descriptors are
+    /// read from a buffer bound with
+    /// [`bind_descriptor_buffers`](crate::command_buffer::AutoCommandBufferBuilder::bind_descriptor_buffers),
+    /// at the offset given with
+    /// [`set_descriptor_buffer_offsets`](crate::command_buffer::AutoCommandBufferBuilder::set_descriptor_buffer_offsets).
+    ///
+    /// If set, there are several restrictions:
+    /// - The [`descriptor_buffer`](crate::device::Features::descriptor_buffer) feature must be
+    ///   enabled on the device.
+    /// - `flags` must not also contain [`UPDATE_AFTER_BIND_POOL`].
+    /// - There must be no bindings with a type of [`DescriptorType::UniformBufferDynamic`] or
+    ///   [`DescriptorType::StorageBufferDynamic`].
+    ///
+    /// [`UPDATE_AFTER_BIND_POOL`]: DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL
     DESCRIPTOR_BUFFER = DESCRIPTOR_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_descriptor_buffer)]),
-    ]), */
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -576,10 +678,41 @@ impl DescriptorSetLayoutBinding {
         } = binding_requirements;
 
         if !descriptor_types.contains(&self.descriptor_type) {
+            let mut problem = format!(
+                "the descriptor type `DescriptorType::{:?}` is not one of the types allowed by \
+                the descriptor binding requirements ({})",
+                self.descriptor_type,
+                descriptor_types
+                    .iter()
+                    .map(|ty| format!("`DescriptorType::{:?}`", ty))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            );
+
+            // Mixing up combined image samplers with separate images and samplers is a common
+            // mistake, so point it out explicitly.
+            let is_separate = |ty: &DescriptorType| {
+                matches!(ty, DescriptorType::SampledImage | DescriptorType::Sampler)
+            };
+
+            if descriptor_types.contains(&DescriptorType::CombinedImageSampler)
+                && is_separate(&self.descriptor_type)
+            {
+                problem.push_str(
+                    "; the shader uses a combined image sampler, such as `sampler2D`, but the \
+                    binding is a separate image or sampler",
+                );
+            } else if self.descriptor_type == DescriptorType::CombinedImageSampler
+                && descriptor_types.iter().any(is_separate)
+            {
+                problem.push_str(
+                    "; the shader uses a separate image or sampler, such as `texture2D` or \
+                    `sampler`, but the binding is a combined image sampler",
+                );
+            }
+
             return Err(Box::new(ValidationError {
-                problem: "the descriptor type is not one of the types allowed by the \
-                    descriptor binding requirements"
-                    .into(),
+                problem: problem.into(),
                 ..Default::default()
             }));
         }
@@ -766,6 +899,110 @@ impl DescriptorSetLayoutBinding {
             }
         }
 
+        if binding_flags.intersects(DescriptorBindingFlags::UPDATE_AFTER_BIND) {
+            let enabled_features = device.enabled_features();
+            let required_feature: Option<(bool, &[RequiresAllOf], &[&str])> = match descriptor_type {
+                DescriptorType::UniformBuffer => Some((
+                    enabled_features.descriptor_binding_uniform_buffer_update_after_bind,
+                    &[RequiresAllOf(&[Requires::Feature("descriptor_binding_uniform_buffer_update_after_bind")])],
+                    &["VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingUniformBufferUpdateAfterBind-03005"],
+                )),
+                DescriptorType::Sampler
+                | DescriptorType::CombinedImageSampler
+                | DescriptorType::SampledImage => Some((
+                    enabled_features.descriptor_binding_sampled_image_update_after_bind,
+                    &[RequiresAllOf(&[Requires::Feature("descriptor_binding_sampled_image_update_after_bind")])],
+                    &["VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingSampledImageUpdateAfterBind-03006"],
+                )),
+                DescriptorType::StorageImage => Some((
+                    enabled_features.descriptor_binding_storage_image_update_after_bind,
+                    &[RequiresAllOf(&[Requires::Feature("descriptor_binding_storage_image_update_after_bind")])],
+                    &["VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingStorageImageUpdateAfterBind-03007"],
+                )),
+                DescriptorType::StorageBuffer => Some((
+                    enabled_features.descriptor_binding_storage_buffer_update_after_bind,
+                    &[RequiresAllOf(&[Requires::Feature("descriptor_binding_storage_buffer_update_after_bind")])],
+                    &["VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingStorageBufferUpdateAfterBind-03008"],
+                )),
+                DescriptorType::UniformTexelBuffer => Some((
+                    enabled_features.descriptor_binding_uniform_texel_buffer_update_after_bind,
+                    &[RequiresAllOf(&[Requires::Feature("descriptor_binding_uniform_texel_buffer_update_after_bind")])],
+                    &["VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingUniformTexelBufferUpdateAfterBind-03009"],
+                )),
+                DescriptorType::StorageTexelBuffer => Some((
+                    enabled_features.descriptor_binding_storage_texel_buffer_update_after_bind,
+                    &[RequiresAllOf(&[Requires::Feature("descriptor_binding_storage_texel_buffer_update_after_bind")])],
+                    &["VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingStorageTexelBufferUpdateAfterBind-03010"],
+                )),
+                DescriptorType::InlineUniformBlock => Some((
+                    enabled_features.descriptor_binding_inline_uniform_block_update_after_bind,
+                    &[RequiresAllOf(&[Requires::Feature("descriptor_binding_inline_uniform_block_update_after_bind")])],
+                    &["VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingInlineUniformBlockUpdateAfterBind-02211"],
+                )),
+                DescriptorType::AccelerationStructure => Some((
+                    enabled_features.descriptor_binding_acceleration_structure_update_after_bind,
+                    &[RequiresAllOf(&[Requires::Feature("descriptor_binding_acceleration_structure_update_after_bind")])],
+                    &["VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingAccelerationStructureUpdateAfterBind-03570"],
+                )),
+                DescriptorType::UniformBufferDynamic | DescriptorType::StorageBufferDynamic => {
+                    return Err(Box::new(ValidationError {
+                        problem: "`binding_flags` contains \
+                            `DescriptorBindingFlags::UPDATE_AFTER_BIND`, and \
+                            `descriptor_type` is `DescriptorType::UniformBufferDynamic` or \
+                            `DescriptorType::StorageBufferDynamic`"
+                            .into(),
+                        vuids: &["VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-None-03011"],
+                        ..Default::default()
+                    }));
+                }
+                _ => None,
+            };
+
+            if let Some((enabled, requires_one_of, vuids)) = required_feature {
+                if !enabled {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`binding_flags` contains `DescriptorBindingFlags::UPDATE_AFTER_BIND`, \
+                            and `descriptor_type` is `DescriptorType::{:?}`",
+                            descriptor_type,
+                        )
+                        .into(),
+                        requires_one_of: RequiresOneOf(requires_one_of),
+                        vuids,
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
+        if binding_flags.intersects(DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING)
+            && !device
+                .enabled_features()
+                .descriptor_binding_update_unused_while_pending
+        {
+            return Err(Box::new(ValidationError {
+                context: "binding_flags".into(),
+                problem: "contains `DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "descriptor_binding_update_unused_while_pending",
+                )])]),
+                vuids: &["VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingUpdateUnusedWhilePending-03012"],
+            }));
+        }
+
+        if binding_flags.intersects(DescriptorBindingFlags::PARTIALLY_BOUND)
+            && !device.enabled_features().descriptor_binding_partially_bound
+        {
+            return Err(Box::new(ValidationError {
+                context: "binding_flags".into(),
+                problem: "contains `DescriptorBindingFlags::PARTIALLY_BOUND`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "descriptor_binding_partially_bound",
+                )])]),
+                vuids: &["VUID-VkDescriptorSetLayoutBindingFlagsCreateInfo-descriptorBindingPartiallyBound-03013"],
+            }));
+        }
+
         Ok(())
     }
 }
@@ -790,29 +1027,43 @@ vulkan_bitflags! {
     /// Flags that control how a binding in a descriptor set layout is created.
     DescriptorBindingFlags = DescriptorBindingFlags(u32);
 
-    /* TODO: enable
-    // TODO: document
+    /// Whether the descriptors in the binding can be updated after the descriptor set has been
+    /// bound to a command buffer, as long as the command buffer has not been submitted yet.
+    ///
+    /// If set, the layout must have the [`DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL`]
+    /// flag, and the `descriptor_binding_*_update_after_bind` feature corresponding to the
+    /// `descriptor_type` must be enabled. The `descriptor_type` must not be
+    /// [`DescriptorType::UniformBufferDynamic`] or [`DescriptorType::StorageBufferDynamic`].
     UPDATE_AFTER_BIND = UPDATE_AFTER_BIND
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_2)]),
         RequiresAllOf([DeviceExtension(ext_descriptor_indexing)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Whether the descriptors in the binding can be updated while a command buffer that uses the
+    /// descriptor set is pending execution, as long as the updated descriptors are not used by
+    /// that command buffer.
+    ///
+    /// If set, the [`descriptor_binding_update_unused_while_pending`] feature must be enabled.
+    ///
+    /// [`descriptor_binding_update_unused_while_pending`]: crate::device::Features::descriptor_binding_update_unused_while_pending
     UPDATE_UNUSED_WHILE_PENDING = UPDATE_UNUSED_WHILE_PENDING
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_2)]),
         RequiresAllOf([DeviceExtension(ext_descriptor_indexing)]),
-    ]), */
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// Whether the descriptors in the binding are allowed to be left unwritten, as long as they
+    /// are not accessed by a shader.
+    ///
+    /// If set, the [`descriptor_binding_partially_bound`] feature must be enabled.
+    ///
+    /// [`descriptor_binding_partially_bound`]: crate::device::Features::descriptor_binding_partially_bound
     PARTIALLY_BOUND = PARTIALLY_BOUND
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_2)]),
         RequiresAllOf([DeviceExtension(ext_descriptor_indexing)]),
-    ]), */
+    ]),
 
     /// Whether the binding has a variable number of descriptors.
     ///
@@ -985,13 +1236,25 @@ pub struct DescriptorSetLayoutSupport {
 #[cfg(test)]
 mod tests {
     use crate::{
-        descriptor_set::layout::{
-            DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
-            DescriptorType,
+        acceleration_structure::{
+            AccelerationStructure, AccelerationStructureCreateInfo, AccelerationStructureType,
+        },
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        descriptor_set::{
+            allocator::StandardDescriptorSetAllocator,
+            layout::{
+                DescriptorBindingFlags, DescriptorSetLayout, DescriptorSetLayoutBinding,
+                DescriptorSetLayoutCreateFlags, DescriptorSetLayoutCreateInfo, DescriptorType,
+            },
+            PersistentDescriptorSet, WriteDescriptorSet,
         },
+        image::sampler::{Sampler, SamplerCreateInfo},
+        memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
         shader::ShaderStages,
+        Validated, Version,
     };
     use ahash::HashMap;
+    use std::sync::Arc;
 
     #[test]
     fn empty() {
@@ -1026,4 +1289,211 @@ mod tests {
                 .collect::<HashMap<_, _>>(),
         );
     }
+
+    #[test]
+    fn supports_oversized() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if !(device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_maintenance3)
+        {
+            return;
+        }
+
+        let create_info = |descriptor_count| DescriptorSetLayoutCreateInfo {
+            bindings: [(
+                0,
+                DescriptorSetLayoutBinding {
+                    descriptor_count,
+                    stages: ShaderStages::COMPUTE,
+                    ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::StorageBuffer)
+                },
+            )]
+            .into(),
+            ..Default::default()
+        };
+
+        assert!(DescriptorSetLayout::supports(&device, &create_info(1))
+            .unwrap()
+            .is_some());
+        assert!(
+            DescriptorSetLayout::supports(&device, &create_info(1 << 30))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn acceleration_structure_requires_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let result = DescriptorSetLayout::new(
+            device,
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::COMPUTE,
+                        ..DescriptorSetLayoutBinding::descriptor_type(
+                            DescriptorType::AccelerationStructure,
+                        )
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        );
+
+        assert!(matches!(result, Err(Validated::ValidationError(_))));
+    }
+
+    #[test]
+    fn acceleration_structure_write() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [khr_acceleration_structure, khr_deferred_host_operations],
+            features: [acceleration_structure, buffer_device_address],
+        );
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::COMPUTE,
+                        ..DescriptorSetLayoutBinding::descriptor_type(
+                            DescriptorType::AccelerationStructure,
+                        )
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let buffer = Buffer::new_slice::<u8>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::ACCELERATION_STRUCTURE_STORAGE
+                    | BufferUsage::SHADER_DEVICE_ADDRESS,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE,
+                ..Default::default()
+            },
+            4096,
+        )
+        .unwrap();
+        let acceleration_structure = unsafe {
+            AccelerationStructure::new(
+                device.clone(),
+                AccelerationStructureCreateInfo {
+                    ty: AccelerationStructureType::TopLevel,
+                    ..AccelerationStructureCreateInfo::new(buffer)
+                },
+            )
+        }
+        .unwrap();
+
+        let descriptor_set_allocator =
+            StandardDescriptorSetAllocator::new(device, Default::default());
+        PersistentDescriptorSet::new(
+            &descriptor_set_allocator,
+            layout,
+            [WriteDescriptorSet::acceleration_structure(
+                0,
+                acceleration_structure,
+            )],
+            [],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn update_after_bind() {
+        let (device, _) = gfx_dev_and_queue!(
+            descriptor_binding_sampled_image_update_after_bind,
+            descriptor_binding_partially_bound
+        );
+
+        let create_info = |flags, binding_flags, descriptor_type| DescriptorSetLayoutCreateInfo {
+            flags,
+            bindings: [
+                (
+                    0,
+                    DescriptorSetLayoutBinding {
+                        binding_flags,
+                        descriptor_count: 4,
+                        stages: ShaderStages::FRAGMENT,
+                        ..DescriptorSetLayoutBinding::descriptor_type(descriptor_type)
+                    },
+                ),
+                (
+                    1,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::FRAGMENT,
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::Sampler)
+                    },
+                ),
+            ]
+            .into(),
+            ..Default::default()
+        };
+        let binding_flags =
+            DescriptorBindingFlags::UPDATE_AFTER_BIND | DescriptorBindingFlags::PARTIALLY_BOUND;
+
+        // The layout must have the `UPDATE_AFTER_BIND_POOL` flag.
+        assert!(matches!(
+            DescriptorSetLayout::new(
+                device.clone(),
+                create_info(
+                    DescriptorSetLayoutCreateFlags::empty(),
+                    binding_flags,
+                    DescriptorType::Sampler,
+                ),
+            ),
+            Err(Validated::ValidationError(_))
+        ));
+
+        // Dynamic buffers can't be updated after bind.
+        assert!(matches!(
+            DescriptorSetLayout::new(
+                device.clone(),
+                create_info(
+                    DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL,
+                    binding_flags,
+                    DescriptorType::UniformBufferDynamic,
+                ),
+            ),
+            Err(Validated::ValidationError(_))
+        ));
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            create_info(
+                DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL,
+                binding_flags,
+                DescriptorType::Sampler,
+            ),
+        )
+        .unwrap();
+
+        let descriptor_set_allocator =
+            StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+        let set = PersistentDescriptorSet::new(&descriptor_set_allocator, layout, [], []).unwrap();
+        let sampler =
+            Sampler::new(device, SamplerCreateInfo::simple_repeat_linear_no_mipmap()).unwrap();
+
+        unsafe {
+            set.update_after_bind([WriteDescriptorSet::sampler_array(0, 2, [sampler.clone()])])
+                .unwrap();
+
+            // Binding 1 doesn't have the `UPDATE_AFTER_BIND` flag.
+            assert!(set
+                .update_after_bind([WriteDescriptorSet::sampler(1, sampler)])
+                .is_err());
+        }
+    }
 }
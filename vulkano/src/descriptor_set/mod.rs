@@ -96,12 +96,13 @@ use crate::{
     },
     device::{DeviceOwned, DeviceOwnedDebugWrapper},
     image::{sampler::Sampler, ImageLayout},
-    ValidationError, VulkanObject,
+    Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanObject,
 };
 use ahash::HashMap;
 use smallvec::{smallvec, SmallVec};
 use std::{
     hash::{Hash, Hasher},
+    ptr,
     sync::Arc,
 };
 
@@ -177,6 +178,13 @@ impl DescriptorSetInner {
             "the provided descriptor set layout is for push descriptors, and cannot be used to \
             build a descriptor set object",
         );
+        assert!(
+            !layout
+                .flags()
+                .intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER),
+            "the provided descriptor set layout is for descriptor buffers, and cannot be used to \
+            build a descriptor set object",
+        );
 
         let max_variable_descriptor_count = layout.variable_descriptor_count();
 
@@ -307,6 +315,132 @@ impl DescriptorSetInner {
     pub(crate) fn resources(&self) -> &DescriptorSetResources {
         &self.resources
     }
+
+    pub(crate) fn validate_clear(&self) -> Result<(), Box<ValidationError>> {
+        let device = self.layout.device();
+
+        if !device.enabled_features().null_descriptor {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "null_descriptor",
+                )])]),
+                vuids: &[
+                    "VUID-VkDescriptorBufferInfo-buffer-02998",
+                    "VUID-VkWriteDescriptorSet-descriptorType-02995",
+                    "VUID-VkWriteDescriptorSet-descriptorType-02997",
+                    "VUID-VkWriteDescriptorSetAccelerationStructureKHR-pAccelerationStructures-03580",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Writes a null descriptor to every descriptor that can be null, and resets the tracked
+    /// resources to their initial state.
+    pub(crate) unsafe fn clear_unchecked(&mut self, handle: ash::vk::DescriptorSet) {
+        enum NullInfo {
+            Image(SmallVec<[ash::vk::DescriptorImageInfo; 1]>),
+            Buffer(SmallVec<[ash::vk::DescriptorBufferInfo; 1]>),
+            BufferView(SmallVec<[ash::vk::BufferView; 1]>),
+            AccelerationStructure(SmallVec<[ash::vk::AccelerationStructureKHR; 1]>),
+        }
+
+        let layout = &self.layout;
+        let mut writes_vk: SmallVec<[_; 8]> = SmallVec::new();
+        let mut infos_vk: SmallVec<[_; 8]> = SmallVec::new();
+
+        for (&binding_num, binding) in layout.bindings() {
+            let count = if binding
+                .binding_flags
+                .intersects(DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT)
+            {
+                self.variable_descriptor_count
+            } else {
+                binding.descriptor_count
+            } as usize;
+
+            if count == 0 {
+                continue;
+            }
+
+            let info = match binding.descriptor_type {
+                DescriptorType::UniformBuffer
+                | DescriptorType::StorageBuffer
+                | DescriptorType::UniformBufferDynamic
+                | DescriptorType::StorageBufferDynamic => NullInfo::Buffer(smallvec![
+                    ash::vk::DescriptorBufferInfo {
+                        buffer: ash::vk::Buffer::null(),
+                        offset: 0,
+                        range: ash::vk::WHOLE_SIZE,
+                    };
+                    count
+                ]),
+                DescriptorType::UniformTexelBuffer | DescriptorType::StorageTexelBuffer => {
+                    NullInfo::BufferView(smallvec![ash::vk::BufferView::null(); count])
+                }
+                DescriptorType::SampledImage | DescriptorType::StorageImage => {
+                    NullInfo::Image(smallvec![Default::default(); count])
+                }
+                // Without an immutable sampler, a valid sampler would have to be written along
+                // with the null image view.
+                DescriptorType::CombinedImageSampler if !binding.immutable_samplers.is_empty() => {
+                    NullInfo::Image(smallvec![Default::default(); count])
+                }
+                DescriptorType::AccelerationStructure => NullInfo::AccelerationStructure(
+                    smallvec![ash::vk::AccelerationStructureKHR::null(); count],
+                ),
+                // Samplers and input attachments can't be null, and inline uniform blocks hold
+                // plain data.
+                DescriptorType::Sampler
+                | DescriptorType::CombinedImageSampler
+                | DescriptorType::InputAttachment
+                | DescriptorType::InlineUniformBlock => continue,
+            };
+
+            writes_vk.push(ash::vk::WriteDescriptorSet {
+                dst_set: handle,
+                dst_binding: binding_num,
+                dst_array_element: 0,
+                descriptor_count: count as u32,
+                descriptor_type: binding.descriptor_type.into(),
+                ..Default::default()
+            });
+            infos_vk.push((
+                info,
+                ash::vk::WriteDescriptorSetAccelerationStructureKHR::default(),
+            ));
+        }
+
+        if !writes_vk.is_empty() {
+            for (write_vk, (info, acceleration_structures)) in
+                writes_vk.iter_mut().zip(infos_vk.iter_mut())
+            {
+                match info {
+                    NullInfo::Image(info) => write_vk.p_image_info = info.as_ptr(),
+                    NullInfo::Buffer(info) => write_vk.p_buffer_info = info.as_ptr(),
+                    NullInfo::BufferView(info) => write_vk.p_texel_buffer_view = info.as_ptr(),
+                    NullInfo::AccelerationStructure(info) => {
+                        acceleration_structures.acceleration_structure_count = info.len() as u32;
+                        acceleration_structures.p_acceleration_structures = info.as_ptr();
+                        write_vk.p_next = acceleration_structures as *const _ as _;
+                    }
+                }
+            }
+
+            let fns = layout.device().fns();
+            (fns.v1_0.update_descriptor_sets)(
+                layout.device().handle(),
+                writes_vk.len() as u32,
+                writes_vk.as_ptr(),
+                0,
+                ptr::null(),
+            );
+        }
+
+        self.resources = DescriptorSetResources::new(layout, self.variable_descriptor_count);
+    }
 }
 
 /// The resources that are bound to a descriptor set.
@@ -9,7 +9,8 @@
 
 //! A simple, immutable descriptor set that is expected to be long-lived.
 //!
-//! Creating a persistent descriptor set allocates from a pool, and can't be modified once created.
+//! Creating a persistent descriptor set allocates from a pool, and can't be modified once created,
+//! except for bindings with the `UPDATE_AFTER_BIND` flag.
 //! You are therefore encouraged to create them at initialization and not the during
 //! performance-critical paths.
 //!
@@ -25,14 +26,15 @@ use super::CopyDescriptorSet;
 use crate::{
     descriptor_set::{
         allocator::{DescriptorSetAlloc, DescriptorSetAllocator, StandardDescriptorSetAlloc},
-        layout::DescriptorSetLayoutCreateFlags,
+        layout::{DescriptorBindingFlags, DescriptorSetLayoutCreateFlags},
         update::WriteDescriptorSet,
         DescriptorSet, DescriptorSetInner, DescriptorSetLayout, DescriptorSetResources,
         UnsafeDescriptorSet,
     },
     device::{Device, DeviceOwned},
-    Validated, VulkanError, VulkanObject,
+    Validated, ValidationError, VulkanError, VulkanObject,
 };
+use smallvec::SmallVec;
 use std::{
     hash::{Hash, Hasher},
     sync::Arc,
@@ -85,6 +87,13 @@ impl PersistentDescriptorSet {
             "the provided descriptor set layout is for push descriptors, and cannot be used to \
             build a descriptor set object",
         );
+        assert!(
+            !layout
+                .flags()
+                .intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER),
+            "the provided descriptor set layout is for descriptor buffers, and cannot be used to \
+            build a descriptor set object",
+        );
 
         let max_count = layout.variable_descriptor_count();
 
@@ -109,6 +118,95 @@ impl PersistentDescriptorSet {
     }
 }
 
+impl<P> PersistentDescriptorSet<P>
+where
+    P: DescriptorSetAlloc,
+{
+    /// Writes to bindings of the descriptor set that have the
+    /// [`DescriptorBindingFlags::UPDATE_AFTER_BIND`] flag, while the descriptor set may already
+    /// be bound to a command buffer.
+    ///
+    /// The written resources are not recorded in the descriptor set's [`resources`], so command
+    /// buffers that use the descriptor set will neither keep them alive nor synchronize access to
+    /// them.
+    ///
+    /// # Safety
+    ///
+    /// - The resources written must be kept alive for as long as the descriptor set may be used
+    ///   by a command buffer that accesses them.
+    /// - The resources must be in the state expected by the shader (for images, the image layout
+    ///   given in the write) when they are accessed, and any access to them must be synchronized
+    ///   manually.
+    /// - The descriptors that are written must not be in use by a command buffer that is pending
+    ///   execution. If the binding has the [`DescriptorBindingFlags::UPDATE_UNUSED_WHILE_PENDING`]
+    ///   flag, this only applies to descriptors that are dynamically used by that command buffer.
+    /// - The descriptor set must not be updated from multiple threads at the same time.
+    ///
+    /// [`resources`]: DescriptorSet::resources
+    pub unsafe fn update_after_bind(
+        &self,
+        descriptor_writes: impl IntoIterator<Item = WriteDescriptorSet>,
+    ) -> Result<(), Box<ValidationError>> {
+        let layout = self.inner.layout();
+        let descriptor_writes: SmallVec<[_; 8]> = descriptor_writes.into_iter().collect();
+
+        for (index, write) in descriptor_writes.iter().enumerate() {
+            write
+                .validate(layout, self.inner.variable_descriptor_count)
+                .map_err(|err| err.add_context(format!("descriptor_writes[{}]", index)))?;
+
+            let layout_binding = &layout.bindings()[&write.binding()];
+
+            if !layout_binding
+                .binding_flags
+                .intersects(DescriptorBindingFlags::UPDATE_AFTER_BIND)
+            {
+                return Err(Box::new(ValidationError {
+                    context: format!("descriptor_writes[{}].binding", index).into(),
+                    problem: "refers to a binding whose `binding_flags` does not contain \
+                        `DescriptorBindingFlags::UPDATE_AFTER_BIND`"
+                        .into(),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        self.alloc
+            .inner()
+            .update_inner(layout, &descriptor_writes, []);
+
+        Ok(())
+    }
+
+    /// Clears the descriptor set, so that it no longer refers to any resource.
+    ///
+    /// A null descriptor is written to every descriptor that can be null: buffers, buffer views,
+    /// sampled and storage images, combined image samplers with immutable samplers, and
+    /// acceleration structures. The resources of the descriptor set are reset to the state of a
+    /// newly allocated set, so the previously written resources are no longer kept alive, and
+    /// must be written again before a pipeline can access them.
+    ///
+    /// Because this takes `&mut self`, the descriptor set can only be cleared through
+    /// [`Arc::get_mut`], when no command buffer holds a reference to it.
+    ///
+    /// The [`null_descriptor`] feature must be enabled on the device.
+    ///
+    /// [`null_descriptor`]: crate::device::Features::null_descriptor
+    pub fn clear(&mut self) -> Result<(), Box<ValidationError>> {
+        self.inner.validate_clear()?;
+
+        unsafe { self.clear_unchecked() };
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn clear_unchecked(&mut self) {
+        let handle = self.alloc.inner().handle();
+        self.inner.clear_unchecked(handle);
+    }
+}
+
 unsafe impl<P> DescriptorSet for PersistentDescriptorSet<P>
 where
     P: DescriptorSetAlloc,
@@ -158,3 +256,110 @@ where
         self.inner().hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        descriptor_set::{
+            allocator::StandardDescriptorSetAllocator,
+            layout::{
+                DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
+                DescriptorType,
+            },
+            DescriptorBindingResources, DescriptorSet, PersistentDescriptorSet, WriteDescriptorSet,
+        },
+        memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+        shader::ShaderStages,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn clear() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_robustness2],
+            features: [null_descriptor],
+        );
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::COMPUTE,
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::StorageBuffer)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let buffer = Buffer::new_slice::<u32>(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE,
+                ..Default::default()
+            },
+            4,
+        )
+        .unwrap();
+
+        let descriptor_set_allocator =
+            StandardDescriptorSetAllocator::new(device, Default::default());
+        let mut set = PersistentDescriptorSet::new(
+            &descriptor_set_allocator,
+            layout,
+            [WriteDescriptorSet::buffer(0, buffer.clone())],
+            [],
+        )
+        .unwrap();
+
+        Arc::get_mut(&mut set).unwrap().clear().unwrap();
+
+        match set.resources().binding(0).unwrap() {
+            DescriptorBindingResources::Buffer(elements) => {
+                assert!(elements.iter().all(Option::is_none))
+            }
+            _ => panic!(),
+        }
+
+        // The buffer is no longer referenced by the descriptor set.
+        assert_eq!(Arc::strong_count(buffer.buffer()), 1);
+    }
+
+    #[test]
+    fn clear_requires_null_descriptor() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::COMPUTE,
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::StorageBuffer)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let descriptor_set_allocator =
+            StandardDescriptorSetAllocator::new(device, Default::default());
+        let mut set =
+            PersistentDescriptorSet::new(&descriptor_set_allocator, layout, [], []).unwrap();
+
+        assert!(Arc::get_mut(&mut set).unwrap().clear().is_err());
+    }
+}
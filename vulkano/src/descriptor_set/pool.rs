@@ -200,6 +200,9 @@ impl DescriptorPool {
     ///
     /// - The total descriptors of the layouts must fit in the pool.
     /// - The total number of descriptor sets allocated from the pool must not overflow the pool.
+    /// - If a layout has the [`DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL`] flag,
+    ///   the pool must have been created with the [`DescriptorPoolCreateFlags::UPDATE_AFTER_BIND`]
+    ///   flag.
     /// - You must ensure that the allocated descriptor sets are no longer in use when the pool
     ///   is destroyed, as destroying the pool is equivalent to freeing all the sets.
     pub unsafe fn allocate_descriptor_sets<'a>(
@@ -211,10 +214,19 @@ impl DescriptorPool {
                 .into_iter()
                 .map(|info| {
                     assert_eq!(self.device.handle(), info.layout.device().handle(),);
-                    debug_assert!(!info
-                        .layout
-                        .flags()
-                        .intersects(DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR));
+                    debug_assert!(!info.layout.flags().intersects(
+                        DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR
+                            | DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER
+                    ));
+                    debug_assert!(
+                        !info
+                            .layout
+                            .flags()
+                            .intersects(DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL)
+                            || self
+                                .flags
+                                .intersects(DescriptorPoolCreateFlags::UPDATE_AFTER_BIND)
+                    );
                     debug_assert!(
                         info.variable_descriptor_count <= info.layout.variable_descriptor_count()
                     );
@@ -487,12 +499,14 @@ vulkan_bitflags! {
     /// destroy the whole pool at once.
     FREE_DESCRIPTOR_SET = FREE_DESCRIPTOR_SET,
 
-    /* TODO: enable
-    // TODO: document
-    UPDATE_AFTER_BIND = UPDATE_AFTER_BIND {
-        api_version: V1_2,
-        device_extensions: [ext_descriptor_indexing],
-    }, */
+    /// Descriptor sets with a layout that has the
+    /// [`DescriptorSetLayoutCreateFlags::UPDATE_AFTER_BIND_POOL`] flag can be allocated from the
+    /// pool.
+    UPDATE_AFTER_BIND = UPDATE_AFTER_BIND
+    RequiresOneOf([
+        RequiresAllOf([APIVersion(V1_2)]),
+        RequiresAllOf([DeviceExtension(ext_descriptor_indexing)]),
+    ]),
 
     /* TODO: enable
     // TODO: document
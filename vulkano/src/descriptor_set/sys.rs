@@ -54,11 +54,23 @@ impl UnsafeDescriptorSet {
     /// - Updating a descriptor set obeys synchronization rules that aren't checked here. Once a
     ///   command buffer contains a pointer/reference to a descriptor set, it is illegal to write
     ///   to it.
+    #[inline]
     pub unsafe fn update<'a>(
         &mut self,
         layout: &DescriptorSetLayout,
         descriptor_writes: impl IntoIterator<Item = &'a WriteDescriptorSet>,
         descriptor_copies: impl IntoIterator<Item = &'a CopyDescriptorSet>,
+    ) {
+        self.update_inner(layout, descriptor_writes, descriptor_copies)
+    }
+
+    // Same as `update`, but through a shared reference, for descriptor sets whose bindings can be
+    // updated after the set has been bound.
+    pub(crate) unsafe fn update_inner<'a>(
+        &self,
+        layout: &DescriptorSetLayout,
+        descriptor_writes: impl IntoIterator<Item = &'a WriteDescriptorSet>,
+        descriptor_copies: impl IntoIterator<Item = &'a CopyDescriptorSet>,
     ) {
         struct PerDescriptorWrite {
             write_info: DescriptorWriteInfo,
@@ -105,7 +105,7 @@ use self::physical::PhysicalDevice;
 pub(crate) use self::properties::PropertiesFfi;
 pub use self::{
     properties::Properties,
-    queue::{Queue, QueueFamilyProperties, QueueFlags, QueueGuard},
+    queue::{Queue, QueueFamilyProperties, QueueFlags, QueueGuard, SubmitDiagnostics},
 };
 pub use crate::fns::DeviceFunctions;
 use crate::{
@@ -117,16 +117,16 @@ use crate::{
         DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo, DescriptorSetLayoutSupport,
     },
     instance::{Instance, InstanceOwned, InstanceOwnedDebugWrapper},
-    macros::{impl_id_counter, vulkan_bitflags},
+    macros::{impl_id_counter, vulkan_bitflags, vulkan_enum},
     memory::ExternalMemoryHandleType,
-    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version, VulkanError,
-    VulkanObject,
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version,
+    VulkanError, VulkanObject,
 };
 use ash::vk::Handle;
 use parking_lot::Mutex;
 use smallvec::{smallvec, SmallVec};
 use std::{
-    ffi::CString,
+    ffi::{CStr, CString},
     fmt::{Debug, Error as FmtError, Formatter},
     fs::File,
     mem::MaybeUninit,
@@ -167,6 +167,9 @@ pub struct Device {
     // This is required for validation in `memory::device_memory`, the count must only be modified
     // in that module.
     pub(crate) allocation_count: AtomicU32,
+    // This is required for validation in `image::sampler`, the count must only be modified in
+    // that module.
+    pub(crate) custom_border_color_sampler_count: AtomicU32,
     fence_pool: Mutex<Vec<ash::vk::Fence>>,
     semaphore_pool: Mutex<Vec<ash::vk::Semaphore>>,
     event_pool: Mutex<Vec<ash::vk::Event>>,
@@ -462,6 +465,7 @@ impl Device {
             active_queue_family_indices,
 
             allocation_count: AtomicU32::new(0),
+            custom_border_color_sampler_count: AtomicU32::new(0),
             fence_pool: Mutex::new(Vec::new()),
             semaphore_pool: Mutex::new(Vec::new()),
             event_pool: Mutex::new(Vec::new()),
@@ -793,6 +797,112 @@ impl Device {
         compatibility_vk == ash::vk::AccelerationStructureCompatibilityKHR::COMPATIBLE
     }
 
+    /// Retrieves information about the fault that caused the device to be lost.
+    ///
+    /// This can be called after an operation has returned [`VulkanError::DeviceLost`]. If the
+    /// device has not been lost, the returned information is empty.
+    ///
+    /// The [`device_fault`](Features::device_fault) feature must be enabled on the device.
+    #[inline]
+    pub fn fault_info(&self) -> Result<DeviceFaultInfo, Validated<VulkanError>> {
+        self.validate_fault_info()?;
+
+        unsafe { Ok(self.fault_info_unchecked()?) }
+    }
+
+    fn validate_fault_info(&self) -> Result<(), Box<ValidationError>> {
+        if !self.enabled_extensions().ext_device_fault {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
+                    "ext_device_fault",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        if !self.enabled_features().device_fault {
+            return Err(Box::new(ValidationError {
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "device_fault",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn fault_info_unchecked(&self) -> Result<DeviceFaultInfo, VulkanError> {
+        let fns = self.fns();
+
+        let (info_vk, address_infos_vk, vendor_infos_vk) = loop {
+            let mut counts_vk = ash::vk::DeviceFaultCountsEXT::default();
+            (fns.ext_device_fault.get_device_fault_info_ext)(
+                self.handle,
+                &mut counts_vk,
+                ptr::null_mut(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+
+            // The vendor binary data is not retrieved.
+            counts_vk.vendor_binary_size = 0;
+
+            let mut address_infos_vk: Vec<ash::vk::DeviceFaultAddressInfoEXT> =
+                Vec::with_capacity(counts_vk.address_info_count as usize);
+            let mut vendor_infos_vk: Vec<ash::vk::DeviceFaultVendorInfoEXT> =
+                Vec::with_capacity(counts_vk.vendor_info_count as usize);
+            let mut info_vk = ash::vk::DeviceFaultInfoEXT {
+                p_address_infos: address_infos_vk.as_mut_ptr(),
+                p_vendor_infos: vendor_infos_vk.as_mut_ptr(),
+                ..Default::default()
+            };
+
+            let result = (fns.ext_device_fault.get_device_fault_info_ext)(
+                self.handle,
+                &mut counts_vk,
+                &mut info_vk,
+            );
+
+            match result {
+                ash::vk::Result::SUCCESS => {
+                    address_infos_vk.set_len(counts_vk.address_info_count as usize);
+                    vendor_infos_vk.set_len(counts_vk.vendor_info_count as usize);
+                    break (info_vk, address_infos_vk, vendor_infos_vk);
+                }
+                ash::vk::Result::INCOMPLETE => (),
+                err => return Err(VulkanError::from(err)),
+            }
+        };
+
+        Ok(DeviceFaultInfo {
+            description: CStr::from_ptr(info_vk.description.as_ptr())
+                .to_string_lossy()
+                .into_owned(),
+            address_infos: address_infos_vk
+                .into_iter()
+                .filter_map(|address_info_vk| {
+                    Some(DeviceFaultAddressInfo {
+                        address_type: address_info_vk.address_type.try_into().ok()?,
+                        reported_address: address_info_vk.reported_address,
+                        address_precision: address_info_vk.address_precision,
+                    })
+                })
+                .collect(),
+            vendor_infos: vendor_infos_vk
+                .into_iter()
+                .map(|vendor_info_vk| DeviceFaultVendorInfo {
+                    description: CStr::from_ptr(vendor_info_vk.description.as_ptr())
+                        .to_string_lossy()
+                        .into_owned(),
+                    vendor_fault_code: vendor_info_vk.vendor_fault_code,
+                    vendor_fault_data: vendor_info_vk.vendor_fault_data,
+                })
+                .collect(),
+        })
+    }
+
     /// Returns whether a descriptor set layout with the given `create_info` could be created
     /// on the device, and additional supported properties where relevant. `Some` is returned if
     /// the descriptor set layout is supported, `None` if it is not.
@@ -1094,6 +1204,7 @@ impl Debug for Device {
             active_queue_family_indices,
 
             allocation_count,
+            custom_border_color_sampler_count: _,
             fence_pool: _,
             semaphore_pool: _,
             event_pool: _,
@@ -1725,6 +1836,78 @@ impl<T> Deref for DeviceOwnedDebugWrapper<T> {
     }
 }
 
+/// Information about the fault that caused a device to be lost.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DeviceFaultInfo {
+    /// A human-readable description of the fault.
+    pub description: String,
+
+    /// The GPU virtual addresses that are related to the fault.
+    pub address_infos: Vec<DeviceFaultAddressInfo>,
+
+    /// Vendor-specific information about the fault.
+    pub vendor_infos: Vec<DeviceFaultVendorInfo>,
+}
+
+/// A GPU virtual address that is related to a device fault.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct DeviceFaultAddressInfo {
+    /// The kind of operation that was performed at the address.
+    pub address_type: DeviceFaultAddressType,
+
+    /// The address that was reported by the device.
+    pub reported_address: DeviceSize,
+
+    /// The precision of `reported_address`. The faulting address lies in a range of this size,
+    /// aligned to this size, that contains `reported_address`.
+    pub address_precision: DeviceSize,
+}
+
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The kind of operation that a [`DeviceFaultAddressInfo`] refers to.
+    DeviceFaultAddressType = DeviceFaultAddressTypeEXT(i32);
+
+    /// No operation is associated with the address.
+    None = NONE,
+
+    /// An invalid read from the address.
+    ReadInvalid = READ_INVALID,
+
+    /// An invalid write to the address.
+    WriteInvalid = WRITE_INVALID,
+
+    /// An attempt to execute an instruction at an invalid address.
+    ExecuteInvalid = EXECUTE_INVALID,
+
+    /// The address is the instruction pointer at the time of the fault, but it is not known
+    /// whether the instruction caused the fault.
+    InstructionPointerUnknown = INSTRUCTION_POINTER_UNKNOWN,
+
+    /// The address is the instruction pointer of an invalid instruction.
+    InstructionPointerInvalid = INSTRUCTION_POINTER_INVALID,
+
+    /// The address is the instruction pointer of the instruction that caused the fault.
+    InstructionPointerFault = INSTRUCTION_POINTER_FAULT,
+}
+
+/// Vendor-specific information about a device fault.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DeviceFaultVendorInfo {
+    /// A human-readable description of the fault.
+    pub description: String,
+
+    /// A vendor-specific fault code.
+    pub vendor_fault_code: u64,
+
+    /// Vendor-specific data that is associated with the fault.
+    pub vendor_fault_data: u64,
+}
+
 /// The properties of a Unix file descriptor when it is imported.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -1735,7 +1918,12 @@ pub struct MemoryFdProperties {
 
 #[cfg(test)]
 mod tests {
-    use crate::device::{Device, DeviceCreateInfo, DeviceExtensions, Features, QueueCreateInfo};
+    use crate::{
+        device::{Device, DeviceCreateInfo, DeviceExtensions, Features, QueueCreateInfo},
+        format::FormatFeatures,
+        image::ImageAspects,
+        Validated,
+    };
     use std::{ffi::CString, sync::Arc};
 
     #[test]
@@ -1883,4 +2071,55 @@ mod tests {
             panic!();
         }
     }
+
+    #[test]
+    fn best_depth_stencil_format() {
+        let (device, _) = gfx_dev_and_queue!();
+        let physical_device = device.physical_device();
+
+        // `D16_UNORM` is required to support depth-stencil attachments.
+        let format = physical_device.best_depth_stencil_format(false).unwrap();
+        assert_eq!(format.aspects(), ImageAspects::DEPTH);
+        assert!(physical_device
+            .format_properties(format)
+            .unwrap()
+            .optimal_tiling_features
+            .intersects(FormatFeatures::DEPTH_STENCIL_ATTACHMENT));
+
+        // At least one of `D24_UNORM_S8_UINT` and `D32_SFLOAT_S8_UINT` is required to support
+        // depth-stencil attachments.
+        let format = physical_device.best_depth_stencil_format(true).unwrap();
+        assert_eq!(
+            format.aspects(),
+            ImageAspects::DEPTH | ImageAspects::STENCIL
+        );
+        assert!(physical_device
+            .format_properties(format)
+            .unwrap()
+            .optimal_tiling_features
+            .intersects(FormatFeatures::DEPTH_STENCIL_ATTACHMENT));
+    }
+
+    #[test]
+    fn fault_info() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // The `device_fault` feature must be enabled.
+        match device.fault_info() {
+            Err(Validated::ValidationError(err)) => {
+                assert!(!err.requires_one_of.0.is_empty());
+            }
+            _ => panic!(),
+        }
+
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_device_fault],
+            features: [device_fault],
+        );
+
+        // The device has not been lost, so there is no fault to report.
+        let fault_info = device.fault_info().unwrap();
+        assert!(fault_info.address_infos.is_empty());
+        assert!(fault_info.vendor_infos.is_empty());
+    }
 }
@@ -13,7 +13,7 @@ use crate::{
     cache::{OnceCache, WeakArcOnceCache},
     device::{properties::Properties, DeviceExtensions, Features, FeaturesFfi, PropertiesFfi},
     display::{Display, DisplayPlaneProperties, DisplayPlanePropertiesRaw, DisplayProperties},
-    format::{DrmFormatModifierProperties, Format, FormatProperties},
+    format::{DrmFormatModifierProperties, Format, FormatFeatures, FormatProperties},
     image::{
         ImageDrmFormatModifierInfo, ImageFormatInfo, ImageFormatProperties, ImageUsage,
         SparseImageFormatInfo, SparseImageFormatProperties,
@@ -1240,6 +1240,39 @@ impl PhysicalDevice {
             })
     }
 
+    /// Returns the depth or depth-stencil format with the highest depth precision that supports
+    /// [`FormatFeatures::DEPTH_STENCIL_ATTACHMENT`] with optimal tiling on this physical device.
+    ///
+    /// If `need_stencil` is `true`, only formats that also have a stencil component are
+    /// considered, otherwise only formats that have only a depth component are considered.
+    ///
+    /// Returns [`VulkanError::FormatNotSupported`] if none of the formats qualify.
+    pub fn best_depth_stencil_format(&self, need_stencil: bool) -> Result<Format, VulkanError> {
+        let candidates: &[Format] = if need_stencil {
+            &[
+                Format::D32_SFLOAT_S8_UINT,
+                Format::D24_UNORM_S8_UINT,
+                Format::D16_UNORM_S8_UINT,
+            ]
+        } else {
+            &[
+                Format::D32_SFLOAT,
+                Format::X8_D24_UNORM_PACK32,
+                Format::D16_UNORM,
+            ]
+        };
+
+        candidates
+            .iter()
+            .copied()
+            .find(|&format| unsafe {
+                self.format_properties_unchecked(format)
+                    .optimal_tiling_features
+                    .intersects(FormatFeatures::DEPTH_STENCIL_ATTACHMENT)
+            })
+            .ok_or(VulkanError::FormatNotSupported)
+    }
+
     /// Retrieves the properties of a format when used by this physical device.
     ///
     /// The results of this function are cached, so that future calls with the same arguments
@@ -39,6 +39,7 @@ use std::{
     mem::{take, MaybeUninit},
     ptr,
     sync::{atomic::Ordering, Arc},
+    time::{Duration, Instant},
 };
 
 /// Represents a queue where commands can be submitted.
@@ -129,6 +130,27 @@ impl Queue {
             state: self.state.lock(),
         })
     }
+
+    /// Locks the queue and waits until all work on it has finished, then releases ownership of
+    /// all resources that were in use by the queue.
+    ///
+    /// Unlike [`Device::wait_idle`], this only waits for this queue, so work on the other queues
+    /// of the device keeps running. This is a shortcut for calling [`QueueGuard::wait_idle`]
+    /// through [`with`](Self::with).
+    #[inline]
+    pub fn wait_idle(&self) -> Result<(), VulkanError> {
+        self.state.lock().wait_idle(&self.device, self.handle)
+    }
+
+    /// Returns diagnostics about the most recent successful command buffer submission to the
+    /// queue, or `None` if nothing was submitted yet.
+    ///
+    /// This includes submissions made by flushing a [`GpuFuture`], so it can be used to measure
+    /// how much CPU time is spent submitting each frame.
+    #[inline]
+    pub fn last_submit_diagnostics(&self) -> Option<SubmitDiagnostics> {
+        self.state.lock().last_submit
+    }
 }
 
 impl Drop for Queue {
@@ -197,8 +219,81 @@ impl<'a> QueueGuard<'a> {
         self.state.wait_idle(&self.queue.device, self.queue.handle)
     }
 
+    /// Binds or unbinds memory to ranges of sparse buffers and images.
+    ///
+    /// The buffers and images must have been created with the `SPARSE_BINDING` create flag, and
+    /// turned into a [`Buffer`] or [`Image`] with `assume_bound`. This queue must belong to a
+    /// queue family that supports [`QueueFlags::SPARSE_BINDING`].
+    ///
+    /// If `fence` is provided, it is signaled once all bind operations have completed.
+    ///
+    /// # Safety
+    ///
+    /// - The semaphores in `wait_semaphores` must be signaled, or have a signal operation
+    ///   pending, and must not have any other wait operations pending.
+    /// - The semaphores in `signal_semaphores` and `fence` must be unsignaled, and must not have
+    ///   any other signal operations pending.
+    /// - The buffer and image ranges that are rebound must not be in use by the device.
+    /// - Memory that is unbound or replaced must not be accessed through the resource afterwards,
+    ///   until memory is bound to that range again.
+    /// - The [`DeviceMemory`] objects that are bound must outlive the binding: they must not be
+    ///   dropped until they are unbound or the resource is destroyed, and until every use of the
+    ///   resource by the device has completed. Vulkano only keeps them alive until the bind
+    ///   operation itself has completed.
+    ///
+    /// [`Buffer`]: crate::buffer::Buffer
+    /// [`DeviceMemory`]: crate::memory::DeviceMemory
+    /// [`Image`]: crate::image::Image
+    #[inline]
+    pub unsafe fn bind_sparse(
+        &mut self,
+        bind_infos: impl IntoIterator<Item = BindSparseInfo>,
+        fence: Option<Arc<Fence>>,
+    ) -> Result<(), Validated<VulkanError>> {
+        let bind_infos: SmallVec<[_; 4]> = bind_infos.into_iter().collect();
+        self.validate_bind_sparse(&bind_infos, fence.as_deref())?;
+
+        Ok(self.bind_sparse_unchecked(bind_infos, fence)?)
+    }
+
+    fn validate_bind_sparse(
+        &self,
+        bind_infos: &[BindSparseInfo],
+        fence: Option<&Fence>,
+    ) -> Result<(), Box<ValidationError>> {
+        let device = self.queue.device();
+        let queue_family_properties = &device.physical_device().queue_family_properties()
+            [self.queue.queue_family_index as usize];
+
+        if !queue_family_properties
+            .queue_flags
+            .intersects(QueueFlags::SPARSE_BINDING)
+        {
+            return Err(Box::new(ValidationError {
+                problem: "the queue family of the queue does not support \
+                    sparse binding operations"
+                    .into(),
+                vuids: &["VUID-vkQueueBindSparse-queuetype"],
+                ..Default::default()
+            }));
+        }
+
+        if let Some(fence) = fence {
+            // VUID-vkQueueBindSparse-commonparent
+            assert_eq!(device, fence.device());
+        }
+
+        for (index, bind_info) in bind_infos.iter().enumerate() {
+            bind_info
+                .validate(device)
+                .map_err(|err| err.add_context(format!("bind_infos[{}]", index)))?;
+        }
+
+        Ok(())
+    }
+
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
-    pub(crate) unsafe fn bind_sparse_unchecked(
+    pub unsafe fn bind_sparse_unchecked(
         &mut self,
         bind_infos: impl IntoIterator<Item = BindSparseInfo>,
         fence: Option<Arc<Fence>>,
@@ -877,7 +972,9 @@ impl<'a> QueueGuard<'a> {
         fence: Option<(&Arc<Fence>, MutexGuard<'_, FenceState>)>,
         states: &mut States<'_>,
     ) -> Result<(), VulkanError> {
-        if self.queue.device.enabled_features().synchronization2 {
+        let start = Instant::now();
+
+        let result = if self.queue.device.enabled_features().synchronization2 {
             struct PerSubmitInfo {
                 wait_semaphore_infos_vk: SmallVec<[ash::vk::SemaphoreSubmitInfo; 4]>,
                 command_buffer_infos_vk: SmallVec<[ash::vk::CommandBufferSubmitInfo; 4]>,
@@ -1004,8 +1101,6 @@ impl<'a> QueueGuard<'a> {
                         .map_or_else(Default::default, |(fence, _)| fence.handle()),
                 )
             }
-            .result()
-            .map_err(VulkanError::from)?;
         } else {
             struct PerSubmitInfo {
                 wait_semaphores_vk: SmallVec<[ash::vk::Semaphore; 4]>,
@@ -1106,9 +1201,18 @@ impl<'a> QueueGuard<'a> {
                     .as_ref()
                     .map_or_else(Default::default, |(fence, _)| fence.handle()),
             )
-            .result()
-            .map_err(VulkanError::from)?;
-        }
+        };
+
+        let duration = start.elapsed();
+        result.result().map_err(VulkanError::from)?;
+
+        let mut diagnostics = SubmitDiagnostics {
+            duration,
+            submit_info_count: submit_infos.len() as u32,
+            command_buffer_count: 0,
+            wait_semaphore_count: 0,
+            signal_semaphore_count: 0,
+        };
 
         for submit_info in submit_infos {
             let SubmitInfo {
@@ -1118,6 +1222,10 @@ impl<'a> QueueGuard<'a> {
                 _ne: _,
             } = submit_info;
 
+            diagnostics.command_buffer_count += command_buffers.len() as u32;
+            diagnostics.wait_semaphore_count += wait_semaphores.len() as u32;
+            diagnostics.signal_semaphore_count += signal_semaphores.len() as u32;
+
             for semaphore_submit_info in wait_semaphores {
                 let state = states
                     .semaphores
@@ -1182,6 +1290,7 @@ impl<'a> QueueGuard<'a> {
         self.state
             .operations
             .push_back((submit_infos.clone().into(), fence));
+        self.state.last_submit = Some(diagnostics);
 
         Ok(())
     }
@@ -1355,6 +1464,7 @@ impl<'a> QueueGuard<'a> {
 #[derive(Debug, Default)]
 struct QueueState {
     operations: VecDeque<(QueueOperation, Option<Arc<Fence>>)>,
+    last_submit: Option<SubmitDiagnostics>,
 }
 
 impl QueueState {
@@ -1653,6 +1763,30 @@ impl<'a> States<'a> {
     }
 }
 
+/// Diagnostics about a command buffer submission to a queue.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SubmitDiagnostics {
+    /// The CPU time that the submission took, including the call to `vkQueueSubmit` or
+    /// `vkQueueSubmit2`.
+    ///
+    /// This does not include the validation done before submitting, nor the time the device
+    /// spends executing the command buffers.
+    pub duration: Duration,
+
+    /// The number of `SubmitInfo`s in the submission.
+    pub submit_info_count: u32,
+
+    /// The total number of command buffers submitted.
+    pub command_buffer_count: u32,
+
+    /// The total number of semaphores waited on.
+    pub wait_semaphore_count: u32,
+
+    /// The total number of semaphores signaled.
+    pub signal_semaphore_count: u32,
+}
+
 /// Properties of a queue family in a physical device.
 #[derive(Clone, Debug)]
 #[non_exhaustive]
@@ -1737,7 +1871,16 @@ vulkan_bitflags! {
 
 #[cfg(test)]
 mod tests {
-    use crate::sync::fence::Fence;
+    use crate::{
+        device::QueueFlags,
+        format::Format,
+        image::{
+            sys::RawImage, ImageAspects, ImageCreateFlags, ImageCreateInfo, ImageMemory, ImageUsage,
+        },
+        memory::{BindSparseInfo, DeviceMemory, MemoryAllocateInfo, SparseImageMemoryBind},
+        sync::fence::Fence,
+        Validated,
+    };
     use std::{sync::Arc, time::Duration};
 
     #[test]
@@ -1765,4 +1908,133 @@ mod tests {
             assert!(fence.is_signaled().unwrap());
         }
     }
+
+    #[test]
+    fn wait_idle() {
+        unsafe {
+            let (device, queue) = gfx_dev_and_queue!();
+
+            let fence = Arc::new(Fence::new(device, Default::default()).unwrap());
+
+            queue
+                .with(|mut q| q.submit_unchecked([Default::default()], Some(fence.clone())))
+                .unwrap();
+
+            queue.wait_idle().unwrap();
+            assert!(fence.is_signaled().unwrap());
+        }
+    }
+
+    #[test]
+    fn last_submit_diagnostics() {
+        unsafe {
+            let (_device, queue) = gfx_dev_and_queue!();
+            assert!(queue.last_submit_diagnostics().is_none());
+
+            queue
+                .with(|mut q| q.submit_unchecked([Default::default(), Default::default()], None))
+                .unwrap();
+
+            let diagnostics = queue.last_submit_diagnostics().unwrap();
+            assert_eq!(diagnostics.submit_info_count, 2);
+            assert_eq!(diagnostics.command_buffer_count, 0);
+            assert_eq!(diagnostics.wait_semaphore_count, 0);
+            assert_eq!(diagnostics.signal_semaphore_count, 0);
+        }
+    }
+
+    #[test]
+    fn bind_sparse() {
+        unsafe {
+            let (device, queue) = gfx_dev_and_queue!(sparse_binding, sparse_residency_image2_d);
+
+            if !device.physical_device().queue_family_properties()
+                [queue.queue_family_index() as usize]
+                .queue_flags
+                .intersects(QueueFlags::SPARSE_BINDING)
+            {
+                return;
+            }
+
+            let image = match RawImage::new(
+                device.clone(),
+                ImageCreateInfo {
+                    flags: ImageCreateFlags::SPARSE_BINDING | ImageCreateFlags::SPARSE_RESIDENCY,
+                    format: Format::R8G8B8A8_UNORM,
+                    extent: [512, 512, 1],
+                    usage: ImageUsage::SAMPLED,
+                    ..Default::default()
+                },
+            ) {
+                Ok(image) => Arc::new(image.assume_bound()),
+                // The format may not support sparse residency.
+                Err(_) => return,
+            };
+
+            let granularity = match image.memory() {
+                ImageMemory::Sparse(requirements) => {
+                    requirements
+                        .iter()
+                        .find(|r| r.format_properties.aspects.intersects(ImageAspects::COLOR))
+                        .unwrap()
+                        .format_properties
+                        .image_granularity
+                }
+                _ => unreachable!(),
+            };
+            let memory_requirements = &image.memory_requirements()[0];
+            let memory = Arc::new(
+                DeviceMemory::allocate(
+                    device.clone(),
+                    MemoryAllocateInfo {
+                        allocation_size: memory_requirements.layout.alignment().as_devicesize(),
+                        memory_type_index: memory_requirements.memory_type_bits.trailing_zeros(),
+                        ..Default::default()
+                    },
+                )
+                .unwrap(),
+            );
+
+            let bind = SparseImageMemoryBind {
+                aspects: ImageAspects::COLOR,
+                extent: granularity,
+                memory: Some((memory.clone(), 0)),
+                ..Default::default()
+            };
+
+            // An empty extent is not allowed.
+            assert!(matches!(
+                queue.with(|mut q| q.bind_sparse(
+                    [BindSparseInfo {
+                        image_binds: vec![(
+                            image.clone(),
+                            vec![SparseImageMemoryBind {
+                                extent: [0, 0, 0],
+                                ..bind.clone()
+                            }],
+                        )],
+                        ..Default::default()
+                    }],
+                    None,
+                )),
+                Err(Validated::ValidationError(_))
+            ));
+
+            let fence = Arc::new(Fence::new(device, Default::default()).unwrap());
+            queue
+                .with(|mut q| {
+                    q.bind_sparse(
+                        [BindSparseInfo {
+                            image_binds: vec![(image.clone(), vec![bind])],
+                            ..Default::default()
+                        }],
+                        Some(fence.clone()),
+                    )
+                })
+                .unwrap();
+
+            fence.wait(Some(Duration::from_secs(5))).unwrap();
+            assert!(fence.is_signaled().unwrap());
+        }
+    }
 }
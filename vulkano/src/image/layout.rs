@@ -210,12 +210,23 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(khr_video_encode_queue)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// A layout for an image that is used as a color or depth/stencil attachment in a render
+    /// pass, and is also read from a shader in the same subpass, as an input attachment or a
+    /// sampled image. This creates a feedback loop, and the graphics pipeline must be created
+    /// with [`PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP`] or
+    /// [`PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP`].
+    ///
+    /// The image must have been created with the
+    /// [`ATTACHMENT_FEEDBACK_LOOP`](crate::image::ImageUsage::ATTACHMENT_FEEDBACK_LOOP) usage, and
+    /// the [`attachment_feedback_loop_layout`] feature must be enabled on the device.
+    ///
+    /// [`PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP`]: crate::pipeline::PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP
+    /// [`PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP`]: crate::pipeline::PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP
+    /// [`attachment_feedback_loop_layout`]: crate::device::Features::attachment_feedback_loop_layout
     AttachmentFeedbackLoopOptimal = ATTACHMENT_FEEDBACK_LOOP_OPTIMAL_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_attachment_feedback_loop_layout)]),
-    ]),*/
+    ]),
 }
 
 impl Default for ImageLayout {
@@ -236,7 +247,8 @@ impl ImageLayout {
             | ImageAspect::Plane2 => match self {
                 ImageLayout::General
                 | ImageLayout::ColorAttachmentOptimal
-                | ImageLayout::TransferDstOptimal => true,
+                | ImageLayout::TransferDstOptimal
+                | ImageLayout::AttachmentFeedbackLoopOptimal => true,
                 ImageLayout::Undefined
                 | ImageLayout::DepthStencilAttachmentOptimal
                 | ImageLayout::DepthStencilReadOnlyOptimal
@@ -256,7 +268,8 @@ impl ImageLayout {
                 | ImageLayout::DepthStencilAttachmentOptimal
                 | ImageLayout::TransferDstOptimal
                 | ImageLayout::DepthAttachmentStencilReadOnlyOptimal
-                | ImageLayout::DepthAttachmentOptimal => true,
+                | ImageLayout::DepthAttachmentOptimal
+                | ImageLayout::AttachmentFeedbackLoopOptimal => true,
 
                 ImageLayout::Undefined
                 | ImageLayout::ColorAttachmentOptimal
@@ -275,7 +288,8 @@ impl ImageLayout {
                 | ImageLayout::DepthStencilAttachmentOptimal
                 | ImageLayout::TransferDstOptimal
                 | ImageLayout::DepthReadOnlyStencilAttachmentOptimal
-                | ImageLayout::StencilAttachmentOptimal => true,
+                | ImageLayout::StencilAttachmentOptimal
+                | ImageLayout::AttachmentFeedbackLoopOptimal => true,
 
                 ImageLayout::Undefined
                 | ImageLayout::ColorAttachmentOptimal
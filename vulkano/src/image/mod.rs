@@ -64,6 +64,10 @@
 pub use self::{aspect::*, layout::*, sys::ImageCreateInfo, usage::*};
 use self::{sys::RawImage, view::ImageViewType};
 use crate::{
+    command_buffer::{
+        allocator::CommandBufferAllocator, AutoCommandBufferBuilder, ClearColorImageInfo,
+        ClearDepthStencilImageInfo,
+    },
     device::{physical::PhysicalDevice, Device, DeviceOwned},
     format::{Format, FormatFeatures},
     macros::{vulkan_bitflags, vulkan_bitflags_enum, vulkan_enum},
@@ -178,6 +182,42 @@ impl Image {
         Ok(Arc::new(image))
     }
 
+    /// Creates a new `Image`, and records a command into `builder` that clears all of its
+    /// subresources to zero.
+    ///
+    /// `create_info.usage` must include [`ImageUsage::TRANSFER_DST`], and the format must support
+    /// being cleared with [`clear_color_image`] or [`clear_depth_stencil_image`].
+    ///
+    /// > **Note**: The image only contains zeros once the command buffer built from `builder`
+    /// > has been executed. Any access to the image must happen after that, either later in the
+    /// > same command buffer or in a submission that waits for it.
+    ///
+    /// [`clear_color_image`]: AutoCommandBufferBuilder::clear_color_image
+    /// [`clear_depth_stencil_image`]: AutoCommandBufferBuilder::clear_depth_stencil_image
+    pub fn new_zeroed<L, A>(
+        allocator: Arc<dyn MemoryAllocator>,
+        create_info: ImageCreateInfo,
+        allocation_info: AllocationCreateInfo,
+        builder: &mut AutoCommandBufferBuilder<L, A>,
+    ) -> Result<Arc<Self>, Validated<ImageAllocateError>>
+    where
+        A: CommandBufferAllocator,
+    {
+        let image = Image::new(allocator, create_info, allocation_info)?;
+
+        if image.format().aspects().intersects(ImageAspects::COLOR) {
+            builder
+                .clear_color_image(ClearColorImageInfo::image(image.clone()))
+                .map_err(|err| err.add_context("clear_color_image"))?;
+        } else {
+            builder
+                .clear_depth_stencil_image(ClearDepthStencilImageInfo::image(image.clone()))
+                .map_err(|err| err.add_context("clear_depth_stencil_image"))?;
+        }
+
+        Ok(image)
+    }
+
     fn from_raw(inner: RawImage, memory: ImageMemory, layout: ImageLayout) -> Self {
         let aspects = inner.format().aspects();
         let aspect_list: SmallVec<[ImageAspect; 4]> = aspects.into_iter().collect();
@@ -984,17 +1024,20 @@ vulkan_bitflags! {
     /// Flags specifying additional properties of an image.
     ImageCreateFlags = ImageCreateFlags(u32);
 
-    /* TODO: enable
     /// The image will be backed by sparse memory binding (through queue commands) instead of
     /// regular binding (through [`bind_memory`]).
     ///
+    /// An image with this flag can be turned into an [`Image`] with [`assume_bound`], after
+    /// which memory is bound to it with [`bind_sparse`].
+    ///
     /// The [`sparse_binding`] feature must be enabled on the device.
     ///
     /// [`bind_memory`]: sys::RawImage::bind_memory
+    /// [`assume_bound`]: sys::RawImage::assume_bound
+    /// [`bind_sparse`]: crate::device::QueueGuard::bind_sparse
     /// [`sparse_binding`]: crate::device::Features::sparse_binding
-    SPARSE_BINDING = SPARSE_BINDING,*/
+    SPARSE_BINDING = SPARSE_BINDING,
 
-    /* TODO: enable
     /// The image can be used without being fully resident in memory at the time of use.
     ///
     /// This requires the `sparse_binding` flag as well.
@@ -1008,22 +1051,21 @@ vulkan_bitflags! {
     ///
     /// [`sparse_binding`]: crate::device::Features::sparse_binding
     /// [`sparse_residency_image2_d`]: crate::device::Features::sparse_residency_image2_d
-    /// [`sparse_residency_image2_3`]: crate::device::Features::sparse_residency_image3_d
+    /// [`sparse_residency_image3_d`]: crate::device::Features::sparse_residency_image3_d
     /// [`sparse_residency2_samples`]: crate::device::Features::sparse_residency2_samples
     /// [`sparse_residency4_samples`]: crate::device::Features::sparse_residency4_samples
     /// [`sparse_residency8_samples`]: crate::device::Features::sparse_residency8_samples
     /// [`sparse_residency16_samples`]: crate::device::Features::sparse_residency16_samples
-    SPARSE_RESIDENCY = SPARSE_RESIDENCY,*/
+    SPARSE_RESIDENCY = SPARSE_RESIDENCY,
 
-    /* TODO: enable
-    /// The buffer's memory can alias with another image or a different part of the same image.
+    /// The image's memory can alias with another image or a different part of the same image.
     ///
     /// This requires the `sparse_binding` flag as well.
     ///
     /// The [`sparse_residency_aliased`] feature must be enabled on the device.
     ///
     /// [`sparse_residency_aliased`]: crate::device::Features::sparse_residency_aliased
-    SPARSE_ALIASED = SPARSE_ALIASED,*/
+    SPARSE_ALIASED = SPARSE_ALIASED,
 
     /// For non-multi-planar formats, whether an image view wrapping the image can have a
     /// different format.
@@ -49,7 +49,7 @@ pub mod ycbcr;
 use self::ycbcr::SamplerYcbcrConversion;
 use crate::{
     device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
-    format::{FormatFeatures, NumericType},
+    format::{ClearColorValue, Format, FormatFeatures, NumericType},
     image::{
         view::{ImageView, ImageViewType},
         ImageAspects,
@@ -59,7 +59,13 @@ use crate::{
     pipeline::graphics::depth_stencil::CompareOp,
     Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
 };
-use std::{mem::MaybeUninit, num::NonZeroU64, ops::RangeInclusive, ptr, sync::Arc};
+use std::{
+    mem::MaybeUninit,
+    num::NonZeroU64,
+    ops::RangeInclusive,
+    ptr,
+    sync::{atomic::Ordering, Arc},
+};
 
 /// Describes how to retrieve data from a sampled image within a shader.
 ///
@@ -99,6 +105,8 @@ pub struct Sampler {
     address_mode: [SamplerAddressMode; 3],
     anisotropy: Option<f32>,
     border_color: Option<BorderColor>,
+    custom_border_color: Option<ClearColorValue>,
+    custom_border_color_format: Option<Format>,
     compare: Option<CompareOp>,
     lod: RangeInclusive<f32>,
     mag_filter: Filter,
@@ -148,6 +156,8 @@ impl Sampler {
             compare,
             ref lod,
             border_color,
+            custom_border_color,
+            custom_border_color_format,
             unnormalized_coordinates,
             reduction_mode,
             ref sampler_ycbcr_conversion,
@@ -185,9 +195,24 @@ impl Sampler {
             unnormalized_coordinates: unnormalized_coordinates as ash::vk::Bool32,
             ..Default::default()
         };
+        let mut sampler_custom_border_color_create_info_vk = None;
         let mut sampler_reduction_mode_create_info_vk = None;
         let mut sampler_ycbcr_conversion_info_vk = None;
 
+        if let Some(custom_border_color) = custom_border_color {
+            let next = sampler_custom_border_color_create_info_vk.insert(
+                ash::vk::SamplerCustomBorderColorCreateInfoEXT {
+                    custom_border_color: custom_border_color.into(),
+                    format: custom_border_color_format
+                        .map_or(ash::vk::Format::UNDEFINED, Into::into),
+                    ..Default::default()
+                },
+            );
+
+            next.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = next as *const _ as *const _;
+        }
+
         if reduction_mode != SamplerReductionMode::WeightedAverage {
             let next = sampler_reduction_mode_create_info_vk.insert(
                 ash::vk::SamplerReductionModeCreateInfo {
@@ -211,6 +236,21 @@ impl Sampler {
             create_info_vk.p_next = next as *const _ as *const _;
         }
 
+        // VUID-VkSamplerCreateInfo-None-04012
+        if custom_border_color.is_some() {
+            let max_samplers = device
+                .physical_device()
+                .properties()
+                .max_custom_border_color_samplers
+                .unwrap_or(0);
+            device
+                .custom_border_color_sampler_count
+                .fetch_update(Ordering::Acquire, Ordering::Relaxed, move |count| {
+                    (count < max_samplers).then_some(count + 1)
+                })
+                .map_err(|_| VulkanError::TooManyObjects)?;
+        }
+
         let handle = unsafe {
             let fns = device.fns();
             let mut output = MaybeUninit::uninit();
@@ -221,11 +261,19 @@ impl Sampler {
                 output.as_mut_ptr(),
             )
             .result()
-            .map_err(VulkanError::from)?;
+            .map_err(|e| {
+                if custom_border_color.is_some() {
+                    device
+                        .custom_border_color_sampler_count
+                        .fetch_sub(1, Ordering::Release);
+                }
+
+                VulkanError::from(e)
+            })?;
             output.assume_init()
         };
 
-        Ok(Self::from_handle(device, handle, create_info))
+        Ok(Self::from_handle_counted(device, handle, create_info))
     }
 
     /// Creates a new `Sampler` from a raw object handle.
@@ -239,6 +287,22 @@ impl Sampler {
         device: Arc<Device>,
         handle: ash::vk::Sampler,
         create_info: SamplerCreateInfo,
+    ) -> Arc<Sampler> {
+        if create_info.custom_border_color.is_some() {
+            device
+                .custom_border_color_sampler_count
+                .fetch_add(1, Ordering::Acquire);
+        }
+
+        Self::from_handle_counted(device, handle, create_info)
+    }
+
+    // Same as `from_handle`, but the sampler has already been added to
+    // `Device::custom_border_color_sampler_count` if needed.
+    unsafe fn from_handle_counted(
+        device: Arc<Device>,
+        handle: ash::vk::Sampler,
+        create_info: SamplerCreateInfo,
     ) -> Arc<Sampler> {
         let SamplerCreateInfo {
             mag_filter,
@@ -250,6 +314,8 @@ impl Sampler {
             compare,
             lod,
             border_color,
+            custom_border_color,
+            custom_border_color_format,
             unnormalized_coordinates,
             reduction_mode,
             sampler_ycbcr_conversion,
@@ -266,6 +332,8 @@ impl Sampler {
                 .into_iter()
                 .any(|mode| mode == SamplerAddressMode::ClampToBorder)
                 .then_some(border_color),
+            custom_border_color,
+            custom_border_color_format,
             compare,
             lod,
             mag_filter,
@@ -416,7 +484,8 @@ impl Sampler {
             match border_color {
                 BorderColor::IntTransparentBlack
                 | BorderColor::IntOpaqueBlack
-                | BorderColor::IntOpaqueWhite => {
+                | BorderColor::IntOpaqueWhite
+                | BorderColor::IntCustom => {
                     // The sampler borderColor is an integer type and the image view
                     // format is not one of the VkFormat integer types or a stencil
                     // component of a depth/stencil format.
@@ -431,7 +500,8 @@ impl Sampler {
                 }
                 BorderColor::FloatTransparentBlack
                 | BorderColor::FloatOpaqueBlack
-                | BorderColor::FloatOpaqueWhite => {
+                | BorderColor::FloatOpaqueWhite
+                | BorderColor::FloatCustom => {
                     // The sampler borderColor is a float type and the image view
                     // format is not one of the VkFormat float types or a depth
                     // component of a depth/stencil format.
@@ -522,6 +592,18 @@ impl Sampler {
         self.border_color
     }
 
+    /// Returns the custom border color value, if one was given.
+    #[inline]
+    pub fn custom_border_color(&self) -> Option<ClearColorValue> {
+        self.custom_border_color
+    }
+
+    /// Returns the format that the custom border color was given for, if any.
+    #[inline]
+    pub fn custom_border_color_format(&self) -> Option<Format> {
+        self.custom_border_color_format
+    }
+
     /// Returns the compare operation if the sampler is a compare-mode sampler.
     #[inline]
     pub fn compare(&self) -> Option<CompareOp> {
@@ -583,6 +665,12 @@ impl Drop for Sampler {
         unsafe {
             let fns = self.device.fns();
             (fns.v1_0.destroy_sampler)(self.device.handle(), self.handle, ptr::null());
+
+            if self.custom_border_color.is_some() {
+                self.device
+                    .custom_border_color_sampler_count
+                    .fetch_sub(1, Ordering::Release);
+            }
         }
     }
 }
@@ -694,6 +782,29 @@ pub struct SamplerCreateInfo {
     /// The default value is [`FloatTransparentBlack`](BorderColor::FloatTransparentBlack).
     pub border_color: BorderColor,
 
+    /// The value of the border color, if `border_color` is
+    /// [`FloatCustom`](BorderColor::FloatCustom) or [`IntCustom`](BorderColor::IntCustom).
+    ///
+    /// This must be `Some` if `border_color` is a custom border color, and `None` otherwise.
+    /// For `FloatCustom` the value must be [`ClearColorValue::Float`], for `IntCustom` it must be
+    /// [`ClearColorValue::Int`] or [`ClearColorValue::Uint`].
+    ///
+    /// No more than the
+    /// [`max_custom_border_color_samplers`](crate::device::Properties::max_custom_border_color_samplers)
+    /// device property of samplers with a custom border color can exist at the same time.
+    ///
+    /// The default value is `None`.
+    pub custom_border_color: Option<ClearColorValue>,
+
+    /// The format of the images that will be sampled with a custom border color.
+    ///
+    /// If set to `None`, the
+    /// [`custom_border_color_without_format`](crate::device::Features::custom_border_color_without_format)
+    /// feature must be enabled on the device. Ignored if `custom_border_color` is `None`.
+    ///
+    /// The default value is `None`.
+    pub custom_border_color_format: Option<Format>,
+
     /// Whether unnormalized texture coordinates are enabled.
     ///
     /// When a sampler is set to use unnormalized coordinates as input, the texture coordinates are
@@ -759,6 +870,8 @@ impl Default for SamplerCreateInfo {
             compare: None,
             lod: 0.0..=0.0,
             border_color: BorderColor::FloatTransparentBlack,
+            custom_border_color: None,
+            custom_border_color_format: None,
             unnormalized_coordinates: false,
             reduction_mode: SamplerReductionMode::WeightedAverage,
             sampler_ycbcr_conversion: None,
@@ -806,6 +919,8 @@ impl SamplerCreateInfo {
             compare,
             ref lod,
             border_color,
+            custom_border_color,
+            custom_border_color_format,
             unnormalized_coordinates,
             reduction_mode,
             ref sampler_ycbcr_conversion,
@@ -852,6 +967,86 @@ impl SamplerCreateInfo {
                 .set_vuids(&["VUID-VkSamplerReductionModeCreateInfo-reductionMode-parameter"])
         })?;
 
+        if matches!(
+            border_color,
+            BorderColor::FloatCustom | BorderColor::IntCustom
+        ) {
+            if !device.enabled_features().custom_border_colors {
+                return Err(Box::new(ValidationError {
+                    context: "border_color".into(),
+                    problem: "is `BorderColor::FloatCustom` or `BorderColor::IntCustom`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "custom_border_colors",
+                    )])]),
+                    vuids: &["VUID-VkSamplerCreateInfo-customBorderColors-04085"],
+                }));
+            }
+
+            let custom_border_color = custom_border_color.ok_or_else(|| {
+                Box::new(ValidationError {
+                    problem: "`border_color` is `BorderColor::FloatCustom` or \
+                        `BorderColor::IntCustom`, but `custom_border_color` is `None`"
+                        .into(),
+                    vuids: &["VUID-VkSamplerCreateInfo-borderColor-04011"],
+                    ..Default::default()
+                })
+            })?;
+
+            let border_color_is_int = border_color == BorderColor::IntCustom;
+
+            if border_color_is_int != (custom_border_color.numeric_type() != NumericType::Float) {
+                return Err(Box::new(ValidationError {
+                    problem: "the numeric type of `custom_border_color` does not match \
+                        `border_color`"
+                        .into(),
+                    vuids: &["VUID-VkSamplerCustomBorderColorCreateInfoEXT-format-07605"],
+                    ..Default::default()
+                }));
+            }
+
+            if let Some(format) = custom_border_color_format {
+                format.validate_device(device).map_err(|err| {
+                    err.add_context("custom_border_color_format").set_vuids(&[
+                        "VUID-VkSamplerCustomBorderColorCreateInfoEXT-format-parameter",
+                    ])
+                })?;
+
+                if let Some(numeric_format) = format.numeric_format_color() {
+                    if border_color_is_int
+                        != matches!(
+                            NumericType::from(numeric_format),
+                            NumericType::Int | NumericType::Uint
+                        )
+                    {
+                        return Err(Box::new(ValidationError {
+                            problem: "the numeric type of `custom_border_color_format` does \
+                                not match `border_color`"
+                                .into(),
+                            vuids: &["VUID-VkSamplerCustomBorderColorCreateInfoEXT-format-07605"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+            } else if !device.enabled_features().custom_border_color_without_format {
+                return Err(Box::new(ValidationError {
+                    context: "custom_border_color_format".into(),
+                    problem: "is `None`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "custom_border_color_without_format",
+                    )])]),
+                    vuids: &["VUID-VkSamplerCustomBorderColorCreateInfoEXT-format-04014"],
+                }));
+            }
+        } else if custom_border_color.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "`custom_border_color` is `Some`, but `border_color` is not \
+                    `BorderColor::FloatCustom` or `BorderColor::IntCustom`"
+                    .into(),
+                vuids: &["VUID-VkSamplerCreateInfo-borderColor-04011"],
+                ..Default::default()
+            }));
+        }
+
         if address_mode.contains(&SamplerAddressMode::MirrorClampToEdge) {
             if !(device.enabled_features().sampler_mirror_clamp_to_edge
                 || device.enabled_extensions().khr_sampler_mirror_clamp_to_edge)
@@ -1417,19 +1612,25 @@ vulkan_enum! {
     /// The value `(1, 1, 1, 1)`. Can only be used with integer images.
     IntOpaqueWhite = INT_OPAQUE_WHITE,
 
-    /* TODO: enable
-    // TODO: document
-    FloatCustom = VK_BORDER_COLOR_FLOAT_CUSTOM_EXT
+    /// A custom floating-point value, which is given in
+    /// [`SamplerCreateInfo::custom_border_color`]. Can only be used with floating-point images.
+    ///
+    /// The [`custom_border_colors`](crate::device::Features::custom_border_colors) feature must
+    /// be enabled on the device.
+    FloatCustom = FLOAT_CUSTOM_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_custom_border_color)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// A custom integer value, which is given in
+    /// [`SamplerCreateInfo::custom_border_color`]. Can only be used with integer images.
+    ///
+    /// The [`custom_border_colors`](crate::device::Features::custom_border_colors) feature must
+    /// be enabled on the device.
     IntCustom = INT_CUSTOM_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_custom_border_color)]),
-    ]),*/
+    ]),
 }
 
 vulkan_enum! {
@@ -1463,11 +1664,13 @@ vulkan_enum! {
 #[cfg(test)]
 mod tests {
     use crate::{
+        format::{ClearColorValue, Format},
         image::sampler::{
-            Filter, Sampler, SamplerAddressMode, SamplerCreateInfo, SamplerReductionMode,
+            BorderColor, Filter, Sampler, SamplerAddressMode, SamplerCreateInfo,
+            SamplerReductionMode,
         },
         pipeline::graphics::depth_stencil::CompareOp,
-        Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError,
+        Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError,
     };
 
     #[test]
@@ -1715,4 +1918,108 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn custom_border_color_extension() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let r = Sampler::new(
+            device,
+            SamplerCreateInfo {
+                address_mode: [SamplerAddressMode::ClampToBorder; 3],
+                border_color: BorderColor::FloatCustom,
+                custom_border_color: Some(ClearColorValue::Float([0.3, 0.0, 0.0, 1.0])),
+                custom_border_color_format: Some(Format::R8G8B8A8_UNORM),
+                ..Default::default()
+            },
+        );
+
+        match r {
+            Err(Validated::ValidationError(err))
+                if matches!(
+                    *err,
+                    ValidationError {
+                        requires_one_of: RequiresOneOf([RequiresAllOf([
+                            Requires::DeviceExtension("ext_custom_border_color")
+                        ])]),
+                        ..
+                    }
+                ) => {}
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn custom_border_color() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_custom_border_color],
+            features: [custom_border_colors],
+        );
+
+        let create_info = SamplerCreateInfo {
+            address_mode: [SamplerAddressMode::ClampToBorder; 3],
+            border_color: BorderColor::FloatCustom,
+            custom_border_color: Some(ClearColorValue::Float([0.3, 0.0, 0.0, 1.0])),
+            custom_border_color_format: Some(Format::R8G8B8A8_UNORM),
+            ..Default::default()
+        };
+
+        let sampler = Sampler::new(device.clone(), create_info.clone()).unwrap();
+        assert_eq!(sampler.border_color(), Some(BorderColor::FloatCustom));
+        assert_eq!(
+            sampler.custom_border_color(),
+            Some(ClearColorValue::Float([0.3, 0.0, 0.0, 1.0])),
+        );
+
+        // The value must be given.
+        assert!(Sampler::new(
+            device.clone(),
+            SamplerCreateInfo {
+                custom_border_color: None,
+                ..create_info.clone()
+            },
+        )
+        .is_err());
+        // An integer value can't be used with a floating-point border color.
+        assert!(Sampler::new(
+            device.clone(),
+            SamplerCreateInfo {
+                custom_border_color: Some(ClearColorValue::Uint([1, 0, 0, 1])),
+                ..create_info.clone()
+            },
+        )
+        .is_err());
+        // Nor can an integer format.
+        assert!(Sampler::new(
+            device.clone(),
+            SamplerCreateInfo {
+                custom_border_color_format: Some(Format::R8G8B8A8_UINT),
+                ..create_info.clone()
+            },
+        )
+        .is_err());
+
+        // The number of samplers with a custom border color is limited.
+        let max_samplers = device
+            .physical_device()
+            .properties()
+            .max_custom_border_color_samplers
+            .unwrap();
+
+        if max_samplers <= 4096 {
+            let mut samplers = vec![sampler];
+
+            while samplers.len() < max_samplers as usize {
+                samplers.push(Sampler::new(device.clone(), create_info.clone()).unwrap());
+            }
+
+            assert!(matches!(
+                Sampler::new(device.clone(), create_info.clone()),
+                Err(Validated::Error(VulkanError::TooManyObjects)),
+            ));
+
+            samplers.pop();
+            Sampler::new(device, create_info).unwrap();
+        }
+    }
 }
@@ -497,8 +497,12 @@ impl RawImage {
         }
     }
 
-    #[allow(dead_code)] // Remove when sparse memory is implemented
-    fn get_sparse_memory_requirements(&self) -> Vec<SparseImageMemoryRequirements> {
+    /// Returns the sparse memory requirements for this image.
+    ///
+    /// This returns one element for each aspect or set of aspects of the image that has separate
+    /// sparse memory requirements. If the image was not created with
+    /// [`ImageCreateFlags::SPARSE_RESIDENCY`], the returned list is empty.
+    pub fn sparse_memory_requirements(&self) -> Vec<SparseImageMemoryRequirements> {
         let device = &self.device;
 
         unsafe {
@@ -740,6 +744,14 @@ impl RawImage {
     ) -> Result<(), Box<ValidationError>> {
         let physical_device = self.device().physical_device();
 
+        if self.flags.intersects(ImageCreateFlags::SPARSE_BINDING) {
+            return Err(Box::new(ValidationError {
+                problem: "`self.flags()` contains `ImageCreateFlags::SPARSE_BINDING`".into(),
+                vuids: &["VUID-VkBindImageMemoryInfo-image-01045"],
+                ..Default::default()
+            }));
+        }
+
         if self.flags.intersects(ImageCreateFlags::DISJOINT) {
             match self.tiling {
                 ImageTiling::Optimal | ImageTiling::Linear => {
@@ -827,10 +839,6 @@ impl RawImage {
             // VUID-VkBindImageMemoryInfo-image-07460
             // Ensured by taking ownership of `RawImage`.
 
-            // VUID-VkBindImageMemoryInfo-image-01045
-            // Currently ensured by not having sparse binding flags, but this needs to be checked
-            // once those are enabled.
-
             // VUID-VkBindImageMemoryInfo-memoryOffset-01046
             // Assume that `allocation` was created correctly.
 
@@ -1185,11 +1193,52 @@ impl RawImage {
             return Err((VulkanError::from(err), self, allocations.into_iter()));
         }
 
+        let layout = self.default_layout();
+
+        Ok(Image::from_raw(
+            self,
+            ImageMemory::Normal(allocations),
+            layout,
+        ))
+    }
+
+    /// Converts the raw image into a full image without binding any memory to it, so that
+    /// memory can be bound with [`bind_sparse`] instead.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `self.flags()` does not contain [`ImageCreateFlags::SPARSE_BINDING`].
+    ///
+    /// # Safety
+    ///
+    /// - If `self.flags()` does not contain [`ImageCreateFlags::SPARSE_RESIDENCY`], then the
+    ///   whole image must be bound to memory before it is accessed by the device.
+    /// - If `self.flags()` contains [`ImageCreateFlags::SPARSE_RESIDENCY`], then the regions of
+    ///   the image that are accessed by the device must be bound to memory, unless the
+    ///   [`residency_non_resident_strict`] property is `true`.
+    ///
+    /// [`bind_sparse`]: crate::device::QueueGuard::bind_sparse
+    /// [`residency_non_resident_strict`]: crate::device::Properties::residency_non_resident_strict
+    #[inline]
+    pub unsafe fn assume_bound(self) -> Image {
+        assert!(self.flags.intersects(ImageCreateFlags::SPARSE_BINDING));
+
+        let sparse_memory_requirements = self.sparse_memory_requirements();
+        let layout = self.default_layout();
+
+        Image::from_raw(
+            self,
+            ImageMemory::Sparse(sparse_memory_requirements),
+            layout,
+        )
+    }
+
+    fn default_layout(&self) -> ImageLayout {
         let usage = self
             .usage
             .difference(ImageUsage::TRANSFER_SRC | ImageUsage::TRANSFER_DST);
 
-        let layout = if usage.intersects(ImageUsage::SAMPLED | ImageUsage::INPUT_ATTACHMENT)
+        if usage.intersects(ImageUsage::SAMPLED | ImageUsage::INPUT_ATTACHMENT)
             && usage
                 .difference(ImageUsage::SAMPLED | ImageUsage::INPUT_ATTACHMENT)
                 .is_empty()
@@ -1207,13 +1256,7 @@ impl RawImage {
             ImageLayout::DepthStencilAttachmentOptimal
         } else {
             ImageLayout::General
-        };
-
-        Ok(Image::from_raw(
-            self,
-            ImageMemory::Normal(allocations),
-            layout,
-        ))
+        }
     }
 
     /// Returns the memory requirements for this image.
@@ -2590,6 +2633,178 @@ impl ImageCreateInfo {
             }
         }
 
+        if flags.intersects(ImageCreateFlags::SPARSE_BINDING) {
+            if !device.enabled_features().sparse_binding {
+                return Err(Box::new(ValidationError {
+                    context: "flags".into(),
+                    problem: "contains `ImageCreateFlags::SPARSE_BINDING`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "sparse_binding",
+                    )])]),
+                    vuids: &["VUID-VkImageCreateInfo-flags-00969"],
+                }));
+            }
+
+            if usage.intersects(ImageUsage::TRANSIENT_ATTACHMENT) {
+                return Err(Box::new(ValidationError {
+                    problem: "`flags` contains `ImageCreateFlags::SPARSE_BINDING`, but \
+                        `usage` contains `ImageUsage::TRANSIENT_ATTACHMENT`"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-None-01925"],
+                    ..Default::default()
+                }));
+            }
+        } else if flags
+            .intersects(ImageCreateFlags::SPARSE_RESIDENCY | ImageCreateFlags::SPARSE_ALIASED)
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `ImageCreateFlags::SPARSE_RESIDENCY` or \
+                    `ImageCreateFlags::SPARSE_ALIASED`, but does not also contain \
+                    `ImageCreateFlags::SPARSE_BINDING`"
+                    .into(),
+                vuids: &["VUID-VkImageCreateInfo-flags-00987"],
+                ..Default::default()
+            }));
+        }
+
+        if flags.intersects(ImageCreateFlags::SPARSE_RESIDENCY) {
+            if tiling == ImageTiling::Linear {
+                return Err(Box::new(ValidationError {
+                    problem: "`flags` contains `ImageCreateFlags::SPARSE_RESIDENCY`, but \
+                        `tiling` is `ImageTiling::Linear`"
+                        .into(),
+                    vuids: &["VUID-VkImageCreateInfo-tiling-04121"],
+                    ..Default::default()
+                }));
+            }
+
+            match image_type {
+                ImageType::Dim1d => {
+                    return Err(Box::new(ValidationError {
+                        problem: "`flags` contains `ImageCreateFlags::SPARSE_RESIDENCY`, but \
+                            `image_type` is `ImageType::Dim1d`"
+                            .into(),
+                        vuids: &["VUID-VkImageCreateInfo-imageType-00970"],
+                        ..Default::default()
+                    }));
+                }
+                ImageType::Dim2d => {
+                    if !device.enabled_features().sparse_residency_image2_d {
+                        return Err(Box::new(ValidationError {
+                            problem: "`flags` contains `ImageCreateFlags::SPARSE_RESIDENCY`, and \
+                                `image_type` is `ImageType::Dim2d`"
+                                .into(),
+                            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                                "sparse_residency_image2_d",
+                            )])]),
+                            vuids: &["VUID-VkImageCreateInfo-imageType-00971"],
+                            ..Default::default()
+                        }));
+                    }
+
+                    match samples {
+                        SampleCount::Sample1 => (),
+                        SampleCount::Sample2 => {
+                            if !device.enabled_features().sparse_residency2_samples {
+                                return Err(Box::new(ValidationError {
+                                    problem: "`flags` contains \
+                                        `ImageCreateFlags::SPARSE_RESIDENCY`, and `samples` is \
+                                        `SampleCount::Sample2`"
+                                        .into(),
+                                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                                        Requires::Feature("sparse_residency2_samples"),
+                                    ])]),
+                                    vuids: &["VUID-VkImageCreateInfo-imageType-00973"],
+                                    ..Default::default()
+                                }));
+                            }
+                        }
+                        SampleCount::Sample4 => {
+                            if !device.enabled_features().sparse_residency4_samples {
+                                return Err(Box::new(ValidationError {
+                                    problem: "`flags` contains \
+                                        `ImageCreateFlags::SPARSE_RESIDENCY`, and `samples` is \
+                                        `SampleCount::Sample4`"
+                                        .into(),
+                                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                                        Requires::Feature("sparse_residency4_samples"),
+                                    ])]),
+                                    vuids: &["VUID-VkImageCreateInfo-imageType-00974"],
+                                    ..Default::default()
+                                }));
+                            }
+                        }
+                        SampleCount::Sample8 => {
+                            if !device.enabled_features().sparse_residency8_samples {
+                                return Err(Box::new(ValidationError {
+                                    problem: "`flags` contains \
+                                        `ImageCreateFlags::SPARSE_RESIDENCY`, and `samples` is \
+                                        `SampleCount::Sample8`"
+                                        .into(),
+                                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                                        Requires::Feature("sparse_residency8_samples"),
+                                    ])]),
+                                    vuids: &["VUID-VkImageCreateInfo-imageType-00975"],
+                                    ..Default::default()
+                                }));
+                            }
+                        }
+                        SampleCount::Sample16 => {
+                            if !device.enabled_features().sparse_residency16_samples {
+                                return Err(Box::new(ValidationError {
+                                    problem: "`flags` contains \
+                                        `ImageCreateFlags::SPARSE_RESIDENCY`, and `samples` is \
+                                        `SampleCount::Sample16`"
+                                        .into(),
+                                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                                        Requires::Feature("sparse_residency16_samples"),
+                                    ])]),
+                                    vuids: &["VUID-VkImageCreateInfo-imageType-00976"],
+                                    ..Default::default()
+                                }));
+                            }
+                        }
+                        SampleCount::Sample32 | SampleCount::Sample64 => {
+                            return Err(Box::new(ValidationError {
+                                problem: "`flags` contains `ImageCreateFlags::SPARSE_RESIDENCY`, \
+                                    but `samples` is greater than `SampleCount::Sample16`"
+                                    .into(),
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
+                ImageType::Dim3d => {
+                    if !device.enabled_features().sparse_residency_image3_d {
+                        return Err(Box::new(ValidationError {
+                            problem: "`flags` contains `ImageCreateFlags::SPARSE_RESIDENCY`, and \
+                                `image_type` is `ImageType::Dim3d`"
+                                .into(),
+                            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                                "sparse_residency_image3_d",
+                            )])]),
+                            vuids: &["VUID-VkImageCreateInfo-imageType-00972"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+            }
+        }
+
+        if flags.intersects(ImageCreateFlags::SPARSE_ALIASED)
+            && !device.enabled_features().sparse_residency_aliased
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `ImageCreateFlags::SPARSE_ALIASED`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "sparse_residency_aliased",
+                )])]),
+                vuids: &["VUID-VkImageCreateInfo-flags-01924"],
+            }));
+        }
+
         /* Check sharing mode and queue families */
 
         match sharing {
@@ -105,12 +105,13 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(khr_video_encode_queue)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The image can be used in the
+    /// [`AttachmentFeedbackLoopOptimal`](crate::image::ImageLayout::AttachmentFeedbackLoopOptimal)
+    /// layout, to be read from a shader while it is used as an attachment.
     ATTACHMENT_FEEDBACK_LOOP = ATTACHMENT_FEEDBACK_LOOP_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_attachment_feedback_loop_layout)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -30,7 +30,7 @@ use crate::{
     VulkanObject,
 };
 use smallvec::{smallvec, SmallVec};
-use std::{fmt::Debug, hash::Hash, mem::MaybeUninit, num::NonZeroU64, ptr, sync::Arc};
+use std::{fmt::Debug, hash::Hash, mem::MaybeUninit, num::NonZeroU64, ops::Range, ptr, sync::Arc};
 
 /// A wrapper around an image that makes it available to shaders or framebuffers.
 ///
@@ -1077,6 +1077,174 @@ impl ImageViewCreateInfo {
     }
 }
 
+/// A builder for the subresource range of an image view.
+///
+/// Unlike [`ImageSubresourceRange`], the mip levels and array layers can be left to cover the
+/// whole image, and are only resolved once the image is known. [`build`](Self::build) checks the
+/// selected ranges against the image's actual number of mip levels and array layers, so that
+/// mistakes are caught before the image view is created.
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use vulkano::image::{view::{ImageView, ImageViewCreateInfo, SubresourceRange}, Image};
+/// # fn example(image: Arc<Image>) -> Result<(), Box<dyn std::error::Error>> {
+/// let view = ImageView::new(
+///     image.clone(),
+///     ImageViewCreateInfo {
+///         subresource_range: SubresourceRange::new().single_mip(2).build(&image)?,
+///         ..ImageViewCreateInfo::from_image(&image)
+///     },
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SubresourceRange {
+    aspects: Option<ImageAspects>,
+    mip_levels: Option<Range<u32>>,
+    array_layers: Option<Range<u32>>,
+}
+
+impl SubresourceRange {
+    /// Returns a `SubresourceRange` that covers all mip levels and array layers of the image,
+    /// with the aspects determined from the image format.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Selects the aspects to include, instead of those determined from the image format.
+    #[inline]
+    pub fn aspects(mut self, aspects: ImageAspects) -> Self {
+        self.aspects = Some(aspects);
+        self
+    }
+
+    /// Selects all mip levels of the image.
+    #[inline]
+    pub fn all_mips(mut self) -> Self {
+        self.mip_levels = None;
+        self
+    }
+
+    /// Selects only the mip level `mip_level`.
+    #[inline]
+    pub fn single_mip(mut self, mip_level: u32) -> Self {
+        // `u32::MAX` is never a valid mip level, and is rejected by `build`.
+        self.mip_levels = Some(mip_level..mip_level.saturating_add(1));
+        self
+    }
+
+    /// Selects the mip levels in `mip_levels`.
+    #[inline]
+    pub fn mip_range(mut self, mip_levels: Range<u32>) -> Self {
+        self.mip_levels = Some(mip_levels);
+        self
+    }
+
+    /// Selects all array layers of the image.
+    #[inline]
+    pub fn all_layers(mut self) -> Self {
+        self.array_layers = None;
+        self
+    }
+
+    /// Selects the array layers in `array_layers`.
+    #[inline]
+    pub fn layer_range(mut self, array_layers: Range<u32>) -> Self {
+        self.array_layers = Some(array_layers);
+        self
+    }
+
+    /// Resolves the range against `image`, returning an `ImageSubresourceRange` that can be used
+    /// in [`ImageViewCreateInfo::subresource_range`].
+    ///
+    /// Returns an error if a selected range is empty, or extends beyond the mip levels or array
+    /// layers of `image`. For 3D images, the array layers are not checked here, as they may refer
+    /// to depth slices of the image; these are checked when the image view is created.
+    pub fn build(&self, image: &Image) -> Result<ImageSubresourceRange, Box<ValidationError>> {
+        let Self {
+            aspects,
+            mip_levels,
+            array_layers,
+        } = self;
+
+        let full_range = image.subresource_range();
+        let aspects = aspects.unwrap_or(full_range.aspects);
+        let mip_levels = mip_levels.clone().unwrap_or(full_range.mip_levels);
+        let array_layers = array_layers.clone().unwrap_or(full_range.array_layers);
+
+        if mip_levels.start >= image.mip_levels() {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "the start of the selected mip levels ({}) is not less than \
+                    `image.mip_levels()` ({})",
+                    mip_levels.start,
+                    image.mip_levels(),
+                )
+                .into(),
+                vuids: &["VUID-VkImageViewCreateInfo-subresourceRange-01478"],
+                ..Default::default()
+            }));
+        }
+
+        if mip_levels.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "mip_levels".into(),
+                problem: "is empty".into(),
+                vuids: &["VUID-VkImageSubresourceRange-levelCount-01720"],
+                ..Default::default()
+            }));
+        }
+
+        if mip_levels.end > image.mip_levels() {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "the end of the selected mip levels ({}) is greater than \
+                    `image.mip_levels()` ({})",
+                    mip_levels.end,
+                    image.mip_levels(),
+                )
+                .into(),
+                vuids: &["VUID-VkImageViewCreateInfo-subresourceRange-01718"],
+                ..Default::default()
+            }));
+        }
+
+        if array_layers.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "array_layers".into(),
+                problem: "is empty".into(),
+                vuids: &["VUID-VkImageSubresourceRange-layerCount-01721"],
+                ..Default::default()
+            }));
+        }
+
+        if image.image_type() != ImageType::Dim3d && array_layers.end > image.array_layers() {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "the end of the selected array layers ({}) is greater than \
+                    `image.array_layers()` ({})",
+                    array_layers.end,
+                    image.array_layers(),
+                )
+                .into(),
+                vuids: &[
+                    "VUID-VkImageViewCreateInfo-image-06724",
+                    "VUID-VkImageViewCreateInfo-subresourceRange-06725",
+                ],
+                ..Default::default()
+            }));
+        }
+
+        Ok(ImageSubresourceRange {
+            aspects,
+            mip_levels,
+            array_layers,
+        })
+    }
+}
+
 vulkan_enum! {
     #[non_exhaustive]
 
@@ -1197,3 +1365,89 @@ unsafe fn get_format_features(view_format: Format, image: &Image) -> FormatFeatu
 
     format_features
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ImageView, ImageViewCreateInfo, SubresourceRange};
+    use crate::{
+        format::Format,
+        image::{Image, ImageCreateInfo, ImageType, ImageUsage},
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn subresource_range_builder() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device));
+        let image = Image::new(
+            memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format: Format::R8G8B8A8_UNORM,
+                extent: [32, 32, 1],
+                array_layers: 4,
+                mip_levels: 3,
+                usage: ImageUsage::SAMPLED,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        let range = SubresourceRange::new().build(&image).unwrap();
+        assert_eq!(range, image.subresource_range());
+
+        let range = SubresourceRange::new()
+            .single_mip(2)
+            .layer_range(1..3)
+            .build(&image)
+            .unwrap();
+        assert_eq!(range.mip_levels, 2..3);
+        assert_eq!(range.array_layers, 1..3);
+
+        let range = SubresourceRange::new()
+            .mip_range(0..1)
+            .all_mips()
+            .all_layers()
+            .build(&image)
+            .unwrap();
+        assert_eq!(range.mip_levels, 0..3);
+        assert_eq!(range.array_layers, 0..4);
+
+        ImageView::new(
+            image.clone(),
+            ImageViewCreateInfo {
+                subresource_range: SubresourceRange::new().single_mip(1).build(&image).unwrap(),
+                ..ImageViewCreateInfo::from_image(&image)
+            },
+        )
+        .unwrap();
+
+        // The range must not exceed the mip levels or array layers of the image.
+        assert!(SubresourceRange::new()
+            .mip_range(1..4)
+            .build(&image)
+            .is_err());
+        assert!(SubresourceRange::new().single_mip(3).build(&image).is_err());
+        assert_eq!(
+            SubresourceRange::new()
+                .single_mip(u32::MAX)
+                .build(&image)
+                .unwrap_err()
+                .vuids,
+            ["VUID-VkImageViewCreateInfo-subresourceRange-01478"],
+        );
+        assert!(SubresourceRange::new()
+            .layer_range(2..5)
+            .build(&image)
+            .is_err());
+
+        // The range must not be empty.
+        assert!(SubresourceRange::new()
+            .mip_range(1..1)
+            .build(&image)
+            .is_err());
+    }
+}
@@ -96,11 +96,12 @@
 //!
 //! # Cargo features
 //!
-//! | Feature              | Description                                                    |
-//! |----------------------|----------------------------------------------------------------|
-//! | `macros`             | Include reexports from [`vulkano-macros`]. Enabled by default. |
-//! | `document_unchecked` | Include `_unchecked` functions in the generated documentation. |
-//! | `serde`              | Enables (de)serialization of certain types using [`serde`].    |
+//! | Feature                      | Description                                                    |
+//! |------------------------------|----------------------------------------------------------------|
+//! | `macros`                     | Include reexports from [`vulkano-macros`]. Enabled by default. |
+//! | `document_unchecked`         | Include `_unchecked` functions in the generated documentation. |
+//! | `serde`                      | Enables (de)serialization of certain types using [`serde`].    |
+//! | `runtime-shader-compilation` | Enables compiling GLSL shaders at runtime using [`shaderc`].   |
 //!
 //! [`VulkanLibrary`]: crate::VulkanLibrary
 //! [`Instance`]: crate::instance::Instance
@@ -125,6 +126,7 @@
 //! [`Framebuffer`]: crate::render_pass::Framebuffer
 //! [`vulkano-macros`]: vulkano_macros
 //! [`serde`]: https://crates.io/crates/serde
+//! [`shaderc`]: https://crates.io/crates/shaderc
 
 //#![warn(missing_docs)]        // TODO: activate
 #![warn(
@@ -302,6 +304,10 @@ impl Display for VulkanError {
         let msg = match self {
             VulkanError::NotReady => "a resource is not yet ready",
             VulkanError::Timeout => "an operation has not completed in the specified time",
+            VulkanError::PipelineCompileRequired => {
+                "creating a pipeline requires compilation, but \
+                `PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED` was specified"
+            }
             VulkanError::OutOfHostMemory => "a host memory allocation has failed",
             VulkanError::OutOfDeviceMemory => "a device memory allocation has failed",
             VulkanError::InitializationFailed => {
@@ -666,6 +666,19 @@ pub struct AllocationCreateInfo {
     /// The default value is [`MemoryAllocatePreference::Unknown`].
     pub allocate_preference: MemoryAllocatePreference,
 
+    /// If set, forces the allocation into a memory type that has all of these property flags,
+    /// regardless of what `memory_type_filter` would otherwise choose.
+    ///
+    /// This is intended as a debugging aid. For example, setting this to
+    /// `HOST_VISIBLE | HOST_COHERENT` allows mapping a normally device-local buffer in order to
+    /// inspect its contents, without otherwise changing the code that creates it. The flags are
+    /// added to the required flags of `memory_type_filter`, and the preferences of the filter
+    /// are only used to choose among the memory types that have all of them. If no memory type
+    /// has all of the flags, allocating returns [`MemoryAllocatorError::FindMemoryType`].
+    ///
+    /// The default value is [`None`].
+    pub force_property_flags: Option<MemoryPropertyFlags>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -676,6 +689,7 @@ impl Default for AllocationCreateInfo {
             memory_type_filter: MemoryTypeFilter::PREFER_DEVICE,
             memory_type_bits: u32::MAX,
             allocate_preference: MemoryAllocatePreference::Unknown,
+            force_property_flags: None,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1282,12 +1296,18 @@ unsafe impl<S: Suballocator + Send + 'static> MemoryAllocator for GenericMemoryA
         memory_type_bits &= create_info.memory_type_bits;
 
         let AllocationCreateInfo {
-            memory_type_filter,
+            mut memory_type_filter,
             memory_type_bits: _,
             allocate_preference,
+            force_property_flags,
             _ne: _,
         } = create_info;
 
+        if let Some(flags) = force_property_flags {
+            memory_type_filter.required_flags |= flags;
+            memory_type_filter.not_preferred_flags -= flags;
+        }
+
         let size = layout.size();
 
         let mut memory_type_index = self
@@ -1564,6 +1584,230 @@ impl<S: Suballocator> Block<S> {
     }
 }
 
+/// A [memory allocator] whose placement of allocations only depends on the sequence of
+/// allocations and deallocations made with it.
+///
+/// Making the same sequence of allocations with the same device always results in the same memory
+/// types, `DeviceMemory` blocks and offsets, which makes this allocator useful for reproducible
+/// tests. It is built on the same pools and [`FreeListAllocator`] as the
+/// [`StandardMemoryAllocator`], but avoids everything that makes the latter adapt to runtime
+/// conditions:
+///
+/// - Blocks are searched in the order they were created, rather than by how much free space they
+///   have left.
+/// - Every block has exactly the size given when creating the allocator. If allocating a block
+///   fails, an error is returned instead of retrying with a smaller size.
+/// - A dedicated allocation is only made if the resource requires one, if the allocation is
+///   larger than the block size, or if [`MemoryAllocatePreference::AlwaysAllocate`] is used. The
+///   implementation's preference for a dedicated allocation, and the number of allocations
+///   already made on the device, are ignored.
+/// - If allocating from the chosen memory type fails, other memory types are not tried.
+///
+/// The allocator is less efficient than the `StandardMemoryAllocator` and is not meant to be used
+/// outside of testing.
+///
+/// [memory allocator]: MemoryAllocator
+#[derive(Debug)]
+pub struct DeterministicMemoryAllocator {
+    inner: StandardMemoryAllocator,
+}
+
+impl DeterministicMemoryAllocator {
+    /// Creates a new `DeterministicMemoryAllocator`, which allocates `DeviceMemory` blocks of
+    /// `block_size` bytes for all memory types.
+    ///
+    /// Memory types that are lazily allocated, protected, device-coherent or RDMA-capable are not
+    /// used, as with [`StandardMemoryAllocator::new_default`].
+    pub fn new(device: Arc<Device>, block_size: DeviceSize) -> Result<Self, Box<ValidationError>> {
+        let memory_types = &device.physical_device().memory_properties().memory_types;
+        let block_sizes = vec![block_size; memory_types.len()];
+        let mut memory_type_bits = u32::MAX;
+
+        for (index, memory_type) in memory_types.iter().enumerate() {
+            if memory_type.property_flags.intersects(
+                MemoryPropertyFlags::LAZILY_ALLOCATED
+                    | MemoryPropertyFlags::PROTECTED
+                    | MemoryPropertyFlags::DEVICE_COHERENT
+                    | MemoryPropertyFlags::RDMA_CAPABLE,
+            ) {
+                memory_type_bits &= !(1 << index);
+            }
+        }
+
+        let inner = GenericMemoryAllocator::new(
+            device,
+            GenericMemoryAllocatorCreateInfo {
+                block_sizes: &block_sizes,
+                memory_type_bits,
+                dedicated_allocation: false,
+                ..Default::default()
+            },
+        )?;
+
+        Ok(DeterministicMemoryAllocator { inner })
+    }
+}
+
+unsafe impl MemoryAllocator for DeterministicMemoryAllocator {
+    fn find_memory_type_index(
+        &self,
+        memory_type_bits: u32,
+        filter: MemoryTypeFilter,
+    ) -> Option<u32> {
+        self.inner.find_memory_type_index(memory_type_bits, filter)
+    }
+
+    /// Allocates memory from a specific memory type.
+    ///
+    /// The allocation is made in the first block, in creation order, that has room for it. If no
+    /// block has room and `never_allocate` is `false`, a new block is allocated.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if `memory_type_index` is not less than the number of available memory types.
+    fn allocate_from_type(
+        &self,
+        memory_type_index: u32,
+        mut layout: DeviceLayout,
+        allocation_type: AllocationType,
+        never_allocate: bool,
+    ) -> Result<MemoryAlloc, MemoryAllocatorError> {
+        let pool = &self.inner.pools[memory_type_index as usize];
+
+        if layout.size() > pool.block_size {
+            return Err(MemoryAllocatorError::BlockSizeExceeded);
+        }
+
+        layout = layout.align_to(pool.atom_size).unwrap();
+
+        let mut blocks = pool.blocks.lock();
+
+        for block in blocks.iter_mut() {
+            if let Ok(allocation) =
+                block.allocate(layout, allocation_type, self.inner.buffer_image_granularity)
+            {
+                return Ok(allocation);
+            }
+        }
+
+        if never_allocate {
+            return Err(MemoryAllocatorError::OutOfPoolMemory);
+        }
+
+        let device_memory = self
+            .inner
+            .allocate_device_memory(
+                memory_type_index,
+                pool.block_size,
+                None,
+                ExternalMemoryHandleTypes::empty(),
+            )
+            .map_err(MemoryAllocatorError::AllocateDeviceMemory)?;
+        blocks.push(Block::new(device_memory));
+        let block = blocks.last_mut().unwrap();
+
+        match block.allocate(layout, allocation_type, self.inner.buffer_image_granularity) {
+            Ok(allocation) => Ok(allocation),
+            // This can't happen as the block is at least as large as the allocation.
+            Err(SuballocatorError::OutOfRegionMemory) => unreachable!(),
+            // This can't happen as the block is new and we're still holding an exclusive lock.
+            Err(SuballocatorError::FragmentedRegion) => unreachable!(),
+        }
+    }
+
+    fn allocate(
+        &self,
+        requirements: MemoryRequirements,
+        allocation_type: AllocationType,
+        create_info: AllocationCreateInfo,
+        dedicated_allocation: Option<DedicatedAllocation<'_>>,
+    ) -> Result<MemoryAlloc, MemoryAllocatorError> {
+        let MemoryRequirements {
+            layout,
+            memory_type_bits,
+            prefers_dedicated_allocation: _,
+            requires_dedicated_allocation,
+        } = requirements;
+
+        let AllocationCreateInfo {
+            mut memory_type_filter,
+            memory_type_bits: allowed_memory_type_bits,
+            allocate_preference,
+            force_property_flags,
+            _ne: _,
+        } = create_info;
+
+        if let Some(flags) = force_property_flags {
+            memory_type_filter.required_flags |= flags;
+            memory_type_filter.not_preferred_flags -= flags;
+        }
+
+        let memory_type_index = self
+            .find_memory_type_index(
+                memory_type_bits & self.inner.memory_type_bits & allowed_memory_type_bits,
+                memory_type_filter,
+            )
+            .ok_or(MemoryAllocatorError::FindMemoryType)?;
+        let block_size = self.inner.pools[memory_type_index as usize].block_size;
+        let size = layout.size();
+
+        match allocate_preference {
+            MemoryAllocatePreference::Unknown => {
+                // VUID-vkBindBufferMemory-buffer-01444
+                // VUID-vkBindImageMemory-image-01445
+                if requires_dedicated_allocation || size > block_size {
+                    self.allocate_dedicated(
+                        memory_type_index,
+                        size,
+                        dedicated_allocation,
+                        ExternalMemoryHandleTypes::empty(),
+                    )
+                } else {
+                    self.allocate_from_type(memory_type_index, layout, allocation_type, false)
+                }
+            }
+            MemoryAllocatePreference::NeverAllocate => {
+                if requires_dedicated_allocation {
+                    return Err(MemoryAllocatorError::DedicatedAllocationRequired);
+                }
+
+                self.allocate_from_type(memory_type_index, layout, allocation_type, true)
+            }
+            MemoryAllocatePreference::AlwaysAllocate => self.allocate_dedicated(
+                memory_type_index,
+                size,
+                dedicated_allocation,
+                ExternalMemoryHandleTypes::empty(),
+            ),
+        }
+    }
+
+    fn allocate_dedicated(
+        &self,
+        memory_type_index: u32,
+        allocation_size: DeviceSize,
+        dedicated_allocation: Option<DedicatedAllocation<'_>>,
+        export_handle_types: ExternalMemoryHandleTypes,
+    ) -> Result<MemoryAlloc, MemoryAllocatorError> {
+        self.inner.allocate_dedicated(
+            memory_type_index,
+            allocation_size,
+            dedicated_allocation,
+            export_handle_types,
+        )
+    }
+
+    unsafe fn deallocate(&self, allocation: MemoryAlloc) {
+        self.inner.deallocate(allocation)
+    }
+}
+
+unsafe impl DeviceOwned for DeterministicMemoryAllocator {
+    fn device(&self) -> &Arc<Device> {
+        self.inner.device()
+    }
+}
+
 /// Parameters to create a new [`GenericMemoryAllocator`].
 #[derive(Clone, Debug)]
 pub struct GenericMemoryAllocatorCreateInfo<'a> {
@@ -1726,6 +1970,116 @@ pub(crate) const fn align_down(val: DeviceSize, alignment: DeviceAlignment) -> D
     val & !(alignment.as_devicesize() - 1)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::{
+        AllocationCreateInfo, AllocationType, DeterministicMemoryAllocator, DeviceLayout,
+        MemoryAllocator, MemoryAllocatorError, MemoryTypeFilter, StandardMemoryAllocator,
+    };
+    use crate::{
+        memory::{MemoryPropertyFlags, MemoryRequirements},
+        VulkanObject,
+    };
+
+    #[test]
+    fn deterministic_placement() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // Returns the block index, offset and size of each allocation made by the sequence.
+        let run = || {
+            let allocator = DeterministicMemoryAllocator::new(device.clone(), 1 << 20).unwrap();
+            let memory_type_index = allocator
+                .find_memory_type_index(u32::MAX, MemoryTypeFilter::PREFER_DEVICE)
+                .unwrap();
+            let mut blocks = Vec::new();
+            let mut allocations = Vec::new();
+            let mut placements = Vec::new();
+
+            for i in 0..64u64 {
+                let layout = DeviceLayout::from_size_alignment(1000 + i * 4096, 256).unwrap();
+                let allocation = allocator
+                    .allocate_from_type(memory_type_index, layout, AllocationType::Linear, false)
+                    .unwrap();
+
+                let handle = allocation.device_memory.handle();
+                let block_index = match blocks.iter().position(|&h| h == handle) {
+                    Some(index) => index,
+                    None => {
+                        blocks.push(handle);
+                        blocks.len() - 1
+                    }
+                };
+                let suballocation = allocation.suballocation.as_ref().unwrap();
+                placements.push((block_index, suballocation.offset, suballocation.size));
+
+                // Free every third allocation, to create holes that later allocations can fill.
+                if i % 3 == 0 {
+                    unsafe { allocator.deallocate(allocation) };
+                } else {
+                    allocations.push(allocation);
+                }
+            }
+
+            for allocation in allocations {
+                unsafe { allocator.deallocate(allocation) };
+            }
+
+            placements
+        };
+
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn force_property_flags() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let allocator = StandardMemoryAllocator::new_default(device.clone());
+        let memory_types = &device.physical_device().memory_properties().memory_types;
+        let requirements = MemoryRequirements {
+            layout: DeviceLayout::from_size_alignment(1024, 16).unwrap(),
+            memory_type_bits: u32::MAX,
+            prefers_dedicated_allocation: false,
+            requires_dedicated_allocation: false,
+        };
+
+        let forced = MemoryPropertyFlags::HOST_VISIBLE | MemoryPropertyFlags::HOST_COHERENT;
+        let allocation = allocator
+            .allocate(
+                requirements,
+                AllocationType::Linear,
+                AllocationCreateInfo {
+                    memory_type_filter: MemoryTypeFilter::PREFER_DEVICE,
+                    force_property_flags: Some(forced),
+                    ..Default::default()
+                },
+                None,
+            )
+            .unwrap();
+        let memory_type_index = allocation.device_memory.memory_type_index();
+        assert!(memory_types[memory_type_index as usize]
+            .property_flags
+            .contains(forced));
+        unsafe { allocator.deallocate(allocation) };
+
+        // Protected memory types can never be host-visible.
+        assert!(matches!(
+            allocator.allocate(
+                requirements,
+                AllocationType::Linear,
+                AllocationCreateInfo {
+                    force_property_flags: Some(
+                        MemoryPropertyFlags::HOST_VISIBLE | MemoryPropertyFlags::PROTECTED,
+                    ),
+                    ..Default::default()
+                },
+                None,
+            ),
+            Err(MemoryAllocatorError::FindMemoryType),
+        ));
+    }
+}
+
 mod array_vec {
     use std::ops::{Deref, DerefMut};
 
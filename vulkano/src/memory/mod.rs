@@ -97,9 +97,12 @@ use self::allocator::{
 };
 pub use self::{alignment::*, device_memory::*};
 use crate::{
-    buffer::{sys::RawBuffer, Subbuffer},
+    buffer::{sys::RawBuffer, Buffer, BufferCreateFlags, Subbuffer},
     device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
-    image::{sys::RawImage, Image, ImageAspects},
+    image::{
+        mip_level_extent, sys::RawImage, Image, ImageAspects, ImageCreateFlags, ImageMemory,
+        SparseImageMemoryRequirements,
+    },
     macros::vulkan_bitflags,
     sync::{semaphore::Semaphore, HostAccessError},
     DeviceSize, Validated, ValidationError, VulkanError,
@@ -792,6 +795,91 @@ impl Default for BindSparseInfo {
     }
 }
 
+impl BindSparseInfo {
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            ref wait_semaphores,
+            ref buffer_binds,
+            ref image_opaque_binds,
+            ref image_binds,
+            ref signal_semaphores,
+            _ne: _,
+        } = self;
+
+        for semaphore in wait_semaphores {
+            // VUID-VkBindSparseInfo-commonparent
+            assert_eq!(device, semaphore.device().as_ref());
+        }
+
+        for (index, (buffer, memory_binds)) in buffer_binds.iter().enumerate() {
+            let buffer = buffer.buffer();
+
+            // VUID-VkSparseBufferMemoryBindInfo-buffer-parameter
+            assert_eq!(device, buffer.device().as_ref());
+
+            if !buffer.flags().intersects(BufferCreateFlags::SPARSE_BINDING) {
+                return Err(Box::new(ValidationError {
+                    context: format!("buffer_binds[{}].0.buffer().flags()", index).into(),
+                    problem: "does not contain `BufferCreateFlags::SPARSE_BINDING`".into(),
+                    ..Default::default()
+                }));
+            }
+
+            for (bind_index, memory_bind) in memory_binds.iter().enumerate() {
+                memory_bind.validate(device, buffer).map_err(|err| {
+                    err.add_context(format!("buffer_binds[{}].1[{}]", index, bind_index))
+                })?;
+            }
+        }
+
+        for (index, (image, memory_binds)) in image_opaque_binds.iter().enumerate() {
+            // VUID-VkSparseImageOpaqueMemoryBindInfo-image-parameter
+            assert_eq!(device, image.device().as_ref());
+
+            if !image.flags().intersects(ImageCreateFlags::SPARSE_BINDING) {
+                return Err(Box::new(ValidationError {
+                    context: format!("image_opaque_binds[{}].0.flags()", index).into(),
+                    problem: "does not contain `ImageCreateFlags::SPARSE_BINDING`".into(),
+                    ..Default::default()
+                }));
+            }
+
+            for (bind_index, memory_bind) in memory_binds.iter().enumerate() {
+                memory_bind.validate(device, image).map_err(|err| {
+                    err.add_context(format!("image_opaque_binds[{}].1[{}]", index, bind_index))
+                })?;
+            }
+        }
+
+        for (index, (image, memory_binds)) in image_binds.iter().enumerate() {
+            // VUID-VkSparseImageMemoryBindInfo-image-parameter
+            assert_eq!(device, image.device().as_ref());
+
+            if !image.flags().intersects(ImageCreateFlags::SPARSE_RESIDENCY) {
+                return Err(Box::new(ValidationError {
+                    context: format!("image_binds[{}].0.flags()", index).into(),
+                    problem: "does not contain `ImageCreateFlags::SPARSE_RESIDENCY`".into(),
+                    vuids: &["VUID-VkSparseImageMemoryBindInfo-image-02901"],
+                    ..Default::default()
+                }));
+            }
+
+            for (bind_index, memory_bind) in memory_binds.iter().enumerate() {
+                memory_bind.validate(device, image).map_err(|err| {
+                    err.add_context(format!("image_binds[{}].1[{}]", index, bind_index))
+                })?;
+            }
+        }
+
+        for semaphore in signal_semaphores {
+            // VUID-VkBindSparseInfo-commonparent
+            assert_eq!(device, semaphore.device().as_ref());
+        }
+
+        Ok(())
+    }
+}
+
 /// Parameters for a single sparse bind operation on a buffer.
 #[derive(Clone, Debug, Default)]
 pub struct SparseBufferMemoryBind {
@@ -814,6 +902,29 @@ pub struct SparseBufferMemoryBind {
     pub memory: Option<(Arc<DeviceMemory>, DeviceSize)>,
 }
 
+impl SparseBufferMemoryBind {
+    pub(crate) fn validate(
+        &self,
+        device: &Device,
+        buffer: &Buffer,
+    ) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            offset,
+            size,
+            ref memory,
+        } = self;
+
+        validate_sparse_memory_bind(
+            device,
+            offset,
+            size,
+            memory.as_ref(),
+            buffer.memory_requirements().layout.size(),
+            buffer.memory_requirements(),
+        )
+    }
+}
+
 /// Parameters for a single sparse bind operation on parts of an image with an opaque memory layout.
 ///
 /// This type of sparse bind should be used for mip tail regions, the metadata aspect, and for the
@@ -845,6 +956,107 @@ pub struct SparseImageOpaqueMemoryBind {
     pub metadata: bool,
 }
 
+impl SparseImageOpaqueMemoryBind {
+    pub(crate) fn validate(
+        &self,
+        device: &Device,
+        image: &Image,
+    ) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            offset,
+            size,
+            ref memory,
+            metadata,
+        } = self;
+
+        let memory_requirements = &image.memory_requirements()[0];
+
+        if metadata
+            && !sparse_memory_requirements(image)
+                .iter()
+                .any(|requirements| {
+                    requirements
+                        .format_properties
+                        .aspects
+                        .intersects(ImageAspects::METADATA)
+                })
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`metadata` is `true`, but the image does not have a metadata aspect"
+                    .into(),
+                vuids: &["VUID-VkSparseMemoryBind-flags-00998"],
+                ..Default::default()
+            }));
+        }
+
+        validate_sparse_memory_bind(
+            device,
+            offset,
+            size,
+            memory.as_ref(),
+            memory_requirements.layout.size(),
+            memory_requirements,
+        )
+    }
+}
+
+fn validate_sparse_memory_bind(
+    device: &Device,
+    offset: DeviceSize,
+    size: DeviceSize,
+    memory: Option<&(Arc<DeviceMemory>, DeviceSize)>,
+    resource_size: DeviceSize,
+    memory_requirements: &MemoryRequirements,
+) -> Result<(), Box<ValidationError>> {
+    if size == 0 {
+        return Err(Box::new(ValidationError {
+            context: "size".into(),
+            problem: "is zero".into(),
+            vuids: &["VUID-VkSparseMemoryBind-size-01098"],
+            ..Default::default()
+        }));
+    }
+
+    if offset >= resource_size {
+        return Err(Box::new(ValidationError {
+            problem: "`offset` is not less than the size of the resource".into(),
+            vuids: &["VUID-VkSparseMemoryBind-resourceOffset-01099"],
+            ..Default::default()
+        }));
+    }
+
+    if size > resource_size - offset {
+        return Err(Box::new(ValidationError {
+            problem: "`offset + size` is greater than the size of the resource".into(),
+            vuids: &["VUID-VkSparseMemoryBind-size-01100"],
+            ..Default::default()
+        }));
+    }
+
+    if !is_aligned(offset, memory_requirements.layout.alignment()) {
+        return Err(Box::new(ValidationError {
+            problem: "`offset` is not a multiple of the sparse block size of the resource".into(),
+            ..Default::default()
+        }));
+    }
+
+    if !(is_aligned(size, memory_requirements.layout.alignment()) || offset + size == resource_size)
+    {
+        return Err(Box::new(ValidationError {
+            problem: "`size` is not a multiple of the sparse block size of the resource, and \
+                `offset + size` does not equal the size of the resource"
+                .into(),
+            ..Default::default()
+        }));
+    }
+
+    if let Some((memory, memory_offset)) = memory {
+        validate_sparse_memory(device, size, memory, *memory_offset, memory_requirements)?;
+    }
+
+    Ok(())
+}
+
 /// Parameters for a single sparse bind operation on parts of an image with a known memory layout.
 ///
 /// This type of sparse bind can only be used for images that have the `sparse_residency` flag set.
@@ -897,6 +1109,227 @@ pub struct SparseImageMemoryBind {
     pub memory: Option<(Arc<DeviceMemory>, DeviceSize)>,
 }
 
+impl SparseImageMemoryBind {
+    pub(crate) fn validate(
+        &self,
+        device: &Device,
+        image: &Image,
+    ) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            aspects,
+            mip_level,
+            array_layer,
+            offset,
+            extent,
+            ref memory,
+        } = self;
+
+        aspects.validate_device(device).map_err(|err| {
+            err.add_context("aspects")
+                .set_vuids(&["VUID-VkImageSubresource-aspectMask-parameter"])
+        })?;
+
+        if aspects.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "aspects".into(),
+                problem: "is empty".into(),
+                vuids: &["VUID-VkImageSubresource-aspectMask-requiredbitmask"],
+                ..Default::default()
+            }));
+        }
+
+        if mip_level >= image.mip_levels() {
+            return Err(Box::new(ValidationError {
+                problem: "`mip_level` is not less than the number of mip levels in the image"
+                    .into(),
+                vuids: &["VUID-VkSparseImageMemoryBindInfo-subresource-01722"],
+                ..Default::default()
+            }));
+        }
+
+        if array_layer >= image.array_layers() {
+            return Err(Box::new(ValidationError {
+                problem: "`array_layer` is not less than the number of array layers in the image"
+                    .into(),
+                vuids: &["VUID-VkSparseImageMemoryBindInfo-subresource-01723"],
+                ..Default::default()
+            }));
+        }
+
+        let sparse_memory_requirements = sparse_memory_requirements(image);
+        let format_properties = match sparse_memory_requirements
+            .iter()
+            .find(|requirements| requirements.format_properties.aspects.contains(aspects))
+        {
+            Some(requirements) => &requirements.format_properties,
+            None => {
+                return Err(Box::new(ValidationError {
+                    problem: "the sparse memory requirements of the image do not contain an \
+                        element whose aspects contain `aspects`"
+                        .into(),
+                    vuids: &["VUID-VkSparseImageMemoryBindInfo-subresource-01106"],
+                    ..Default::default()
+                }));
+            }
+        };
+
+        let subresource_extent = mip_level_extent(image.extent(), mip_level).unwrap();
+        let granularity = format_properties.image_granularity;
+
+        for i in 0..3 {
+            if extent[i] == 0 {
+                return Err(Box::new(ValidationError {
+                    context: format!("extent[{}]", i).into(),
+                    problem: "is zero".into(),
+                    vuids: &[
+                        "VUID-VkSparseImageMemoryBind-extent-09388",
+                        "VUID-VkSparseImageMemoryBind-extent-09389",
+                        "VUID-VkSparseImageMemoryBind-extent-09390",
+                    ],
+                    ..Default::default()
+                }));
+            }
+
+            if offset[i] % granularity[i] != 0 {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`offset[{0}]` is not a multiple of `image_granularity[{0}]` of the \
+                        sparse memory requirements of the image",
+                        i,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkSparseImageMemoryBind-offset-01107",
+                        "VUID-VkSparseImageMemoryBind-offset-01109",
+                        "VUID-VkSparseImageMemoryBind-offset-01111",
+                    ],
+                    ..Default::default()
+                }));
+            }
+
+            let end = match offset[i].checked_add(extent[i]) {
+                Some(end) => end,
+                None => {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`offset[{0}] + extent[{0}]` is greater than `u32::MAX`",
+                            i
+                        )
+                        .into(),
+                        ..Default::default()
+                    }));
+                }
+            };
+
+            if end > subresource_extent[i] {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`offset[{0}] + extent[{0}]` is greater than the extent of the \
+                        subresource",
+                        i,
+                    )
+                    .into(),
+                    ..Default::default()
+                }));
+            }
+
+            if !(extent[i] % granularity[i] == 0 || end == subresource_extent[i]) {
+                return Err(Box::new(ValidationError {
+                    problem: format!(
+                        "`extent[{0}]` is not a multiple of `image_granularity[{0}]` of the \
+                        sparse memory requirements of the image, and `offset[{0}] + \
+                        extent[{0}]` does not equal the extent of the subresource",
+                        i,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkSparseImageMemoryBind-extent-01108",
+                        "VUID-VkSparseImageMemoryBind-extent-01110",
+                        "VUID-VkSparseImageMemoryBind-extent-01112",
+                    ],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if let Some((memory, memory_offset)) = memory {
+            let memory_requirements = &image.memory_requirements()[0];
+            let block_size = memory_requirements.layout.alignment().as_devicesize();
+            let block_count = (0..3)
+                .map(|i| extent[i].div_ceil(granularity[i]) as DeviceSize)
+                .product::<DeviceSize>();
+
+            validate_sparse_memory(
+                device,
+                block_count * block_size,
+                memory,
+                *memory_offset,
+                memory_requirements,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn sparse_memory_requirements(image: &Image) -> &[SparseImageMemoryRequirements] {
+    match image.memory() {
+        ImageMemory::Sparse(sparse_memory_requirements) => sparse_memory_requirements,
+        ImageMemory::Normal(_) | ImageMemory::Swapchain { .. } => &[],
+    }
+}
+
+fn validate_sparse_memory(
+    device: &Device,
+    size: DeviceSize,
+    memory: &DeviceMemory,
+    memory_offset: DeviceSize,
+    memory_requirements: &MemoryRequirements,
+) -> Result<(), Box<ValidationError>> {
+    // VUID-VkSparseMemoryBind-memory-parameter
+    assert_eq!(device, memory.device().as_ref());
+
+    if memory_requirements.memory_type_bits & (1 << memory.memory_type_index()) == 0 {
+        return Err(Box::new(ValidationError {
+            problem: "`memory.0.memory_type_index()` is not a bit set in the \
+                `memory_type_bits` of the memory requirements of the resource"
+                .into(),
+            vuids: &["VUID-VkSparseMemoryBind-memory-01096"],
+            ..Default::default()
+        }));
+    }
+
+    if !is_aligned(memory_offset, memory_requirements.layout.alignment()) {
+        return Err(Box::new(ValidationError {
+            problem: "`memory.1` is not aligned according to the `alignment` of the memory \
+                requirements of the resource"
+                .into(),
+            vuids: &["VUID-VkSparseMemoryBind-memory-01096"],
+            ..Default::default()
+        }));
+    }
+
+    if memory_offset >= memory.allocation_size() {
+        return Err(Box::new(ValidationError {
+            problem: "`memory.1` is not less than `memory.0.allocation_size()`".into(),
+            vuids: &["VUID-VkSparseMemoryBind-memoryOffset-01101"],
+            ..Default::default()
+        }));
+    }
+
+    if size > memory.allocation_size() - memory_offset {
+        return Err(Box::new(ValidationError {
+            problem: "the size of the bound region is greater than \
+                `memory.0.allocation_size() - memory.1`"
+                .into(),
+            vuids: &["VUID-VkSparseMemoryBind-size-01102"],
+            ..Default::default()
+        }));
+    }
+
+    Ok(())
+}
+
 #[inline(always)]
 pub(crate) fn is_aligned(offset: DeviceSize, alignment: DeviceAlignment) -> bool {
     offset & (alignment.as_devicesize() - 1) == 0
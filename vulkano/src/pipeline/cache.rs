@@ -98,6 +98,135 @@ impl PipelineCache {
         Ok(Self::new_unchecked(device, create_info)?)
     }
 
+    /// Builds a new pipeline cache from data that was previously retrieved using
+    /// [`get_data`](PipelineCache::get_data), possibly on a different system.
+    ///
+    /// Unlike [`new`](PipelineCache::new), the header of `data` is checked against the properties
+    /// of `device` before the data is handed to the Vulkan implementation. If the data was
+    /// produced by a different driver or physical device, an error is returned that explains the
+    /// mismatch, instead of the implementation silently discarding the data. If `data` is empty,
+    /// an empty cache is created.
+    ///
+    /// # Safety
+    ///
+    /// - Only the header of `data` is checked. The remainder of `data` must be valid data that
+    ///   was previously retrieved using [`get_data`](PipelineCache::get_data).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use vulkano::device::Device;
+    /// use vulkano::pipeline::cache::PipelineCache;
+    /// # let device: Arc<Device> = return;
+    ///
+    /// let data = std::fs::read("pipeline_cache.bin").unwrap_or_default();
+    ///
+    /// // Fall back to an empty cache if the stored data is unusable, for example after a driver
+    /// // update.
+    /// let cache = unsafe {
+    ///     PipelineCache::new_from_bytes(device.clone(), &data)
+    ///         .or_else(|_| PipelineCache::new(device.clone(), Default::default()))
+    ///         .unwrap()
+    /// };
+    /// ```
+    pub unsafe fn new_from_bytes(
+        device: Arc<Device>,
+        data: &[u8],
+    ) -> Result<Arc<PipelineCache>, Validated<VulkanError>> {
+        Self::validate_data_header(&device, data).map_err(|err| err.add_context("data"))?;
+
+        Self::new(
+            device,
+            PipelineCacheCreateInfo {
+                initial_data: data.to_vec(),
+                ..Default::default()
+            },
+        )
+    }
+
+    fn validate_data_header(device: &Device, data: &[u8]) -> Result<(), Box<ValidationError>> {
+        if data.is_empty() {
+            return Ok(());
+        }
+
+        // The header is laid out as `VkPipelineCacheHeaderVersionOne`, with all fields stored
+        // least significant byte first.
+        const HEADER_SIZE: usize = 32;
+
+        if data.len() < HEADER_SIZE {
+            return Err(Box::new(ValidationError {
+                problem: "is too short to contain a pipeline cache header".into(),
+                ..Default::default()
+            }));
+        }
+
+        let read_u32 =
+            |offset: usize| u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap());
+
+        let header_size = read_u32(0);
+
+        if (header_size as usize) < HEADER_SIZE || header_size as usize > data.len() {
+            return Err(Box::new(ValidationError {
+                problem: format!("has an invalid header size of {}", header_size).into(),
+                ..Default::default()
+            }));
+        }
+
+        let header_version = read_u32(4);
+
+        if header_version != ash::vk::PipelineCacheHeaderVersion::ONE.as_raw() as u32 {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "has header version {}, which is not supported",
+                    header_version
+                )
+                .into(),
+                ..Default::default()
+            }));
+        }
+
+        let properties = device.physical_device().properties();
+        let vendor_id = read_u32(8);
+
+        if vendor_id != properties.vendor_id {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "was created with vendor ID {:#x}, but the vendor ID of the physical device \
+                    is {:#x}",
+                    vendor_id, properties.vendor_id,
+                )
+                .into(),
+                ..Default::default()
+            }));
+        }
+
+        let device_id = read_u32(12);
+
+        if device_id != properties.device_id {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "was created with device ID {:#x}, but the device ID of the physical device \
+                    is {:#x}",
+                    device_id, properties.device_id,
+                )
+                .into(),
+                ..Default::default()
+            }));
+        }
+
+        if data[16..32] != properties.pipeline_cache_uuid {
+            return Err(Box::new(ValidationError {
+                problem: "was created with a different pipeline cache UUID than that of the \
+                    physical device; the driver or physical device has likely changed"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+
     fn validate_new(
         device: &Device,
         create_info: &PipelineCacheCreateInfo,
@@ -245,9 +374,27 @@ impl PipelineCache {
     /// Merges other pipeline caches into this one.
     ///
     /// It is `self` that is modified here. The pipeline caches passed as parameter are untouched.
+    /// This can be used to combine the caches that were filled by several threads compiling
+    /// pipelines in parallel, before storing the data of the combined cache.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if any element of `src_caches` does not belong to the same device as `self`.
     ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use vulkano::pipeline::cache::PipelineCache;
+    /// # let master_cache: Arc<PipelineCache> = return;
+    /// # let worker_caches: Vec<Arc<PipelineCache>> = return;
+    /// master_cache
+    ///     .merge(worker_caches.iter().map(AsRef::as_ref))
+    ///     .unwrap();
+    ///
+    /// let data = master_cache.get_data().unwrap();
+    /// ```
     // FIXME: vkMergePipelineCaches is not thread safe for the destination cache
-    // TODO: write example
     pub fn merge<'a>(
         &self,
         src_caches: impl IntoIterator<Item = &'a PipelineCache>,
@@ -260,6 +407,9 @@ impl PipelineCache {
 
     fn validate_merge(&self, src_caches: &[&PipelineCache]) -> Result<(), Box<ValidationError>> {
         for (index, &src_cache) in src_caches.iter().enumerate() {
+            // VUID-vkMergePipelineCaches-pSrcCaches-parent
+            assert_eq!(self.device(), src_cache.device());
+
             if src_cache == self {
                 return Err(Box::new(ValidationError {
                     context: format!("src_caches[{}]", index).into(),
@@ -399,6 +549,7 @@ mod tests {
         },
         shader::{ShaderModule, ShaderModuleCreateInfo},
     };
+    use std::sync::Arc;
 
     #[test]
     fn merge_self_forbidden() {
@@ -549,6 +700,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn merge_caches() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let build_pipeline = |cache: &Arc<PipelineCache>, code: &[u32]| {
+            let cs = unsafe {
+                let module =
+                    ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(code)).unwrap();
+                module.entry_point("main").unwrap()
+            };
+            let stage = PipelineShaderStageCreateInfo::new(cs);
+            let layout = PipelineLayout::new(
+                device.clone(),
+                PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
+                    .into_pipeline_layout_create_info(device.clone())
+                    .unwrap(),
+            )
+            .unwrap();
+            ComputePipeline::new(
+                device.clone(),
+                Some(cache.clone()),
+                ComputePipelineCreateInfo::stage_layout(stage, layout),
+            )
+            .unwrap()
+        };
+
+        /*
+         * #version 450
+         * void main() {
+         * }
+         */
+        const FIRST_MODULE: [u32; 48] = [
+            119734787, 65536, 524298, 6, 0, 131089, 1, 393227, 1, 1280527431, 1685353262,
+            808793134, 0, 196622, 0, 1, 327695, 5, 4, 1852399981, 0, 393232, 4, 17, 1, 1, 1,
+            196611, 2, 450, 262149, 4, 1852399981, 0, 131091, 2, 196641, 3, 2, 327734, 2, 4, 0, 3,
+            131320, 5, 65789, 65592,
+        ];
+
+        /*
+         * #version 450
+         *
+         * void main() {
+         *     uint idx = gl_GlobalInvocationID.x;
+         * }
+         */
+        const SECOND_MODULE: [u32; 108] = [
+            119734787, 65536, 524298, 16, 0, 131089, 1, 393227, 1, 1280527431, 1685353262,
+            808793134, 0, 196622, 0, 1, 393231, 5, 4, 1852399981, 0, 11, 393232, 4, 17, 1, 1, 1,
+            196611, 2, 450, 262149, 4, 1852399981, 0, 196613, 8, 7890025, 524293, 11, 1197436007,
+            1633841004, 1986939244, 1952539503, 1231974249, 68, 262215, 11, 11, 28, 131091, 2,
+            196641, 3, 2, 262165, 6, 32, 0, 262176, 7, 7, 6, 262167, 9, 6, 3, 262176, 10, 1, 9,
+            262203, 10, 11, 1, 262187, 6, 12, 0, 262176, 13, 1, 6, 327734, 2, 4, 0, 3, 131320, 5,
+            262203, 7, 8, 7, 327745, 13, 14, 11, 12, 262205, 6, 15, 14, 196670, 8, 15, 65789,
+            65592,
+        ];
+
+        let empty_data = unsafe { PipelineCache::new(device.clone(), Default::default()) }
+            .unwrap()
+            .get_data()
+            .unwrap();
+
+        let first_cache =
+            unsafe { PipelineCache::new(device.clone(), Default::default()).unwrap() };
+        let _first_pipeline = build_pipeline(&first_cache, &FIRST_MODULE);
+
+        let second_cache =
+            unsafe { PipelineCache::new(device.clone(), Default::default()).unwrap() };
+        let _second_pipeline = build_pipeline(&second_cache, &SECOND_MODULE);
+
+        let merged_cache = unsafe { PipelineCache::new(device, Default::default()).unwrap() };
+        merged_cache
+            .merge([first_cache.as_ref(), second_cache.as_ref()])
+            .unwrap();
+
+        let first_data = first_cache.get_data().unwrap();
+        let merged_data = merged_cache.get_data().unwrap();
+
+        // Implementations are not required to store anything in the cache.
+        if first_data.len() > empty_data.len() {
+            assert!(merged_data.len() > empty_data.len());
+            assert!(merged_data.len() >= first_data.len());
+        }
+    }
+
     #[test]
     fn cache_data_does_not_change() {
         let (device, _queue) = gfx_dev_and_queue!();
@@ -612,4 +847,32 @@ mod tests {
 
         assert_eq!(cache_data, second_data);
     }
+
+    #[test]
+    fn new_from_bytes_rejects_foreign_header() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        let cache = unsafe { PipelineCache::new(device.clone(), Default::default()).unwrap() };
+        let data = cache.get_data().unwrap();
+
+        unsafe {
+            PipelineCache::new_from_bytes(device.clone(), &data).unwrap();
+            PipelineCache::new_from_bytes(device.clone(), &[]).unwrap();
+        }
+
+        let mangle = |offset: usize| {
+            let mut data = data.clone();
+            data[offset] ^= 0xff;
+            data
+        };
+
+        // Header size, header version, vendor ID, device ID and pipeline cache UUID.
+        for offset in [3, 4, 8, 12, 16, 31] {
+            assert!(
+                unsafe { PipelineCache::new_from_bytes(device.clone(), &mangle(offset)) }.is_err()
+            );
+        }
+
+        assert!(unsafe { PipelineCache::new_from_bytes(device, &data[..16]) }.is_err());
+    }
 }
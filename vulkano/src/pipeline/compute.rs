@@ -22,17 +22,22 @@
 //! any descriptor sets and/or push constants that the pipeline needs, and then issuing a `dispatch`
 //! command on the command buffer.
 
-use super::{PipelineCreateFlags, PipelineShaderStageCreateInfo};
+use super::{
+    executable_statistics_unchecked, validate_descriptor_buffer_flag,
+    validate_executable_statistics, validate_protected_access_flags, PipelineCreateFlags,
+    PipelineCreationFeedback, PipelineCreationFeedbackVk, PipelineExecutableStatistics,
+    PipelineShaderStageCreateInfo,
+};
 use crate::{
     device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
     instance::InstanceOwnedDebugWrapper,
     macros::impl_id_counter,
     pipeline::{cache::PipelineCache, layout::PipelineLayout, Pipeline, PipelineBindPoint},
     shader::{DescriptorBindingRequirements, ShaderExecution, ShaderStage},
-    Validated, ValidationError, VulkanError, VulkanObject,
+    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
-use std::{ffi::CString, fmt::Debug, mem::MaybeUninit, num::NonZeroU64, ptr, sync::Arc};
+use std::{ffi::CString, fmt::Debug, mem::MaybeUninit, num::NonZeroU64, ptr, slice, sync::Arc};
 
 /// A pipeline object that describes to the Vulkan implementation how it should perform compute
 /// operations.
@@ -50,9 +55,12 @@ pub struct ComputePipeline {
 
     flags: PipelineCreateFlags,
     layout: DeviceOwnedDebugWrapper<Arc<PipelineLayout>>,
+    local_size: [u32; 3],
+    required_subgroup_size: Option<u32>,
 
     descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
     num_used_descriptor_sets: u32,
+    creation_feedback: Option<PipelineCreationFeedback>,
 }
 
 impl ComputePipeline {
@@ -167,7 +175,15 @@ impl ComputePipeline {
             };
         }
 
+        let mut creation_feedback_vk = PipelineCreationFeedbackVk::new(&device, 1);
+        let creation_feedback_create_info_vk = creation_feedback_vk
+            .as_mut()
+            .map(PipelineCreationFeedbackVk::create_info_vk);
+
         let create_infos_vk = ash::vk::ComputePipelineCreateInfo {
+            p_next: creation_feedback_create_info_vk
+                .as_ref()
+                .map_or(ptr::null(), |info| info as *const _ as *const _),
             flags: flags.into(),
             stage: stage_vk,
             layout: layout.handle(),
@@ -194,7 +210,15 @@ impl ComputePipeline {
             output.assume_init()
         };
 
-        Ok(Self::from_handle(device, handle, create_info))
+        let creation_feedback = creation_feedback_vk
+            .map(|feedback_vk| feedback_vk.to_feedback(slice::from_ref(&create_info.stage)));
+
+        Ok(Self::from_handle_with_creation_feedback(
+            device,
+            handle,
+            create_info,
+            creation_feedback,
+        ))
     }
 
     /// Creates a new `ComputePipeline` from a raw object handle.
@@ -208,6 +232,15 @@ impl ComputePipeline {
         device: Arc<Device>,
         handle: ash::vk::Pipeline,
         create_info: ComputePipelineCreateInfo,
+    ) -> Arc<ComputePipeline> {
+        Self::from_handle_with_creation_feedback(device, handle, create_info, None)
+    }
+
+    unsafe fn from_handle_with_creation_feedback(
+        device: Arc<Device>,
+        handle: ash::vk::Pipeline,
+        create_info: ComputePipelineCreateInfo,
+        creation_feedback: Option<PipelineCreationFeedback>,
     ) -> Arc<ComputePipeline> {
         let ComputePipelineCreateInfo {
             flags,
@@ -217,6 +250,11 @@ impl ComputePipeline {
             _ne: _,
         } = create_info;
 
+        let local_size = match stage.entry_point.info().execution {
+            ShaderExecution::Compute(execution) => execution.local_size,
+            _ => unreachable!(),
+        };
+        let required_subgroup_size = stage.required_subgroup_size;
         let descriptor_binding_requirements: HashMap<_, _> = stage
             .entry_point
             .info()
@@ -238,9 +276,12 @@ impl ComputePipeline {
 
             flags,
             layout: DeviceOwnedDebugWrapper(layout),
+            local_size,
+            required_subgroup_size,
 
             descriptor_binding_requirements,
             num_used_descriptor_sets,
+            creation_feedback,
         })
     }
 
@@ -255,6 +296,62 @@ impl ComputePipeline {
     pub fn flags(&self) -> PipelineCreateFlags {
         self.flags
     }
+
+    /// Returns the local size of the compute shader, which is the number of invocations in a
+    /// single workgroup in each dimension.
+    #[inline]
+    pub fn local_size(&self) -> [u32; 3] {
+        self.local_size
+    }
+
+    /// Returns the [`required_subgroup_size`] that the compute shader was created with.
+    ///
+    /// If this is `None`, the subgroup size that the implementation chose can be queried with
+    /// [`executable_statistics`].
+    ///
+    /// [`required_subgroup_size`]: PipelineShaderStageCreateInfo::required_subgroup_size
+    /// [`executable_statistics`]: Self::executable_statistics
+    #[inline]
+    pub fn required_subgroup_size(&self) -> Option<u32> {
+        self.required_subgroup_size
+    }
+
+    /// Returns the properties and statistics of the executables that the pipeline was compiled
+    /// into.
+    ///
+    /// The pipeline must have been created with [`PipelineCreateFlags::CAPTURE_STATISTICS`], and
+    /// the [`pipeline_executable_info`] feature must be enabled on the device.
+    ///
+    /// [`pipeline_executable_info`]: crate::device::Features::pipeline_executable_info
+    #[inline]
+    pub fn executable_statistics(
+        &self,
+    ) -> Result<Vec<PipelineExecutableStatistics>, Validated<VulkanError>> {
+        validate_executable_statistics(&self.device, self.flags)?;
+
+        unsafe { Ok(self.executable_statistics_unchecked()?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_statistics_unchecked(
+        &self,
+    ) -> Result<Vec<PipelineExecutableStatistics>, VulkanError> {
+        executable_statistics_unchecked(&self.device, self.handle)
+    }
+
+    /// Returns feedback about the creation of the pipeline, such as whether it was found in the
+    /// pipeline cache and how long it took to create.
+    ///
+    /// This is `None` if the pipeline was created with [`from_handle`](Self::from_handle), or if
+    /// the device API version is less than 1.3 and the [`ext_pipeline_creation_feedback`]
+    /// extension was not enabled on the device.
+    ///
+    /// [`ext_pipeline_creation_feedback`]: crate::device::DeviceExtensions::ext_pipeline_creation_feedback
+    #[inline]
+    pub fn creation_feedback(&self) -> Option<&PipelineCreationFeedback> {
+        self.creation_feedback.as_ref()
+    }
 }
 
 impl Pipeline for ComputePipeline {
@@ -366,6 +463,35 @@ impl ComputePipelineCreateInfo {
                 .set_vuids(&["VUID-VkComputePipelineCreateInfo-flags-parameter"])
         })?;
 
+        if flags.intersects(
+            PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED
+                | PipelineCreateFlags::EARLY_RETURN_ON_FAILURE,
+        ) && !device.enabled_features().pipeline_creation_cache_control
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED` or \
+                    `PipelineCreateFlags::EARLY_RETURN_ON_FAILURE`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "pipeline_creation_cache_control",
+                )])]),
+                vuids: &["VUID-VkComputePipelineCreateInfo-pipelineCreationCacheControl-02875"],
+            }));
+        }
+
+        if flags.intersects(PipelineCreateFlags::INDIRECT_BINDABLE) {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::INDIRECT_BINDABLE`".into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        validate_descriptor_buffer_flag(device, flags, layout)?;
+        validate_protected_access_flags(device, flags)?;
+
         stage
             .validate(device)
             .map_err(|err| err.add_context("stage"))?;
@@ -454,20 +580,34 @@ mod tests {
     use crate::{
         buffer::{Buffer, BufferCreateInfo, BufferUsage},
         command_buffer::{
-            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder, CommandBufferUsage,
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            CommandBufferUsage, DispatchIndirectCommand,
         },
         descriptor_set::{
-            allocator::StandardDescriptorSetAllocator, PersistentDescriptorSet, WriteDescriptorSet,
+            allocator::StandardDescriptorSetAllocator,
+            layout::{
+                DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateFlags,
+                DescriptorSetLayoutCreateInfo, DescriptorType,
+            },
+            PersistentDescriptorSet, WriteDescriptorSet,
         },
         memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
         pipeline::{
-            compute::ComputePipelineCreateInfo, layout::PipelineDescriptorSetLayoutCreateInfo,
-            ComputePipeline, Pipeline, PipelineBindPoint, PipelineLayout,
+            cache::PipelineCache,
+            compute::ComputePipelineCreateInfo,
+            layout::{PipelineDescriptorSetLayoutCreateInfo, PipelineLayoutCreateInfo},
+            ComputePipeline, Pipeline, PipelineBindPoint, PipelineCreateFlags,
+            PipelineCreationFeedbackFlags, PipelineLayout, PipelineShaderStageCreateFlags,
             PipelineShaderStageCreateInfo,
         },
-        shader::{ShaderModule, ShaderModuleCreateInfo},
-        sync::{now, GpuFuture},
+        query::{QueryPool, QueryPoolCreateInfo, QueryResultFlags, QueryType},
+        shader::{
+            ShaderExecution, ShaderModule, ShaderModuleCreateInfo, ShaderStage, ShaderStages,
+        },
+        sync::{now, GpuFuture, PipelineStage},
+        Validated, Version, VulkanError,
     };
+    use smallvec::smallvec;
     use std::sync::Arc;
 
     // TODO: test for basic creation
@@ -549,7 +689,7 @@ mod tests {
         )
         .unwrap();
 
-        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone());
+        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone(), Default::default());
         let set = PersistentDescriptorSet::new(
             &ds_allocator,
             pipeline.layout().set_layouts().get(0).unwrap().clone(),
@@ -590,65 +730,162 @@ mod tests {
     }
 
     #[test]
-    fn required_subgroup_size() {
-        // This test checks whether required_subgroup_size works.
-        // It executes a single compute shader (one invocation) that writes the subgroup size
-        // to a buffer. The buffer content is then checked for the right value.
+    fn dispatch_base() {
+        // This test checks whether dispatch_base offsets the workgroup IDs.
+        // It dispatches two workgroups starting at a base of 2, which each write their ID plus one
+        // to the buffer element indexed by their ID.
 
-        let (device, queue) = gfx_dev_and_queue!(subgroup_size_control);
+        let (device, queue) = gfx_dev_and_queue!();
+
+        if !(device.api_version() >= Version::V1_1 || device.enabled_extensions().khr_device_group)
+        {
+            return;
+        }
 
         let cs = unsafe {
             /*
             #version 450
 
-            #extension GL_KHR_shader_subgroup_basic: enable
-
-            layout(local_size_x = 128, local_size_y = 1, local_size_z = 1) in;
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
 
             layout(set = 0, binding = 0) buffer Output {
-                uint write;
+                uint data[];
             } write;
 
             void main() {
-                if (gl_GlobalInvocationID.x == 0) {
-                    write.write = gl_SubgroupSize;
-                }
+                write.data[gl_WorkGroupID.x] = gl_WorkGroupID.x + 1;
             }
             */
-            const MODULE: [u32; 246] = [
-                119734787, 65536, 851978, 30, 0, 131089, 1, 131089, 61, 393227, 1, 1280527431,
-                1685353262, 808793134, 0, 196622, 0, 1, 458767, 5, 4, 1852399981, 0, 9, 23, 393232,
-                4, 17, 128, 1, 1, 196611, 2, 450, 655364, 1197427783, 1279741775, 1885560645,
-                1953718128, 1600482425, 1701734764, 1919509599, 1769235301, 25974, 524292,
-                1197427783, 1279741775, 1852399429, 1685417059, 1768185701, 1952671090, 6649449,
-                589828, 1264536647, 1935626824, 1701077352, 1970495346, 1869768546, 1650421877,
-                1667855201, 0, 262149, 4, 1852399981, 0, 524293, 9, 1197436007, 1633841004,
-                1986939244, 1952539503, 1231974249, 68, 262149, 18, 1886680399, 29813, 327686, 18,
-                0, 1953067639, 101, 262149, 20, 1953067639, 101, 393221, 23, 1398762599,
-                1919378037, 1399879023, 6650473, 262215, 9, 11, 28, 327752, 18, 0, 35, 0, 196679,
-                18, 3, 262215, 20, 34, 0, 262215, 20, 33, 0, 196679, 23, 0, 262215, 23, 11, 36,
-                196679, 24, 0, 262215, 29, 11, 25, 131091, 2, 196641, 3, 2, 262165, 6, 32, 0,
-                262167, 7, 6, 3, 262176, 8, 1, 7, 262203, 8, 9, 1, 262187, 6, 10, 0, 262176, 11, 1,
-                6, 131092, 14, 196638, 18, 6, 262176, 19, 2, 18, 262203, 19, 20, 2, 262165, 21, 32,
-                1, 262187, 21, 22, 0, 262203, 11, 23, 1, 262176, 25, 2, 6, 262187, 6, 27, 128,
-                262187, 6, 28, 1, 393260, 7, 29, 27, 28, 28, 327734, 2, 4, 0, 3, 131320, 5, 327745,
-                11, 12, 9, 10, 262205, 6, 13, 12, 327850, 14, 15, 13, 10, 196855, 17, 0, 262394,
-                15, 16, 17, 131320, 16, 262205, 6, 24, 23, 327745, 25, 26, 20, 22, 196670, 26, 24,
-                131321, 17, 131320, 17, 65789, 65592,
+            const MODULE: [u32; 133] = [
+                119734787, 65536, 0, 22, 0, 131089, 1, 196622, 0, 1, 393231, 5, 16, 1852399981, 0,
+                6, 393232, 16, 17, 1, 1, 1, 262215, 6, 11, 26, 262215, 7, 6, 4, 327752, 8, 0, 35,
+                0, 196679, 8, 3, 262215, 10, 34, 0, 262215, 10, 33, 0, 131091, 1, 196641, 2, 1,
+                262165, 3, 32, 0, 262167, 4, 3, 3, 262176, 5, 1, 4, 262203, 5, 6, 1, 196637, 7, 3,
+                196638, 8, 7, 262176, 9, 2, 8, 262203, 9, 10, 2, 262165, 11, 32, 1, 262187, 11, 12,
+                0, 262187, 3, 13, 1, 262176, 14, 2, 3, 262176, 15, 1, 3, 327734, 1, 16, 0, 2,
+                131320, 17, 327745, 15, 18, 6, 12, 262205, 3, 19, 18, 327808, 3, 20, 19, 13,
+                393281, 14, 21, 10, 12, 19, 196670, 21, 20, 65789, 65592,
             ];
             let module =
                 ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
             module.entry_point("main").unwrap()
         };
 
-        let properties = device.physical_device().properties();
-        let subgroup_size = properties.min_subgroup_size.unwrap_or(1);
+        let pipeline = {
+            let stage = PipelineShaderStageCreateInfo::new(cs);
+            let layout = PipelineLayout::new(
+                device.clone(),
+                PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
+                    .into_pipeline_layout_create_info(device.clone())
+                    .unwrap(),
+            )
+            .unwrap();
+            ComputePipeline::new(
+                device.clone(),
+                None,
+                ComputePipelineCreateInfo {
+                    flags: PipelineCreateFlags::DISPATCH_BASE,
+                    ..ComputePipelineCreateInfo::stage_layout(stage, layout)
+                },
+            )
+            .unwrap()
+        };
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let data_buffer = Buffer::from_iter(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            [0u32; 4],
+        )
+        .unwrap();
+
+        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+        let set = PersistentDescriptorSet::new(
+            &ds_allocator,
+            pipeline.layout().set_layouts().get(0).unwrap().clone(),
+            [WriteDescriptorSet::buffer(0, data_buffer.clone())],
+            [],
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        cbb.bind_pipeline_compute(pipeline.clone())
+            .unwrap()
+            .bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                pipeline.layout().clone(),
+                0,
+                set,
+            )
+            .unwrap()
+            .dispatch_base([2, 0, 0], [2, 1, 1])
+            .unwrap();
+        let cb = cbb.build().unwrap();
+
+        let future = now(device)
+            .then_execute(queue, cb)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        future.wait(None).unwrap();
+
+        let data_buffer_content = data_buffer.read().unwrap();
+        assert_eq!(*data_buffer_content, [0, 0, 3, 4]);
+    }
+
+    #[test]
+    fn dispatch_indirect() {
+        // This test checks whether dispatch_indirect reads the group counts from a buffer.
+        // It dispatches three workgroups, whose count is read from an indirect buffer. Each
+        // workgroup writes its ID plus one to the buffer element indexed by their ID.
+
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(set = 0, binding = 0) buffer Output {
+                uint data[];
+            } write;
+
+            void main() {
+                write.data[gl_WorkGroupID.x] = gl_WorkGroupID.x + 1;
+            }
+            */
+            const MODULE: [u32; 133] = [
+                119734787, 65536, 0, 22, 0, 131089, 1, 196622, 0, 1, 393231, 5, 16, 1852399981, 0,
+                6, 393232, 16, 17, 1, 1, 1, 262215, 6, 11, 26, 262215, 7, 6, 4, 327752, 8, 0, 35,
+                0, 196679, 8, 3, 262215, 10, 34, 0, 262215, 10, 33, 0, 131091, 1, 196641, 2, 1,
+                262165, 3, 32, 0, 262167, 4, 3, 3, 262176, 5, 1, 4, 262203, 5, 6, 1, 196637, 7, 3,
+                196638, 8, 7, 262176, 9, 2, 8, 262203, 9, 10, 2, 262165, 11, 32, 1, 262187, 11, 12,
+                0, 262187, 3, 13, 1, 262176, 14, 2, 3, 262176, 15, 1, 3, 327734, 1, 16, 0, 2,
+                131320, 17, 327745, 15, 18, 6, 12, 262205, 3, 19, 18, 327808, 3, 20, 19, 13,
+                393281, 14, 21, 10, 12, 19, 196670, 21, 20, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
 
         let pipeline = {
-            let stage = PipelineShaderStageCreateInfo {
-                required_subgroup_size: Some(subgroup_size),
-                ..PipelineShaderStageCreateInfo::new(cs)
-            };
+            let stage = PipelineShaderStageCreateInfo::new(cs);
             let layout = PipelineLayout::new(
                 device.clone(),
                 PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
@@ -665,7 +902,21 @@ mod tests {
         };
 
         let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
-        let data_buffer = Buffer::from_data(
+        let indirect_buffer = Buffer::from_iter(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::INDIRECT_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            [DispatchIndirectCommand { x: 3, y: 1, z: 1 }],
+        )
+        .unwrap();
+        let data_buffer = Buffer::from_iter(
             memory_allocator,
             BufferCreateInfo {
                 usage: BufferUsage::STORAGE_BUFFER,
@@ -676,11 +927,11 @@ mod tests {
                     | MemoryTypeFilter::HOST_RANDOM_ACCESS,
                 ..Default::default()
             },
-            0,
+            [0u32; 4],
         )
         .unwrap();
 
-        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone());
+        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone(), Default::default());
         let set = PersistentDescriptorSet::new(
             &ds_allocator,
             pipeline.layout().set_layouts().get(0).unwrap().clone(),
@@ -705,7 +956,7 @@ mod tests {
                 set,
             )
             .unwrap()
-            .dispatch([128, 1, 1])
+            .dispatch_indirect(indirect_buffer)
             .unwrap();
         let cb = cbb.build().unwrap();
 
@@ -717,6 +968,1169 @@ mod tests {
         future.wait(None).unwrap();
 
         let data_buffer_content = data_buffer.read().unwrap();
-        assert_eq!(*data_buffer_content, subgroup_size);
+        assert_eq!(*data_buffer_content, [1, 2, 3, 0]);
+    }
+
+    #[test]
+    fn timestamp_query() {
+        // This test checks whether timestamps can be written and read back.
+        // It writes a timestamp before and after a dispatch, and checks that the second
+        // timestamp is later than the first.
+
+        let (device, queue) = gfx_dev_and_queue!();
+
+        if queue.device().physical_device().queue_family_properties()
+            [queue.queue_family_index() as usize]
+            .timestamp_valid_bits
+            .is_none()
+        {
+            return;
+        }
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(set = 0, binding = 0) buffer Output {
+                uint data[];
+            } write;
+
+            void main() {
+                write.data[gl_WorkGroupID.x] = gl_WorkGroupID.x + 1;
+            }
+            */
+            const MODULE: [u32; 133] = [
+                119734787, 65536, 0, 22, 0, 131089, 1, 196622, 0, 1, 393231, 5, 16, 1852399981, 0,
+                6, 393232, 16, 17, 1, 1, 1, 262215, 6, 11, 26, 262215, 7, 6, 4, 327752, 8, 0, 35,
+                0, 196679, 8, 3, 262215, 10, 34, 0, 262215, 10, 33, 0, 131091, 1, 196641, 2, 1,
+                262165, 3, 32, 0, 262167, 4, 3, 3, 262176, 5, 1, 4, 262203, 5, 6, 1, 196637, 7, 3,
+                196638, 8, 7, 262176, 9, 2, 8, 262203, 9, 10, 2, 262165, 11, 32, 1, 262187, 11, 12,
+                0, 262187, 3, 13, 1, 262176, 14, 2, 3, 262176, 15, 1, 3, 327734, 1, 16, 0, 2,
+                131320, 17, 327745, 15, 18, 6, 12, 262205, 3, 19, 18, 327808, 3, 20, 19, 13,
+                393281, 14, 21, 10, 12, 19, 196670, 21, 20, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let pipeline = {
+            let stage = PipelineShaderStageCreateInfo::new(cs);
+            let layout = PipelineLayout::new(
+                device.clone(),
+                PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
+                    .into_pipeline_layout_create_info(device.clone())
+                    .unwrap(),
+            )
+            .unwrap();
+            ComputePipeline::new(
+                device.clone(),
+                None,
+                ComputePipelineCreateInfo::stage_layout(stage, layout),
+            )
+            .unwrap()
+        };
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let data_buffer = Buffer::from_iter(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            [0u32; 1024],
+        )
+        .unwrap();
+
+        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+        let set = PersistentDescriptorSet::new(
+            &ds_allocator,
+            pipeline.layout().set_layouts().get(0).unwrap().clone(),
+            [WriteDescriptorSet::buffer(0, data_buffer)],
+            [],
+        )
+        .unwrap();
+
+        let query_pool = QueryPool::new(
+            device.clone(),
+            QueryPoolCreateInfo {
+                query_count: 2,
+                ..QueryPoolCreateInfo::query_type(QueryType::Timestamp)
+            },
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        unsafe {
+            cbb.reset_query_pool(query_pool.clone(), 0..2)
+                .unwrap()
+                .write_timestamp(query_pool.clone(), 0, PipelineStage::TopOfPipe)
+                .unwrap();
+        }
+
+        cbb.bind_pipeline_compute(pipeline.clone())
+            .unwrap()
+            .bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                pipeline.layout().clone(),
+                0,
+                set,
+            )
+            .unwrap()
+            .dispatch([1024, 1, 1])
+            .unwrap();
+
+        unsafe {
+            cbb.write_timestamp(query_pool.clone(), 1, PipelineStage::BottomOfPipe)
+                .unwrap();
+        }
+
+        let cb = cbb.build().unwrap();
+
+        let future = now(device)
+            .then_execute(queue, cb)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        future.wait(None).unwrap();
+
+        let mut timestamps = [0u64; 2];
+        assert!(query_pool
+            .get_results(0..2, &mut timestamps, QueryResultFlags::WAIT)
+            .unwrap());
+
+        let [start, end] = timestamps;
+        assert!(end > start);
+        assert!(query_pool.timestamp_to_nanoseconds(end - start) > 0.0);
+    }
+
+    #[test]
+    fn required_subgroup_size() {
+        // This test checks whether required_subgroup_size works.
+        // It executes a single compute shader (one invocation) that writes the subgroup size
+        // to a buffer. The buffer content is then checked for the right value.
+
+        let (device, queue) = gfx_dev_and_queue!(subgroup_size_control);
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            #extension GL_KHR_shader_subgroup_basic: enable
+
+            layout(local_size_x = 128, local_size_y = 1, local_size_z = 1) in;
+
+            layout(set = 0, binding = 0) buffer Output {
+                uint write;
+            } write;
+
+            void main() {
+                if (gl_GlobalInvocationID.x == 0) {
+                    write.write = gl_SubgroupSize;
+                }
+            }
+            */
+            const MODULE: [u32; 246] = [
+                119734787, 65536, 851978, 30, 0, 131089, 1, 131089, 61, 393227, 1, 1280527431,
+                1685353262, 808793134, 0, 196622, 0, 1, 458767, 5, 4, 1852399981, 0, 9, 23, 393232,
+                4, 17, 128, 1, 1, 196611, 2, 450, 655364, 1197427783, 1279741775, 1885560645,
+                1953718128, 1600482425, 1701734764, 1919509599, 1769235301, 25974, 524292,
+                1197427783, 1279741775, 1852399429, 1685417059, 1768185701, 1952671090, 6649449,
+                589828, 1264536647, 1935626824, 1701077352, 1970495346, 1869768546, 1650421877,
+                1667855201, 0, 262149, 4, 1852399981, 0, 524293, 9, 1197436007, 1633841004,
+                1986939244, 1952539503, 1231974249, 68, 262149, 18, 1886680399, 29813, 327686, 18,
+                0, 1953067639, 101, 262149, 20, 1953067639, 101, 393221, 23, 1398762599,
+                1919378037, 1399879023, 6650473, 262215, 9, 11, 28, 327752, 18, 0, 35, 0, 196679,
+                18, 3, 262215, 20, 34, 0, 262215, 20, 33, 0, 196679, 23, 0, 262215, 23, 11, 36,
+                196679, 24, 0, 262215, 29, 11, 25, 131091, 2, 196641, 3, 2, 262165, 6, 32, 0,
+                262167, 7, 6, 3, 262176, 8, 1, 7, 262203, 8, 9, 1, 262187, 6, 10, 0, 262176, 11, 1,
+                6, 131092, 14, 196638, 18, 6, 262176, 19, 2, 18, 262203, 19, 20, 2, 262165, 21, 32,
+                1, 262187, 21, 22, 0, 262203, 11, 23, 1, 262176, 25, 2, 6, 262187, 6, 27, 128,
+                262187, 6, 28, 1, 393260, 7, 29, 27, 28, 28, 327734, 2, 4, 0, 3, 131320, 5, 327745,
+                11, 12, 9, 10, 262205, 6, 13, 12, 327850, 14, 15, 13, 10, 196855, 17, 0, 262394,
+                15, 16, 17, 131320, 16, 262205, 6, 24, 23, 327745, 25, 26, 20, 22, 196670, 26, 24,
+                131321, 17, 131320, 17, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let properties = device.physical_device().properties();
+        let subgroup_size = properties.min_subgroup_size.unwrap_or(1);
+
+        let pipeline = {
+            let stage = PipelineShaderStageCreateInfo {
+                required_subgroup_size: Some(subgroup_size),
+                ..PipelineShaderStageCreateInfo::new(cs)
+            };
+            let layout = PipelineLayout::new(
+                device.clone(),
+                PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
+                    .into_pipeline_layout_create_info(device.clone())
+                    .unwrap(),
+            )
+            .unwrap();
+            ComputePipeline::new(
+                device.clone(),
+                None,
+                ComputePipelineCreateInfo::stage_layout(stage, layout),
+            )
+            .unwrap()
+        };
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let data_buffer = Buffer::from_data(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            0,
+        )
+        .unwrap();
+
+        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+        let set = PersistentDescriptorSet::new(
+            &ds_allocator,
+            pipeline.layout().set_layouts().get(0).unwrap().clone(),
+            [WriteDescriptorSet::buffer(0, data_buffer.clone())],
+            [],
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        cbb.bind_pipeline_compute(pipeline.clone())
+            .unwrap()
+            .bind_descriptor_sets(
+                PipelineBindPoint::Compute,
+                pipeline.layout().clone(),
+                0,
+                set,
+            )
+            .unwrap()
+            .dispatch([128, 1, 1])
+            .unwrap();
+        let cb = cbb.build().unwrap();
+
+        let future = now(device)
+            .then_execute(queue, cb)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        future.wait(None).unwrap();
+
+        let data_buffer_content = data_buffer.read().unwrap();
+        assert_eq!(*data_buffer_content, subgroup_size);
+    }
+
+    #[test]
+    fn with_local_size() {
+        let (device, _queue) = gfx_dev_and_queue!();
+
+        if device.api_version() < Version::V1_1 {
+            return;
+        }
+
+        let module = |code: &[u32]| unsafe {
+            ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(code)).unwrap()
+        };
+
+        // A compute shader with an empty `main`, whose local size is specified with
+        // `LocalSizeId`, using the `uint` specialization constants 0, 1 and 2, all defaulting to
+        // 1.
+        const LOCAL_SIZE_ID: [u32; 63] = [
+            119734787, 66048, 0, 9, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+            393547, 1, 38, 5, 6, 7, 262215, 5, 1, 0, 262215, 6, 1, 1, 262215, 7, 1, 2, 131091, 2,
+            196641, 3, 2, 262165, 4, 32, 0, 262194, 4, 5, 1, 262194, 4, 6, 1, 262194, 4, 7, 1,
+            327734, 2, 1, 0, 3, 131320, 8, 65789, 65592,
+        ];
+        // The same shader, with the local size specified with `LocalSize` instead.
+        const LOCAL_SIZE: [u32; 63] = [
+            119734787, 66048, 0, 9, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+            393232, 1, 17, 1, 1, 1, 262215, 5, 1, 0, 262215, 6, 1, 1, 262215, 7, 1, 2, 131091, 2,
+            196641, 3, 2, 262165, 4, 32, 0, 262194, 4, 5, 1, 262194, 4, 6, 1, 262194, 4, 7, 1,
+            327734, 2, 1, 0, 3, 131320, 8, 65789, 65592,
+        ];
+
+        let local_size =
+            |stage: &PipelineShaderStageCreateInfo| match stage.entry_point.info().execution {
+                ShaderExecution::Compute(execution) => execution.local_size,
+                _ => unreachable!(),
+            };
+
+        let stage =
+            PipelineShaderStageCreateInfo::new(module(&LOCAL_SIZE_ID).entry_point("main").unwrap());
+        assert_eq!(local_size(&stage), [1, 1, 1]);
+
+        let stage = stage.with_local_size([4, 2, 1]).unwrap();
+        assert_eq!(local_size(&stage), [4, 2, 1]);
+
+        let max_size = device
+            .physical_device()
+            .properties()
+            .max_compute_work_group_size;
+        assert!(stage.with_local_size([max_size[0] + 1, 1, 1]).is_err());
+
+        let stage =
+            PipelineShaderStageCreateInfo::new(module(&LOCAL_SIZE).entry_point("main").unwrap());
+        assert!(stage.with_local_size([4, 2, 1]).is_err());
+    }
+
+    #[test]
+    fn executable_statistics() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [khr_pipeline_executable_properties],
+            features: [pipeline_executable_info],
+        );
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(constant_id = 83) const int VALUE = 0xdeadbeef;
+
+            layout(set = 0, binding = 0) buffer Output {
+                int write;
+            } write;
+
+            void main() {
+                write.write = VALUE;
+            }
+            */
+            const MODULE: [u32; 120] = [
+                119734787, 65536, 524289, 14, 0, 131089, 1, 393227, 1, 1280527431, 1685353262,
+                808793134, 0, 196622, 0, 1, 327695, 5, 4, 1852399981, 0, 393232, 4, 17, 1, 1, 1,
+                196611, 2, 450, 262149, 4, 1852399981, 0, 262149, 7, 1886680399, 29813, 327686, 7,
+                0, 1953067639, 101, 262149, 9, 1953067639, 101, 262149, 11, 1431060822, 69, 327752,
+                7, 0, 35, 0, 196679, 7, 3, 262215, 9, 34, 0, 262215, 9, 33, 0, 262215, 11, 1, 83,
+                131091, 2, 196641, 3, 2, 262165, 6, 32, 1, 196638, 7, 6, 262176, 8, 2, 7, 262203,
+                8, 9, 2, 262187, 6, 10, 0, 262194, 6, 11, 3735928559, 262176, 12, 2, 6, 327734, 2,
+                4, 0, 3, 131320, 5, 327745, 12, 13, 9, 10, 196670, 13, 11, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
+                .into_pipeline_layout_create_info(device.clone())
+                .unwrap(),
+        )
+        .unwrap();
+
+        let pipeline = ComputePipeline::new(
+            device.clone(),
+            None,
+            ComputePipelineCreateInfo {
+                flags: PipelineCreateFlags::CAPTURE_STATISTICS,
+                ..ComputePipelineCreateInfo::stage_layout(stage.clone(), layout.clone())
+            },
+        )
+        .unwrap();
+
+        let executables = pipeline.executable_statistics().unwrap();
+        assert!(!executables.is_empty());
+        assert!(executables
+            .iter()
+            .any(|executable| !executable.statistics.is_empty()));
+
+        // Statistics can't be queried if they were not captured.
+        let pipeline = ComputePipeline::new(
+            device,
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .unwrap();
+        assert!(pipeline.executable_statistics().is_err());
+    }
+
+    #[test]
+    fn fail_on_pipeline_compile_required() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_pipeline_creation_cache_control],
+            features: [pipeline_creation_cache_control],
+        );
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(constant_id = 83) const int VALUE = 0xdeadbeef;
+
+            layout(set = 0, binding = 0) buffer Output {
+                int write;
+            } write;
+
+            void main() {
+                write.write = VALUE;
+            }
+            */
+            const MODULE: [u32; 120] = [
+                119734787, 65536, 524289, 14, 0, 131089, 1, 393227, 1, 1280527431, 1685353262,
+                808793134, 0, 196622, 0, 1, 327695, 5, 4, 1852399981, 0, 393232, 4, 17, 1, 1, 1,
+                196611, 2, 450, 262149, 4, 1852399981, 0, 262149, 7, 1886680399, 29813, 327686, 7,
+                0, 1953067639, 101, 262149, 9, 1953067639, 101, 262149, 11, 1431060822, 69, 327752,
+                7, 0, 35, 0, 196679, 7, 3, 262215, 9, 34, 0, 262215, 9, 33, 0, 262215, 11, 1, 83,
+                131091, 2, 196641, 3, 2, 262165, 6, 32, 1, 196638, 7, 6, 262176, 8, 2, 7, 262203,
+                8, 9, 2, 262187, 6, 10, 0, 262194, 6, 11, 3735928559, 262176, 12, 2, 6, 327734, 2,
+                4, 0, 3, 131320, 5, 327745, 12, 13, 9, 10, 196670, 13, 11, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
+                .into_pipeline_layout_create_info(device.clone())
+                .unwrap(),
+        )
+        .unwrap();
+
+        // With an empty cache, the implementation will usually have to compile the pipeline, but
+        // it is allowed to succeed anyway.
+        let cache = unsafe { PipelineCache::new(device.clone(), Default::default()).unwrap() };
+
+        match ComputePipeline::new(
+            device,
+            Some(cache),
+            ComputePipelineCreateInfo {
+                flags: PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED,
+                ..ComputePipelineCreateInfo::stage_layout(stage, layout)
+            },
+        ) {
+            Ok(_) | Err(Validated::Error(VulkanError::PipelineCompileRequired)) => (),
+            Err(err) => panic!("unexpected error: {:?}", err),
+        }
+    }
+
+    #[test]
+    fn fail_on_pipeline_compile_required_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.enabled_features().pipeline_creation_cache_control {
+            return;
+        }
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 35] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 5, 3, 1852399981, 0,
+                393232, 3, 17, 1, 1, 1, 131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4,
+                65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        assert!(matches!(
+            ComputePipeline::new(
+                device,
+                None,
+                ComputePipelineCreateInfo {
+                    flags: PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED,
+                    ..ComputePipelineCreateInfo::stage_layout(stage, layout)
+                },
+            ),
+            Err(Validated::ValidationError(_)),
+        ));
+    }
+
+    #[test]
+    fn protected_access_flags_exclusive() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_pipeline_protected_access],
+            features: [pipeline_protected_access],
+        );
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 35] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 5, 3, 1852399981, 0,
+                393232, 3, 17, 1, 1, 1, 131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4,
+                65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        ComputePipeline::new(
+            device.clone(),
+            None,
+            ComputePipelineCreateInfo {
+                flags: PipelineCreateFlags::NO_PROTECTED_ACCESS,
+                ..ComputePipelineCreateInfo::stage_layout(stage.clone(), layout.clone())
+            },
+        )
+        .unwrap();
+
+        assert!(matches!(
+            ComputePipeline::new(
+                device,
+                None,
+                ComputePipelineCreateInfo {
+                    flags: PipelineCreateFlags::NO_PROTECTED_ACCESS
+                        | PipelineCreateFlags::PROTECTED_ACCESS_ONLY,
+                    ..ComputePipelineCreateInfo::stage_layout(stage, layout)
+                },
+            ),
+            Err(Validated::ValidationError(_)),
+        ));
+    }
+
+    #[test]
+    fn push_descriptor_set() {
+        // This test pushes a uniform buffer descriptor, and runs a compute shader that copies its
+        // value to a storage buffer, which is then checked for the right value.
+
+        let (device, queue) = gfx_dev_and_queue!(extensions: [khr_push_descriptor], features: []);
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(set = 0, binding = 0) uniform Input {
+                int value;
+            } read;
+
+            layout(set = 0, binding = 1) buffer Output {
+                int write;
+            } write;
+
+            void main() {
+                write.write = read.value;
+            }
+            */
+            const MODULE: [u32; 118] = [
+                119734787, 65536, 524289, 17, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981,
+                0, 393232, 1, 17, 1, 1, 1, 327752, 5, 0, 35, 0, 196679, 5, 2, 327752, 8, 0, 35, 0,
+                196679, 8, 3, 262215, 7, 34, 0, 262215, 7, 33, 0, 262215, 10, 34, 0, 262215, 10,
+                33, 1, 131091, 2, 196641, 3, 2, 262165, 4, 32, 1, 196638, 5, 4, 262176, 6, 2, 5,
+                262203, 6, 7, 2, 196638, 8, 4, 262176, 9, 2, 8, 262203, 9, 10, 2, 262187, 4, 11, 0,
+                262176, 12, 2, 4, 327734, 2, 1, 0, 3, 131320, 13, 327745, 12, 14, 7, 11, 262205, 4,
+                15, 14, 327745, 12, 16, 10, 11, 196670, 16, 15, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let pipeline = {
+            let stage = PipelineShaderStageCreateInfo::new(cs);
+            let mut layout_create_info =
+                PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage]);
+            layout_create_info.set_layouts[0].flags |=
+                DescriptorSetLayoutCreateFlags::PUSH_DESCRIPTOR;
+            let layout = PipelineLayout::new(
+                device.clone(),
+                layout_create_info
+                    .into_pipeline_layout_create_info(device.clone())
+                    .unwrap(),
+            )
+            .unwrap();
+            ComputePipeline::new(
+                device.clone(),
+                None,
+                ComputePipelineCreateInfo::stage_layout(stage, layout),
+            )
+            .unwrap()
+        };
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let input_buffer = Buffer::from_data(
+            memory_allocator.clone(),
+            BufferCreateInfo {
+                usage: BufferUsage::UNIFORM_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_SEQUENTIAL_WRITE,
+                ..Default::default()
+            },
+            0x12345678i32,
+        )
+        .unwrap();
+        let output_buffer = Buffer::from_data(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            0i32,
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device.clone(), Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // There is no set 1 to push to.
+        assert!(cbb
+            .push_descriptor_set(
+                PipelineBindPoint::Compute,
+                pipeline.layout().clone(),
+                1,
+                smallvec![WriteDescriptorSet::buffer(0, input_buffer.clone())],
+            )
+            .is_err());
+
+        cbb.bind_pipeline_compute(pipeline.clone())
+            .unwrap()
+            .push_descriptor_set(
+                PipelineBindPoint::Compute,
+                pipeline.layout().clone(),
+                0,
+                smallvec![
+                    WriteDescriptorSet::buffer(0, input_buffer),
+                    WriteDescriptorSet::buffer(1, output_buffer.clone()),
+                ],
+            )
+            .unwrap()
+            .dispatch([1, 1, 1])
+            .unwrap();
+        let cb = cbb.build().unwrap();
+
+        let future = now(device)
+            .then_execute(queue, cb)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        future.wait(None).unwrap();
+
+        assert_eq!(*output_buffer.read().unwrap(), 0x12345678);
+    }
+
+    #[test]
+    fn require_full_subgroups() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_subgroup_size_control],
+            features: [compute_full_subgroups],
+        );
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 128, local_size_y = 1, local_size_z = 1) in;
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 35] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 5, 3, 1852399981, 0,
+                393232, 3, 17, 128, 1, 1, 131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4,
+                65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let stage = PipelineShaderStageCreateInfo {
+            flags: PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS,
+            ..PipelineShaderStageCreateInfo::new(cs)
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        // The subgroup size is a power of two no greater than 128, so it divides the local size.
+        let pipeline = ComputePipeline::new(
+            device,
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .unwrap();
+        assert_eq!(pipeline.bind_point(), PipelineBindPoint::Compute);
+    }
+
+    #[test]
+    fn require_full_subgroups_required_subgroup_size() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_subgroup_size_control],
+            features: [compute_full_subgroups, subgroup_size_control],
+        );
+
+        // A required subgroup size of 32 must be supported for compute shaders.
+        let properties = device.physical_device().properties();
+
+        if !(properties
+            .required_subgroup_size_stages
+            .unwrap_or_default()
+            .intersects(ShaderStages::COMPUTE)
+            && (properties.min_subgroup_size.unwrap_or(0)
+                ..=properties.max_subgroup_size.unwrap_or(0))
+                .contains(&32))
+        {
+            return;
+        }
+
+        let create_pipeline = |local_size_x| {
+            let cs = unsafe {
+                /*
+                #version 450
+
+                layout(local_size_x = LOCAL_SIZE_X, local_size_y = 1, local_size_z = 1) in;
+
+                void main() {
+                }
+                */
+                let module: [u32; 35] = [
+                    119734787,
+                    65536,
+                    0,
+                    5,
+                    0,
+                    131089,
+                    1,
+                    196622,
+                    0,
+                    1,
+                    327695,
+                    5,
+                    3,
+                    1852399981,
+                    0,
+                    393232,
+                    3,
+                    17,
+                    local_size_x,
+                    1,
+                    1,
+                    131091,
+                    1,
+                    196641,
+                    2,
+                    1,
+                    327734,
+                    1,
+                    3,
+                    0,
+                    2,
+                    131320,
+                    4,
+                    65789,
+                    65592,
+                ];
+                let module =
+                    ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&module))
+                        .unwrap();
+                module.entry_point("main").unwrap()
+            };
+
+            let stage = PipelineShaderStageCreateInfo {
+                flags: PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS,
+                required_subgroup_size: Some(32),
+                ..PipelineShaderStageCreateInfo::new(cs)
+            };
+            let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+            ComputePipeline::new(
+                device.clone(),
+                None,
+                ComputePipelineCreateInfo::stage_layout(stage, layout),
+            )
+        };
+
+        assert!(create_pipeline(64).is_ok());
+
+        // 48 is not a multiple of the required subgroup size.
+        match create_pipeline(48) {
+            Err(Validated::ValidationError(err)) => {
+                assert_eq!(
+                    err.vuids,
+                    ["VUID-VkPipelineShaderStageCreateInfo-pNext-02757"],
+                );
+            }
+            Err(err) => panic!("unexpected error: {:?}", err),
+            Ok(_) => panic!("pipeline creation should have failed"),
+        }
+    }
+
+    #[test]
+    fn local_size() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 35] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 5, 3, 1852399981, 0,
+                393232, 3, 17, 64, 1, 1, 131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4,
+                65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let pipeline = ComputePipeline::new(
+            device,
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .unwrap();
+        assert_eq!(pipeline.local_size(), [64, 1, 1]);
+    }
+
+    #[test]
+    fn combined_image_sampler_mismatch() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(set = 0, binding = 0) uniform sampler2D tex;
+
+            void main() {
+                tex;
+            }
+            */
+            const MODULE: [u32; 70] = [
+                119734787, 65536, 0, 11, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+                393232, 1, 17, 1, 1, 1, 262215, 8, 34, 0, 262215, 8, 33, 0, 131091, 2, 196641, 3,
+                2, 196630, 4, 32, 589849, 5, 4, 1, 0, 0, 0, 1, 0, 196635, 6, 5, 262176, 7, 0, 6,
+                262203, 7, 8, 0, 327734, 2, 1, 0, 3, 131320, 9, 262205, 6, 10, 8, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let pipeline = |descriptor_type: DescriptorType| {
+            let set_layout = DescriptorSetLayout::new(
+                device.clone(),
+                DescriptorSetLayoutCreateInfo {
+                    bindings: [(
+                        0,
+                        DescriptorSetLayoutBinding {
+                            stages: ShaderStages::COMPUTE,
+                            ..DescriptorSetLayoutBinding::descriptor_type(descriptor_type)
+                        },
+                    )]
+                    .into(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let layout = PipelineLayout::new(
+                device.clone(),
+                PipelineLayoutCreateInfo {
+                    set_layouts: vec![set_layout],
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            ComputePipeline::new(
+                device.clone(),
+                None,
+                ComputePipelineCreateInfo::stage_layout(
+                    PipelineShaderStageCreateInfo::new(cs.clone()),
+                    layout,
+                ),
+            )
+        };
+
+        match pipeline(DescriptorType::SampledImage) {
+            Err(Validated::ValidationError(err)) => {
+                assert!(err.problem.contains("combined image sampler"))
+            }
+            _ => panic!(),
+        }
+        assert!(pipeline(DescriptorType::CombinedImageSampler).is_ok());
+    }
+
+    #[test]
+    fn required_subgroup_size_getter() {
+        let (device, _) = gfx_dev_and_queue!(subgroup_size_control);
+
+        let properties = device.physical_device().properties();
+        if !properties
+            .required_subgroup_size_stages
+            .is_some_and(|stages| stages.intersects(ShaderStages::COMPUTE))
+            || !(properties.min_subgroup_size.unwrap_or(1)
+                ..=properties.max_subgroup_size.unwrap_or(1))
+                .contains(&32)
+        {
+            return;
+        }
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 35] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 5, 3, 1852399981, 0,
+                393232, 3, 17, 64, 1, 1, 131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4,
+                65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let stage = PipelineShaderStageCreateInfo {
+            required_subgroup_size: Some(32),
+            ..PipelineShaderStageCreateInfo::new(cs)
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let pipeline = ComputePipeline::new(
+            device,
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .unwrap();
+        assert_eq!(pipeline.required_subgroup_size(), Some(32));
+    }
+
+    #[test]
+    fn dispatch_for() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 8, local_size_y = 8, local_size_z = 1) in;
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 35] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 5, 3, 1852399981, 0,
+                393232, 3, 17, 8, 8, 1, 131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4,
+                65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let pipeline = ComputePipeline::new(
+            device.clone(),
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .unwrap();
+        assert_eq!(pipeline.local_size(), [8, 8, 1]);
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+        let mut cbb = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+
+        // A compute pipeline must be bound.
+        assert!(cbb.dispatch_for([100, 50, 1]).is_err());
+
+        cbb.bind_pipeline_compute(pipeline)
+            .unwrap()
+            .dispatch_for([100, 50, 1])
+            .unwrap();
+
+        // The rounded up group count must still be within the device limits.
+        assert!(cbb.dispatch_for([u32::MAX, 1, 1]).is_err());
+    }
+
+    #[test]
+    fn used_descriptor_set_indices() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(set = 0, binding = 0) buffer A {
+                int a;
+            };
+            layout(set = 2, binding = 0) buffer B {
+                int b;
+            };
+
+            void main() {
+                a = 0;
+                b = 0;
+            }
+            */
+            const MODULE: [u32; 102] = [
+                119734787, 65536, 0, 14, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+                393232, 1, 17, 1, 1, 1, 327752, 5, 0, 35, 0, 196679, 5, 3, 262215, 7, 34, 0,
+                262215, 7, 33, 0, 262215, 8, 34, 2, 262215, 8, 33, 0, 131091, 2, 196641, 3, 2,
+                262165, 4, 32, 1, 196638, 5, 4, 262176, 6, 2, 5, 262203, 6, 7, 2, 262203, 6, 8, 2,
+                262187, 4, 9, 0, 262176, 10, 2, 4, 327734, 2, 1, 0, 3, 131320, 11, 327745, 10, 12,
+                7, 9, 196670, 12, 9, 327745, 10, 13, 8, 9, 196670, 13, 9, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineDescriptorSetLayoutCreateInfo::from_stages([&stage])
+                .into_pipeline_layout_create_info(device.clone())
+                .unwrap(),
+        )
+        .unwrap();
+        let pipeline = ComputePipeline::new(
+            device,
+            None,
+            ComputePipelineCreateInfo::stage_layout(stage, layout),
+        )
+        .unwrap();
+
+        // Set 1 is in the layout, but is not accessed by the shader.
+        assert_eq!(pipeline.layout().set_layouts().len(), 3);
+        assert_eq!(pipeline.num_used_descriptor_sets(), 3);
+        assert_eq!(
+            pipeline.used_descriptor_set_indices().collect::<Vec<_>>(),
+            [0, 2],
+        );
+    }
+
+    #[test]
+    fn creation_feedback() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_pipeline_creation_feedback],
+            features: [],
+        );
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 64, local_size_y = 1, local_size_z = 1) in;
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 35] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 5, 3, 1852399981, 0,
+                393232, 3, 17, 64, 1, 1, 131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4,
+                65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        let stage = PipelineShaderStageCreateInfo::new(cs);
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+        let cache = unsafe { PipelineCache::new(device.clone(), Default::default()).unwrap() };
+        let create_pipeline = || {
+            ComputePipeline::new(
+                device.clone(),
+                Some(cache.clone()),
+                ComputePipelineCreateInfo::stage_layout(stage.clone(), layout.clone()),
+            )
+            .unwrap()
+        };
+
+        let feedback = create_pipeline().creation_feedback().unwrap().clone();
+        assert!(feedback.stages.contains_key(&ShaderStage::Compute));
+
+        // The implementation is allowed to not provide any feedback.
+        if !feedback
+            .flags
+            .intersects(PipelineCreationFeedbackFlags::VALID)
+        {
+            return;
+        }
+
+        // The first pipeline was added to the cache, so the second one may be found in it, but
+        // the implementation is not required to report cache hits.
+        let pipeline = create_pipeline();
+        let feedback = pipeline.creation_feedback().unwrap();
+        assert!(feedback
+            .flags
+            .intersects(PipelineCreationFeedbackFlags::VALID));
+        assert!(feedback.stages.contains_key(&ShaderStage::Compute));
     }
 }
@@ -72,7 +72,8 @@ impl ColorBlendState {
             attachments: (0..num)
                 .map(|_| ColorBlendAttachmentState {
                     blend: None,
-                    color_write_mask: ColorComponents::all(),
+                    blend_enable: StateMode::Fixed(true),
+                    color_write_mask: StateMode::Fixed(ColorComponents::all()),
                     color_write_enable: StateMode::Fixed(true),
                 })
                 .collect(),
@@ -122,12 +123,30 @@ impl ColorBlendState {
         self
     }
 
+    /// Sets blending to be enabled and disabled dynamically for all attachments.
+    #[inline]
+    pub fn blend_enable_dynamic(mut self) -> Self {
+        self.attachments
+            .iter_mut()
+            .for_each(|attachment_state| attachment_state.blend_enable = StateMode::Dynamic);
+        self
+    }
+
     /// Sets the color write mask for all attachments.
     #[inline]
     pub fn color_write_mask(mut self, color_write_mask: ColorComponents) -> Self {
+        self.attachments.iter_mut().for_each(|attachment_state| {
+            attachment_state.color_write_mask = StateMode::Fixed(color_write_mask)
+        });
+        self
+    }
+
+    /// Sets the color write mask for all attachments to dynamic.
+    #[inline]
+    pub fn color_write_mask_dynamic(mut self) -> Self {
         self.attachments
             .iter_mut()
-            .for_each(|attachment_state| attachment_state.color_write_mask = color_write_mask);
+            .for_each(|attachment_state| attachment_state.color_write_mask = StateMode::Dynamic);
         self
     }
 
@@ -192,6 +211,35 @@ impl ColorBlendState {
             }
         }
 
+        // The dynamic states apply to all attachments at once.
+        if let Some(first) = attachments.first() {
+            for (index, state) in attachments.iter().enumerate().skip(1) {
+                if state.blend_enable.is_dynamic() != first.blend_enable.is_dynamic() {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`attachments[{}].blend_enable` is dynamic, but \
+                            `attachments[0].blend_enable` is not, or vice versa",
+                            index
+                        )
+                        .into(),
+                        ..Default::default()
+                    }));
+                }
+
+                if state.color_write_mask.is_dynamic() != first.color_write_mask.is_dynamic() {
+                    return Err(Box::new(ValidationError {
+                        problem: format!(
+                            "`attachments[{}].color_write_mask` is dynamic, but \
+                            `attachments[0].color_write_mask` is not, or vice versa",
+                            index
+                        )
+                        .into(),
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
         if device.enabled_features().independent_blend {
             for (index, state) in attachments.iter().enumerate() {
                 state
@@ -325,8 +373,24 @@ pub struct ColorBlendAttachmentState {
     /// If set to `None`, blending is disabled, and all incoming pixels will be used directly.
     pub blend: Option<AttachmentBlend>,
 
+    /// Sets whether blending is enabled for the attachment. If enabled, the blend parameters are
+    /// taken from `blend`. If disabled, `blend` is ignored and blending is disabled.
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_color_blend_enable`](crate::device::Features::extended_dynamic_state3_color_blend_enable)
+    /// feature must be enabled on the device, and the other attachments must also be set to
+    /// `Dynamic`.
+    ///
+    /// The default value is `Fixed(true)`.
+    pub blend_enable: StateMode<bool>,
+
     /// Sets which components of the final pixel value are written to the attachment.
-    pub color_write_mask: ColorComponents,
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_color_write_mask`](crate::device::Features::extended_dynamic_state3_color_write_mask)
+    /// feature must be enabled on the device, and the other attachments must also be set to
+    /// `Dynamic`.
+    pub color_write_mask: StateMode<ColorComponents>,
 
     /// Sets whether anything at all is written to the attachment. If enabled, the pixel data
     /// that is written is determined by the `color_write_mask`. If disabled, the mask is ignored
@@ -342,7 +406,8 @@ impl ColorBlendAttachmentState {
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
             ref blend,
-            color_write_mask: _,
+            blend_enable,
+            color_write_mask,
             color_write_enable,
         } = self;
 
@@ -352,6 +417,40 @@ impl ColorBlendAttachmentState {
                 .map_err(|err| err.add_context("blend"))?;
         }
 
+        if blend_enable.is_dynamic()
+            && !device
+                .enabled_features()
+                .extended_dynamic_state3_color_blend_enable
+        {
+            return Err(Box::new(ValidationError {
+                context: "blend_enable".into(),
+                problem: "is dynamic".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "extended_dynamic_state3_color_blend_enable",
+                )])]),
+                vuids: &[
+                    "VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3ColorBlendEnable-07378",
+                ],
+            }));
+        }
+
+        if color_write_mask.is_dynamic()
+            && !device
+                .enabled_features()
+                .extended_dynamic_state3_color_write_mask
+        {
+            return Err(Box::new(ValidationError {
+                context: "color_write_mask".into(),
+                problem: "is dynamic".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "extended_dynamic_state3_color_write_mask",
+                )])]),
+                vuids: &[
+                    "VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3ColorWriteMask-07380",
+                ],
+            }));
+        }
+
         match color_write_enable {
             StateMode::Fixed(enable) => {
                 if !enable && !device.enabled_features().color_write_enable {
@@ -0,0 +1,89 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Graphics pipeline libraries, which contain only part of the state of a graphics pipeline.
+//!
+//! The state of a graphics pipeline is divided into four subsets. Each subset can be created
+//! separately as a pipeline library, by specifying the [`LIBRARY`] flag and
+//! [`GraphicsPipelineCreateInfo::library`]. A complete graphics pipeline can then be created by
+//! linking the libraries together with [`GraphicsPipelineCreateInfo::libraries`]. This allows
+//! shaders and state to be compiled ahead of time and reused between pipelines.
+//!
+//! [`LIBRARY`]: crate::pipeline::PipelineCreateFlags::LIBRARY
+//! [`GraphicsPipelineCreateInfo::library`]: super::GraphicsPipelineCreateInfo::library
+//! [`GraphicsPipelineCreateInfo::libraries`]: super::GraphicsPipelineCreateInfo::libraries
+
+use crate::{device::Device, macros::vulkan_bitflags, ValidationError};
+
+/// Parameters describing the subset of state that a graphics pipeline library contains.
+#[derive(Clone, Debug)]
+pub struct GraphicsPipelineLibraryCreateInfo {
+    /// The subsets of state that the library contains.
+    ///
+    /// Only the state belonging to these subsets may be specified in the
+    /// [`GraphicsPipelineCreateInfo`](super::GraphicsPipelineCreateInfo), and the state
+    /// belonging to all other subsets must be `None`.
+    ///
+    /// The default value is empty, which must be overridden.
+    pub flags: GraphicsPipelineLibraryFlags,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl GraphicsPipelineLibraryCreateInfo {
+    /// Returns a `GraphicsPipelineLibraryCreateInfo` with the specified `flags`.
+    #[inline]
+    pub fn flags(flags: GraphicsPipelineLibraryFlags) -> Self {
+        Self {
+            flags,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    pub(crate) fn validate(&self, _device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self { flags, _ne: _ } = self;
+
+        if flags.is_empty() {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "is empty".into(),
+                vuids: &["VUID-VkGraphicsPipelineLibraryCreateInfoEXT-flags-requiredbitmask"],
+                ..Default::default()
+            }));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for GraphicsPipelineLibraryCreateInfo {
+    #[inline]
+    fn default() -> Self {
+        Self::flags(GraphicsPipelineLibraryFlags::empty())
+    }
+}
+
+vulkan_bitflags! {
+    /// The subsets of state that a graphics pipeline library contains.
+    GraphicsPipelineLibraryFlags = GraphicsPipelineLibraryFlagsEXT(u32);
+
+    /// The vertex input interface: `vertex_input_state` and `input_assembly_state`.
+    VERTEX_INPUT_INTERFACE = VERTEX_INPUT_INTERFACE,
+
+    /// The pre-rasterization shaders: the vertex, tessellation and geometry shader stages,
+    /// `tessellation_state`, `viewport_state`, `rasterization_state` and
+    /// `discard_rectangle_state`.
+    PRE_RASTERIZATION_SHADERS = PRE_RASTERIZATION_SHADERS,
+
+    /// The fragment shader: the fragment shader stage and `depth_stencil_state`.
+    FRAGMENT_SHADER = FRAGMENT_SHADER,
+
+    /// The fragment output interface: `multisample_state` and `color_blend_state`.
+    FRAGMENT_OUTPUT_INTERFACE = FRAGMENT_OUTPUT_INTERFACE,
+}
@@ -58,9 +58,10 @@
 
 use self::{
     color_blend::ColorBlendState,
-    depth_stencil::{DepthBoundsState, DepthState, DepthStencilState},
+    depth_stencil::{DepthBoundsState, DepthState, DepthStencilState, StencilOp},
     discard_rectangle::DiscardRectangleState,
     input_assembly::{InputAssemblyState, PrimitiveTopology, PrimitiveTopologyClass},
+    library::{GraphicsPipelineLibraryCreateInfo, GraphicsPipelineLibraryFlags},
     multisample::MultisampleState,
     rasterization::RasterizationState,
     subpass::PipelineSubpassType,
@@ -69,20 +70,22 @@ use self::{
     viewport::ViewportState,
 };
 use super::{
-    cache::PipelineCache, DynamicState, Pipeline, PipelineBindPoint, PipelineCreateFlags,
-    PipelineLayout, PipelineShaderStageCreateInfo, StateMode,
+    cache::PipelineCache, executable_statistics_unchecked, validate_descriptor_buffer_flag,
+    validate_executable_statistics, validate_protected_access_flags, DynamicState, Pipeline,
+    PipelineBindPoint, PipelineCreateFlags, PipelineCreationFeedback, PipelineCreationFeedbackVk,
+    PipelineExecutableStatistics, PipelineLayout, PipelineShaderStageCreateInfo, StateMode,
 };
 use crate::{
     device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
     format::FormatFeatures,
-    image::{ImageAspect, ImageAspects},
+    image::{ImageAspect, ImageAspects, SampleCount},
     instance::InstanceOwnedDebugWrapper,
     macros::impl_id_counter,
     pipeline::{
         graphics::{
             color_blend::ColorBlendAttachmentState,
             depth_stencil::{StencilOpState, StencilState},
-            rasterization::{CullMode, FrontFace},
+            rasterization::{CullMode, FrontFace, PolygonMode, ProvokingVertexMode},
             subpass::PipelineRenderingCreateInfo,
             tessellation::TessellationDomainOrigin,
             vertex_input::VertexInputRate,
@@ -106,6 +109,7 @@ pub mod color_blend;
 pub mod depth_stencil;
 pub mod discard_rectangle;
 pub mod input_assembly;
+pub mod library;
 pub mod multisample;
 pub mod rasterization;
 pub mod subpass;
@@ -128,6 +132,7 @@ pub struct GraphicsPipeline {
     flags: PipelineCreateFlags,
     // TODO: replace () with an object that describes the shaders in some way.
     shaders: HashMap<ShaderStage, ()>,
+    required_subgroup_sizes: HashMap<ShaderStage, u32>,
     descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
     num_used_descriptor_sets: u32,
     fragment_tests_stages: Option<FragmentTestsStages>,
@@ -145,6 +150,8 @@ pub struct GraphicsPipeline {
     dynamic_state: HashMap<DynamicState, bool>,
 
     discard_rectangle_state: Option<DiscardRectangleState>,
+    library_flags: GraphicsPipelineLibraryFlags,
+    creation_feedback: Option<PipelineCreationFeedback>,
 }
 
 impl GraphicsPipeline {
@@ -168,6 +175,16 @@ impl GraphicsPipeline {
         create_info
             .validate(device)
             .map_err(|err| err.add_context("create_info"))?;
+
+        if create_info.base_pipeline_index.is_some() {
+            return Err(Box::new(ValidationError {
+                context: "create_info.base_pipeline_index".into(),
+                problem: "is `Some`, but only a single pipeline is being created".into(),
+                vuids: &["VUID-VkGraphicsPipelineCreateInfo-flags-07985"],
+                ..Default::default()
+            }));
+        }
+
         Ok(())
     }
 
@@ -177,6 +194,236 @@ impl GraphicsPipeline {
         cache: Option<Arc<PipelineCache>>,
         create_info: GraphicsPipelineCreateInfo,
     ) -> Result<Arc<Self>, VulkanError> {
+        let cache_handle = match cache.as_ref() {
+            Some(cache) => cache.handle(),
+            None => ash::vk::PipelineCache::null(),
+        };
+
+        let mut creation_feedback_vk =
+            PipelineCreationFeedbackVk::new(&device, create_info.stages.len());
+
+        let handle = Self::with_create_info_vk(
+            &device,
+            &create_info,
+            creation_feedback_vk.as_mut(),
+            |create_info_vk| {
+                let fns = device.fns();
+                let mut output = MaybeUninit::uninit();
+                (fns.v1_0.create_graphics_pipelines)(
+                    device.handle(),
+                    cache_handle,
+                    1,
+                    &create_info_vk,
+                    ptr::null(),
+                    output.as_mut_ptr(),
+                )
+                .result()
+                .map_err(VulkanError::from)?;
+
+                Ok::<_, VulkanError>(output.assume_init())
+            },
+        )?;
+
+        // Some drivers return `VK_SUCCESS` but provide a null handle if they
+        // fail to create the pipeline (due to invalid shaders, etc)
+        // This check ensures that we don't create an invalid `GraphicsPipeline` instance
+        if handle == ash::vk::Pipeline::null() {
+            panic!("vkCreateGraphicsPipelines provided a NULL handle");
+        }
+
+        let creation_feedback =
+            creation_feedback_vk.map(|feedback_vk| feedback_vk.to_feedback(&create_info.stages));
+
+        Ok(Self::from_handle_with_creation_feedback(
+            device,
+            handle,
+            create_info,
+            creation_feedback,
+        ))
+    }
+
+    /// Creates multiple `GraphicsPipeline`s with a single call to `vkCreateGraphicsPipelines`.
+    ///
+    /// This lets the implementation create the pipelines together, which can be faster than
+    /// creating them one by one. Pipelines in the batch can be derived from earlier pipelines in
+    /// the same batch, using [`GraphicsPipelineCreateInfo::base_pipeline_index`].
+    ///
+    /// The pipelines are returned in the same order as `create_infos`.
+    pub fn new_batch(
+        device: Arc<Device>,
+        cache: Option<Arc<PipelineCache>>,
+        create_infos: impl IntoIterator<Item = GraphicsPipelineCreateInfo>,
+    ) -> Result<Vec<Arc<Self>>, Validated<VulkanError>> {
+        let create_infos: Vec<_> = create_infos.into_iter().collect();
+        Self::validate_new_batch(&device, cache.as_ref().map(AsRef::as_ref), &create_infos)?;
+
+        unsafe { Ok(Self::new_batch_unchecked(device, cache, create_infos)?) }
+    }
+
+    fn validate_new_batch(
+        device: &Device,
+        _cache: Option<&PipelineCache>,
+        create_infos: &[GraphicsPipelineCreateInfo],
+    ) -> Result<(), Box<ValidationError>> {
+        for (index, create_info) in create_infos.iter().enumerate() {
+            create_info
+                .validate(device)
+                .map_err(|err| err.add_context(format!("create_infos[{}]", index)))?;
+
+            if let Some(base_pipeline_index) = create_info.base_pipeline_index {
+                if base_pipeline_index as usize >= index {
+                    return Err(Box::new(ValidationError {
+                        context: format!("create_infos[{}].base_pipeline_index", index).into(),
+                        problem: "does not refer to an earlier element of `create_infos`".into(),
+                        vuids: &["VUID-vkCreateGraphicsPipelines-flags-00720"],
+                        ..Default::default()
+                    }));
+                }
+
+                if !create_infos[base_pipeline_index as usize]
+                    .flags
+                    .intersects(PipelineCreateFlags::ALLOW_DERIVATIVES)
+                {
+                    return Err(Box::new(ValidationError {
+                        context: format!("create_infos[{}].flags", base_pipeline_index,).into(),
+                        problem: format!(
+                            "does not contain `PipelineCreateFlags::ALLOW_DERIVATIVES`, but \
+                            `create_infos[{}].base_pipeline_index` refers to it",
+                            index,
+                        )
+                        .into(),
+                        vuids: &["VUID-vkCreateGraphicsPipelines-flags-00721"],
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_batch_unchecked(
+        device: Arc<Device>,
+        cache: Option<Arc<PipelineCache>>,
+        create_infos: Vec<GraphicsPipelineCreateInfo>,
+    ) -> Result<Vec<Arc<Self>>, VulkanError> {
+        if create_infos.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let cache_handle = match cache.as_ref() {
+            Some(cache) => cache.handle(),
+            None => ash::vk::PipelineCache::null(),
+        };
+
+        let mut creation_feedbacks_vk: Vec<_> = create_infos
+            .iter()
+            .map(|create_info| PipelineCreationFeedbackVk::new(&device, create_info.stages.len()))
+            .collect();
+
+        let handles = Self::with_create_infos_vk(
+            &device,
+            &create_infos,
+            &mut creation_feedbacks_vk,
+            &mut Vec::with_capacity(create_infos.len()),
+            &mut |create_infos_vk| {
+                let fns = device.fns();
+                let mut output = Vec::with_capacity(create_infos_vk.len());
+                let result = (fns.v1_0.create_graphics_pipelines)(
+                    device.handle(),
+                    cache_handle,
+                    create_infos_vk.len() as u32,
+                    create_infos_vk.as_ptr(),
+                    ptr::null(),
+                    output.as_mut_ptr(),
+                );
+                output.set_len(create_infos_vk.len());
+
+                if let Err(err) = result.result() {
+                    // Pipelines that were created successfully must still be destroyed.
+                    for handle in output {
+                        if handle != ash::vk::Pipeline::null() {
+                            (fns.v1_0.destroy_pipeline)(device.handle(), handle, ptr::null());
+                        }
+                    }
+
+                    return Err(VulkanError::from(err));
+                }
+
+                Ok(output)
+            },
+        )?;
+
+        // Some drivers return `VK_SUCCESS` but provide a null handle if they
+        // fail to create the pipeline (due to invalid shaders, etc)
+        // This check ensures that we don't create an invalid `GraphicsPipeline` instance
+        if handles.contains(&ash::vk::Pipeline::null()) {
+            panic!("vkCreateGraphicsPipelines provided a NULL handle");
+        }
+
+        Ok(handles
+            .into_iter()
+            .zip(create_infos)
+            .zip(creation_feedbacks_vk)
+            .map(|((handle, create_info), creation_feedback_vk)| {
+                let creation_feedback = creation_feedback_vk
+                    .map(|feedback_vk| feedback_vk.to_feedback(&create_info.stages));
+                Self::from_handle_with_creation_feedback(
+                    device.clone(),
+                    handle,
+                    create_info,
+                    creation_feedback,
+                )
+            })
+            .collect())
+    }
+
+    /// Calls `f` with the Vulkan create infos of all elements of `create_infos`, appended to
+    /// `create_infos_vk`. `creation_feedbacks_vk` must have the same length as `create_infos`.
+    unsafe fn with_create_infos_vk<R>(
+        device: &Device,
+        create_infos: &[GraphicsPipelineCreateInfo],
+        creation_feedbacks_vk: &mut [Option<PipelineCreationFeedbackVk>],
+        create_infos_vk: &mut Vec<ash::vk::GraphicsPipelineCreateInfo>,
+        f: &mut dyn FnMut(&[ash::vk::GraphicsPipelineCreateInfo]) -> R,
+    ) -> R {
+        match create_infos.split_first() {
+            // The Vulkan create info points to data owned by `with_create_info_vk`, so the
+            // remaining create infos are built while it is still alive.
+            Some((create_info, rest)) => {
+                let (creation_feedback_vk, creation_feedbacks_vk) =
+                    creation_feedbacks_vk.split_first_mut().unwrap();
+
+                Self::with_create_info_vk(
+                    device,
+                    create_info,
+                    creation_feedback_vk.as_mut(),
+                    |create_info_vk| {
+                        create_infos_vk.push(create_info_vk);
+                        Self::with_create_infos_vk(
+                            device,
+                            rest,
+                            creation_feedbacks_vk,
+                            create_infos_vk,
+                            f,
+                        )
+                    },
+                )
+            }
+            None => f(create_infos_vk),
+        }
+    }
+
+    /// Calls `f` with the Vulkan create info corresponding to `create_info`. The pointers in the
+    /// Vulkan create info are only valid for the duration of the call. If `creation_feedback_vk`
+    /// is provided, the implementation writes the pipeline creation feedback into it.
+    unsafe fn with_create_info_vk<R>(
+        device: &Device,
+        create_info: &GraphicsPipelineCreateInfo,
+        creation_feedback_vk: Option<&mut PipelineCreationFeedbackVk>,
+        f: impl FnOnce(ash::vk::GraphicsPipelineCreateInfo) -> R,
+    ) -> R {
         let &GraphicsPipelineCreateInfo {
             flags,
             ref stages,
@@ -193,10 +440,14 @@ impl GraphicsPipeline {
             ref layout,
             ref subpass,
             ref base_pipeline,
+            base_pipeline_index,
 
             ref discard_rectangle_state,
+            attachment_feedback_loop_enable_dynamic,
+            ref library,
+            ref libraries,
             _ne: _,
-        } = &create_info;
+        } = create_info;
 
         let mut dynamic_state: HashMap<DynamicState, bool> = HashMap::default();
 
@@ -525,6 +776,8 @@ impl GraphicsPipeline {
 
         let mut rasterization_state_vk = None;
         let mut rasterization_line_state_vk = None;
+        let mut rasterization_stream_state_vk = None;
+        let mut rasterization_provoking_vertex_state_vk = None;
 
         if let Some(rasterization_state) = rasterization_state {
             let &RasterizationState {
@@ -537,6 +790,8 @@ impl GraphicsPipeline {
                 line_width,
                 line_rasterization_mode,
                 line_stipple,
+                rasterization_stream,
+                provoking_vertex_mode,
                 _ne: _,
             } = rasterization_state;
 
@@ -551,6 +806,17 @@ impl GraphicsPipeline {
                 }
             };
 
+            let polygon_mode = match polygon_mode {
+                StateMode::Fixed(polygon_mode) => {
+                    dynamic_state.insert(DynamicState::PolygonMode, false);
+                    polygon_mode.into()
+                }
+                StateMode::Dynamic => {
+                    dynamic_state.insert(DynamicState::PolygonMode, true);
+                    PolygonMode::default().into()
+                }
+            };
+
             let cull_mode = match cull_mode {
                 StateMode::Fixed(cull_mode) => {
                     dynamic_state.insert(DynamicState::CullMode, false);
@@ -617,7 +883,7 @@ impl GraphicsPipeline {
                     flags: ash::vk::PipelineRasterizationStateCreateFlags::empty(),
                     depth_clamp_enable: depth_clamp_enable as ash::vk::Bool32,
                     rasterizer_discard_enable,
-                    polygon_mode: polygon_mode.into(),
+                    polygon_mode,
                     cull_mode,
                     front_face,
                     depth_bias_enable,
@@ -657,6 +923,30 @@ impl GraphicsPipeline {
                     },
                 ) as *const _ as *const _;
             }
+
+            if rasterization_stream != 0 {
+                let rasterization_stream_state = rasterization_stream_state_vk.insert(
+                    ash::vk::PipelineRasterizationStateStreamCreateInfoEXT {
+                        p_next: rasterization_state.p_next,
+                        rasterization_stream,
+                        ..Default::default()
+                    },
+                );
+                rasterization_state.p_next = rasterization_stream_state as *const _ as *const _;
+            }
+
+            if provoking_vertex_mode != ProvokingVertexMode::First {
+                let rasterization_provoking_vertex_state = rasterization_provoking_vertex_state_vk
+                    .insert(
+                        ash::vk::PipelineRasterizationProvokingVertexStateCreateInfoEXT {
+                            p_next: rasterization_state.p_next,
+                            provoking_vertex_mode: provoking_vertex_mode.into(),
+                            ..Default::default()
+                        },
+                    );
+                rasterization_state.p_next =
+                    rasterization_provoking_vertex_state as *const _ as *const _;
+            }
         }
 
         let mut multisample_state_vk = None;
@@ -671,6 +961,17 @@ impl GraphicsPipeline {
                 _ne: _,
             } = multisample_state;
 
+            let rasterization_samples = match rasterization_samples {
+                StateMode::Fixed(rasterization_samples) => {
+                    dynamic_state.insert(DynamicState::RasterizationSamples, false);
+                    rasterization_samples.into()
+                }
+                StateMode::Dynamic => {
+                    dynamic_state.insert(DynamicState::RasterizationSamples, true);
+                    SampleCount::Sample1.into()
+                }
+            };
+
             let (sample_shading_enable, min_sample_shading) =
                 if let Some(min_sample_shading) = sample_shading {
                     (ash::vk::TRUE, min_sample_shading)
@@ -680,7 +981,7 @@ impl GraphicsPipeline {
 
             let _ = multisample_state_vk.insert(ash::vk::PipelineMultisampleStateCreateInfo {
                 flags: ash::vk::PipelineMultisampleStateCreateFlags::empty(),
-                rasterization_samples: rasterization_samples.into(),
+                rasterization_samples,
                 sample_shading_enable,
                 min_sample_shading,
                 p_sample_mask: sample_mask as _,
@@ -898,18 +1199,31 @@ impl GraphicsPipeline {
                 |color_blend_attachment_state| {
                     let &ColorBlendAttachmentState {
                         blend,
+                        blend_enable,
                         color_write_mask,
                         color_write_enable: _,
                     } = color_blend_attachment_state;
 
-                    let blend = if let Some(blend) = blend {
-                        blend.into()
-                    } else {
-                        Default::default()
+                    let blend = match blend {
+                        Some(blend) if blend_enable != StateMode::Fixed(false) => blend.into(),
+                        _ => Default::default(),
+                    };
+
+                    let color_write_mask = match color_write_mask {
+                        StateMode::Fixed(color_write_mask) => {
+                            dynamic_state.insert(DynamicState::ColorWriteMask, false);
+                            color_write_mask.into()
+                        }
+                        StateMode::Dynamic => {
+                            dynamic_state.insert(DynamicState::ColorWriteMask, true);
+                            Default::default()
+                        }
                     };
 
+                    dynamic_state.insert(DynamicState::ColorBlendEnable, blend_enable.is_dynamic());
+
                     ash::vk::PipelineColorBlendAttachmentState {
-                        color_write_mask: color_write_mask.into(),
+                        color_write_mask,
                         ..blend
                     }
                 },
@@ -959,6 +1273,7 @@ impl GraphicsPipeline {
                     |color_blend_attachment_state| {
                         let &ColorBlendAttachmentState {
                             blend: _,
+                            blend_enable: _,
                             color_write_mask: _,
                             color_write_enable,
                         } = color_blend_attachment_state;
@@ -985,6 +1300,26 @@ impl GraphicsPipeline {
             }
         }
 
+        if attachment_feedback_loop_enable_dynamic {
+            dynamic_state.insert(DynamicState::AttachmentFeedbackLoopEnable, true);
+        }
+
+        let mut library_create_info_vk =
+            library
+                .as_ref()
+                .map(|library| ash::vk::GraphicsPipelineLibraryCreateInfoEXT {
+                    flags: library.flags.into(),
+                    ..Default::default()
+                });
+
+        let libraries_vk: SmallVec<[_; 4]> = libraries.iter().map(VulkanObject::handle).collect();
+        let mut pipeline_library_create_info_vk =
+            (!libraries_vk.is_empty()).then(|| ash::vk::PipelineLibraryCreateInfoKHR {
+                library_count: libraries_vk.len() as u32,
+                p_libraries: libraries_vk.as_ptr(),
+                ..Default::default()
+            });
+
         let mut dynamic_state_list: SmallVec<[_; 4]> = SmallVec::new();
         let mut dynamic_state_vk = None;
 
@@ -1006,18 +1341,18 @@ impl GraphicsPipeline {
             }
         }
 
-        let render_pass = subpass.as_ref().unwrap();
         let mut render_pass_vk = ash::vk::RenderPass::null();
         let mut subpass_vk = 0;
         let mut color_attachment_formats_vk: SmallVec<[_; 4]> = SmallVec::new();
         let mut rendering_create_info_vk = None;
 
-        match render_pass {
-            PipelineSubpassType::BeginRenderPass(subpass) => {
+        match subpass {
+            None => (),
+            Some(PipelineSubpassType::BeginRenderPass(subpass)) => {
                 render_pass_vk = subpass.render_pass().handle();
                 subpass_vk = subpass.index();
             }
-            PipelineSubpassType::BeginRendering(rendering_info) => {
+            Some(PipelineSubpassType::BeginRendering(rendering_info)) => {
                 let &PipelineRenderingCreateInfo {
                     view_mask,
                     ref color_attachment_formats,
@@ -1130,7 +1465,7 @@ impl GraphicsPipeline {
             base_pipeline_handle: base_pipeline
                 .as_ref()
                 .map_or(ash::vk::Pipeline::null(), VulkanObject::handle),
-            base_pipeline_index: -1,
+            base_pipeline_index: base_pipeline_index.map_or(-1, |index| index as i32),
             ..Default::default()
         };
 
@@ -1144,36 +1479,25 @@ impl GraphicsPipeline {
             create_info_vk.p_next = info as *const _ as *const _;
         }
 
-        let cache_handle = match cache.as_ref() {
-            Some(cache) => cache.handle(),
-            None => ash::vk::PipelineCache::null(),
-        };
+        if let Some(info) = library_create_info_vk.as_mut() {
+            info.p_next = create_info_vk.p_next as *mut _;
+            create_info_vk.p_next = info as *const _ as *const _;
+        }
 
-        let handle = {
-            let fns = device.fns();
-            let mut output = MaybeUninit::uninit();
-            (fns.v1_0.create_graphics_pipelines)(
-                device.handle(),
-                cache_handle,
-                1,
-                &create_info_vk,
-                ptr::null(),
-                output.as_mut_ptr(),
-            )
-            .result()
-            .map_err(VulkanError::from)?;
+        if let Some(info) = pipeline_library_create_info_vk.as_mut() {
+            info.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = info as *const _ as *const _;
+        }
 
-            output.assume_init()
-        };
+        let mut creation_feedback_create_info_vk =
+            creation_feedback_vk.map(PipelineCreationFeedbackVk::create_info_vk);
 
-        // Some drivers return `VK_SUCCESS` but provide a null handle if they
-        // fail to create the pipeline (due to invalid shaders, etc)
-        // This check ensures that we don't create an invalid `GraphicsPipeline` instance
-        if handle == ash::vk::Pipeline::null() {
-            panic!("vkCreateGraphicsPipelines provided a NULL handle");
+        if let Some(info) = creation_feedback_create_info_vk.as_mut() {
+            info.p_next = create_info_vk.p_next;
+            create_info_vk.p_next = info as *const _ as *const _;
         }
 
-        Ok(Self::from_handle(device, handle, create_info))
+        f(create_info_vk)
     }
 
     /// Creates a new `GraphicsPipeline` from a raw object handle.
@@ -1187,30 +1511,44 @@ impl GraphicsPipeline {
         device: Arc<Device>,
         handle: ash::vk::Pipeline,
         create_info: GraphicsPipelineCreateInfo,
+    ) -> Arc<Self> {
+        Self::from_handle_with_creation_feedback(device, handle, create_info, None)
+    }
+
+    unsafe fn from_handle_with_creation_feedback(
+        device: Arc<Device>,
+        handle: ash::vk::Pipeline,
+        create_info: GraphicsPipelineCreateInfo,
+        creation_feedback: Option<PipelineCreationFeedback>,
     ) -> Arc<Self> {
         let GraphicsPipelineCreateInfo {
             flags,
             stages,
 
-            vertex_input_state,
-            input_assembly_state,
-            tessellation_state,
-            viewport_state,
-            rasterization_state,
-            multisample_state,
-            depth_stencil_state,
-            color_blend_state,
+            mut vertex_input_state,
+            mut input_assembly_state,
+            mut tessellation_state,
+            mut viewport_state,
+            mut rasterization_state,
+            mut multisample_state,
+            mut depth_stencil_state,
+            mut color_blend_state,
 
             layout,
-            subpass,
+            mut subpass,
             base_pipeline: _,
+            base_pipeline_index: _,
 
-            discard_rectangle_state,
+            mut discard_rectangle_state,
+            attachment_feedback_loop_enable_dynamic,
+            library,
+            libraries,
 
             _ne: _,
         } = create_info;
 
         let mut shaders = HashMap::default();
+        let mut required_subgroup_sizes = HashMap::default();
         let mut descriptor_binding_requirements: HashMap<
             (u32, u32),
             DescriptorBindingRequirements,
@@ -1219,13 +1557,19 @@ impl GraphicsPipeline {
 
         for stage in &stages {
             let &PipelineShaderStageCreateInfo {
-                ref entry_point, ..
+                ref entry_point,
+                required_subgroup_size,
+                ..
             } = stage;
 
             let entry_point_info = entry_point.info();
             let stage = ShaderStage::from(&entry_point_info.execution);
             shaders.insert(stage, ());
 
+            if let Some(required_subgroup_size) = required_subgroup_size {
+                required_subgroup_sizes.insert(stage, required_subgroup_size);
+            }
+
             if let ShaderExecution::Fragment(FragmentShaderExecution {
                 fragment_tests_stages: s,
                 ..
@@ -1246,13 +1590,6 @@ impl GraphicsPipeline {
             }
         }
 
-        let num_used_descriptor_sets = descriptor_binding_requirements
-            .keys()
-            .map(|loc| loc.0)
-            .max()
-            .map(|x| x + 1)
-            .unwrap_or(0);
-
         let mut dynamic_state: HashMap<DynamicState, bool> = HashMap::default();
 
         if vertex_input_state.is_some() {
@@ -1343,6 +1680,7 @@ impl GraphicsPipeline {
         if let Some(rasterization_state) = &rasterization_state {
             let &RasterizationState {
                 rasterizer_discard_enable,
+                polygon_mode,
                 cull_mode,
                 front_face,
                 depth_bias,
@@ -1360,6 +1698,8 @@ impl GraphicsPipeline {
                 }
             }
 
+            dynamic_state.insert(DynamicState::PolygonMode, polygon_mode.is_dynamic());
+
             match cull_mode {
                 StateMode::Fixed(_) => {
                     dynamic_state.insert(DynamicState::CullMode, false);
@@ -1418,6 +1758,18 @@ impl GraphicsPipeline {
             }
         }
 
+        if let Some(multisample_state) = &multisample_state {
+            let &MultisampleState {
+                rasterization_samples,
+                ..
+            } = multisample_state;
+
+            dynamic_state.insert(
+                DynamicState::RasterizationSamples,
+                rasterization_samples.is_dynamic(),
+            );
+        }
+
         if let Some(depth_stencil_state) = &depth_stencil_state {
             let DepthStencilState {
                 flags: _,
@@ -1545,6 +1897,18 @@ impl GraphicsPipeline {
                 _ne: _,
             } = color_blend_state;
 
+            for color_blend_attachment_state in attachments {
+                let &ColorBlendAttachmentState {
+                    blend: _,
+                    blend_enable,
+                    color_write_mask,
+                    color_write_enable: _,
+                } = color_blend_attachment_state;
+
+                dynamic_state.insert(DynamicState::ColorBlendEnable, blend_enable.is_dynamic());
+                dynamic_state.insert(DynamicState::ColorWriteMask, color_write_mask.is_dynamic());
+            }
+
             if let Some(logic_op) = logic_op {
                 match logic_op {
                     StateMode::Fixed(_) => {
@@ -1569,6 +1933,7 @@ impl GraphicsPipeline {
                 for color_blend_attachment_state in attachments {
                     let &ColorBlendAttachmentState {
                         blend: _,
+                        blend_enable: _,
                         color_write_mask: _,
                         color_write_enable,
                     } = color_blend_attachment_state;
@@ -1598,6 +1963,89 @@ impl GraphicsPipeline {
             }
         }
 
+        if attachment_feedback_loop_enable_dynamic {
+            dynamic_state.insert(DynamicState::AttachmentFeedbackLoopEnable, true);
+        }
+
+        let mut library_flags = if flags.intersects(PipelineCreateFlags::LIBRARY) {
+            library.map_or(GraphicsPipelineLibraryFlags::empty(), |library| {
+                library.flags
+            })
+        } else if libraries.is_empty() {
+            GraphicsPipelineLibraryFlags::all()
+        } else {
+            GraphicsPipelineLibraryFlags::empty()
+        };
+
+        // Take the state of the subsets that are contained in the linked libraries.
+        for pipeline_library in &libraries {
+            let contains = |subset| pipeline_library.library_flags.intersects(subset);
+
+            if contains(GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE) {
+                vertex_input_state = Some(pipeline_library.vertex_input_state.clone());
+                input_assembly_state = Some(pipeline_library.input_assembly_state);
+            }
+
+            if contains(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS) {
+                tessellation_state = pipeline_library.tessellation_state;
+                viewport_state = pipeline_library.viewport_state.clone();
+                rasterization_state = Some(pipeline_library.rasterization_state.clone());
+                discard_rectangle_state = pipeline_library.discard_rectangle_state.clone();
+            }
+
+            if contains(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER) {
+                depth_stencil_state = pipeline_library.depth_stencil_state.clone();
+                fragment_tests_stages = pipeline_library.fragment_tests_stages;
+            }
+
+            if contains(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE) {
+                multisample_state = pipeline_library.multisample_state;
+                color_blend_state = pipeline_library.color_blend_state.clone();
+            }
+
+            if subpass.is_none()
+                && contains(
+                    GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS
+                        | GraphicsPipelineLibraryFlags::FRAGMENT_SHADER
+                        | GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE,
+                )
+            {
+                subpass = Some(pipeline_library.subpass.clone());
+            }
+
+            shaders.extend(pipeline_library.shaders.iter().map(|(&k, &v)| (k, v)));
+            required_subgroup_sizes.extend(
+                pipeline_library
+                    .required_subgroup_sizes
+                    .iter()
+                    .map(|(&k, &v)| (k, v)),
+            );
+
+            for (&loc, reqs) in &pipeline_library.descriptor_binding_requirements {
+                match descriptor_binding_requirements.entry(loc) {
+                    Entry::Occupied(entry) => {
+                        entry.into_mut().merge(reqs).expect("Could not produce an intersection of the shader descriptor requirements");
+                    }
+                    Entry::Vacant(entry) => {
+                        entry.insert(reqs.clone());
+                    }
+                }
+            }
+
+            for (&state, &is_dynamic) in &pipeline_library.dynamic_state {
+                *dynamic_state.entry(state).or_default() |= is_dynamic;
+            }
+
+            library_flags |= pipeline_library.library_flags;
+        }
+
+        let num_used_descriptor_sets = descriptor_binding_requirements
+            .keys()
+            .map(|loc| loc.0)
+            .max()
+            .map(|x| x + 1)
+            .unwrap_or(0);
+
         Arc::new(Self {
             handle,
             device: InstanceOwnedDebugWrapper(device),
@@ -1605,23 +2053,31 @@ impl GraphicsPipeline {
 
             flags,
             shaders,
+            required_subgroup_sizes,
             descriptor_binding_requirements,
             num_used_descriptor_sets,
             fragment_tests_stages,
 
-            vertex_input_state: vertex_input_state.unwrap(), // Can be None if there's a mesh shader, but we don't support that yet
-            input_assembly_state: input_assembly_state.unwrap(), // Can be None if there's a mesh shader, but we don't support that yet
+            // These can be None for pipeline libraries that don't contain the subset, in which
+            // case the default is returned. They can also be None if there's a mesh shader, but
+            // we don't support that yet.
+            vertex_input_state: vertex_input_state.unwrap_or_default(),
+            input_assembly_state: input_assembly_state.unwrap_or_default(),
             tessellation_state,
             viewport_state,
-            rasterization_state: rasterization_state.unwrap(), // Can be None for pipeline libraries, but we don't support that yet
+            rasterization_state: rasterization_state.unwrap_or_default(),
             multisample_state,
             depth_stencil_state,
             color_blend_state,
             layout: DeviceOwnedDebugWrapper(layout),
-            subpass: subpass.unwrap(),
+            subpass: subpass.unwrap_or_else(|| {
+                PipelineSubpassType::BeginRendering(PipelineRenderingCreateInfo::default())
+            }),
             dynamic_state,
 
             discard_rectangle_state,
+            library_flags,
+            creation_feedback,
         })
     }
 
@@ -1637,6 +2093,43 @@ impl GraphicsPipeline {
         self.flags
     }
 
+    /// Returns the properties and statistics of the executables that the pipeline was compiled
+    /// into.
+    ///
+    /// The pipeline must have been created with [`PipelineCreateFlags::CAPTURE_STATISTICS`], and
+    /// the [`pipeline_executable_info`] feature must be enabled on the device.
+    ///
+    /// [`pipeline_executable_info`]: crate::device::Features::pipeline_executable_info
+    #[inline]
+    pub fn executable_statistics(
+        &self,
+    ) -> Result<Vec<PipelineExecutableStatistics>, Validated<VulkanError>> {
+        validate_executable_statistics(&self.device, self.flags)?;
+
+        unsafe { Ok(self.executable_statistics_unchecked()?) }
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    #[inline]
+    pub unsafe fn executable_statistics_unchecked(
+        &self,
+    ) -> Result<Vec<PipelineExecutableStatistics>, VulkanError> {
+        executable_statistics_unchecked(&self.device, self.handle)
+    }
+
+    /// Returns feedback about the creation of the pipeline, such as whether it was found in the
+    /// pipeline cache and how long it took to create.
+    ///
+    /// This is `None` if the pipeline was created with [`from_handle`](Self::from_handle), or if
+    /// the device API version is less than 1.3 and the [`ext_pipeline_creation_feedback`]
+    /// extension was not enabled on the device.
+    ///
+    /// [`ext_pipeline_creation_feedback`]: crate::device::DeviceExtensions::ext_pipeline_creation_feedback
+    #[inline]
+    pub fn creation_feedback(&self) -> Option<&PipelineCreationFeedback> {
+        self.creation_feedback.as_ref()
+    }
+
     /// Returns information about a particular shader.
     ///
     /// `None` is returned if the pipeline does not contain this shader.
@@ -1649,9 +2142,23 @@ impl GraphicsPipeline {
         self.shaders.get(&stage).copied()
     }
 
-    /// Returns the vertex input state used to create this pipeline.
+    /// Returns the [`required_subgroup_size`] that the shader of the given stage was created
+    /// with.
+    ///
+    /// `None` is returned if the pipeline does not contain this shader, or if no subgroup size
+    /// was required for it. In that case, the subgroup size that the implementation chose can be
+    /// queried with [`executable_statistics`].
+    ///
+    /// [`required_subgroup_size`]: PipelineShaderStageCreateInfo::required_subgroup_size
+    /// [`executable_statistics`]: Self::executable_statistics
     #[inline]
-    pub fn vertex_input_state(&self) -> &VertexInputState {
+    pub fn required_subgroup_size(&self, stage: ShaderStage) -> Option<u32> {
+        self.required_subgroup_sizes.get(&stage).copied()
+    }
+
+    /// Returns the vertex input state used to create this pipeline.
+    #[inline]
+    pub fn vertex_input_state(&self) -> &VertexInputState {
         &self.vertex_input_state
     }
 
@@ -1718,6 +2225,15 @@ impl GraphicsPipeline {
         self.dynamic_state.iter().map(|(k, v)| (*k, *v))
     }
 
+    /// Returns whether a particular state must be dynamically set.
+    ///
+    /// Returns `false` both if the state is fixed, and if the pipeline does not contain the state
+    /// at all. Use [`dynamic_state`](Self::dynamic_state) to distinguish the two.
+    #[inline]
+    pub fn is_dynamic(&self, state: DynamicState) -> bool {
+        self.dynamic_state(state).unwrap_or(false)
+    }
+
     /// Returns the discard rectangle state used to create this pipeline.
     #[inline]
     pub fn discard_rectangle_state(&self) -> Option<&DiscardRectangleState> {
@@ -1729,6 +2245,16 @@ impl GraphicsPipeline {
     pub fn fragment_tests_stages(&self) -> Option<FragmentTestsStages> {
         self.fragment_tests_stages
     }
+
+    /// Returns the subsets of graphics pipeline state that the pipeline contains.
+    ///
+    /// This contains all subsets unless the pipeline is a library. For a library, the state of
+    /// subsets that it doesn't contain is not meaningful; the accessors for that state return
+    /// the default value or `None`.
+    #[inline]
+    pub fn library_flags(&self) -> GraphicsPipelineLibraryFlags {
+        self.library_flags
+    }
 }
 
 impl Pipeline for GraphicsPipeline {
@@ -1885,6 +2411,17 @@ pub struct GraphicsPipelineCreateInfo {
     /// The default value is `None`.
     pub base_pipeline: Option<Arc<GraphicsPipeline>>,
 
+    /// The index of the pipeline to use as a base when creating this pipeline, within the same
+    /// call to [`GraphicsPipeline::new_batch`].
+    ///
+    /// If this is `Some`, then `flags` must contain [`PipelineCreateFlags::DERIVATIVE`],
+    /// `base_pipeline` must be `None`, the index must refer to an earlier element of the batch,
+    /// and the `flags` of that element must contain [`PipelineCreateFlags::ALLOW_DERIVATIVES`].
+    /// This must be `None` when creating a single pipeline with [`GraphicsPipeline::new`].
+    ///
+    /// The default value is `None`.
+    pub base_pipeline_index: Option<u32>,
+
     /// The discard rectangle state.
     ///
     /// This state is always used if it is provided.
@@ -1892,6 +2429,46 @@ pub struct GraphicsPipelineCreateInfo {
     /// The default value is `None`.
     pub discard_rectangle_state: Option<DiscardRectangleState>,
 
+    /// Whether the attachment feedback loop aspects are set dynamically with
+    /// [`set_attachment_feedback_loop_enable`], rather than with
+    /// [`PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP`] and
+    /// [`PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP`].
+    ///
+    /// If set to `true`, the
+    /// [`attachment_feedback_loop_dynamic_state`](crate::device::Features::attachment_feedback_loop_dynamic_state)
+    /// feature must be enabled on the device, and `flags` must not contain either of those flags.
+    ///
+    /// The default value is `false`.
+    ///
+    /// [`set_attachment_feedback_loop_enable`]: crate::command_buffer::AutoCommandBufferBuilder::set_attachment_feedback_loop_enable
+    pub attachment_feedback_loop_enable_dynamic: bool,
+
+    /// The subsets of state that the pipeline library contains, if `flags` contains
+    /// [`PipelineCreateFlags::LIBRARY`].
+    ///
+    /// If `flags` contains `LIBRARY`, this must be `Some`, and only the state belonging to the
+    /// specified subsets may be provided. Otherwise, this must be `None`.
+    ///
+    /// If this is `Some`, the
+    /// [`graphics_pipeline_library`](crate::device::Features::graphics_pipeline_library)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `None`.
+    pub library: Option<GraphicsPipelineLibraryCreateInfo>,
+
+    /// Pipeline libraries to link into this pipeline.
+    ///
+    /// If this is not empty, the
+    /// [`graphics_pipeline_library`](crate::device::Features::graphics_pipeline_library)
+    /// feature must be enabled on the device. Each library must have been created with
+    /// [`PipelineCreateFlags::LIBRARY`] from the same device, and the libraries must not contain
+    /// the same subset of state as each other, or as `library`. If `flags` does not contain
+    /// `LIBRARY`, then the libraries together must contain all subsets of state, and `stages`
+    /// must be empty and all other state must be `None`.
+    ///
+    /// The default value is empty.
+    pub libraries: Vec<Arc<GraphicsPipeline>>,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -1913,7 +2490,11 @@ impl GraphicsPipelineCreateInfo {
             layout,
             subpass: None,
             base_pipeline: None,
+            base_pipeline_index: None,
             discard_rectangle_state: None,
+            attachment_feedback_loop_enable_dynamic: false,
+            library: None,
+            libraries: Vec::new(),
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -1935,8 +2516,12 @@ impl GraphicsPipelineCreateInfo {
             ref layout,
             ref subpass,
             ref base_pipeline,
+            base_pipeline_index,
 
             ref discard_rectangle_state,
+            attachment_feedback_loop_enable_dynamic,
+            ref library,
+            ref libraries,
             _ne: _,
         } = self;
 
@@ -1945,28 +2530,195 @@ impl GraphicsPipelineCreateInfo {
                 .set_vuids(&["VUID-VkGraphicsPipelineCreateInfo-flags-parameter"])
         })?;
 
-        if flags.intersects(PipelineCreateFlags::DERIVATIVE) {
-            let base_pipeline = base_pipeline.as_ref().ok_or_else(|| {
-                Box::new(ValidationError {
-                    problem: "`flags` contains `PipelineCreateFlags::DERIVATIVE`, but \
-                        `base_pipeline` is `None`"
+        if flags.intersects(PipelineCreateFlags::DISPATCH_BASE) {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::DISPATCH_BASE`".into(),
+                vuids: &["VUID-VkGraphicsPipelineCreateInfo-flags-00764"],
+                ..Default::default()
+            }));
+        }
+
+        validate_descriptor_buffer_flag(device, flags, layout)?;
+        validate_protected_access_flags(device, flags)?;
+
+        if flags.intersects(PipelineCreateFlags::INDIRECT_BINDABLE)
+            && !device.enabled_features().device_generated_commands
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::INDIRECT_BINDABLE`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "device_generated_commands",
+                )])]),
+                vuids: &["VUID-VkGraphicsPipelineCreateInfo-flags-02877"],
+            }));
+        }
+
+        if flags.intersects(
+            PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED
+                | PipelineCreateFlags::EARLY_RETURN_ON_FAILURE,
+        ) && !device.enabled_features().pipeline_creation_cache_control
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::FAIL_ON_PIPELINE_COMPILE_REQUIRED` or \
+                    `PipelineCreateFlags::EARLY_RETURN_ON_FAILURE`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "pipeline_creation_cache_control",
+                )])]),
+                vuids: &["VUID-VkGraphicsPipelineCreateInfo-pipelineCreationCacheControl-02878"],
+            }));
+        }
+
+        if flags.intersects(PipelineCreateFlags::LIBRARY) {
+            if !device.enabled_features().graphics_pipeline_library {
+                return Err(Box::new(ValidationError {
+                    context: "flags".into(),
+                    problem: "contains `PipelineCreateFlags::LIBRARY`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "graphics_pipeline_library",
+                    )])]),
+                    vuids: &["VUID-VkGraphicsPipelineCreateInfo-graphicsPipelineLibrary-06606"],
+                }));
+            }
+
+            if library.is_none() {
+                return Err(Box::new(ValidationError {
+                    problem: "`flags` contains `PipelineCreateFlags::LIBRARY`, but \
+                        `library` is `None`"
                         .into(),
-                    vuids: &["VUID-VkGraphicsPipelineCreateInfo-flags-07984"],
                     ..Default::default()
-                })
-            })?;
+                }));
+            }
+        } else if library.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "`flags` does not contain `PipelineCreateFlags::LIBRARY`, but \
+                    `library` is `Some`"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        if let Some(library) = library {
+            library
+                .validate(device)
+                .map_err(|err| err.add_context("library"))?;
+        }
 
-            if !base_pipeline
+        if !libraries.is_empty() && !device.enabled_features().graphics_pipeline_library {
+            return Err(Box::new(ValidationError {
+                context: "libraries".into(),
+                problem: "is not empty".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "graphics_pipeline_library",
+                )])]),
+                vuids: &["VUID-VkGraphicsPipelineCreateInfo-graphicsPipelineLibrary-06607"],
+            }));
+        }
+
+        let mut library_flags_present = library
+            .as_ref()
+            .map_or(GraphicsPipelineLibraryFlags::empty(), |library| {
+                library.flags
+            });
+
+        for (library_index, pipeline_library) in libraries.iter().enumerate() {
+            // VUID-VkGraphicsPipelineCreateInfo-commonparent
+            assert_eq!(device, pipeline_library.device().as_ref());
+
+            if !pipeline_library
                 .flags()
-                .intersects(PipelineCreateFlags::ALLOW_DERIVATIVES)
+                .intersects(PipelineCreateFlags::LIBRARY)
             {
                 return Err(Box::new(ValidationError {
-                    context: "base_pipeline.flags()".into(),
-                    problem: "does not contain `PipelineCreateFlags::ALLOW_DERIVATIVES`".into(),
-                    vuids: &["VUID-vkCreateGraphicsPipelines-flags-00721"],
+                    context: format!("libraries[{}].flags()", library_index).into(),
+                    problem: "does not contain `PipelineCreateFlags::LIBRARY`".into(),
+                    vuids: &["VUID-VkPipelineLibraryCreateInfoKHR-pLibraries-03381"],
                     ..Default::default()
                 }));
             }
+
+            if library_flags_present.intersects(pipeline_library.library_flags()) {
+                return Err(Box::new(ValidationError {
+                    context: format!("libraries[{}].library_flags()", library_index).into(),
+                    problem: "contains a subset of state that is also contained in `library` or \
+                        in an earlier element of `libraries`"
+                        .into(),
+                    // vuids?
+                    ..Default::default()
+                }));
+            }
+
+            layout
+                .ensure_compatible_with_shader(
+                    pipeline_library
+                        .descriptor_binding_requirements()
+                        .iter()
+                        .map(|(k, v)| (*k, v)),
+                    None,
+                )
+                .map_err(|err| {
+                    Box::new(ValidationError {
+                        context: format!("libraries[{}]", library_index).into(),
+                        ..ValidationError::from_error(err)
+                    })
+                })?;
+
+            library_flags_present |= pipeline_library.library_flags();
+        }
+
+        if !libraries.is_empty()
+            && !flags.intersects(PipelineCreateFlags::LIBRARY)
+            && library_flags_present != GraphicsPipelineLibraryFlags::all()
+        {
+            return Err(Box::new(ValidationError {
+                problem: "`flags` does not contain `PipelineCreateFlags::LIBRARY`, and \
+                    `libraries` is not empty, but the elements of `libraries` do not together \
+                    contain all subsets of graphics pipeline state"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        if flags.intersects(PipelineCreateFlags::DERIVATIVE) {
+            match (base_pipeline, base_pipeline_index) {
+                (Some(base_pipeline), None) => {
+                    if !base_pipeline
+                        .flags()
+                        .intersects(PipelineCreateFlags::ALLOW_DERIVATIVES)
+                    {
+                        return Err(Box::new(ValidationError {
+                            context: "base_pipeline.flags()".into(),
+                            problem: "does not contain `PipelineCreateFlags::ALLOW_DERIVATIVES`"
+                                .into(),
+                            vuids: &["VUID-vkCreateGraphicsPipelines-flags-00721"],
+                            ..Default::default()
+                        }));
+                    }
+                }
+                (None, Some(_)) => (),
+                (None, None) => {
+                    return Err(Box::new(ValidationError {
+                        problem: "`flags` contains `PipelineCreateFlags::DERIVATIVE`, but \
+                            `base_pipeline` and `base_pipeline_index` are both `None`"
+                            .into(),
+                        vuids: &[
+                            "VUID-VkGraphicsPipelineCreateInfo-flags-07984",
+                            "VUID-VkGraphicsPipelineCreateInfo-flags-07985",
+                        ],
+                        ..Default::default()
+                    }));
+                }
+                (Some(_), Some(_)) => {
+                    return Err(Box::new(ValidationError {
+                        problem: "`base_pipeline` and `base_pipeline_index` are both `Some`".into(),
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-flags-07986"],
+                        ..Default::default()
+                    }));
+                }
+            }
         } else if base_pipeline.is_some() {
             return Err(Box::new(ValidationError {
                 problem: "`flags` does not contain `PipelineCreateFlags::DERIVATIVE`, but \
@@ -1974,6 +2726,13 @@ impl GraphicsPipelineCreateInfo {
                     .into(),
                 ..Default::default()
             }));
+        } else if base_pipeline_index.is_some() {
+            return Err(Box::new(ValidationError {
+                problem: "`flags` does not contain `PipelineCreateFlags::DERIVATIVE`, but \
+                    `base_pipeline_index` is `Some`"
+                    .into(),
+                ..Default::default()
+            }));
         }
 
         /*
@@ -2050,7 +2809,18 @@ impl GraphicsPipelineCreateInfo {
             Validate needed/unused state
         */
 
-        let need_pre_rasterization_shader_state = true;
+        // The subsets of state that this create info itself provides. When linking libraries into
+        // a complete pipeline, all of the state comes from the libraries.
+        let own_library_flags = if flags.intersects(PipelineCreateFlags::LIBRARY) {
+            library.as_ref().unwrap().flags
+        } else if libraries.is_empty() {
+            GraphicsPipelineLibraryFlags::all()
+        } else {
+            GraphicsPipelineLibraryFlags::empty()
+        };
+
+        let need_pre_rasterization_shader_state =
+            own_library_flags.intersects(GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS);
 
         // Check this first because everything else depends on it.
         match (
@@ -2080,22 +2850,31 @@ impl GraphicsPipelineCreateInfo {
             _ => (),
         }
 
-        let need_vertex_input_state = need_pre_rasterization_shader_state
-            && stages
-                .iter()
-                .any(|stage| matches!(stage.entry_point.info().execution, ShaderExecution::Vertex));
-        let need_fragment_shader_state = need_pre_rasterization_shader_state
-            && rasterization_state
-                .as_ref()
-                .unwrap()
-                .rasterizer_discard_enable
-                != StateMode::Fixed(true);
-        let need_fragment_output_state = need_pre_rasterization_shader_state
-            && rasterization_state
-                .as_ref()
-                .unwrap()
-                .rasterizer_discard_enable
-                != StateMode::Fixed(true);
+        let (need_vertex_input_state, need_fragment_shader_state, need_fragment_output_state) =
+            if own_library_flags == GraphicsPipelineLibraryFlags::all() {
+                let rasterizer_discard = rasterization_state
+                    .as_ref()
+                    .unwrap()
+                    .rasterizer_discard_enable
+                    == StateMode::Fixed(true);
+
+                (
+                    stages.iter().any(|stage| {
+                        matches!(stage.entry_point.info().execution, ShaderExecution::Vertex)
+                    }),
+                    !rasterizer_discard,
+                    !rasterizer_discard,
+                )
+            } else {
+                // A pipeline library must provide the state of exactly the subsets it contains.
+                (
+                    own_library_flags
+                        .intersects(GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE),
+                    own_library_flags.intersects(GraphicsPipelineLibraryFlags::FRAGMENT_SHADER),
+                    own_library_flags
+                        .intersects(GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE),
+                )
+            };
 
         match (vertex_stage.is_some(), need_pre_rasterization_shader_state) {
             (true, false) => {
@@ -2350,24 +3129,29 @@ impl GraphicsPipelineCreateInfo {
             _ => (),
         }
 
+        let subpass_has_depth_stencil_attachment = match subpass {
+            Some(PipelineSubpassType::BeginRenderPass(subpass)) => {
+                subpass.subpass_desc().depth_stencil_attachment.is_some()
+            }
+            Some(PipelineSubpassType::BeginRendering(rendering_info)) => {
+                rendering_info.depth_attachment_format.is_some()
+                    || rendering_info.stencil_attachment_format.is_some()
+            }
+            None => false,
+        };
+
         match (
             depth_stencil_state.is_some(),
-            !need_fragment_output_state
-                || match subpass {
-                    Some(PipelineSubpassType::BeginRenderPass(subpass)) => {
-                        subpass.subpass_desc().depth_stencil_attachment.is_some()
-                    }
-                    Some(PipelineSubpassType::BeginRendering(rendering_info)) => {
-                        rendering_info.depth_attachment_format.is_some()
-                            || rendering_info.stencil_attachment_format.is_some()
-                    }
-                    None => false,
-                },
+            if own_library_flags == GraphicsPipelineLibraryFlags::all() {
+                !need_fragment_output_state || subpass_has_depth_stencil_attachment
+            } else {
+                need_fragment_shader_state && subpass_has_depth_stencil_attachment
+            },
         ) {
             (true, false) => {
                 return Err(Box::new(ValidationError {
-                    problem: "the pipeline is being created with \
-                        fragment output state, and \
+                    problem: "the pipeline is not being created with \
+                        fragment shader state, or \
                         `subpass` does not have a depth/stencil attachment, but \
                         `depth_stencil_state` is `Some`"
                         .into(),
@@ -2376,9 +3160,8 @@ impl GraphicsPipelineCreateInfo {
             }
             (false, true) => {
                 return Err(Box::new(ValidationError {
-                    problem: "the pipeline is not being created with \
-                        fragment output state, or \
-                        `subpass` has a depth/stencil attachment, but \
+                    problem: "`subpass` has a depth/stencil attachment, or \
+                        `rasterization_state.rasterization_discard_enable` is `true`, but \
                         `depth_stencil_state` is `None`"
                         .into(),
                     vuids: &[
@@ -2612,9 +3395,6 @@ impl GraphicsPipelineCreateInfo {
             depth_stencil_state
                 .validate(device)
                 .map_err(|err| err.add_context("depth_stencil_state"))?;
-
-            // TODO:
-            // VUID-VkGraphicsPipelineCreateInfo-renderPass-06040
         }
 
         if let Some(color_blend_state) = color_blend_state {
@@ -2629,6 +3409,17 @@ impl GraphicsPipelineCreateInfo {
                     // VUID-VkGraphicsPipelineCreateInfo-commonparent
                     assert_eq!(device, subpass.render_pass().device().as_ref());
 
+                    if subpass.subpass_desc().pipeline_bind_point != PipelineBindPoint::Graphics {
+                        return Err(Box::new(ValidationError {
+                            context: "subpass".into(),
+                            problem: "the `pipeline_bind_point` of the subpass is not \
+                                `PipelineBindPoint::Graphics`"
+                                .into(),
+                            // vuids?
+                            ..Default::default()
+                        }));
+                    }
+
                     if subpass.subpass_desc().view_mask != 0 {
                         if stages_present.intersects(
                             ShaderStages::TESSELLATION_CONTROL
@@ -2722,6 +3513,47 @@ impl GraphicsPipelineCreateInfo {
                     }
                 }
             }
+
+            let view_mask = match subpass {
+                PipelineSubpassType::BeginRenderPass(subpass) => subpass.subpass_desc().view_mask,
+                PipelineSubpassType::BeginRendering(rendering_info) => rendering_info.view_mask,
+            };
+
+            if flags.intersects(PipelineCreateFlags::VIEW_INDEX_FROM_DEVICE_INDEX) && view_mask == 0
+            {
+                return Err(Box::new(ValidationError {
+                    problem:
+                        "`flags` contains `PipelineCreateFlags::VIEW_INDEX_FROM_DEVICE_INDEX`, \
+                        but the view mask of `subpass` is 0"
+                            .into(),
+                    // vuids?
+                    ..Default::default()
+                }));
+            }
+
+            if let PipelineSubpassType::BeginRendering(_) = subpass {
+                if flags.intersects(PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP) {
+                    return Err(Box::new(ValidationError {
+                        problem: "`flags` contains \
+                            `PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP`, but \
+                            `subpass` is `PipelineSubpassType::BeginRendering`"
+                            .into(),
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-flags-06482"],
+                        ..Default::default()
+                    }));
+                }
+
+                if flags.intersects(PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP) {
+                    return Err(Box::new(ValidationError {
+                        problem: "`flags` contains \
+                            `PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP`, but \
+                            `subpass` is `PipelineSubpassType::BeginRendering`"
+                            .into(),
+                        vuids: &["VUID-VkGraphicsPipelineCreateInfo-flags-06483"],
+                        ..Default::default()
+                    }));
+                }
+            }
         }
 
         if let Some(discard_rectangle_state) = discard_rectangle_state {
@@ -2741,6 +3573,39 @@ impl GraphicsPipelineCreateInfo {
                 .map_err(|err| err.add_context("discard_rectangle_state"))?;
         }
 
+        if attachment_feedback_loop_enable_dynamic {
+            if !device
+                .enabled_features()
+                .attachment_feedback_loop_dynamic_state
+            {
+                return Err(Box::new(ValidationError {
+                    context: "attachment_feedback_loop_enable_dynamic".into(),
+                    problem: "is `true`".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "attachment_feedback_loop_dynamic_state",
+                    )])]),
+                    vuids: &["VUID-VkGraphicsPipelineCreateInfo-attachmentFeedbackLoopDynamicState-08910"],
+                }));
+            }
+
+            if flags.intersects(
+                PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP
+                    | PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP,
+            ) {
+                return Err(Box::new(ValidationError {
+                    problem: "`attachment_feedback_loop_enable_dynamic` is `true`, but \
+                        `flags` contains `PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP` or \
+                        `PipelineCreateFlags::DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP`"
+                        .into(),
+                    vuids: &[
+                        "VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-08715",
+                        "VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-08716",
+                    ],
+                    ..Default::default()
+                }));
+            }
+        }
+
         /*
             Checks that rely on multiple pieces of state
         */
@@ -2913,15 +3778,22 @@ impl GraphicsPipelineCreateInfo {
             match subpass {
                 PipelineSubpassType::BeginRenderPass(subpass) => {
                     if let Some(samples) = subpass.num_samples() {
-                        if multisample_state.rasterization_samples != samples {
-                            return Err(Box::new(ValidationError {
-                                problem: "`multisample_state.rasterization_samples` does not \
-                                    equal the number of samples in the color and depth/stencil \
-                                    attachments of `subpass`"
+                        if let StateMode::Fixed(rasterization_samples) =
+                            multisample_state.rasterization_samples
+                        {
+                            if rasterization_samples != samples {
+                                return Err(Box::new(ValidationError {
+                                    problem: format!(
+                                        "`multisample_state.rasterization_samples` ({:?}) does \
+                                        not equal the number of samples in the color and \
+                                        depth/stencil attachments of `subpass` ({:?})",
+                                        rasterization_samples, samples,
+                                    )
                                     .into(),
-                                vuids: &["VUID-VkGraphicsPipelineCreateInfo-subpass-00757"],
-                                ..Default::default()
-                            }));
+                                    vuids: &["VUID-VkGraphicsPipelineCreateInfo-subpass-00757"],
+                                    ..Default::default()
+                                }));
+                            }
                         }
                     }
 
@@ -3031,6 +3903,46 @@ impl GraphicsPipelineCreateInfo {
                         ..Default::default()
                     }));
                 }
+
+                if let (Some(stencil_state), PipelineSubpassType::BeginRenderPass(subpass)) =
+                    (&depth_stencil_state.stencil, subpass)
+                {
+                    let stencil_writable = subpass
+                        .subpass_desc()
+                        .depth_stencil_attachment
+                        .as_ref()
+                        .is_some_and(|depth_stencil_attachment| {
+                            depth_stencil_attachment
+                                .stencil_layout
+                                .unwrap_or(depth_stencil_attachment.layout)
+                                .is_writable(ImageAspect::Stencil)
+                        });
+
+                    if !stencil_writable {
+                        let writes_stencil = [&stencil_state.front, &stencil_state.back]
+                            .into_iter()
+                            .any(|op_state| match op_state.ops {
+                                StateMode::Fixed(ops) => {
+                                    ops.fail_op != StencilOp::Keep
+                                        || ops.pass_op != StencilOp::Keep
+                                        || ops.depth_fail_op != StencilOp::Keep
+                                }
+                                StateMode::Dynamic => false,
+                            });
+
+                        if writes_stencil {
+                            return Err(Box::new(ValidationError {
+                                problem: "`subpass` has a depth/stencil attachment whose layout \
+                                    for the stencil aspect is read-only, but the `fail_op`, \
+                                    `pass_op` or `depth_fail_op` of `depth_stencil_state.stencil` \
+                                    is not `StencilOp::Keep`"
+                                    .into(),
+                                vuids: &["VUID-VkGraphicsPipelineCreateInfo-renderPass-06040"],
+                                ..Default::default()
+                            }));
+                        }
+                    }
+                }
             }
         }
 
@@ -3104,3 +4016,1163 @@ impl GraphicsPipelineCreateInfo {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        color_blend::{ColorBlendState, ColorComponents},
+        input_assembly::{InputAssemblyState, PrimitiveTopology},
+        library::{GraphicsPipelineLibraryCreateInfo, GraphicsPipelineLibraryFlags},
+        multisample::MultisampleState,
+        rasterization::{
+            LineRasterizationMode, LineStipple, ProvokingVertexMode, RasterizationState,
+        },
+        subpass::{PipelineRenderingCreateInfo, PipelineSubpassType},
+        vertex_input::VertexInputState,
+        viewport::{Viewport, ViewportState},
+        GraphicsPipeline, GraphicsPipelineCreateInfo,
+    };
+    use crate::{
+        device::Device,
+        format::Format,
+        image::{ImageLayout, SampleCount},
+        pipeline::{
+            graphics::rasterization::CullMode, DynamicState, PartialStateMode, Pipeline,
+            PipelineCreateFlags, PipelineLayout, PipelineShaderStageCreateInfo, StateMode,
+        },
+        render_pass::{
+            AttachmentDescription, AttachmentLoadOp, AttachmentReference, AttachmentStoreOp,
+            RenderPass, RenderPassCreateInfo, Subpass, SubpassDescription,
+        },
+        shader::{ShaderModule, ShaderModuleCreateInfo},
+        Validated,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn rendering_create_info_formats() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rendering_info = |format| PipelineRenderingCreateInfo {
+            color_attachment_formats: vec![Some(format)],
+            ..Default::default()
+        };
+
+        // RGBA8 is always renderable.
+        assert!(rendering_info(Format::R8G8B8A8_UNORM)
+            .validate(&device)
+            .is_ok());
+        // Depth formats can't be used as color attachments.
+        assert!(rendering_info(Format::D16_UNORM).validate(&device).is_err());
+        assert!(PipelineRenderingCreateInfo {
+            depth_attachment_format: Some(Format::R8G8B8A8_UNORM),
+            ..Default::default()
+        }
+        .validate(&device)
+        .is_err());
+    }
+
+    #[test]
+    fn rasterization_samples_mismatch() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let render_pass = single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 4,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass, 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        match GraphicsPipeline::new(
+            device,
+            None,
+            GraphicsPipelineCreateInfo {
+                stages: [PipelineShaderStageCreateInfo::new(vs)]
+                    .into_iter()
+                    .collect(),
+                vertex_input_state: Some(VertexInputState::default()),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState {
+                    rasterization_samples: StateMode::Fixed(SampleCount::Sample1),
+                    ..Default::default()
+                }),
+                color_blend_state: Some(ColorBlendState::new(subpass.num_color_attachments())),
+                subpass: Some(subpass.into()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        ) {
+            Err(Validated::ValidationError(err)) => {
+                assert_eq!(
+                    err.vuids,
+                    ["VUID-VkGraphicsPipelineCreateInfo-subpass-00757"]
+                );
+                assert!(err.problem.contains("Sample1"));
+                assert!(err.problem.contains("Sample4"));
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn new_batch_derivatives() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let render_pass = single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass, 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let create_info = |flags, cull_mode, base_pipeline_index| GraphicsPipelineCreateInfo {
+            flags,
+            stages: [PipelineShaderStageCreateInfo::new(vs.clone())]
+                .into_iter()
+                .collect(),
+            vertex_input_state: Some(VertexInputState::default()),
+            input_assembly_state: Some(InputAssemblyState::default()),
+            viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+            rasterization_state: Some(RasterizationState {
+                cull_mode: StateMode::Fixed(cull_mode),
+                ..Default::default()
+            }),
+            multisample_state: Some(MultisampleState::default()),
+            color_blend_state: Some(ColorBlendState::new(subpass.num_color_attachments())),
+            subpass: Some(subpass.clone().into()),
+            base_pipeline_index,
+            ..GraphicsPipelineCreateInfo::layout(layout.clone())
+        };
+
+        let pipelines = GraphicsPipeline::new_batch(
+            device.clone(),
+            None,
+            [
+                create_info(PipelineCreateFlags::ALLOW_DERIVATIVES, CullMode::None, None),
+                create_info(PipelineCreateFlags::DERIVATIVE, CullMode::Back, Some(0)),
+                create_info(PipelineCreateFlags::DERIVATIVE, CullMode::Front, Some(0)),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(pipelines.len(), 3);
+        assert_eq!(
+            pipelines[1].rasterization_state().cull_mode,
+            StateMode::Fixed(CullMode::Back),
+        );
+        assert!(pipelines[2]
+            .flags()
+            .intersects(PipelineCreateFlags::DERIVATIVE));
+        assert!(pipelines
+            .iter()
+            .all(|pipeline| pipeline.layout() == &layout));
+
+        // The base pipeline must come before its derivatives.
+        assert!(matches!(
+            GraphicsPipeline::new_batch(
+                device.clone(),
+                None,
+                [
+                    create_info(PipelineCreateFlags::DERIVATIVE, CullMode::Back, Some(1)),
+                    create_info(PipelineCreateFlags::ALLOW_DERIVATIVES, CullMode::None, None),
+                ],
+            ),
+            Err(Validated::ValidationError(_)),
+        ));
+
+        // Indices can't be used when creating a single pipeline.
+        assert!(matches!(
+            GraphicsPipeline::new(
+                device,
+                None,
+                create_info(PipelineCreateFlags::DERIVATIVE, CullMode::Back, Some(0)),
+            ),
+            Err(Validated::ValidationError(_)),
+        ));
+    }
+
+    #[test]
+    fn dynamic_scissor() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let render_pass = single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass, 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let pipeline = GraphicsPipeline::new(
+            device,
+            None,
+            GraphicsPipelineCreateInfo {
+                stages: [PipelineShaderStageCreateInfo::new(vs)]
+                    .into_iter()
+                    .collect(),
+                vertex_input_state: Some(VertexInputState::default()),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                viewport_state: Some(ViewportState {
+                    viewports: PartialStateMode::Fixed(
+                        [Viewport {
+                            offset: [0.0, 0.0],
+                            extent: [64.0, 64.0],
+                            depth_range: 0.0..=1.0,
+                        }]
+                        .into_iter()
+                        .collect(),
+                    ),
+                    scissors: PartialStateMode::Dynamic(StateMode::Fixed(1)),
+                    ..Default::default()
+                }),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState::default()),
+                color_blend_state: Some(ColorBlendState::new(subpass.num_color_attachments())),
+                subpass: Some(subpass.into()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )
+        .unwrap();
+
+        assert!(pipeline.is_dynamic(DynamicState::Scissor));
+        assert!(!pipeline.is_dynamic(DynamicState::Viewport));
+        assert_eq!(pipeline.dynamic_state(DynamicState::Scissor), Some(true));
+        assert_eq!(pipeline.dynamic_state(DynamicState::Viewport), Some(false));
+        assert_eq!(
+            pipeline.dynamic_state(DynamicState::AttachmentFeedbackLoopEnable),
+            None,
+        );
+        assert!(pipeline
+            .dynamic_states()
+            .any(|(state, dynamic)| state == DynamicState::Scissor && dynamic));
+    }
+
+    #[test]
+    fn link_libraries() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [khr_pipeline_library, ext_graphics_pipeline_library],
+            features: [graphics_pipeline_library],
+        );
+
+        let render_pass = single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass, 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let vertex_input_library = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                flags: PipelineCreateFlags::LIBRARY,
+                vertex_input_state: Some(VertexInputState::default()),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                library: Some(GraphicsPipelineLibraryCreateInfo::flags(
+                    GraphicsPipelineLibraryFlags::VERTEX_INPUT_INTERFACE,
+                )),
+                ..GraphicsPipelineCreateInfo::layout(layout.clone())
+            },
+        )
+        .unwrap();
+
+        let shaders_library = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                flags: PipelineCreateFlags::LIBRARY,
+                stages: [PipelineShaderStageCreateInfo::new(vs)]
+                    .into_iter()
+                    .collect(),
+                viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState::default()),
+                color_blend_state: Some(ColorBlendState::new(subpass.num_color_attachments())),
+                subpass: Some(subpass.into()),
+                library: Some(GraphicsPipelineLibraryCreateInfo::flags(
+                    GraphicsPipelineLibraryFlags::PRE_RASTERIZATION_SHADERS
+                        | GraphicsPipelineLibraryFlags::FRAGMENT_SHADER
+                        | GraphicsPipelineLibraryFlags::FRAGMENT_OUTPUT_INTERFACE,
+                )),
+                ..GraphicsPipelineCreateInfo::layout(layout.clone())
+            },
+        )
+        .unwrap();
+
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            GraphicsPipelineCreateInfo {
+                libraries: vec![vertex_input_library.clone(), shaders_library.clone()],
+                ..GraphicsPipelineCreateInfo::layout(layout.clone())
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            pipeline.library_flags(),
+            GraphicsPipelineLibraryFlags::all()
+        );
+        assert!(!pipeline.flags().intersects(PipelineCreateFlags::LIBRARY));
+        assert!(pipeline.viewport_state().is_some());
+        assert!(pipeline.color_blend_state().is_some());
+
+        // Each subset of state must be contained in only one library.
+        assert!(matches!(
+            GraphicsPipeline::new(
+                device.clone(),
+                None,
+                GraphicsPipelineCreateInfo {
+                    libraries: vec![
+                        vertex_input_library.clone(),
+                        vertex_input_library,
+                        shaders_library.clone(),
+                    ],
+                    ..GraphicsPipelineCreateInfo::layout(layout.clone())
+                },
+            ),
+            Err(Validated::ValidationError(_)),
+        ));
+
+        // All subsets of state must be present when linking a complete pipeline.
+        assert!(matches!(
+            GraphicsPipeline::new(
+                device,
+                None,
+                GraphicsPipelineCreateInfo {
+                    libraries: vec![shaders_library],
+                    ..GraphicsPipelineCreateInfo::layout(layout)
+                },
+            ),
+            Err(Validated::ValidationError(_)),
+        ));
+    }
+
+    #[test]
+    fn view_index_from_device_index() {
+        // Every device is part of a device group, so a single device is enough here.
+        let (device, _) = gfx_dev_and_queue!(multiview);
+
+        let multiview_render_pass = RenderPass::new(
+            device.clone(),
+            RenderPassCreateInfo {
+                attachments: vec![AttachmentDescription {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: SampleCount::Sample1,
+                    load_op: AttachmentLoadOp::Clear,
+                    store_op: AttachmentStoreOp::Store,
+                    initial_layout: ImageLayout::ColorAttachmentOptimal,
+                    final_layout: ImageLayout::ColorAttachmentOptimal,
+                    ..Default::default()
+                }],
+                subpasses: vec![SubpassDescription {
+                    view_mask: 0b11,
+                    color_attachments: vec![Some(AttachmentReference {
+                        attachment: 0,
+                        layout: ImageLayout::ColorAttachmentOptimal,
+                        ..Default::default()
+                    })],
+                    ..Default::default()
+                }],
+                correlated_view_masks: vec![0b11],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let single_view_render_pass = single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let create_info = |subpass: Subpass| GraphicsPipelineCreateInfo {
+            flags: PipelineCreateFlags::VIEW_INDEX_FROM_DEVICE_INDEX,
+            stages: [PipelineShaderStageCreateInfo::new(vs.clone())]
+                .into_iter()
+                .collect(),
+            vertex_input_state: Some(VertexInputState::default()),
+            input_assembly_state: Some(InputAssemblyState::default()),
+            viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+            rasterization_state: Some(RasterizationState::default()),
+            multisample_state: Some(MultisampleState::default()),
+            color_blend_state: Some(ColorBlendState::new(subpass.num_color_attachments())),
+            subpass: Some(subpass.into()),
+            ..GraphicsPipelineCreateInfo::layout(layout.clone())
+        };
+
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            create_info(Subpass::from(multiview_render_pass, 0).unwrap()),
+        )
+        .unwrap();
+        assert!(pipeline
+            .flags()
+            .intersects(PipelineCreateFlags::VIEW_INDEX_FROM_DEVICE_INDEX));
+
+        // The flag requires multiview.
+        assert!(matches!(
+            GraphicsPipeline::new(
+                device,
+                None,
+                create_info(Subpass::from(single_view_render_pass, 0).unwrap()),
+            ),
+            Err(Validated::ValidationError(_)),
+        ));
+    }
+
+    #[test]
+    fn color_attachment_feedback_loop() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_attachment_feedback_loop_layout],
+            features: [attachment_feedback_loop_layout, dynamic_rendering],
+        );
+
+        let feedback_render_pass = RenderPass::new(
+            device.clone(),
+            RenderPassCreateInfo {
+                attachments: vec![AttachmentDescription {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: SampleCount::Sample1,
+                    load_op: AttachmentLoadOp::Load,
+                    store_op: AttachmentStoreOp::Store,
+                    initial_layout: ImageLayout::AttachmentFeedbackLoopOptimal,
+                    final_layout: ImageLayout::AttachmentFeedbackLoopOptimal,
+                    ..Default::default()
+                }],
+                subpasses: vec![SubpassDescription {
+                    color_attachments: vec![Some(AttachmentReference {
+                        attachment: 0,
+                        layout: ImageLayout::AttachmentFeedbackLoopOptimal,
+                        ..Default::default()
+                    })],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let plain_render_pass = single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let create_info = |subpass: PipelineSubpassType| GraphicsPipelineCreateInfo {
+            flags: PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP,
+            stages: [PipelineShaderStageCreateInfo::new(vs.clone())]
+                .into_iter()
+                .collect(),
+            vertex_input_state: Some(VertexInputState::default()),
+            input_assembly_state: Some(InputAssemblyState::default()),
+            viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+            rasterization_state: Some(RasterizationState::default()),
+            multisample_state: Some(MultisampleState::default()),
+            color_blend_state: Some(ColorBlendState::new(1)),
+            subpass: Some(subpass),
+            ..GraphicsPipelineCreateInfo::layout(layout.clone())
+        };
+
+        // The layout of the attachments is only checked when drawing.
+        for render_pass in [feedback_render_pass, plain_render_pass] {
+            GraphicsPipeline::new(
+                device.clone(),
+                None,
+                create_info(Subpass::from(render_pass, 0).unwrap().into()),
+            )
+            .unwrap();
+        }
+
+        match GraphicsPipeline::new(
+            device,
+            None,
+            create_info(
+                PipelineRenderingCreateInfo {
+                    color_attachment_formats: vec![Some(Format::R8G8B8A8_UNORM)],
+                    ..Default::default()
+                }
+                .into(),
+            ),
+        ) {
+            Err(Validated::ValidationError(err)) => {
+                assert_eq!(err.vuids, ["VUID-VkGraphicsPipelineCreateInfo-flags-06482"]);
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn attachment_feedback_loop_enable_dynamic() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [
+                ext_attachment_feedback_loop_layout,
+                ext_attachment_feedback_loop_dynamic_state,
+            ],
+            features: [attachment_feedback_loop_layout, attachment_feedback_loop_dynamic_state],
+        );
+
+        let render_pass = single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let create_info = |flags| GraphicsPipelineCreateInfo {
+            flags,
+            stages: [PipelineShaderStageCreateInfo::new(vs.clone())]
+                .into_iter()
+                .collect(),
+            vertex_input_state: Some(VertexInputState::default()),
+            input_assembly_state: Some(InputAssemblyState::default()),
+            viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+            rasterization_state: Some(RasterizationState::default()),
+            multisample_state: Some(MultisampleState::default()),
+            color_blend_state: Some(ColorBlendState::new(1)),
+            subpass: Some(Subpass::from(render_pass.clone(), 0).unwrap().into()),
+            attachment_feedback_loop_enable_dynamic: true,
+            ..GraphicsPipelineCreateInfo::layout(layout.clone())
+        };
+
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            create_info(PipelineCreateFlags::empty()),
+        )
+        .unwrap();
+        assert_eq!(
+            pipeline.dynamic_state(DynamicState::AttachmentFeedbackLoopEnable),
+            Some(true),
+        );
+
+        // The feedback loop can't be both dynamic and baked into the pipeline.
+        match GraphicsPipeline::new(
+            device,
+            None,
+            create_info(PipelineCreateFlags::COLOR_ATTACHMENT_FEEDBACK_LOOP),
+        ) {
+            Err(Validated::ValidationError(err)) => {
+                assert_eq!(
+                    err.vuids,
+                    [
+                        "VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-08715",
+                        "VUID-VkGraphicsPipelineCreateInfo-pDynamicStates-08716",
+                    ]
+                );
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn dynamic_color_write_mask() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_extended_dynamic_state3],
+            features: [extended_dynamic_state3_color_write_mask],
+        );
+
+        let render_pass = single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color0: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+                color1: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [color0, color1],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let create_info = |color_blend_state| GraphicsPipelineCreateInfo {
+            stages: [PipelineShaderStageCreateInfo::new(vs.clone())]
+                .into_iter()
+                .collect(),
+            vertex_input_state: Some(VertexInputState::default()),
+            input_assembly_state: Some(InputAssemblyState::default()),
+            viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+            rasterization_state: Some(RasterizationState::default()),
+            multisample_state: Some(MultisampleState::default()),
+            color_blend_state: Some(color_blend_state),
+            subpass: Some(Subpass::from(render_pass.clone(), 0).unwrap().into()),
+            ..GraphicsPipelineCreateInfo::layout(layout.clone())
+        };
+
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            create_info(ColorBlendState::new(2).color_write_mask_dynamic()),
+        )
+        .unwrap();
+        assert_eq!(
+            pipeline.dynamic_state(DynamicState::ColorWriteMask),
+            Some(true),
+        );
+        assert_eq!(
+            pipeline.dynamic_state(DynamicState::ColorBlendEnable),
+            Some(false),
+        );
+
+        // The dynamic state applies to all attachments, so they can't be mixed.
+        let mut color_blend_state = ColorBlendState::new(2).color_write_mask_dynamic();
+        color_blend_state.attachments[1].color_write_mask =
+            StateMode::Fixed(ColorComponents::all());
+        assert!(matches!(
+            GraphicsPipeline::new(device, None, create_info(color_blend_state)),
+            Err(Validated::ValidationError(_)),
+        ));
+    }
+
+    #[test]
+    fn indirect_bindable() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [nv_device_generated_commands],
+            features: [device_generated_commands],
+        );
+
+        let render_pass = single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass, 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let pipeline = GraphicsPipeline::new(
+            device,
+            None,
+            GraphicsPipelineCreateInfo {
+                flags: PipelineCreateFlags::INDIRECT_BINDABLE,
+                stages: [PipelineShaderStageCreateInfo::new(vs)]
+                    .into_iter()
+                    .collect(),
+                vertex_input_state: Some(VertexInputState::default()),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState::default()),
+                color_blend_state: Some(ColorBlendState::new(subpass.num_color_attachments())),
+                subpass: Some(subpass.into()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )
+        .unwrap();
+        assert!(pipeline
+            .flags()
+            .intersects(PipelineCreateFlags::INDIRECT_BINDABLE));
+    }
+
+    #[test]
+    fn line_rasterization() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_line_rasterization],
+            features: [bresenham_lines, stippled_bresenham_lines],
+        );
+
+        let render_pass = single_pass_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+            },
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass, 0).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let create_info = |rasterization_state| GraphicsPipelineCreateInfo {
+            stages: [PipelineShaderStageCreateInfo::new(vs.clone())]
+                .into_iter()
+                .collect(),
+            vertex_input_state: Some(VertexInputState::default()),
+            input_assembly_state: Some(
+                InputAssemblyState::new().topology(PrimitiveTopology::LineList),
+            ),
+            viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+            rasterization_state: Some(rasterization_state),
+            multisample_state: Some(MultisampleState::default()),
+            color_blend_state: Some(ColorBlendState::new(subpass.num_color_attachments())),
+            subpass: Some(subpass.clone().into()),
+            ..GraphicsPipelineCreateInfo::layout(layout.clone())
+        };
+
+        let pipeline = GraphicsPipeline::new(
+            device.clone(),
+            None,
+            create_info(
+                RasterizationState::new()
+                    .line_rasterization_mode(LineRasterizationMode::Bresenham)
+                    .line_stipple(LineStipple {
+                        factor: 2,
+                        pattern: 0b1111_0000_1111_0000,
+                    }),
+            ),
+        )
+        .unwrap();
+
+        let rasterization_state = pipeline.rasterization_state();
+        assert_eq!(
+            rasterization_state.line_rasterization_mode,
+            LineRasterizationMode::Bresenham,
+        );
+        assert!(matches!(
+            rasterization_state.line_stipple,
+            Some(StateMode::Fixed(LineStipple { factor: 2, .. })),
+        ));
+        assert_eq!(
+            pipeline.dynamic_state(DynamicState::LineStipple),
+            Some(false)
+        );
+
+        // The stipple factor must be between 1 and 256.
+        assert!(matches!(
+            GraphicsPipeline::new(
+                device.clone(),
+                None,
+                create_info(
+                    RasterizationState::new()
+                        .line_rasterization_mode(LineRasterizationMode::Bresenham)
+                        .line_stipple(LineStipple {
+                            factor: 0,
+                            pattern: 0xffff,
+                        }),
+                ),
+            ),
+            Err(Validated::ValidationError(_)),
+        ));
+
+        // Smooth lines require the `smooth_lines` feature, which is not enabled.
+        assert!(matches!(
+            GraphicsPipeline::new(
+                device,
+                None,
+                create_info(
+                    RasterizationState::new()
+                        .line_rasterization_mode(LineRasterizationMode::RectangularSmooth),
+                ),
+            ),
+            Err(Validated::ValidationError(_)),
+        ));
+    }
+
+    #[test]
+    fn provoking_vertex_last() {
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [ext_provoking_vertex],
+            features: [provoking_vertex_last],
+        );
+        let (device_without_feature, _) =
+            gfx_dev_and_queue!(extensions: [ext_provoking_vertex], features: []);
+
+        let create_pipeline = |device: Arc<Device>| {
+            let render_pass = single_pass_renderpass!(
+                device.clone(),
+                attachments: {
+                    color: {
+                        format: Format::R8G8B8A8_UNORM,
+                        samples: 1,
+                        load_op: Clear,
+                        store_op: DontCare,
+                    },
+                },
+                pass: {
+                    color: [color],
+                    depth_stencil: {},
+                },
+            )
+            .unwrap();
+            let subpass = Subpass::from(render_pass, 0).unwrap();
+
+            let vs = unsafe {
+                /*
+                #version 450
+
+                void main() {
+                }
+                */
+                const MODULE: [u32; 29] = [
+                    119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981,
+                    0, 131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+                ];
+                let module =
+                    ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE))
+                        .unwrap();
+                module.entry_point("main").unwrap()
+            };
+            let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+            GraphicsPipeline::new(
+                device,
+                None,
+                GraphicsPipelineCreateInfo {
+                    stages: [PipelineShaderStageCreateInfo::new(vs)]
+                        .into_iter()
+                        .collect(),
+                    vertex_input_state: Some(VertexInputState::default()),
+                    input_assembly_state: Some(InputAssemblyState::default()),
+                    viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                    rasterization_state: Some(RasterizationState {
+                        provoking_vertex_mode: ProvokingVertexMode::Last,
+                        ..Default::default()
+                    }),
+                    multisample_state: Some(MultisampleState::default()),
+                    color_blend_state: Some(ColorBlendState::new(subpass.num_color_attachments())),
+                    subpass: Some(subpass.into()),
+                    ..GraphicsPipelineCreateInfo::layout(layout)
+                },
+            )
+        };
+
+        let pipeline = create_pipeline(device).unwrap();
+        assert_eq!(
+            pipeline.rasterization_state().provoking_vertex_mode,
+            ProvokingVertexMode::Last,
+        );
+
+        // The `provoking_vertex_last` feature must be enabled.
+        assert!(matches!(
+            create_pipeline(device_without_feature),
+            Err(Validated::ValidationError(_)),
+        ));
+    }
+
+    #[test]
+    fn subpass() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let render_pass = ordered_passes_renderpass!(
+            device.clone(),
+            attachments: {
+                color: {
+                    format: Format::R8G8B8A8_UNORM,
+                    samples: 1,
+                    load_op: Clear,
+                    store_op: DontCare,
+                },
+            },
+            passes: [
+                { color: [color], depth_stencil: {}, input: [] },
+                { color: [color], depth_stencil: {}, input: [] },
+            ],
+        )
+        .unwrap();
+        let subpass = Subpass::from(render_pass.clone(), 1).unwrap();
+
+        let vs = unsafe {
+            /*
+            #version 450
+
+            void main() {
+            }
+            */
+            const MODULE: [u32; 29] = [
+                119734787, 65536, 0, 5, 0, 131089, 1, 196622, 0, 1, 327695, 0, 3, 1852399981, 0,
+                131091, 1, 196641, 2, 1, 327734, 1, 3, 0, 2, 131320, 4, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+        let layout = PipelineLayout::new(device.clone(), Default::default()).unwrap();
+
+        let pipeline = GraphicsPipeline::new(
+            device,
+            None,
+            GraphicsPipelineCreateInfo {
+                stages: [PipelineShaderStageCreateInfo::new(vs)]
+                    .into_iter()
+                    .collect(),
+                vertex_input_state: Some(VertexInputState::default()),
+                input_assembly_state: Some(InputAssemblyState::default()),
+                viewport_state: Some(ViewportState::viewport_dynamic_scissor_irrelevant()),
+                rasterization_state: Some(RasterizationState::default()),
+                multisample_state: Some(MultisampleState::default()),
+                color_blend_state: Some(ColorBlendState::new(subpass.num_color_attachments())),
+                subpass: Some(subpass.into()),
+                ..GraphicsPipelineCreateInfo::layout(layout)
+            },
+        )
+        .unwrap();
+
+        match pipeline.subpass() {
+            PipelineSubpassType::BeginRenderPass(subpass) => {
+                assert_eq!(subpass.index(), 1);
+                assert_eq!(subpass.render_pass(), &render_pass);
+            }
+            PipelineSubpassType::BeginRendering(_) => panic!(),
+        }
+    }
+}
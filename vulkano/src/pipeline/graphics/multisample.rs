@@ -11,7 +11,8 @@
 //! anti-aliasing.
 
 use crate::{
-    device::Device, image::SampleCount, Requires, RequiresAllOf, RequiresOneOf, ValidationError,
+    device::Device, image::SampleCount, pipeline::StateMode, Requires, RequiresAllOf,
+    RequiresOneOf, ValidationError,
 };
 
 // TODO: handle some weird behaviors with non-floating-point targets
@@ -23,8 +24,15 @@ pub struct MultisampleState {
     /// locations within each pixel and assign to each of these locations a different depth value.
     /// The depth and stencil test will then be run for each sample.
     ///
-    /// The default value is [`SampleCount::Sample1`].
-    pub rasterization_samples: SampleCount,
+    /// If the pipeline is created for a subpass of a render pass that has color or depth/stencil
+    /// attachments, this must equal the number of samples of those attachments.
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_rasterization_samples`](crate::device::Features::extended_dynamic_state3_rasterization_samples)
+    /// feature must be enabled on the device.
+    ///
+    /// The default value is `Fixed(SampleCount::Sample1)`.
+    pub rasterization_samples: StateMode<SampleCount>,
 
     /// Controls the proportion (between 0.0 and 1.0) of the samples that will be run through the
     /// fragment shader.
@@ -66,7 +74,7 @@ impl MultisampleState {
     #[inline]
     pub fn new() -> MultisampleState {
         MultisampleState {
-            rasterization_samples: SampleCount::Sample1,
+            rasterization_samples: StateMode::Fixed(SampleCount::Sample1),
             sample_shading: None,
             sample_mask: [0xFFFFFFFF; 2],
             alpha_to_coverage_enable: false,
@@ -85,13 +93,32 @@ impl MultisampleState {
             _ne: _,
         } = self;
 
-        rasterization_samples
-            .validate_device(device)
-            .map_err(|err| {
-                err.add_context("rasterization_samples").set_vuids(&[
-                    "VUID-VkPipelineMultisampleStateCreateInfo-rasterizationSamples-parameter",
-                ])
-            })?;
+        match rasterization_samples {
+            StateMode::Fixed(rasterization_samples) => rasterization_samples
+                .validate_device(device)
+                .map_err(|err| {
+                    err.add_context("rasterization_samples").set_vuids(&[
+                        "VUID-VkPipelineMultisampleStateCreateInfo-rasterizationSamples-parameter",
+                    ])
+                })?,
+            StateMode::Dynamic => {
+                if !device
+                    .enabled_features()
+                    .extended_dynamic_state3_rasterization_samples
+                {
+                    return Err(Box::new(ValidationError {
+                        context: "rasterization_samples".into(),
+                        problem: "is dynamic".into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "extended_dynamic_state3_rasterization_samples",
+                        )])]),
+                        vuids: &[
+                            "VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3RasterizationSamples-07373",
+                        ],
+                    }));
+                }
+            }
+        }
 
         if let Some(min_sample_shading) = sample_shading {
             if !device.enabled_features().sample_rate_shading {
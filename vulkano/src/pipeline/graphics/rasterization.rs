@@ -36,10 +36,14 @@ pub struct RasterizationState {
     /// This setting can ask the rasterizer to downgrade triangles into lines or points, or lines
     /// into points.
     ///
-    /// If set to a value other than `Fill`, the
+    /// If set to a value other than `Fixed(Fill)`, the
     /// [`fill_mode_non_solid`](crate::device::Features::fill_mode_non_solid) feature must be
     /// enabled on the device.
-    pub polygon_mode: PolygonMode,
+    ///
+    /// If set to `Dynamic`, the
+    /// [`extended_dynamic_state3_polygon_mode`](crate::device::Features::extended_dynamic_state3_polygon_mode)
+    /// feature must be enabled on the device.
+    pub polygon_mode: StateMode<PolygonMode>,
 
     /// Specifies whether front faces or back faces should be discarded, or none, or both.
     ///
@@ -82,6 +86,31 @@ pub struct RasterizationState {
     /// extension and an additional feature must be enabled on the device.
     pub line_stipple: Option<StateMode<LineStipple>>,
 
+    /// The vertex stream that is rasterized, when the vertex processing stages output to multiple
+    /// vertex streams.
+    ///
+    /// If this is not set to `0`, the [`ext_transform_feedback`] extension and the
+    /// [`geometry_streams`] feature must be enabled on the device, and the
+    /// [`transform_feedback_rasterization_stream_select`] device property must be `true`.
+    ///
+    /// The default value is `0`.
+    ///
+    /// [`ext_transform_feedback`]: crate::device::DeviceExtensions::ext_transform_feedback
+    /// [`geometry_streams`]: crate::device::Features::geometry_streams
+    /// [`transform_feedback_rasterization_stream_select`]: crate::device::Properties::transform_feedback_rasterization_stream_select
+    pub rasterization_stream: u32,
+
+    /// The vertex of each primitive whose values are used for outputs of the vertex processing
+    /// stages that are decorated with `flat`.
+    ///
+    /// If this is set to [`ProvokingVertexMode::Last`], the
+    /// [`ext_provoking_vertex`](crate::device::DeviceExtensions::ext_provoking_vertex) extension
+    /// and the [`provoking_vertex_last`](crate::device::Features::provoking_vertex_last) feature
+    /// must be enabled on the device.
+    ///
+    /// The default value is [`ProvokingVertexMode::First`].
+    pub provoking_vertex_mode: ProvokingVertexMode,
+
     pub _ne: crate::NonExhaustive,
 }
 
@@ -94,13 +123,15 @@ impl RasterizationState {
         Self {
             depth_clamp_enable: false,
             rasterizer_discard_enable: StateMode::Fixed(false),
-            polygon_mode: Default::default(),
+            polygon_mode: StateMode::Fixed(Default::default()),
             cull_mode: StateMode::Fixed(Default::default()),
             front_face: StateMode::Fixed(Default::default()),
             depth_bias: None,
             line_width: StateMode::Fixed(1.0),
             line_rasterization_mode: Default::default(),
             line_stipple: None,
+            rasterization_stream: 0,
+            provoking_vertex_mode: ProvokingVertexMode::First,
             _ne: crate::NonExhaustive(()),
         }
     }
@@ -108,7 +139,14 @@ impl RasterizationState {
     /// Sets the polygon mode.
     #[inline]
     pub fn polygon_mode(mut self, polygon_mode: PolygonMode) -> Self {
-        self.polygon_mode = polygon_mode;
+        self.polygon_mode = StateMode::Fixed(polygon_mode);
+        self
+    }
+
+    /// Sets the polygon mode to dynamic.
+    #[inline]
+    pub fn polygon_mode_dynamic(mut self) -> Self {
+        self.polygon_mode = StateMode::Dynamic;
         self
     }
 
@@ -140,6 +178,30 @@ impl RasterizationState {
         self
     }
 
+    /// Sets the line rasterization mode.
+    #[inline]
+    pub fn line_rasterization_mode(
+        mut self,
+        line_rasterization_mode: LineRasterizationMode,
+    ) -> Self {
+        self.line_rasterization_mode = line_rasterization_mode;
+        self
+    }
+
+    /// Enables line stippling, and sets the stipple factor and pattern.
+    #[inline]
+    pub fn line_stipple(mut self, line_stipple: LineStipple) -> Self {
+        self.line_stipple = Some(StateMode::Fixed(line_stipple));
+        self
+    }
+
+    /// Enables line stippling, and sets the stipple factor and pattern to dynamic.
+    #[inline]
+    pub fn line_stipple_dynamic(mut self) -> Self {
+        self.line_stipple = Some(StateMode::Dynamic);
+        self
+    }
+
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
             depth_clamp_enable,
@@ -151,20 +213,21 @@ impl RasterizationState {
             line_width,
             line_rasterization_mode,
             ref line_stipple,
+            rasterization_stream,
+            provoking_vertex_mode,
             _ne: _,
         } = self;
 
         let properties = device.physical_device().properties();
 
-        polygon_mode.validate_device(device).map_err(|err| {
-            err.add_context("polygon_mode")
-                .set_vuids(&["VUID-VkPipelineRasterizationStateCreateInfo-polygonMode-parameter"])
-        })?;
-
         line_rasterization_mode
             .validate_device(device)
             .map_err(|err| err.add_context("line_rasterization_mode").set_vuids(&["VUID-VkPipelineRasterizationLineStateCreateInfoEXT-lineRasterizationMode-parameter"]))?;
 
+        provoking_vertex_mode
+            .validate_device(device)
+            .map_err(|err| err.add_context("provoking_vertex_mode").set_vuids(&["VUID-VkPipelineRasterizationProvokingVertexStateCreateInfoEXT-provokingVertexMode-parameter"]))?;
+
         if depth_clamp_enable && !device.enabled_features().depth_clamp {
             return Err(Box::new(ValidationError {
                 context: "depth_clamp_enable".into(),
@@ -176,22 +239,51 @@ impl RasterizationState {
             }));
         }
 
-        if polygon_mode != PolygonMode::Fill && !device.enabled_features().fill_mode_non_solid {
-            return Err(Box::new(ValidationError {
-                context: "polygon_mode".into(),
-                problem: "is not `PolygonMode::Fill`".into(),
-                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
-                    "fill_mode_non_solid",
-                )])]),
-                vuids: &["VUID-VkPipelineRasterizationStateCreateInfo-polygonMode-01507"],
-            }));
+        match polygon_mode {
+            StateMode::Fixed(polygon_mode) => {
+                polygon_mode.validate_device(device).map_err(|err| {
+                    err.add_context("polygon_mode").set_vuids(&[
+                        "VUID-VkPipelineRasterizationStateCreateInfo-polygonMode-parameter",
+                    ])
+                })?;
+
+                if polygon_mode != PolygonMode::Fill
+                    && !device.enabled_features().fill_mode_non_solid
+                {
+                    return Err(Box::new(ValidationError {
+                        context: "polygon_mode".into(),
+                        problem: "is not `PolygonMode::Fill`".into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "fill_mode_non_solid",
+                        )])]),
+                        vuids: &["VUID-VkPipelineRasterizationStateCreateInfo-polygonMode-01507"],
+                    }));
+                }
+            }
+            StateMode::Dynamic => {
+                if !device
+                    .enabled_features()
+                    .extended_dynamic_state3_polygon_mode
+                {
+                    return Err(Box::new(ValidationError {
+                        context: "polygon_mode".into(),
+                        problem: "is dynamic".into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "extended_dynamic_state3_polygon_mode",
+                        )])]),
+                        vuids: &[
+                            "VUID-VkGraphicsPipelineCreateInfo-extendedDynamicState3PolygonMode-07372",
+                        ],
+                    }));
+                }
+            }
         }
 
         match rasterizer_discard_enable {
             StateMode::Fixed(false) => {
                 if device.enabled_extensions().khr_portability_subset
                     && !device.enabled_features().point_polygons
-                    && polygon_mode == PolygonMode::Point
+                    && polygon_mode == StateMode::Fixed(PolygonMode::Point)
                 {
                     return Err(Box::new(ValidationError {
                         problem: "this device is a portability subset device, \
@@ -330,7 +422,7 @@ impl RasterizationState {
             if !device.enabled_extensions().ext_line_rasterization {
                 return Err(Box::new(ValidationError {
                     context: "line_rasterization_mode".into(),
-                    problem: "`is not `LineRasterizationMode::Default`".into(),
+                    problem: "is not `LineRasterizationMode::Default`".into(),
                     requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::DeviceExtension(
                         "ext_line_rasterization",
                     )])]),
@@ -475,6 +567,58 @@ impl RasterizationState {
             }
         }
 
+        if rasterization_stream != 0 {
+            if !(device.enabled_extensions().ext_transform_feedback
+                && device.enabled_features().geometry_streams)
+            {
+                return Err(Box::new(ValidationError {
+                    context: "rasterization_stream".into(),
+                    problem: "is not 0".into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                        Requires::DeviceExtension("ext_transform_feedback"),
+                        Requires::Feature("geometry_streams"),
+                    ])]),
+                    vuids: &["VUID-VkPipelineRasterizationStateStreamCreateInfoEXT-geometryStreams-02324"],
+                }));
+            }
+
+            if rasterization_stream >= properties.max_transform_feedback_streams.unwrap_or(0) {
+                return Err(Box::new(ValidationError {
+                    context: "rasterization_stream".into(),
+                    problem: "is not less than the `max_transform_feedback_streams` limit".into(),
+                    vuids: &["VUID-VkPipelineRasterizationStateStreamCreateInfoEXT-rasterizationStream-02325"],
+                    ..Default::default()
+                }));
+            }
+
+            if !properties
+                .transform_feedback_rasterization_stream_select
+                .unwrap_or(false)
+            {
+                return Err(Box::new(ValidationError {
+                    context: "rasterization_stream".into(),
+                    problem: "is not 0, but the `transform_feedback_rasterization_stream_select` \
+                        property is `false`"
+                        .into(),
+                    vuids: &["VUID-VkPipelineRasterizationStateStreamCreateInfoEXT-rasterizationStream-02326"],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if provoking_vertex_mode == ProvokingVertexMode::Last
+            && !device.enabled_features().provoking_vertex_last
+        {
+            return Err(Box::new(ValidationError {
+                context: "provoking_vertex_mode".into(),
+                problem: "is `ProvokingVertexMode::Last`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "provoking_vertex_last",
+                )])]),
+                vuids: &["VUID-VkPipelineRasterizationProvokingVertexStateCreateInfoEXT-provokingVertexMode-04883"],
+            }));
+        }
+
         Ok(())
     }
 }
@@ -655,6 +799,35 @@ impl Default for LineRasterizationMode {
     }
 }
 
+vulkan_enum! {
+    #[non_exhaustive]
+
+    /// The vertex of a primitive that provides the values of flat-shaded outputs.
+    ProvokingVertexMode = ProvokingVertexModeEXT(i32);
+
+    /// The first vertex of each primitive is the provoking vertex. This is the native convention
+    /// of Vulkan.
+    First = FIRST_VERTEX,
+
+    /// The last vertex of each primitive is the provoking vertex. This is the convention used by
+    /// OpenGL.
+    ///
+    /// The [`provoking_vertex_last`](crate::device::Features::provoking_vertex_last) feature must
+    /// be enabled on the device.
+    Last = LAST_VERTEX
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_provoking_vertex)]),
+    ]),
+}
+
+impl Default for ProvokingVertexMode {
+    /// Returns `ProvokingVertexMode::First`.
+    #[inline]
+    fn default() -> Self {
+        Self::First
+    }
+}
+
 /// The parameters of a stippled line.
 #[derive(Clone, Copy, Debug)]
 pub struct LineStipple {
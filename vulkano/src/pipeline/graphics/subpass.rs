@@ -19,7 +19,17 @@ use crate::{
 /// Selects the type of subpass that a graphics pipeline is created for.
 #[derive(Clone, Debug)]
 pub enum PipelineSubpassType {
+    /// The pipeline will be used in a subpass of a render pass object, which is begun with
+    /// [`begin_render_pass`].
+    ///
+    /// [`begin_render_pass`]: crate::command_buffer::AutoCommandBufferBuilder::begin_render_pass
     BeginRenderPass(Subpass),
+
+    /// The pipeline will be used with dynamic rendering, which is begun with
+    /// [`begin_rendering`]. The attachments used when rendering must match the formats given
+    /// here.
+    ///
+    /// [`begin_rendering`]: crate::command_buffer::AutoCommandBufferBuilder::begin_rendering
     BeginRendering(PipelineRenderingCreateInfo),
 }
 
@@ -107,6 +107,41 @@ impl ViewportState {
         }
     }
 
+    /// Creates a `ViewportState` with the given fixed viewports, and matching scissors that
+    /// cover the whole viewport.
+    ///
+    /// This is the same as [`viewport_fixed_scissor_irrelevant`]. More than one viewport
+    /// requires the [`multi_viewport`] feature to be enabled on the device, and the number of
+    /// viewports must not exceed the [`max_viewports`] limit.
+    ///
+    /// [`viewport_fixed_scissor_irrelevant`]: Self::viewport_fixed_scissor_irrelevant
+    /// [`multi_viewport`]: crate::device::Features::multi_viewport
+    /// [`max_viewports`]: crate::device::Properties::max_viewports
+    #[inline]
+    pub fn fixed(viewports: impl IntoIterator<Item = Viewport>) -> Self {
+        Self::viewport_fixed_scissor_irrelevant(viewports)
+    }
+
+    /// Creates a `ViewportState` with `count` viewports and scissors, whose values are set
+    /// dynamically with the [`set_viewport`] and [`set_scissor`] commands.
+    ///
+    /// The number of viewports is fixed at pipeline creation, so this doesn't require the
+    /// dynamic viewport count of [`viewport_count_dynamic_scissor_count_dynamic`]. This is the
+    /// same as [`viewport_dynamic_scissor_dynamic`]. A `count` greater than 1 requires the
+    /// [`multi_viewport`] feature to be enabled on the device, and `count` must not exceed the
+    /// [`max_viewports`] limit.
+    ///
+    /// [`set_viewport`]: crate::command_buffer::AutoCommandBufferBuilder::set_viewport
+    /// [`set_scissor`]: crate::command_buffer::AutoCommandBufferBuilder::set_scissor
+    /// [`viewport_count_dynamic_scissor_count_dynamic`]: Self::viewport_count_dynamic_scissor_count_dynamic
+    /// [`viewport_dynamic_scissor_dynamic`]: Self::viewport_dynamic_scissor_dynamic
+    /// [`multi_viewport`]: crate::device::Features::multi_viewport
+    /// [`max_viewports`]: crate::device::Properties::max_viewports
+    #[inline]
+    pub fn with_count(count: u32) -> Self {
+        Self::viewport_dynamic_scissor_dynamic(count)
+    }
+
     /// Creates a `ViewportState` with fixed state from the given viewports and scissors.
     pub fn viewport_fixed_scissor_fixed(
         data: impl IntoIterator<Item = (Viewport, Scissor)>,
@@ -373,6 +408,61 @@ pub struct Viewport {
 }
 
 impl Viewport {
+    /// Returns a viewport that covers the whole of a framebuffer with the given extent, with a
+    /// depth range of `0.0` to `1.0`.
+    #[inline]
+    pub fn for_extent(extent: [u32; 2]) -> Self {
+        Viewport {
+            offset: [0.0, 0.0],
+            extent: [extent[0] as f32, extent[1] as f32],
+            depth_range: 0.0..=1.0,
+        }
+    }
+
+    /// Returns the part of the viewport that lies within a framebuffer with the given extent.
+    ///
+    /// Note that shrinking a viewport also changes how vertex coordinates map onto the
+    /// framebuffer, so this is not the same as clipping with a scissor. A negative height, which
+    /// flips the viewport vertically, is preserved. The depth range is left unchanged.
+    pub fn clamp_to_extent(&self, extent: [u32; 2]) -> Self {
+        let clamp_axis = |offset: f32, size: f32, max: f32| {
+            let (start, end) = if size < 0.0 {
+                (offset + size, offset)
+            } else {
+                (offset, offset + size)
+            };
+            let start = start.clamp(0.0, max);
+            let end = end.clamp(0.0, max);
+
+            if size < 0.0 {
+                (end, start - end)
+            } else {
+                (start, end - start)
+            }
+        };
+
+        let (offset_x, extent_x) = clamp_axis(self.offset[0], self.extent[0], extent[0] as f32);
+        let (offset_y, extent_y) = clamp_axis(self.offset[1], self.extent[1], extent[1] as f32);
+
+        Viewport {
+            offset: [offset_x, offset_y],
+            extent: [extent_x, extent_y],
+            depth_range: self.depth_range.clone(),
+        }
+    }
+
+    /// Checks whether the viewport can be used on `device`.
+    ///
+    /// This performs the same validation that is done when the viewport is passed to a pipeline
+    /// or command buffer, including the checks against the
+    /// [`max_viewport_dimensions`](crate::device::Properties::max_viewport_dimensions) and
+    /// [`viewport_bounds_range`](crate::device::Properties::viewport_bounds_range) limits. It
+    /// allows catching a bad viewport early, for example right after a window was resized.
+    #[inline]
+    pub fn validate_for_device(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        self.validate(device)
+    }
+
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
             offset,
@@ -541,6 +631,32 @@ impl Scissor {
             extent: [0x7fffffff, 0x7fffffff],
         }
     }
+
+    /// Returns a scissor that covers the whole of a framebuffer with the given extent.
+    #[inline]
+    pub fn for_extent(extent: [u32; 2]) -> Scissor {
+        Scissor {
+            offset: [0, 0],
+            extent,
+        }
+    }
+
+    /// Returns the part of the scissor that lies within a framebuffer with the given extent.
+    ///
+    /// If the scissor lies entirely outside the framebuffer, the returned scissor has a zero
+    /// extent.
+    #[inline]
+    pub fn clamp_to_extent(&self, extent: [u32; 2]) -> Scissor {
+        let offset = [self.offset[0].min(extent[0]), self.offset[1].min(extent[1])];
+
+        Scissor {
+            offset,
+            extent: [
+                (self.offset[0].saturating_add(self.extent[0])).min(extent[0]) - offset[0],
+                (self.offset[1].saturating_add(self.extent[1])).min(extent[1]) - offset[1],
+            ],
+        }
+    }
 }
 
 impl Default for Scissor {
@@ -575,3 +691,83 @@ impl From<ash::vk::Rect2D> for Scissor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Scissor, Viewport, ViewportState};
+
+    #[test]
+    fn multi_viewport_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let viewports = [
+            Viewport::for_extent([64, 64]),
+            Viewport::for_extent([32, 32]),
+        ];
+        assert!(ViewportState::fixed(viewports.clone())
+            .validate(&device)
+            .is_err());
+        assert!(ViewportState::with_count(2).validate(&device).is_err());
+        assert!(ViewportState::fixed(viewports[..1].to_vec())
+            .validate(&device)
+            .is_ok());
+        assert!(ViewportState::with_count(1).validate(&device).is_ok());
+    }
+
+    #[test]
+    fn viewport_for_extent() {
+        let viewport = Viewport::for_extent([800, 600]);
+        assert_eq!(viewport.offset, [0.0, 0.0]);
+        assert_eq!(viewport.extent, [800.0, 600.0]);
+        assert_eq!(viewport.depth_range, 0.0..=1.0);
+    }
+
+    #[test]
+    fn viewport_clamp_to_extent() {
+        let viewport = Viewport {
+            offset: [-10.0, 50.0],
+            extent: [200.0, 100.0],
+            depth_range: 0.25..=0.75,
+        };
+        let clamped = viewport.clamp_to_extent([100, 120]);
+        assert_eq!(clamped.offset, [0.0, 50.0]);
+        assert_eq!(clamped.extent, [100.0, 70.0]);
+        assert_eq!(clamped.depth_range, 0.25..=0.75);
+
+        // A flipped viewport stays flipped.
+        let flipped = Viewport {
+            offset: [0.0, 150.0],
+            extent: [100.0, -150.0],
+            depth_range: 0.0..=1.0,
+        };
+        let clamped = flipped.clamp_to_extent([100, 100]);
+        assert_eq!(clamped.offset, [0.0, 100.0]);
+        assert_eq!(clamped.extent, [100.0, -100.0]);
+    }
+
+    #[test]
+    fn scissor_clamp_to_extent() {
+        assert_eq!(
+            Scissor::irrelevant().clamp_to_extent([640, 480]),
+            Scissor::for_extent([640, 480]),
+        );
+
+        let scissor = Scissor {
+            offset: [600, 10],
+            extent: [100, 100],
+        };
+        assert_eq!(
+            scissor.clamp_to_extent([640, 480]),
+            Scissor {
+                offset: [600, 10],
+                extent: [40, 100],
+            },
+        );
+
+        let outside = Scissor {
+            offset: [700, 500],
+            extent: [10, 10],
+        };
+        assert_eq!(outside.clamp_to_extent([640, 480]).extent, [0, 0]);
+    }
+}
@@ -104,6 +104,27 @@ pub struct PipelineLayout {
 }
 
 impl PipelineLayout {
+    /// Creates a new `PipelineLayout` that matches the descriptor binding and push constant
+    /// requirements of `stages`.
+    ///
+    /// The requirements of all stages are combined, so that a binding that is used by several
+    /// stages is accessible from each of them. If two stages use the same binding in incompatible
+    /// ways, for example as a uniform buffer in one stage and as a storage buffer in another,
+    /// then an error is returned.
+    ///
+    /// This is equivalent to creating a layout from
+    /// [`PipelineDescriptorSetLayoutCreateInfo::from_stages`], and has the same limitations.
+    pub fn from_stages<'a>(
+        device: Arc<Device>,
+        stages: impl IntoIterator<Item = &'a PipelineShaderStageCreateInfo>,
+    ) -> Result<Arc<PipelineLayout>, Validated<VulkanError>> {
+        let create_info = PipelineDescriptorSetLayoutCreateInfo::merge_stages(stages)?
+            .into_pipeline_layout_create_info(device.clone())
+            .map_err(|err| err.error)?;
+
+        Self::new(device, create_info)
+    }
+
     /// Creates a new `PipelineLayout`.
     pub fn new(
         device: Arc<Device>,
@@ -629,6 +650,26 @@ impl PipelineLayoutCreateInfo {
         let mut total_descriptors = [0; TOTAL_DESCRIPTOR_LIMITS.len()];
         let mut has_push_descriptor_set = false;
 
+        if set_layouts.iter().any(|set_layout| {
+            set_layout
+                .flags()
+                .intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER)
+        }) && !set_layouts.iter().all(|set_layout| {
+            set_layout
+                .flags()
+                .intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER)
+        }) {
+            return Err(Box::new(ValidationError {
+                context: "set_layouts".into(),
+                problem: "contains both descriptor set layouts whose flags include \
+                    `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`, and descriptor set \
+                    layouts whose flags do not"
+                    .into(),
+                vuids: &["VUID-VkPipelineLayoutCreateInfo-pSetLayouts-08008"],
+                ..Default::default()
+            }));
+        }
+
         for (_set_num, set_layout) in set_layouts.iter().enumerate() {
             assert_eq!(device, set_layout.device().as_ref());
 
@@ -926,6 +967,14 @@ impl PipelineDescriptorSetLayoutCreateInfo {
     pub fn from_stages<'a>(
         stages: impl IntoIterator<Item = &'a PipelineShaderStageCreateInfo>,
     ) -> Self {
+        // TODO: return an error here instead of panicking?
+        Self::merge_stages(stages)
+            .expect("Could not produce an intersection of the shader descriptor requirements")
+    }
+
+    pub(crate) fn merge_stages<'a>(
+        stages: impl IntoIterator<Item = &'a PipelineShaderStageCreateInfo>,
+    ) -> Result<Self, Box<ValidationError>> {
         // Produce `DescriptorBindingRequirements` for each binding, by iterating over all
         // shaders and adding the requirements of each.
         let mut descriptor_binding_requirements: HashMap<
@@ -935,7 +984,7 @@ impl PipelineDescriptorSetLayoutCreateInfo {
         let mut max_set_num = 0;
         let mut push_constant_ranges: Vec<PushConstantRange> = Vec::new();
 
-        for stage in stages {
+        for (stage_index, stage) in stages.into_iter().enumerate() {
             let entry_point_info = stage.entry_point.info();
 
             for (&(set_num, binding_num), reqs) in &entry_point_info.descriptor_binding_requirements
@@ -947,8 +996,13 @@ impl PipelineDescriptorSetLayoutCreateInfo {
                         // Previous shaders already added requirements, so we merge
                         // requirements of the current shader into the requirements of the
                         // previous ones.
-                        // TODO: return an error here instead of panicking?
-                        entry.into_mut().merge(reqs).expect("Could not produce an intersection of the shader descriptor requirements");
+                        entry.into_mut().merge(reqs).map_err(|err| {
+                            err.add_context(format!(
+                                "stages[{}].entry_point.info().descriptor_binding_requirements\
+                                [({}, {})]",
+                                stage_index, set_num, binding_num,
+                            ))
+                        })?;
                     }
                     Entry::Vacant(entry) => {
                         // No previous shader had this descriptor yet, so we just insert the
@@ -983,11 +1037,11 @@ impl PipelineDescriptorSetLayoutCreateInfo {
                 .insert(binding_num, DescriptorSetLayoutBinding::from(&reqs));
         }
 
-        Self {
+        Ok(Self {
             flags: PipelineLayoutCreateFlags::empty(),
             set_layouts,
             push_constant_ranges,
-        }
+        })
     }
 
     /// Converts the `PipelineDescriptorSetLayoutCreateInfo` into a `PipelineLayoutCreateInfo` by
@@ -1051,8 +1105,13 @@ mod tests {
 
     use super::PipelineLayout;
     use crate::{
-        pipeline::layout::{PipelineLayoutCreateInfo, PushConstantRange},
-        shader::ShaderStages,
+        descriptor_set::layout::DescriptorType,
+        pipeline::{
+            layout::{PipelineLayoutCreateInfo, PushConstantRange},
+            PipelineShaderStageCreateInfo,
+        },
+        shader::{ShaderModule, ShaderModuleCreateInfo, ShaderStages},
+        Validated,
     };
 
     #[test]
@@ -1220,6 +1279,91 @@ mod tests {
             assert_eq!(layout.push_constant_ranges_disjoint.as_slice(), expected);
         }
     }
+
+    #[test]
+    fn from_stages() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let stage = |module: &[u32]| {
+            let module =
+                unsafe { ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(module)) }
+                    .unwrap();
+            PipelineShaderStageCreateInfo::new(module.entry_point("main").unwrap())
+        };
+
+        /*
+        #version 450
+
+        layout(set = 0, binding = 0) uniform Uniforms {
+            float value;
+        };
+
+        void main() {
+            float x = value;
+        }
+        */
+        let vs = stage(&[
+            119734787, 65536, 0, 14, 0, 131089, 1, 196622, 0, 1, 327695, 0, 1, 1852399981, 0,
+            327752, 5, 0, 35, 0, 196679, 5, 2, 262215, 7, 34, 0, 262215, 7, 33, 0, 131091, 2,
+            196641, 3, 2, 196630, 4, 32, 196638, 5, 4, 262176, 6, 2, 5, 262203, 6, 7, 2, 262165, 8,
+            32, 1, 262187, 8, 9, 0, 262176, 10, 2, 4, 327734, 2, 1, 0, 3, 131320, 11, 327745, 10,
+            12, 7, 9, 262205, 4, 13, 12, 65789, 65592,
+        ]);
+
+        /*
+        #version 450
+
+        layout(set = 0, binding = 0) uniform Uniforms {
+            float value;
+        };
+
+        void main() {
+            float x = value;
+        }
+        */
+        let fs = stage(&[
+            119734787, 65536, 0, 14, 0, 131089, 1, 196622, 0, 1, 327695, 4, 1, 1852399981, 0,
+            196624, 1, 7, 327752, 5, 0, 35, 0, 196679, 5, 2, 262215, 7, 34, 0, 262215, 7, 33, 0,
+            131091, 2, 196641, 3, 2, 196630, 4, 32, 196638, 5, 4, 262176, 6, 2, 5, 262203, 6, 7, 2,
+            262165, 8, 32, 1, 262187, 8, 9, 0, 262176, 10, 2, 4, 327734, 2, 1, 0, 3, 131320, 11,
+            327745, 10, 12, 7, 9, 262205, 4, 13, 12, 65789, 65592,
+        ]);
+
+        /*
+        #version 450
+
+        layout(set = 0, binding = 0) buffer Storage {
+            float value;
+        };
+
+        void main() {
+            float x = value;
+        }
+        */
+        let fs_storage = stage(&[
+            119734787, 65536, 0, 14, 0, 131089, 1, 196622, 0, 1, 327695, 4, 1, 1852399981, 0,
+            196624, 1, 7, 327752, 5, 0, 35, 0, 196679, 5, 3, 262215, 7, 34, 0, 262215, 7, 33, 0,
+            131091, 2, 196641, 3, 2, 196630, 4, 32, 196638, 5, 4, 262176, 6, 2, 5, 262203, 6, 7, 2,
+            262165, 8, 32, 1, 262187, 8, 9, 0, 262176, 10, 2, 4, 327734, 2, 1, 0, 3, 131320, 11,
+            327745, 10, 12, 7, 9, 262205, 4, 13, 12, 65789, 65592,
+        ]);
+
+        // The binding is shared by both stages.
+        let layout = PipelineLayout::from_stages(device.clone(), [&vs, &fs]).unwrap();
+        assert_eq!(layout.set_layouts().len(), 1);
+        let binding = &layout.set_layouts()[0].bindings()[&0];
+        assert_eq!(binding.descriptor_type, DescriptorType::UniformBuffer);
+        assert_eq!(
+            binding.stages,
+            ShaderStages::VERTEX | ShaderStages::FRAGMENT
+        );
+
+        // The binding is a uniform buffer in one stage, and a storage buffer in the other.
+        assert!(matches!(
+            PipelineLayout::from_stages(device, [&vs, &fs_storage]),
+            Err(Validated::ValidationError(_)),
+        ));
+    }
 }
 
 /* TODO: restore
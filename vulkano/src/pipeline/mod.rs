@@ -17,20 +17,30 @@
 //! the CPU). Consequently it is a CPU-intensive operation that should be performed at
 //! initialization or during a loading screen.
 
-pub use self::{compute::ComputePipeline, graphics::GraphicsPipeline, layout::PipelineLayout};
+pub use self::{
+    compute::ComputePipeline, graphics::GraphicsPipeline, layout::PipelineLayout,
+    subpass_shading::SubpassShadingPipeline,
+};
 use crate::{
+    descriptor_set::layout::DescriptorSetLayoutCreateFlags,
     device::{Device, DeviceOwned},
     macros::{vulkan_bitflags, vulkan_enum},
-    shader::{DescriptorBindingRequirements, EntryPoint, ShaderExecution, ShaderStage},
-    Requires, RequiresAllOf, RequiresOneOf, ValidationError,
+    shader::{
+        spirv::ExecutionModel, DescriptorBindingRequirements, EntryPoint, ShaderExecution,
+        ShaderStage, ShaderStages, SpecializationConstant,
+    },
+    Requires, RequiresAllOf, RequiresOneOf, ValidationError, Version, VulkanError, VulkanObject,
 };
 use ahash::HashMap;
-use std::sync::Arc;
+use bytemuck::cast_slice;
+use smallvec::{smallvec, SmallVec};
+use std::{collections::BTreeSet, ffi::c_char, ptr, sync::Arc, time::Duration};
 
 pub mod cache;
 pub mod compute;
 pub mod graphics;
 pub mod layout;
+pub mod subpass_shading;
 
 /// A trait for operations shared between pipeline types.
 pub trait Pipeline: DeviceOwned {
@@ -48,6 +58,22 @@ pub trait Pipeline: DeviceOwned {
     fn descriptor_binding_requirements(
         &self,
     ) -> &HashMap<(u32, u32), DescriptorBindingRequirements>;
+
+    /// Returns the numbers of the descriptor sets that are actually accessed by this pipeline,
+    /// in ascending order and without duplicates.
+    ///
+    /// Unlike [`num_used_descriptor_sets`](Self::num_used_descriptor_sets), this skips any sets
+    /// below the highest used set number that the pipeline doesn't access.
+    fn used_descriptor_set_indices(&self) -> impl Iterator<Item = u32>
+    where
+        Self: Sized,
+    {
+        self.descriptor_binding_requirements()
+            .keys()
+            .map(|&(set_num, _)| set_num)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+    }
 }
 
 vulkan_enum! {
@@ -75,12 +101,12 @@ vulkan_enum! {
         RequiresAllOf([DeviceExtension(nv_ray_tracing)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// Subpass shading pipelines, which run a compute-like shader for each pixel of the render
+    /// area inside a subpass.
     SubpassShading = SUBPASS_SHADING_HUAWEI
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(huawei_subpass_shading)]),
-    ]),*/
+    ]),
 }
 
 vulkan_bitflags! {
@@ -98,38 +124,52 @@ vulkan_bitflags! {
     /// Create the pipeline by deriving from a base pipeline.
     DERIVATIVE = DERIVATIVE,
 
-    /* TODO: enable
-    // TODO: document
+    /// Shader inputs decorated with `ViewIndex` are assigned the value of `DeviceIndex` instead.
+    ///
+    /// This allows each physical device in a device group to render a different view, for
+    /// example one eye each in a VR renderer. The subpass must still have a nonzero `view_mask`,
+    /// and it still determines which layers of the framebuffer are rendered to. Only the value
+    /// that shaders see as the view index is taken from the device index, so the view mask
+    /// should normally contain a view for each device that renders.
+    ///
+    /// This flag is only valid for graphics pipelines.
     VIEW_INDEX_FROM_DEVICE_INDEX = VIEW_INDEX_FROM_DEVICE_INDEX
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_1)]),
         RequiresAllOf([DeviceExtension(khr_device_group)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The pipeline can be used with a nonzero base workgroup, using the
+    /// [`dispatch_base`](crate::command_buffer::AutoCommandBufferBuilder::dispatch_base) command.
+    ///
+    /// This flag is only valid for compute pipelines.
     DISPATCH_BASE = DISPATCH_BASE
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_1)]),
         RequiresAllOf([DeviceExtension(khr_device_group)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// If creating the pipeline would require compiling it, because it could not be found in the
+    /// pipeline cache, then creation fails with [`VulkanError::PipelineCompileRequired`] instead.
+    ///
+    /// The [`pipeline_creation_cache_control`](crate::device::Features::pipeline_creation_cache_control)
+    /// feature must be enabled on the device.
     FAIL_ON_PIPELINE_COMPILE_REQUIRED = FAIL_ON_PIPELINE_COMPILE_REQUIRED
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_3)]),
         RequiresAllOf([DeviceExtension(ext_pipeline_creation_cache_control)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// When creating multiple pipelines at once, if creating this pipeline fails, then the
+    /// pipelines after it in the batch are not created either.
+    ///
+    /// The [`pipeline_creation_cache_control`](crate::device::Features::pipeline_creation_cache_control)
+    /// feature must be enabled on the device.
     EARLY_RETURN_ON_FAILURE = EARLY_RETURN_ON_FAILURE
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_3)]),
         RequiresAllOf([DeviceExtension(ext_pipeline_creation_cache_control)]),
     ]),
-    */
 
     /* TODO: enable
     // TODO: document
@@ -199,52 +239,72 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(nv_ray_tracing)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// Statistics about the compiled pipeline executables will be captured, and can be retrieved
+    /// after creation with the `executable_statistics` method of the pipeline.
     CAPTURE_STATISTICS = CAPTURE_STATISTICS_KHR
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(khr_pipeline_executable_properties)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    CAPTURE_INTERNAL_REPRESENTATIONS = CAPTURE_INTERNAL_REPRESENTATIONS_KHR{
-        device_extensions: [khr_pipeline_executable_properties],
-    },*/
+    /// The internal representations of the compiled pipeline executables will be captured.
+    CAPTURE_INTERNAL_REPRESENTATIONS = CAPTURE_INTERNAL_REPRESENTATIONS_KHR
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(khr_pipeline_executable_properties)]),
+    ]),
 
-    /* TODO: enable
-    // TODO: document
-    INDIRECT_BINDABLE = INDIRECT_BINDABLE_NV{
-        device_extensions: [nv_device_generated_commands],
-    },*/
+    /// The pipeline can be bound by commands generated on the device, by referencing it in an
+    /// indirect commands layout.
+    ///
+    /// The [`device_generated_commands`] feature must be enabled on the device.
+    ///
+    /// This flag is only valid for graphics pipelines.
+    ///
+    /// [`device_generated_commands`]: crate::device::Features::device_generated_commands
+    INDIRECT_BINDABLE = INDIRECT_BINDABLE_NV
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(nv_device_generated_commands)]),
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The pipeline is a library, which cannot be bound directly, but can be linked into other
+    /// pipelines.
+    ///
+    /// For graphics pipelines, the subset of state that the library contains is specified with
+    /// [`GraphicsPipelineCreateInfo::library`](crate::pipeline::graphics::GraphicsPipelineCreateInfo::library).
     LIBRARY = LIBRARY_KHR
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(khr_pipeline_library)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The pipeline reads its descriptors from descriptor buffers, bound with
+    /// [`bind_descriptor_buffers`], instead of from descriptor sets.
+    ///
+    /// If set, all descriptor set layouts of the pipeline layout must have been created with
+    /// [`DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`]. If not set, none of them may have
+    /// been. The [`descriptor_buffer`](crate::device::Features::descriptor_buffer) feature must
+    /// be enabled on the device.
+    ///
+    /// [`bind_descriptor_buffers`]: crate::command_buffer::AutoCommandBufferBuilder::bind_descriptor_buffers
+    /// [`DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`]: crate::descriptor_set::layout::DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER
     DESCRIPTOR_BUFFER = DESCRIPTOR_BUFFER_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_descriptor_buffer)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// For a pipeline library, the information needed to perform link time optimization is kept,
+    /// so that pipelines linked from this library can use [`LINK_TIME_OPTIMIZATION`].
+    ///
+    /// [`LINK_TIME_OPTIMIZATION`]: PipelineCreateFlags::LINK_TIME_OPTIMIZATION
     RETAIN_LINK_TIME_OPTIMIZATION_INFO = RETAIN_LINK_TIME_OPTIMIZATION_INFO_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_graphics_pipeline_library)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// When linking pipeline libraries, the implementation performs optimizations across the
+    /// libraries. This makes linking slower, but the resulting pipeline may be faster.
     LINK_TIME_OPTIMIZATION = LINK_TIME_OPTIMIZATION_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_graphics_pipeline_library)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -253,19 +313,47 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(nv_ray_tracing_motion_blur)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The pipeline can be used in a subpass where a color attachment is in the
+    /// [`ImageLayout::AttachmentFeedbackLoopOptimal`] layout, so that it can be read from a shader
+    /// while it is written to.
+    ///
+    /// This flag is only valid for graphics pipelines that are created with a subpass of a
+    /// render pass object, not for dynamic rendering. When drawing with the pipeline, a color
+    /// attachment that is read from a shader must be in the `AttachmentFeedbackLoopOptimal`
+    /// layout.
+    ///
+    /// This flag must not be used if the pipeline has the
+    /// [`DynamicState::AttachmentFeedbackLoopEnable`] dynamic state. If the
+    /// [`attachment_feedback_loop_dynamic_state`] feature is enabled, that dynamic state can be
+    /// used instead of this flag, to turn the feedback loop on or off while recording.
+    ///
+    /// [`ImageLayout::AttachmentFeedbackLoopOptimal`]: crate::image::ImageLayout::AttachmentFeedbackLoopOptimal
+    /// [`attachment_feedback_loop_dynamic_state`]: crate::device::Features::attachment_feedback_loop_dynamic_state
     COLOR_ATTACHMENT_FEEDBACK_LOOP = COLOR_ATTACHMENT_FEEDBACK_LOOP_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_attachment_feedback_loop_layout)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The pipeline can be used in a subpass where the depth/stencil attachment is in the
+    /// [`ImageLayout::AttachmentFeedbackLoopOptimal`] layout, so that it can be read from a shader
+    /// while it is written to.
+    ///
+    /// This flag is only valid for graphics pipelines that are created with a subpass of a
+    /// render pass object, not for dynamic rendering. When drawing with the pipeline, a
+    /// depth/stencil attachment that is read from a shader must be in the
+    /// `AttachmentFeedbackLoopOptimal` layout.
+    ///
+    /// This flag must not be used if the pipeline has the
+    /// [`DynamicState::AttachmentFeedbackLoopEnable`] dynamic state. If the
+    /// [`attachment_feedback_loop_dynamic_state`] feature is enabled, that dynamic state can be
+    /// used instead of this flag, to turn the feedback loop on or off while recording.
+    ///
+    /// [`ImageLayout::AttachmentFeedbackLoopOptimal`]: crate::image::ImageLayout::AttachmentFeedbackLoopOptimal
+    /// [`attachment_feedback_loop_dynamic_state`]: crate::device::Features::attachment_feedback_loop_dynamic_state
     DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP = DEPTH_STENCIL_ATTACHMENT_FEEDBACK_LOOP_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_attachment_feedback_loop_layout)]),
-    ]),*/
+    ]),
 
     /* TODO: enable
     // TODO: document
@@ -281,19 +369,32 @@ vulkan_bitflags! {
         RequiresAllOf([DeviceExtension(nv_displacement_micromap)]),
     ]),*/
 
-    /* TODO: enable
-    // TODO: document
+    /// The pipeline must not be bound in a protected command buffer.
+    ///
+    /// The [`pipeline_protected_access`] feature must be enabled on the device. This flag must
+    /// not be combined with [`PROTECTED_ACCESS_ONLY`].
+    ///
+    /// [`pipeline_protected_access`]: crate::device::Features::pipeline_protected_access
+    /// [`PROTECTED_ACCESS_ONLY`]: Self::PROTECTED_ACCESS_ONLY
     NO_PROTECTED_ACCESS = NO_PROTECTED_ACCESS_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_pipeline_protected_access)]),
-    ]),*/
+    ]),
 
-    /* TODO: enable
-    // TODO: document
+    /// The pipeline can only be bound in a protected command buffer.
+    ///
+    /// Vulkano does not support creating protected command buffers yet, so a pipeline with this
+    /// flag can currently not be bound.
+    ///
+    /// The [`pipeline_protected_access`] feature must be enabled on the device. This flag must
+    /// not be combined with [`NO_PROTECTED_ACCESS`].
+    ///
+    /// [`pipeline_protected_access`]: crate::device::Features::pipeline_protected_access
+    /// [`NO_PROTECTED_ACCESS`]: Self::NO_PROTECTED_ACCESS
     PROTECTED_ACCESS_ONLY = PROTECTED_ACCESS_ONLY_EXT
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_pipeline_protected_access)]),
-    ]),*/
+    ]),
 }
 
 /// Specifies a single shader stage when creating a pipeline.
@@ -339,6 +440,99 @@ impl PipelineShaderStageCreateInfo {
         }
     }
 
+    /// Returns a copy of `self` with `flags` set to the given value.
+    ///
+    /// Together with [`with_required_subgroup_size`](Self::with_required_subgroup_size), this
+    /// allows building a stage without struct update syntax:
+    ///
+    /// ```
+    /// # use vulkano::shader::EntryPoint;
+    /// use vulkano::pipeline::{PipelineShaderStageCreateFlags, PipelineShaderStageCreateInfo};
+    /// # let entry_point: EntryPoint = return;
+    ///
+    /// let stage = PipelineShaderStageCreateInfo::new(entry_point)
+    ///     .with_flags(PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS)
+    ///     .with_required_subgroup_size(Some(32));
+    /// ```
+    #[inline]
+    pub fn with_flags(mut self, flags: PipelineShaderStageCreateFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Returns a copy of `self` with `required_subgroup_size` set to the given value.
+    #[inline]
+    pub fn with_required_subgroup_size(mut self, required_subgroup_size: Option<u32>) -> Self {
+        self.required_subgroup_size = required_subgroup_size;
+        self
+    }
+
+    /// Returns a copy of `self` with the local size of the entry point replaced by `local_size`.
+    ///
+    /// The entry point must be a compute shader that specifies its local size with the
+    /// `LocalSizeId` execution mode, where each component is a specialization constant. These
+    /// constants are set to the components of `local_size`, in addition to the specialization
+    /// constants already applied to the entry point, and the result is validated again against the
+    /// workgroup size limits of the device.
+    pub fn with_local_size(self, local_size: [u32; 3]) -> Result<Self, Box<ValidationError>> {
+        let module = self.entry_point.module();
+        let entry_point_info = self.entry_point.info();
+
+        if !matches!(entry_point_info.execution, ShaderExecution::Compute(_)) {
+            return Err(Box::new(ValidationError {
+                context: "entry_point".into(),
+                problem: "is not a compute shader entry point".into(),
+                ..Default::default()
+            }));
+        }
+
+        let execution = ExecutionModel::from(&entry_point_info.execution);
+        let constant_ids = module
+            .base_module()
+            .local_size_id_constant_ids(&entry_point_info.name, execution)
+            .ok_or_else(|| {
+                Box::new(ValidationError {
+                    context: "entry_point".into(),
+                    problem: "does not specify its local size with the `LocalSizeId` execution \
+                        mode"
+                        .into(),
+                    ..Default::default()
+                })
+            })?;
+
+        let mut specialization_info = module.specialization_info().clone();
+
+        for (index, (constant_id, size)) in constant_ids.into_iter().zip(local_size).enumerate() {
+            let constant_id = constant_id.ok_or_else(|| {
+                Box::new(ValidationError {
+                    context: "entry_point".into(),
+                    problem: format!(
+                        "component {} of the `LocalSizeId` execution mode is not a \
+                        specialization constant",
+                        index,
+                    )
+                    .into(),
+                    ..Default::default()
+                })
+            })?;
+            specialization_info.insert(constant_id, SpecializationConstant::U32(size));
+        }
+
+        let entry_point = module
+            .base_module()
+            .specialize(specialization_info)
+            .map_err(|err| err.add_context("local_size"))?
+            .entry_point_with_execution(&entry_point_info.name, execution)
+            .unwrap();
+        let create_info = Self {
+            entry_point,
+            ..self
+        };
+        create_info.validate(module.device())?;
+
+        Ok(create_info)
+    }
+
     pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
         let &Self {
             flags,
@@ -362,13 +556,103 @@ impl PipelineShaderStageCreateInfo {
                 .set_vuids(&["VUID-VkPipelineShaderStageCreateInfo-stage-parameter"])
         })?;
 
+        if flags.intersects(PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE)
+            && !device.enabled_features().subgroup_size_control
+        {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE`"
+                    .into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "subgroup_size_control",
+                )])]),
+                vuids: &["VUID-VkPipelineShaderStageCreateInfo-flags-02784"],
+            }));
+        }
+
+        if flags.intersects(PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS) {
+            if !device.enabled_features().compute_full_subgroups {
+                return Err(Box::new(ValidationError {
+                    context: "flags".into(),
+                    problem: "contains `PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS`"
+                        .into(),
+                    requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                        "compute_full_subgroups",
+                    )])]),
+                    vuids: &["VUID-VkPipelineShaderStageCreateInfo-flags-02785"],
+                }));
+            }
+
+            if !matches!(
+                stage_enum,
+                ShaderStage::Compute | ShaderStage::Mesh | ShaderStage::Task
+            ) {
+                return Err(Box::new(ValidationError {
+                    problem: "`flags` contains \
+                        `PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS`, but \
+                        `entry_point` is not a compute, mesh or task shader entry point"
+                        .into(),
+                    vuids: &["VUID-VkPipelineShaderStageCreateInfo-flags-08988"],
+                    ..Default::default()
+                }));
+            }
+        }
+
         // VUID-VkPipelineShaderStageCreateInfo-pName-00707
         // Guaranteed by definition of `EntryPoint`.
 
+        for (clip_distances, cull_distances) in entry_point.module().clip_cull_distance_counts(
+            &entry_point_info.name,
+            ExecutionModel::from(&entry_point_info.execution),
+        ) {
+            if clip_distances > properties.max_clip_distances {
+                return Err(Box::new(ValidationError {
+                    context: "entry_point".into(),
+                    problem: format!(
+                        "declares {} clip distances, which is greater than the \
+                        `max_clip_distances` limit ({})",
+                        clip_distances, properties.max_clip_distances,
+                    )
+                    .into(),
+                    vuids: &["VUID-VkPipelineShaderStageCreateInfo-maxClipDistances-00708"],
+                    ..Default::default()
+                }));
+            }
+
+            if cull_distances > properties.max_cull_distances {
+                return Err(Box::new(ValidationError {
+                    context: "entry_point".into(),
+                    problem: format!(
+                        "declares {} cull distances, which is greater than the \
+                        `max_cull_distances` limit ({})",
+                        cull_distances, properties.max_cull_distances,
+                    )
+                    .into(),
+                    vuids: &["VUID-VkPipelineShaderStageCreateInfo-maxCullDistances-00709"],
+                    ..Default::default()
+                }));
+            }
+
+            if clip_distances + cull_distances > properties.max_combined_clip_and_cull_distances {
+                return Err(Box::new(ValidationError {
+                    context: "entry_point".into(),
+                    problem: format!(
+                        "declares {} clip distances and {} cull distances, which together are \
+                        greater than the `max_combined_clip_and_cull_distances` limit ({})",
+                        clip_distances,
+                        cull_distances,
+                        properties.max_combined_clip_and_cull_distances,
+                    )
+                    .into(),
+                    vuids: &[
+                        "VUID-VkPipelineShaderStageCreateInfo-maxCombinedClipAndCullDistances-00710",
+                    ],
+                    ..Default::default()
+                }));
+            }
+        }
+
         // TODO:
-        // VUID-VkPipelineShaderStageCreateInfo-maxClipDistances-00708
-        // VUID-VkPipelineShaderStageCreateInfo-maxCullDistances-00709
-        // VUID-VkPipelineShaderStageCreateInfo-maxCombinedClipAndCullDistances-00710
         // VUID-VkPipelineShaderStageCreateInfo-maxSampleMaskWords-00711
         // VUID-VkPipelineShaderStageCreateInfo-stage-02596
         // VUID-VkPipelineShaderStageCreateInfo-stage-02597
@@ -695,6 +979,57 @@ impl PipelineShaderStageCreateInfo {
             }
         }
 
+        if let (true, ShaderExecution::Compute(execution)) = (
+            flags.intersects(PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS),
+            &entry_point_info.execution,
+        ) {
+            let local_size_x = execution.local_size[0];
+
+            if let Some(required_subgroup_size) = required_subgroup_size {
+                if local_size_x % required_subgroup_size != 0 {
+                    return Err(Box::new(ValidationError {
+                        problem: "`flags` contains \
+                            `PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS`, and \
+                            `required_subgroup_size` is `Some`, but the `local_size_x` of \
+                            `entry_point` is not a multiple of `required_subgroup_size`"
+                            .into(),
+                        vuids: &["VUID-VkPipelineShaderStageCreateInfo-pNext-02757"],
+                        ..Default::default()
+                    }));
+                }
+            } else if flags.intersects(PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE)
+            {
+                let max_subgroup_size = properties.max_subgroup_size.unwrap_or(1);
+
+                if local_size_x % max_subgroup_size != 0 {
+                    return Err(Box::new(ValidationError {
+                        problem: "`flags` contains \
+                            `PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS` and \
+                            `PipelineShaderStageCreateFlags::ALLOW_VARYING_SUBGROUP_SIZE`, but \
+                            the `local_size_x` of `entry_point` is not a multiple of the \
+                            `max_subgroup_size` device property"
+                            .into(),
+                        vuids: &["VUID-VkPipelineShaderStageCreateInfo-flags-02758"],
+                        ..Default::default()
+                    }));
+                }
+            } else {
+                let subgroup_size = properties.subgroup_size.unwrap_or(1);
+
+                if local_size_x % subgroup_size != 0 {
+                    return Err(Box::new(ValidationError {
+                        problem: "`flags` contains \
+                            `PipelineShaderStageCreateFlags::REQUIRE_FULL_SUBGROUPS`, but \
+                            the `local_size_x` of `entry_point` is not a multiple of the \
+                            `subgroup_size` device property"
+                            .into(),
+                        vuids: &["VUID-VkPipelineShaderStageCreateInfo-flags-02759"],
+                        ..Default::default()
+                    }));
+                }
+            }
+        }
+
         Ok(())
     }
 }
@@ -705,23 +1040,28 @@ vulkan_bitflags! {
     /// Flags specifying additional properties of a pipeline shader stage.
     PipelineShaderStageCreateFlags = PipelineShaderStageCreateFlags(u32);
 
-    /* TODO: enable
-    // TODO: document
+    /// The `SubgroupSize` built-in of the shader may vary between invocations, and may have any
+    /// value between the `min_subgroup_size` and `max_subgroup_size` device properties.
+    ///
+    /// The [`subgroup_size_control`](crate::device::Features::subgroup_size_control) feature
+    /// must be enabled on the device.
     ALLOW_VARYING_SUBGROUP_SIZE = ALLOW_VARYING_SUBGROUP_SIZE
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_3)]),
         RequiresAllOf([DeviceExtension(ext_subgroup_size_control)]),
     ]),
-    */
 
-    /* TODO: enable
-    // TODO: document
+    /// All subgroups of the shader are launched with all invocations active.
+    ///
+    /// This can only be used with compute, mesh and task shaders, and the `local_size_x` of the
+    /// shader must be a multiple of the subgroup size. The
+    /// [`compute_full_subgroups`](crate::device::Features::compute_full_subgroups) feature must
+    /// be enabled on the device.
     REQUIRE_FULL_SUBGROUPS = REQUIRE_FULL_SUBGROUPS
     RequiresOneOf([
         RequiresAllOf([APIVersion(V1_3)]),
         RequiresAllOf([DeviceExtension(ext_subgroup_size_control)]),
     ]),
-    */
 }
 
 vulkan_enum! {
@@ -1126,6 +1466,13 @@ vulkan_enum! {
     RequiresOneOf([
         RequiresAllOf([DeviceExtension(ext_extended_dynamic_state3)]),
     ]),
+
+    /// The value set with
+    /// [`set_attachment_feedback_loop_enable`](crate::command_buffer::AutoCommandBufferBuilder::set_attachment_feedback_loop_enable).
+    AttachmentFeedbackLoopEnable = ATTACHMENT_FEEDBACK_LOOP_ENABLE_EXT
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(ext_attachment_feedback_loop_dynamic_state)]),
+    ]),
 }
 
 /// Specifies how a dynamic state is handled by a graphics pipeline.
@@ -1140,6 +1487,38 @@ pub enum StateMode<F> {
     Dynamic,
 }
 
+impl<F> StateMode<F> {
+    /// Returns whether `self` is [`StateMode::Fixed`].
+    #[inline]
+    pub fn is_fixed(&self) -> bool {
+        matches!(self, StateMode::Fixed(_))
+    }
+
+    /// Returns whether `self` is [`StateMode::Dynamic`].
+    #[inline]
+    pub fn is_dynamic(&self) -> bool {
+        matches!(self, StateMode::Dynamic)
+    }
+
+    /// Returns a reference to the fixed value, or `None` if the state is dynamic.
+    #[inline]
+    pub fn fixed(&self) -> Option<&F> {
+        match self {
+            StateMode::Fixed(x) => Some(x),
+            StateMode::Dynamic => None,
+        }
+    }
+
+    /// Maps the fixed value with `f`, leaving a dynamic state unchanged.
+    #[inline]
+    pub fn map<G>(self, f: impl FnOnce(F) -> G) -> StateMode<G> {
+        match self {
+            StateMode::Fixed(x) => StateMode::Fixed(f(x)),
+            StateMode::Dynamic => StateMode::Dynamic,
+        }
+    }
+}
+
 impl<T> From<Option<T>> for StateMode<T> {
     fn from(val: Option<T>) -> Self {
         match val {
@@ -1165,3 +1544,467 @@ pub enum PartialStateMode<F, D> {
     Fixed(F),
     Dynamic(D),
 }
+
+/// Properties and statistics of an executable of a pipeline, as returned by the
+/// `executable_statistics` method of a pipeline.
+///
+/// Pipelines are compiled into one or more executables by the implementation, which do not
+/// necessarily map one-to-one to the shader stages of the pipeline.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableStatistics {
+    /// A short human-readable name for the executable.
+    pub name: String,
+
+    /// A human-readable description of the executable.
+    pub description: String,
+
+    /// The shader stages that were compiled into the executable.
+    pub stages: ShaderStages,
+
+    /// The subgroup size with which the executable is dispatched, or 0 if it is not applicable.
+    pub subgroup_size: u32,
+
+    /// The statistics of the executable. Which statistics are reported is implementation-defined.
+    pub statistics: Vec<PipelineExecutableStatistic>,
+}
+
+/// A single statistic of a pipeline executable.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineExecutableStatistic {
+    /// A short human-readable name for the statistic.
+    pub name: String,
+
+    /// A human-readable description of the statistic.
+    pub description: String,
+
+    /// The value of the statistic.
+    pub value: PipelineExecutableStatisticValue,
+}
+
+/// The value of a [`PipelineExecutableStatistic`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum PipelineExecutableStatisticValue {
+    Bool(bool),
+    Int64(i64),
+    Uint64(u64),
+    Float64(f64),
+
+    /// The statistic has a format that vulkano does not know about. Contains the raw bits of the
+    /// value.
+    Unknown(u64),
+}
+
+/// Feedback about the creation of a pipeline, as returned by the `creation_feedback` method of a
+/// pipeline.
+///
+/// This can be used to find out whether a pipeline was retrieved from a
+/// [`PipelineCache`](cache::PipelineCache), and how long it took to create.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct PipelineCreationFeedback {
+    /// Properties of the creation of the pipeline as a whole.
+    ///
+    /// If this does not contain [`PipelineCreationFeedbackFlags::VALID`], then the implementation
+    /// did not provide feedback for the pipeline, and `duration` should be ignored.
+    pub flags: PipelineCreationFeedbackFlags,
+
+    /// The time that was spent creating the pipeline as a whole.
+    pub duration: Duration,
+
+    /// Feedback about the creation of each shader stage of the pipeline.
+    pub stages: HashMap<ShaderStage, PipelineStageCreationFeedback>,
+}
+
+/// Feedback about the creation of a single shader stage of a pipeline.
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub struct PipelineStageCreationFeedback {
+    /// Properties of the creation of the shader stage.
+    ///
+    /// If this does not contain [`PipelineCreationFeedbackFlags::VALID`], then the implementation
+    /// did not provide feedback for the shader stage, and `duration` should be ignored.
+    pub flags: PipelineCreationFeedbackFlags,
+
+    /// The time that was spent creating the shader stage.
+    pub duration: Duration,
+}
+
+vulkan_bitflags! {
+    #[non_exhaustive]
+
+    /// Properties of the creation of a pipeline or one of its shader stages.
+    PipelineCreationFeedbackFlags = PipelineCreationFeedbackFlags(u32);
+
+    /// The implementation provided feedback. If this is not set, then the other flags and the
+    /// duration must be ignored.
+    VALID = VALID,
+
+    /// A pipeline cache was provided when creating the pipeline, and the pipeline or shader stage
+    /// was found in it, so it did not need to be compiled again.
+    APPLICATION_PIPELINE_CACHE_HIT = APPLICATION_PIPELINE_CACHE_HIT,
+
+    /// A base pipeline was provided when creating the pipeline, and it was used to speed up the
+    /// creation of the pipeline or shader stage.
+    BASE_PIPELINE_ACCELERATION = BASE_PIPELINE_ACCELERATION,
+}
+
+/// Storage for the pipeline creation feedback written by the implementation.
+pub(crate) struct PipelineCreationFeedbackVk {
+    pipeline_vk: ash::vk::PipelineCreationFeedback,
+    stages_vk: SmallVec<[ash::vk::PipelineCreationFeedback; 5]>,
+}
+
+impl PipelineCreationFeedbackVk {
+    /// Returns storage for a pipeline with `stage_count` shader stages, or `None` if the device
+    /// does not support pipeline creation feedback.
+    pub(crate) fn new(device: &Device, stage_count: usize) -> Option<Self> {
+        (device.api_version() >= Version::V1_3
+            || device.enabled_extensions().ext_pipeline_creation_feedback)
+            .then(|| Self {
+                pipeline_vk: Default::default(),
+                stages_vk: smallvec![Default::default(); stage_count],
+            })
+    }
+
+    /// Returns a Vulkan create info that points to `self`, to be added to the `p_next` chain of a
+    /// pipeline create info.
+    pub(crate) fn create_info_vk(&mut self) -> ash::vk::PipelineCreationFeedbackCreateInfo {
+        ash::vk::PipelineCreationFeedbackCreateInfo {
+            p_pipeline_creation_feedback: &mut self.pipeline_vk,
+            pipeline_stage_creation_feedback_count: self.stages_vk.len() as u32,
+            p_pipeline_stage_creation_feedbacks: self.stages_vk.as_mut_ptr(),
+            ..Default::default()
+        }
+    }
+
+    /// Converts the feedback that was written, for a pipeline created with `stages`.
+    pub(crate) fn to_feedback(
+        &self,
+        stages: &[PipelineShaderStageCreateInfo],
+    ) -> PipelineCreationFeedback {
+        PipelineCreationFeedback {
+            flags: self.pipeline_vk.flags.into(),
+            duration: Duration::from_nanos(self.pipeline_vk.duration),
+            stages: stages
+                .iter()
+                .zip(&self.stages_vk)
+                .map(|(stage, stage_vk)| {
+                    (
+                        ShaderStage::from(&stage.entry_point.info().execution),
+                        PipelineStageCreationFeedback {
+                            flags: stage_vk.flags.into(),
+                            duration: Duration::from_nanos(stage_vk.duration),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+pub(crate) fn validate_executable_statistics(
+    device: &Device,
+    flags: PipelineCreateFlags,
+) -> Result<(), Box<ValidationError>> {
+    if !device.enabled_features().pipeline_executable_info {
+        return Err(Box::new(ValidationError {
+            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "pipeline_executable_info",
+            )])]),
+            vuids: &[
+                "VUID-vkGetPipelineExecutablePropertiesKHR-pipelineExecutableInfo-03270",
+                "VUID-vkGetPipelineExecutableStatisticsKHR-pipelineExecutableInfo-03272",
+            ],
+            ..Default::default()
+        }));
+    }
+
+    if !flags.intersects(PipelineCreateFlags::CAPTURE_STATISTICS) {
+        return Err(Box::new(ValidationError {
+            context: "flags".into(),
+            problem: "the pipeline was not created with `PipelineCreateFlags::CAPTURE_STATISTICS`"
+                .into(),
+            vuids: &["VUID-vkGetPipelineExecutableStatisticsKHR-pipeline-03274"],
+            ..Default::default()
+        }));
+    }
+
+    Ok(())
+}
+
+// Checks that `PipelineCreateFlags::DESCRIPTOR_BUFFER` agrees with the descriptor set layouts of
+// the pipeline layout.
+pub(crate) fn validate_descriptor_buffer_flag(
+    device: &Device,
+    flags: PipelineCreateFlags,
+    layout: &PipelineLayout,
+) -> Result<(), Box<ValidationError>> {
+    let uses_descriptor_buffer = flags.intersects(PipelineCreateFlags::DESCRIPTOR_BUFFER);
+
+    if uses_descriptor_buffer && !device.enabled_features().descriptor_buffer {
+        return Err(Box::new(ValidationError {
+            context: "flags".into(),
+            problem: "contains `PipelineCreateFlags::DESCRIPTOR_BUFFER`".into(),
+            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "descriptor_buffer",
+            )])]),
+            // vuids?
+            ..Default::default()
+        }));
+    }
+
+    for (set_num, set_layout) in layout.set_layouts().iter().enumerate() {
+        let set_layout_uses_descriptor_buffer = set_layout
+            .flags()
+            .intersects(DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER);
+
+        if uses_descriptor_buffer && !set_layout_uses_descriptor_buffer {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "`flags` contains `PipelineCreateFlags::DESCRIPTOR_BUFFER`, but \
+                    `layout.set_layouts()[{}].flags()` does not contain \
+                    `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`",
+                    set_num,
+                )
+                .into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+
+        if !uses_descriptor_buffer && set_layout_uses_descriptor_buffer {
+            return Err(Box::new(ValidationError {
+                problem: format!(
+                    "`flags` does not contain `PipelineCreateFlags::DESCRIPTOR_BUFFER`, but \
+                    `layout.set_layouts()[{}].flags()` contains \
+                    `DescriptorSetLayoutCreateFlags::DESCRIPTOR_BUFFER`",
+                    set_num,
+                )
+                .into(),
+                // vuids?
+                ..Default::default()
+            }));
+        }
+    }
+
+    Ok(())
+}
+
+// Checks the use of `PipelineCreateFlags::NO_PROTECTED_ACCESS` and
+// `PipelineCreateFlags::PROTECTED_ACCESS_ONLY`.
+pub(crate) fn validate_protected_access_flags(
+    device: &Device,
+    flags: PipelineCreateFlags,
+) -> Result<(), Box<ValidationError>> {
+    if flags.intersects(
+        PipelineCreateFlags::NO_PROTECTED_ACCESS | PipelineCreateFlags::PROTECTED_ACCESS_ONLY,
+    ) && !device.enabled_features().pipeline_protected_access
+    {
+        return Err(Box::new(ValidationError {
+            context: "flags".into(),
+            problem: "contains `PipelineCreateFlags::NO_PROTECTED_ACCESS` or \
+                `PipelineCreateFlags::PROTECTED_ACCESS_ONLY`"
+                .into(),
+            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                "pipeline_protected_access",
+            )])]),
+            // vuids?
+            ..Default::default()
+        }));
+    }
+
+    if flags.contains(
+        PipelineCreateFlags::NO_PROTECTED_ACCESS | PipelineCreateFlags::PROTECTED_ACCESS_ONLY,
+    ) {
+        return Err(Box::new(ValidationError {
+            context: "flags".into(),
+            problem: "contains both `PipelineCreateFlags::NO_PROTECTED_ACCESS` and \
+                `PipelineCreateFlags::PROTECTED_ACCESS_ONLY`"
+                .into(),
+            // vuids?
+            ..Default::default()
+        }));
+    }
+
+    Ok(())
+}
+
+pub(crate) unsafe fn executable_statistics_unchecked(
+    device: &Device,
+    pipeline: ash::vk::Pipeline,
+) -> Result<Vec<PipelineExecutableStatistics>, VulkanError> {
+    fn to_string(chars: &[c_char]) -> String {
+        let bytes: &[u8] = cast_slice(chars);
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        String::from_utf8_lossy(&bytes[0..end]).into()
+    }
+
+    let fns = device.fns();
+    let pipeline_info_vk = ash::vk::PipelineInfoKHR {
+        pipeline,
+        ..Default::default()
+    };
+
+    let executable_properties_vk = loop {
+        let mut count = 0;
+        (fns.khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.handle(),
+            &pipeline_info_vk,
+            &mut count,
+            ptr::null_mut(),
+        )
+        .result()
+        .map_err(VulkanError::from)?;
+
+        let mut properties_vk =
+            vec![ash::vk::PipelineExecutablePropertiesKHR::default(); count as usize];
+        let result = (fns
+            .khr_pipeline_executable_properties
+            .get_pipeline_executable_properties_khr)(
+            device.handle(),
+            &pipeline_info_vk,
+            &mut count,
+            properties_vk.as_mut_ptr(),
+        );
+
+        match result {
+            ash::vk::Result::SUCCESS => {
+                properties_vk.truncate(count as usize);
+                break properties_vk;
+            }
+            ash::vk::Result::INCOMPLETE => (),
+            err => return Err(VulkanError::from(err)),
+        }
+    };
+
+    executable_properties_vk
+        .into_iter()
+        .enumerate()
+        .map(|(executable_index, properties_vk)| {
+            let executable_info_vk = ash::vk::PipelineExecutableInfoKHR {
+                pipeline,
+                executable_index: executable_index as u32,
+                ..Default::default()
+            };
+
+            let statistics_vk = loop {
+                let mut count = 0;
+                (fns.khr_pipeline_executable_properties
+                    .get_pipeline_executable_statistics_khr)(
+                    device.handle(),
+                    &executable_info_vk,
+                    &mut count,
+                    ptr::null_mut(),
+                )
+                .result()
+                .map_err(VulkanError::from)?;
+
+                let mut statistics_vk =
+                    vec![ash::vk::PipelineExecutableStatisticKHR::default(); count as usize];
+                let result = (fns
+                    .khr_pipeline_executable_properties
+                    .get_pipeline_executable_statistics_khr)(
+                    device.handle(),
+                    &executable_info_vk,
+                    &mut count,
+                    statistics_vk.as_mut_ptr(),
+                );
+
+                match result {
+                    ash::vk::Result::SUCCESS => {
+                        statistics_vk.truncate(count as usize);
+                        break statistics_vk;
+                    }
+                    ash::vk::Result::INCOMPLETE => (),
+                    err => return Err(VulkanError::from(err)),
+                }
+            };
+
+            let statistics = statistics_vk
+                .iter()
+                .map(|statistic_vk| PipelineExecutableStatistic {
+                    name: to_string(&statistic_vk.name),
+                    description: to_string(&statistic_vk.description),
+                    value: match statistic_vk.format {
+                        ash::vk::PipelineExecutableStatisticFormatKHR::BOOL32 => {
+                            PipelineExecutableStatisticValue::Bool(statistic_vk.value.b32 != 0)
+                        }
+                        ash::vk::PipelineExecutableStatisticFormatKHR::INT64 => {
+                            PipelineExecutableStatisticValue::Int64(statistic_vk.value.i64)
+                        }
+                        ash::vk::PipelineExecutableStatisticFormatKHR::UINT64 => {
+                            PipelineExecutableStatisticValue::Uint64(statistic_vk.value.u64)
+                        }
+                        ash::vk::PipelineExecutableStatisticFormatKHR::FLOAT64 => {
+                            PipelineExecutableStatisticValue::Float64(statistic_vk.value.f64)
+                        }
+                        _ => PipelineExecutableStatisticValue::Unknown(statistic_vk.value.u64),
+                    },
+                })
+                .collect();
+
+            Ok(PipelineExecutableStatistics {
+                name: to_string(&properties_vk.name),
+                description: to_string(&properties_vk.description),
+                stages: properties_vk.stages.into(),
+                subgroup_size: properties_vk.subgroup_size,
+                statistics,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PipelineShaderStageCreateInfo, StateMode};
+    use crate::shader::{ShaderModule, ShaderModuleCreateInfo};
+
+    #[test]
+    fn state_mode_accessors() {
+        let fixed = StateMode::Fixed(String::from("a"));
+        assert!(fixed.is_fixed());
+        assert!(!fixed.is_dynamic());
+        assert_eq!(fixed.fixed().map(String::as_str), Some("a"));
+        assert_eq!(fixed.map(|x| x.len()), StateMode::Fixed(1));
+
+        let dynamic = StateMode::<String>::Dynamic;
+        assert!(dynamic.is_dynamic());
+        assert!(!dynamic.is_fixed());
+        assert_eq!(dynamic.fixed(), None);
+        assert_eq!(dynamic.map(|x| x.len()), StateMode::Dynamic);
+    }
+
+    #[test]
+    fn max_clip_distances() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // A vertex shader with an output variable `float clip_distances[count]`, decorated with
+        // `BuiltIn ClipDistance`.
+        let stage = |count: u32| {
+            let code = [
+                119734787, 65536, 0, 11, 0, 131089, 1, 131089, 32, 196622, 0, 1, 393231, 0, 1,
+                1852399981, 0, 8, 262215, 8, 11, 3, 131091, 2, 196641, 3, 2, 196630, 4, 32, 262165,
+                5, 32, 0, 262187, 5, 6, count, 262172, 7, 4, 6, 262176, 9, 3, 7, 262203, 9, 8, 3,
+                327734, 2, 1, 0, 3, 131320, 10, 65789, 65592,
+            ];
+            let module = unsafe {
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&code)).unwrap()
+            };
+
+            PipelineShaderStageCreateInfo::new(module.entry_point("main").unwrap())
+        };
+
+        let max_clip_distances = device.physical_device().properties().max_clip_distances;
+
+        if max_clip_distances != 0 {
+            assert!(stage(max_clip_distances).validate(&device).is_ok());
+        }
+
+        let err = stage(max_clip_distances + 1).validate(&device).unwrap_err();
+        assert!(err.problem.contains("clip distances"));
+    }
+}
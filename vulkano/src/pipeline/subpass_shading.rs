@@ -0,0 +1,756 @@
+// Copyright (c) 2023 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! A pipeline that runs a compute-like shader inside a subpass.
+//!
+//! Subpass shading is provided by the [`huawei_subpass_shading`] extension. It allows a shader
+//! that is written like a compute shader to be executed inside a render pass, once for each pixel
+//! of the render area, with access to the input attachments of the subpass. This is mainly useful
+//! for deferred shading on tile-based GPUs, where the G-buffer can then be read from tile memory
+//! instead of from main memory.
+//!
+//! A subpass shading pipeline is created for a specific subpass, whose
+//! [`pipeline_bind_point`](crate::render_pass::SubpassDescription::pipeline_bind_point) must be
+//! [`PipelineBindPoint::SubpassShading`]. It is bound with
+//! [`bind_pipeline_subpass_shading`](crate::command_buffer::AutoCommandBufferBuilder::bind_pipeline_subpass_shading),
+//! and executed with
+//! [`subpass_shading`](crate::command_buffer::AutoCommandBufferBuilder::subpass_shading) while
+//! that subpass is active.
+//!
+//! [`huawei_subpass_shading`]: crate::device::DeviceExtensions::huawei_subpass_shading
+
+use super::{
+    validate_descriptor_buffer_flag, validate_protected_access_flags, PipelineCreateFlags,
+    PipelineShaderStageCreateInfo,
+};
+use crate::{
+    device::{Device, DeviceOwned, DeviceOwnedDebugWrapper},
+    instance::InstanceOwnedDebugWrapper,
+    macros::impl_id_counter,
+    pipeline::{
+        cache::PipelineCache,
+        layout::{PipelineLayout, PushConstantRange},
+        Pipeline, PipelineBindPoint,
+    },
+    render_pass::Subpass,
+    shader::{DescriptorBindingRequirements, EntryPointInfo, ShaderExecution, ShaderStages},
+    Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, VulkanError, VulkanObject,
+};
+use ahash::HashMap;
+use std::{ffi::CString, mem::MaybeUninit, num::NonZeroU64, ptr, sync::Arc};
+
+/// A pipeline object that runs a compute-like shader inside a subpass.
+///
+/// See [the documentation of the module](crate::pipeline::subpass_shading) for more info.
+#[derive(Debug)]
+pub struct SubpassShadingPipeline {
+    handle: ash::vk::Pipeline,
+    device: InstanceOwnedDebugWrapper<Arc<Device>>,
+    id: NonZeroU64,
+
+    flags: PipelineCreateFlags,
+    layout: DeviceOwnedDebugWrapper<Arc<PipelineLayout>>,
+    subpass: Subpass,
+
+    descriptor_binding_requirements: HashMap<(u32, u32), DescriptorBindingRequirements>,
+    num_used_descriptor_sets: u32,
+}
+
+impl SubpassShadingPipeline {
+    /// Creates a new `SubpassShadingPipeline`.
+    #[inline]
+    pub fn new(
+        device: Arc<Device>,
+        cache: Option<Arc<PipelineCache>>,
+        create_info: SubpassShadingPipelineCreateInfo,
+    ) -> Result<Arc<SubpassShadingPipeline>, Validated<VulkanError>> {
+        Self::validate_new(&device, cache.as_ref().map(AsRef::as_ref), &create_info)?;
+
+        unsafe { Ok(Self::new_unchecked(device, cache, create_info)?) }
+    }
+
+    fn validate_new(
+        device: &Device,
+        cache: Option<&PipelineCache>,
+        create_info: &SubpassShadingPipelineCreateInfo,
+    ) -> Result<(), Box<ValidationError>> {
+        // VUID-vkCreateComputePipelines-pipelineCache-parent
+        if let Some(cache) = &cache {
+            assert_eq!(device, cache.device().as_ref());
+        }
+
+        create_info
+            .validate(device)
+            .map_err(|err| err.add_context("create_info"))?;
+
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
+    pub unsafe fn new_unchecked(
+        device: Arc<Device>,
+        cache: Option<Arc<PipelineCache>>,
+        create_info: SubpassShadingPipelineCreateInfo,
+    ) -> Result<Arc<SubpassShadingPipeline>, VulkanError> {
+        let &SubpassShadingPipelineCreateInfo {
+            flags,
+            ref stage,
+            ref layout,
+            ref subpass,
+            _ne: _,
+        } = &create_info;
+
+        let stage_vk;
+        let name_vk;
+        let specialization_info_vk;
+        let specialization_map_entries_vk: Vec<_>;
+        let mut specialization_data_vk: Vec<u8>;
+        let required_subgroup_size_create_info;
+
+        {
+            let &PipelineShaderStageCreateInfo {
+                flags,
+                ref entry_point,
+                ref required_subgroup_size,
+                _ne: _,
+            } = stage;
+
+            let entry_point_info = entry_point.info();
+            name_vk = CString::new(entry_point_info.name.as_str()).unwrap();
+
+            specialization_data_vk = Vec::new();
+            specialization_map_entries_vk = entry_point
+                .module()
+                .specialization_info()
+                .iter()
+                .map(|(&constant_id, value)| {
+                    let data = value.as_bytes();
+                    let offset = specialization_data_vk.len() as u32;
+                    let size = data.len();
+                    specialization_data_vk.extend(data);
+
+                    ash::vk::SpecializationMapEntry {
+                        constant_id,
+                        offset,
+                        size,
+                    }
+                })
+                .collect();
+
+            specialization_info_vk = ash::vk::SpecializationInfo {
+                map_entry_count: specialization_map_entries_vk.len() as u32,
+                p_map_entries: specialization_map_entries_vk.as_ptr(),
+                data_size: specialization_data_vk.len(),
+                p_data: specialization_data_vk.as_ptr() as *const _,
+            };
+            required_subgroup_size_create_info =
+                required_subgroup_size.map(|required_subgroup_size| {
+                    ash::vk::PipelineShaderStageRequiredSubgroupSizeCreateInfo {
+                        required_subgroup_size,
+                        ..Default::default()
+                    }
+                });
+            stage_vk = ash::vk::PipelineShaderStageCreateInfo {
+                p_next: required_subgroup_size_create_info.as_ref().map_or(
+                    ptr::null(),
+                    |required_subgroup_size_create_info| {
+                        required_subgroup_size_create_info as *const _ as _
+                    },
+                ),
+                flags: flags.into(),
+                stage: ash::vk::ShaderStageFlags::SUBPASS_SHADING_HUAWEI,
+                module: entry_point.module().handle(),
+                p_name: name_vk.as_ptr(),
+                p_specialization_info: if specialization_info_vk.data_size == 0 {
+                    ptr::null()
+                } else {
+                    &specialization_info_vk
+                },
+                ..Default::default()
+            };
+        }
+
+        let mut subpass_shading_create_info_vk = ash::vk::SubpassShadingPipelineCreateInfoHUAWEI {
+            render_pass: subpass.render_pass().handle(),
+            subpass: subpass.index(),
+            ..Default::default()
+        };
+
+        let create_infos_vk = ash::vk::ComputePipelineCreateInfo {
+            p_next: &mut subpass_shading_create_info_vk as *mut _ as *const _,
+            flags: flags.into(),
+            stage: stage_vk,
+            layout: layout.handle(),
+            base_pipeline_handle: ash::vk::Pipeline::null(),
+            base_pipeline_index: -1,
+            ..Default::default()
+        };
+
+        let handle = {
+            let fns = device.fns();
+            let mut output = MaybeUninit::uninit();
+            (fns.v1_0.create_compute_pipelines)(
+                device.handle(),
+                cache.as_ref().map_or_else(Default::default, |c| c.handle()),
+                1,
+                &create_infos_vk,
+                ptr::null(),
+                output.as_mut_ptr(),
+            )
+            .result()
+            .map_err(VulkanError::from)?;
+            output.assume_init()
+        };
+
+        Ok(Self::from_handle(device, handle, create_info))
+    }
+
+    /// Creates a new `SubpassShadingPipeline` from a raw object handle.
+    ///
+    /// # Safety
+    ///
+    /// - `handle` must be a valid Vulkan object handle created from `device`.
+    /// - `create_info` must match the info used to create the object.
+    #[inline]
+    pub unsafe fn from_handle(
+        device: Arc<Device>,
+        handle: ash::vk::Pipeline,
+        create_info: SubpassShadingPipelineCreateInfo,
+    ) -> Arc<SubpassShadingPipeline> {
+        let SubpassShadingPipelineCreateInfo {
+            flags,
+            stage,
+            layout,
+            subpass,
+            _ne: _,
+        } = create_info;
+
+        let descriptor_binding_requirements =
+            subpass_shading_descriptor_binding_requirements(stage.entry_point.info());
+        let num_used_descriptor_sets = descriptor_binding_requirements
+            .keys()
+            .map(|loc| loc.0)
+            .max()
+            .map(|x| x + 1)
+            .unwrap_or(0);
+
+        Arc::new(SubpassShadingPipeline {
+            handle,
+            device: InstanceOwnedDebugWrapper(device),
+            id: Self::next_id(),
+
+            flags,
+            layout: DeviceOwnedDebugWrapper(layout),
+            subpass,
+
+            descriptor_binding_requirements,
+            num_used_descriptor_sets,
+        })
+    }
+
+    /// Returns the `Device` that the pipeline was created with.
+    #[inline]
+    pub fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+
+    /// Returns the flags that the pipeline was created with.
+    #[inline]
+    pub fn flags(&self) -> PipelineCreateFlags {
+        self.flags
+    }
+
+    /// Returns the subpass that the pipeline was created for.
+    #[inline]
+    pub fn subpass(&self) -> &Subpass {
+        &self.subpass
+    }
+}
+
+impl Pipeline for SubpassShadingPipeline {
+    #[inline]
+    fn bind_point(&self) -> PipelineBindPoint {
+        PipelineBindPoint::SubpassShading
+    }
+
+    #[inline]
+    fn layout(&self) -> &Arc<PipelineLayout> {
+        &self.layout
+    }
+
+    #[inline]
+    fn num_used_descriptor_sets(&self) -> u32 {
+        self.num_used_descriptor_sets
+    }
+
+    #[inline]
+    fn descriptor_binding_requirements(
+        &self,
+    ) -> &HashMap<(u32, u32), DescriptorBindingRequirements> {
+        &self.descriptor_binding_requirements
+    }
+}
+
+impl_id_counter!(SubpassShadingPipeline);
+
+unsafe impl VulkanObject for SubpassShadingPipeline {
+    type Handle = ash::vk::Pipeline;
+
+    #[inline]
+    fn handle(&self) -> Self::Handle {
+        self.handle
+    }
+}
+
+unsafe impl DeviceOwned for SubpassShadingPipeline {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.device()
+    }
+}
+
+impl Drop for SubpassShadingPipeline {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let fns = self.device.fns();
+            (fns.v1_0.destroy_pipeline)(self.device.handle(), self.handle, ptr::null());
+        }
+    }
+}
+
+/// Parameters to create a new `SubpassShadingPipeline`.
+#[derive(Clone, Debug)]
+pub struct SubpassShadingPipelineCreateInfo {
+    /// Additional properties of the pipeline.
+    ///
+    /// The default value is empty.
+    pub flags: PipelineCreateFlags,
+
+    /// The shader stage to use.
+    ///
+    /// The entry point must be a compute shader entry point. It is executed in the
+    /// [`ShaderStage::SubpassShading`](crate::shader::ShaderStage::SubpassShading) stage, so the
+    /// bindings and push constant ranges of `layout` that it uses must include
+    /// [`ShaderStages::SUBPASS_SHADING`] instead of [`ShaderStages::COMPUTE`].
+    ///
+    /// There is no default value.
+    pub stage: PipelineShaderStageCreateInfo,
+
+    /// The pipeline layout to use.
+    ///
+    /// There is no default value.
+    pub layout: Arc<PipelineLayout>,
+
+    /// The subpass to use the pipeline in.
+    ///
+    /// The `pipeline_bind_point` of the subpass must be [`PipelineBindPoint::SubpassShading`].
+    ///
+    /// There is no default value.
+    pub subpass: Subpass,
+
+    pub _ne: crate::NonExhaustive,
+}
+
+impl SubpassShadingPipelineCreateInfo {
+    /// Returns a `SubpassShadingPipelineCreateInfo` with the specified `stage`, `layout` and
+    /// `subpass`.
+    #[inline]
+    pub fn new(
+        stage: PipelineShaderStageCreateInfo,
+        layout: Arc<PipelineLayout>,
+        subpass: Subpass,
+    ) -> Self {
+        Self {
+            flags: PipelineCreateFlags::empty(),
+            stage,
+            layout,
+            subpass,
+            _ne: crate::NonExhaustive(()),
+        }
+    }
+
+    pub(crate) fn validate(&self, device: &Device) -> Result<(), Box<ValidationError>> {
+        let &Self {
+            flags,
+            ref stage,
+            ref layout,
+            ref subpass,
+            _ne: _,
+        } = self;
+
+        if !device.enabled_features().subpass_shading {
+            return Err(Box::new(ValidationError {
+                problem: "creating a subpass shading pipeline".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "subpass_shading",
+                )])]),
+                ..Default::default()
+            }));
+        }
+
+        flags.validate_device(device).map_err(|err| {
+            err.add_context("flags")
+                .set_vuids(&["VUID-VkComputePipelineCreateInfo-flags-parameter"])
+        })?;
+
+        if flags.intersects(PipelineCreateFlags::DERIVATIVE) {
+            return Err(Box::new(ValidationError {
+                context: "flags".into(),
+                problem: "contains `PipelineCreateFlags::DERIVATIVE`, but derivative subpass \
+                    shading pipelines are not supported"
+                    .into(),
+                ..Default::default()
+            }));
+        }
+
+        validate_descriptor_buffer_flag(device, flags, layout)?;
+        validate_protected_access_flags(device, flags)?;
+
+        stage
+            .validate(device)
+            .map_err(|err| err.add_context("stage"))?;
+
+        let entry_point_info = stage.entry_point.info();
+
+        if !matches!(entry_point_info.execution, ShaderExecution::Compute(_)) {
+            return Err(Box::new(ValidationError {
+                context: "stage.entry_point".into(),
+                problem: "is not a compute shader entry point".into(),
+                vuids: &["VUID-VkComputePipelineCreateInfo-stage-00701"],
+                ..Default::default()
+            }));
+        }
+
+        // VUID-VkComputePipelineCreateInfo-commonparent
+        assert_eq!(device, subpass.render_pass().device().as_ref());
+
+        if subpass.subpass_desc().pipeline_bind_point != PipelineBindPoint::SubpassShading {
+            return Err(Box::new(ValidationError {
+                context: "subpass".into(),
+                problem: "the `pipeline_bind_point` of the subpass is not \
+                    `PipelineBindPoint::SubpassShading`"
+                    .into(),
+                vuids: &["VUID-VkSubpassShadingPipelineCreateInfoHUAWEI-subpass-04946"],
+                ..Default::default()
+            }));
+        }
+
+        layout
+            .ensure_compatible_with_shader(
+                subpass_shading_descriptor_binding_requirements(entry_point_info)
+                    .iter()
+                    .map(|(k, v)| (*k, v)),
+                subpass_shading_push_constant_requirements(entry_point_info).as_ref(),
+            )
+            .map_err(|err| {
+                Box::new(ValidationError {
+                    context: "stage.entry_point".into(),
+                    vuids: &[
+                        "VUID-VkComputePipelineCreateInfo-layout-07987",
+                        "VUID-VkComputePipelineCreateInfo-layout-07988",
+                        "VUID-VkComputePipelineCreateInfo-layout-07990",
+                        "VUID-VkComputePipelineCreateInfo-layout-07991",
+                    ],
+                    ..ValidationError::from_error(err)
+                })
+            })?;
+
+        Ok(())
+    }
+}
+
+// The entry point is reflected as a compute shader, but it is executed in the subpass shading
+// stage, so the stages that the layout must provide are different.
+fn subpass_shading_descriptor_binding_requirements(
+    entry_point_info: &EntryPointInfo,
+) -> HashMap<(u32, u32), DescriptorBindingRequirements> {
+    entry_point_info
+        .descriptor_binding_requirements
+        .iter()
+        .map(|(&loc, reqs)| {
+            let mut reqs = reqs.clone();
+            reqs.stages = replace_compute_stage(reqs.stages);
+
+            for desc_reqs in reqs.descriptors.values_mut() {
+                desc_reqs.memory_read = replace_compute_stage(desc_reqs.memory_read);
+                desc_reqs.memory_write = replace_compute_stage(desc_reqs.memory_write);
+            }
+
+            (loc, reqs)
+        })
+        .collect()
+}
+
+fn subpass_shading_push_constant_requirements(
+    entry_point_info: &EntryPointInfo,
+) -> Option<PushConstantRange> {
+    entry_point_info
+        .push_constant_requirements
+        .map(|range| PushConstantRange {
+            stages: replace_compute_stage(range.stages),
+            ..range
+        })
+}
+
+fn replace_compute_stage(stages: ShaderStages) -> ShaderStages {
+    if stages.intersects(ShaderStages::COMPUTE) {
+        (stages - ShaderStages::COMPUTE) | ShaderStages::SUBPASS_SHADING
+    } else {
+        stages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SubpassShadingPipeline, SubpassShadingPipelineCreateInfo};
+    use crate::{
+        buffer::{Buffer, BufferCreateInfo, BufferUsage},
+        command_buffer::{
+            allocator::StandardCommandBufferAllocator, AutoCommandBufferBuilder,
+            CommandBufferUsage, PrimaryCommandBufferAbstract, RenderPassBeginInfo,
+            SubpassBeginInfo, SubpassContents,
+        },
+        descriptor_set::{
+            allocator::StandardDescriptorSetAllocator,
+            layout::{
+                DescriptorSetLayout, DescriptorSetLayoutBinding, DescriptorSetLayoutCreateInfo,
+                DescriptorType,
+            },
+            PersistentDescriptorSet, WriteDescriptorSet,
+        },
+        memory::allocator::{AllocationCreateInfo, MemoryTypeFilter, StandardMemoryAllocator},
+        pipeline::{
+            layout::PipelineLayoutCreateInfo, PipelineBindPoint, PipelineLayout,
+            PipelineShaderStageCreateInfo,
+        },
+        render_pass::{
+            Framebuffer, FramebufferCreateInfo, RenderPass, RenderPassCreateInfo, Subpass,
+            SubpassDescription,
+        },
+        shader::{ShaderModule, ShaderModuleCreateInfo, ShaderStages},
+        sync::GpuFuture,
+        Validated,
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn subpass_shading() {
+        let (device, queue) = gfx_dev_and_queue!(
+            extensions: [huawei_subpass_shading],
+            features: [subpass_shading],
+        );
+
+        let render_pass = |pipeline_bind_point| {
+            RenderPass::new(
+                device.clone(),
+                RenderPassCreateInfo {
+                    subpasses: vec![SubpassDescription {
+                        pipeline_bind_point,
+                        ..Default::default()
+                    }],
+                    ..Default::default()
+                },
+            )
+            .unwrap()
+        };
+
+        let cs = unsafe {
+            /*
+            #version 450
+
+            layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+            layout(set = 0, binding = 0) buffer Data {
+                uint data[];
+            } buf;
+
+            void main() {
+                buf.data[0] = 1;
+            }
+            */
+            const MODULE: [u32; 148] = [
+                119734787, 65536, 28, 24, 0, 131089, 1, 720906, 1599492179, 1599227979, 1919906931,
+                1600481121, 1717990754, 1935635045, 1634889588, 1667196263, 1936941420, 0, 393227,
+                1, 1280527431, 1685353262, 808793134, 0, 196622, 0, 1, 327695, 5, 21, 1852399981,
+                0, 393232, 21, 17, 1, 1, 1, 262215, 4, 6, 4, 327752, 5, 0, 35, 0, 196679, 5, 2,
+                262215, 9, 34, 0, 262215, 9, 33, 0, 131091, 2, 262165, 3, 32, 0, 196637, 4, 3,
+                196638, 5, 4, 262165, 7, 32, 1, 262187, 7, 6, 1, 262187, 7, 8, 0, 262176, 10, 12,
+                5, 262203, 10, 9, 12, 196641, 13, 2, 262187, 3, 14, 1, 262176, 16, 12, 4, 262176,
+                17, 12, 3, 262187, 3, 18, 0, 327734, 2, 12, 0, 13, 131320, 11, 131321, 15, 131320,
+                15, 393281, 17, 19, 9, 18, 8, 196670, 19, 14, 65789, 65592, 327734, 2, 21, 0, 13,
+                131320, 20, 131321, 22, 131320, 22, 262201, 2, 23, 12, 65789, 65592,
+            ];
+            let module =
+                ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&MODULE)).unwrap();
+            module.entry_point("main").unwrap()
+        };
+
+        // The layout must provide the bindings to the subpass shading stage, not the compute
+        // stage.
+        let set_layout = DescriptorSetLayout::new(
+            device.clone(),
+            DescriptorSetLayoutCreateInfo {
+                bindings: [(
+                    0,
+                    DescriptorSetLayoutBinding {
+                        stages: ShaderStages::SUBPASS_SHADING,
+                        ..DescriptorSetLayoutBinding::descriptor_type(DescriptorType::StorageBuffer)
+                    },
+                )]
+                .into(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let layout = PipelineLayout::new(
+            device.clone(),
+            PipelineLayoutCreateInfo {
+                set_layouts: vec![set_layout.clone()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // The subpass must use the subpass shading bind point.
+        match SubpassShadingPipeline::new(
+            device.clone(),
+            None,
+            SubpassShadingPipelineCreateInfo::new(
+                PipelineShaderStageCreateInfo::new(cs.clone()),
+                layout.clone(),
+                Subpass::from(render_pass(PipelineBindPoint::Graphics), 0).unwrap(),
+            ),
+        ) {
+            Err(Validated::ValidationError(err)) => {
+                assert_eq!(
+                    err.vuids,
+                    ["VUID-VkSubpassShadingPipelineCreateInfoHUAWEI-subpass-04946"],
+                );
+            }
+            _ => panic!(),
+        }
+
+        let render_pass = render_pass(PipelineBindPoint::SubpassShading);
+        let pipeline = SubpassShadingPipeline::new(
+            device.clone(),
+            None,
+            SubpassShadingPipelineCreateInfo::new(
+                PipelineShaderStageCreateInfo::new(cs),
+                layout.clone(),
+                Subpass::from(render_pass.clone(), 0).unwrap(),
+            ),
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                extent: [32, 32],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+        let buffer = Buffer::from_data(
+            memory_allocator,
+            BufferCreateInfo {
+                usage: BufferUsage::STORAGE_BUFFER,
+                ..Default::default()
+            },
+            AllocationCreateInfo {
+                memory_type_filter: MemoryTypeFilter::PREFER_DEVICE
+                    | MemoryTypeFilter::HOST_RANDOM_ACCESS,
+                ..Default::default()
+            },
+            0u32,
+        )
+        .unwrap();
+        let ds_allocator = StandardDescriptorSetAllocator::new(device.clone(), Default::default());
+        let set = PersistentDescriptorSet::new(
+            &ds_allocator,
+            set_layout,
+            [WriteDescriptorSet::buffer(0, buffer.clone())],
+            [],
+        )
+        .unwrap();
+
+        let cb_allocator = StandardCommandBufferAllocator::new(device, Default::default());
+
+        // The subpass contents must be inline.
+        {
+            let mut builder = AutoCommandBufferBuilder::primary(
+                &cb_allocator,
+                queue.queue_family_index(),
+                CommandBufferUsage::OneTimeSubmit,
+            )
+            .unwrap();
+            builder
+                .begin_render_pass(
+                    RenderPassBeginInfo::framebuffer(framebuffer.clone()),
+                    SubpassBeginInfo {
+                        contents: SubpassContents::SecondaryCommandBuffers,
+                        ..Default::default()
+                    },
+                )
+                .unwrap()
+                .bind_pipeline_subpass_shading(pipeline.clone())
+                .unwrap()
+                .bind_descriptor_sets(
+                    PipelineBindPoint::SubpassShading,
+                    layout.clone(),
+                    0,
+                    set.clone(),
+                )
+                .unwrap();
+            match builder.subpass_shading() {
+                Err(err) => assert_eq!(
+                    err.problem,
+                    "the contents of the current subpass instance is not \
+                    `SubpassContents::Inline`",
+                ),
+                Ok(_) => panic!(),
+            }
+        }
+
+        let mut builder = AutoCommandBufferBuilder::primary(
+            &cb_allocator,
+            queue.queue_family_index(),
+            CommandBufferUsage::OneTimeSubmit,
+        )
+        .unwrap();
+        builder
+            .begin_render_pass(
+                RenderPassBeginInfo::framebuffer(framebuffer),
+                Default::default(),
+            )
+            .unwrap()
+            .bind_pipeline_subpass_shading(pipeline)
+            .unwrap()
+            .bind_descriptor_sets(PipelineBindPoint::SubpassShading, layout, 0, set)
+            .unwrap()
+            .subpass_shading()
+            .unwrap()
+            .end_render_pass(Default::default())
+            .unwrap();
+        let command_buffer = builder.build().unwrap();
+
+        // The storage buffer is written through the descriptor set, so it must be tracked as in
+        // use while the command buffer is executing.
+        let future = command_buffer
+            .execute(queue)
+            .unwrap()
+            .then_signal_fence_and_flush()
+            .unwrap();
+        assert!(buffer.write().is_err());
+
+        future.wait(None).unwrap();
+        assert_eq!(*buffer.read().unwrap(), 1);
+    }
+}
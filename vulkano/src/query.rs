@@ -145,6 +145,19 @@ impl QueryPool {
         self.query_count
     }
 
+    /// Converts a difference between two timestamp values, as written by [`write_timestamp`],
+    /// into nanoseconds.
+    ///
+    /// Timestamps are counted in device-specific ticks. This multiplies `ticks` by the
+    /// [`timestamp_period`] property of the physical device.
+    ///
+    /// [`write_timestamp`]: crate::command_buffer::AutoCommandBufferBuilder::write_timestamp
+    /// [`timestamp_period`]: crate::device::Properties::timestamp_period
+    #[inline]
+    pub fn timestamp_to_nanoseconds(&self, ticks: u64) -> f64 {
+        ticks as f64 * self.device.physical_device().properties().timestamp_period as f64
+    }
+
     /// Copies the results of a range of queries to a buffer on the CPU.
     ///
     /// [`self.ty().result_len()`] will be written for each query in the range, plus 1 extra
@@ -256,7 +269,8 @@ impl QueryPool {
             | QueryType::AccelerationStructureCompactedSize
             | QueryType::AccelerationStructureSerializationSize
             | QueryType::AccelerationStructureSerializationBottomLevelPointers
-            | QueryType::AccelerationStructureSize => (),
+            | QueryType::AccelerationStructureSize
+            | QueryType::PrimitivesGenerated => (),
         }
 
         Ok(())
@@ -388,7 +402,8 @@ impl QueryPoolCreateInfo {
             | QueryType::AccelerationStructureCompactedSize
             | QueryType::AccelerationStructureSerializationSize
             | QueryType::AccelerationStructureSerializationBottomLevelPointers
-            | QueryType::AccelerationStructureSize => (),
+            | QueryType::AccelerationStructureSize
+            | QueryType::PrimitivesGenerated => (),
         };
 
         if query_count == 0 {
@@ -470,13 +485,22 @@ pub enum QueryType {
     ///
     /// [`write_acceleration_structures_properties`]: crate::command_buffer::AutoCommandBufferBuilder::write_acceleration_structures_properties
     AccelerationStructureSize = ash::vk::QueryType::ACCELERATION_STRUCTURE_SIZE_KHR.as_raw(),
+
+    /// Tracks the number of primitives that are generated by the last pre-rasterization shader
+    /// stage, before any clipping or rasterizer discard takes place.
+    ///
+    /// Used with the [`begin_query`] and [`end_query`] commands.
+    ///
+    /// [`begin_query`]: crate::command_buffer::AutoCommandBufferBuilder::begin_query
+    /// [`end_query`]: crate::command_buffer::AutoCommandBufferBuilder::end_query
+    PrimitivesGenerated = ash::vk::QueryType::PRIMITIVES_GENERATED_EXT.as_raw(),
 }
 
 impl QueryType {
     /// Returns the number of [`QueryResultElement`]s that are needed to hold the result of a
     /// single query of this type.
     ///
-    /// - For [`Occlusion`] and [`Timestamp`] queries, this returns 1.
+    /// - For [`Occlusion`], [`Timestamp`] and [`PrimitivesGenerated`] queries, this returns 1.
     /// - For [`PipelineStatistics`] queries, this returns the number of statistics flags enabled.
     ///
     /// If the results are retrieved with [`WITH_AVAILABILITY`] enabled, then an additional element
@@ -485,6 +509,7 @@ impl QueryType {
     /// [`Occlusion`]: QueryType::Occlusion
     /// [`Timestamp`]: QueryType::Timestamp
     /// [`PipelineStatistics`]: QueryType::PipelineStatistics
+    /// [`PrimitivesGenerated`]: QueryType::PrimitivesGenerated
     /// [`WITH_AVAILABILITY`]: QueryResultFlags::WITH_AVAILABILITY
     #[inline]
     pub const fn result_len(&self) -> DeviceSize {
@@ -494,7 +519,8 @@ impl QueryType {
             | Self::AccelerationStructureCompactedSize
             | Self::AccelerationStructureSerializationSize
             | Self::AccelerationStructureSerializationBottomLevelPointers
-            | Self::AccelerationStructureSize => 1,
+            | Self::AccelerationStructureSize
+            | Self::PrimitivesGenerated => 1,
             Self::PipelineStatistics(flags) => flags.count() as DeviceSize,
         }
     }
@@ -550,6 +576,17 @@ impl QueryType {
                     }));
                 }
             }
+            QueryType::PrimitivesGenerated => {
+                if !device.enabled_extensions().ext_primitives_generated_query {
+                    return Err(Box::new(ValidationError {
+                        problem: "is `QueryType::PrimitivesGenerated`".into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[
+                            Requires::DeviceExtension("ext_primitives_generated_query"),
+                        ])]),
+                        ..Default::default()
+                    }));
+                }
+            }
         }
 
         Ok(())
@@ -575,6 +612,7 @@ impl From<&QueryType> for ash::vk::QueryType {
             QueryType::AccelerationStructureSize => {
                 ash::vk::QueryType::ACCELERATION_STRUCTURE_SIZE_KHR
             }
+            QueryType::PrimitivesGenerated => ash::vk::QueryType::PRIMITIVES_GENERATED_EXT,
         }
     }
 }
@@ -739,4 +777,16 @@ mod tests {
             Err(Validated::ValidationError(_)),
         ));
     }
+
+    #[test]
+    fn primitives_generated_extension() {
+        let (device, _) = gfx_dev_and_queue!();
+        assert!(matches!(
+            QueryPool::new(
+                device,
+                QueryPoolCreateInfo::query_type(QueryType::PrimitivesGenerated),
+            ),
+            Err(Validated::ValidationError(_)),
+        ));
+    }
 }
@@ -115,6 +115,7 @@ impl RenderPass {
                 .map(|subpass| {
                     let &SubpassDescription {
                         flags,
+                        pipeline_bind_point,
                         view_mask,
                         ref input_attachments,
                         ref color_attachments,
@@ -307,7 +308,7 @@ impl RenderPass {
                     (
                         ash::vk::SubpassDescription2 {
                             flags: flags.into(),
-                            pipeline_bind_point: ash::vk::PipelineBindPoint::GRAPHICS, // TODO: any need to make this user-specifiable?
+                            pipeline_bind_point: pipeline_bind_point.into(),
                             view_mask,
                             input_attachment_count: 0,
                             p_input_attachments: ptr::null(),
@@ -585,6 +586,7 @@ impl RenderPass {
             .map(|subpass| {
                 let &SubpassDescription {
                     flags,
+                    pipeline_bind_point,
                     view_mask: _,
                     ref input_attachments,
                     ref color_attachments,
@@ -696,7 +698,7 @@ impl RenderPass {
                 (
                     ash::vk::SubpassDescription {
                         flags: flags.into(),
-                        pipeline_bind_point: ash::vk::PipelineBindPoint::GRAPHICS,
+                        pipeline_bind_point: pipeline_bind_point.into(),
                         input_attachment_count: 0,
                         p_input_attachments: ptr::null(),
                         color_attachment_count: 0,
@@ -46,6 +46,13 @@ macro_rules! single_pass_renderpass {
 }
 
 /// Builds a `RenderPass` object whose template parameter is of indeterminate type.
+///
+/// If the depth/stencil attachment of a pass is also listed as one of its input attachments, both
+/// references use the [`DepthStencilReadOnlyOptimal`] layout, so that the depth buffer can be
+/// read in the shader while still being used for depth testing. Depth and stencil writes must
+/// then be disabled in pipelines used with that pass.
+///
+/// [`DepthStencilReadOnlyOptimal`]: crate::image::ImageLayout::DepthStencilReadOnlyOptimal
 #[macro_export]
 macro_rules! ordered_passes_renderpass {
     (
@@ -102,6 +109,13 @@ macro_rules! ordered_passes_renderpass {
 
             let subpasses = vec![
                 $({
+                    // A depth/stencil attachment that is also read as an input attachment in the
+                    // same subpass must be in a read-only layout for both uses.
+                    #[allow(unused)]
+                    let read_only_depth_stencil_atch: Option<u32> = None
+                        $(.or(Some($depth_stencil_atch)))?
+                        .filter(|atch| [$($input_atch),*].contains(atch));
+
                     let desc = $crate::render_pass::SubpassDescription {
                         color_attachments: vec![
                             $({
@@ -131,13 +145,18 @@ macro_rules! ordered_passes_renderpass {
                         )?],
                         depth_stencil_attachment: {
                             None $(.or({
+                                let layout = if read_only_depth_stencil_atch.is_some() {
+                                    $crate::image::ImageLayout::DepthStencilReadOnlyOptimal
+                                } else {
+                                    $crate::image::ImageLayout::DepthStencilAttachmentOptimal
+                                };
                                 let layouts = &mut layouts[$depth_stencil_atch as usize];
-                                layouts.final_layout = Some($crate::image::ImageLayout::DepthStencilAttachmentOptimal);
+                                layouts.final_layout = Some(layout);
                                 layouts.initial_layout = layouts.initial_layout.or(layouts.final_layout);
 
                                 Some($crate::render_pass::AttachmentReference {
                                     attachment: $depth_stencil_atch,
-                                    layout: $crate::image::ImageLayout::DepthStencilAttachmentOptimal,
+                                    layout,
                                     ..Default::default()
                                 })
                             }))?
@@ -167,13 +186,18 @@ macro_rules! ordered_passes_renderpass {
                         },
                         input_attachments: vec![
                             $({
+                                let layout = if read_only_depth_stencil_atch == Some($input_atch) {
+                                    $crate::image::ImageLayout::DepthStencilReadOnlyOptimal
+                                } else {
+                                    $crate::image::ImageLayout::ShaderReadOnlyOptimal
+                                };
                                 let layouts = &mut layouts[$input_atch as usize];
-                                layouts.final_layout = Some($crate::image::ImageLayout::ShaderReadOnlyOptimal);
+                                layouts.final_layout = Some(layout);
                                 layouts.initial_layout = layouts.initial_layout.or(layouts.final_layout);
 
                                 Some($crate::render_pass::AttachmentReference {
                                     attachment: $input_atch,
-                                    layout: $crate::image::ImageLayout::ShaderReadOnlyOptimal,
+                                    layout,
                                     ..Default::default()
                                 })
                             }),*
@@ -32,6 +32,7 @@ use crate::{
     image::{ImageAspects, ImageLayout, SampleCount},
     instance::InstanceOwnedDebugWrapper,
     macros::{impl_id_counter, vulkan_bitflags, vulkan_bitflags_enum, vulkan_enum},
+    pipeline::PipelineBindPoint,
     shader::ShaderInterface,
     sync::{AccessFlags, DependencyFlags, MemoryBarrier, PipelineStages},
     Requires, RequiresAllOf, RequiresOneOf, Validated, ValidationError, Version, VulkanError,
@@ -223,6 +224,7 @@ impl RenderPass {
         for subpass_desc in &subpasses {
             let &SubpassDescription {
                 flags: _,
+                pipeline_bind_point: _,
                 view_mask,
                 ref input_attachments,
                 ref color_attachments,
@@ -494,6 +496,7 @@ impl RenderPass {
             .all(|(subpass1, subpass2)| {
                 let SubpassDescription {
                     flags: flags1,
+                    pipeline_bind_point: pipeline_bind_point1,
                     view_mask: view_mask1,
                     input_attachments: input_attachments1,
                     color_attachments: color_attachments1,
@@ -507,6 +510,7 @@ impl RenderPass {
                 } = subpass1;
                 let SubpassDescription {
                     flags: flags2,
+                    pipeline_bind_point: pipeline_bind_point2,
                     view_mask: view_mask2,
                     input_attachments: input_attachments2,
                     color_attachments: color_attachments2,
@@ -519,7 +523,7 @@ impl RenderPass {
                     _ne: _,
                 } = subpass2;
 
-                if flags1 != flags2 {
+                if flags1 != flags2 || pipeline_bind_point1 != pipeline_bind_point2 {
                     return false;
                 }
 
@@ -891,6 +895,7 @@ impl RenderPassCreateInfo {
 
             let &SubpassDescription {
                 flags: _,
+                pipeline_bind_point: _,
                 view_mask,
                 ref input_attachments,
                 ref color_attachments,
@@ -1634,11 +1639,17 @@ vulkan_bitflags! {
     /// Flags specifying additional properties of a render pass.
     RenderPassCreateFlags = RenderPassCreateFlags(u32);
 
-    /* TODO: enable
-    // TODO: document
-    TRANSFORM = TRANSFORM_QCOM {
-        device_extensions: [qcom_render_pass_transform],
-    }, */
+    /// The render pass is rendered with a transform, which is given by
+    /// [`RenderPassBeginInfo::transform`] when beginning the render pass. This lets the
+    /// implementation rotate the rendered image to match the [`pre_transform`] of a swapchain,
+    /// instead of rotating it in a separate pass.
+    ///
+    /// [`RenderPassBeginInfo::transform`]: crate::command_buffer::RenderPassBeginInfo::transform
+    /// [`pre_transform`]: crate::swapchain::SwapchainCreateInfo::pre_transform
+    TRANSFORM = TRANSFORM_QCOM
+    RequiresOneOf([
+        RequiresAllOf([DeviceExtension(qcom_render_pass_transform)]),
+    ]),
 }
 
 /// Describes an attachment that will be used in a render pass.
@@ -1790,6 +1801,32 @@ impl AttachmentDescription {
                 .set_vuids(&["VUID-VkAttachmentDescription2-finalLayout-parameter"])
         })?;
 
+        if initial_layout == ImageLayout::AttachmentFeedbackLoopOptimal
+            && !device.enabled_features().attachment_feedback_loop_layout
+        {
+            return Err(Box::new(ValidationError {
+                context: "initial_layout".into(),
+                problem: "is `ImageLayout::AttachmentFeedbackLoopOptimal`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "attachment_feedback_loop_layout",
+                )])]),
+                vuids: &["VUID-VkAttachmentDescription2-attachmentFeedbackLoopLayout-07309"],
+            }));
+        }
+
+        if final_layout == ImageLayout::AttachmentFeedbackLoopOptimal
+            && !device.enabled_features().attachment_feedback_loop_layout
+        {
+            return Err(Box::new(ValidationError {
+                context: "final_layout".into(),
+                problem: "is `ImageLayout::AttachmentFeedbackLoopOptimal`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "attachment_feedback_loop_layout",
+                )])]),
+                vuids: &["VUID-VkAttachmentDescription2-attachmentFeedbackLoopLayout-07310"],
+            }));
+        }
+
         if matches!(
             final_layout,
             ImageLayout::Undefined | ImageLayout::Preinitialized
@@ -2210,6 +2247,18 @@ pub struct SubpassDescription {
     /// The default value is empty.
     pub flags: SubpassDescriptionFlags,
 
+    /// The type of pipeline that is used in this subpass.
+    ///
+    /// If this is [`PipelineBindPoint::SubpassShading`], then the subpass is used for subpass
+    /// shading with a [`SubpassShadingPipeline`], and the [`subpass_shading`] feature must be
+    /// enabled on the device.
+    ///
+    /// The default value is [`PipelineBindPoint::Graphics`].
+    ///
+    /// [`SubpassShadingPipeline`]: crate::pipeline::subpass_shading::SubpassShadingPipeline
+    /// [`subpass_shading`]: crate::device::Features::subpass_shading
+    pub pipeline_bind_point: PipelineBindPoint,
+
     /// If not `0`, enables multiview rendering, and specifies the view indices that are rendered to
     /// in this subpass. The value is a bitmask, so that that for example `0b11` will draw to the
     /// first two views and `0b101` will draw to the first and third view.
@@ -2310,6 +2359,7 @@ impl Default for SubpassDescription {
     fn default() -> Self {
         Self {
             flags: SubpassDescriptionFlags::empty(),
+            pipeline_bind_point: PipelineBindPoint::Graphics,
             view_mask: 0,
             color_attachments: Vec::new(),
             color_resolve_attachments: Vec::new(),
@@ -2330,6 +2380,7 @@ impl SubpassDescription {
 
         let &Self {
             flags,
+            pipeline_bind_point,
             view_mask,
             ref input_attachments,
             ref color_attachments,
@@ -2347,6 +2398,37 @@ impl SubpassDescription {
                 .set_vuids(&["VUID-VkSubpassDescription2-flags-parameter"])
         })?;
 
+        pipeline_bind_point.validate_device(device).map_err(|err| {
+            err.add_context("pipeline_bind_point")
+                .set_vuids(&["VUID-VkSubpassDescription2-pipelineBindPoint-parameter"])
+        })?;
+
+        match pipeline_bind_point {
+            PipelineBindPoint::Graphics => (),
+            PipelineBindPoint::SubpassShading => {
+                if !device.enabled_features().subpass_shading {
+                    return Err(Box::new(ValidationError {
+                        context: "pipeline_bind_point".into(),
+                        problem: "is `PipelineBindPoint::SubpassShading`".into(),
+                        requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                            "subpass_shading",
+                        )])]),
+                        ..Default::default()
+                    }));
+                }
+            }
+            _ => {
+                return Err(Box::new(ValidationError {
+                    context: "pipeline_bind_point".into(),
+                    problem: "is not `PipelineBindPoint::Graphics` or \
+                        `PipelineBindPoint::SubpassShading`"
+                        .into(),
+                    vuids: &["VUID-VkSubpassDescription2-pipelineBindPoint-04953"],
+                    ..Default::default()
+                }));
+            }
+        }
+
         if color_attachments.len() as u32 > properties.max_color_attachments {
             return Err(Box::new(ValidationError {
                 context: "color_attachments".into(),
@@ -3170,6 +3252,19 @@ impl AttachmentReference {
             }));
         }
 
+        if layout == ImageLayout::AttachmentFeedbackLoopOptimal
+            && !device.enabled_features().attachment_feedback_loop_layout
+        {
+            return Err(Box::new(ValidationError {
+                context: "layout".into(),
+                problem: "is `ImageLayout::AttachmentFeedbackLoopOptimal`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "attachment_feedback_loop_layout",
+                )])]),
+                vuids: &["VUID-VkAttachmentReference2-attachmentFeedbackLoopLayout-07311"],
+            }));
+        }
+
         if let Some(stencil_layout) = stencil_layout {
             if !device.enabled_features().separate_depth_stencil_layouts {
                 return Err(Box::new(ValidationError {
@@ -3655,8 +3750,19 @@ pub(crate) struct AttachmentUse {
 
 #[cfg(test)]
 mod tests {
-    use super::{RenderPassCreateInfo, SubpassDescription};
-    use crate::{format::Format, render_pass::RenderPass};
+    use super::{
+        AttachmentDescription, AttachmentReference, RenderPassCreateFlags, RenderPassCreateInfo,
+        SubpassDescription,
+    };
+    use crate::{
+        command_buffer::RenderPassBeginInfo,
+        format::Format,
+        image::ImageLayout,
+        pipeline::PipelineBindPoint,
+        render_pass::{Framebuffer, FramebufferCreateInfo, RenderPass},
+        swapchain::SurfaceTransform,
+        Validated,
+    };
 
     #[test]
     fn empty() {
@@ -3671,6 +3777,121 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn transform() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if !device.enabled_extensions().qcom_render_pass_transform {
+            assert!(RenderPass::new(
+                device.clone(),
+                RenderPassCreateInfo {
+                    flags: RenderPassCreateFlags::TRANSFORM,
+                    subpasses: vec![SubpassDescription::default()],
+                    ..Default::default()
+                },
+            )
+            .is_err());
+        }
+
+        let render_pass = RenderPass::new(
+            device.clone(),
+            RenderPassCreateInfo {
+                subpasses: vec![SubpassDescription::default()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                extent: [64, 64],
+                layers: 1,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        // The render pass was not created with `RenderPassCreateFlags::TRANSFORM`.
+        assert!(RenderPassBeginInfo {
+            transform: SurfaceTransform::Rotate90,
+            ..RenderPassBeginInfo::framebuffer(framebuffer.clone())
+        }
+        .validate(&device)
+        .is_err());
+        assert!(RenderPassBeginInfo::framebuffer(framebuffer)
+            .validate(&device)
+            .is_ok());
+    }
+
+    #[test]
+    fn transform_render_area() {
+        let (device, _) =
+            gfx_dev_and_queue!(extensions: [qcom_render_pass_transform], features: []);
+
+        let render_pass = RenderPass::new(
+            device.clone(),
+            RenderPassCreateInfo {
+                flags: RenderPassCreateFlags::TRANSFORM,
+                subpasses: vec![SubpassDescription::default()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let framebuffer = Framebuffer::new(
+            render_pass,
+            FramebufferCreateInfo {
+                extent: [64, 32],
+                layers: 1,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let validate = |transform, render_area_offset, render_area_extent| {
+            RenderPassBeginInfo {
+                render_area_offset,
+                render_area_extent,
+                transform,
+                ..RenderPassBeginInfo::framebuffer(framebuffer.clone())
+            }
+            .validate(&device)
+        };
+
+        assert!(validate(SurfaceTransform::Identity, [0, 0], [64, 32]).is_ok());
+        assert!(validate(SurfaceTransform::Rotate180, [0, 0], [64, 32]).is_ok());
+
+        // The width and height are swapped by a rotation of 90 or 270 degrees.
+        assert!(validate(SurfaceTransform::Rotate90, [0, 0], [32, 64]).is_ok());
+        assert!(validate(SurfaceTransform::Rotate270, [0, 0], [32, 64]).is_ok());
+
+        match validate(SurfaceTransform::Rotate90, [0, 0], [64, 32]) {
+            Err(err) => assert_eq!(err.vuids, ["VUID-VkRenderPassBeginInfo-pNext-02870"]),
+            Ok(_) => panic!(),
+        }
+
+        // The render area must cover the whole framebuffer.
+        match validate(SurfaceTransform::Identity, [0, 0], [32, 32]) {
+            Err(err) => assert_eq!(err.vuids, ["VUID-VkRenderPassBeginInfo-pNext-02870"]),
+            Ok(_) => panic!(),
+        }
+
+        match validate(SurfaceTransform::Identity, [16, 0], [48, 32]) {
+            Err(err) => assert_eq!(err.vuids, ["VUID-VkRenderPassBeginInfo-pNext-02869"]),
+            Ok(_) => panic!(),
+        }
+
+        // Only rotations are allowed.
+        match validate(SurfaceTransform::HorizontalMirror, [0, 0], [64, 32]) {
+            Err(err) => {
+                assert_eq!(
+                    err.vuids,
+                    ["VUID-VkRenderPassTransformBeginInfoQCOM-transform-02871"]
+                )
+            }
+            Ok(_) => panic!(),
+        }
+    }
+
     #[test]
     fn too_many_color_atch() {
         let (device, _) = gfx_dev_and_queue!();
@@ -3721,4 +3942,119 @@ mod tests {
         assert_ne!(granularity[0], 0);
         assert_ne!(granularity[1], 0);
     }
+
+    #[test]
+    fn read_only_depth_input_attachment() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = ordered_passes_renderpass!(
+            device,
+            attachments: {
+                color: { format: Format::R8G8B8A8_UNORM, samples: 1, load_op: Clear, store_op: Store, },
+                depth: { format: Format::D16_UNORM, samples: 1, load_op: Clear, store_op: DontCare, },
+            },
+            passes: [
+                { color: [], depth_stencil: {depth}, input: [] },
+                { color: [color], depth_stencil: {depth}, input: [depth] },
+            ],
+        )
+        .unwrap();
+
+        let subpass_desc = &rp.subpasses()[1];
+        assert_eq!(
+            subpass_desc
+                .depth_stencil_attachment
+                .as_ref()
+                .unwrap()
+                .layout,
+            ImageLayout::DepthStencilReadOnlyOptimal,
+        );
+        assert_eq!(
+            subpass_desc.input_attachments[0].as_ref().unwrap().layout,
+            ImageLayout::DepthStencilReadOnlyOptimal,
+        );
+        assert_eq!(
+            rp.subpasses()[0]
+                .depth_stencil_attachment
+                .as_ref()
+                .unwrap()
+                .layout,
+            ImageLayout::DepthStencilAttachmentOptimal,
+        );
+    }
+
+    #[test]
+    fn subpass_shading_requires_feature() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        // The device is created without the `subpass_shading` feature.
+        assert!(RenderPass::new(
+            device,
+            RenderPassCreateInfo {
+                subpasses: vec![SubpassDescription {
+                    pipeline_bind_point: PipelineBindPoint::SubpassShading,
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn attachment_feedback_loop_layout_requires_feature() {
+        // The device has the extension, but not the `attachment_feedback_loop_layout` feature.
+        let (device, _) =
+            gfx_dev_and_queue!(extensions: [ext_attachment_feedback_loop_layout], features: []);
+
+        let create_info = |attachment_layout, reference_layout| RenderPassCreateInfo {
+            attachments: vec![AttachmentDescription {
+                format: Format::R8G8B8A8_UNORM,
+                initial_layout: attachment_layout,
+                final_layout: attachment_layout,
+                ..Default::default()
+            }],
+            subpasses: vec![SubpassDescription {
+                color_attachments: vec![Some(AttachmentReference {
+                    attachment: 0,
+                    layout: reference_layout,
+                    ..Default::default()
+                })],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        match RenderPass::new(
+            device.clone(),
+            create_info(
+                ImageLayout::AttachmentFeedbackLoopOptimal,
+                ImageLayout::ColorAttachmentOptimal,
+            ),
+        ) {
+            Err(Validated::ValidationError(err)) => {
+                assert_eq!(
+                    err.vuids,
+                    ["VUID-VkAttachmentDescription2-attachmentFeedbackLoopLayout-07309"]
+                );
+            }
+            _ => panic!(),
+        }
+
+        match RenderPass::new(
+            device,
+            create_info(
+                ImageLayout::ColorAttachmentOptimal,
+                ImageLayout::AttachmentFeedbackLoopOptimal,
+            ),
+        ) {
+            Err(Validated::ValidationError(err)) => {
+                assert_eq!(
+                    err.vuids,
+                    ["VUID-VkAttachmentReference2-attachmentFeedbackLoopLayout-07311"]
+                );
+            }
+            _ => panic!(),
+        }
+    }
 }
@@ -159,6 +159,9 @@ use std::{
     sync::Arc,
 };
 
+#[cfg(feature = "macros")]
+pub use vulkano_macros::SpecializationConstants;
+
 pub mod reflect;
 pub mod spirv;
 
@@ -291,6 +294,85 @@ impl ShaderModule {
         })
     }
 
+    /// Compiles GLSL source code into SPIR-V at runtime, and creates a new shader module from the
+    /// result.
+    ///
+    /// `stage` is the shader stage that `source` is written for, and `entry_point` is the name
+    /// that the entry point will be given in the compiled module. The source is compiled for the
+    /// API version of `device`, and the resulting SPIR-V code is validated as in [`new`].
+    ///
+    /// This is an alternative to the `vulkano_shaders::shader!` macro for shaders that are only
+    /// known at runtime, such as user-provided or hot-reloaded shaders.
+    ///
+    /// If the source code fails to compile, [`GlslCompileError::Compile`] is returned with the
+    /// messages of the compiler.
+    ///
+    /// The `runtime-shader-compilation` feature must be enabled on Vulkano.
+    ///
+    /// [`new`]: Self::new
+    #[cfg(feature = "runtime-shader-compilation")]
+    pub fn from_glsl(
+        device: Arc<Device>,
+        source: &str,
+        stage: ShaderStage,
+        entry_point: &str,
+    ) -> Result<Arc<ShaderModule>, Validated<GlslCompileError>> {
+        use shaderc::{CompileOptions, Compiler, EnvVersion, ShaderKind, TargetEnv};
+
+        let shader_kind = match stage {
+            ShaderStage::Vertex => ShaderKind::Vertex,
+            ShaderStage::TessellationControl => ShaderKind::TessControl,
+            ShaderStage::TessellationEvaluation => ShaderKind::TessEvaluation,
+            ShaderStage::Geometry => ShaderKind::Geometry,
+            ShaderStage::Fragment => ShaderKind::Fragment,
+            ShaderStage::Compute => ShaderKind::Compute,
+            ShaderStage::Raygen => ShaderKind::RayGeneration,
+            ShaderStage::AnyHit => ShaderKind::AnyHit,
+            ShaderStage::ClosestHit => ShaderKind::ClosestHit,
+            ShaderStage::Miss => ShaderKind::Miss,
+            ShaderStage::Intersection => ShaderKind::Intersection,
+            ShaderStage::Callable => ShaderKind::Callable,
+            ShaderStage::Task => ShaderKind::Task,
+            ShaderStage::Mesh => ShaderKind::Mesh,
+            ShaderStage::SubpassShading => {
+                return Err(Box::new(ValidationError {
+                    context: "stage".into(),
+                    problem: "is `ShaderStage::SubpassShading`, which cannot be compiled from GLSL"
+                        .into(),
+                    ..Default::default()
+                })
+                .into());
+            }
+        };
+
+        let env_version = match device.api_version() {
+            version if version >= Version::V1_3 => EnvVersion::Vulkan1_3,
+            version if version >= Version::V1_2 => EnvVersion::Vulkan1_2,
+            version if version >= Version::V1_1 => EnvVersion::Vulkan1_1,
+            _ => EnvVersion::Vulkan1_0,
+        };
+
+        let compiler = Compiler::new().ok_or(GlslCompileError::CompilerUnavailable)?;
+        let mut compile_options =
+            CompileOptions::new().ok_or(GlslCompileError::CompilerUnavailable)?;
+        compile_options.set_target_env(TargetEnv::Vulkan, env_version as u32);
+
+        let artifact = compiler
+            .compile_into_spirv(
+                source,
+                shader_kind,
+                "shader.glsl",
+                entry_point,
+                Some(&compile_options),
+            )
+            .map_err(|err| GlslCompileError::Compile(err.to_string()))?;
+
+        // SAFETY: The SPIR-V code was produced by the GLSL compiler, and is parsed and validated
+        // by `new` before creating the module.
+        unsafe { Self::new(device, ShaderModuleCreateInfo::new(artifact.as_binary())) }
+            .map_err(|err| err.map(GlslCompileError::CreateShaderModule))
+    }
+
     /// Builds a new shader module from SPIR-V 32-bit words. The shader code is parsed and the
     /// necessary information is extracted from it.
     ///
@@ -335,6 +417,16 @@ impl ShaderModule {
         &self.specialization_constants
     }
 
+    /// Returns the `constant_id`s of the specialization constants that make up the `LocalSizeId`
+    /// execution mode of an entry point.
+    pub(crate) fn local_size_id_constant_ids(
+        &self,
+        name: &str,
+        execution: ExecutionModel,
+    ) -> Option<[Option<u32>; 3]> {
+        reflect::local_size_id_constant_ids(&self.spirv, name, execution)
+    }
+
     /// Applies the specialization constants to the shader module,
     /// and returns a specialized version of the module.
     ///
@@ -352,6 +444,39 @@ impl ShaderModule {
         SpecializedShaderModule::new(self.clone(), specialization_info)
     }
 
+    /// Applies the specialization constants provided by a [`SpecializationConstants`] value to
+    /// the shader module, and returns a specialized version of the module.
+    ///
+    /// Unlike [`specialize`], this requires every constant provided by `constants` to be declared
+    /// in the shader, so that a mismatch between the Rust type and the shader is reported rather
+    /// than silently ignored.
+    ///
+    /// [`specialize`]: Self::specialize
+    pub fn specialize_with(
+        self: &Arc<Self>,
+        constants: &impl SpecializationConstants,
+    ) -> Result<Arc<SpecializedShaderModule>, Box<ValidationError>> {
+        let specialization_info: HashMap<_, _> =
+            constants.specialization_constants().into_iter().collect();
+
+        for &constant_id in specialization_info.keys() {
+            if !self.specialization_constants.contains_key(&constant_id) {
+                return Err(Box::new(ValidationError {
+                    context: "constants".into(),
+                    problem: format!(
+                        "provides a value for the specialization constant with `constant_id` {}, \
+                        but the shader module does not declare such a specialization constant",
+                        constant_id,
+                    )
+                    .into(),
+                    ..Default::default()
+                }));
+            }
+        }
+
+        SpecializedShaderModule::new(self.clone(), specialization_info)
+    }
+
     #[cfg_attr(not(feature = "document_unchecked"), doc(hidden))]
     #[inline]
     pub unsafe fn specialize_unchecked(
@@ -445,6 +570,50 @@ unsafe impl DeviceOwned for ShaderModule {
 
 impl_id_counter!(ShaderModule);
 
+/// Error that can happen when compiling GLSL source code with [`ShaderModule::from_glsl`].
+#[cfg(feature = "runtime-shader-compilation")]
+#[derive(Clone, Debug)]
+pub enum GlslCompileError {
+    /// The GLSL compiler could not be initialized.
+    CompilerUnavailable,
+
+    /// The source code could not be compiled. Contains the messages of the compiler.
+    Compile(String),
+
+    /// Creating the shader module from the compiled SPIR-V code failed.
+    CreateShaderModule(VulkanError),
+}
+
+#[cfg(feature = "runtime-shader-compilation")]
+impl std::error::Error for GlslCompileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::CreateShaderModule(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "runtime-shader-compilation")]
+impl std::fmt::Display for GlslCompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CompilerUnavailable => write!(f, "the GLSL compiler could not be initialized"),
+            Self::Compile(messages) => {
+                write!(f, "compiling the GLSL source code failed: {}", messages)
+            }
+            Self::CreateShaderModule(_) => write!(f, "creating the shader module failed"),
+        }
+    }
+}
+
+#[cfg(feature = "runtime-shader-compilation")]
+impl From<GlslCompileError> for Validated<GlslCompileError> {
+    fn from(err: GlslCompileError) -> Self {
+        Self::Error(err)
+    }
+}
+
 pub struct ShaderModuleCreateInfo<'a> {
     /// The SPIR-V code, in the form of 32-bit words.
     ///
@@ -556,6 +725,45 @@ impl<'a> ShaderModuleCreateInfo<'a> {
             validate_spirv_extension(device, extension).map_err(|err| err.add_context("code"))?;
         }
 
+        for instruction in spirv
+            .iter_functions()
+            .flat_map(|function| function.iter_instructions())
+        {
+            if let Instruction::ReadClockKHR { scope, .. } = *instruction {
+                // Scopes must be constants in Vulkan, so this only fails if the code is invalid.
+                let scope = match *spirv.id(scope).instruction() {
+                    Instruction::Constant { ref value, .. } => value[0],
+                    _ => continue,
+                };
+
+                match scope {
+                    // `Scope::Device`
+                    1 if !device.enabled_features().shader_device_clock => {
+                        return Err(Box::new(ValidationError {
+                            context: "code".into(),
+                            problem: "uses `OpReadClockKHR` with the `Device` scope".into(),
+                            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                                "shader_device_clock",
+                            )])]),
+                            vuids: &["VUID-RuntimeSpirv-shaderDeviceClock-06268"],
+                        }));
+                    }
+                    // `Scope::Subgroup`
+                    3 if !device.enabled_features().shader_subgroup_clock => {
+                        return Err(Box::new(ValidationError {
+                            context: "code".into(),
+                            problem: "uses `OpReadClockKHR` with the `Subgroup` scope".into(),
+                            requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                                "shader_subgroup_clock",
+                            )])]),
+                            vuids: &["VUID-RuntimeSpirv-shaderSubgroupClock-06267"],
+                        }));
+                    }
+                    _ => (),
+                }
+            }
+        }
+
         // VUID-VkShaderModuleCreateInfo-pCode-08736
         // VUID-VkShaderModuleCreateInfo-pCode-08737
         // VUID-VkShaderModuleCreateInfo-pCode-08738
@@ -565,6 +773,30 @@ impl<'a> ShaderModuleCreateInfo<'a> {
     }
 }
 
+/// A type whose values provide the specialization constants of a shader.
+///
+/// This trait can be derived for structs with named fields. Each field must have a
+/// `#[constant_id(...)]` attribute, and a type that can be converted into a
+/// [`SpecializationConstant`]:
+///
+/// ```
+/// use vulkano::shader::SpecializationConstants;
+///
+/// #[derive(Clone, Copy, SpecializationConstants)]
+/// struct MyConstants {
+///     #[constant_id(0)]
+///     tile_size: u32,
+///     #[constant_id(1)]
+///     enable_shadows: bool,
+/// }
+/// ```
+///
+/// The value can then be passed to [`ShaderModule::specialize_with`].
+pub trait SpecializationConstants {
+    /// Returns the `constant_id` and value of each specialization constant provided by `self`.
+    fn specialization_constants(&self) -> Vec<(u32, SpecializationConstant)>;
+}
+
 /// The value to provide for a specialization constant, when creating a pipeline.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum SpecializationConstant {
@@ -699,7 +931,7 @@ impl From<f64> for SpecializationConstant {
 pub struct SpecializedShaderModule {
     base_module: Arc<ShaderModule>,
     specialization_info: HashMap<u32, SpecializationConstant>,
-    _spirv: Option<Spirv>,
+    spirv: Option<Spirv>,
     entry_point_infos: SmallVec<[EntryPointInfo; 1]>,
 }
 
@@ -760,7 +992,7 @@ impl SpecializedShaderModule {
         Arc::new(Self {
             base_module,
             specialization_info,
-            _spirv: spirv,
+            spirv,
             entry_point_infos,
         })
     }
@@ -777,6 +1009,20 @@ impl SpecializedShaderModule {
         &self.specialization_info
     }
 
+    /// Returns the number of clip and cull distances that an entry point declares, as
+    /// `(clip, cull)` pairs for its input and output interface respectively.
+    pub(crate) fn clip_cull_distance_counts(
+        &self,
+        name: &str,
+        execution: ExecutionModel,
+    ) -> [(u32, u32); 2] {
+        reflect::clip_cull_distance_counts(
+            self.spirv.as_ref().unwrap_or(&self.base_module.spirv),
+            name,
+            execution,
+        )
+    }
+
     /// Returns information about the entry point with the provided name. Returns `None` if no entry
     /// point with that name exists in the shader module or if multiple entry points with the same
     /// name exist.
@@ -884,6 +1130,88 @@ impl EntryPoint {
     pub fn info(&self) -> &EntryPointInfo {
         &self.module.entry_point_infos[self.info_index]
     }
+
+    /// Returns the range of push constants that the entry point declares, if any.
+    ///
+    /// This is a shortcut for `self.info().push_constant_requirements`. It can be used to fill
+    /// [`PipelineLayoutCreateInfo::push_constant_ranges`] so that the pipeline layout matches the
+    /// shader.
+    ///
+    /// [`PipelineLayoutCreateInfo::push_constant_ranges`]: crate::pipeline::layout::PipelineLayoutCreateInfo::push_constant_ranges
+    #[inline]
+    pub fn push_constant_requirements(&self) -> Option<PushConstantRange> {
+        self.info().push_constant_requirements
+    }
+
+    /// Returns the same entry point, with the provided specialization constants applied in
+    /// addition to those that were already applied to its module.
+    ///
+    /// Every constant in `constants` must be declared in the shader module, and must have the
+    /// same type as in the shader (as returned by [`ShaderModule::specialization_constants`]).
+    ///
+    /// ```no_run
+    /// # use vulkano::shader::{EntryPoint, SpecializationConstant};
+    /// # let entry_point: EntryPoint = return;
+    /// let entry_point = entry_point
+    ///     .specialize([
+    ///         (0, SpecializationConstant::U32(64)),
+    ///         (1, SpecializationConstant::Bool(true)),
+    ///     ])
+    ///     .unwrap();
+    /// ```
+    pub fn specialize(
+        &self,
+        constants: impl IntoIterator<Item = (u32, SpecializationConstant)>,
+    ) -> Result<EntryPoint, Box<ValidationError>> {
+        let base_module = self.module.base_module();
+        let mut specialization_info = self.module.specialization_info().clone();
+
+        for (constant_id, value) in constants {
+            let default_value = base_module
+                .specialization_constants
+                .get(&constant_id)
+                .ok_or_else(|| {
+                    Box::new(ValidationError {
+                        context: "constants".into(),
+                        problem: format!(
+                            "provides a value for the specialization constant with \
+                            `constant_id` {}, but the shader module does not declare such a \
+                            specialization constant",
+                            constant_id,
+                        )
+                        .into(),
+                        ..Default::default()
+                    })
+                })?;
+
+            if !value.eq_type(default_value) {
+                return Err(Box::new(ValidationError {
+                    context: "constants".into(),
+                    problem: format!(
+                        "the value provided for the specialization constant with `constant_id` \
+                        {} does not have the same type as the specialization constant in the \
+                        shader module",
+                        constant_id,
+                    )
+                    .into(),
+                    vuids: &["VUID-VkSpecializationMapEntry-constantID-00776"],
+                    ..Default::default()
+                }));
+            }
+
+            specialization_info.insert(constant_id, value);
+        }
+
+        let info = self.info();
+
+        unsafe {
+            Ok(
+                SpecializedShaderModule::new_unchecked(base_module.clone(), specialization_info)
+                    .entry_point_with_execution(&info.name, (&info.execution).into())
+                    .unwrap(),
+            )
+        }
+    }
 }
 
 /// The mode in which a shader executes. This includes both information about the shader type/stage,
@@ -1528,3 +1856,266 @@ impl From<ShaderStages> for PipelineStages {
         result
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ComputeShaderExecution, EntryPoint, ShaderExecution, ShaderModule, ShaderModuleCreateInfo,
+        ShaderStages, SpecializationConstant, SpecializationConstants,
+    };
+    use crate::{device::Device, pipeline::layout::PushConstantRange, Validated, Version};
+    use std::sync::Arc;
+
+    #[test]
+    fn derive_specialization_constants() {
+        #[derive(Clone, Copy, SpecializationConstants)]
+        struct TestConstants {
+            #[constant_id(3)]
+            tile_size: u32,
+            #[constant_id(1)]
+            enabled: bool,
+        }
+
+        let constants = TestConstants {
+            tile_size: 16,
+            enabled: true,
+        }
+        .specialization_constants();
+
+        assert_eq!(
+            constants,
+            [
+                (3, SpecializationConstant::U32(16)),
+                (1, SpecializationConstant::Bool(true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn specialize_with_undeclared_constant() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        /*
+        #version 450
+
+        layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+        layout(constant_id = 83) const int VALUE = 0xdeadbeef;
+
+        layout(set = 0, binding = 0) buffer Output {
+            int write;
+        } write;
+
+        void main() {
+            write.write = VALUE;
+        }
+        */
+        const MODULE: [u32; 120] = [
+            119734787, 65536, 524289, 14, 0, 131089, 1, 393227, 1, 1280527431, 1685353262,
+            808793134, 0, 196622, 0, 1, 327695, 5, 4, 1852399981, 0, 393232, 4, 17, 1, 1, 1,
+            196611, 2, 450, 262149, 4, 1852399981, 0, 262149, 7, 1886680399, 29813, 327686, 7, 0,
+            1953067639, 101, 262149, 9, 1953067639, 101, 262149, 11, 1431060822, 69, 327752, 7, 0,
+            35, 0, 196679, 7, 3, 262215, 9, 34, 0, 262215, 9, 33, 0, 262215, 11, 1, 83, 131091, 2,
+            196641, 3, 2, 262165, 6, 32, 1, 196638, 7, 6, 262176, 8, 2, 7, 262203, 8, 9, 2, 262187,
+            6, 10, 0, 262194, 6, 11, 3735928559, 262176, 12, 2, 6, 327734, 2, 4, 0, 3, 131320, 5,
+            327745, 12, 13, 9, 10, 196670, 13, 11, 65789, 65592,
+        ];
+        let module =
+            unsafe { ShaderModule::new(device, ShaderModuleCreateInfo::new(&MODULE)).unwrap() };
+
+        #[derive(Clone, Copy, SpecializationConstants)]
+        struct Declared {
+            #[constant_id(83)]
+            value: i32,
+        }
+
+        #[derive(Clone, Copy, SpecializationConstants)]
+        struct Undeclared {
+            #[constant_id(84)]
+            value: i32,
+        }
+
+        #[derive(Clone, Copy, SpecializationConstants)]
+        struct WrongType {
+            #[constant_id(83)]
+            value: u32,
+        }
+
+        assert!(module.specialize_with(&Declared { value: 42 }).is_ok());
+        assert!(module.specialize_with(&Undeclared { value: 42 }).is_err());
+        assert!(module.specialize_with(&WrongType { value: 42 }).is_err());
+    }
+
+    #[test]
+    fn shader_clock_features() {
+        // Only the subgroup clock is enabled.
+        let (device, _) = gfx_dev_and_queue!(
+            extensions: [khr_shader_clock],
+            features: [shader_subgroup_clock],
+        );
+
+        // A compute shader that reads the clock with the given scope into a `uvec2`.
+        let module = |scope: u32| {
+            let code = [
+                119734787, 65536, 0, 9, 0, 131089, 1, 131089, 5055, 458762, 1599492179, 1599227979,
+                1684105331, 1667199589, 1801678700, 0, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+                393232, 1, 17, 1, 1, 1, 131091, 2, 196641, 3, 2, 262165, 4, 32, 0, 262167, 5, 4, 2,
+                262187, 4, 6, scope, 327734, 2, 1, 0, 3, 131320, 7, 267200, 5, 8, 6, 65789, 65592,
+            ];
+
+            unsafe { ShaderModule::new(device.clone(), ShaderModuleCreateInfo::new(&code)) }
+        };
+
+        // `Scope::Subgroup`
+        assert!(module(3).is_ok());
+        // `Scope::Device`
+        assert!(matches!(module(1), Err(Validated::ValidationError(_))));
+    }
+
+    #[test]
+    fn non_uniform_indexing_features() {
+        let (device, _) = gfx_dev_and_queue!(shader_sampled_image_array_non_uniform_indexing);
+        let (device_without_feature, _) = gfx_dev_and_queue!();
+
+        if device.api_version() < Version::V1_2
+            || device_without_feature.api_version() < Version::V1_2
+        {
+            return;
+        }
+
+        // A compute shader that declares the `ShaderNonUniform` and
+        // `SampledImageArrayNonUniformIndexing` capabilities.
+        const MODULE: [u32; 47] = [
+            119734787, 65536, 0, 5, 0, 131089, 1, 131089, 5301, 131089, 5307, 524298, 1599492179,
+            1599363141, 1668506980, 1953524082, 1767862895, 2019910766, 6778473, 196622, 0, 1,
+            327695, 5, 1, 1852399981, 0, 393232, 1, 17, 1, 1, 1, 131091, 2, 196641, 3, 2, 327734,
+            2, 1, 0, 3, 131320, 4, 65789, 65592,
+        ];
+
+        let module = |device: Arc<Device>| unsafe {
+            ShaderModule::new(device, ShaderModuleCreateInfo::new(&MODULE))
+        };
+
+        match module(device_without_feature) {
+            Err(Validated::ValidationError(err)) => {
+                assert!(err.problem.contains("SampledImageArrayNonUniformIndexing"))
+            }
+            _ => panic!(),
+        }
+        assert!(module(device).is_ok());
+    }
+
+    #[test]
+    fn entry_point_specialize_local_size() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        /*
+        #version 450
+
+        layout(local_size_x_id = 0) in;
+
+        void main() {
+        }
+        */
+        const MODULE: [u32; 65] = [
+            119734787, 65536, 0, 10, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+            393232, 1, 17, 1, 1, 1, 262215, 6, 1, 0, 262215, 8, 11, 25, 131091, 2, 196641, 3, 2,
+            262165, 4, 32, 0, 262167, 5, 4, 3, 262194, 4, 6, 1, 262187, 4, 7, 1, 393267, 5, 8, 6,
+            7, 7, 327734, 2, 1, 0, 3, 131320, 9, 65789, 65592,
+        ];
+        let module =
+            unsafe { ShaderModule::new(device, ShaderModuleCreateInfo::new(&MODULE)).unwrap() };
+        let entry_point = module.entry_point("main").unwrap();
+
+        let local_size = |entry_point: &EntryPoint| match entry_point.info().execution {
+            ShaderExecution::Compute(ComputeShaderExecution { local_size }) => local_size,
+            _ => unreachable!(),
+        };
+        assert_eq!(local_size(&entry_point), [1, 1, 1]);
+
+        let specialized = entry_point
+            .specialize([(0, SpecializationConstant::U32(64))])
+            .unwrap();
+        assert_eq!(local_size(&specialized), [64, 1, 1]);
+        assert_eq!(
+            specialized.module().specialization_info()[&0],
+            SpecializationConstant::U32(64),
+        );
+
+        // The constant must be declared in the shader, and have the same type.
+        assert!(entry_point
+            .specialize([(1, SpecializationConstant::U32(64))])
+            .is_err());
+        assert!(entry_point
+            .specialize([(0, SpecializationConstant::Bool(true))])
+            .is_err());
+    }
+
+    #[test]
+    fn entry_point_push_constant_requirements() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        /*
+        #version 450
+
+        layout(local_size_x = 1, local_size_y = 1, local_size_z = 1) in;
+
+        layout(push_constant) uniform PushConstants {
+            mat4 transform;
+        };
+
+        void main() {
+        }
+        */
+        const MODULE: [u32; 74] = [
+            119734787, 65536, 0, 11, 0, 131089, 1, 196622, 0, 1, 327695, 5, 1, 1852399981, 0,
+            393232, 1, 17, 1, 1, 1, 327752, 7, 0, 35, 0, 262216, 7, 0, 5, 327752, 7, 0, 7, 16,
+            196679, 7, 2, 131091, 2, 196641, 3, 2, 196630, 4, 32, 262167, 5, 4, 4, 262168, 6, 5, 4,
+            196638, 7, 6, 262176, 8, 9, 7, 262203, 8, 9, 9, 327734, 2, 1, 0, 3, 131320, 10, 65789,
+            65592,
+        ];
+        let module =
+            unsafe { ShaderModule::new(device, ShaderModuleCreateInfo::new(&MODULE)).unwrap() };
+        let entry_point = module.entry_point("main").unwrap();
+
+        assert_eq!(
+            entry_point.push_constant_requirements(),
+            Some(PushConstantRange {
+                stages: ShaderStages::COMPUTE,
+                offset: 0,
+                size: 64,
+            }),
+        );
+    }
+
+    #[cfg(feature = "runtime-shader-compilation")]
+    #[test]
+    fn from_glsl() {
+        use super::{GlslCompileError, ShaderStage};
+
+        let (device, _) = gfx_dev_and_queue!();
+
+        let module = ShaderModule::from_glsl(
+            device.clone(),
+            "#version 450\nlayout(local_size_x = 1) in;\nvoid main() {}",
+            ShaderStage::Compute,
+            "main",
+        )
+        .unwrap();
+        let entry_point = module.entry_point("main").unwrap();
+        assert!(matches!(
+            entry_point.info().execution,
+            ShaderExecution::Compute(_)
+        ));
+
+        match ShaderModule::from_glsl(
+            device,
+            "#version 450\nvoid main() { undeclared = 1; }",
+            ShaderStage::Compute,
+            "main",
+        ) {
+            Err(Validated::Error(GlslCompileError::Compile(_))) => (),
+            _ => panic!(),
+        }
+    }
+}
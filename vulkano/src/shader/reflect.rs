@@ -1139,6 +1139,195 @@ pub(super) fn specialization_constants(spirv: &Spirv) -> HashMap<u32, Specializa
         .collect()
 }
 
+/// Returns the `constant_id`s of the specialization constants that make up the `LocalSizeId`
+/// execution mode of the entry point named `name` with the given execution model.
+///
+/// Returns `None` if the entry point doesn't exist, doesn't use `LocalSizeId`, or if its local
+/// size is overridden by a `WorkgroupSize` built-in. A component is `None` if it is not a
+/// specialization constant.
+pub(super) fn local_size_id_constant_ids(
+    spirv: &Spirv,
+    name: &str,
+    execution_model: ExecutionModel,
+) -> Option<[Option<u32>; 3]> {
+    let function_id = spirv
+        .iter_entry_point()
+        .find_map(|instruction| match *instruction {
+            Instruction::EntryPoint {
+                execution_model: entry_point_execution_model,
+                entry_point,
+                name: ref entry_point_name,
+                ..
+            } if entry_point_execution_model == execution_model && entry_point_name == name => {
+                Some(entry_point)
+            }
+            _ => None,
+        })?;
+
+    let has_workgroup_size = spirv.iter_decoration().any(|instruction| {
+        matches!(
+            instruction,
+            Instruction::Decorate {
+                decoration: Decoration::BuiltIn {
+                    built_in: BuiltIn::WorkgroupSize,
+                },
+                ..
+            }
+        )
+    });
+
+    if has_workgroup_size {
+        return None;
+    }
+
+    spirv
+        .iter_execution_mode()
+        .find_map(|instruction| match *instruction {
+            Instruction::ExecutionModeId {
+                entry_point,
+                mode:
+                    ExecutionMode::LocalSizeId {
+                        x_size,
+                        y_size,
+                        z_size,
+                    },
+            } if entry_point == function_id => Some([x_size, y_size, z_size]),
+            _ => None,
+        })
+        .map(|ids| {
+            ids.map(|id| {
+                let id_info = spirv.id(id);
+
+                if !matches!(id_info.instruction(), Instruction::SpecConstant { .. }) {
+                    return None;
+                }
+
+                id_info
+                    .iter_decoration()
+                    .find_map(|instruction| match *instruction {
+                        Instruction::Decorate {
+                            decoration:
+                                Decoration::SpecId {
+                                    specialization_constant_id,
+                                },
+                            ..
+                        } => Some(specialization_constant_id),
+                        _ => None,
+                    })
+            })
+        })
+}
+
+/// Returns the number of clip and cull distances that an entry point declares, as
+/// `(clip, cull)` pairs for its input and output interface respectively.
+pub(super) fn clip_cull_distance_counts(
+    spirv: &Spirv,
+    name: &str,
+    execution_model: ExecutionModel,
+) -> [(u32, u32); 2] {
+    let interface = spirv
+        .iter_entry_point()
+        .find_map(|instruction| match *instruction {
+            Instruction::EntryPoint {
+                execution_model: entry_point_execution_model,
+                name: ref entry_point_name,
+                ref interface,
+                ..
+            } if entry_point_execution_model == execution_model && entry_point_name == name => {
+                Some(interface)
+            }
+            _ => None,
+        });
+
+    let mut counts = [(0, 0); 2];
+
+    for &id in interface.into_iter().flatten() {
+        let (result_type_id, storage_class) = match *spirv.id(id).instruction() {
+            Instruction::Variable {
+                result_type_id,
+                storage_class,
+                ..
+            } => (result_type_id, storage_class),
+            _ => continue,
+        };
+
+        // Per-vertex inputs and outputs have an extra outer array.
+        let (counts, per_vertex) = match storage_class {
+            StorageClass::Input => (
+                &mut counts[0],
+                matches!(
+                    execution_model,
+                    ExecutionModel::TessellationControl
+                        | ExecutionModel::TessellationEvaluation
+                        | ExecutionModel::Geometry
+                ),
+            ),
+            StorageClass::Output => (
+                &mut counts[1],
+                matches!(execution_model, ExecutionModel::TessellationControl),
+            ),
+            _ => continue,
+        };
+
+        let mut type_id = match *spirv.id(result_type_id).instruction() {
+            Instruction::TypePointer { ty, .. } => ty,
+            _ => continue,
+        };
+
+        if per_vertex {
+            if let Instruction::TypeArray { element_type, .. } = *spirv.id(type_id).instruction() {
+                type_id = element_type;
+            }
+        }
+
+        let mut add = |built_in: BuiltIn, type_id: Id| {
+            let count = match built_in {
+                BuiltIn::ClipDistance => &mut counts.0,
+                BuiltIn::CullDistance => &mut counts.1,
+                _ => return,
+            };
+
+            if let Instruction::TypeArray { length, .. } = *spirv.id(type_id).instruction() {
+                if let Instruction::Constant { ref value, .. } = *spirv.id(length).instruction() {
+                    *count += value[0];
+                }
+            }
+        };
+
+        // The built-in is either decorated on the variable itself, or on a member of a block.
+        for instruction in spirv.id(id).iter_decoration() {
+            if let Instruction::Decorate {
+                decoration: Decoration::BuiltIn { built_in },
+                ..
+            } = *instruction
+            {
+                add(built_in, type_id);
+            }
+        }
+
+        let type_id_info = spirv.id(type_id);
+
+        if let Instruction::TypeStruct {
+            ref member_types, ..
+        } = *type_id_info.instruction()
+        {
+            for (member_info, &member_type) in type_id_info.iter_members().zip(member_types) {
+                for instruction in member_info.iter_decoration() {
+                    if let Instruction::MemberDecorate {
+                        decoration: Decoration::BuiltIn { built_in },
+                        ..
+                    } = *instruction
+                    {
+                        add(built_in, member_type);
+                    }
+                }
+            }
+        }
+    }
+
+    counts
+}
+
 /// Extracts the `ShaderInterface` with the given storage class from `spirv`.
 fn shader_interface(
     spirv: &Spirv,
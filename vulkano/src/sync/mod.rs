@@ -21,8 +21,9 @@ pub(crate) use self::pipeline::{PipelineStageAccess, PipelineStageAccessFlags};
 pub use self::{
     future::{now, GpuFuture},
     pipeline::{
-        AccessFlags, BufferMemoryBarrier, DependencyFlags, DependencyInfo, ImageMemoryBarrier,
-        MemoryBarrier, PipelineStage, PipelineStages, QueueFamilyOwnershipTransfer,
+        AccessFlags, BarrierBatch, BufferMemoryBarrier, DependencyFlags, DependencyInfo,
+        ImageMemoryBarrier, MemoryBarrier, PipelineStage, PipelineStages,
+        QueueFamilyOwnershipTransfer,
     },
 };
 use crate::{device::Queue, VulkanError};
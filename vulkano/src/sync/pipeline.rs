@@ -16,7 +16,7 @@ use crate::{
     },
     macros::{vulkan_bitflags, vulkan_bitflags_enum},
     shader::ShaderStages,
-    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError,
+    DeviceSize, Requires, RequiresAllOf, RequiresOneOf, ValidationError, VulkanObject,
 };
 use ahash::HashMap;
 use once_cell::sync::Lazy;
@@ -985,7 +985,8 @@ impl From<PipelineStages> for AccessFlags {
                 | PipelineStages::COMPUTE_SHADER
                 | PipelineStages::RAY_TRACING_SHADER
                 | PipelineStages::TASK_SHADER
-                | PipelineStages::MESH_SHADER,
+                | PipelineStages::MESH_SHADER
+                | PipelineStages::SUBPASS_SHADING,
         ) {
             result |= AccessFlags::SHADER_READ
                 | AccessFlags::UNIFORM_READ
@@ -1396,6 +1397,10 @@ pipeline_stage_access! {
     MeshShader_ShaderStorageRead, MESH_SHADER, SHADER_STORAGE_READ;
     MeshShader_ShaderStorageWrite, MESH_SHADER, SHADER_STORAGE_WRITE;
     MeshShader_AccelerationStructureRead, MESH_SHADER, ACCELERATION_STRUCTURE_READ;
+    SubpassShading_UniformRead, SUBPASS_SHADING, UNIFORM_READ;
+    SubpassShading_ShaderSampledRead, SUBPASS_SHADING, SHADER_SAMPLED_READ;
+    SubpassShading_ShaderStorageRead, SUBPASS_SHADING, SHADER_STORAGE_READ;
+    SubpassShading_ShaderStorageWrite, SUBPASS_SHADING, SHADER_STORAGE_WRITE;
     SubpassShading_InputAttachmentRead, SUBPASS_SHADING, INPUT_ATTACHMENT_READ;
     InvocationMask_InvocationMaskRead, INVOCATION_MASK, INVOCATION_MASK_READ;
     AccelerationStructureCopy_AccelerationStructureRead, ACCELERATION_STRUCTURE_COPY, ACCELERATION_STRUCTURE_READ;
@@ -1462,6 +1467,10 @@ impl PipelineStageAccess {
                             PipelineStage::MeshShader,
                             PipelineStageAccess::MeshShader_UniformRead,
                         ),
+                        (
+                            PipelineStage::SubpassShading,
+                            PipelineStageAccess::SubpassShading_UniformRead,
+                        ),
                     ]
                     .into_iter()
                     .collect(),
@@ -1514,6 +1523,10 @@ impl PipelineStageAccess {
                             PipelineStage::MeshShader,
                             PipelineStageAccess::MeshShader_ShaderSampledRead,
                         ),
+                        (
+                            PipelineStage::SubpassShading,
+                            PipelineStageAccess::SubpassShading_ShaderSampledRead,
+                        ),
                     ]
                     .into_iter()
                     .collect(),
@@ -1567,6 +1580,10 @@ impl PipelineStageAccess {
                             PipelineStage::MeshShader,
                             PipelineStageAccess::MeshShader_ShaderStorageRead,
                         ),
+                        (
+                            PipelineStage::SubpassShading,
+                            PipelineStageAccess::SubpassShading_ShaderStorageRead,
+                        ),
                     ]
                     .into_iter()
                     .collect(),
@@ -1644,6 +1661,10 @@ impl PipelineStageAccess {
                             PipelineStage::MeshShader,
                             PipelineStageAccess::MeshShader_ShaderStorageWrite,
                         ),
+                        (
+                            PipelineStage::SubpassShading,
+                            PipelineStageAccess::SubpassShading_ShaderStorageWrite,
+                        ),
                     ]
                     .into_iter()
                     .collect(),
@@ -1707,6 +1728,7 @@ impl PipelineStageAccessFlags {
                 | PipelineStageAccessFlags::CommandPreprocess_CommandPreprocessWrite
                 | PipelineStageAccessFlags::TaskShader_ShaderStorageWrite
                 | PipelineStageAccessFlags::MeshShader_ShaderStorageWrite
+                | PipelineStageAccessFlags::SubpassShading_ShaderStorageWrite
                 | PipelineStageAccessFlags::AccelerationStructureCopy_AccelerationStructureWrite
                 | PipelineStageAccessFlags::AccelerationStructureCopy_TransferWrite
                 | PipelineStageAccessFlags::OpticalFlow_OpticalFlowWrite
@@ -1813,6 +1835,118 @@ impl Default for DependencyInfo {
     }
 }
 
+/// A batch of pipeline barriers, that are combined into as few [`DependencyInfo`]s as possible,
+/// so that they can be recorded with few pipeline barrier commands.
+///
+/// Barriers are added to the same `DependencyInfo` as the barriers that were added before them,
+/// unless they may apply to the same memory as one of those barriers. All barriers in a single
+/// pipeline barrier command are executed at the same time, so such a barrier instead starts a new
+/// `DependencyInfo`, to preserve the ordering between the two. This is the case for:
+/// - image memory barriers for overlapping subresources of the same image, for example two
+///   layout transitions;
+/// - buffer memory barriers for overlapping ranges of the same buffer;
+/// - global memory barriers, which apply to all memory.
+///
+/// The barriers are recorded with [`pipeline_barrier_batch`].
+///
+/// [`pipeline_barrier_batch`]: crate::command_buffer::sys::UnsafeCommandBufferBuilder::pipeline_barrier_batch
+#[derive(Clone, Debug, Default)]
+pub struct BarrierBatch {
+    dependency_infos: SmallVec<[DependencyInfo; 1]>,
+}
+
+impl BarrierBatch {
+    /// Returns a new, empty `BarrierBatch`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether the batch contains any barriers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.dependency_infos.is_empty()
+    }
+
+    /// Returns the `DependencyInfo`s that the barriers were combined into, in the order they must
+    /// be recorded.
+    #[inline]
+    pub fn dependency_infos(&self) -> &[DependencyInfo] {
+        &self.dependency_infos
+    }
+
+    /// Adds a global memory barrier to the batch.
+    pub fn memory_barrier(&mut self, barrier: MemoryBarrier) -> &mut Self {
+        self.current(|dependency_info| !dependency_info.is_empty())
+            .memory_barriers
+            .push(barrier);
+
+        self
+    }
+
+    /// Adds a buffer memory barrier to the batch.
+    pub fn buffer_memory_barrier(&mut self, barrier: BufferMemoryBarrier) -> &mut Self {
+        self.current(|dependency_info| {
+            !dependency_info.memory_barriers.is_empty()
+                || dependency_info.buffer_memory_barriers.iter().any(|other| {
+                    other.buffer.handle() == barrier.buffer.handle()
+                        && other.range.start < barrier.range.end
+                        && barrier.range.start < other.range.end
+                })
+        })
+        .buffer_memory_barriers
+        .push(barrier);
+
+        self
+    }
+
+    /// Adds an image memory barrier to the batch.
+    pub fn image_memory_barrier(&mut self, barrier: ImageMemoryBarrier) -> &mut Self {
+        self.current(|dependency_info| {
+            !dependency_info.memory_barriers.is_empty()
+                || dependency_info.image_memory_barriers.iter().any(|other| {
+                    other.image.handle() == barrier.image.handle()
+                        && subresource_ranges_overlap(
+                            &other.subresource_range,
+                            &barrier.subresource_range,
+                        )
+                })
+        })
+        .image_memory_barriers
+        .push(barrier);
+
+        self
+    }
+
+    // Returns the `DependencyInfo` to add a barrier to, starting a new one if the barrier
+    // conflicts with the last one.
+    fn current(&mut self, conflicts: impl FnOnce(&DependencyInfo) -> bool) -> &mut DependencyInfo {
+        let needs_new = match self.dependency_infos.last() {
+            Some(dependency_info) => conflicts(dependency_info),
+            None => true,
+        };
+
+        if needs_new {
+            self.dependency_infos.push(DependencyInfo::default());
+        }
+
+        self.dependency_infos.last_mut().unwrap()
+    }
+}
+
+fn subresource_ranges_overlap(a: &ImageSubresourceRange, b: &ImageSubresourceRange) -> bool {
+    // Only depth and stencil are known to never alias each other. Color and plane aspects are
+    // conservatively assumed to overlap.
+    let aspects_overlap = a.aspects.intersects(b.aspects)
+        || !(ImageAspects::DEPTH | ImageAspects::STENCIL).contains(a.aspects | b.aspects);
+
+    aspects_overlap
+        && a.mip_levels.start < b.mip_levels.end
+        && b.mip_levels.start < a.mip_levels.end
+        && a.array_layers.start < b.array_layers.end
+        && b.array_layers.start < a.array_layers.end
+}
+
 vulkan_bitflags! {
     #[non_exhaustive]
 
@@ -3552,8 +3686,18 @@ impl ImageMemoryBarrier {
         // VUID-VkImageMemoryBarrier2-synchronization2-07794
         // If the synchronization2 feature is not enabled, newLayout must not be VK_IMAGE_LAYOUT_ATTACHMENT_OPTIMAL_KHR or VK_IMAGE_LAYOUT_READ_ONLY_OPTIMAL_KHR
 
-        // VUID-VkImageMemoryBarrier2-attachmentFeedbackLoopLayout-07313
-        // If the attachmentFeedbackLoopLayout feature is not enabled, newLayout must not be VK_IMAGE_LAYOUT_ATTACHMENT_FEEDBACK_LOOP_OPTIMAL_EXT
+        if new_layout == ImageLayout::AttachmentFeedbackLoopOptimal
+            && !device.enabled_features().attachment_feedback_loop_layout
+        {
+            return Err(Box::new(ValidationError {
+                context: "new_layout".into(),
+                problem: "is `ImageLayout::AttachmentFeedbackLoopOptimal`".into(),
+                requires_one_of: RequiresOneOf(&[RequiresAllOf(&[Requires::Feature(
+                    "attachment_feedback_loop_layout",
+                )])]),
+                vuids: &["VUID-VkImageMemoryBarrier2-attachmentFeedbackLoopLayout-07313"],
+            }));
+        }
 
         subresource_range
             .validate(device)
@@ -3923,6 +4067,31 @@ impl ImageMemoryBarrier {
                         }));
                     }
                 }
+                ImageLayout::AttachmentFeedbackLoopOptimal => {
+                    if !(image
+                        .usage()
+                        .intersects(ImageUsage::ATTACHMENT_FEEDBACK_LOOP)
+                        && image.usage().intersects(
+                            ImageUsage::COLOR_ATTACHMENT | ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+                        )
+                        && image
+                            .usage()
+                            .intersects(ImageUsage::SAMPLED | ImageUsage::INPUT_ATTACHMENT))
+                    {
+                        return Err(Box::new(ValidationError {
+                            problem:
+                                "`old_layout` is `ImageLayout::AttachmentFeedbackLoopOptimal`, \
+                                but `image.usage()` does not contain \
+                                `ImageUsage::ATTACHMENT_FEEDBACK_LOOP`, at least one of \
+                                `ImageUsage::COLOR_ATTACHMENT` or \
+                                `ImageUsage::DEPTH_STENCIL_ATTACHMENT`, and at least one of \
+                                `ImageUsage::SAMPLED` or `ImageUsage::INPUT_ATTACHMENT`"
+                                    .into(),
+                            vuids: &["VUID-VkImageMemoryBarrier2-srcQueueFamilyIndex-07006"],
+                            ..Default::default()
+                        }));
+                    }
+                }
                 ImageLayout::Undefined | ImageLayout::General | ImageLayout::PresentSrc => (),
             }
 
@@ -4038,6 +4207,31 @@ impl ImageMemoryBarrier {
                         }));
                     }
                 }
+                ImageLayout::AttachmentFeedbackLoopOptimal => {
+                    if !(image
+                        .usage()
+                        .intersects(ImageUsage::ATTACHMENT_FEEDBACK_LOOP)
+                        && image.usage().intersects(
+                            ImageUsage::COLOR_ATTACHMENT | ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+                        )
+                        && image
+                            .usage()
+                            .intersects(ImageUsage::SAMPLED | ImageUsage::INPUT_ATTACHMENT))
+                    {
+                        return Err(Box::new(ValidationError {
+                            problem:
+                                "`new_layout` is `ImageLayout::AttachmentFeedbackLoopOptimal`, \
+                                but `image.usage()` does not contain \
+                                `ImageUsage::ATTACHMENT_FEEDBACK_LOOP`, at least one of \
+                                `ImageUsage::COLOR_ATTACHMENT` or \
+                                `ImageUsage::DEPTH_STENCIL_ATTACHMENT`, and at least one of \
+                                `ImageUsage::SAMPLED` or `ImageUsage::INPUT_ATTACHMENT`"
+                                    .into(),
+                            vuids: &["VUID-VkImageMemoryBarrier2-srcQueueFamilyIndex-07006"],
+                            ..Default::default()
+                        }));
+                    }
+                }
                 ImageLayout::General | ImageLayout::PresentSrc => (),
             }
 
@@ -4218,3 +4412,116 @@ impl From<QueueFamilyOwnershipTransfer> for (u32, u32) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{BarrierBatch, ImageMemoryBarrier, PipelineStages};
+    use crate::{
+        format::Format,
+        image::{
+            Image, ImageAspects, ImageCreateInfo, ImageLayout, ImageSubresourceRange, ImageType,
+            ImageUsage,
+        },
+        memory::allocator::{AllocationCreateInfo, StandardMemoryAllocator},
+    };
+    use std::sync::Arc;
+
+    #[test]
+    fn barrier_batch() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device));
+
+        let images: Vec<_> = (0..4)
+            .map(|_| {
+                Image::new(
+                    memory_allocator.clone(),
+                    ImageCreateInfo {
+                        image_type: ImageType::Dim2d,
+                        format: Format::R8G8B8A8_UNORM,
+                        extent: [32, 32, 1],
+                        usage: ImageUsage::COLOR_ATTACHMENT | ImageUsage::SAMPLED,
+                        ..Default::default()
+                    },
+                    AllocationCreateInfo::default(),
+                )
+                .unwrap()
+            })
+            .collect();
+
+        let transition = |image: &Arc<Image>, old_layout, new_layout| ImageMemoryBarrier {
+            src_stages: PipelineStages::COLOR_ATTACHMENT_OUTPUT,
+            dst_stages: PipelineStages::FRAGMENT_SHADER,
+            old_layout,
+            new_layout,
+            subresource_range: image.subresource_range(),
+            ..ImageMemoryBarrier::image(image.clone())
+        };
+
+        // Independent transitions are combined into one pipeline barrier.
+        let mut batch = BarrierBatch::new();
+        assert!(batch.is_empty());
+
+        for image in &images {
+            batch.image_memory_barrier(transition(
+                image,
+                ImageLayout::ColorAttachmentOptimal,
+                ImageLayout::ShaderReadOnlyOptimal,
+            ));
+        }
+
+        assert_eq!(batch.dependency_infos().len(), 1);
+        assert_eq!(batch.dependency_infos()[0].image_memory_barriers.len(), 4);
+
+        // A second transition of the same subresource must come after the first.
+        batch.image_memory_barrier(transition(
+            &images[0],
+            ImageLayout::ShaderReadOnlyOptimal,
+            ImageLayout::General,
+        ));
+
+        assert_eq!(batch.dependency_infos().len(), 2);
+    }
+
+    #[test]
+    fn barrier_batch_depth_stencil() {
+        let (device, _) = gfx_dev_and_queue!();
+        let memory_allocator = Arc::new(StandardMemoryAllocator::new_default(device.clone()));
+
+        let format = match device.physical_device().best_depth_stencil_format(true) {
+            Ok(x) => x,
+            Err(_) => return,
+        };
+        let image = Image::new(
+            memory_allocator,
+            ImageCreateInfo {
+                image_type: ImageType::Dim2d,
+                format,
+                extent: [32, 32, 1],
+                usage: ImageUsage::DEPTH_STENCIL_ATTACHMENT,
+                ..Default::default()
+            },
+            AllocationCreateInfo::default(),
+        )
+        .unwrap();
+
+        let transition = |aspects| ImageMemoryBarrier {
+            new_layout: ImageLayout::General,
+            subresource_range: ImageSubresourceRange {
+                aspects,
+                mip_levels: 0..1,
+                array_layers: 0..1,
+            },
+            ..ImageMemoryBarrier::image(image.clone())
+        };
+
+        // The depth and stencil aspects don't overlap.
+        let mut batch = BarrierBatch::new();
+        batch
+            .image_memory_barrier(transition(ImageAspects::DEPTH))
+            .image_memory_barrier(transition(ImageAspects::STENCIL));
+        assert_eq!(batch.dependency_infos().len(), 1);
+
+        batch.image_memory_barrier(transition(ImageAspects::DEPTH | ImageAspects::STENCIL));
+        assert_eq!(batch.dependency_infos().len(), 2);
+    }
+}
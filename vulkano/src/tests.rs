@@ -27,14 +27,23 @@ macro_rules! instance {
 }
 
 /// Creates a device and a queue for graphics operations.
+///
+/// The device extensions and features to enable can be listed as
+/// `gfx_dev_and_queue!(extensions: [..], features: [..])`, or only the features as
+/// `gfx_dev_and_queue!(feature_a, feature_b)`. Returns if no device supports all of them.
 macro_rules! gfx_dev_and_queue {
-    ($($feature:ident),*) => ({
+    (extensions: [$($extension:ident),* $(,)?], features: [$($feature:ident),* $(,)?] $(,)?) => ({
         use crate::device::physical::PhysicalDeviceType;
         use crate::device::{Device, DeviceCreateInfo, DeviceExtensions, QueueCreateInfo};
         use crate::device::Features;
 
         let instance = instance!();
-        let enabled_extensions = DeviceExtensions::empty();
+        let enabled_extensions = DeviceExtensions {
+            $(
+                $extension: true,
+            )*
+            .. DeviceExtensions::empty()
+        };
         let enabled_features = Features {
             $(
                 $feature: true,
@@ -88,6 +97,10 @@ macro_rules! gfx_dev_and_queue {
 
         (device, queues.next().unwrap())
     });
+
+    ($($feature:ident),*) => ({
+        gfx_dev_and_queue!(extensions: [], features: [$($feature),*])
+    });
 }
 
 macro_rules! assert_should_panic {